@@ -83,6 +83,7 @@ export default defineConfig({
           { text: "Nerdfont icons", link: "/guide/nerdfont-icons" },
           { text: "direnv", link: "/guide/direnv" },
           { text: "Git worktree caveats", link: "/guide/git-worktree-caveats" },
+          { text: "Jujutsu (jj)", link: "/guide/jujutsu" },
         ],
       },
       {
@@ -91,14 +92,39 @@ export default defineConfig({
           { text: "add", link: "/reference/commands/add" },
           { text: "merge", link: "/reference/commands/merge" },
           { text: "remove", link: "/reference/commands/remove" },
+          { text: "archive", link: "/reference/commands/archive" },
+          { text: "restore", link: "/reference/commands/restore" },
+          { text: "undo", link: "/reference/commands/undo" },
+          { text: "replay", link: "/reference/commands/replay" },
+          { text: "sync", link: "/reference/commands/sync" },
           { text: "list", link: "/reference/commands/list" },
           { text: "open", link: "/reference/commands/open" },
+          { text: "attach", link: "/reference/commands/attach" },
           { text: "close", link: "/reference/commands/close" },
+          { text: "info", link: "/reference/commands/info" },
           { text: "path", link: "/reference/commands/path" },
+          { text: "logs", link: "/reference/commands/logs" },
+          { text: "env", link: "/reference/commands/env" },
+          { text: "run", link: "/reference/commands/run" },
+          { text: "test", link: "/reference/commands/test" },
+          { text: "conflicts", link: "/reference/commands/conflicts" },
+          { text: "gc", link: "/reference/commands/gc" },
+          { text: "pr", link: "/reference/commands/pr" },
+          { text: "workspace", link: "/reference/commands/workspace" },
+          { text: "status", link: "/reference/commands/status" },
           { text: "dashboard", link: "/reference/commands/dashboard" },
+          { text: "top", link: "/reference/commands/top" },
+          { text: "daemon", link: "/reference/commands/daemon" },
+          { text: "watch", link: "/reference/commands/watch" },
+          { text: "serve", link: "/reference/commands/serve" },
+          { text: "import", link: "/reference/commands/import" },
+          { text: "setup", link: "/reference/commands/setup" },
           { text: "init", link: "/reference/commands/init" },
+          { text: "config", link: "/reference/commands/config" },
           { text: "claude prune", link: "/reference/commands/claude" },
+          { text: "shell-init", link: "/reference/commands/shell-init" },
           { text: "completions", link: "/reference/commands/completions" },
+          { text: "self-update", link: "/reference/commands/self-update" },
           { text: "docs", link: "/reference/commands/docs" },
         ],
       },
@@ -0,0 +1,156 @@
+//! Screen-reader-friendly output.
+//!
+//! Tables, icons and colors read poorly through a screen reader or braille
+//! display: column alignment is lost, Nerd Font glyphs are read as nothing or
+//! as code points, and a red row means nothing. In accessible mode commands
+//! print one line per item instead, made of labeled fields in words, e.g.
+//! `Worktree: fix-auth; Agent: waiting for 5 minutes; Git: 12 lines added`.
+
+use crate::git::GitStatus;
+
+/// Environment variable that turns on accessible output for every command
+pub const ENV: &str = "WORKMUX_A11Y";
+
+/// Whether to print accessible output: requested with `--a11y`, or with
+/// `WORKMUX_A11Y` set to anything but empty or `0`
+pub fn enabled(flag: bool) -> bool {
+    flag || std::env::var(ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// One item as `Label: value` fields separated by semicolons. Fields without
+/// a value are left out rather than read as a dash.
+pub fn line(fields: &[(&str, Option<String>)]) -> String {
+    fields
+        .iter()
+        .filter_map(|(label, value)| value.as_ref().map(|v| format!("{}: {}", label, v)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// A duration in words, largest units first: "1 hour 5 minutes"
+pub fn spoken_duration(secs: u64) -> String {
+    let units = [
+        (secs / 86_400, "day"),
+        (secs % 86_400 / 3600, "hour"),
+        (secs % 3600 / 60, "minute"),
+    ];
+    let parts: Vec<String> = units
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .take(2)
+        .map(|(n, unit)| plural(*n, unit))
+        .collect();
+    if parts.is_empty() {
+        plural(secs, "second")
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Git status in words, in the order of the dashboard's Git column
+pub fn describe_git_status(status: Option<&GitStatus>) -> Option<String> {
+    let status = status?;
+    let mut parts = Vec::new();
+    if status.lines_added > 0 {
+        parts.push(format!(
+            "{} added",
+            plural(status.lines_added as u64, "line")
+        ));
+    }
+    if status.lines_removed > 0 {
+        parts.push(format!(
+            "{} removed",
+            plural(status.lines_removed as u64, "line")
+        ));
+    }
+    if status.is_dirty || status.uncommitted_added > 0 || status.uncommitted_removed > 0 {
+        let mut uncommitted = Vec::new();
+        if status.uncommitted_added > 0 {
+            uncommitted.push(format!(
+                "{} added",
+                plural(status.uncommitted_added as u64, "line")
+            ));
+        }
+        if status.uncommitted_removed > 0 {
+            uncommitted.push(format!(
+                "{} removed",
+                plural(status.uncommitted_removed as u64, "line")
+            ));
+        }
+        if uncommitted.is_empty() {
+            parts.push("uncommitted changes".to_string());
+        } else {
+            parts.push(format!(
+                "uncommitted changes with {}",
+                uncommitted.join(" and ")
+            ));
+        }
+    }
+    if status.has_conflict {
+        parts.push("conflicts with base branch".to_string());
+    }
+    if status.ahead > 0 {
+        parts.push(format!("{} ahead", plural(status.ahead as u64, "commit")));
+    }
+    if status.behind > 0 {
+        parts.push(format!("{} behind", plural(status.behind as u64, "commit")));
+    }
+    if parts.is_empty() {
+        Some("no changes".to_string())
+    } else {
+        Some(parts.join(", "))
+    }
+}
+
+fn plural(n: u64, unit: &str) -> String {
+    if n == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_skips_missing_fields() {
+        assert_eq!(
+            line(&[
+                ("Worktree", Some("fix-auth".to_string())),
+                ("PR", None),
+                ("Agent", Some("waiting".to_string())),
+            ]),
+            "Worktree: fix-auth; Agent: waiting"
+        );
+    }
+
+    #[test]
+    fn spoken_duration_uses_two_largest_units() {
+        assert_eq!(spoken_duration(1), "1 second");
+        assert_eq!(spoken_duration(45), "45 seconds");
+        assert_eq!(spoken_duration(3600 + 5 * 60 + 7), "1 hour 5 minutes");
+        assert_eq!(spoken_duration(2 * 86_400 + 60), "2 days 1 minute");
+    }
+
+    #[test]
+    fn git_status_in_words() {
+        assert_eq!(describe_git_status(None), None);
+        assert_eq!(
+            describe_git_status(Some(&GitStatus::default())).as_deref(),
+            Some("no changes")
+        );
+        let status = GitStatus {
+            lines_added: 12,
+            is_dirty: true,
+            uncommitted_removed: 2,
+            behind: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            describe_git_status(Some(&status)).as_deref(),
+            Some("12 lines added, uncommitted changes with 2 lines removed, 1 commit behind")
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Records of archived worktrees.
+//!
+//! `workmux archive` keeps a worktree's branch under a ref namespace
+//! (`refs/workmux/archive/<handle>` by default) instead of deleting it, and
+//! writes a record to `<git common dir>/workmux/archive/<handle>.json` with
+//! the worktree's metadata, so `workmux restore` can recreate it as it was.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::git;
+use crate::metadata::WorktreeMetadata;
+use crate::state::StateFile;
+
+const ARCHIVE_FILE: StateFile = StateFile {
+    name: "archived worktree",
+    migrations: &[],
+};
+
+/// A worktree torn down by `workmux archive`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchivedWorktree {
+    /// How the worktree was created (branch, base, agent, prompt, ...)
+    pub metadata: WorktreeMetadata,
+    /// Ref the branch was archived to
+    pub ref_name: String,
+    /// Commit the ref points at
+    pub commit: String,
+    /// Unix timestamp of `workmux archive`
+    pub archived_at: u64,
+    /// Remote the ref was also pushed to
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Monorepo package the worktree was scoped to (`add --package`)
+    #[serde(default)]
+    pub package: Option<String>,
+    /// The archived commit only holds the worktree's uncommitted changes,
+    /// which `restore` turns back into uncommitted changes
+    #[serde(default)]
+    pub wip_commit: bool,
+}
+
+fn archive_dir() -> Result<PathBuf> {
+    Ok(git::get_git_common_dir()?.join("workmux").join("archive"))
+}
+
+fn archive_path(dir: &Path, handle: &str) -> PathBuf {
+    dir.join(format!("{}.json", handle))
+}
+
+/// The record of an archived worktree, if any
+pub fn load(handle: &str) -> Option<ArchivedWorktree> {
+    ARCHIVE_FILE.load_from(&archive_path(&archive_dir().ok()?, handle))
+}
+
+/// All archived worktrees of the repository with their handles, oldest first
+pub fn load_all() -> Vec<(String, ArchivedWorktree)> {
+    let Ok(entries) = archive_dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+    let mut archived: Vec<(String, ArchivedWorktree)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let handle = path.file_stem()?.to_str()?.to_string();
+            Some((handle, ARCHIVE_FILE.load_from(&path)?))
+        })
+        .collect();
+    archived.sort_by_key(|(_, a)| a.archived_at);
+    archived
+}
+
+pub fn save(handle: &str, archived: &ArchivedWorktree) -> Result<()> {
+    let dir = archive_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    ARCHIVE_FILE.save_to(&archive_path(&dir, handle), archived)
+}
+
+/// Forget a restored worktree
+pub fn delete(handle: &str) {
+    if let Ok(dir) = archive_dir() {
+        let path = archive_path(&dir, handle);
+        if path.exists()
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            debug!(handle, error = %e, "archive:failed to delete record");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_record_round_trips_through_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = archive_path(dir.path(), "fix-login");
+        let archived = ArchivedWorktree {
+            metadata: WorktreeMetadata {
+                branch: "fix-login".to_string(),
+                base: Some("main".to_string()),
+                ..Default::default()
+            },
+            ref_name: "refs/workmux/archive/fix-login".to_string(),
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            archived_at: 1_700_000_000,
+            remote: None,
+            package: None,
+            wip_commit: true,
+        };
+        ARCHIVE_FILE.save_to(&path, &archived).unwrap();
+        assert_eq!(ARCHIVE_FILE.load_from(&path), Some(archived));
+    }
+}
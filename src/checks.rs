@@ -0,0 +1,44 @@
+//! Results of `workmux test --record`.
+//!
+//! Kept in `~/.cache/workmux/checks.json`, keyed by worktree path like the git
+//! status cache, so the dashboard can show a Checks column for agents of any
+//! repository.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::state::StateFile;
+
+const CHECKS_FILE: StateFile = StateFile {
+    name: "checks.json",
+    migrations: &[],
+};
+
+/// Outcome of the test command in one worktree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub passed: bool,
+    /// Commit the worktree was at when the command ran
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// Unix timestamp of when the command finished
+    pub finished_at: u64,
+}
+
+/// Recorded results, keyed by worktree path
+pub fn load_all() -> HashMap<PathBuf, CheckResult> {
+    CHECKS_FILE.load().unwrap_or_default()
+}
+
+/// Record results, replacing earlier ones for the same worktrees and
+/// forgetting worktrees that no longer exist
+pub fn record(results: &HashMap<PathBuf, CheckResult>) {
+    let mut all = load_all();
+    all.retain(|path, _| Path::exists(path));
+    all.extend(results.iter().map(|(p, r)| (p.clone(), r.clone())));
+    if let Err(e) = CHECKS_FILE.save(&all) {
+        debug!(error = %e, "checks:failed to save");
+    }
+}
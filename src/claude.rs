@@ -102,3 +102,105 @@ pub fn prune_stale_entries() -> Result<usize> {
 
     Ok(removed_count)
 }
+
+/// The workmux status plugin manifest, whose `hooks` are installed by `setup`
+const STATUS_PLUGIN: &str = include_str!("../.claude-plugin/plugin.json");
+
+/// Marker identifying hooks that workmux installed
+const STATUS_HOOK_COMMAND: &str = "workmux set-window-status";
+
+/// Add the workmux status hooks to ~/.claude/settings.json.
+/// Returns false if they were already present.
+pub fn install_status_hooks() -> Result<bool> {
+    let settings_path = home::home_dir()
+        .map(|h| h.join(".claude").join("settings.json"))
+        .context("Could not determine home directory")?;
+
+    let mut settings: serde_json::Value = if settings_path.exists() {
+        let contents = fs::read_to_string(&settings_path)
+            .with_context(|| format!("Failed to read {}", settings_path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?
+    } else {
+        serde_json::json!({})
+    };
+
+    let plugin: serde_json::Value = serde_json::from_str(STATUS_PLUGIN)?;
+    if !merge_status_hooks(&mut settings, &plugin["hooks"]) {
+        return Ok(false);
+    }
+
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if settings_path.exists() {
+        fs::copy(&settings_path, settings_path.with_extension("json.bak"))
+            .context("Failed to back up Claude settings")?;
+    }
+    fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)
+        .with_context(|| format!("Failed to write {}", settings_path.display()))?;
+    Ok(true)
+}
+
+/// Merge the status `hooks` into Claude settings, leaving events that already
+/// run a workmux status hook untouched. Returns true if anything was added.
+fn merge_status_hooks(settings: &mut serde_json::Value, hooks: &serde_json::Value) -> bool {
+    let Some(hooks) = hooks.as_object() else {
+        return false;
+    };
+    if !settings.is_object() {
+        *settings = serde_json::json!({});
+    }
+    let existing = settings
+        .as_object_mut()
+        .expect("settings is an object")
+        .entry("hooks")
+        .or_insert_with(|| serde_json::json!({}));
+    let Some(existing) = existing.as_object_mut() else {
+        return false;
+    };
+
+    let mut changed = false;
+    for (event, entries) in hooks {
+        let event_hooks = existing
+            .entry(event.clone())
+            .or_insert_with(|| serde_json::json!([]));
+        let already_installed = event_hooks.to_string().contains(STATUS_HOOK_COMMAND);
+        if let (false, Some(list), Some(new)) = (
+            already_installed,
+            event_hooks.as_array_mut(),
+            entries.as_array(),
+        ) {
+            list.extend(new.iter().cloned());
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_status_hooks_adds_missing_events_once() {
+        let plugin: serde_json::Value = serde_json::from_str(STATUS_PLUGIN).unwrap();
+        let mut settings = json!({
+            "model": "opus",
+            "hooks": {
+                "Stop": [{"hooks": [{"type": "command", "command": "say done"}]}]
+            }
+        });
+
+        assert!(merge_status_hooks(&mut settings, &plugin["hooks"]));
+        assert_eq!(settings["model"], "opus");
+        let stop = settings["hooks"]["Stop"].as_array().unwrap();
+        assert_eq!(stop.len(), 2);
+        assert!(settings["hooks"]["UserPromptSubmit"].is_array());
+
+        // Running again is a no-op
+        assert!(!merge_status_hooks(&mut settings, &plugin["hooks"]));
+        assert_eq!(settings["hooks"]["Stop"].as_array().unwrap().len(), 2);
+    }
+}
@@ -1,8 +1,11 @@
+use crate::command::OutputFormat;
 use crate::command::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
-use crate::{claude, command, git};
+use crate::{claude, command, error, git, tmux};
 use anyhow::{Context, Result};
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{Shell, generate};
+use clap_complete_nushell::Nushell;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 struct WorktreeBranchParser;
@@ -32,7 +35,7 @@ impl WorktreeBranchParser {
             // Filter out the main branch, as it's not a candidate for merging/removing.
             .filter(|branch| main_branch.as_deref() != Some(branch.as_str()))
             // Filter out detached HEAD states.
-            .filter(|branch| branch != "(detached)")
+            .filter(|branch| !git::is_detached(branch))
             .collect()
     }
 }
@@ -173,19 +176,34 @@ struct Cli {
     command: Commands,
 }
 
+// Parsed once per run, so the size of `Add` doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new worktree and tmux window
     Add {
         /// Name of the branch (creates if it doesn't exist) or remote ref (e.g., origin/feature).
         /// When used with --pr, this becomes the custom local branch name.
-        #[arg(required_unless_present_any = ["pr", "auto_name"], value_parser = GitBranchParser::new())]
+        #[arg(
+            required_unless_present_any = ["pr", "auto_name", "from_issue", "stdin"],
+            conflicts_with = "stdin",
+            value_parser = GitBranchParser::new()
+        )]
         branch_name: Option<String>,
 
         /// Pull request number to checkout
         #[arg(long, conflicts_with_all = ["base", "auto_name"])]
         pr: Option<u32>,
 
+        /// Issue number to start from: names the branch after the issue title, uses the
+        /// issue body as the prompt, and links the branch so PRs and merges close the issue
+        #[arg(
+            long,
+            value_name = "NUMBER",
+            conflicts_with_all = ["pr", "auto_name", "detached", "prompt", "prompt_file", "prompt_editor"]
+        )]
+        from_issue: Option<u32>,
+
         /// Generate branch name from prompt using LLM
         #[arg(short = 'A', long = "auto-name", conflicts_with = "pr")]
         auto_name: bool,
@@ -194,10 +212,39 @@ enum Commands {
         #[arg(long)]
         base: Option<String>,
 
+        /// Stack the worktree on another worktree: branch from its branch, and move
+        /// onto whatever it is merged into when it is merged
+        #[arg(
+            long,
+            value_name = "PARENT",
+            value_parser = WorktreeHandleParser::new(),
+            conflicts_with_all = ["base", "pr", "from_issue", "detached", "stdin"]
+        )]
+        stack: Option<String>,
+
+        /// Pane layout from `layouts` in the config, instead of `panes`
+        #[arg(long, value_name = "NAME")]
+        layout: Option<String>,
+
+        /// Create the worktree at a commit (--base, defaults to HEAD) without creating a
+        /// branch, for throwaway experiments. The name only names the worktree.
+        #[arg(long, conflicts_with_all = ["pr", "package", "with_changes"])]
+        detached: bool,
+
+        /// Make the first `git push` of the new branch set its upstream to origin/<branch>
+        /// (same as `auto_track_upstream: true` in config)
+        #[arg(long, conflicts_with = "detached")]
+        track_upstream: bool,
+
         /// Explicit name for the worktree directory and tmux window (overrides worktree_naming strategy and worktree_prefix)
         #[arg(long)]
         name: Option<String>,
 
+        /// Scope the worktree to a monorepo package: sparse-checks out the package directory,
+        /// prefixes the handle with its name, and starts panes inside it
+        #[arg(long, value_name = "PATH", conflicts_with = "with_changes")]
+        package: Option<String>,
+
         #[command(flatten)]
         prompt: PromptArgs,
 
@@ -213,6 +260,31 @@ enum Commands {
         /// Block until the created tmux window is closed
         #[arg(short = 'W', long)]
         wait: bool,
+
+        /// Print the worktrees a --stdin task list would create, without creating them
+        #[arg(long, requires = "stdin")]
+        dry_run: bool,
+    },
+
+    /// Create a fresh worktree the way an existing one was created (same base,
+    /// agent, prompt or prompt template, and setup steps) for a second attempt
+    Replay {
+        /// Worktree to replay
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: String,
+
+        /// Branch for the new worktree (defaults to <branch>-2, <branch>-3, ...)
+        #[arg(value_parser = GitBranchParser::new())]
+        branch: Option<String>,
+
+        /// Start from the exact commit the original was created from instead of
+        /// the current tip of its base branch
+        #[arg(long)]
+        same_commit: bool,
+
+        /// Create tmux window in the background (do not switch to it)
+        #[arg(short = 'b', long = "background")]
+        background: bool,
     },
 
     /// Open a tmux window for an existing worktree
@@ -233,10 +305,26 @@ enum Commands {
         #[arg(long, short = 'n')]
         new: bool,
 
+        /// Pane layout from `layouts` in the config (defaults to the one the
+        /// worktree was created with)
+        #[arg(long, value_name = "NAME")]
+        layout: Option<String>,
+
+        /// Record the agent pane's output for `workmux logs --agent`
+        #[arg(long)]
+        log_agent: bool,
+
         #[command(flatten)]
         prompt: PromptArgs,
     },
 
+    /// Attach to a worktree's tmux window from outside tmux, opening it if needed
+    Attach {
+        /// Worktree name (directory name, visible in tmux window)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: String,
+    },
+
     /// Close a worktree's tmux window (keeps the worktree and branch)
     Close {
         /// Worktree name (defaults to current directory if omitted)
@@ -274,22 +362,67 @@ enum Commands {
         #[arg(short = 'n', long)]
         no_verify: bool,
 
-        /// Show a system notification on successful merge
+        /// Also delete the branch on the remote it tracks, after cleaning up
+        /// (default: `merge.delete_remote_branch`)
+        #[arg(long, conflicts_with_all = ["keep", "check", "commits"])]
+        delete_remote: bool,
+
+        /// Show a system notification when the merge succeeds or fails
         #[arg(long)]
         notification: bool,
+
+        /// Only report whether the branch merges cleanly (and which files would
+        /// conflict), without changing anything
+        #[arg(long)]
+        check: bool,
+
+        /// Propose an order for merging every worktree whose agent is done,
+        /// keeping conflicts between them to a minimum, and offer to run it
+        #[arg(long, conflicts_with_all = ["name", "check"])]
+        plan: bool,
+
+        /// Merge the branch's open PR on GitHub/GitLab instead of locally
+        /// (respecting required checks), wait for it to land, then clean up
+        #[arg(long, conflicts_with_all = ["into", "ignore_uncommitted", "check", "plan"])]
+        pr: bool,
+
+        /// With `--pr`, don't wait for a queued PR to land: request the merge,
+        /// report its status, and keep the worktree if it hasn't merged yet
+        #[arg(long, requires = "pr")]
+        no_wait: bool,
+
+        /// Cherry-pick only these commits onto the target instead of merging
+        /// the whole branch, and keep the worktree. Takes commits and ranges
+        /// such as `abc123..HEAD`, resolved in the worktree.
+        #[arg(
+            long,
+            value_name = "COMMIT",
+            num_args = 1..,
+            conflicts_with_all = ["rebase", "squash", "ignore_uncommitted", "check", "plan", "pr"]
+        )]
+        commits: Vec<String>,
+
+        /// Print a JSON summary of the merge to stdout (progress and hook
+        /// output go to stderr)
+        #[arg(long, conflicts_with_all = ["check", "plan", "pr"])]
+        json: bool,
     },
 
     /// Remove a worktree, tmux window, and branch without merging
     #[command(visible_alias = "rm")]
     Remove {
         /// Worktree names (defaults to current directory name if empty)
-        #[arg(value_parser = WorktreeHandleParser::new(), conflicts_with_all = ["gone", "all"], num_args = 0..)]
+        #[arg(value_parser = WorktreeHandleParser::new(), conflicts_with_all = ["gone", "merged", "all"], num_args = 0..)]
         names: Vec<String>,
 
-        /// Remove worktrees whose upstream remote branch has been deleted (e.g., after PR merge)
-        #[arg(long, conflicts_with = "all")]
+        /// Remove worktrees whose upstream remote branch has been deleted or whose PR was merged
+        #[arg(long, conflicts_with_all = ["merged", "all"])]
         gone: bool,
 
+        /// Remove worktrees whose branch is fully merged into the main branch
+        #[arg(long, conflicts_with = "all")]
+        merged: bool,
+
         /// Remove all worktrees (except the main worktree)
         #[arg(long)]
         all: bool,
@@ -301,6 +434,65 @@ enum Commands {
         /// Keep the local branch (only remove worktree and tmux window)
         #[arg(short = 'k', long)]
         keep_branch: bool,
+
+        /// Also delete the branch on the remote it tracks, after removing it
+        /// locally (default: `remove.delete_remote_branch`)
+        #[arg(long, conflicts_with_all = ["keep_branch", "archive"])]
+        delete_remote: bool,
+
+        /// Archive the branch instead of deleting it (see `workmux archive`)
+        #[arg(long, conflicts_with_all = ["gone", "merged", "all", "keep_branch"])]
+        archive: bool,
+
+        /// Print a JSON summary of what was removed to stdout (progress and
+        /// hook output go to stderr)
+        #[arg(long, conflicts_with_all = ["gone", "merged", "all", "archive"])]
+        json: bool,
+    },
+
+    /// Keep a worktree's branch and uncommitted changes under an archive ref,
+    /// then remove the worktree, tmux window, and branch
+    Archive {
+        /// Worktree names (defaults to current directory name if empty)
+        #[arg(value_parser = WorktreeHandleParser::new(), num_args = 0..)]
+        names: Vec<String>,
+    },
+
+    /// Recreate an archived worktree (lists archived worktrees without a name)
+    Restore {
+        /// Name of the archived worktree
+        name: Option<String>,
+    },
+
+    /// Reverse the most recent remove, merge, or close: recreate the
+    /// worktree, revert the merge, or reopen the window
+    Undo {
+        /// Revert a merge without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Fetch, then rebase (or merge) every worktree onto its updated base branch
+    Sync {
+        /// Worktree names (defaults to all worktrees)
+        #[arg(value_parser = WorktreeHandleParser::new(), num_args = 0..)]
+        names: Vec<String>,
+
+        /// Rebase each branch onto its base (default, unless `sync_strategy` says otherwise)
+        #[arg(long, conflicts_with = "merge")]
+        rebase: bool,
+
+        /// Merge the base into each branch instead of rebasing
+        #[arg(long)]
+        merge: bool,
+
+        /// Ask the agent in each conflicted worktree to resolve the conflicts
+        #[arg(long)]
+        notify: bool,
+
+        /// First remove worktrees whose PR was merged or whose upstream was deleted
+        #[arg(long)]
+        cleanup: bool,
     },
 
     /// List all worktrees
@@ -309,6 +501,93 @@ enum Commands {
         /// Show PR status for each worktree (requires gh CLI)
         #[arg(long)]
         pr: bool,
+
+        /// Only show worktrees scoped to this monorepo package (see `add --package`)
+        #[arg(long, value_name = "PATH")]
+        package: Option<String>,
+
+        /// List the worktrees of every repository with agents (live or
+        /// remembered), grouped by project
+        #[arg(long)]
+        all: bool,
+
+        /// Output as JSON (same as `--format json`)
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Output format: a table, JSON, or NUON for nushell (`| from nuon`)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+
+        /// Print labeled plain text lines for screen readers instead of a table
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        a11y: bool,
+
+        /// Print stable tab-separated lines for scripts and editor plugins
+        #[arg(long, conflicts_with_all = ["json", "format", "a11y"])]
+        porcelain: bool,
+    },
+
+    /// Work with pull requests for worktree branches
+    Pr {
+        #[command(subcommand)]
+        command: PrCommands,
+    },
+
+    /// Show how a worktree was created: base, agent, prompt, PR, and issue
+    Info {
+        /// Worktree name (defaults to current directory if omitted)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the recorded output of a worktree's hooks and agent
+    Logs {
+        /// Worktree name (defaults to current directory if omitted)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Print the log of this hook instead of listing them
+        #[arg(long, value_enum, group = "log")]
+        hook: Option<crate::workflow::hooks::Hook>,
+
+        /// With --hook, show the run this many before the latest
+        #[arg(long, value_name = "N", default_value_t = 0, requires = "hook")]
+        previous: usize,
+
+        /// Print the agent pane's output, recorded with `--log-agent`
+        #[arg(long, group = "log")]
+        agent: bool,
+
+        /// Keep printing what is written to the log until interrupted
+        #[arg(short, long, requires = "log", conflicts_with = "previous")]
+        follow: bool,
+    },
+
+    /// Show the environment variables workmux sets for a worktree
+    Env {
+        /// Worktree name (defaults to current directory if omitted)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = command::env::EnvFormat::Text)]
+        format: command::env::EnvFormat,
+    },
+
+    /// Change the shell's directory to a worktree (needs `workmux shell-init`)
+    Cd {
+        /// Worktree name (defaults to the main worktree)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Print the directory instead (used by the shell function)
+        #[arg(long, hide = true)]
+        print: bool,
     },
 
     /// Get the filesystem path of a worktree
@@ -316,10 +595,185 @@ enum Commands {
         /// Worktree name (directory name)
         #[arg(value_parser = WorktreeHandleParser::new())]
         name: String,
+
+        /// Output as JSON (handle, branch, and path)
+        #[arg(long)]
+        json: bool,
+
+        /// Print handle, branch, and path as one tab-separated line
+        #[arg(long, conflicts_with = "json")]
+        porcelain: bool,
+    },
+
+    /// Run a command in a worktree's directory (e.g. `workmux run fix-auth -- cargo test`)
+    Run {
+        /// Worktree name (handle or branch)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: String,
+
+        /// Don't set the WM_* variables hooks get (WM_HANDLE, WM_BRANCH_NAME,
+        /// WM_WORKTREE_PATH, WM_PROJECT_ROOT)
+        #[arg(long)]
+        no_env: bool,
+
+        /// Command and arguments, after `--`
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Run the test command in every worktree in parallel
+    Test {
+        /// Worktree names (defaults to all worktrees)
+        #[arg(value_parser = WorktreeHandleParser::new(), num_args = 0..)]
+        names: Vec<String>,
+
+        /// Command to run instead of `test_command` from the config
+        #[arg(long, short = 'c')]
+        command: Option<String>,
+
+        /// How many worktrees to test at once (defaults to the number of CPUs)
+        #[arg(long, short = 'j')]
+        jobs: Option<usize>,
+
+        /// Record the results for the dashboard's Checks column
+        #[arg(long)]
+        record: bool,
+    },
+
+    /// List pairs of worktrees whose changes touch the same files, which will
+    /// likely conflict when merged
+    Conflicts {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show disk usage, age and merge status of worktrees, and remove merged
+    /// ones that have gone idle
+    Gc {
+        /// Remove idle merged worktrees without asking
+        #[arg(long)]
+        auto: bool,
+
+        /// Days without agent activity or commits before a merged worktree
+        /// counts as idle
+        #[arg(long, value_name = "DAYS", default_value_t = 7)]
+        older_than: u64,
+    },
+
+    /// Show agent status across all tmux sessions
+    Status {
+        /// Output as JSON (same as `--format json`)
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Output format: a table, JSON, or NUON for nushell (`| from nuon`)
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+
+        /// Print labeled plain text lines for screen readers instead of a table
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        a11y: bool,
+
+        /// Print stable tab-separated lines for scripts and editor plugins
+        #[arg(long, conflicts_with_all = ["json", "format", "a11y"])]
+        porcelain: bool,
+
+        /// Print one compact line of agent counts by status, for tmux's
+        /// `status-right` or a shell prompt segment
+        #[arg(long, conflicts_with_all = ["json", "format", "a11y", "porcelain"])]
+        once: bool,
+
+        /// With --once, count each project's agents separately
+        #[arg(long, requires = "once")]
+        by_project: bool,
+    },
+
+    /// Manage worktrees across a multi-repo workspace
+    #[command(visible_alias = "ws")]
+    Workspace {
+        #[command(subcommand)]
+        command: WorkspaceCommands,
+    },
+
+    /// Run a background daemon that keeps agent and git status up to date
+    Daemon {
+        /// Refresh interval in seconds
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Print agent status changes as newline-delimited JSON until interrupted
+    Watch {
+        /// Polling interval in seconds
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Serve workmux operations over JSON-RPC on a unix socket (for editor plugins)
+    Serve {
+        /// Path of the unix socket to listen on
+        #[arg(long)]
+        socket: std::path::PathBuf,
+    },
+
+    /// Adopt existing worktrees (ghq clones, bare-repo layouts) into workmux
+    Import {
+        /// Directory to scan for repositories (defaults to the current directory)
+        dir: Option<std::path::PathBuf>,
+
+        /// Move worktrees to the directory workmux would have created them in
+        #[arg(long = "move")]
+        move_worktrees: bool,
+
+        /// Show what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many directory levels to search for repositories
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
     },
 
-    /// Generate example .workmux.yaml configuration file
-    Init,
+    /// Interactively configure global settings, shell completions, and status hooks
+    Setup {
+        /// Accept the defaults without prompting
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Create a .workmux.yaml for this project, proposing panes, hooks and files
+    /// for the detected project type
+    Init {
+        /// Write the fully commented example configuration instead
+        #[arg(long)]
+        example: bool,
+
+        /// Accept the proposed settings without prompting
+        #[arg(short, long, conflicts_with = "example")]
+        yes: bool,
+    },
+
+    /// Update workmux to the latest GitHub release
+    SelfUpdate {
+        /// Only check whether an update is available
+        #[arg(long)]
+        check: bool,
+
+        /// Reinstall even if already on the latest version
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Share the project's workmux setup as a bundle, or check it for mistakes
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Check the global and project config files for mistakes (same as
+    /// `config validate`)
+    LintConfig,
 
     /// Show detailed documentation (renders README.md)
     Docs,
@@ -332,19 +786,41 @@ enum Commands {
         /// Preview pane size as percentage (10-90). Larger = more preview, less table.
         #[arg(long, short = 'P', value_parser = clap::value_parser!(u8).range(10..=90))]
         preview_size: Option<u8>,
+
+        /// Line-based agent list for screen readers instead of the TUI
+        #[arg(long)]
+        a11y: bool,
     },
 
+    /// Compact agent monitor for a small pane: sorted by priority, with
+    /// status changes highlighted, and no preview
+    Top,
+
     /// Claude Code integration commands
     Claude {
         #[command(subcommand)]
         command: ClaudeCommands,
     },
 
+    /// Status line command for Claude Code: records the session's cost and
+    /// context use for the dashboard, then prints a status line
+    Statusline {
+        /// Print the output of this status line command, given the same JSON,
+        /// instead of workmux's line
+        #[arg(long)]
+        command: Option<String>,
+    },
+
     /// Set agent status for the current tmux window (used by hooks)
     #[command(hide = true)]
     SetWindowStatus {
         #[arg(value_enum)]
         command: command::set_window_status::SetWindowStatusCommand,
+
+        /// Ignored; lets agents that append a payload (e.g. Codex's `notify`)
+        /// call this directly
+        #[arg(hide = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        _payload: Vec<String>,
     },
 
     /// Set the base branch for the current worktree (used after rebasing)
@@ -355,11 +831,51 @@ enum Commands {
         base: String,
     },
 
+    /// Print shell functions for `workmux cd` and a prompt segment, with
+    /// completions (e.g. `eval "$(workmux shell-init zsh)"`)
+    ShellInit {
+        /// The shell to print the functions for
+        #[arg(value_enum)]
+        shell: command::shell_init::InitShell,
+    },
+
     /// Generate shell completions
     Completions {
         /// The shell to generate completions for
         #[arg(value_enum)]
-        shell: Shell,
+        shell: CompletionShell,
+    },
+
+    /// Wait for a freshly launched agent to start, then paste in its prompt
+    /// (internal use)
+    #[command(hide = true, name = "_await-agent")]
+    AwaitAgent {
+        /// The agent's pane
+        pane: String,
+
+        /// The agent's name, for messages
+        #[arg(long)]
+        agent: String,
+
+        /// Prompt file to paste in once the agent is ready
+        #[arg(long)]
+        paste: Option<PathBuf>,
+
+        /// Regex matching the agent's screen once it takes input
+        #[arg(long)]
+        ready_pattern: Option<String>,
+
+        /// Type the prompt in as keypresses instead of pasting it
+        #[arg(long)]
+        literal_input: bool,
+
+        /// Milliseconds between the pieces the prompt is sent in
+        #[arg(long, default_value_t = 30)]
+        input_delay_ms: u64,
+
+        /// Send Enter right after the prompt instead of once it shows up
+        #[arg(long)]
+        enter_with_input: bool,
     },
 
     /// Output worktree branch names for shell completion (internal use)
@@ -375,6 +891,151 @@ enum Commands {
     CompleteGitBranches,
 }
 
+#[derive(Subcommand)]
+enum PrCommands {
+    /// Push the worktree's branch and open a GitHub pull request
+    Create {
+        /// Worktree name (defaults to current directory if omitted)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// PR title (defaults to the commit subject, or the branch name for several commits)
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// PR body (defaults to the commit message, or a list of commit subjects)
+        #[arg(short, long, conflicts_with = "from_prompt")]
+        body: Option<String>,
+
+        /// Use the prompt the agent was started with as the PR body
+        #[arg(long)]
+        from_prompt: bool,
+
+        /// Branch to merge into (defaults to the branch the worktree was created from)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Create the PR as a draft
+        #[arg(short, long)]
+        draft: bool,
+    },
+
+    /// Show review, CI, and mergeability of each worktree's PR
+    Status {
+        /// Worktree names (defaults to all worktrees)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        names: Vec<String>,
+
+        /// Open the PRs in the browser (all open PRs when no names are given)
+        #[arg(long, conflicts_with = "json")]
+        web: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Package .workmux.yaml, the .workmux/ directory, and hook scripts into a tarball
+    Export {
+        /// Output file (defaults to workmux-config.tar.gz)
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// Unpack a config bundle (file path or http(s) URL) into the repository
+    Import {
+        /// Bundle file path or URL
+        source: String,
+
+        /// Overwrite files that already exist
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Check the global and project config files for mistakes
+    #[command(alias = "lint")]
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum WorkspaceCommands {
+    /// Create a worktree in every workspace repository and one combined tmux window
+    Add {
+        /// Name of the branch to create in each repository
+        branch_name: String,
+
+        /// Workspace to use (defaults to the only one, or the one containing the current repo)
+        #[arg(short, long)]
+        workspace: Option<String>,
+
+        /// Base branch/commit/tag to branch from (defaults to each repo's current branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// The agent to run in each repository's pane
+        #[arg(short, long)]
+        agent: Option<String>,
+
+        #[command(flatten)]
+        setup: SetupFlags,
+    },
+
+    /// Merge the branch in every workspace repository and clean up
+    Merge {
+        /// Worktree name or branch
+        name: String,
+
+        /// Workspace to use (defaults to the only one, or the one containing the current repo)
+        #[arg(short, long)]
+        workspace: Option<String>,
+
+        /// Ignore uncommitted and staged changes
+        #[arg(long)]
+        ignore_uncommitted: bool,
+
+        /// Rebase the branch onto the target before merging (fast-forward merge)
+        #[arg(long, group = "merge_strategy")]
+        rebase: bool,
+
+        /// Squash all commits from the branch into a single commit on the target
+        #[arg(long, group = "merge_strategy")]
+        squash: bool,
+
+        /// Keep the worktrees, window, and branches after merging (skip cleanup)
+        #[arg(short = 'k', long)]
+        keep: bool,
+
+        /// Skip running pre-merge hooks
+        #[arg(short = 'n', long)]
+        no_verify: bool,
+    },
+
+    /// Remove the worktree from every workspace repository
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Worktree name or branch
+        name: String,
+
+        /// Workspace to use (defaults to the only one, or the one containing the current repo)
+        #[arg(short, long)]
+        workspace: Option<String>,
+
+        /// Skip confirmation and ignore uncommitted changes
+        #[arg(short, long)]
+        force: bool,
+
+        /// Keep the local branches (only remove worktrees and tmux window)
+        #[arg(short = 'k', long)]
+        keep_branch: bool,
+    },
+
+    /// List configured workspaces and their repositories
+    #[command(visible_alias = "ls")]
+    List,
+}
+
 #[derive(Subcommand)]
 enum ClaudeCommands {
     /// Remove stale entries from ~/.claude.json for deleted worktrees
@@ -383,39 +1044,77 @@ enum ClaudeCommands {
 
 // --- Public Entry Point ---
 pub fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::try_parse().unwrap_or_else(|e| {
+        if e.use_stderr() {
+            let _ = e.print();
+            std::process::exit(error::USAGE.into());
+        }
+        // --help and --version
+        e.exit()
+    });
 
     match cli.command {
         Commands::Add {
             branch_name,
             pr,
+            from_issue,
             auto_name,
             base,
+            stack,
+            layout,
+            detached,
+            track_upstream,
             name,
+            package,
             prompt,
             setup,
             rescue,
             multi,
             wait,
+            dry_run,
         } => command::add::run(
             branch_name.as_deref(),
             pr,
+            from_issue,
             auto_name,
             base.as_deref(),
+            stack.as_deref(),
+            layout.as_deref(),
+            detached,
+            track_upstream,
             name,
+            package.as_deref(),
             prompt,
             setup,
             rescue,
             multi,
             wait,
+            dry_run,
         ),
+        Commands::Replay {
+            name,
+            branch,
+            same_commit,
+            background,
+        } => command::replay::run(&name, branch.as_deref(), same_commit, background),
         Commands::Open {
             name,
             run_hooks,
             force_files,
             new,
+            layout,
+            log_agent,
+            prompt,
+        } => command::open::run(
+            &name,
+            run_hooks,
+            force_files,
+            new,
+            layout.as_deref(),
+            log_agent,
             prompt,
-        } => command::open::run(&name, run_hooks, force_files, new, prompt),
+        ),
+        Commands::Attach { name } => command::attach::run(&name),
         Commands::Close { name } => command::close::run(name.as_deref()),
         Commands::Merge {
             name,
@@ -425,39 +1124,239 @@ pub fn run() -> Result<()> {
             squash,
             keep,
             no_verify,
+            delete_remote,
             notification,
+            check,
+            plan,
+            pr,
+            no_wait,
+            commits,
+            json,
         } => command::merge::run(
             name.as_deref(),
             into.as_deref(),
-            ignore_uncommitted,
-            rebase,
-            squash,
-            keep,
-            no_verify,
-            notification,
+            command::merge::MergeOptions {
+                ignore_uncommitted,
+                rebase,
+                squash,
+                keep,
+                no_verify,
+                delete_remote,
+                notification,
+                check,
+                plan,
+                pr,
+                no_wait,
+                commits: &commits,
+                json,
+            },
         ),
         Commands::Remove {
             names,
             gone,
+            merged,
             all,
             force,
             keep_branch,
-        } => command::remove::run(names, gone, all, force, keep_branch),
-        Commands::List { pr } => command::list::run(pr),
-        Commands::Path { name } => command::path::run(&name),
-        Commands::Init => crate::config::Config::init(),
+            delete_remote,
+            archive,
+            json,
+        } => {
+            if archive {
+                command::archive::run(names)
+            } else {
+                command::remove::run(
+                    names,
+                    gone,
+                    merged,
+                    all,
+                    force,
+                    keep_branch,
+                    delete_remote,
+                    json,
+                )
+            }
+        }
+        Commands::Archive { names } => command::archive::run(names),
+        Commands::Restore { name } => command::restore::run(name.as_deref()),
+        Commands::Undo { yes } => command::undo::run(yes),
+        Commands::Sync {
+            names,
+            rebase,
+            merge,
+            notify,
+            cleanup,
+        } => command::sync::run(names, rebase, merge, notify, cleanup),
+        Commands::List {
+            pr,
+            package,
+            all,
+            json,
+            format,
+            a11y,
+            porcelain,
+        } => command::list::run(
+            pr,
+            package.as_deref(),
+            all,
+            OutputFormat::or_json(format, json),
+            a11y,
+            porcelain,
+        ),
+        Commands::Pr { command } => match command {
+            PrCommands::Create {
+                name,
+                title,
+                body,
+                from_prompt,
+                base,
+                draft,
+            } => command::pr::create(
+                name.as_deref(),
+                command::pr::CreateOptions {
+                    title: title.as_deref(),
+                    body: body.as_deref(),
+                    from_prompt,
+                    base: base.as_deref(),
+                    draft,
+                },
+            ),
+            PrCommands::Status { names, web, json } => command::pr::status(&names, web, json),
+        },
+        Commands::Info { name, json } => command::info::run(name.as_deref(), json),
+        Commands::Logs {
+            name,
+            hook,
+            previous,
+            agent,
+            follow,
+        } => command::logs::run(name.as_deref(), hook, previous, agent, follow),
+        Commands::Env { name, format } => command::env::run(name.as_deref(), format),
+        Commands::Cd { name, print } => command::cd::run(name.as_deref(), print),
+        Commands::Path {
+            name,
+            json,
+            porcelain,
+        } => command::path::run(&name, json, porcelain),
+        Commands::Run {
+            name,
+            no_env,
+            command,
+        } => command::run::run(&name, &command, !no_env),
+        Commands::Test {
+            names,
+            command,
+            jobs,
+            record,
+        } => command::test::run(names, command, jobs, record),
+        Commands::Conflicts { json } => command::conflicts::run(json),
+        Commands::Gc { auto, older_than } => command::gc::run(auto, older_than),
+        Commands::Status {
+            json,
+            format,
+            a11y,
+            porcelain,
+            once,
+            by_project,
+        } => {
+            if once {
+                command::status::run_once(by_project)
+            } else {
+                command::status::run(OutputFormat::or_json(format, json), a11y, porcelain)
+            }
+        }
+        Commands::Workspace { command } => match command {
+            WorkspaceCommands::Add {
+                branch_name,
+                workspace,
+                base,
+                agent,
+                setup,
+            } => command::workspace::add(
+                workspace.as_deref(),
+                &branch_name,
+                base.as_deref(),
+                agent.as_deref(),
+                setup,
+            ),
+            WorkspaceCommands::Merge {
+                name,
+                workspace,
+                ignore_uncommitted,
+                rebase,
+                squash,
+                keep,
+                no_verify,
+            } => command::workspace::merge(
+                workspace.as_deref(),
+                &name,
+                ignore_uncommitted,
+                rebase,
+                squash,
+                keep,
+                no_verify,
+            ),
+            WorkspaceCommands::Remove {
+                name,
+                workspace,
+                force,
+                keep_branch,
+            } => command::workspace::remove(workspace.as_deref(), &name, force, keep_branch),
+            WorkspaceCommands::List => command::workspace::list(),
+        },
+        Commands::Daemon { interval } => command::daemon::run(interval),
+        Commands::Watch { interval } => command::watch::run(interval),
+        Commands::Serve { socket } => command::serve::run(&socket),
+        Commands::Import {
+            dir,
+            move_worktrees,
+            dry_run,
+            depth,
+        } => command::import::run(dir, move_worktrees, dry_run, depth),
+        Commands::Setup { yes } => command::setup::run(yes),
+        Commands::Init { example: true, .. } => crate::config::Config::init(),
+        Commands::Init { yes, .. } => command::init::run(yes),
+        Commands::SelfUpdate { check, force } => command::self_update::run(check, force),
+        Commands::Config { command } => match command {
+            ConfigCommands::Export { output } => command::config::export(output),
+            ConfigCommands::Import { source, force } => command::config::import(&source, force),
+            ConfigCommands::Validate => command::config::validate(),
+        },
+        Commands::LintConfig => command::config::validate(),
         Commands::Docs => command::docs::run(),
         Commands::Changelog => command::changelog::run(),
-        Commands::Dashboard { preview_size } => command::dashboard::run(preview_size),
+        Commands::Dashboard { preview_size, a11y } => command::dashboard::run(preview_size, a11y),
+        Commands::Top => command::top::run(),
         Commands::Claude { command } => match command {
             ClaudeCommands::Prune => prune_claude_config(),
         },
-        Commands::SetWindowStatus { command } => command::set_window_status::run(command),
+        Commands::Statusline { command } => command::statusline::run(command.as_deref()),
+        Commands::SetWindowStatus { command, .. } => command::set_window_status::run(command),
         Commands::SetBase { base } => command::set_base::run(&base),
+        Commands::ShellInit { shell } => command::shell_init::run(shell),
         Commands::Completions { shell } => {
             generate_completions(shell);
             Ok(())
         }
+        Commands::AwaitAgent {
+            pane,
+            agent,
+            paste,
+            ready_pattern,
+            literal_input,
+            input_delay_ms,
+            enter_with_input,
+        } => command::await_agent::run(
+            &pane,
+            &agent,
+            paste.as_deref(),
+            ready_pattern.as_deref(),
+            tmux::InputOptions {
+                literal: literal_input,
+                delay: std::time::Duration::from_millis(input_delay_ms),
+                enter_separately: !enter_with_input,
+            },
+        ),
         Commands::CompleteBranches => {
             for branch in WorktreeBranchParser::new().get_branches() {
                 println!("{branch}");
@@ -484,35 +1383,156 @@ fn prune_claude_config() -> Result<()> {
     Ok(())
 }
 
-fn generate_completions(shell: Shell) {
+/// Shells `workmux completions` supports: clap_complete's, plus nushell
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CompletionShell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    #[value(name = "powershell")]
+    PowerShell,
+    Zsh,
+}
+
+fn generate_completions(shell: CompletionShell) {
+    let script = match shell {
+        CompletionShell::Bash => completion_script(Shell::Bash),
+        CompletionShell::Elvish => completion_script(Shell::Elvish),
+        CompletionShell::Fish => completion_script(Shell::Fish),
+        CompletionShell::Nushell => nushell_completion_script(),
+        CompletionShell::PowerShell => completion_script(Shell::PowerShell),
+        CompletionShell::Zsh => completion_script(Shell::Zsh),
+    };
+    print!("{}", script);
+}
+
+/// Build the completion script for a shell, including dynamic branch completion
+pub fn completion_script(shell: Shell) -> String {
     let mut cmd = Cli::command();
     let name = cmd.get_name().to_string();
 
     // Generate base completions
     let mut buf = Vec::new();
     generate(shell, &mut cmd, &name, &mut buf);
-    let base_script = String::from_utf8_lossy(&buf);
-    print!("{base_script}");
+    let mut script = String::from_utf8_lossy(&buf).into_owned();
 
     // Append dynamic branch completion for each shell
-    // Note: PowerShell and Elvish are not supported because clap_complete generates
-    // anonymous completers that can't be wrapped without breaking standard completions.
     match shell {
-        Shell::Zsh => print_zsh_dynamic_completion(),
-        Shell::Bash => print_bash_dynamic_completion(),
-        Shell::Fish => print_fish_dynamic_completion(),
+        Shell::Zsh => script.push_str(include_str!("scripts/completions/zsh_dynamic.zsh")),
+        Shell::Bash => script.push_str(include_str!("scripts/completions/bash_dynamic.bash")),
+        Shell::Fish => script.push_str(include_str!("scripts/completions/fish_dynamic.fish")),
+        Shell::Elvish => script.push_str(include_str!("scripts/completions/elvish_dynamic.elv")),
+        // The generated completer is an anonymous script block that can't be
+        // wrapped, so the dynamic part goes at its start and returns early
+        Shell::PowerShell => {
+            let params = POWERSHELL_COMPLETER_PARAMS;
+            let dynamic = include_str!("scripts/completions/powershell_dynamic.ps1");
+            script = script.replacen(params, &format!("{}{}", params, dynamic), 1);
+        }
         _ => {}
     }
+    script
 }
 
-fn print_zsh_dynamic_completion() {
-    print!("{}", include_str!("scripts/completions/zsh_dynamic.zsh"));
-}
+/// First line of the script block clap_complete registers for PowerShell
+const POWERSHELL_COMPLETER_PARAMS: &str = "param($wordToComplete, $commandAst, $cursorPosition)\n";
+
+/// Subcommands whose first positional argument is a worktree handle or branch
+const HANDLE_SUBCOMMANDS: &[&str] = &[
+    "open", "attach", "remove", "archive", "replay", "path", "env", "run", "test", "info", "merge",
+    "sync", "cd",
+];
 
-fn print_bash_dynamic_completion() {
-    print!("{}", include_str!("scripts/completions/bash_dynamic.bash"));
+/// The nushell module from clap_complete_nushell, with custom completers on
+/// the arguments that take handles or branches. The generator writes one
+/// `export extern "workmux <subcommand>" [...]` per subcommand with one
+/// parameter per line, so the first positional one gets the completer.
+pub fn nushell_completion_script() -> String {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    let mut buf = Vec::new();
+    generate(Nushell, &mut cmd, &name, &mut buf);
+    let generated = String::from_utf8_lossy(&buf);
+
+    let mut script = String::new();
+    let mut completer = None;
+    for line in generated.lines() {
+        if let Some(sub) = line
+            .trim_start()
+            .strip_prefix("export extern \"workmux ")
+            .and_then(|rest| rest.strip_suffix("\" ["))
+        {
+            completer = if sub == "add" {
+                Some("nu-complete workmux git-branches")
+            } else if HANDLE_SUBCOMMANDS.contains(&sub) {
+                Some("nu-complete workmux handles")
+            } else {
+                None
+            };
+        }
+        let param = line.trim_start();
+        if let Some(name) = completer
+            && !param.starts_with('-')
+            && let Some(i) = param.find(": string")
+        {
+            let at = line.len() - param.len() + i + ": string".len();
+            let annotation = format!("@\"{}\"", name);
+            // Take the annotation's width out of the padding before the
+            // description, keeping one space
+            let (rest, description) = line[at..].split_at(line[at..].find('#').unwrap_or(0));
+            let padding = rest.len().saturating_sub(annotation.len()).max(1);
+            script.push_str(&line[..at]);
+            script.push_str(&annotation);
+            if !description.is_empty() {
+                script.push_str(&" ".repeat(padding));
+            }
+            script.push_str(description);
+            completer = None;
+        } else {
+            script.push_str(line);
+        }
+        script.push('\n');
+        if line == "module completions {" {
+            script.push_str(include_str!("scripts/completions/nushell_dynamic.nu"));
+        }
+    }
+    script
 }
 
-fn print_fish_dynamic_completion() {
-    print!("{}", include_str!("scripts/completions/fish_dynamic.fish"));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_completion_script_completes_handles() {
+        for shell in [
+            Shell::Bash,
+            Shell::Zsh,
+            Shell::Fish,
+            Shell::Elvish,
+            Shell::PowerShell,
+        ] {
+            let script = completion_script(shell);
+            assert!(script.contains("_complete-handles"), "{shell}");
+            assert!(script.contains("_complete-git-branches"), "{shell}");
+        }
+    }
+
+    #[test]
+    fn nushell_completion_uses_custom_completers() {
+        let script = nushell_completion_script();
+        assert!(script.contains("^workmux _complete-handles |"));
+        assert!(
+            script
+                .contains("    ...names: string@\"nu-complete workmux handles\" # Worktree names")
+        );
+        assert!(
+            script
+                .contains("    name: string@\"nu-complete workmux handles\" # Worktree to replay")
+        );
+        assert!(script.contains("    branch_name?: string@\"nu-complete workmux git-branches\""));
+        // Only the first positional: `replay`'s new branch name isn't a handle
+        assert!(script.contains("    branch?: string           # Branch for the new worktree"));
+    }
 }
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result, anyhow};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use tracing::{debug, trace};
 
@@ -109,14 +109,80 @@ impl<'a> Cmd<'a> {
     }
 }
 
+/// A command running `script` in the shell used for hooks: `sh -c`. Windows
+/// has no `sh` of its own, so there it falls back to `cmd /C` unless one (such
+/// as the one shipped with Git for Windows) is on PATH.
+pub fn shell(script: &str) -> Command {
+    if cfg!(windows) && which::which("sh").is_err() {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(script);
+        return cmd;
+    }
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(script);
+    cmd
+}
+
+/// A path printed by git or tmux, as a native path. Git for Windows prints
+/// `C:/repo/src`; this turns it into `C:\repo\src` and drops the `\\?\`
+/// prefix of extended-length paths, so paths compare equal to the ones the
+/// standard library produces.
+pub fn native_path(path: &str) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(normalize_windows_path(path))
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+fn normalize_windows_path(path: &str) -> String {
+    let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+    path.replace('/', "\\")
+}
+
+/// `C:\Users\me\repo` as WSL mounts it: `/mnt/c/Users/me/repo`. Paths
+/// without a drive letter are returned unchanged (apart from separators).
+pub fn windows_to_wsl_path(path: &str) -> String {
+    let path = path.strip_prefix(r"\\?\").unwrap_or(path);
+    let unix = path.replace('\\', "/");
+    let mut chars = unix.chars();
+    match (chars.next(), chars.next()) {
+        (Some(drive), Some(':')) if drive.is_ascii_alphabetic() => {
+            format!("/mnt/{}{}", drive.to_ascii_lowercase(), chars.as_str())
+        }
+        _ => unix,
+    }
+}
+
+/// The reverse of [`windows_to_wsl_path`]: `/mnt/c/Users/me` becomes
+/// `C:\Users\me`. Paths outside `/mnt/<drive>` are returned unchanged.
+pub fn wsl_to_windows_path(path: &str) -> String {
+    let Some(rest) = path.strip_prefix("/mnt/") else {
+        return path.to_string();
+    };
+    let mut chars = rest.chars();
+    match (chars.next(), chars.as_str()) {
+        (Some(drive), tail)
+            if drive.is_ascii_alphabetic() && (tail.is_empty() || tail.starts_with('/')) =>
+        {
+            format!(
+                "{}:{}",
+                drive.to_ascii_uppercase(),
+                if tail.is_empty() { "\\" } else { tail }.replace('/', "\\")
+            )
+        }
+        _ => path.to_string(),
+    }
+}
+
 /// Helper to create a shell command with additional environment variables
 pub fn shell_command_with_env(
     command: &str,
     workdir: &Path,
     env_vars: &[(&str, &str)],
 ) -> Result<()> {
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c").arg(command).current_dir(workdir);
+    let mut cmd = shell(command);
+    cmd.current_dir(workdir);
 
     for (key, value) in env_vars {
         cmd.env(key, value);
@@ -135,3 +201,31 @@ pub fn shell_command_with_env(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_paths_normalize_separators_and_prefix() {
+        assert_eq!(normalize_windows_path("C:/repo/src"), r"C:\repo\src");
+        assert_eq!(normalize_windows_path(r"\\?\C:\repo"), r"C:\repo");
+    }
+
+    #[test]
+    fn wsl_paths_round_trip() {
+        assert_eq!(
+            windows_to_wsl_path(r"C:\Users\me\repo"),
+            "/mnt/c/Users/me/repo"
+        );
+        assert_eq!(windows_to_wsl_path("D:/work"), "/mnt/d/work");
+        assert_eq!(windows_to_wsl_path("/home/me"), "/home/me");
+        assert_eq!(
+            wsl_to_windows_path("/mnt/c/Users/me/repo"),
+            r"C:\Users\me\repo"
+        );
+        assert_eq!(wsl_to_windows_path("/mnt/d"), r"D:\");
+        assert_eq!(wsl_to_windows_path("/home/me"), "/home/me");
+        assert_eq!(wsl_to_windows_path("/mnt/data/x"), "/mnt/data/x");
+    }
+}
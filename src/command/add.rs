@@ -1,4 +1,5 @@
-use crate::prompt::{Prompt, PromptDocument, foreach_from_frontmatter};
+use crate::cmd::Cmd;
+use crate::prompt::{Prompt, PromptDocument, foreach_from_frontmatter, substitute_template_vars};
 use crate::spinner;
 use crate::template::{
     TemplateEnv, WorktreeSpec, create_template_env, generate_worktree_specs, parse_foreach_matrix,
@@ -7,7 +8,7 @@ use crate::template::{
 use crate::workflow::SetupOptions;
 use crate::workflow::pr::detect_remote_branch;
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt, parse_prompt_with_frontmatter};
-use crate::{config, git, tmux, workflow};
+use crate::{config, git, jj, metadata, tmux, workflow};
 use anyhow::{Context, Result, anyhow};
 use serde_json::Value;
 use std::collections::BTreeMap;
@@ -67,62 +68,90 @@ fn read_stdin_lines() -> Result<Vec<String>> {
     Ok(lines)
 }
 
-/// Check preconditions for the add command (git repo and tmux session).
-/// Returns Ok(()) if all preconditions are met, or an error listing all failures.
-fn check_preconditions() -> Result<()> {
-    let is_git = git::is_git_repo()?;
-    let is_tmux = tmux::is_running()?;
-
-    if is_git && is_tmux {
-        return Ok(());
-    }
-
-    let mut errors = Vec::new();
-
-    if !is_tmux {
-        errors.push("tmux is not running.");
-    }
-    if !is_git {
-        errors.push("Current directory is not a git repository.");
-    }
-
-    // Add blank line before suggestions
-    errors.push("");
-
-    if !is_tmux {
-        errors.push("Please start a tmux session first.");
-    }
-    if !is_git {
-        errors.push("Please run this command from within a git repository.");
+/// Check preconditions for the add command. Only a git or jj repository is
+/// required; returns whether tmux is running, since without it worktrees are
+/// created without a window.
+fn check_preconditions() -> Result<bool> {
+    if !git::is_git_repo()? && jj::workspace_root().is_err() {
+        return Err(anyhow!(
+            "Current directory is not a git repository.\n\nPlease run this command from within a git repository."
+        ));
     }
-
-    Err(anyhow!(errors.join("\n")))
+    tmux::is_running()
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     branch_name: Option<&str>,
     pr: Option<u32>,
+    from_issue: Option<u32>,
     auto_name: bool,
     base: Option<&str>,
+    stack: Option<&str>,
+    layout: Option<&str>,
+    detached: bool,
+    track_upstream: bool,
     name: Option<String>,
-    prompt_args: PromptArgs,
+    package: Option<&str>,
+    mut prompt_args: PromptArgs,
     setup: SetupFlags,
     rescue: RescueArgs,
     multi: MultiArgs,
     wait: bool,
+    dry_run: bool,
 ) -> Result<()> {
-    // Ensure preconditions are met (git repo and tmux session)
-    check_preconditions()?;
+    // Ensure preconditions are met (git repo, and whether there's a tmux session)
+    let has_tmux = check_preconditions()?;
+
+    let package = package
+        .map(crate::naming::normalize_package_path)
+        .transpose()?;
+
+    // A stacked worktree branches from its parent's branch
+    let stack_parent = stack.map(resolve_stack_parent).transpose()?;
+    let base = match &stack_parent {
+        Some((_, parent_branch)) => Some(parent_branch.as_str()),
+        None => base,
+    };
+
+    // Catch an unknown layout before creating anything
+    if let Some(layout) = layout {
+        config::Config::load(None)?.with_layout(Some(layout))?;
+    }
 
     // Construct setup options from flags
     let mut options = SetupOptions::new(!setup.no_hooks, !setup.no_file_ops, !setup.no_pane_cmds);
     options.focus_window = !setup.background;
+    options.log_agent = setup.log_agent;
+    if !has_tmux {
+        // Worktree-only mode, e.g. on Windows without tmux
+        options.open_window = false;
+        println!(
+            "tmux is not running, so no window will be opened. Use 'workmux open' from a tmux session later."
+        );
+    }
 
     // Detect stdin input early
     let stdin_lines = read_stdin_lines()?;
     let has_stdin = !stdin_lines.is_empty();
 
+    if multi.stdin {
+        return run_task_list(
+            &stdin_lines,
+            base,
+            track_upstream,
+            package.as_deref(),
+            options,
+            layout,
+            &multi,
+            wait,
+            dry_run,
+        );
+    }
+
+    // Issue title for the `{issue_title}` template variable
+    let mut issue_title: Option<String> = None;
+
     // Determine if we're in explicit multi-worktree mode (before loading prompt)
     let is_explicit_multi =
         has_stdin || multi.foreach.is_some() || multi.count.is_some() || multi.agent.len() > 1;
@@ -132,7 +161,9 @@ pub fn run(
     let (final_branch_name, preloaded_prompt, remote_branch_for_pr, deferred_auto_name) =
         if auto_name {
             // Use editor if no prompt source specified, otherwise use provided source
-            let use_editor = prompt_args.prompt.is_none() && prompt_args.prompt_file.is_none();
+            let use_editor = prompt_args.prompt.is_none()
+                && prompt_args.prompt_file.is_none()
+                && prompt_args.prompt_template.is_none();
 
             // Cannot use interactive editor when stdin is piped (editor can't read terminal)
             if has_stdin && (prompt_args.prompt_editor || use_editor) {
@@ -146,6 +177,7 @@ pub fn run(
                 prompt_editor: use_editor || prompt_args.prompt_editor,
                 prompt_inline: prompt_args.prompt.as_deref(),
                 prompt_file: prompt_args.prompt_file.as_ref(),
+                prompt_template: prompt_args.prompt_template.as_deref(),
             })?
             .ok_or_else(|| anyhow!("Prompt is required for --auto-name"))?;
 
@@ -168,11 +200,23 @@ pub fn run(
             // Handle PR checkout if --pr flag is provided
             let result = workflow::pr::resolve_pr_ref(pr_number, branch_name)?;
             (result.local_branch, None, Some(result.remote_branch), false)
+        } else if let Some(issue_number) = from_issue {
+            // Name the branch after the issue and hand its text to the agent
+            let result = workflow::issue::resolve_issue(issue_number, branch_name)?;
+            issue_title = Some(result.title);
+            // A prompt template replaces the issue text as the prompt
+            let prompt = match prompt_args.prompt_template {
+                Some(_) => None,
+                None => Some(Prompt::Inline(result.prompt)),
+            };
+            (result.branch, prompt, None, false)
         } else {
             // Normal flow: use provided branch name
             (
                 branch_name
-                    .expect("branch_name required when --pr and --auto-name not provided")
+                    .expect(
+                        "branch_name required when --pr, --from-issue and --auto-name not provided",
+                    )
                     .to_string(),
                 None,
                 None,
@@ -182,10 +226,37 @@ pub fn run(
 
     // Use the determined branch name and override base if from PR
     let branch_name = &final_branch_name;
+
+    // A profile matching the branch supplies the base when --base isn't given,
+    // and a prompt template when no prompt is
+    let profile = if deferred_auto_name {
+        None
+    } else {
+        config::Config::load(multi.agent.first().map(|s| s.as_str()))?
+            .branch_profile(branch_name)
+            .cloned()
+    };
+    if let Some(ref profile) = profile {
+        let has_prompt = preloaded_prompt.is_some()
+            || prompt_args.prompt.is_some()
+            || prompt_args.prompt_file.is_some()
+            || prompt_args.prompt_editor
+            || prompt_args.prompt_template.is_some();
+        if !has_prompt && from_issue.is_none() {
+            prompt_args.prompt_template = profile.prompt_template.clone();
+        }
+    }
+
     let base = if remote_branch_for_pr.is_some() {
         None
     } else {
-        base
+        base.or(profile.as_ref().and_then(|p| p.base.as_deref()))
+    };
+
+    // Custom `--var` values; `Some` when the prompt comes from a template
+    let template_vars = match prompt_args.prompt_template {
+        Some(_) => Some(prompt_args.template_vars()?),
+        None => None,
     };
 
     // Validate --with-changes compatibility
@@ -209,7 +280,10 @@ pub fn run(
 
     // Handle rescue flow early if requested
     if rescue.with_changes {
-        let rescue_config = config::Config::load(multi.agent.first().map(|s| s.as_str()))?;
+        let mut rescue_config = config::Config::load(multi.agent.first().map(|s| s.as_str()))?;
+        if track_upstream {
+            rescue_config.auto_track_upstream = Some(true);
+        }
         let rescue_context = workflow::WorkflowContext::new(rescue_config)?;
         // Derive handle for rescue flow (uses config for naming strategy/prefix)
         let handle =
@@ -234,14 +308,17 @@ pub fn run(
             prompt_editor: prompt_args.prompt_editor,
             prompt_inline: prompt_args.prompt.as_deref(),
             prompt_file: prompt_args.prompt_file.as_ref(),
+            prompt_template: prompt_args.prompt_template.as_deref(),
         })?
     };
 
     // Parse prompt document to extract frontmatter (if applicable)
     let prompt_doc = if let Some(ref prompt_src) = prompt_template {
         // Account for implicit editor usage triggered by auto_name
-        let implicit_editor =
-            auto_name && prompt_args.prompt.is_none() && prompt_args.prompt_file.is_none();
+        let implicit_editor = auto_name
+            && prompt_args.prompt.is_none()
+            && prompt_args.prompt_file.is_none()
+            && prompt_args.prompt_template.is_none();
         let from_editor_or_file = prompt_args.prompt_editor
             || implicit_editor
             || matches!(prompt_src, Prompt::FromFile(_));
@@ -280,6 +357,9 @@ pub fn run(
     // If we have a PR remote branch, use that; otherwise detect from branch_name
     let (remote_branch, template_base_name) = if let Some(ref pr_remote) = remote_branch_for_pr {
         (Some(pr_remote.clone()), branch_name.to_string())
+    } else if detached {
+        // The name is not a branch, so never treat it as a remote ref
+        (None, branch_name.to_string())
     } else {
         detect_remote_branch(branch_name, base)?
     };
@@ -317,17 +397,139 @@ pub fn run(
         specs: &specs,
         resolved_base,
         remote_branch: remote_branch.as_deref(),
+        detached,
+        track_upstream,
+        issue: from_issue,
+        pr,
+        issue_title: issue_title.as_deref(),
         prompt_doc: prompt_doc.as_ref(),
+        template: prompt_args.prompt_template.as_deref(),
+        template_vars: template_vars.as_ref(),
         options,
         env: &env,
         explicit_name: name.as_deref(),
+        package: package.as_deref(),
+        base_name: &template_base_name,
         wait,
         deferred_auto_name,
         max_concurrent: multi.max_concurrent,
+        dry_run: false,
+        stack_parent: stack_parent.as_ref().map(|(handle, _)| handle.as_str()),
+        layout,
+    };
+    plan.execute()
+}
+
+/// Handle and branch of the worktree `--stack` names
+fn resolve_stack_parent(name: &str) -> Result<(String, String)> {
+    let (path, branch) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}' to stack on", name))?;
+    if git::is_detached(&branch) {
+        return Err(anyhow!(
+            "Cannot stack on '{}': its worktree is detached and has no branch",
+            name
+        ));
+    }
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(name)
+        .to_string();
+    Ok((handle, branch))
+}
+
+/// Create a worktree for each `branch :: prompt` line of a `--stdin` task list
+#[allow(clippy::too_many_arguments)]
+fn run_task_list(
+    lines: &[String],
+    base: Option<&str>,
+    track_upstream: bool,
+    package: Option<&str>,
+    options: SetupOptions,
+    layout: Option<&str>,
+    multi: &MultiArgs,
+    wait: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if multi.agent.len() > 1 {
+        return Err(anyhow!(
+            "--stdin can only be used with zero or one --agent, but {} were provided",
+            multi.agent.len()
+        ));
+    }
+    let agent = multi.agent.first().cloned();
+
+    let mut specs: Vec<WorktreeSpec> = Vec::new();
+    for line in lines.iter().filter(|line| !line.starts_with('#')) {
+        let (branch, prompt) = parse_task_line(line)?;
+        if specs.iter().any(|spec| spec.branch_name == branch) {
+            return Err(anyhow!(
+                "Branch '{}' appears twice in the task list",
+                branch
+            ));
+        }
+        specs.push(WorktreeSpec {
+            branch_name: branch,
+            agent: agent.clone(),
+            template_context: Value::Object(Default::default()),
+            prompt,
+        });
+    }
+    let Some(first) = specs.first() else {
+        return Err(anyhow!(
+            "--stdin expects a task list on stdin, one 'branch :: prompt' per line"
+        ));
+    };
+
+    let env = create_template_env();
+    let plan = CreationPlan {
+        specs: &specs,
+        resolved_base: base,
+        remote_branch: None,
+        detached: false,
+        track_upstream,
+        issue: None,
+        pr: None,
+        issue_title: None,
+        prompt_doc: None,
+        template: None,
+        template_vars: None,
+        options,
+        env: &env,
+        explicit_name: None,
+        package,
+        base_name: &first.branch_name,
+        wait,
+        deferred_auto_name: false,
+        max_concurrent: multi.max_concurrent,
+        dry_run,
+        stack_parent: None,
+        layout,
     };
     plan.execute()
 }
 
+/// Split a task line into its branch and prompt: `fix-login :: Fix the login
+/// redirect`. A line without `::` is a branch with no prompt.
+fn parse_task_line(line: &str) -> Result<(String, Option<String>)> {
+    let (branch, prompt) = match line.split_once("::") {
+        Some((branch, prompt)) => (branch.trim(), Some(prompt.trim())),
+        None => (line.trim(), None),
+    };
+    if branch.is_empty() {
+        return Err(anyhow!("Task has no branch name: '{}'", line));
+    }
+    if branch.contains(char::is_whitespace) {
+        return Err(anyhow!(
+            "Branch name '{}' contains spaces. Separate the prompt with ' :: ', \
+             e.g. 'fix-login :: Fix the login redirect'",
+            branch
+        ));
+    }
+    let prompt = prompt.filter(|p| !p.is_empty()).map(str::to_string);
+    Ok((branch.to_string(), prompt))
+}
+
 /// Handle the rescue flow (--with-changes).
 /// Returns Ok(true) if rescue flow was handled, Ok(false) if normal flow should continue.
 fn handle_rescue_flow(
@@ -442,21 +644,124 @@ struct CreationPlan<'a> {
     specs: &'a [WorktreeSpec],
     resolved_base: Option<&'a str>,
     remote_branch: Option<&'a str>,
+    /// Create worktrees without branches (`--detached`)
+    detached: bool,
+    /// Enable upstream tracking on first push (`--track-upstream`)
+    track_upstream: bool,
+    /// Issue the worktrees were created from (`--from-issue`)
+    issue: Option<u32>,
+    /// PR the worktree checks out (`--pr`)
+    pr: Option<u32>,
+    issue_title: Option<&'a str>,
     prompt_doc: Option<&'a PromptDocument>,
+    /// Template the prompt comes from (`--prompt-template`)
+    template: Option<&'a str>,
+    /// Custom `--var` values when the prompt comes from `--prompt-template`
+    template_vars: Option<&'a BTreeMap<String, String>>,
     options: SetupOptions,
     env: &'a TemplateEnv,
     explicit_name: Option<&'a str>,
+    package: Option<&'a str>,
+    /// Base branch name the specs were generated from (used as the group name)
+    base_name: &'a str,
     wait: bool,
     deferred_auto_name: bool,
     max_concurrent: Option<u32>,
+    /// Only print what would be created (`--dry-run`)
+    dry_run: bool,
+    /// Handle of the worktree the new ones are stacked on (`--stack`)
+    stack_parent: Option<&'a str>,
+    /// Pane layout from the config's `layouts` (`--layout`)
+    layout: Option<&'a str>,
 }
 
 impl<'a> CreationPlan<'a> {
     /// Execute the creation plan, creating all worktrees according to the specs.
     fn execute(&self) -> Result<()> {
+        if self.dry_run {
+            return self.preview();
+        }
         self.create_worktrees()
     }
 
+    /// Print the worktrees the plan would create, with their agent and prompt
+    fn preview(&self) -> Result<()> {
+        println!(
+            "Would create {} worktree{}:",
+            self.specs.len(),
+            if self.specs.len() == 1 { "" } else { "s" }
+        );
+        for spec in self.specs {
+            let config = config::Config::load(spec.agent.as_deref())?;
+            let branch = self.dated_branch(&spec.branch_name, &config)?;
+            let config = config.for_branch(&branch);
+            let handle = crate::naming::derive_scoped_handle(
+                &branch,
+                self.explicit_name,
+                self.package,
+                &config,
+            )?;
+            println!("  {}", branch);
+            if handle != branch {
+                println!("    Handle: {}", handle);
+            }
+            if let Some(agent) = spec.agent.as_deref().or(config.agent.as_deref()) {
+                println!("    Agent: {}", agent);
+            }
+            if let Some(prompt) = &spec.prompt {
+                println!("    Prompt: {}", prompt_summary(prompt));
+            }
+        }
+        Ok(())
+    }
+
+    /// The branch to create for `branch`: with today's date appended under the
+    /// `date-suffix` naming strategy, unless it is an existing branch, a PR, an
+    /// issue or a remote branch being checked out
+    fn dated_branch(&self, branch: &str, config: &config::Config) -> Result<String> {
+        let applies = config.worktree_naming == config::WorktreeNaming::DateSuffix
+            && !self.detached
+            && self.remote_branch.is_none()
+            && self.pr.is_none()
+            && self.issue.is_none()
+            && !git::branch_exists(branch).unwrap_or(false);
+        if !applies {
+            return Ok(branch.to_string());
+        }
+        let date = Cmd::new("date")
+            .arg("+%m%d")
+            .run_and_capture_stdout()
+            .context("Failed to read today's date")?;
+        Ok(crate::naming::date_suffixed(branch, date.trim(), |name| {
+            git::branch_exists(name).unwrap_or(false)
+        }))
+    }
+
+    /// Values for the `{name}` variables of a prompt template
+    fn template_values(
+        &self,
+        custom: &BTreeMap<String, String>,
+        branch: &str,
+        handle: &str,
+    ) -> BTreeMap<String, String> {
+        let mut values = custom.clone();
+        values.insert("branch".to_string(), branch.to_string());
+        values.insert("handle".to_string(), handle.to_string());
+        // New branches start from the current branch unless --base is given
+        let base = match (self.resolved_base, self.remote_branch) {
+            (Some(base), _) => Some(base.to_string()),
+            (None, None) => git::get_current_branch().ok().filter(|b| !b.is_empty()),
+            (None, Some(_)) => None,
+        };
+        if let Some(base) = base {
+            values.insert("base".to_string(), base);
+        }
+        if let Some(title) = self.issue_title {
+            values.insert("issue_title".to_string(), title.to_string());
+        }
+        values
+    }
+
     fn create_worktrees(&self) -> Result<()> {
         if self.specs.len() > 1 {
             println!("Preparing to create {} worktrees...", self.specs.len());
@@ -466,6 +771,8 @@ impl<'a> CreationPlan<'a> {
         let mut created_windows = Vec::new();
         // Track currently active windows for --max-concurrent
         let mut active_windows: Vec<String> = Vec::new();
+        // Group name shared by all windows of a multi-worktree run
+        let mut group: Option<String> = None;
 
         for (i, spec) in self.specs.iter().enumerate() {
             // Concurrency control: wait for a slot if at limit
@@ -483,7 +790,10 @@ impl<'a> CreationPlan<'a> {
                 }
             }
             // Load config for this specific agent to ensure correct agent resolution
-            let config = config::Config::load(spec.agent.as_deref())?;
+            let mut config = config::Config::load(spec.agent.as_deref())?;
+            if self.track_upstream {
+                config.auto_track_upstream = Some(true);
+            }
 
             // Render prompt first (needed for deferred auto-name)
             let rendered_prompt = if let Some(doc) = self.prompt_doc {
@@ -492,7 +802,7 @@ impl<'a> CreationPlan<'a> {
                         .with_context(|| format!("Failed to render prompt for spec index {}", i))?,
                 )
             } else {
-                None
+                spec.prompt.clone()
             };
 
             // If auto-name was deferred, run it now using the rendered prompt
@@ -501,6 +811,12 @@ impl<'a> CreationPlan<'a> {
             } else {
                 spec.branch_name.clone()
             };
+            let final_branch_name = self.dated_branch(&final_branch_name, &config)?;
+            // Panes and hooks of a profile matching the branch, then the
+            // panes of an explicit layout
+            let config = config
+                .for_branch(&final_branch_name)
+                .with_layout(self.layout)?;
 
             if self.specs.len() > 1 {
                 println!(
@@ -513,9 +829,20 @@ impl<'a> CreationPlan<'a> {
 
             // Derive handle from branch name, optional explicit name, and config
             // For single specs, explicit_name overrides; for multi-specs, it's None (disallowed)
-            let handle =
-                crate::naming::derive_handle(&final_branch_name, self.explicit_name, &config)?;
-
+            let handle = crate::naming::derive_scoped_handle(
+                &final_branch_name,
+                self.explicit_name,
+                self.package,
+                &config,
+            )?;
+
+            let rendered_prompt = match (rendered_prompt, self.template_vars) {
+                (Some(text), Some(custom)) => Some(substitute_template_vars(
+                    &text,
+                    &self.template_values(custom, &final_branch_name, &handle),
+                )?),
+                (text, _) => text,
+            };
             let prompt_for_spec = rendered_prompt.map(Prompt::Inline);
 
             super::announce_hooks(&config, Some(&self.options), super::HookPhase::PostCreate);
@@ -526,13 +853,13 @@ impl<'a> CreationPlan<'a> {
             // Calculate window name for tracking
             let full_window_name = tmux::prefixed(&context.prefix, &handle);
 
-            if self.wait {
+            if self.wait && self.options.open_window {
                 created_windows.push(full_window_name.clone());
             }
 
             // Track for concurrency control
-            if self.max_concurrent.is_some() {
-                active_windows.push(full_window_name);
+            if self.max_concurrent.is_some() && self.options.open_window {
+                active_windows.push(full_window_name.clone());
             }
 
             let result = workflow::create(
@@ -545,6 +872,8 @@ impl<'a> CreationPlan<'a> {
                     prompt: prompt_for_spec.as_ref(),
                     options: self.options.clone(),
                     agent: spec.agent.as_deref(),
+                    package: self.package,
+                    detached: self.detached,
                 },
             )
             .with_context(|| {
@@ -554,14 +883,58 @@ impl<'a> CreationPlan<'a> {
                 )
             })?;
 
+            // Tag windows created together so the dashboard lists them side by side.
+            // With deferred auto-naming the base name is a placeholder, so use
+            // the first generated branch name instead.
+            if self.specs.len() > 1 {
+                let group = group.get_or_insert_with(|| {
+                    if self.deferred_auto_name {
+                        final_branch_name.clone()
+                    } else {
+                        self.base_name.to_string()
+                    }
+                });
+                if let Err(e) = tmux::set_window_group(&full_window_name, group) {
+                    tracing::debug!(error = %e, "add:failed to set window group");
+                }
+            }
+
+            if let Some(issue) = self.issue {
+                git::set_branch_issue(&result.branch_name, issue).with_context(|| {
+                    format!("Created worktree but failed to link it to issue #{}", issue)
+                })?;
+            }
+            if self.issue.is_some()
+                || self.pr.is_some()
+                || self.template.is_some()
+                || self.stack_parent.is_some()
+                || self.layout.is_some()
+            {
+                metadata::update(&handle, |m| {
+                    m.issue = self.issue;
+                    m.pr = self.pr;
+                    m.template = self.template.map(str::to_string);
+                    m.template_vars = self.template_vars.cloned().unwrap_or_default();
+                    m.parent = self.stack_parent.map(str::to_string);
+                    m.layout = self.layout.map(str::to_string);
+                });
+            }
+
             if result.post_create_hooks_run > 0 {
                 println!("✓ Setup complete");
             }
 
-            println!(
-                "✓ Successfully created worktree and tmux window for '{}'",
-                result.branch_name
-            );
+            if self.options.open_window {
+                println!(
+                    "✓ Successfully created worktree and tmux window for '{}'",
+                    result.branch_name
+                );
+            } else {
+                println!(
+                    "✓ Successfully created worktree for '{}'",
+                    result.branch_name
+                );
+            }
             if let Some(ref base) = result.base_branch {
                 println!("  Base: {}", base);
             }
@@ -575,3 +948,53 @@ impl<'a> CreationPlan<'a> {
         Ok(())
     }
 }
+
+/// The first line of a prompt, shortened to fit a preview line
+fn prompt_summary(prompt: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = prompt.lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_CHARS {
+        let cut: String = line.chars().take(MAX_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn task_line_splits_branch_and_prompt() {
+        assert_eq!(
+            parse_task_line("fix-login :: Fix the login redirect :: twice").unwrap(),
+            (
+                "fix-login".to_string(),
+                Some("Fix the login redirect :: twice".to_string())
+            )
+        );
+        assert_eq!(
+            parse_task_line("  docs-typos  ").unwrap(),
+            ("docs-typos".to_string(), None)
+        );
+        assert_eq!(
+            parse_task_line("docs-typos ::").unwrap(),
+            ("docs-typos".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn task_line_rejects_missing_separator_and_branch() {
+        assert!(parse_task_line("fix the login redirect").is_err());
+        assert!(parse_task_line(":: Fix the login redirect").is_err());
+    }
+
+    #[test]
+    fn prompt_summary_keeps_the_first_line_short() {
+        assert_eq!(prompt_summary("Fix it\nthen test"), "Fix it");
+        let summary = prompt_summary(&"word ".repeat(20));
+        assert_eq!(summary.chars().count(), 60);
+        assert!(summary.ends_with("word…"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::archive::{self, ArchivedWorktree};
+use crate::workflow::WorkflowContext;
+use crate::{config, daemon, git, metadata, spinner, workflow};
+use anyhow::{Context, Result, anyhow};
+
+/// Message of the commit that keeps a worktree's uncommitted changes
+const WIP_COMMIT_MESSAGE: &str = "WIP: uncommitted changes archived by workmux";
+
+pub fn run(names: Vec<String>) -> Result<()> {
+    let resolved_names: Vec<String> = if names.is_empty() {
+        vec![super::resolve_name(None)?]
+    } else {
+        names
+            .iter()
+            .map(|n| super::resolve_name(Some(n)))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    for name in resolved_names {
+        archive_worktree(&name)?;
+    }
+    Ok(())
+}
+
+/// Keep the worktree's branch (and uncommitted changes) under the archive
+/// namespace, then tear down the worktree, window, and branch
+fn archive_worktree(name: &str) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let context = WorkflowContext::new(config)?;
+
+    let (worktree_path, branch) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let handle = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not derive handle from worktree path: {:?}",
+                worktree_path
+            )
+        })?
+        .to_string();
+
+    if git::is_detached(&branch) {
+        return Err(anyhow!(
+            "Cannot archive '{}': it has a detached HEAD, so there is no branch to keep",
+            handle
+        ));
+    }
+    if branch == context.main_branch || worktree_path == context.main_worktree_root {
+        return Err(anyhow!("Cannot archive the main worktree"));
+    }
+
+    let archive_config = &context.config.archive;
+    if !archive_config.namespace().starts_with("refs/") {
+        return Err(anyhow!(
+            "archive.namespace must start with refs/ (got '{}')",
+            archive_config.namespace()
+        ));
+    }
+    let ref_name = archive_config.ref_name(&handle);
+    let existing = archive::load(&handle);
+
+    // Uncommitted work goes into a commit of its own, so the ref keeps it too
+    let has_changes =
+        worktree_path.exists() && git::has_uncommitted_changes(&worktree_path).unwrap_or(false);
+    if has_changes {
+        git::commit_all_in(&worktree_path, WIP_COMMIT_MESSAGE)
+            .context("Failed to save uncommitted changes")?;
+    }
+    let commit = git::resolve_commit(&format!("refs/heads/{}", branch))?;
+
+    // Only a retry of an interrupted archive of the same commit may replace one
+    if let Some(ref existing) = existing
+        && existing.commit != commit
+    {
+        return Err(anyhow!(
+            "'{}' is already archived at {}. Restore it with 'workmux restore {}' first.",
+            handle,
+            existing.ref_name,
+            handle
+        ));
+    }
+
+    git::update_ref(&ref_name, &commit)?;
+    let mut pushed_to = None;
+    if let Some(ref remote) = archive_config.remote {
+        match spinner::with_spinner(&format!("Pushing {} to '{}'", ref_name, remote), || {
+            git::push_ref(remote, &ref_name)
+        }) {
+            Ok(()) => pushed_to = Some(remote.clone()),
+            Err(e) => eprintln!("Warning: archive kept locally only: {:#}", e),
+        }
+    }
+
+    // Record before tearing down, while the metadata and branch config exist
+    let record = ArchivedWorktree {
+        metadata: metadata::load_or_infer(&handle, &branch),
+        ref_name: ref_name.clone(),
+        commit,
+        archived_at: daemon::now_secs(),
+        remote: pushed_to,
+        package: git::get_branch_package(&branch),
+        wip_commit: has_changes || existing.is_some_and(|e| e.wip_commit),
+    };
+    archive::save(&handle, &record).context("Failed to record the archive")?;
+
+    super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
+    workflow::remove(&handle, true, false, false, &context).context("Failed to remove worktree")?;
+
+    println!("✓ Archived '{}' to {}", handle, ref_name);
+    if record.wip_commit {
+        println!("  Uncommitted changes were kept and come back on restore");
+    }
+    println!("  Restore it with: workmux restore {}", handle);
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::prompt::{TEMPLATE_BUILTIN_VARS, is_var_name};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(clap::Args, Debug)]
@@ -18,6 +20,55 @@ pub struct PromptArgs {
     /// Open $EDITOR to write the prompt
     #[arg(short = 'e', long = "prompt-editor", conflicts_with_all = ["prompt", "prompt_file"])]
     pub prompt_editor: bool,
+
+    /// Use the prompt template `.workmux/templates/<NAME>.md`, filling in
+    /// {branch}, {base}, {handle}, {issue_title} and any --var values
+    #[arg(
+        long = "prompt-template",
+        value_name = "NAME",
+        conflicts_with_all = ["prompt", "prompt_file", "prompt_editor"]
+    )]
+    pub prompt_template: Option<String>,
+
+    /// Set a prompt template variable (repeatable), e.g. --var ticket=ABC-123
+    #[arg(
+        long = "var",
+        value_name = "KEY=VALUE",
+        requires = "prompt_template",
+        value_parser = parse_template_var
+    )]
+    pub vars: Vec<(String, String)>,
+}
+
+impl PromptArgs {
+    /// Custom `--var` values, checked against the built-in template variables
+    pub fn template_vars(&self) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut vars = BTreeMap::new();
+        for (key, value) in &self.vars {
+            if TEMPLATE_BUILTIN_VARS.contains(&key.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "--var {} would override a built-in template variable",
+                    key
+                ));
+            }
+            vars.insert(key.clone(), value.clone());
+        }
+        Ok(vars)
+    }
+}
+
+fn parse_template_var(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", s))?;
+    let key = key.trim();
+    if !is_var_name(key) {
+        return Err(format!(
+            "invalid variable name '{}' (use letters, digits, '_' or '-')",
+            key
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
 }
 
 #[derive(clap::Args, Debug)]
@@ -37,6 +88,10 @@ pub struct SetupFlags {
     /// Create tmux window in the background (do not switch to it)
     #[arg(short = 'b', long = "background")]
     pub background: bool,
+
+    /// Record the agent pane's output for `workmux logs --agent`
+    #[arg(long)]
+    pub log_agent: bool,
 }
 
 #[derive(clap::Args, Debug)]
@@ -61,6 +116,17 @@ pub struct MultiArgs {
     #[arg(long, conflicts_with_all = ["agent", "count"])]
     pub foreach: Option<String>,
 
+    /// Read a task list from stdin, one `branch :: prompt` per line, and create
+    /// a worktree for each (the prompt is optional; `#` lines are skipped)
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "count", "foreach", "pr", "from_issue", "auto_name", "detached", "name",
+            "with_changes", "prompt", "prompt_file", "prompt_editor", "prompt_template",
+        ]
+    )]
+    pub stdin: bool,
+
     /// Template for branch names in multi-worktree modes.
     /// Variables: {{ base_name }}, {{ agent }}, {{ num }}, {{ foreach_vars }}.
     #[arg(
@@ -0,0 +1,53 @@
+use crate::workflow::{SetupOptions, WorkflowContext};
+use crate::{config, git, tmux, workflow};
+use anyhow::{Context, Result, anyhow};
+use tracing::info;
+
+/// Session started for `attach` when no tmux server is running and no
+/// `session_strategy` names one
+const DEFAULT_SESSION: &str = "workmux";
+
+pub fn run(name: &str) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let context = WorkflowContext::new(config)?;
+
+    let (worktree_path, _) = git::find_worktree(name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = worktree_path
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid worktree path: no directory name"))?
+        .to_string_lossy()
+        .to_string();
+    let full_name = tmux::prefixed(&context.prefix, &handle);
+
+    // Without a server there is nowhere to open the window: start one with a
+    // session of its own, whose initial window is dropped once ours exists
+    let mut placeholder_window = None;
+    if !tmux::is_running()? {
+        let session = context
+            .config
+            .window_session(&context.main_worktree_root)
+            .unwrap_or_else(|| DEFAULT_SESSION.to_string());
+        info!(session, "attach:starting tmux server");
+        placeholder_window = Some(tmux::start_detached_session(&session, &worktree_path)?);
+        tmux::set_window_session(Some(session));
+    }
+
+    if tmux::find_window_session(&full_name).is_none() {
+        let options = SetupOptions::new(false, false, true);
+        workflow::open(&handle, &context, options, false)
+            .context("Failed to open worktree environment")?;
+    }
+    if let Some(window_id) = placeholder_window {
+        let _ = tmux::kill_window_by_id(&window_id);
+    }
+
+    let session = tmux::find_window_session(&full_name)
+        .ok_or_else(|| anyhow!("No tmux window found for '{}'", handle))?;
+    info!(session, window = full_name, "attach:attaching");
+    tmux::attach_to_window(&session, &full_name)
+}
@@ -0,0 +1,245 @@
+//! `workmux _await-agent`: make sure a freshly launched agent started.
+//!
+//! Runs in the tmux server (`run-shell -b`) once `add` has typed the agent's
+//! command into its pane. The agent counts as started once the shell runs a
+//! command in the foreground, and as ready for a pasted prompt
+//! when its screen matches the profile's `ready_pattern` or stops changing.
+//! When the agent doesn't start (missing binary, crash, too slow), nothing is
+//! typed into the shell left behind and the failure is shown in tmux and as a
+//! desktop notification.
+
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::{notification, tmux};
+
+use super::dashboard::ansi::strip_ansi_escapes;
+use super::set_window_status;
+
+/// How long an agent gets to start and draw its screen
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time between looks at the pane
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Looks in a row the agent's screen must stay the same to count as settled,
+/// without a `ready_pattern`
+const SETTLED_POLLS: u32 = 4;
+/// Looks in a row a command must stay in the foreground to count as the
+/// agent. Shell prompt hooks run short foreground jobs too.
+const STARTED_POLLS: u32 = 2;
+
+/// What one look at the pane says about the agent
+#[derive(Debug, PartialEq)]
+enum Probe {
+    /// The shell hasn't started the agent yet
+    Starting,
+    /// The agent runs but may still be drawing its screen
+    Running,
+    /// The agent takes input
+    Ready,
+    Failed(&'static str),
+}
+
+pub fn run(
+    pane: &str,
+    agent: &str,
+    paste: Option<&Path>,
+    ready_pattern: Option<&str>,
+    input: tmux::InputOptions,
+) -> Result<()> {
+    let ready = ready_pattern.and_then(|p| match Regex::new(p) {
+        Ok(re) => Some(re),
+        Err(e) => {
+            warn!(agent, error = %e, "await_agent:invalid ready_pattern, ignored");
+            None
+        }
+    });
+    let deadline = Instant::now() + START_TIMEOUT;
+    let mut started = false;
+    let mut last_screen: Option<String> = None;
+    // The screen when the agent was first seen running, to tell when it has
+    // drawn something of its own
+    let mut launch_screen: Option<String> = None;
+    let mut stable_polls = 0;
+    let mut running_polls = 0;
+
+    loop {
+        let Ok(running) = tmux::pane_runs_command(pane) else {
+            // The pane was closed, nobody to tell
+            info!(pane, "await_agent:pane gone");
+            return Ok(());
+        };
+        let screen = strip_ansi_escapes(&tmux::capture_pane(pane, 0).unwrap_or_default());
+        if last_screen.as_deref() == Some(screen.as_str()) {
+            stable_polls += 1;
+        } else {
+            stable_polls = 0;
+        }
+
+        running_polls = if running { running_polls + 1 } else { 0 };
+        started |= running_polls >= STARTED_POLLS;
+        if running && launch_screen.is_none() {
+            // Already running at the first look, so the screen may be its own
+            launch_screen = Some(if last_screen.is_none() {
+                String::new()
+            } else {
+                screen.clone()
+            });
+        }
+        let drawn = launch_screen.as_ref().is_some_and(|s| *s != screen);
+
+        let probe = probe(
+            agent,
+            running,
+            started,
+            &screen,
+            ready.as_ref(),
+            drawn && stable_polls >= SETTLED_POLLS,
+        );
+        match probe {
+            Probe::Failed(reason) => return fail(pane, agent, paste, reason),
+            Probe::Ready if started => break,
+            // A prompt given as arguments is already on its way
+            Probe::Running if started && paste.is_none() => break,
+            _ if Instant::now() >= deadline => {
+                if !started {
+                    return fail(pane, agent, paste, "didn't start within 30s");
+                }
+                // Typing into the agent is harmless even if it looks busy
+                warn!(pane, agent, "await_agent:agent never looked ready");
+                break;
+            }
+            _ => {}
+        }
+        last_screen = Some(screen);
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    info!(pane, agent, "await_agent:agent started");
+    if let Some(file) = paste {
+        let prompt = std::fs::read_to_string(file)
+            .map_err(|e| anyhow!("Failed to read prompt file {}: {}", file.display(), e))?;
+        tmux::paste_multiline(pane, prompt.trim_end(), input)?;
+    }
+    Ok(())
+}
+
+/// Judge one look at the pane. `running` is whether the shell runs a command
+/// in the foreground, `started` whether the agent was seen running already,
+/// and `settled` whether the agent has drawn its screen and stopped changing
+/// it.
+fn probe(
+    agent: &str,
+    running: bool,
+    started: bool,
+    screen: &str,
+    ready: Option<&Regex>,
+    settled: bool,
+) -> Probe {
+    if !running {
+        if started {
+            return Probe::Failed("exited right after starting");
+        }
+        if shell_reported_missing(screen, agent) {
+            return Probe::Failed("command not found");
+        }
+        return Probe::Starting;
+    }
+    let ready = match ready {
+        Some(pattern) => pattern.is_match(screen),
+        None => settled,
+    };
+    if ready { Probe::Ready } else { Probe::Running }
+}
+
+/// Whether the shell printed that it couldn't find the agent (bash, zsh, sh,
+/// and fish wording, by name or by path). Errors about other commands, say from the shell's rc
+/// file, don't count.
+fn shell_reported_missing(screen: &str, agent: &str) -> bool {
+    screen.lines().any(|line| {
+        line.contains(agent)
+            && (line.contains("command not found")
+                || line.ends_with(": not found")
+                || line.contains("No such file or directory")
+                || line.contains("Unknown command"))
+    })
+}
+
+/// Leave the pane alone and make the failure hard to miss
+fn fail(pane: &str, agent: &str, prompt_file: Option<&Path>, reason: &str) -> Result<()> {
+    let mut message = format!("{}: {}", agent, reason);
+    if let Some(file) = prompt_file {
+        message.push_str(&format!("; prompt not sent (kept in {})", file.display()));
+    }
+    warn!(pane, agent, reason, "await_agent:agent failed to start");
+    // The working status set at launch would stick around otherwise
+    let _ = set_window_status::clear_status(pane);
+    tmux::display_message(pane, &format!("workmux: {}", message));
+    notification::show(&message);
+    Err(anyhow!(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_waits_for_the_shell_to_hand_over() {
+        assert_eq!(
+            probe("aider", false, false, "$ aider", None, false),
+            Probe::Starting
+        );
+        assert_eq!(
+            probe("aider", true, false, "Aider v0.8", None, false),
+            Probe::Running
+        );
+        assert_eq!(
+            probe("aider", true, true, "Aider v0.8", None, true),
+            Probe::Ready
+        );
+    }
+
+    #[test]
+    fn probe_uses_the_ready_pattern_when_set() {
+        let ready = Regex::new(r"(?m)^> $").unwrap();
+        assert_eq!(
+            probe("aider", true, true, "Loading...", Some(&ready), true),
+            Probe::Running
+        );
+        assert_eq!(
+            probe("aider", true, true, "Welcome\n> \n", Some(&ready), false),
+            Probe::Ready
+        );
+    }
+
+    #[test]
+    fn probe_fails_when_the_agent_is_missing_or_exits() {
+        let missing = "$ aider --notifications\nzsh: command not found: aider\n$ ";
+        assert_eq!(
+            probe("aider", false, false, missing, None, false),
+            Probe::Failed("command not found")
+        );
+        let path = "bash: /opt/aider/bin/aider: No such file or directory\n";
+        assert_eq!(
+            probe("aider", false, false, path, None, false),
+            Probe::Failed("command not found")
+        );
+        let fish = "fish: Unknown command: aider\n";
+        assert_eq!(
+            probe("aider", false, false, fish, None, false),
+            Probe::Failed("command not found")
+        );
+        // Someone else's missing command
+        let rc = "bash: direnv: command not found\n$ aider";
+        assert_eq!(
+            probe("aider", false, false, rc, None, false),
+            Probe::Starting
+        );
+        assert_eq!(
+            probe("aider", false, true, "Traceback...", None, false),
+            Probe::Failed("exited right after starting")
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::git;
+use anyhow::{Context, Result, anyhow};
+
+/// `workmux cd`: the shell function from `workmux shell-init` calls this with
+/// `--print` and changes to the directory printed
+pub fn run(name: Option<&str>, print: bool) -> Result<()> {
+    if !print {
+        return Err(anyhow!(
+            "'workmux cd' needs the shell integration to change your shell's directory.\n\
+             Add this to your shell's rc file, then start a new shell:\n  \
+             eval \"$(workmux shell-init bash)\"    # or zsh\n  \
+             workmux shell-init fish | source      # in config.fish"
+        ));
+    }
+
+    let path = match name {
+        Some(name) => {
+            git::find_worktree(name)
+                .with_context(|| {
+                    format!(
+                        "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+                        name
+                    )
+                })?
+                .0
+        }
+        None => git::get_main_worktree_root()?,
+    };
+    println!("{}", path.display());
+    Ok(())
+}
@@ -1,9 +1,14 @@
+use crate::events::{self, Event, Operation};
 use crate::{config, git, tmux};
 use anyhow::{Context, Result, anyhow};
 
 pub fn run(name: Option<&str>) -> Result<()> {
+    tmux::ensure_running()?;
     let config = config::Config::load(None)?;
     let prefix = config.window_prefix();
+    if let Ok(root) = git::get_main_worktree_root() {
+        tmux::set_window_session(config.window_session(&root));
+    }
 
     // When no name is provided, prefer the current tmux window name
     // This handles duplicate windows (e.g., wm:feature-2) correctly
@@ -48,6 +53,11 @@ pub fn run(name: Option<&str>) -> Result<()> {
         ));
     }
 
+    let handle = full_window_name
+        .strip_prefix(prefix)
+        .unwrap_or(&full_window_name);
+    events::record(Event::new(handle, Operation::Close));
+
     if is_current_window {
         // Schedule the window close with a small delay so the command can complete
         tmux::schedule_window_close_by_full_name(
@@ -0,0 +1,344 @@
+//! Share a project's workmux setup as a bundle, and check config files for
+//! mistakes.
+//!
+//! A bundle is a gzipped tarball of the project config, the `.workmux/`
+//! directory (prompt templates, hook scripts) and any scripts in the repository
+//! that hooks or pane commands refer to by relative path.
+
+use anyhow::{Context, Result, anyhow};
+use std::path::{Component, Path, PathBuf};
+
+use crate::cmd::Cmd;
+use crate::config::{self, Config};
+use crate::git;
+
+/// Directory for shared prompt templates and hook scripts, bundled when present
+const BUNDLE_DIR: &str = ".workmux";
+
+/// Default file name for `config export`
+const DEFAULT_BUNDLE_NAME: &str = "workmux-config.tar.gz";
+
+pub fn export(output: Option<PathBuf>) -> Result<()> {
+    let root = git::get_main_worktree_root().context("Not in a git repository")?;
+    let config_path = config::find_project_config(&root).ok_or_else(|| {
+        anyhow!(
+            "No .workmux.yaml found in {}. Run 'workmux init' to create one.",
+            root.display()
+        )
+    })?;
+    let project_config = Config::load_from_path(&config_path)?.unwrap_or_default();
+
+    let mut files = vec![PathBuf::from(
+        config_path
+            .file_name()
+            .ok_or_else(|| anyhow!("Invalid config path"))?,
+    )];
+    if root.join(BUNDLE_DIR).is_dir() {
+        files.push(PathBuf::from(BUNDLE_DIR));
+    }
+    for script in referenced_scripts(&project_config, |rel| root.join(rel).is_file()) {
+        if !files.contains(&script) {
+            files.push(script);
+        }
+    }
+
+    let output = output.unwrap_or_else(|| PathBuf::from(DEFAULT_BUNDLE_NAME));
+    let output = std::path::absolute(&output).unwrap_or(output);
+    let mut args = vec![
+        "-czf".to_string(),
+        output.to_string_lossy().into_owned(),
+        "-C".to_string(),
+        root.to_string_lossy().into_owned(),
+        "--".to_string(),
+    ];
+    args.extend(files.iter().map(|f| f.to_string_lossy().into_owned()));
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    Cmd::new("tar")
+        .args(&args)
+        .run()
+        .context("Failed to create config bundle")?;
+
+    println!("✓ Exported config bundle to {}", output.display());
+    for file in &files {
+        println!("  {}", file.display());
+    }
+    Ok(())
+}
+
+pub fn import(source: &str, force: bool) -> Result<()> {
+    let root = git::get_main_worktree_root().context("Not in a git repository")?;
+
+    let is_url = source.starts_with("http://") || source.starts_with("https://");
+    let archive = if is_url {
+        let path =
+            std::env::temp_dir().join(format!("workmux-bundle-{}.tar.gz", std::process::id()));
+        Cmd::new("curl")
+            .args(&["-fsSL", "-o", &path.to_string_lossy(), source])
+            .run()
+            .with_context(|| format!("Failed to download config bundle from {}", source))?;
+        path
+    } else {
+        PathBuf::from(source)
+    };
+
+    let result = extract(&archive, &root, force);
+    if is_url {
+        let _ = std::fs::remove_file(&archive);
+    }
+    let entries = result?;
+
+    println!("✓ Imported config bundle into {}", root.display());
+    for entry in &entries {
+        println!("  {}", entry);
+    }
+    Ok(())
+}
+
+/// Check the global and project config files, listing every problem found
+pub fn validate() -> Result<()> {
+    let mut files = Vec::new();
+    let mut global = None;
+    if let Some(path) = config::global_config_path().filter(|p| p.exists()) {
+        let result = Config::load_from_path(&path);
+        let problems = match result {
+            Ok(Some(ref config)) => config.lint(None),
+            Ok(None) => Vec::new(),
+            Err(ref e) => vec![e.to_string()],
+        };
+        global = result.ok().flatten();
+        files.push((path, problems));
+    }
+    if let Some(path) = config::project_config_path() {
+        let problems = match Config::load_from_path(&path) {
+            Ok(Some(config)) => config.lint(Some(&global.unwrap_or_default())),
+            Ok(None) => Vec::new(),
+            Err(e) => vec![e.to_string()],
+        };
+        files.push((path, problems));
+    }
+
+    if files.is_empty() {
+        println!("No config files found. Run 'workmux init' to create one.");
+        return Ok(());
+    }
+
+    let mut count = 0;
+    for (path, problems) in &files {
+        if problems.is_empty() {
+            println!("✓ {}", path.display());
+        } else {
+            println!("✗ {}", path.display());
+            for problem in problems {
+                println!("  - {}", problem);
+            }
+        }
+        count += problems.len();
+    }
+    if count > 0 {
+        return Err(anyhow!(
+            "Found {} problem{} in the config",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+    Ok(())
+}
+
+/// Validate and extract a bundle into `root`, returning the files it contained
+fn extract(archive: &Path, root: &Path, force: bool) -> Result<Vec<String>> {
+    if !archive.is_file() {
+        return Err(anyhow!("Bundle '{}' does not exist", archive.display()));
+    }
+    let listing = Cmd::new("tar")
+        .args(&["-tzf", &archive.to_string_lossy()])
+        .run_and_capture_stdout()
+        .with_context(|| format!("'{}' is not a valid config bundle", archive.display()))?;
+    let files = bundle_files(&listing)?;
+    let verbose = Cmd::new("tar")
+        .args(&["-tvzf", &archive.to_string_lossy()])
+        .run_and_capture_stdout()
+        .with_context(|| format!("'{}' is not a valid config bundle", archive.display()))?;
+    reject_links(&verbose)?;
+
+    if !force {
+        let existing: Vec<&String> = files.iter().filter(|f| root.join(f).exists()).collect();
+        if !existing.is_empty() {
+            let list: Vec<&str> = existing.iter().map(|f| f.as_str()).collect();
+            return Err(anyhow!(
+                "These files already exist: {}. Use --force to overwrite them.",
+                list.join(", ")
+            ));
+        }
+    }
+
+    Cmd::new("tar")
+        .args(&[
+            "-xzf",
+            &archive.to_string_lossy(),
+            "-C",
+            &root.to_string_lossy(),
+        ])
+        .run()
+        .context("Failed to extract config bundle")?;
+    Ok(files)
+}
+
+/// Parse a `tar -t` listing into the files it contains, rejecting entries
+/// that would be written outside the target directory
+fn bundle_files(listing: &str) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in listing.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let path = Path::new(entry);
+        let escapes = path.components().any(|c| {
+            matches!(
+                c,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if escapes {
+            return Err(anyhow!(
+                "Refusing to import unsafe bundle entry '{}'",
+                entry
+            ));
+        }
+        if !entry.ends_with('/') {
+            files.push(entry.trim_start_matches("./").to_string());
+        }
+    }
+    if files.is_empty() {
+        return Err(anyhow!("Config bundle is empty"));
+    }
+    Ok(files)
+}
+
+/// Refuse bundles with anything but regular files and directories, using the
+/// type character that starts each line of a `tar -tv` listing. A symlink or
+/// hard link entry could make later entries land outside the target directory.
+fn reject_links(verbose_listing: &str) -> Result<()> {
+    for line in verbose_listing.lines().filter(|l| !l.trim().is_empty()) {
+        if !matches!(line.chars().next(), Some('-' | 'd')) {
+            return Err(anyhow!(
+                "Refusing to import bundle entry that is not a regular file or directory: {}",
+                line.trim()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Relative paths of repository scripts that hooks and pane commands refer to.
+///
+/// `is_file` reports whether a relative path exists in the repository, so
+/// tokens that merely look like paths are ignored.
+fn referenced_scripts(config: &Config, is_file: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let hooks = [
+        &config.post_create,
+        &config.pre_merge,
+        &config.post_merge,
+        &config.pre_remove,
+        &config.post_remove,
+    ];
+    let profile_hooks = config.profiles.iter().flat_map(|p| {
+        [
+            &p.post_create,
+            &p.pre_merge,
+            &p.post_merge,
+            &p.pre_remove,
+            &p.post_remove,
+        ]
+    });
+    let pane_commands = std::iter::once(&config.panes)
+        .chain(config.profiles.iter().map(|p| &p.panes))
+        .flatten()
+        .chain(config.layouts.values())
+        .flatten()
+        .filter_map(|p| p.command.as_ref());
+    let commands = hooks
+        .into_iter()
+        .chain(profile_hooks)
+        .flatten()
+        .flatten()
+        .chain(pane_commands);
+
+    let mut scripts: Vec<PathBuf> = Vec::new();
+    for command in commands {
+        for token in command.split_whitespace() {
+            let token = token.trim_matches(|c| c == '"' || c == '\'');
+            let path = Path::new(token.trim_start_matches("./"));
+            let relative = path.components().all(|c| matches!(c, Component::Normal(_)));
+            if !relative || path.starts_with(BUNDLE_DIR) || token.starts_with('-') {
+                continue;
+            }
+            if is_file(path) && !scripts.iter().any(|s| s == path) {
+                scripts.push(path.to_path_buf());
+            }
+        }
+    }
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn referenced_scripts_finds_existing_relative_paths() {
+        let config = Config {
+            post_create: Some(vec![
+                "./scripts/setup.sh --fast".to_string(),
+                "bash 'scripts/seed.sh'".to_string(),
+                "npm install".to_string(),
+            ]),
+            pre_remove: Some(vec![
+                "/usr/local/bin/cleanup.sh".to_string(),
+                "../outside.sh".to_string(),
+                ".workmux/hooks/pre-remove.sh".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let existing = [
+            "scripts/setup.sh",
+            "scripts/seed.sh",
+            ".workmux/hooks/pre-remove.sh",
+        ];
+        let scripts = referenced_scripts(&config, |p| existing.iter().any(|e| p == Path::new(e)));
+        assert_eq!(
+            scripts,
+            vec![
+                PathBuf::from("scripts/setup.sh"),
+                PathBuf::from("scripts/seed.sh")
+            ]
+        );
+    }
+
+    #[test]
+    fn bundle_files_skips_directories() {
+        let listing = ".workmux.yaml\n.workmux/\n.workmux/prompts/review.md\n./scripts/setup.sh\n";
+        assert_eq!(
+            bundle_files(listing).unwrap(),
+            vec![
+                ".workmux.yaml",
+                ".workmux/prompts/review.md",
+                "scripts/setup.sh"
+            ]
+        );
+    }
+
+    #[test]
+    fn bundle_files_rejects_unsafe_entries() {
+        assert!(bundle_files(".workmux.yaml\n../evil.sh\n").is_err());
+        assert!(bundle_files("/etc/passwd\n").is_err());
+        assert!(bundle_files("").is_err());
+    }
+
+    #[test]
+    fn reject_links_allows_only_files_and_directories() {
+        let ok = "-rw-r--r-- me/me  12 2024-01-01 10:00 .workmux.yaml\n\
+                  drwxr-xr-x me/me   0 2024-01-01 10:00 .workmux/\n";
+        assert!(reject_links(ok).is_ok());
+        assert!(reject_links("lrwxrwxrwx me/me 0 2024-01-01 10:00 .workmux -> /tmp\n").is_err());
+        assert!(
+            reject_links("hrw-r--r-- me/me 0 2024-01-01 10:00 a.sh link to /etc/passwd\n").is_err()
+        );
+    }
+}
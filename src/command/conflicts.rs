@@ -0,0 +1,57 @@
+use crate::conflicts;
+use crate::git::{self, GitStatus};
+use anyhow::Result;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+/// List pairs of worktrees whose changes touch the same files
+pub fn run(json: bool) -> Result<()> {
+    let main_root = git::get_main_worktree_root()?;
+    let paths: Vec<PathBuf> = git::list_worktrees()?
+        .into_iter()
+        .map(|(path, _)| path)
+        .filter(|path| *path != main_root)
+        .collect();
+    let statuses = git::cached_git_statuses(&paths);
+    let worktrees: Vec<(&Path, &GitStatus)> = paths
+        .iter()
+        .filter_map(|path| statuses.get(path).map(|status| (path.as_path(), status)))
+        .collect();
+    let overlaps = conflicts::find_overlaps(&worktrees);
+
+    let handle = |path: &Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+
+    if json {
+        let output: Vec<_> = overlaps
+            .iter()
+            .map(|o| {
+                json!({
+                    "worktrees": [handle(&o.first), handle(&o.second)],
+                    "files": o.files,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if overlaps.is_empty() {
+        println!("No worktrees change the same files.");
+        return Ok(());
+    }
+    for overlap in &overlaps {
+        println!(
+            "⚠ {} and {} both change:",
+            handle(&overlap.first),
+            handle(&overlap.second)
+        );
+        for file in &overlap.files {
+            println!("    {}", file);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::cmd::Cmd;
+use crate::config::Config;
+use crate::daemon::{self, DaemonState};
+use crate::git;
+use crate::merge_queue;
+use crate::registry;
+use crate::tmux;
+
+/// How often git status is recomputed (git is much slower than tmux queries)
+const GIT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn run(interval_secs: u64) -> Result<()> {
+    let pid = std::process::id();
+
+    if let Some(existing) = daemon::load_fresh_state()
+        && existing.pid != pid
+        && is_process_alive(existing.pid)
+    {
+        return Err(anyhow!(
+            "A workmux daemon is already running (pid {})",
+            existing.pid
+        ));
+    }
+
+    let state_path = daemon::get_state_path()?;
+    println!(
+        "workmux daemon running (pid {}), refreshing every {}s",
+        pid, interval_secs
+    );
+    println!("State file: {}", state_path.display());
+    info!(pid, interval_secs, "daemon:start");
+
+    let interval = Duration::from_secs(interval_secs);
+    // Only needed for status icons and the window prefix of queued merges
+    let config = Config::load(None).unwrap_or_default();
+    // Seed git statuses from the dashboard cache so readers see data immediately
+    let mut git_statuses: HashMap<PathBuf, git::GitStatus> = git::load_status_cache();
+    let mut last_git_refresh: Option<Instant> = None;
+
+    loop {
+        let started = Instant::now();
+        let agents = tmux::get_all_agent_panes().unwrap_or_default();
+        registry::record(&agents);
+        merge_queue::run_due(&agents, &config);
+
+        if last_git_refresh.is_none_or(|t| t.elapsed() >= GIT_REFRESH_INTERVAL) {
+            last_git_refresh = Some(Instant::now());
+            let mut fresh = HashMap::new();
+            for agent in &agents {
+                if !fresh.contains_key(&agent.path) {
+                    fresh.insert(agent.path.clone(), git::get_git_status(&agent.path));
+                }
+            }
+            git_statuses = fresh;
+            git::save_status_cache(&git_statuses);
+        }
+
+        let state = DaemonState {
+            pid,
+            interval_secs,
+            updated_at: daemon::now_secs(),
+            agents,
+            git_statuses: git_statuses.clone(),
+        };
+        if let Err(e) = daemon::save_state(&state) {
+            warn!(error = %e, "daemon:failed to write state");
+        }
+        debug!(
+            agents = state.agents.len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "daemon:refresh"
+        );
+
+        std::thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
+fn is_process_alive(pid: u32) -> bool {
+    let pid = pid.to_string();
+    Cmd::new("kill")
+        .args(&["-0", &pid])
+        .run_as_check()
+        .unwrap_or(false)
+}
@@ -1,6 +1,9 @@
 //! Action enum and dispatcher for dashboard key handling.
 
-use super::app::{App, ViewMode};
+use crate::config::MainWorktreeAgents;
+
+use super::app::{App, StatusFilter, ViewMode};
+use super::diff::DiffTarget;
 
 /// All possible actions in the dashboard.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,10 +18,14 @@ pub enum Action {
     JumpToSelected,
     JumpToIndex(usize),
     PeekSelected,
+    ReopenSelected,
+    RemoveSelected,
 
     // Dashboard commands
     CycleSortMode,
     ToggleStaleFilter,
+    SetStatusFilter(StatusFilter),
+    TogglePause,
     EnterInputMode,
     ExitInputMode,
     ScrollPreviewUp,
@@ -26,22 +33,77 @@ pub enum Action {
     IncreasePreviewSize,
     DecreasePreviewSize,
     LoadWipDiff,
+    OpenLog,
     SendCommitDashboard,
     TriggerMergeDashboard,
+    ToggleMergeQueue,
+    TogglePin,
+    RequestSnooze,
 
     // Input mode
     SendKey(String),
 
+    // Prompt composer
+    OpenComposer,
+    ComposerAppendChar(char),
+    ComposerNewline,
+    ComposerDeleteChar,
+    SendComposer,
+    CancelComposer,
+
+    // Filter
+    StartFilter,
+    FilterAppendChar(char),
+    FilterDeleteChar,
+    ApplyFilter,
+    ClearFilter,
+    StartPreviewSearch,
+    PreviewSearchAppendChar(char),
+    PreviewSearchDeleteChar,
+    KeepPreviewSearch,
+    ClearPreviewSearch,
+    NextPreviewMatch,
+    PrevPreviewMatch,
+
+    // Remove confirmation
+    ConfirmRemove { force: bool },
+    CancelRemove,
+
+    // Snooze duration prompt
+    Snooze(u64),
+    CancelSnooze,
+
+    // Commit log modal
+    CloseLog,
+    LogScrollUp,
+    LogScrollDown,
+    LogPageUp,
+    LogPageDown,
+
     // Diff view navigation
     CloseDiff,
     ScrollUp,
     ScrollDown,
     ScrollPageUp,
     ScrollPageDown,
+    NextFile,
+    PrevFile,
+    ToggleFileFocus,
     ToggleDiffType,
     EnterPatchMode,
     SendCommitDiff,
     TriggerMergeDiff,
+    StartReviewComment,
+    SendReview,
+    StartCompareInput,
+    MarkReviewed,
+
+    // Compare target prompt
+    CompareAppendChar(char),
+    CompareDeleteChar,
+    CycleCompareSuggestion,
+    SubmitCompare,
+    CancelCompare,
 
     // Patch mode
     StageAndNext,
@@ -70,7 +132,12 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             false
         }
         Action::Quit => {
-            app.should_quit = true;
+            // The first q/Esc closes a kept preview search
+            if app.preview_search.is_some() {
+                app.clear_preview_search();
+            } else {
+                app.should_quit = true;
+            }
             false
         }
 
@@ -95,6 +162,14 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.peek_selected();
             false
         }
+        Action::ReopenSelected => {
+            app.reopen_selected();
+            false
+        }
+        Action::RemoveSelected => {
+            app.request_remove_selected();
+            false
+        }
 
         // Dashboard commands
         Action::CycleSortMode => {
@@ -105,8 +180,24 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.toggle_stale_filter();
             false
         }
+        Action::SetStatusFilter(filter) => {
+            app.set_status_filter(filter);
+            false
+        }
+        Action::TogglePause => {
+            app.paused = !app.paused;
+            // Catch up on what changed while paused
+            if !app.paused {
+                app.refresh();
+                app.refresh_preview();
+            }
+            !app.paused
+        }
         Action::EnterInputMode => {
-            if app.table_state.selected().is_some() && !app.agents.is_empty() {
+            if app.table_state.selected().is_some()
+                && !app.agents.is_empty()
+                && app.selected_offline().is_none()
+            {
                 app.input_mode = true;
             }
             false
@@ -115,6 +206,85 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.input_mode = false;
             false
         }
+        Action::OpenComposer => {
+            if app.table_state.selected().is_some()
+                && !app.agents.is_empty()
+                && app.selected_offline().is_none()
+            {
+                if app.selected_in_main_worktree()
+                    && app.config.main_worktree_agents.unwrap_or_default()
+                        == MainWorktreeAgents::Block
+                {
+                    app.status_message = Some(
+                        "Not sending prompts to an agent in the main worktree (main_worktree_agents: block)"
+                            .to_string(),
+                    );
+                } else {
+                    app.composer = Some(String::new());
+                }
+            }
+            false
+        }
+        Action::StartFilter => {
+            app.filter_input = true;
+            false
+        }
+        Action::FilterAppendChar(c) => {
+            app.filter.push(c);
+            app.refresh();
+            false
+        }
+        Action::FilterDeleteChar => {
+            app.filter.pop();
+            app.refresh();
+            false
+        }
+        Action::ApplyFilter => {
+            app.filter_input = false;
+            false
+        }
+        Action::ClearFilter => {
+            app.filter_input = false;
+            app.filter.clear();
+            app.refresh();
+            false
+        }
+        Action::StartPreviewSearch => {
+            app.start_preview_search();
+            false
+        }
+        Action::PreviewSearchAppendChar(c) => {
+            if let Some(search) = app.preview_search.as_mut() {
+                search.query.push(c);
+            }
+            app.jump_to_match(None);
+            false
+        }
+        Action::PreviewSearchDeleteChar => {
+            if let Some(search) = app.preview_search.as_mut() {
+                search.query.pop();
+            }
+            app.jump_to_match(None);
+            false
+        }
+        Action::KeepPreviewSearch => {
+            if let Some(search) = app.preview_search.as_mut() {
+                search.input = false;
+            }
+            false
+        }
+        Action::ClearPreviewSearch => {
+            app.clear_preview_search();
+            false
+        }
+        Action::NextPreviewMatch => {
+            app.jump_to_match(Some(-1));
+            false
+        }
+        Action::PrevPreviewMatch => {
+            app.jump_to_match(Some(1));
+            false
+        }
         Action::ScrollPreviewUp => {
             app.scroll_preview_up(app.preview_height, app.preview_line_count);
             false
@@ -132,7 +302,11 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             false
         }
         Action::LoadWipDiff => {
-            app.load_diff(false);
+            app.load_diff(DiffTarget::Uncommitted);
+            false
+        }
+        Action::OpenLog => {
+            app.open_log();
             false
         }
         Action::SendCommitDashboard => {
@@ -143,6 +317,68 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.trigger_merge_for_selected();
             false
         }
+        Action::ToggleMergeQueue => {
+            app.toggle_merge_queue();
+            false
+        }
+        Action::TogglePin => {
+            app.toggle_pin();
+            false
+        }
+        Action::RequestSnooze => {
+            app.request_snooze_selected();
+            false
+        }
+
+        // Commit log modal
+        Action::CloseLog => {
+            app.log_view = None;
+            false
+        }
+        Action::LogScrollUp => {
+            if let Some(ref mut log) = app.log_view {
+                log.scroll_by(-1);
+            }
+            false
+        }
+        Action::LogScrollDown => {
+            if let Some(ref mut log) = app.log_view {
+                log.scroll_by(1);
+            }
+            false
+        }
+        Action::LogPageUp => {
+            if let Some(ref mut log) = app.log_view {
+                log.page_by(-1);
+            }
+            false
+        }
+        Action::LogPageDown => {
+            if let Some(ref mut log) = app.log_view {
+                log.page_by(1);
+            }
+            false
+        }
+
+        // Remove confirmation
+        Action::ConfirmRemove { force } => {
+            app.confirm_remove(force);
+            false
+        }
+        Action::CancelRemove => {
+            app.pending_remove = None;
+            false
+        }
+
+        // Snooze duration prompt
+        Action::Snooze(secs) => {
+            app.confirm_snooze(secs);
+            false
+        }
+        Action::CancelSnooze => {
+            app.pending_snooze = None;
+            false
+        }
 
         // Input mode
         Action::SendKey(key) => {
@@ -151,6 +387,34 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             true // Signal that preview was refreshed
         }
 
+        // Prompt composer
+        Action::ComposerAppendChar(c) => {
+            if let Some(ref mut input) = app.composer {
+                input.push(c);
+            }
+            false
+        }
+        Action::ComposerNewline => {
+            if let Some(ref mut input) = app.composer {
+                input.push('\n');
+            }
+            false
+        }
+        Action::ComposerDeleteChar => {
+            if let Some(ref mut input) = app.composer {
+                input.pop();
+            }
+            false
+        }
+        Action::SendComposer => {
+            app.send_composer();
+            true
+        }
+        Action::CancelComposer => {
+            app.composer = None;
+            false
+        }
+
         // Diff view
         Action::CloseDiff => {
             app.close_diff();
@@ -180,15 +444,85 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             }
             false
         }
+        Action::NextFile | Action::PrevFile => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.jump_file(action == Action::NextFile);
+            }
+            false
+        }
+        Action::ToggleFileFocus => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.toggle_file_focus();
+            }
+            false
+        }
         Action::ToggleDiffType => {
             let is_branch_diff = if let ViewMode::Diff(ref diff) = app.view_mode {
-                diff.is_branch_diff
+                diff.is_branch_diff()
             } else {
                 false
             };
-            app.load_diff(!is_branch_diff);
+            app.load_diff(if is_branch_diff {
+                DiffTarget::Uncommitted
+            } else {
+                DiffTarget::Base
+            });
             false
         }
+        Action::StartReviewComment => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.comment_input = Some(String::new());
+                diff.comment_to_review = true;
+            }
+            false
+        }
+        Action::SendReview => {
+            app.send_review();
+            false
+        }
+        Action::StartCompareInput => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.ref_input = Some(String::new());
+            }
+            false
+        }
+        Action::MarkReviewed => {
+            app.mark_review_checkpoint();
+            false
+        }
+
+        // Compare target prompt
+        Action::CompareAppendChar(c) => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode
+                && let Some(ref mut input) = diff.ref_input
+            {
+                input.push(c);
+            }
+            false
+        }
+        Action::CompareDeleteChar => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode
+                && let Some(ref mut input) = diff.ref_input
+            {
+                input.pop();
+            }
+            false
+        }
+        Action::CycleCompareSuggestion => {
+            app.cycle_diff_target_suggestion();
+            false
+        }
+        Action::SubmitCompare => {
+            app.submit_diff_target();
+            false
+        }
+        Action::CancelCompare => {
+            if let ViewMode::Diff(ref mut diff) = app.view_mode {
+                diff.ref_input = None;
+            }
+            false
+        }
+
         Action::EnterPatchMode => {
             app.enter_patch_mode();
             false
@@ -222,6 +556,7 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
         Action::StartComment => {
             if let ViewMode::Diff(ref mut diff) = app.view_mode {
                 diff.comment_input = Some(String::new());
+                diff.comment_to_review = false;
             }
             false
         }
@@ -246,7 +581,13 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             false
         }
         Action::SendComment => {
-            app.send_hunk_comment();
+            let to_review =
+                matches!(app.view_mode, ViewMode::Diff(ref diff) if diff.comment_to_review);
+            if to_review {
+                app.add_review_comment();
+            } else {
+                app.send_hunk_comment();
+            }
             false
         }
         Action::DeleteChar => {
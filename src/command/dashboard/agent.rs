@@ -2,6 +2,8 @@
 
 use std::path::Path;
 
+use crate::config;
+
 /// Extract the worktree name from a window name.
 /// Returns (worktree_name, is_main) where is_main indicates if this is the main worktree.
 pub fn extract_worktree_name(window_name: &str, window_prefix: &str) -> (String, bool) {
@@ -14,9 +16,26 @@ pub fn extract_worktree_name(window_name: &str, window_prefix: &str) -> (String,
     }
 }
 
+/// Whether `path` is inside a repository's main worktree rather than a linked
+/// worktree: the nearest `.git` is a directory, where linked worktrees have a
+/// `.git` file.
+pub fn in_main_worktree(path: &Path) -> bool {
+    path.ancestors()
+        .map(|dir| dir.join(".git"))
+        .find(|git| git.exists())
+        .is_some_and(|git| git.is_dir())
+}
+
 /// Extract project name from a worktree path.
-/// Looks for __worktrees pattern or uses directory name as fallback.
-pub fn extract_project_name(path: &Path) -> String {
+/// Uses the `{repo}` component of the `worktree_dir` config when set, then looks
+/// for the __worktrees pattern, and uses the directory name as fallback.
+pub fn extract_project_name(path: &Path, worktree_dir: Option<&str>) -> String {
+    if let Some(project) =
+        worktree_dir.and_then(|template| config::project_from_worktree_dir(template, path))
+    {
+        return project;
+    }
+
     // Walk up the path to find __worktrees
     for ancestor in path.ancestors() {
         if let Some(name) = ancestor.file_name() {
@@ -37,6 +56,9 @@ pub fn extract_project_name(path: &Path) -> String {
         .unwrap_or_else(|| path.to_string_lossy().to_string())
 }
 
+/// How long an agent can keep the same status before it counts as stale
+pub const STALE_THRESHOLD_SECS: u64 = 60 * 60;
+
 /// Check if an agent is stale based on its status timestamp.
 pub fn is_stale(status_ts: Option<u64>, stale_threshold_secs: u64, now_secs: u64) -> bool {
     status_ts
@@ -44,17 +66,69 @@ pub fn is_stale(status_ts: Option<u64>, stale_threshold_secs: u64, now_secs: u64
         .unwrap_or(false)
 }
 
+/// Rank of a status in the Priority sort, lower first: waiting (needs
+/// input), done (needs review), working, then stale or unknown.
+pub fn status_priority(status: Option<&str>, icons: &config::StatusIcons, stale: bool) -> u8 {
+    if stale {
+        return 3;
+    }
+    match status.unwrap_or("") {
+        s if s == icons.waiting() => 0,
+        s if s == icons.done() => 1,
+        s if s == icons.working() => 2,
+        _ => 3,
+    }
+}
+
 /// Get elapsed seconds since the status timestamp.
 pub fn elapsed_secs(status_ts: Option<u64>, now_secs: u64) -> Option<u64> {
     status_ts.map(|ts| now_secs.saturating_sub(ts))
 }
 
-/// Format a duration in seconds as HH:MM:SS.
-pub fn format_duration(secs: u64) -> String {
-    let hours = secs / 3600;
-    let mins = (secs % 3600) / 60;
-    let secs = secs % 60;
-    format!("{:02}:{:02}:{:02}", hours, mins, secs)
+/// Reorder items so members of the same group directly follow the first
+/// (highest-ranked) member. Ungrouped items keep their position.
+pub fn cluster_groups<T, K: PartialEq>(
+    items: Vec<T>,
+    group_of: impl Fn(&T) -> Option<K>,
+) -> Vec<T> {
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    let mut clustered = Vec::with_capacity(slots.len());
+    for i in 0..slots.len() {
+        let Some(item) = slots[i].take() else {
+            continue;
+        };
+        let group = group_of(&item);
+        clustered.push(item);
+        if let Some(group) = group {
+            for slot in slots.iter_mut().skip(i + 1) {
+                if slot.as_ref().and_then(&group_of).as_ref() == Some(&group) {
+                    clustered.extend(slot.take());
+                }
+            }
+        }
+    }
+    clustered
+}
+
+/// Whether a row matches the dashboard filter. Every whitespace-separated
+/// term must fuzzy-match at least one field: its characters appear in order,
+/// ignoring case and punctuation (so `feat/auth` matches `feat-auth`).
+pub fn matches_filter(query: &str, fields: &[&str]) -> bool {
+    query
+        .split_whitespace()
+        .all(|term| fields.iter().any(|field| fuzzy_contains(field, term)))
+}
+
+fn fuzzy_contains(haystack: &str, needle: &str) -> bool {
+    let normalize = |s: &str| -> Vec<char> {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let haystack = normalize(haystack);
+    let mut remaining = haystack.iter();
+    normalize(needle).iter().all(|c| remaining.any(|h| h == c))
 }
 
 #[cfg(test)]
@@ -76,16 +150,46 @@ mod tests {
         assert!(is_main);
     }
 
+    #[test]
+    fn in_main_worktree_checks_nearest_git() {
+        let tmp = tempfile::tempdir().unwrap();
+        let main = tmp.path().join("repo");
+        let linked = tmp.path().join("repo__worktrees").join("fix");
+        std::fs::create_dir_all(main.join(".git")).unwrap();
+        std::fs::create_dir_all(main.join("src")).unwrap();
+        std::fs::create_dir_all(&linked).unwrap();
+        std::fs::write(linked.join(".git"), "gitdir: ../../repo/.git/worktrees/fix").unwrap();
+
+        assert!(in_main_worktree(&main.join("src")));
+        assert!(!in_main_worktree(&linked));
+        assert!(!in_main_worktree(tmp.path()));
+    }
+
     #[test]
     fn test_extract_project_name_worktrees() {
         let path = PathBuf::from("/home/user/myproject__worktrees/fix-bug");
-        assert_eq!(extract_project_name(&path), "myproject");
+        assert_eq!(extract_project_name(&path, None), "myproject");
+    }
+
+    #[test]
+    fn test_extract_project_name_worktree_dir_template() {
+        let path = PathBuf::from("/worktrees/myproject/fix-bug");
+        assert_eq!(
+            extract_project_name(&path, Some("/worktrees/{repo}/{handle}")),
+            "myproject"
+        );
+        // Paths outside the template fall back to the usual rules
+        let path = PathBuf::from("/home/user/other__worktrees/fix-bug");
+        assert_eq!(
+            extract_project_name(&path, Some("/worktrees/{repo}/{handle}")),
+            "other"
+        );
     }
 
     #[test]
     fn test_extract_project_name_fallback() {
         let path = PathBuf::from("/home/user/myproject");
-        assert_eq!(extract_project_name(&path), "myproject");
+        assert_eq!(extract_project_name(&path, None), "myproject");
     }
 
     #[test]
@@ -103,6 +207,16 @@ mod tests {
         assert!(!is_stale(None, 60, 200));
     }
 
+    #[test]
+    fn test_status_priority_ranks_waiting_first() {
+        let icons = config::StatusIcons::default();
+        assert_eq!(status_priority(Some("💬"), &icons, false), 0);
+        assert_eq!(status_priority(Some("✅"), &icons, false), 1);
+        assert_eq!(status_priority(Some("🤖"), &icons, false), 2);
+        assert_eq!(status_priority(Some("💬"), &icons, true), 3);
+        assert_eq!(status_priority(None, &icons, false), 3);
+    }
+
     #[test]
     fn test_elapsed_secs() {
         assert_eq!(elapsed_secs(Some(100), 200), Some(100));
@@ -110,9 +224,30 @@ mod tests {
     }
 
     #[test]
-    fn test_format_duration() {
-        assert_eq!(format_duration(0), "00:00:00");
-        assert_eq!(format_duration(61), "00:01:01");
-        assert_eq!(format_duration(3661), "01:01:01");
+    fn test_cluster_groups_moves_members_after_first() {
+        let items = vec![
+            ("a", Some("fix")),
+            ("b", None),
+            ("c", Some("feat")),
+            ("d", Some("fix")),
+            ("e", Some("feat")),
+        ];
+        let names: Vec<_> = cluster_groups(items, |(_, g)| *g)
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(names, vec!["a", "d", "b", "c", "e"]);
+    }
+
+    #[test]
+    fn test_matches_filter_fuzzy_terms() {
+        let fields = ["myproject", "feature-auth", "waiting"];
+        assert!(matches_filter("", &fields));
+        assert!(matches_filter("fauth", &fields));
+        assert!(matches_filter("Feature/Auth", &fields));
+        assert!(matches_filter("myp wait", &fields));
+        assert!(!matches_filter("htua", &fields));
+        // Each term must match, but they may match different fields
+        assert!(!matches_filter("myp done", &fields));
     }
 }
@@ -3,22 +3,31 @@
 use anyhow::Result;
 use ratatui::style::Color;
 use ratatui::widgets::TableState;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, mpsc};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::checks::{self, CheckResult};
+use crate::cmd::Cmd;
 use crate::config::Config;
+use crate::conflicts;
 use crate::git::{self, GitStatus};
+use crate::metadata::{self, WorktreeMetadata};
+use crate::registry::{self, RegisteredWorktree};
 use crate::tmux::{self, AgentPane};
+use crate::{daemon, duration, merge_queue, notification, pins, usage};
 
 use super::agent;
 use super::ansi::parse_ansi_to_lines;
 use super::diff::{
-    DiffView, extract_file_list, get_diff_content, get_file_list_numstat, map_file_offsets,
-    parse_hunk_header,
+    DiffTarget, DiffView, extract_file_list, get_diff_content, get_file_list_numstat,
+    map_file_offsets, parse_hunk_header,
 };
+use super::log::{LogView, load_log};
+use super::review::{ReviewComment, format_review, hunk_line_at};
+use super::search::{self, PreviewSearch, SEARCH_PREVIEW_LINES};
 use super::settings::{
     load_hide_stale_from_tmux, load_preview_size_from_tmux, save_hide_stale_to_tmux,
     save_preview_size_to_tmux,
@@ -37,12 +46,60 @@ pub enum ViewMode {
     Diff(Box<DiffView>),
 }
 
+/// Status the table is narrowed to by the quick-filter keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Waiting,
+    Working,
+    Done,
+}
+
+impl StatusFilter {
+    /// Semantic status name the filter keeps, None for all
+    pub fn status_name(self) -> Option<&'static str> {
+        match self {
+            StatusFilter::All => None,
+            StatusFilter::Waiting => Some("waiting"),
+            StatusFilter::Working => Some("working"),
+            StatusFilter::Done => Some("done"),
+        }
+    }
+}
+
+/// A worktree removal waiting for confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRemove {
+    pub handle: String,
+    pub path: PathBuf,
+    /// Worktree has uncommitted changes (requires a force confirmation)
+    pub is_dirty: bool,
+}
+
+/// How long `z` snoozes an agent for: the key, its label, and seconds
+pub const SNOOZE_DURATIONS: [(char, &str, u64); 4] = [
+    ('1', "30m", 30 * 60),
+    ('2', "1h", 60 * 60),
+    ('3', "4h", 4 * 60 * 60),
+    ('4', "1d", 24 * 60 * 60),
+];
+
+/// An agent waiting for a snooze duration to be picked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingSnooze {
+    pub handle: String,
+    pub pane_id: String,
+}
+
 /// App state for the TUI
 pub struct App {
     pub agents: Vec<AgentPane>,
+    /// Worktrees that have hosted agents but have no open window (listed after `agents`)
+    pub offline: Vec<RegisteredWorktree>,
     pub table_state: TableState,
-    /// Track the selected item by pane_id to preserve selection across reorders
-    selected_pane_id: Option<String>,
+    /// Track the selected row by key (see `row_key`) to preserve selection across reorders
+    selected_row_key: Option<String>,
     /// The directory from which the dashboard was launched (used to indicate the active worktree).
     pub current_worktree: Option<PathBuf>,
     pub stale_threshold_secs: u64,
@@ -58,6 +115,11 @@ pub struct App {
     preview_pane_id: Option<String>,
     /// Input mode: keystrokes are sent directly to the selected agent's pane
     pub input_mode: bool,
+    /// Prompt composer buffer (Some = composer open). Sent to the selected
+    /// agent as a single paste, unlike input mode.
+    pub composer: Option<String>,
+    /// Commit log modal for the selected worktree (`l`)
+    pub log_view: Option<LogView>,
     /// Manual scroll offset for the preview (None = auto-scroll to bottom)
     pub preview_scroll: Option<u16>,
     /// Number of lines in the current preview content
@@ -66,6 +128,15 @@ pub struct App {
     pub preview_height: u16,
     /// Git status for each worktree path
     pub git_statuses: HashMap<PathBuf, GitStatus>,
+    /// Results recorded by `workmux test --record`, keyed by worktree path
+    pub checks: HashMap<PathBuf, CheckResult>,
+    /// Worktree paths pinned to the top of the table (`*`)
+    pub pinned: BTreeSet<PathBuf>,
+    /// Worktrees changing files that another worktree of the same project
+    /// also changed, with the paths of those others
+    pub overlaps: HashMap<PathBuf, Vec<PathBuf>>,
+    /// Step of the `add`/`merge` running on each worktree, by handle
+    pub worktree_ops: HashMap<String, String>,
     /// Channel receiver for git status updates from background thread
     git_rx: mpsc::Receiver<(PathBuf, GitStatus)>,
     /// Channel sender for git status updates (cloned for background threads)
@@ -78,10 +149,35 @@ pub struct App {
     pub spinner_frame: u8,
     /// Whether to hide stale agents from the list
     pub hide_stale: bool,
+    /// Only list agents in this status (offline worktrees are hidden too)
+    pub status_filter: StatusFilter,
+    /// Whether automatic refreshing of the agent list and preview is paused
+    pub paused: bool,
+    /// Fuzzy filter query narrowing the table (empty = show all)
+    pub filter: String,
+    /// Whether the filter query is being edited (keys go to the query)
+    pub filter_input: bool,
+    /// Search in the preview pane, while one is open
+    pub preview_search: Option<PreviewSearch>,
     /// Whether to show the help overlay
     pub show_help: bool,
     /// Preview pane size as percentage (1-90). Higher = larger preview.
     pub preview_size: u8,
+    /// Worktree removal awaiting confirmation (shown in the footer)
+    pub pending_remove: Option<PendingRemove>,
+    /// Snooze duration prompt (`z`)
+    pub pending_snooze: Option<PendingSnooze>,
+    /// One-off message shown in the footer until the next key press
+    pub status_message: Option<String>,
+    /// Review comments left in the diff view, per worktree, until sent
+    pub review_comments: HashMap<PathBuf, Vec<ReviewComment>>,
+    /// Metadata recorded by `workmux add`, keyed by agent pane path
+    pub worktree_metadata: HashMap<PathBuf, WorktreeMetadata>,
+    /// Metadata directory of each agent pane path, looked up once per path
+    metadata_dirs: HashMap<PathBuf, Option<PathBuf>>,
+    /// Panes past their status alert threshold as of the last refresh
+    /// (None until the first refresh, so agents already overdue don't ring)
+    alerted_panes: Option<HashSet<String>>,
 }
 
 impl App {
@@ -101,10 +197,11 @@ impl App {
 
         let mut app = Self {
             agents: Vec::new(),
+            offline: Vec::new(),
             table_state: TableState::default(),
-            selected_pane_id: None,
+            selected_row_key: None,
             current_worktree,
-            stale_threshold_secs: 60 * 60, // 60 minutes
+            stale_threshold_secs: agent::STALE_THRESHOLD_SECS,
             config,
             should_quit: false,
             should_jump: false,
@@ -113,10 +210,16 @@ impl App {
             preview: None,
             preview_pane_id: None,
             input_mode: false,
+            composer: None,
+            log_view: None,
             preview_scroll: None,
             preview_line_count: 0,
             preview_height: 0,
             git_statuses: git::load_status_cache(),
+            checks: checks::load_all(),
+            pinned: pins::load(),
+            overlaps: HashMap::new(),
+            worktree_ops: HashMap::new(),
             git_rx,
             git_tx,
             // Set to past to trigger immediate fetch on first refresh
@@ -124,14 +227,26 @@ impl App {
             is_git_fetching: Arc::new(AtomicBool::new(false)),
             spinner_frame: 0,
             hide_stale: load_hide_stale_from_tmux(),
+            status_filter: StatusFilter::default(),
+            paused: false,
+            filter: String::new(),
+            filter_input: false,
+            preview_search: None,
             show_help: false,
             preview_size,
+            pending_remove: None,
+            pending_snooze: None,
+            status_message: None,
+            review_comments: HashMap::new(),
+            worktree_metadata: HashMap::new(),
+            metadata_dirs: HashMap::new(),
+            alerted_panes: None,
         };
         app.refresh();
         // Select first item if available
-        if !app.agents.is_empty() {
+        if app.row_count() > 0 {
             app.table_state.select(Some(0));
-            app.selected_pane_id = app.agents.first().map(|a| a.pane_id.clone());
+            app.selected_row_key = app.row_key(0);
         }
         // Initial preview fetch
         app.update_preview();
@@ -139,8 +254,45 @@ impl App {
     }
 
     pub fn refresh(&mut self) {
-        self.agents = tmux::get_all_agent_panes().unwrap_or_default();
+        // Prefer the snapshot from a running `workmux daemon` over querying tmux/git
+        let daemon_git_statuses = match daemon::load_fresh_state() {
+            Some(state) => {
+                self.agents = state.agents;
+                Some(state.git_statuses)
+            }
+            None => {
+                self.agents = tmux::get_all_agent_panes().unwrap_or_default();
+                // Without a daemon, the dashboard starts queued merges itself
+                let started = merge_queue::run_due(&self.agents, &self.config);
+                if !started.is_empty() {
+                    self.status_message = Some(format!("Merging {}", started.join(", ")));
+                }
+                None
+            }
+        };
+        usage::fill_from_titles(&mut self.agents, &self.config);
+        self.checks = checks::load_all();
+        self.pinned = pins::load();
         self.sort_agents();
+        self.check_alerts();
+        self.worktree_ops = tmux::get_worktree_ops();
+        self.load_worktree_metadata();
+
+        // Worktrees seen with agents before whose windows are now closed.
+        // Computed before the stale filter so hidden agents don't show as offline.
+        let prefix = self.config.window_prefix().to_string();
+        let mut offline: Vec<RegisteredWorktree> =
+            registry::offline(registry::record(&self.agents), &self.agents)
+                .into_iter()
+                .filter(|e| !agent::extract_worktree_name(&e.window_name, &prefix).1)
+                .collect();
+        offline.sort_by_cached_key(|e| {
+            (
+                agent::extract_project_name(&e.path, self.config.worktree_dir.as_deref()),
+                agent::extract_worktree_name(&e.window_name, &prefix).0,
+            )
+        });
+        self.offline = offline;
 
         // Filter out stale agents if hide_stale is enabled
         if self.hide_stale {
@@ -157,57 +309,73 @@ impl App {
             });
         }
 
+        if let Some(status) = self.status_filter.status_name() {
+            let agents = std::mem::take(&mut self.agents);
+            self.agents = agents
+                .into_iter()
+                .filter(|a| !a.exited && self.status_name(a) == Some(status))
+                .collect();
+            self.offline.clear();
+        }
+
+        if !self.filter.trim().is_empty() {
+            let agents = std::mem::take(&mut self.agents);
+            self.agents = agents
+                .into_iter()
+                .filter(|a| self.agent_matches_filter(a))
+                .collect();
+            let offline = std::mem::take(&mut self.offline);
+            self.offline = offline
+                .into_iter()
+                .filter(|e| self.offline_matches_filter(e))
+                .collect();
+        }
+
         // Consume any pending git status updates from background thread
         while let Ok((path, status)) = self.git_rx.try_recv() {
             self.git_statuses.insert(path, status);
         }
 
-        // Trigger background git status fetch every 5 seconds
-        if self.last_git_fetch.elapsed() >= Duration::from_secs(5) {
+        if let Some(statuses) = daemon_git_statuses {
+            self.git_statuses.extend(statuses);
+        } else if self.last_git_fetch.elapsed() >= Duration::from_secs(5) {
+            // Trigger background git status fetch every 5 seconds
             self.last_git_fetch = std::time::Instant::now();
             self.spawn_git_status_fetch();
         }
+        self.update_overlaps();
 
-        // Restore selection by pane_id to follow the item across reorders
-        if let Some(ref pane_id) = self.selected_pane_id {
-            // Find the new index of the previously selected item
-            if let Some(new_idx) = self.agents.iter().position(|a| &a.pane_id == pane_id) {
-                self.table_state.select(Some(new_idx));
-            } else {
-                // Item was removed (filtered out or closed), keep selection in bounds
-                self.selected_pane_id = None;
-                if self.agents.is_empty() {
-                    self.table_state.select(None);
-                } else if let Some(selected) = self.table_state.selected() {
-                    if selected >= self.agents.len() {
-                        self.table_state.select(Some(self.agents.len() - 1));
-                    }
-                    // Update selected_pane_id to the new selection
-                    if let Some(idx) = self.table_state.selected() {
-                        self.selected_pane_id = self.agents.get(idx).map(|a| a.pane_id.clone());
-                    }
-                }
-            }
-        } else if let Some(selected) = self.table_state.selected() {
-            // No tracked pane_id but we have a selection - adjust if out of bounds
-            if selected >= self.agents.len() {
-                self.table_state.select(if self.agents.is_empty() {
-                    None
-                } else {
-                    Some(self.agents.len() - 1)
-                });
-            }
-            // Sync selected_pane_id to ensure we start tracking the current selection
-            if let Some(idx) = self.table_state.selected() {
-                self.selected_pane_id = self.agents.get(idx).map(|a| a.pane_id.clone());
-            }
-        }
+        self.restore_selection();
 
         // Update preview for current selection
         self.update_preview();
     }
 
     /// Spawn a background thread to fetch git status for all agent worktrees
+    /// Find worktrees whose changes touch the same files (main worktrees
+    /// aside, since they aren't merged anywhere)
+    fn update_overlaps(&mut self) {
+        let prefix = self.config.window_prefix();
+        let mut paths: Vec<&PathBuf> = self
+            .agents
+            .iter()
+            .filter(|a| !agent::extract_worktree_name(&a.window_name, prefix).1)
+            .map(|a| &a.path)
+            .chain(self.offline.iter().map(|e| &e.path))
+            .collect();
+        paths.sort();
+        paths.dedup();
+        let worktrees: Vec<(&Path, &GitStatus)> = paths
+            .into_iter()
+            .filter_map(|path| self.git_statuses.get(path).map(|s| (path.as_path(), s)))
+            .collect();
+        let worktree_dir = self.config.worktree_dir.as_deref();
+        let overlaps = conflicts::find_overlaps_by_repo(&worktrees, |path| {
+            agent::extract_project_name(path, worktree_dir)
+        });
+        self.overlaps = conflicts::by_worktree(&overlaps);
+    }
+
     fn spawn_git_status_fetch(&self) {
         // Skip if a fetch is already in progress (prevents thread pile-up)
         if self
@@ -220,7 +388,12 @@ impl App {
 
         let tx = self.git_tx.clone();
         let is_fetching = self.is_git_fetching.clone();
-        let agent_paths: Vec<PathBuf> = self.agents.iter().map(|a| a.path.clone()).collect();
+        let agent_paths: Vec<PathBuf> = self
+            .agents
+            .iter()
+            .map(|a| a.path.clone())
+            .chain(self.offline.iter().map(|e| e.path.clone()))
+            .collect();
 
         std::thread::spawn(move || {
             // Reset flag when thread completes (even on panic)
@@ -240,6 +413,45 @@ impl App {
         });
     }
 
+    /// Select the same row as before the rows changed (refresh, re-sort), by
+    /// its key. When it is gone, the selection stays at the same position and
+    /// input mode ends, so keys typed for that agent don't reach another one.
+    fn restore_selection(&mut self) {
+        let keys: Vec<Option<String>> = (0..self.row_count()).map(|i| self.row_key(i)).collect();
+        let tracked = self.selected_row_key.take();
+        let (selected, found) =
+            follow_selection(&keys, tracked.as_deref(), self.table_state.selected());
+        if tracked.is_some() && !found && self.input_mode {
+            self.input_mode = false;
+            self.status_message = Some("The agent went away, left input mode".to_string());
+        }
+        self.table_state.select(selected);
+        self.selected_row_key = selected.and_then(|idx| keys[idx].clone());
+    }
+
+    /// Total number of table rows (live agents followed by offline worktrees)
+    pub fn row_count(&self) -> usize {
+        self.agents.len() + self.offline.len()
+    }
+
+    /// Stable key for a table row: the pane_id for live agents, the worktree
+    /// path for offline worktrees.
+    fn row_key(&self, idx: usize) -> Option<String> {
+        match self.agents.get(idx) {
+            Some(agent) => Some(agent.pane_id.clone()),
+            None => self
+                .offline
+                .get(idx - self.agents.len())
+                .map(|e| format!("offline:{}", e.path.display())),
+        }
+    }
+
+    /// The selected offline worktree, if an offline row is selected
+    pub fn selected_offline(&self) -> Option<&RegisteredWorktree> {
+        let selected = self.table_state.selected()?;
+        self.offline.get(selected.checked_sub(self.agents.len())?)
+    }
+
     /// Update the preview for the currently selected agent.
     /// Only fetches if the selection has changed or preview is stale.
     pub fn update_preview(&mut self) {
@@ -254,9 +466,12 @@ impl App {
             self.preview_pane_id = current_pane_id.clone();
             self.preview = current_pane_id
                 .as_ref()
-                .and_then(|pane_id| tmux::capture_pane(pane_id, PREVIEW_LINES));
+                .and_then(|pane_id| tmux::capture_pane(pane_id, self.preview_lines()));
             // Reset scroll position when selection changes
             self.preview_scroll = None;
+            if self.preview_search.is_some() {
+                self.jump_to_match(None);
+            }
         }
     }
 
@@ -265,7 +480,67 @@ impl App {
         self.preview = self
             .preview_pane_id
             .as_ref()
-            .and_then(|pane_id| tmux::capture_pane(pane_id, PREVIEW_LINES));
+            .and_then(|pane_id| tmux::capture_pane(pane_id, self.preview_lines()));
+    }
+
+    /// Lines of history to capture, more while searching the preview
+    fn preview_lines(&self) -> u16 {
+        if self.preview_search.is_some() {
+            SEARCH_PREVIEW_LINES
+        } else {
+            PREVIEW_LINES
+        }
+    }
+
+    /// Open a search in the preview, capturing more of the pane's history
+    pub fn start_preview_search(&mut self) {
+        self.preview_search = Some(PreviewSearch {
+            input: true,
+            ..PreviewSearch::default()
+        });
+        self.refresh_preview();
+    }
+
+    /// Close the preview search and follow the pane's output again
+    pub fn clear_preview_search(&mut self) {
+        self.preview_search = None;
+        self.preview_scroll = None;
+        self.refresh_preview();
+    }
+
+    /// Preview lines containing the search query, oldest first
+    pub fn preview_matches(&self) -> Vec<usize> {
+        match (&self.preview_search, &self.preview) {
+            (Some(search), Some(preview)) => search::matching_lines(preview, &search.query),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Scroll the preview to a match: `None` for the newest, otherwise
+    /// `step` matches from the current one (negative is older), wrapping
+    pub fn jump_to_match(&mut self, step: Option<isize>) {
+        let matches = self.preview_matches();
+        let Some(search) = self.preview_search.as_mut() else {
+            return;
+        };
+        if matches.is_empty() {
+            search.current = 0;
+            return;
+        }
+        let count = matches.len() as isize;
+        search.current = match step {
+            None => matches.len() - 1,
+            Some(step) => (search.current as isize + step).rem_euclid(count) as usize,
+        };
+        // Line count as rendered (the preview is trimmed the same way)
+        let line_count = self
+            .preview
+            .as_deref()
+            .map(|p| p.trim_end().lines().count())
+            .unwrap_or(0) as u16;
+        let max_scroll = line_count.saturating_sub(self.preview_height);
+        let line = matches[search.current] as u16;
+        self.preview_scroll = Some(line.saturating_sub(self.preview_height / 2).min(max_scroll));
     }
 
     /// Parse pane_id (e.g., "%0", "%10") to a number for proper ordering
@@ -279,10 +554,9 @@ impl App {
     /// Sort agents based on the current sort mode
     fn sort_agents(&mut self) {
         // Extract config values needed for sorting to avoid borrowing issues
-        let waiting = self.config.status_icons.waiting().to_string();
-        let working = self.config.status_icons.working().to_string();
-        let done = self.config.status_icons.done().to_string();
+        let icons = self.config.status_icons.clone();
         let stale_threshold = self.stale_threshold_secs;
+        let worktree_dir = self.config.worktree_dir.clone();
 
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -291,21 +565,8 @@ impl App {
 
         // Helper closure to get status priority (lower = higher priority)
         let get_priority = |agent: &AgentPane| -> u8 {
-            let is_stale = agent
-                .status_ts
-                .map(|ts| now.saturating_sub(ts) > stale_threshold)
-                .unwrap_or(false);
-
-            if is_stale {
-                return 3; // Stale: lowest priority
-            }
-
-            match agent.status.as_deref().unwrap_or("") {
-                s if s == waiting => 0, // Waiting: needs input
-                s if s == done => 1,    // Done: needs review
-                s if s == working => 2, // Working: no action needed
-                _ => 3,                 // Unknown/other: lowest priority
-            }
+            let is_stale = agent::is_stale(agent.status_ts, stale_threshold, now);
+            agent::status_priority(agent.status.as_deref(), &icons, is_stale)
         };
 
         // Helper closure to get elapsed time (lower = more recent)
@@ -323,14 +584,25 @@ impl App {
         // Include pane_id as final tiebreaker for stable ordering within groups
         match self.sort_mode {
             SortMode::Priority => {
-                // Sort by priority, then by elapsed time (most recent first), then by pane_id
-                self.agents
-                    .sort_by_cached_key(|a| (get_priority(a), get_elapsed(a), pane_num(a)));
+                // Sort by priority, then by elapsed time (most recent first), then by pane_id.
+                // Snoozed agents are parked on purpose and go last.
+                self.agents.sort_by_cached_key(|a| {
+                    (
+                        a.is_snoozed(now),
+                        get_priority(a),
+                        get_elapsed(a),
+                        pane_num(a),
+                    )
+                });
             }
             SortMode::Project => {
                 // Sort by project name first, then by status priority within each project
                 self.agents.sort_by_cached_key(|a| {
-                    (Self::extract_project_name(a), get_priority(a), pane_num(a))
+                    (
+                        agent::extract_project_name(&a.path, worktree_dir.as_deref()),
+                        get_priority(a),
+                        pane_num(a),
+                    )
                 });
             }
             SortMode::Recency => {
@@ -341,6 +613,19 @@ impl App {
                 self.agents.sort_by_cached_key(pane_num);
             }
         }
+
+        // Keep worktrees created together (e.g., `add --count 3`) next to each other.
+        // Groups are per session, so same-named groups of different projects
+        // (`session_strategy: per-project`) stay apart.
+        self.agents = agent::cluster_groups(std::mem::take(&mut self.agents), |a| {
+            a.group
+                .as_deref()
+                .map(|g| (a.session.clone(), g.to_string()))
+        });
+
+        // Pinned agents go first whatever the sort mode, in the same order
+        let pinned = &self.pinned;
+        self.agents.sort_by_key(|a| !pinned.contains(&a.path));
     }
 
     /// Cycle to the next sort mode, re-sort, and persist to tmux
@@ -348,6 +633,17 @@ impl App {
         self.sort_mode = self.sort_mode.next();
         self.sort_mode.save_to_tmux();
         self.sort_agents();
+        self.restore_selection();
+    }
+
+    /// Narrow the table to one status, or back to all when it already is
+    pub fn set_status_filter(&mut self, filter: StatusFilter) {
+        self.status_filter = if self.status_filter == filter {
+            StatusFilter::All
+        } else {
+            filter
+        };
+        self.refresh();
     }
 
     /// Toggle hiding stale agents
@@ -357,6 +653,100 @@ impl App {
         self.refresh();
     }
 
+    /// Ring the bell and/or notify for agents that just crossed their
+    /// status alert threshold
+    fn check_alerts(&mut self) {
+        let alerted: HashSet<String> = self
+            .agents
+            .iter()
+            .filter(|a| self.is_alert(a))
+            .map(|a| a.pane_id.clone())
+            .collect();
+        let previous = self.alerted_panes.replace(alerted.clone());
+        let Some(previous) = previous else {
+            return;
+        };
+        let newly_alerted: Vec<&AgentPane> = self
+            .agents
+            .iter()
+            .filter(|a| alerted.contains(&a.pane_id) && !previous.contains(&a.pane_id))
+            .collect();
+        if newly_alerted.is_empty() {
+            return;
+        }
+
+        if self.config.dashboard.alert_bell.unwrap_or(false) {
+            use std::io::Write;
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+        if self.config.dashboard.alert_notify.unwrap_or(false) {
+            for agent in newly_alerted {
+                let status = self.status_name(agent).unwrap_or("idle");
+                let elapsed = self.get_elapsed(agent).unwrap_or(0);
+                notification::show(&format!(
+                    "{} has been {} for {}",
+                    self.extract_worktree_name(agent).0,
+                    status,
+                    duration::format_duration(elapsed)
+                ));
+            }
+        }
+    }
+
+    /// Semantic status (working/waiting/done) of an agent, if recognized
+    fn status_name(&self, agent: &AgentPane) -> Option<&'static str> {
+        self.config
+            .status_icons
+            .status_name(agent.status.as_deref()?)
+    }
+
+    /// Whether the agent has been in its status longer than its configured alert threshold
+    pub fn is_alert(&self, agent: &AgentPane) -> bool {
+        let Some(threshold) = self
+            .status_name(agent)
+            .and_then(|status| self.config.dashboard.alerts.for_status(status))
+        else {
+            return false;
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        !agent.is_snoozed(now) && agent::is_stale(agent.status_ts, threshold, now)
+    }
+
+    fn agent_matches_filter(&self, agent: &AgentPane) -> bool {
+        let status_name = if agent.exited {
+            "exited"
+        } else {
+            self.status_name(agent).unwrap_or("")
+        };
+        let stale = if self.is_stale(agent) { "stale" } else { "" };
+        agent::matches_filter(
+            &self.filter,
+            &[
+                &self.extract_project_name(agent),
+                &self.extract_worktree_name(agent).0,
+                agent.summary_title(&self.config).unwrap_or(""),
+                status_name,
+                stale,
+            ],
+        )
+    }
+
+    fn offline_matches_filter(&self, entry: &RegisteredWorktree) -> bool {
+        agent::matches_filter(
+            &self.filter,
+            &[
+                &agent::extract_project_name(&entry.path, self.config.worktree_dir.as_deref()),
+                &agent::extract_worktree_name(&entry.window_name, self.config.window_prefix()).0,
+                "offline",
+            ],
+        )
+    }
+
     /// Increase preview size by 10% (max 90%)
     pub fn increase_preview_size(&mut self) {
         self.preview_size = (self.preview_size + 10).min(90);
@@ -370,12 +760,13 @@ impl App {
     }
 
     pub fn next(&mut self) {
-        if self.agents.is_empty() {
+        let row_count = self.row_count();
+        if row_count == 0 {
             return;
         }
         let i = match self.table_state.selected() {
             Some(i) => {
-                if i >= self.agents.len() - 1 {
+                if i >= row_count - 1 {
                     0
                 } else {
                     i + 1
@@ -384,18 +775,19 @@ impl App {
             None => 0,
         };
         self.table_state.select(Some(i));
-        self.selected_pane_id = self.agents.get(i).map(|a| a.pane_id.clone());
+        self.selected_row_key = self.row_key(i);
         self.update_preview();
     }
 
     pub fn previous(&mut self) {
-        if self.agents.is_empty() {
+        let row_count = self.row_count();
+        if row_count == 0 {
             return;
         }
         let i = match self.table_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.agents.len() - 1
+                    row_count - 1
                 } else {
                     i - 1
                 }
@@ -403,7 +795,7 @@ impl App {
             None => 0,
         };
         self.table_state.select(Some(i));
-        self.selected_pane_id = self.agents.get(i).map(|a| a.pane_id.clone());
+        self.selected_row_key = self.row_key(i);
         self.update_preview();
     }
 
@@ -420,11 +812,96 @@ impl App {
     pub fn jump_to_index(&mut self, index: usize) {
         if index < self.agents.len() {
             self.table_state.select(Some(index));
-            self.selected_pane_id = self.agents.get(index).map(|a| a.pane_id.clone());
+            self.selected_row_key = self.row_key(index);
             self.jump_to_selected();
         }
     }
 
+    /// Reopen the tmux window of the selected offline worktree via `workmux open`
+    pub fn reopen_selected(&mut self) {
+        let Some(entry) = self.selected_offline() else {
+            return;
+        };
+        let handle =
+            agent::extract_worktree_name(&entry.window_name, self.config.window_prefix()).0;
+        let path = entry.path.clone();
+        // Run as a subprocess so hook output doesn't draw over the TUI
+        let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("workmux"));
+        let exe = exe.to_string_lossy();
+        match Cmd::new(&exe).args(&["open", &handle]).workdir(&path).run() {
+            Ok(_) => {
+                self.should_jump = true;
+                self.refresh();
+            }
+            Err(e) => tracing::warn!(handle, error = %e, "dashboard:failed to reopen worktree"),
+        }
+    }
+
+    /// Ask for confirmation to remove the selected worktree (live or offline)
+    pub fn request_remove_selected(&mut self) {
+        let selected = match self.table_state.selected() {
+            Some(idx) => match self.agents.get(idx) {
+                Some(agent) => Some((agent.path.clone(), agent.window_name.clone())),
+                None => self
+                    .selected_offline()
+                    .map(|e| (e.path.clone(), e.window_name.clone())),
+            },
+            None => None,
+        };
+        let Some((path, window_name)) = selected else {
+            return;
+        };
+        let (handle, is_main) =
+            agent::extract_worktree_name(&window_name, self.config.window_prefix());
+        if is_main {
+            self.status_message = Some("Cannot remove the main worktree".to_string());
+            return;
+        }
+        let is_dirty = git::has_uncommitted_changes(&path).unwrap_or(false);
+        self.pending_remove = Some(PendingRemove {
+            handle,
+            path,
+            is_dirty,
+        });
+    }
+
+    /// Remove the pending worktree, window, and branch via `workmux remove`.
+    /// Dirty worktrees are only removed when `force` is set.
+    pub fn confirm_remove(&mut self, force: bool) {
+        let Some(pending) = self.pending_remove.take() else {
+            return;
+        };
+        if pending.is_dirty && !force {
+            // Keep asking: a plain "yes" isn't enough to discard changes
+            self.pending_remove = Some(pending);
+            return;
+        }
+
+        // The dashboard prompt replaces remove's own confirmation, so always pass
+        // --force. Run from the main worktree so the removed directory isn't the cwd.
+        let workdir = git::get_main_worktree_root_in(Some(&pending.path))
+            .unwrap_or_else(|_| pending.path.clone());
+        let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("workmux"));
+        let exe = exe.to_string_lossy();
+        match Cmd::new(&exe)
+            .args(&["remove", "--force", &pending.handle])
+            .workdir(&workdir)
+            .run()
+        {
+            Ok(_) => {
+                self.status_message = Some(format!("Removed '{}'", pending.handle));
+                self.refresh();
+            }
+            Err(e) => {
+                tracing::warn!(handle = pending.handle, error = %e, "dashboard:failed to remove worktree");
+                // The last line of the error carries remove's own message
+                let reason = e.to_string().lines().last().unwrap_or_default().to_string();
+                self.status_message =
+                    Some(format!("Failed to remove '{}': {}", pending.handle, reason));
+            }
+        }
+    }
+
     pub fn peek_selected(&mut self) {
         // Switch to pane but keep popup open
         if let Some(selected) = self.table_state.selected()
@@ -444,6 +921,41 @@ impl App {
         }
     }
 
+    /// Whether the selected agent runs in a main worktree
+    pub fn selected_in_main_worktree(&self) -> bool {
+        self.table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+            .is_some_and(|agent| agent::in_main_worktree(&agent.path))
+    }
+
+    /// Send the composed prompt to the selected agent in one paste, followed by
+    /// Enter, and close the composer
+    pub fn send_composer(&mut self) {
+        let Some(message) = self.composer.take() else {
+            return;
+        };
+        if message.trim().is_empty() {
+            return;
+        }
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let input = tmux::agent_input(&agent.pane_id, &self.config);
+        match tmux::paste_multiline(&agent.pane_id, message.trim_end(), input) {
+            Ok(()) => self.refresh_preview(),
+            Err(e) => {
+                // Keep the text so it can be retried
+                self.composer = Some(message);
+                self.status_message = Some(format!("Failed to send prompt: {}", e));
+            }
+        }
+    }
+
     /// Scroll preview up (toward older content). Returns the amount to scroll by.
     pub fn scroll_preview_up(&mut self, visible_height: u16, total_lines: u16) {
         let max_scroll = total_lines.saturating_sub(visible_height);
@@ -466,8 +978,31 @@ impl App {
         }
     }
 
+    /// Re-read the recorded metadata of every agent's worktree. The files are
+    /// small; only finding each repository's metadata directory runs git.
+    fn load_worktree_metadata(&mut self) {
+        let prefix = self.config.window_prefix().to_string();
+        self.worktree_metadata.clear();
+        for agent in &self.agents {
+            let (handle, is_main) = agent::extract_worktree_name(&agent.window_name, &prefix);
+            if is_main {
+                continue;
+            }
+            let dir = self
+                .metadata_dirs
+                .entry(agent.path.clone())
+                .or_insert_with(|| metadata::dir_in(&agent.path));
+            if let Some(recorded) = dir
+                .as_deref()
+                .and_then(|dir| metadata::load_from(dir, &handle))
+            {
+                self.worktree_metadata.insert(agent.path.clone(), recorded);
+            }
+        }
+    }
+
     pub fn format_duration(&self, secs: u64) -> String {
-        agent::format_duration(secs)
+        duration::format_duration(secs)
     }
 
     pub fn is_stale(&self, agent: &AgentPane) -> bool {
@@ -487,6 +1022,11 @@ impl App {
     }
 
     pub fn get_status_display(&self, agent: &AgentPane) -> (String, Color) {
+        // The agent is gone and the pane is back at its shell
+        if agent.exited {
+            return ("exited".to_string(), Color::DarkGray);
+        }
+
         let status = agent.status.as_deref().unwrap_or("");
         let is_stale = self.is_stale(agent);
 
@@ -506,6 +1046,23 @@ impl App {
             (status.to_string(), Color::White, false)
         };
 
+        // Mark agents queued to merge when they're done
+        let status_text = if agent.merge_queued {
+            format!("{} ⤵", status_text)
+        } else {
+            status_text
+        };
+
+        // Snoozed agents are dimmed with the time left
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if let Some(until) = agent.snoozed_until.filter(|_| agent.is_snoozed(now)) {
+            let left = duration::format_duration(until - now);
+            return (format!("{} 💤 {}", status_text, left), Color::DarkGray);
+        }
+
         // If stale, dim the color and add timer-off indicator
         if is_stale {
             let display_text = format!("{} \u{f051b}", status_text);
@@ -520,14 +1077,34 @@ impl App {
         }
     }
 
+    /// In-flight operations on worktrees that have no row in the table
+    pub fn unlisted_worktree_ops(&self) -> Vec<(&str, &str)> {
+        let prefix = self.config.window_prefix();
+        let listed: HashSet<String> = self
+            .agents
+            .iter()
+            .map(|a| &a.window_name)
+            .chain(self.offline.iter().map(|e| &e.window_name))
+            .map(|w| agent::extract_worktree_name(w, prefix).0)
+            .collect();
+        let mut ops: Vec<(&str, &str)> = self
+            .worktree_ops
+            .iter()
+            .filter(|(handle, _)| !listed.contains(*handle))
+            .map(|(handle, op)| (handle.as_str(), op.as_str()))
+            .collect();
+        ops.sort();
+        ops
+    }
+
     /// Extract the worktree name from an agent.
     /// Returns (worktree_name, is_main) where is_main indicates if this is the main worktree.
     pub fn extract_worktree_name(&self, agent_pane: &AgentPane) -> (String, bool) {
         agent::extract_worktree_name(&agent_pane.window_name, self.config.window_prefix())
     }
 
-    pub fn extract_project_name(agent_pane: &AgentPane) -> String {
-        agent::extract_project_name(&agent_pane.path)
+    pub fn extract_project_name(&self, agent_pane: &AgentPane) -> String {
+        agent::extract_project_name(&agent_pane.path, self.config.worktree_dir.as_deref())
     }
 
     /// Stage a single hunk using git apply --cached
@@ -601,7 +1178,7 @@ impl App {
     pub fn enter_patch_mode(&mut self) {
         // Check if we are in WIP diff view (patch mode not supported for branch diffs)
         let is_wip_diff = if let ViewMode::Diff(ref diff) = self.view_mode {
-            !diff.is_branch_diff
+            !diff.is_branch_diff()
         } else {
             false
         };
@@ -720,7 +1297,7 @@ impl App {
                     title: format!("WIP: {}", worktree_name),
                     worktree_path: path,
                     pane_id,
-                    is_branch_diff: false,
+                    target: DiffTarget::Uncommitted,
                     lines_added,
                     lines_removed,
                     patch_mode: false,
@@ -730,7 +1307,10 @@ impl App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    comment_to_review: false,
+                    ref_input: None,
                     file_list,
+                    focused_file: None,
                 }));
             }
             Err(e) => {
@@ -744,7 +1324,7 @@ impl App {
                     title: "Error".to_string(),
                     worktree_path: path,
                     pane_id,
-                    is_branch_diff: false,
+                    target: DiffTarget::Uncommitted,
                     lines_added: 0,
                     lines_removed: 0,
                     patch_mode: false,
@@ -754,7 +1334,10 @@ impl App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    comment_to_review: false,
+                    ref_input: None,
                     file_list: Vec::new(),
+                    focused_file: None,
                 }));
             }
         }
@@ -849,7 +1432,70 @@ impl App {
         );
 
         // Use paste_multiline to properly handle newlines in the message
-        let _ = tmux::paste_multiline(&diff.pane_id, &message);
+        let input = tmux::agent_input(&diff.pane_id, &self.config);
+        let _ = tmux::paste_multiline(&diff.pane_id, &message, input);
+    }
+
+    /// Add the typed comment to the worktree's review, attached to the current
+    /// hunk in patch mode or to the file (and hunk, if found) being viewed
+    pub fn add_review_comment(&mut self) {
+        let ViewMode::Diff(ref mut diff) = self.view_mode else {
+            return;
+        };
+        let text = match diff.comment_input.take() {
+            Some(c) if !c.trim().is_empty() => c,
+            _ => return,
+        };
+
+        let comment = if diff.patch_mode
+            && let Some(hunk) = diff.hunks.get(diff.current_hunk)
+        {
+            ReviewComment {
+                file: Some(hunk.filename.clone()),
+                line: parse_hunk_header(&hunk.hunk_body).map(|(_, new_start)| new_start),
+                excerpt: Some(hunk.hunk_body.clone()),
+                text,
+            }
+        } else {
+            let file = diff
+                .current_file_index()
+                .and_then(|idx| diff.file_list.get(idx));
+            ReviewComment {
+                file: file.map(|f| f.filename.clone()),
+                line: file
+                    .and_then(|f| hunk_line_at(&diff.parsed_lines, f.start_line, diff.scroll)),
+                excerpt: None,
+                text,
+            }
+        };
+        self.review_comments
+            .entry(diff.worktree_path.clone())
+            .or_default()
+            .push(comment);
+    }
+
+    /// Send the collected review comments to the agent as one prompt and
+    /// return to the dashboard
+    pub fn send_review(&mut self) {
+        let ViewMode::Diff(ref diff) = self.view_mode else {
+            return;
+        };
+        let Some(comments) = self.review_comments.remove(&diff.worktree_path) else {
+            return;
+        };
+        let input = tmux::agent_input(&diff.pane_id, &self.config);
+        match tmux::paste_multiline(&diff.pane_id, &format_review(&comments), input) {
+            Ok(()) => {
+                self.status_message = Some(format!("Sent review ({} comment(s))", comments.len()));
+                self.close_diff();
+            }
+            Err(e) => {
+                // Keep the comments so sending can be retried
+                self.review_comments
+                    .insert(diff.worktree_path.clone(), comments);
+                self.status_message = Some(format!("Failed to send review: {}", e));
+            }
+        }
     }
 
     /// Split the current hunk into smaller hunks if possible
@@ -880,9 +1526,27 @@ impl App {
         false
     }
 
-    /// Load diff for the selected worktree
-    /// - `branch_diff`: if true, diff against main branch; if false, diff HEAD (uncommitted)
-    pub fn load_diff(&mut self, branch_diff: bool) {
+    /// Open the commit log of the selected worktree since its base branch
+    pub fn open_log(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let base = self
+            .git_statuses
+            .get(&agent.path)
+            .map(|s| s.base_branch.as_str())
+            .filter(|b| !b.is_empty())
+            .unwrap_or("main");
+        let title = format!("{} → {}", self.extract_worktree_name(agent).0, base);
+        self.log_view = Some(LogView::new(title, &load_log(&agent.path, base)));
+    }
+
+    /// Load diff for the selected worktree, comparing it against `target`
+    pub fn load_diff(&mut self, target: DiffTarget) {
         let Some(selected) = self.table_state.selected() else {
             return;
         };
@@ -894,36 +1558,69 @@ impl App {
         let pane_id = agent.pane_id.clone();
         let worktree_name = self.extract_worktree_name(agent).0;
 
-        let (diff_arg, title) = if branch_diff {
-            // Get the base branch from git status if available, fallback to "main"
-            let base = self
-                .git_statuses
-                .get(path)
-                .map(|s| s.base_branch.as_str())
-                .filter(|b| !b.is_empty())
-                .unwrap_or("main");
-            (
-                format!("{}...HEAD", base),
-                format!("Review: {} → {}", worktree_name, base),
-            )
-        } else {
-            ("HEAD".to_string(), format!("WIP: {}", worktree_name))
+        let resolved = match &target {
+            DiffTarget::Uncommitted => Ok((
+                "HEAD".to_string(),
+                format!("WIP: {}", worktree_name),
+                "No uncommitted changes".to_string(),
+            )),
+            DiffTarget::Base => {
+                // Get the base branch from git status if available, fallback to "main"
+                let base = self
+                    .git_statuses
+                    .get(path)
+                    .map(|s| s.base_branch.as_str())
+                    .filter(|b| !b.is_empty())
+                    .unwrap_or("main");
+                Ok((
+                    format!("{}...HEAD", base),
+                    format!("Review: {} → {}", worktree_name, base),
+                    "No commits on this branch yet".to_string(),
+                ))
+            }
+            // Plain tree diff: the checkpoint may no longer be an ancestor
+            // if the agent rewrote history since
+            DiffTarget::Checkpoint => match git::get_review_checkpoint(path) {
+                Some(sha) => Ok((
+                    format!("{}..HEAD", sha),
+                    format!(
+                        "Since review: {} → {}",
+                        worktree_name,
+                        &sha[..sha.len().min(7)]
+                    ),
+                    "No changes since the last review".to_string(),
+                )),
+                None => Err(
+                    "No review checkpoint yet. Press R in the diff view to mark HEAD as reviewed."
+                        .to_string(),
+                ),
+            },
+            DiffTarget::Ref(rev) => {
+                if git::commit_exists_in(rev, path) {
+                    Ok((
+                        format!("{}...HEAD", rev),
+                        format!("Diff: {} → {}", worktree_name, rev),
+                        format!("No commits since {}", rev),
+                    ))
+                } else {
+                    Err(format!("'{}' does not name a commit", rev))
+                }
+            }
         };
 
         // Include untracked files only for uncommitted changes view
         // Don't parse hunks eagerly - they're only needed for patch mode,
         // which reloads and parses them on demand via reload_unstaged_diff()
-        let include_untracked = !branch_diff;
+        let include_untracked = target == DiffTarget::Uncommitted;
         let parse_hunks = false;
-        match get_diff_content(path, &diff_arg, include_untracked, parse_hunks) {
-            Ok((content, lines_added, lines_removed, hunks)) => {
+        let loaded = resolved.and_then(|(diff_arg, title, empty_msg)| {
+            get_diff_content(path, &diff_arg, include_untracked, parse_hunks)
+                .map(|diff| (diff_arg, title, empty_msg, diff))
+        });
+        match loaded {
+            Ok((diff_arg, title, empty_msg, (content, lines_added, lines_removed, hunks))) => {
                 let (content, line_count) = if content.trim().is_empty() {
-                    let msg = if branch_diff {
-                        "No commits on this branch yet"
-                    } else {
-                        "No uncommitted changes"
-                    };
-                    (msg.to_string(), 1)
+                    (empty_msg, 1)
                 } else {
                     let count = content.lines().count();
                     (content, count)
@@ -947,7 +1644,7 @@ impl App {
                     title,
                     worktree_path: path.clone(),
                     pane_id,
-                    is_branch_diff: branch_diff,
+                    target,
                     lines_added,
                     lines_removed,
                     patch_mode: false,
@@ -957,7 +1654,10 @@ impl App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    comment_to_review: false,
+                    ref_input: None,
                     file_list,
+                    focused_file: None,
                 }));
             }
             Err(e) => {
@@ -972,7 +1672,7 @@ impl App {
                     title: "Error".to_string(),
                     worktree_path: path.clone(),
                     pane_id,
-                    is_branch_diff: branch_diff,
+                    target,
                     lines_added: 0,
                     lines_removed: 0,
                     patch_mode: false,
@@ -982,12 +1682,73 @@ impl App {
                     hunks_processed: 0,
                     staged_hunks: Vec::new(),
                     comment_input: None,
+                    comment_to_review: false,
+                    ref_input: None,
                     file_list: Vec::new(),
+                    focused_file: None,
                 }));
             }
         }
     }
 
+    /// Mark the diffed worktree's HEAD as reviewed, then show what changed
+    /// since (nothing, until the agent commits again)
+    pub fn mark_review_checkpoint(&mut self) {
+        let ViewMode::Diff(ref diff) = self.view_mode else {
+            return;
+        };
+        match git::set_review_checkpoint(&diff.worktree_path) {
+            Ok(_) => self.load_diff(DiffTarget::Checkpoint),
+            Err(e) => {
+                let msg = format!("Failed to mark as reviewed: {}", e);
+                if let ViewMode::Diff(ref mut diff) = self.view_mode {
+                    diff.parsed_lines = parse_ansi_to_lines(&msg);
+                    diff.content = msg;
+                    diff.line_count = 1;
+                    diff.scroll = 0;
+                    diff.file_list.clear();
+                }
+            }
+        }
+    }
+
+    /// Fill the compare prompt with the next common target
+    pub fn cycle_diff_target_suggestion(&mut self) {
+        let ViewMode::Diff(ref mut diff) = self.view_mode else {
+            return;
+        };
+        let Some(ref mut input) = diff.ref_input else {
+            return;
+        };
+        let base = self
+            .git_statuses
+            .get(&diff.worktree_path)
+            .map(|s| s.base_branch.as_str())
+            .filter(|b| !b.is_empty())
+            .unwrap_or("main");
+        let suggestions = [
+            "base".to_string(),
+            "checkpoint".to_string(),
+            format!("origin/{}", base),
+        ];
+        let next = suggestions
+            .iter()
+            .position(|s| s == input)
+            .map_or(0, |i| (i + 1) % suggestions.len());
+        *input = suggestions[next].clone();
+    }
+
+    /// Reload the diff against the target typed into the compare prompt
+    pub fn submit_diff_target(&mut self) {
+        let ViewMode::Diff(ref mut diff) = self.view_mode else {
+            return;
+        };
+        let Some(input) = diff.ref_input.take() else {
+            return;
+        };
+        self.load_diff(DiffTarget::from_input(&input));
+    }
+
     /// Close the diff modal and return to dashboard view
     pub fn close_diff(&mut self) {
         self.view_mode = ViewMode::Dashboard;
@@ -1030,4 +1791,189 @@ impl App {
             let _ = tmux::send_keys(&agent.pane_id, &action);
         }
     }
+
+    /// Pin the selected agent's worktree to the top of the table, or unpin it
+    pub fn toggle_pin(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let (handle, _) = self.extract_worktree_name(agent);
+        let pinned = !self.pinned.contains(&agent.path);
+        if let Err(e) = pins::set_pinned(&agent.path, pinned) {
+            tracing::warn!(handle, error = %e, "dashboard:failed to save pin");
+            return;
+        }
+        // Refresh rather than re-sort so the selection follows the agent
+        self.refresh();
+        self.status_message = Some(if pinned {
+            format!("Pinned '{}'", handle)
+        } else {
+            format!("Unpinned '{}'", handle)
+        });
+    }
+
+    /// Queue the selected agent's worktree to merge once the agent is done,
+    /// or unqueue it if it already is
+    pub fn toggle_merge_queue(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let (handle, is_main) = self.extract_worktree_name(agent);
+        if is_main {
+            self.status_message = Some("Cannot merge the main worktree".to_string());
+            return;
+        }
+        let queued = !agent.merge_queued;
+        if let Err(e) = tmux::set_merge_queued(&agent.pane_id, queued) {
+            tracing::warn!(handle, error = %e, "dashboard:failed to queue merge");
+            return;
+        }
+
+        // The flag is per window: update every pane of it until the next refresh
+        let (session, window_name) = (agent.session.clone(), agent.window_name.clone());
+        for agent in &mut self.agents {
+            if agent.session == session && agent.window_name == window_name {
+                agent.merge_queued = queued;
+            }
+        }
+        self.status_message = Some(if queued {
+            format!("'{}' will merge when its agent is done", handle)
+        } else {
+            format!("'{}' is no longer queued to merge", handle)
+        });
+    }
+
+    /// Wake the selected agent if it is snoozed, or else ask how long to
+    /// snooze it for
+    pub fn request_snooze_selected(&mut self) {
+        let Some(agent) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.agents.get(selected))
+        else {
+            return;
+        };
+        let handle = self.extract_worktree_name(agent).0;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if agent.is_snoozed(now) {
+            let pane_id = agent.pane_id.clone();
+            if self.set_snooze(&pane_id, None) {
+                self.status_message = Some(format!("'{}' is awake", handle));
+            }
+            return;
+        }
+        self.pending_snooze = Some(PendingSnooze {
+            handle,
+            pane_id: agent.pane_id.clone(),
+        });
+    }
+
+    /// Snooze the pending agent for `secs`
+    pub fn confirm_snooze(&mut self, secs: u64) {
+        let Some(pending) = self.pending_snooze.take() else {
+            return;
+        };
+        let until = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            + secs;
+        if self.set_snooze(&pending.pane_id, Some(until)) {
+            self.status_message = Some(format!(
+                "Snoozed '{}' for {}",
+                pending.handle,
+                duration::format_duration(secs)
+            ));
+        }
+    }
+
+    /// Store the snooze on the agent's window and re-sort. Returns false if
+    /// tmux refused it.
+    fn set_snooze(&mut self, pane_id: &str, until: Option<u64>) -> bool {
+        if let Err(e) = tmux::set_snoozed_until(pane_id, until) {
+            tracing::warn!(pane_id, error = %e, "dashboard:failed to snooze");
+            return false;
+        }
+
+        // The option is per window: update every pane of it until the next refresh
+        let Some(window) = self
+            .agents
+            .iter()
+            .find(|a| a.pane_id == pane_id)
+            .map(|a| (a.session.clone(), a.window_name.clone()))
+        else {
+            return true;
+        };
+        for agent in &mut self.agents {
+            if (&agent.session, &agent.window_name) == (&window.0, &window.1) {
+                agent.snoozed_until = until;
+            }
+        }
+        self.sort_agents();
+        // Keep the same agent selected after it moves
+        self.restore_selection();
+        true
+    }
+}
+
+/// The row to select once the rows are `keys`: the one keyed `key` when it is
+/// still there, otherwise `selected` kept in bounds. Also returns whether the
+/// keyed row was found.
+fn follow_selection(
+    keys: &[Option<String>],
+    key: Option<&str>,
+    selected: Option<usize>,
+) -> (Option<usize>, bool) {
+    if let Some(key) = key
+        && let Some(idx) = keys.iter().position(|k| k.as_deref() == Some(key))
+    {
+        return (Some(idx), true);
+    }
+    let selected = match keys.len() {
+        0 => None,
+        len => selected.map(|idx| idx.min(len - 1)),
+    };
+    (selected, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(keys: &[&str]) -> Vec<Option<String>> {
+        keys.iter().map(|k| Some(k.to_string())).collect()
+    }
+
+    #[test]
+    fn selection_follows_the_agent_when_rows_reorder() {
+        assert_eq!(
+            follow_selection(&keys(&["%3", "%1", "%2"]), Some("%1"), Some(0)),
+            (Some(1), true)
+        );
+    }
+
+    #[test]
+    fn selection_stays_in_bounds_when_the_agent_is_gone() {
+        assert_eq!(
+            follow_selection(&keys(&["%1", "%2"]), Some("%9"), Some(1)),
+            (Some(1), false)
+        );
+        assert_eq!(
+            follow_selection(&keys(&["%1"]), Some("%9"), Some(3)),
+            (Some(0), false)
+        );
+        assert_eq!(follow_selection(&[], Some("%9"), Some(0)), (None, false));
+        assert_eq!(follow_selection(&keys(&["%1"]), None, None), (None, false));
+    }
 }
@@ -1,6 +1,7 @@
 //! Diff domain types and helper functions.
 
 use ratatui::text::Line;
+use std::ops::Range;
 use std::path::PathBuf;
 
 use super::ansi::{parse_ansi_to_lines, strip_ansi_escapes};
@@ -188,6 +189,31 @@ impl DiffHunk {
     }
 }
 
+/// What the diff view compares the worktree against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffTarget {
+    /// Uncommitted changes (`git diff HEAD`)
+    Uncommitted,
+    /// Commits since the base branch (`base...HEAD`)
+    Base,
+    /// Changes since the commit last marked as reviewed
+    Checkpoint,
+    /// Commits since any ref typed into the prompt (e.g. `origin/main`)
+    Ref(String),
+}
+
+impl DiffTarget {
+    /// Parse the compare prompt: `base` and `checkpoint` are keywords, anything
+    /// else is taken as a ref. Empty input means the base branch.
+    pub fn from_input(input: &str) -> Self {
+        match input.trim() {
+            "" | "base" => DiffTarget::Base,
+            "checkpoint" => DiffTarget::Checkpoint,
+            other => DiffTarget::Ref(other.to_string()),
+        }
+    }
+}
+
 /// State for the diff view
 #[derive(Debug, PartialEq)]
 pub struct DiffView {
@@ -207,8 +233,8 @@ pub struct DiffView {
     pub worktree_path: PathBuf,
     /// Pane ID for sending commands to agent
     pub pane_id: String,
-    /// Whether this is a branch diff (true) or uncommitted diff (false)
-    pub is_branch_diff: bool,
+    /// What the worktree is compared against
+    pub target: DiffTarget,
     /// Number of lines added in the diff
     pub lines_added: usize,
     /// Number of lines removed in the diff
@@ -227,19 +253,125 @@ pub struct DiffView {
     pub staged_hunks: Vec<DiffHunk>,
     /// Comment input buffer (Some = comment mode active)
     pub comment_input: Option<String>,
+    /// Whether the comment being typed is added to the review instead of
+    /// being sent to the agent right away
+    pub comment_to_review: bool,
+    /// Compare target input buffer (Some = prompt open)
+    pub ref_input: Option<String>,
     /// List of files in the diff for the sidebar
     pub file_list: Vec<FileEntry>,
+    /// Index in `file_list` of the file shown on its own, if any
+    pub focused_file: Option<usize>,
 }
 
 impl DiffView {
+    /// Index in `file_list` of the file currently visible, based on the scroll
+    /// position or, in patch mode, the current hunk
+    pub fn current_file_index(&self) -> Option<usize> {
+        if self.file_list.is_empty() {
+            return None;
+        }
+
+        // In patch mode, use the current hunk's filename
+        if self.patch_mode && !self.hunks.is_empty() {
+            let current_filename = &self.hunks[self.current_hunk].filename;
+            return self
+                .file_list
+                .iter()
+                .position(|f| &f.filename == current_filename);
+        }
+
+        // Find the last file whose start_line is <= current scroll position
+        let mut current_idx = 0;
+        for (idx, file) in self.file_list.iter().enumerate() {
+            if file.start_line <= self.scroll {
+                current_idx = idx;
+            } else {
+                break;
+            }
+        }
+        Some(current_idx)
+    }
+
+    /// Whether this shows committed changes rather than uncommitted ones
+    pub fn is_branch_diff(&self) -> bool {
+        self.target != DiffTarget::Uncommitted
+    }
+
+    /// Lines of `parsed_lines` in the file at `idx`: from its header to the
+    /// next file's
+    fn file_range(&self, idx: usize) -> Option<Range<usize>> {
+        let file = self.file_list.get(idx)?;
+        let end = self
+            .file_list
+            .get(idx + 1)
+            .map_or(self.line_count, |next| next.start_line);
+        Some(file.start_line..end.max(file.start_line))
+    }
+
+    /// Lines that can be scrolled through: the focused file's, or the whole
+    /// diff (in patch mode, the scroll is within the current hunk)
+    pub fn visible_range(&self) -> Range<usize> {
+        if self.patch_mode {
+            return 0..self.line_count;
+        }
+        self.focused_file
+            .and_then(|idx| self.file_range(idx))
+            .unwrap_or(0..self.line_count)
+    }
+
+    /// Jump to the header of the next file (`forward`) or the previous one.
+    /// With a file focused, the next file is shown on its own instead.
+    pub fn jump_file(&mut self, forward: bool) {
+        let Some(current) = self.current_file_index() else {
+            return;
+        };
+        let target = if forward {
+            current + 1
+        } else if self
+            .file_range(current)
+            .is_some_and(|range| self.scroll > range.start)
+        {
+            // Back to the top of the current file first, like `[[` in vim
+            current
+        } else {
+            current.saturating_sub(1)
+        };
+        let Some(file) = self.file_list.get(target) else {
+            return;
+        };
+        self.scroll = file.start_line;
+        if self.focused_file.is_some() {
+            self.focused_file = Some(target);
+        }
+    }
+
+    /// Show only the file currently in view, or the whole diff again
+    pub fn toggle_file_focus(&mut self) {
+        if self.patch_mode {
+            return;
+        }
+        self.focused_file = match self.focused_file {
+            Some(_) => None,
+            None => self.current_file_index(),
+        };
+        let range = self.visible_range();
+        self.scroll = self.scroll.clamp(range.start, range.end.saturating_sub(1));
+    }
+
     pub fn scroll_up(&mut self) {
-        self.scroll = self.scroll.saturating_sub(1);
+        self.scroll = self
+            .scroll
+            .saturating_sub(1)
+            .max(self.visible_range().start);
     }
 
     pub fn scroll_down(&mut self) {
-        let max_scroll = self
-            .line_count
-            .saturating_sub(self.viewport_height as usize);
+        let range = self.visible_range();
+        let max_scroll = range
+            .end
+            .saturating_sub(self.viewport_height as usize)
+            .max(range.start);
         if self.scroll < max_scroll {
             self.scroll += 1;
         }
@@ -247,19 +379,24 @@ impl DiffView {
 
     pub fn scroll_page_up(&mut self) {
         let page = self.viewport_height as usize;
-        self.scroll = self.scroll.saturating_sub(page);
+        self.scroll = self
+            .scroll
+            .saturating_sub(page)
+            .max(self.visible_range().start);
     }
 
     pub fn scroll_page_down(&mut self) {
         let page = self.viewport_height as usize;
         // In patch mode, use current hunk's line count; otherwise use full diff
-        let effective_line_count = if self.patch_mode && !self.hunks.is_empty() {
-            self.hunks[self.current_hunk].parsed_lines.len()
+        let (start, end) = if self.patch_mode && !self.hunks.is_empty() {
+            (0, self.hunks[self.current_hunk].parsed_lines.len())
         } else {
-            self.line_count
+            let range = self.visible_range();
+            (range.start, range.end)
         };
-        let max_scroll = effective_line_count.saturating_sub(self.viewport_height as usize);
-        self.scroll = (self.scroll + page).min(max_scroll);
+        let max_scroll = end.saturating_sub(self.viewport_height as usize).max(start);
+        // A file jump may have scrolled past the end; paging never goes back
+        self.scroll = (self.scroll + page).min(max_scroll.max(self.scroll));
     }
 }
 
@@ -773,6 +910,20 @@ mod tests {
         assert_eq!(parse_hunk_header("invalid"), None);
     }
 
+    #[test]
+    fn test_diff_target_from_input() {
+        assert_eq!(DiffTarget::from_input(""), DiffTarget::Base);
+        assert_eq!(DiffTarget::from_input("base"), DiffTarget::Base);
+        assert_eq!(
+            DiffTarget::from_input(" checkpoint "),
+            DiffTarget::Checkpoint
+        );
+        assert_eq!(
+            DiffTarget::from_input("origin/main"),
+            DiffTarget::Ref("origin/main".to_string())
+        );
+    }
+
     #[test]
     fn test_count_hunk_stats() {
         let hunk = "@@ -1,3 +1,4 @@\n context\n+added\n-removed\n context";
@@ -907,4 +1058,90 @@ mod tests {
         assert_eq!(files[0].start_line, 0);
         assert_eq!(files[1].start_line, 3);
     }
+
+    fn view_with_files(starts: &[usize], line_count: usize) -> DiffView {
+        DiffView {
+            content: String::new(),
+            parsed_lines: vec![Line::from(""); line_count],
+            scroll: 0,
+            line_count,
+            viewport_height: 5,
+            title: String::new(),
+            worktree_path: PathBuf::new(),
+            pane_id: String::new(),
+            target: DiffTarget::Uncommitted,
+            lines_added: 0,
+            lines_removed: 0,
+            patch_mode: false,
+            hunks: Vec::new(),
+            current_hunk: 0,
+            hunks_total: 0,
+            hunks_processed: 0,
+            staged_hunks: Vec::new(),
+            comment_input: None,
+            comment_to_review: false,
+            ref_input: None,
+            file_list: starts
+                .iter()
+                .enumerate()
+                .map(|(i, &start_line)| FileEntry {
+                    filename: format!("file{}.rs", i),
+                    lines_added: 1,
+                    lines_removed: 0,
+                    start_line,
+                    is_new: false,
+                })
+                .collect(),
+            focused_file: None,
+        }
+    }
+
+    #[test]
+    fn test_jump_file() {
+        let mut diff = view_with_files(&[0, 10, 40], 45);
+        diff.jump_file(true);
+        assert_eq!(diff.scroll, 10);
+        // The last file starts past the last full page; jumping still shows its header
+        diff.jump_file(true);
+        assert_eq!(diff.scroll, 40);
+        diff.jump_file(true);
+        assert_eq!(diff.scroll, 40);
+        diff.scroll_page_down();
+        assert_eq!(diff.scroll, 40);
+
+        // Back to the top of the current file first, then to the previous one
+        diff.scroll = 15;
+        diff.jump_file(false);
+        assert_eq!(diff.scroll, 10);
+        diff.jump_file(false);
+        assert_eq!(diff.scroll, 0);
+    }
+
+    #[test]
+    fn test_file_focus_limits_scrolling() {
+        let mut diff = view_with_files(&[0, 10, 40], 45);
+        diff.scroll = 12;
+        diff.toggle_file_focus();
+        assert_eq!(diff.focused_file, Some(1));
+        assert_eq!(diff.visible_range(), 10..40);
+
+        diff.scroll_page_down();
+        diff.scroll_page_down();
+        diff.scroll_page_down();
+        diff.scroll_page_down();
+        diff.scroll_page_down();
+        assert_eq!(diff.scroll, 35);
+        diff.scroll = 10;
+        diff.scroll_up();
+        assert_eq!(diff.scroll, 10);
+
+        // Moving on keeps showing one file at a time
+        diff.jump_file(true);
+        assert_eq!(diff.focused_file, Some(2));
+        assert_eq!(diff.visible_range(), 40..45);
+
+        diff.toggle_file_focus();
+        assert_eq!(diff.focused_file, None);
+        assert_eq!(diff.visible_range(), 0..45);
+    }
 }
@@ -3,15 +3,23 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::actions::Action;
+use super::app::{SNOOZE_DURATIONS, StatusFilter};
 
 /// Context for key handling - determines which keymap is active.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Context {
     DashboardNormal,
     DashboardInput,
+    Composer,
+    Filter,
+    PreviewSearch,
+    ConfirmRemove,
+    Snooze,
     DiffNormal,
+    CompareInput,
     Patch,
     Comment,
+    Log,
 }
 
 /// Map a key event to an action for the given context.
@@ -19,9 +27,16 @@ pub fn action_for_key(ctx: Context, key: KeyEvent) -> Option<Action> {
     match ctx {
         Context::DashboardNormal => dashboard_normal_key(key),
         Context::DashboardInput => dashboard_input_key(key),
+        Context::Composer => composer_key(key),
+        Context::Filter => filter_key(key),
+        Context::PreviewSearch => preview_search_key(key),
+        Context::ConfirmRemove => confirm_remove_key(key),
+        Context::Snooze => snooze_key(key),
         Context::DiffNormal => diff_normal_key(key),
+        Context::CompareInput => compare_input_key(key),
         Context::Patch => patch_key(key),
         Context::Comment => comment_key(key),
+        Context::Log => log_key(key),
     }
 }
 
@@ -34,9 +49,23 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('k') | KeyCode::Up => Some(Action::Previous),
         KeyCode::Enter => Some(Action::JumpToSelected),
         KeyCode::Char('p') => Some(Action::PeekSelected),
+        KeyCode::Char('o') => Some(Action::ReopenSelected),
+        KeyCode::Char('x') => Some(Action::RemoveSelected),
         KeyCode::Char('s') => Some(Action::CycleSortMode),
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::StartPreviewSearch)
+        }
         KeyCode::Char('f') => Some(Action::ToggleStaleFilter),
+        KeyCode::Char('w') => Some(Action::SetStatusFilter(StatusFilter::Waiting)),
+        KeyCode::Char('W') => Some(Action::SetStatusFilter(StatusFilter::Working)),
+        KeyCode::Char('D') => Some(Action::SetStatusFilter(StatusFilter::Done)),
+        KeyCode::Char('A') => Some(Action::SetStatusFilter(StatusFilter::All)),
+        KeyCode::Char(' ') => Some(Action::TogglePause),
         KeyCode::Char('i') => Some(Action::EnterInputMode),
+        KeyCode::Char('P') => Some(Action::OpenComposer),
+        KeyCode::Char('/') => Some(Action::StartFilter),
+        KeyCode::Char('n') => Some(Action::NextPreviewMatch),
+        KeyCode::Char('N') => Some(Action::PrevPreviewMatch),
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             Some(Action::ScrollPreviewUp)
         }
@@ -46,8 +75,12 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('+') | KeyCode::Char('=') => Some(Action::IncreasePreviewSize),
         KeyCode::Char('-') | KeyCode::Char('_') => Some(Action::DecreasePreviewSize),
         KeyCode::Char('d') => Some(Action::LoadWipDiff),
+        KeyCode::Char('l') => Some(Action::OpenLog),
         KeyCode::Char('c') => Some(Action::SendCommitDashboard),
         KeyCode::Char('m') => Some(Action::TriggerMergeDashboard),
+        KeyCode::Char('M') => Some(Action::ToggleMergeQueue),
+        KeyCode::Char('*') => Some(Action::TogglePin),
+        KeyCode::Char('z') => Some(Action::RequestSnooze),
         KeyCode::Char(c @ '1'..='9') => Some(Action::JumpToIndex((c as u8 - b'1') as usize)),
         _ => None,
     }
@@ -68,6 +101,73 @@ fn dashboard_input_key(key: KeyEvent) -> Option<Action> {
     }
 }
 
+fn composer_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelComposer),
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+            Some(Action::ComposerNewline)
+        }
+        // For terminals that don't report Alt+Enter
+        KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ComposerNewline)
+        }
+        KeyCode::Enter => Some(Action::SendComposer),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::CancelComposer)
+        }
+        KeyCode::Backspace => Some(Action::ComposerDeleteChar),
+        KeyCode::Char(c) => Some(Action::ComposerAppendChar(c)),
+        _ => None,
+    }
+}
+
+fn filter_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::ClearFilter),
+        KeyCode::Enter => Some(Action::ApplyFilter),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Down => Some(Action::Next),
+        KeyCode::Up => Some(Action::Previous),
+        KeyCode::Backspace => Some(Action::FilterDeleteChar),
+        KeyCode::Char(c) => Some(Action::FilterAppendChar(c)),
+        _ => None,
+    }
+}
+
+fn preview_search_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::ClearPreviewSearch),
+        KeyCode::Enter => Some(Action::KeepPreviewSearch),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Backspace => Some(Action::PreviewSearchDeleteChar),
+        KeyCode::Char(c) => Some(Action::PreviewSearchAppendChar(c)),
+        _ => None,
+    }
+}
+
+fn confirm_remove_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('y') => Some(Action::ConfirmRemove { force: false }),
+        KeyCode::Char('F') => Some(Action::ConfirmRemove { force: true }),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        // Anything else cancels, so a stray key never removes a worktree
+        _ => Some(Action::CancelRemove),
+    }
+}
+
+fn snooze_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Char(c) => Some(
+            SNOOZE_DURATIONS
+                .iter()
+                .find(|(key, _, _)| *key == c)
+                .map_or(Action::CancelSnooze, |(_, _, secs)| Action::Snooze(*secs)),
+        ),
+        _ => Some(Action::CancelSnooze),
+    }
+}
+
 fn diff_normal_key(key: KeyEvent) -> Option<Action> {
     match key.code {
         KeyCode::Char('?') => Some(Action::ShowHelp),
@@ -83,10 +183,29 @@ fn diff_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             Some(Action::ScrollPageUp)
         }
+        KeyCode::Char('n') => Some(Action::NextFile),
+        KeyCode::Char('p') => Some(Action::PrevFile),
+        KeyCode::Char('f') => Some(Action::ToggleFileFocus),
         KeyCode::Tab => Some(Action::ToggleDiffType),
         KeyCode::Char('a') => Some(Action::EnterPatchMode),
         KeyCode::Char('c') => Some(Action::SendCommitDiff),
         KeyCode::Char('m') => Some(Action::TriggerMergeDiff),
+        KeyCode::Char('O') => Some(Action::StartReviewComment),
+        KeyCode::Char('S') => Some(Action::SendReview),
+        KeyCode::Char('r') => Some(Action::StartCompareInput),
+        KeyCode::Char('R') => Some(Action::MarkReviewed),
+        _ => None,
+    }
+}
+
+fn compare_input_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelCompare),
+        KeyCode::Enter => Some(Action::SubmitCompare),
+        KeyCode::Tab => Some(Action::CycleCompareSuggestion),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Backspace => Some(Action::CompareDeleteChar),
+        KeyCode::Char(c) => Some(Action::CompareAppendChar(c)),
         _ => None,
     }
 }
@@ -106,6 +225,8 @@ fn patch_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('u') => Some(Action::UndoStagedHunk),
         KeyCode::Char('s') => Some(Action::SplitHunk),
         KeyCode::Char('o') => Some(Action::StartComment),
+        KeyCode::Char('O') => Some(Action::StartReviewComment),
+        KeyCode::Char('S') => Some(Action::SendReview),
         KeyCode::Char('k') | KeyCode::Up => Some(Action::PrevHunk),
         KeyCode::Char('j') | KeyCode::Down => Some(Action::NextHunk),
         KeyCode::Char('c') => Some(Action::SendCommitDiff),
@@ -115,6 +236,25 @@ fn patch_key(key: KeyEvent) -> Option<Action> {
     }
 }
 
+fn log_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('?') => Some(Action::ShowHelp),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('l') => Some(Action::CloseLog),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Char('j') | KeyCode::Down => Some(Action::LogScrollDown),
+        KeyCode::Char('k') | KeyCode::Up => Some(Action::LogScrollUp),
+        KeyCode::PageDown => Some(Action::LogPageDown),
+        KeyCode::PageUp => Some(Action::LogPageUp),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::LogPageDown)
+        }
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::LogPageUp)
+        }
+        _ => None,
+    }
+}
+
 fn comment_key(key: KeyEvent) -> Option<Action> {
     match key.code {
         KeyCode::Esc => Some(Action::CancelComment),
@@ -134,34 +274,90 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("j/k", "Navigate up/down"),
             ("Enter", "Jump to agent"),
             ("p", "Peek agent (keep popup)"),
+            ("o", "Reopen offline worktree"),
+            ("x", "Remove worktree"),
             ("s", "Cycle sort mode"),
             ("f", "Toggle stale filter"),
+            ("w/W/D", "Only waiting/working/done"),
+            ("A", "All statuses"),
+            ("Space", "Pause/resume refreshing"),
             ("i", "Enter input mode"),
+            ("P", "Compose prompt for agent"),
+            ("/", "Filter agents"),
+            ("Ctrl+f", "Search preview"),
+            ("n/N", "Older/newer match"),
             ("Ctrl+u/d", "Scroll preview"),
             ("+/-", "Resize preview"),
             ("d", "View diff"),
+            ("l", "View commit log"),
             ("c", "Commit changes"),
             ("m", "Merge branch"),
+            ("M", "Merge when agent is done"),
+            ("*", "Pin to top (toggle)"),
+            ("z", "Snooze / wake agent"),
             ("1-9", "Quick jump"),
         ],
         Context::DashboardInput => vec![("Esc", "Exit input mode"), ("<keys>", "Send to agent")],
+        Context::Composer => vec![
+            ("<type>", "Write prompt"),
+            ("Alt+Enter", "New line"),
+            ("Enter", "Send to agent"),
+            ("Esc", "Cancel"),
+        ],
+        Context::Filter => vec![
+            ("<type>", "Filter by project, worktree, title or status"),
+            ("Up/Down", "Navigate"),
+            ("Enter", "Keep filter"),
+            ("Esc", "Clear filter"),
+        ],
+        Context::PreviewSearch => vec![
+            ("<type>", "Search preview output"),
+            ("Enter", "Keep search (n/N to move)"),
+            ("Esc", "Close search"),
+        ],
+        Context::ConfirmRemove => vec![
+            ("y", "Remove worktree"),
+            ("F", "Force remove (discard changes)"),
+            ("<other>", "Cancel"),
+        ],
+        Context::Snooze => vec![
+            ("1", "Snooze for 30 minutes"),
+            ("2", "Snooze for 1 hour"),
+            ("3", "Snooze for 4 hours"),
+            ("4", "Snooze for 1 day"),
+            ("<other>", "Cancel"),
+        ],
         Context::DiffNormal => vec![
             ("?", "Show help"),
             ("q/Esc", "Close diff"),
             ("j/k", "Scroll line"),
             ("Ctrl+d/u", "Scroll page"),
+            ("n/p", "Next/prev file"),
+            ("f", "Show only this file"),
             ("Tab", "Toggle WIP/Review"),
+            ("O", "Add review comment"),
+            ("S", "Send review to agent"),
+            ("r", "Compare against ref"),
+            ("R", "Mark as reviewed"),
             ("a", "Enter patch mode (WIP only)"),
             ("c", "Commit changes"),
             ("m", "Merge branch"),
         ],
+        Context::CompareInput => vec![
+            ("<type>", "Ref, base or checkpoint"),
+            ("Tab", "Cycle suggestions"),
+            ("Enter", "Show diff"),
+            ("Esc", "Cancel"),
+        ],
         Context::Patch => vec![
             ("?", "Show help"),
             ("y", "Stage hunk"),
             ("n", "Skip hunk"),
             ("u", "Undo last staged"),
             ("s", "Split hunk"),
-            ("o", "Add comment"),
+            ("o", "Send comment on hunk"),
+            ("O", "Add review comment"),
+            ("S", "Send review to agent"),
             ("j/k", "Next/prev hunk"),
             ("Ctrl+d/u", "Scroll hunk"),
             ("c", "Commit changes"),
@@ -170,9 +366,15 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
         ],
         Context::Comment => vec![
             ("Esc", "Cancel"),
-            ("Enter", "Send comment"),
+            ("Enter", "Send or add comment"),
             ("<type>", "Input text"),
         ],
+        Context::Log => vec![
+            ("?", "Show help"),
+            ("q/Esc/l", "Close log"),
+            ("j/k", "Scroll line"),
+            ("Ctrl+d/u", "Scroll page"),
+        ],
     }
 }
 
@@ -184,9 +386,16 @@ mod tests {
     fn test_each_context_has_help_rows() {
         assert!(!help_rows(Context::DashboardNormal).is_empty());
         assert!(!help_rows(Context::DashboardInput).is_empty());
+        assert!(!help_rows(Context::Composer).is_empty());
+        assert!(!help_rows(Context::Filter).is_empty());
+        assert!(!help_rows(Context::PreviewSearch).is_empty());
+        assert!(!help_rows(Context::ConfirmRemove).is_empty());
+        assert!(!help_rows(Context::Snooze).is_empty());
         assert!(!help_rows(Context::DiffNormal).is_empty());
+        assert!(!help_rows(Context::CompareInput).is_empty());
         assert!(!help_rows(Context::Patch).is_empty());
         assert!(!help_rows(Context::Comment).is_empty());
+        assert!(!help_rows(Context::Log).is_empty());
     }
 
     #[test]
@@ -194,9 +403,16 @@ mod tests {
         for ctx in [
             Context::DashboardNormal,
             Context::DashboardInput,
+            Context::Composer,
+            Context::Filter,
+            Context::PreviewSearch,
+            Context::ConfirmRemove,
+            Context::Snooze,
             Context::DiffNormal,
+            Context::CompareInput,
             Context::Patch,
             Context::Comment,
+            Context::Log,
         ] {
             let rows = help_rows(ctx);
             let keys: Vec<_> = rows.iter().map(|(k, _)| *k).collect();
@@ -253,4 +469,171 @@ mod tests {
             Some(Action::StageAndNext)
         );
     }
+
+    #[test]
+    fn test_confirm_remove_cancels_on_other_keys() {
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        let force = KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT);
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+        let x = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::ConfirmRemove, y),
+            Some(Action::ConfirmRemove { force: false })
+        );
+        assert_eq!(
+            action_for_key(Context::ConfirmRemove, force),
+            Some(Action::ConfirmRemove { force: true })
+        );
+        assert_eq!(
+            action_for_key(Context::ConfirmRemove, n),
+            Some(Action::CancelRemove)
+        );
+        // Pressing the remove key again must not confirm
+        assert_eq!(
+            action_for_key(Context::ConfirmRemove, x),
+            Some(Action::CancelRemove)
+        );
+    }
+
+    #[test]
+    fn test_status_filter_keys() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('w')),
+            Some(Action::SetStatusFilter(StatusFilter::Waiting))
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('W')),
+            Some(Action::SetStatusFilter(StatusFilter::Working))
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('D')),
+            Some(Action::SetStatusFilter(StatusFilter::Done))
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('A')),
+            Some(Action::SetStatusFilter(StatusFilter::All))
+        );
+    }
+
+    #[test]
+    fn test_filter_keys() {
+        let slash = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, slash),
+            Some(Action::StartFilter)
+        );
+        // Typed keys go to the query instead of triggering commands
+        assert_eq!(
+            action_for_key(Context::Filter, q),
+            Some(Action::FilterAppendChar('q'))
+        );
+        assert_eq!(
+            action_for_key(Context::Filter, esc),
+            Some(Action::ClearFilter)
+        );
+    }
+
+    #[test]
+    fn test_preview_search_keys() {
+        let ctrl_f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        let f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+        let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, ctrl_f),
+            Some(Action::StartPreviewSearch)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, f),
+            Some(Action::ToggleStaleFilter)
+        );
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, n),
+            Some(Action::NextPreviewMatch)
+        );
+        assert_eq!(
+            action_for_key(Context::PreviewSearch, n),
+            Some(Action::PreviewSearchAppendChar('n'))
+        );
+    }
+
+    #[test]
+    fn test_pin_key() {
+        let star = KeyEvent::new(KeyCode::Char('*'), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, star),
+            Some(Action::TogglePin)
+        );
+    }
+
+    #[test]
+    fn test_snooze_keys() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, key('z')),
+            Some(Action::RequestSnooze)
+        );
+        assert_eq!(
+            action_for_key(Context::Snooze, key('2')),
+            Some(Action::Snooze(60 * 60))
+        );
+        // Anything else cancels
+        assert_eq!(
+            action_for_key(Context::Snooze, key('z')),
+            Some(Action::CancelSnooze)
+        );
+        assert_eq!(
+            action_for_key(
+                Context::Snooze,
+                KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)
+            ),
+            Some(Action::CancelSnooze)
+        );
+    }
+
+    #[test]
+    fn test_compare_input_keys() {
+        let r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::DiffNormal, r),
+            Some(Action::StartCompareInput)
+        );
+        // Typed keys go to the ref instead of closing the diff
+        assert_eq!(
+            action_for_key(Context::CompareInput, q),
+            Some(Action::CompareAppendChar('q'))
+        );
+        assert_eq!(
+            action_for_key(Context::CompareInput, tab),
+            Some(Action::CycleCompareSuggestion)
+        );
+    }
+
+    #[test]
+    fn test_composer_keys() {
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::Composer, enter),
+            Some(Action::SendComposer)
+        );
+        assert_eq!(
+            action_for_key(Context::Composer, alt_enter),
+            Some(Action::ComposerNewline)
+        );
+        assert_eq!(
+            action_for_key(Context::Composer, q),
+            Some(Action::ComposerAppendChar('q'))
+        );
+    }
 }
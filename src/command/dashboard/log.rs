@@ -0,0 +1,98 @@
+//! Commit log modal: the commits a worktree has on top of its base branch.
+
+use ratatui::text::Line;
+use std::path::Path;
+
+use super::ansi::parse_ansi_to_lines;
+
+/// State of the commit log modal
+pub struct LogView {
+    /// Shown in the modal's border, e.g. "fix-auth → main"
+    pub title: String,
+    pub lines: Vec<Line<'static>>,
+    pub scroll: usize,
+    /// Height of the visible area (updated during rendering)
+    pub viewport_height: usize,
+}
+
+impl LogView {
+    pub fn new(title: String, content: &str) -> Self {
+        Self {
+            title,
+            lines: parse_ansi_to_lines(content.trim_end()),
+            scroll: 0,
+            viewport_height: 0,
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(self.viewport_height)
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.max_scroll());
+    }
+
+    /// Scroll by most of a page, keeping a couple of lines for context
+    pub fn page_by(&mut self, pages: isize) {
+        let page = self.viewport_height.saturating_sub(2).max(1) as isize;
+        self.scroll_by(pages * page);
+    }
+}
+
+/// `git log --oneline --graph base..HEAD` in color, or a message saying why
+/// there is nothing to show
+pub fn load_log(path: &Path, base: &str) -> String {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args([
+            "--no-pager",
+            "log",
+            "--oneline",
+            "--graph",
+            "--decorate",
+            "--color=always",
+        ])
+        .arg(format!("{}..HEAD", base))
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let log = String::from_utf8_lossy(&output.stdout).into_owned();
+            if log.trim().is_empty() {
+                format!("No commits on top of {} yet", base)
+            } else {
+                log
+            }
+        }
+        Ok(output) => format!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => format!("Error running git log: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_stays_within_content() {
+        let content: String = (0..30).map(|i| format!("* commit {}\n", i)).collect();
+        let mut log = LogView::new("test".to_string(), &content);
+        log.viewport_height = 10;
+
+        log.scroll_by(-5);
+        assert_eq!(log.scroll, 0);
+        log.page_by(1);
+        assert_eq!(log.scroll, 8);
+        log.page_by(5);
+        assert_eq!(log.scroll, 20);
+        log.scroll_by(-1);
+        assert_eq!(log.scroll, 19);
+    }
+}
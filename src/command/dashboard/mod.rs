@@ -14,21 +14,28 @@
 //! - `ansi`: ANSI escape sequence parsing and stripping
 //! - `diff`: Diff domain types and helper functions
 //! - `keymap`: Key-to-action mapping per context with help text
+//! - `plain`: Line-based alternative for screen readers (`--a11y`)
+//! - `review`: Review comments collected in the diff view
 //! - `settings`: Tmux-persisted dashboard settings
 //! - `sort`: Sort mode enum and tmux persistence
 //! - `spinner`: Spinner animation constants
 //! - `ui/`: TUI rendering modules
+//!   - `composer`: Prompt composer overlay
 //!   - `dashboard`: Table, preview, and footer
 //!   - `diff`: Normal diff, patch mode, file list
 //!   - `format`: Git status formatting
 //!   - `help`: Help overlay
 
 mod actions;
-mod agent;
-mod ansi;
+pub(crate) mod agent;
+pub(crate) mod ansi;
 mod app;
 mod diff;
 mod keymap;
+mod log;
+mod plain;
+mod review;
+mod search;
 mod settings;
 mod sort;
 mod spinner;
@@ -57,19 +64,31 @@ use self::ui::ui;
 fn get_context(app: &App) -> Context {
     match &app.view_mode {
         ViewMode::Dashboard => {
-            if app.input_mode {
+            if app.log_view.is_some() {
+                Context::Log
+            } else if app.pending_remove.is_some() {
+                Context::ConfirmRemove
+            } else if app.pending_snooze.is_some() {
+                Context::Snooze
+            } else if app.composer.is_some() {
+                Context::Composer
+            } else if app.input_mode {
                 Context::DashboardInput
+            } else if app.filter_input {
+                Context::Filter
+            } else if app.preview_search.as_ref().is_some_and(|s| s.input) {
+                Context::PreviewSearch
             } else {
                 Context::DashboardNormal
             }
         }
         ViewMode::Diff(diff) => {
-            if diff.patch_mode {
-                if diff.comment_input.is_some() {
-                    Context::Comment
-                } else {
-                    Context::Patch
-                }
+            if diff.comment_input.is_some() {
+                Context::Comment
+            } else if diff.patch_mode {
+                Context::Patch
+            } else if diff.ref_input.is_some() {
+                Context::CompareInput
             } else {
                 Context::DiffNormal
             }
@@ -79,6 +98,14 @@ fn get_context(app: &App) -> Context {
 
 /// Handle mouse events for diff view scrolling.
 fn handle_mouse_event(app: &mut App, kind: MouseEventKind) {
+    if let Some(ref mut log) = app.log_view {
+        match kind {
+            MouseEventKind::ScrollUp => log.scroll_by(-3),
+            MouseEventKind::ScrollDown => log.scroll_by(3),
+            _ => {}
+        }
+        return;
+    }
     if let ViewMode::Diff(ref mut diff_view) = app.view_mode {
         let total_lines = if diff_view.patch_mode {
             diff_view
@@ -103,13 +130,16 @@ fn handle_mouse_event(app: &mut App, kind: MouseEventKind) {
     }
 }
 
-pub fn run(cli_preview_size: Option<u8>) -> Result<()> {
-    // Check if tmux is running
-    if !tmux::is_running().unwrap_or(false) {
-        println!("No tmux server running.");
-        return Ok(());
+pub fn run(cli_preview_size: Option<u8>, a11y: bool) -> Result<()> {
+    tmux::ensure_running()?;
+
+    if crate::a11y::enabled(a11y) {
+        return plain::run();
     }
 
+    // Print config warnings while the screen is still ours to print on
+    let _ = crate::config::Config::load(None);
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -128,11 +158,11 @@ pub fn run(cli_preview_size: Option<u8>) -> Result<()> {
     // Main loop
     let tick_rate = Duration::from_millis(250);
     let mut last_tick = std::time::Instant::now();
-    let refresh_interval = Duration::from_secs(2);
+    let refresh_interval = app.config.dashboard.refresh_interval();
     let mut last_refresh = std::time::Instant::now();
     // Preview refreshes more frequently than the agent list
     // Use a faster refresh rate when in input mode for responsive typing feedback
-    let preview_refresh_interval_normal = Duration::from_millis(500);
+    let preview_refresh_interval_normal = app.config.dashboard.preview_refresh_interval();
     let preview_refresh_interval_input = Duration::from_millis(100);
     let mut last_preview_refresh = std::time::Instant::now();
 
@@ -165,6 +195,9 @@ pub fn run(cli_preview_size: Option<u8>) -> Result<()> {
                 continue;
             }
 
+            // Footer messages last until the next key press
+            app.status_message = None;
+
             // Help overlay handling - close on any key if open
             if app.show_help {
                 app.show_help = false;
@@ -177,7 +210,7 @@ pub fn run(cli_preview_size: Option<u8>) -> Result<()> {
             // Special case: EnterPatchMode only works in WIP diff view (not branch diff)
             if ctx == Context::DiffNormal
                 && let ViewMode::Diff(ref diff) = app.view_mode
-                && diff.is_branch_diff
+                && diff.is_branch_diff()
             {
                 // Skip patch mode action for branch diffs
                 if let Some(actions::Action::EnterPatchMode) = action_for_key(ctx, key) {
@@ -199,15 +232,18 @@ pub fn run(cli_preview_size: Option<u8>) -> Result<()> {
             app.spinner_frame = (app.spinner_frame + 1) % SPINNER_FRAME_COUNT;
         }
 
-        // Auto-refresh agent list every 2 seconds
-        if last_refresh.elapsed() >= refresh_interval {
+        // Auto-refresh agent list (every 2 seconds by default) unless paused
+        if !app.paused && last_refresh.elapsed() >= refresh_interval {
             app.refresh();
             last_refresh = std::time::Instant::now();
         }
 
         // Auto-refresh preview more frequently for live updates
-        // Uses faster refresh rate in input mode (set at top of loop)
-        if last_preview_refresh.elapsed() >= current_preview_interval {
+        // Uses faster refresh rate in input mode (set at top of loop), which
+        // keeps going while paused so typed keys still show up
+        if (!app.paused || app.input_mode)
+            && last_preview_refresh.elapsed() >= current_preview_interval
+        {
             app.refresh_preview();
             last_preview_refresh = std::time::Instant::now();
         }
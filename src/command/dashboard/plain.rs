@@ -0,0 +1,143 @@
+//! Line-based alternative to the dashboard for screen readers (`--a11y`).
+//!
+//! Instead of redrawing a full-screen table, it prints the agents as numbered
+//! lines of labeled fields and reads commands from stdin, so everything shows
+//! up once, in order, in the terminal's normal scrollback.
+
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use crate::command::status::a11y_line;
+use crate::config::Config;
+use crate::tmux::{self, AgentPane};
+use crate::{daemon, git};
+
+use super::ansi::strip_ansi_escapes;
+
+/// Lines of pane output read by `read <n>`
+const READ_LINES: usize = 20;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Refresh,
+    Quit,
+    /// Switch to the agent with this 1-based number
+    Go(usize),
+    /// Print the recent output of the agent with this 1-based number
+    Read(usize),
+    Unknown,
+}
+
+fn parse_command(input: &str) -> Command {
+    let mut words = input.split_whitespace();
+    let (first, second) = (words.next(), words.next());
+    match (first, second) {
+        (None, _) | (Some("r" | "refresh"), None) => Command::Refresh,
+        (Some("q" | "quit" | "exit"), None) => Command::Quit,
+        (Some(n), None) => n.parse().map_or(Command::Unknown, Command::Go),
+        (Some("go" | "g"), Some(n)) => n.parse().map_or(Command::Unknown, Command::Go),
+        (Some("read" | "p"), Some(n)) => n.parse().map_or(Command::Unknown, Command::Read),
+        _ => Command::Unknown,
+    }
+}
+
+pub fn run() -> Result<()> {
+    let config = Config::load(None)?;
+    println!(
+        "workmux dashboard, accessible mode. Type a number to go to that agent, \
+         \"read\" and a number to hear its recent output, Enter to refresh, or q to quit."
+    );
+
+    let mut agents = daemon::agent_panes();
+    print_agents(&agents, &config);
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().context("Failed to flush stdout")?;
+        let mut input = String::new();
+        if stdin.lock().read_line(&mut input)? == 0 {
+            return Ok(());
+        }
+        match parse_command(&input) {
+            Command::Refresh => {
+                agents = daemon::agent_panes();
+                print_agents(&agents, &config);
+            }
+            Command::Quit => return Ok(()),
+            Command::Go(n) => match n.checked_sub(1).and_then(|i| agents.get(i)) {
+                Some(agent) => {
+                    tmux::switch_to_pane(&agent.pane_id)?;
+                    return Ok(());
+                }
+                None => println!("No agent number {}.", n),
+            },
+            Command::Read(n) => match n.checked_sub(1).and_then(|i| agents.get(i)) {
+                Some(agent) => print_output(agent),
+                None => println!("No agent number {}.", n),
+            },
+            Command::Unknown => {
+                println!("Unknown command. Type a number, \"read\" and a number, Enter, or q.")
+            }
+        }
+    }
+}
+
+fn print_agents(agents: &[AgentPane], config: &Config) {
+    if agents.is_empty() {
+        println!("No active agents.");
+        return;
+    }
+    let paths: Vec<PathBuf> = agents.iter().map(|a| a.path.clone()).collect();
+    let git_statuses = git::cached_git_statuses(&paths);
+    let now = daemon::now_secs();
+    let count = agents.len();
+    println!("{} {}.", count, if count == 1 { "agent" } else { "agents" });
+    for (i, agent) in agents.iter().enumerate() {
+        println!(
+            "{}. {}",
+            i + 1,
+            a11y_line(agent, config, &git_statuses, now)
+        );
+    }
+}
+
+/// The last non-blank lines of the agent's pane, without colors
+fn print_output(agent: &AgentPane) {
+    let Some(output) = tmux::capture_pane(&agent.pane_id, super::app::PREVIEW_LINES) else {
+        println!("Could not read the agent's output.");
+        return;
+    };
+    let text = strip_ansi_escapes(&output);
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.is_empty() {
+        println!("No output yet.");
+        return;
+    }
+    println!("Recent output:");
+    for line in &lines[lines.len().saturating_sub(READ_LINES)..] {
+        println!("{}", line);
+    }
+    println!("End of output.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command_accepts_numbers_and_words() {
+        assert_eq!(parse_command("\n"), Command::Refresh);
+        assert_eq!(parse_command("r"), Command::Refresh);
+        assert_eq!(parse_command(" q \n"), Command::Quit);
+        assert_eq!(parse_command("2"), Command::Go(2));
+        assert_eq!(parse_command("go 3"), Command::Go(3));
+        assert_eq!(parse_command("read 1"), Command::Read(1));
+        assert_eq!(parse_command("read"), Command::Unknown);
+        assert_eq!(parse_command("jump x"), Command::Unknown);
+    }
+}
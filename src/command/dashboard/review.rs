@@ -0,0 +1,109 @@
+//! Review comments collected in the diff view and sent to the agent as one
+//! markdown document.
+
+use ratatui::text::Line;
+
+use super::diff::parse_hunk_header;
+
+/// A comment left on a file or hunk while reviewing a diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewComment {
+    /// File the comment is about (None for a general comment)
+    pub file: Option<String>,
+    /// First line of the hunk in the new version of the file
+    pub line: Option<usize>,
+    /// Hunk the comment was left on, quoted in the review (patch mode only)
+    pub excerpt: Option<String>,
+    pub text: String,
+}
+
+/// Start line of the hunk shown at `scroll`, found by scanning back for its
+/// `@@` header, but not past `file_start`
+pub fn hunk_line_at(lines: &[Line], file_start: usize, scroll: usize) -> Option<usize> {
+    let end = scroll.min(lines.len().checked_sub(1)?);
+    (file_start.min(end)..=end).rev().find_map(|idx| {
+        let text: String = lines[idx]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        if text.trim_start().starts_with("@@") {
+            parse_hunk_header(&text).map(|(_, new_start)| new_start)
+        } else {
+            None
+        }
+    })
+}
+
+/// Render the comments as a prompt asking the agent to address them
+pub fn format_review(comments: &[ReviewComment]) -> String {
+    let mut out = String::from("Please address the following review comments:\n");
+    for comment in comments {
+        let location = match (&comment.file, comment.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file.clone(),
+            (None, _) => "General".to_string(),
+        };
+        out.push_str(&format!("\n## {}\n\n", location));
+        if let Some(ref excerpt) = comment.excerpt {
+            // Use a longer fence if the hunk itself contains one
+            let mut fence = "```".to_string();
+            while excerpt.contains(&fence) {
+                fence.push('`');
+            }
+            out.push_str(&format!(
+                "{}diff\n{}\n{}\n\n",
+                fence,
+                excerpt.trim_end(),
+                fence
+            ));
+        }
+        out.push_str(comment.text.trim());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_review() {
+        let comments = vec![
+            ReviewComment {
+                file: Some("src/lib.rs".to_string()),
+                line: Some(12),
+                excerpt: Some("@@ -10,2 +12,3 @@\n+let x = 1;".to_string()),
+                text: "Use a constant here".to_string(),
+            },
+            ReviewComment {
+                file: Some("README.md".to_string()),
+                line: None,
+                excerpt: None,
+                text: "Document the new flag".to_string(),
+            },
+        ];
+        let review = format_review(&comments);
+        assert!(review.starts_with("Please address the following review comments:\n"));
+        assert!(review.contains("## src/lib.rs:12\n\n```diff\n@@ -10,2 +12,3 @@\n+let x = 1;\n```\n\nUse a constant here\n"));
+        assert!(review.contains("## README.md\n\nDocument the new flag\n"));
+    }
+
+    #[test]
+    fn test_hunk_line_at() {
+        let lines = vec![
+            Line::raw("diff --git a/src/main.rs b/src/main.rs"),
+            Line::raw("@@ -1,3 +1,4 @@"),
+            Line::raw(" context"),
+            Line::raw("@@ -20,3 +21,4 @@ fn main()"),
+            Line::raw("+added"),
+        ];
+        assert_eq!(hunk_line_at(&lines, 0, 0), None);
+        assert_eq!(hunk_line_at(&lines, 0, 2), Some(1));
+        assert_eq!(hunk_line_at(&lines, 0, 4), Some(21));
+        // Doesn't look past the start of the current file
+        assert_eq!(hunk_line_at(&lines, 3, 10), Some(21));
+        assert_eq!(hunk_line_at(&[], 0, 0), None);
+    }
+}
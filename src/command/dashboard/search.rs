@@ -0,0 +1,134 @@
+//! Search in the preview pane (`Ctrl+f`).
+//!
+//! Matching ignores ASCII case and the terminal's color codes. Non-ASCII
+//! letters match exactly, which keeps byte offsets the same in the lowercased
+//! text so matches can be highlighted in place.
+
+use std::ops::Range;
+
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+use super::ansi::strip_ansi_escapes;
+
+/// Lines captured for the preview while searching it, to find older output
+pub const SEARCH_PREVIEW_LINES: u16 = 2000;
+
+/// Search in the preview pane
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreviewSearch {
+    pub query: String,
+    /// Whether the query is being typed (keys go to the query)
+    pub input: bool,
+    /// Index of the match the preview is scrolled to, oldest first
+    pub current: usize,
+}
+
+/// Byte ranges of `query` in `line`, ignoring ASCII case
+pub fn match_ranges(line: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let line = line.to_ascii_lowercase();
+    let query = query.to_ascii_lowercase();
+    line.match_indices(&query)
+        .map(|(start, m)| start..start + m.len())
+        .collect()
+}
+
+/// Indices of the lines of captured pane output that contain `query`
+pub fn matching_lines(output: &str, query: &str) -> Vec<usize> {
+    strip_ansi_escapes(output.trim_end())
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !match_ranges(line, query).is_empty())
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Restyle the parts of `line` that match `query`, keeping the colors of the
+/// rest
+pub fn highlight<'a>(line: Line<'a>, query: &str, style: Style) -> Line<'a> {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    let ranges = match_ranges(&text, query);
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in line.spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        // Cut points inside this span where a match starts or ends
+        let mut cuts = vec![0];
+        for range in &ranges {
+            for point in [range.start, range.end] {
+                if point > offset && point < end {
+                    cuts.push(point - offset);
+                }
+            }
+        }
+        cuts.push(content.len());
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        for pair in cuts.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let matched = ranges
+                .iter()
+                .any(|r| r.start <= offset + from && offset + to <= r.end);
+            let piece_style = if matched {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(content[from..to].to_string(), piece_style));
+        }
+        offset = end;
+    }
+    Line { spans, ..line }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Modifier};
+
+    #[test]
+    fn match_ranges_ignores_ascii_case() {
+        assert_eq!(
+            match_ranges("Error: error ERROR", "error"),
+            vec![0..5, 7..12, 13..18]
+        );
+        assert!(match_ranges("anything", "").is_empty());
+    }
+
+    #[test]
+    fn matching_lines_skips_color_codes() {
+        let output = "ok\n\x1b[31mfatal\x1b[0m: boom\nok\nfatal again\n\n";
+        assert_eq!(matching_lines(output, "FATAL"), vec![1, 3]);
+    }
+
+    #[test]
+    fn highlight_splits_spans_and_keeps_their_style() {
+        let red = Style::default().fg(Color::Red);
+        let line = Line::from(vec![Span::styled("an err", red), Span::raw("or here")]);
+        let mark = Style::default().add_modifier(Modifier::REVERSED);
+        let out = highlight(line, "error", mark);
+        let pieces: Vec<(&str, Style)> = out
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+        assert_eq!(
+            pieces,
+            vec![
+                ("an ", red),
+                ("err", red.patch(mark)),
+                ("or", Style::default().patch(mark)),
+                (" here", Style::default()),
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Sort mode logic for the dashboard agent list.
 
-use crate::cmd::Cmd;
+use crate::tmux;
 
 const TMUX_SORT_MODE_VAR: &str = "@workmux_sort_mode";
 
@@ -61,7 +61,7 @@ impl SortMode {
 
     /// Load sort mode from tmux global variable
     pub fn load_from_tmux() -> Self {
-        Cmd::new("tmux")
+        tmux::cmd()
             .args(&["show-option", "-gqv", TMUX_SORT_MODE_VAR])
             .run_and_capture_stdout()
             .ok()
@@ -72,7 +72,7 @@ impl SortMode {
 
     /// Save sort mode to tmux global variable
     pub fn save_to_tmux(&self) {
-        let _ = Cmd::new("tmux")
+        let _ = tmux::cmd()
             .args(&["set-option", "-g", TMUX_SORT_MODE_VAR, self.as_str()])
             .run();
     }
@@ -0,0 +1,100 @@
+//! Prompt composer overlay rendering.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Clear, Paragraph, Wrap},
+};
+
+use super::super::app::App;
+
+/// Render the prompt composer as a centered box over the dashboard.
+pub fn render_composer(f: &mut Frame, app: &App) {
+    let Some(ref input) = app.composer else {
+        return;
+    };
+    let target = app
+        .table_state
+        .selected()
+        .and_then(|selected| app.agents.get(selected))
+        .map(|agent| app.extract_worktree_name(agent).0)
+        .unwrap_or_default();
+
+    let area = f.area();
+    let width = (area.width * 7 / 10).max(40).min(area.width);
+    let height = 12.min(area.height);
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(100, 100, 120)))
+        .title(Line::from(vec![
+            Span::raw(" "),
+            Span::styled(
+                "Prompt",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+            Span::styled(target, Style::default().fg(Color::White)),
+            Span::raw(" "),
+            Span::styled(
+                if app.selected_in_main_worktree() {
+                    "⚠ main worktree "
+                } else {
+                    ""
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]))
+        .title_bottom(Line::from(vec![
+            Span::styled(" [Enter]", Style::default().fg(Color::Green)),
+            Span::raw(" send  "),
+            Span::styled("[Alt+Enter]", Style::default().fg(Color::Yellow)),
+            Span::raw(" new line  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::raw(" cancel "),
+        ]));
+
+    let cursor = Span::styled("▏", Style::default().fg(Color::White));
+    let text = if input.is_empty() {
+        Text::from(Line::from(vec![
+            cursor,
+            Span::styled(
+                "Type a prompt for the agent...",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+    } else {
+        let mut lines: Vec<Line> = input.split('\n').map(Line::raw).collect();
+        if let Some(last) = lines.last_mut() {
+            last.spans.push(cursor);
+        }
+        Text::from(lines)
+    };
+
+    // Keep the end of the text (where the cursor is) in view
+    let inner_width = popup_area.width.saturating_sub(2).max(1) as usize;
+    let inner_height = popup_area.height.saturating_sub(2) as usize;
+    let wrapped_lines: usize = input
+        .split('\n')
+        .map(|line| (line.chars().count() + 1).div_ceil(inner_width))
+        .sum();
+    let scroll = wrapped_lines.saturating_sub(inner_height) as u16;
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
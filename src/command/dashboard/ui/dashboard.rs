@@ -10,9 +10,14 @@ use ratatui::{
 };
 use std::collections::{BTreeMap, HashSet};
 
-use super::super::app::App;
+use crate::tmux::AgentPane;
+use crate::usage::{self, AgentUsage};
+
+use super::super::agent;
+use super::super::app::{App, SNOOZE_DURATIONS};
+use super::super::search;
 use super::super::spinner::SPINNER_FRAMES;
-use super::format::format_git_status;
+use super::format::{format_check, format_diff_stat, format_git_status, format_worktree_metadata};
 
 /// Render the dashboard view (table + preview + footer).
 pub fn render_dashboard(f: &mut Frame, app: &mut App) {
@@ -35,7 +40,55 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
     render_preview(f, app, chunks[1]);
 
     // Footer - show different help based on mode
-    let footer_text = if app.input_mode {
+    let footer_text = if let Some(ref pending) = app.pending_remove {
+        let mut spans = vec![Span::styled(
+            format!("  Remove '{}'?", pending.handle),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )];
+        if pending.is_dirty {
+            spans.extend(vec![
+                Span::styled(
+                    " It has uncommitted changes that will be lost.  ",
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled("[F]", Style::default().fg(Color::Red)),
+                Span::raw(" force remove  "),
+            ]);
+        } else {
+            spans.extend(vec![
+                Span::raw(" Deletes the worktree, window, and branch.  "),
+                Span::styled("[y]", Style::default().fg(Color::Red)),
+                Span::raw(" remove  "),
+            ]);
+        }
+        spans.extend(vec![
+            Span::styled("[any]", Style::default().fg(Color::Cyan)),
+            Span::raw(" cancel"),
+        ]);
+        Paragraph::new(Line::from(spans))
+    } else if let Some(ref pending) = app.pending_snooze {
+        let mut spans = vec![Span::styled(
+            format!("  Snooze '{}' for", pending.handle),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for (key, label, _) in SNOOZE_DURATIONS {
+            spans.push(Span::styled(
+                format!("  [{}]", key),
+                Style::default().fg(Color::Cyan),
+            ));
+            spans.push(Span::raw(format!(" {}", label)));
+        }
+        spans.extend(vec![
+            Span::styled("  [any]", Style::default().fg(Color::Cyan)),
+            Span::raw(" cancel"),
+        ]);
+        Paragraph::new(Line::from(spans))
+    } else if let Some(ref message) = app.status_message {
+        Paragraph::new(Line::from(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::Yellow),
+        )))
+    } else if app.input_mode {
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "  INPUT MODE",
@@ -47,10 +100,73 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
             Span::styled("[Esc]", Style::default().fg(Color::Yellow)),
             Span::raw(" exit"),
         ]))
+    } else if let Some(search) = app.preview_search.as_ref().filter(|s| s.input) {
+        Paragraph::new(Line::from(vec![
+            Span::styled("  search preview: ", Style::default().fg(Color::Yellow)),
+            Span::raw(search.query.clone()),
+            Span::styled("_", Style::default().fg(Color::DarkGray)),
+            Span::raw("  "),
+            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+            Span::raw(" keep  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" close"),
+        ]))
+    } else if app.filter_input {
+        Paragraph::new(Line::from(vec![
+            Span::styled("  /", Style::default().fg(Color::Yellow)),
+            Span::raw(app.filter.clone()),
+            Span::styled("_", Style::default().fg(Color::DarkGray)),
+            Span::raw("  "),
+            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+            Span::raw(" keep  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Yellow)),
+            Span::raw(" clear"),
+        ]))
     } else {
-        let mut spans = vec![
+        let mut spans = Vec::new();
+        if app.paused {
+            spans.push(Span::styled(
+                "  ⏸ PAUSED",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        for (handle, op) in app.unlisted_worktree_ops() {
+            spans.push(Span::styled(
+                format!("  ⏳ {}: {}", handle, op),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        // Spend across all listed agents
+        let costs: Vec<f64> = app.agents.iter().filter_map(|a| a.usage.cost_usd).collect();
+        if !costs.is_empty() {
+            spans.push(Span::styled(
+                format!("  total {}", usage::format_cost(costs.iter().sum())),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if let Some(status) = app.status_filter.status_name() {
+            spans.push(Span::styled(
+                format!("  only {} [A] all", status),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if !app.filter.is_empty() {
+            spans.push(Span::styled(
+                format!("  /{}", app.filter),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.extend(vec![
             Span::styled("  [i]", Style::default().fg(Color::Green)),
             Span::raw(" input  "),
+            Span::styled("[P]", Style::default().fg(Color::Green)),
+            Span::raw(" prompt  "),
             Span::styled("[d]", Style::default().fg(Color::Yellow)),
             Span::raw(" diff  "),
             Span::styled("[1-9]", Style::default().fg(Color::Yellow)),
@@ -63,7 +179,7 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
             Span::raw("  "),
             Span::styled("[f]", Style::default().fg(Color::Cyan)),
             Span::raw(" filter: "),
-        ];
+        ]);
 
         if app.hide_stale {
             spans.push(Span::styled(
@@ -108,17 +224,26 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
         Line::from(Span::styled("Git", Style::default().fg(Color::Cyan).bold()))
     };
 
+    // Cost and Ctx columns only once some agent reports usage
+    let show_usage = app.agents.iter().any(|a| !a.usage.is_empty());
+
     let header_style = Style::default().fg(Color::Cyan).bold();
-    let header = Row::new(vec![
+    let mut header_cells = vec![
         Cell::from("#").style(header_style),
         Cell::from("Project").style(header_style),
         Cell::from("Worktree").style(header_style),
+        Cell::from("Diff").style(header_style),
         Cell::from(git_header),
+        Cell::from("Checks").style(header_style),
         Cell::from("Status").style(header_style),
         Cell::from("Time").style(header_style),
-        Cell::from("Title").style(header_style),
-    ])
-    .height(1);
+    ];
+    if show_usage {
+        header_cells.push(Cell::from("Cost").style(header_style));
+        header_cells.push(Cell::from("Ctx").style(header_style));
+    }
+    header_cells.push(Cell::from("Title").style(header_style));
+    let header = Row::new(header_cells).height(1);
 
     // Group agents by (session, window_name) to detect multi-pane windows
     let mut window_groups: BTreeMap<(String, String), Vec<usize>> = BTreeMap::new();
@@ -137,8 +262,24 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     // Track position within each window group for pane numbering
     let mut window_positions: BTreeMap<(String, String), usize> = BTreeMap::new();
 
+    // Check if a path corresponds to the current working directory.
+    // Try canonicalized comparison first (handles symlinks), fall back to direct comparison.
+    let is_current_path = |path: &std::path::Path| {
+        app.current_worktree.as_ref().is_some_and(|cwd| {
+            // Try canonical comparison first (resolves symlinks like /var -> /private/var on macOS)
+            if let (Ok(cwd_canonical), Ok(path_canonical)) =
+                (cwd.canonicalize(), path.canonicalize())
+            {
+                cwd_canonical == path_canonical
+            } else {
+                // Fall back to direct comparison
+                path == cwd
+            }
+        })
+    };
+
     // Pre-compute row data to calculate max widths
-    let row_data: Vec<_> = app
+    let mut row_data: Vec<_> = app
         .agents
         .iter()
         .enumerate()
@@ -160,27 +301,50 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 String::new()
             };
 
-            let project = App::extract_project_name(agent);
+            let project = app.extract_project_name(agent);
             let (worktree_name, is_main) = app.extract_worktree_name(agent);
-            // Check if this agent corresponds to the current working directory.
-            // Try canonicalized comparison first (handles symlinks), fall back to direct comparison.
-            let is_current = app.current_worktree.as_ref().is_some_and(|cwd| {
-                // Try canonical comparison first (resolves symlinks like /var -> /private/var on macOS)
-                if let (Ok(cwd_canonical), Ok(agent_canonical)) =
-                    (cwd.canonicalize(), agent.path.canonicalize())
-                {
-                    cwd_canonical == agent_canonical
-                } else {
-                    // Fall back to direct comparison
-                    agent.path == *cwd
-                }
-            });
-            let worktree_display = format!("{}{}", worktree_name, pane_suffix);
-            let title = agent
-                .pane_title
-                .as_ref()
-                .map(|t| t.strip_prefix("... ").unwrap_or(t).to_string())
-                .unwrap_or_default();
+            let is_current = is_current_path(&agent.path);
+            // Tree marker for worktrees created together (e.g., `add --count 3`)
+            let same_group = |other: Option<&AgentPane>| {
+                agent.group.is_some() && other.is_some_and(|o| o.group == agent.group)
+            };
+            let group_marker = match (
+                same_group(idx.checked_sub(1).and_then(|i| app.agents.get(i))),
+                same_group(app.agents.get(idx + 1)),
+            ) {
+                (false, true) => "┌ ",
+                (true, true) => "├ ",
+                (true, false) => "└ ",
+                (false, false) => "",
+            };
+            // Agents in the main worktree change files outside any branch
+            let in_main = agent::in_main_worktree(&agent.path);
+            let main_marker = if in_main { "⚠ " } else { "" };
+            let pin_marker = if app.pinned.contains(&agent.path) {
+                "★ "
+            } else {
+                ""
+            };
+            let worktree_display = format!(
+                "{}{}{}{}{}",
+                group_marker, pin_marker, main_marker, worktree_name, pane_suffix
+            );
+            let worktree_style = if in_main {
+                Style::default().fg(Color::Yellow)
+            } else if is_current {
+                Style::default().fg(Color::White)
+            } else if is_main {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let title = match app.worktree_ops.get(&worktree_name) {
+                Some(op) => format!("⏳ {}", op),
+                None => agent
+                    .summary_title(&app.config)
+                    .map(|t| t.strip_prefix("... ").unwrap_or(t).to_string())
+                    .unwrap_or_default(),
+            };
             let (status_text, status_color) = app.get_status_display(agent);
             let duration = app
                 .get_elapsed(agent)
@@ -189,27 +353,77 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
 
             // Get git status for this worktree (may be None if not yet fetched)
             let git_status = app.git_statuses.get(&agent.path);
-            let git_spans = format_git_status(git_status, app.spinner_frame);
+            let diff_spans = format_diff_stat(git_status);
+            let git_spans = format_git_status(
+                git_status,
+                app.overlaps.contains_key(&agent.path),
+                app.spinner_frame,
+            );
 
             (
                 jump_key,
                 project,
                 worktree_display,
-                is_main,
+                worktree_style,
                 is_current,
+                diff_spans,
                 git_spans,
                 status_text,
                 status_color,
                 duration,
                 title,
+                app.is_alert(agent),
+                format_check(app.checks.get(&agent.path)),
+                agent.usage,
             )
         })
         .collect();
 
+    // Offline worktrees (window closed) follow the live agents, without jump keys
+    row_data.extend(app.offline.iter().map(|entry| {
+        let project = agent::extract_project_name(&entry.path, app.config.worktree_dir.as_deref());
+        let worktree_name =
+            agent::extract_worktree_name(&entry.window_name, app.config.window_prefix()).0;
+        let git_status = app.git_statuses.get(&entry.path);
+        let diff_spans = format_diff_stat(git_status);
+        let git_spans = format_git_status(
+            git_status,
+            app.overlaps.contains_key(&entry.path),
+            app.spinner_frame,
+        );
+        let title = app
+            .worktree_ops
+            .get(&worktree_name)
+            .map(|op| format!("⏳ {}", op))
+            .unwrap_or_default();
+        let is_current = is_current_path(&entry.path);
+        let worktree_style = if is_current {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default()
+        };
+        (
+            String::new(),
+            project,
+            worktree_name,
+            worktree_style,
+            is_current,
+            diff_spans,
+            git_spans,
+            "offline".to_string(),
+            Color::DarkGray,
+            "-".to_string(),
+            title,
+            false,
+            format_check(app.checks.get(&entry.path)),
+            AgentUsage::default(),
+        )
+    }));
+
     // Calculate max project name width (with padding, capped)
     let max_project_width = row_data
         .iter()
-        .map(|(_, project, _, _, _, _, _, _, _, _)| project.len())
+        .map(|(_, project, _, _, _, _, _, _, _, _, _, _, _, _)| project.len())
         .max()
         .unwrap_or(5)
         .clamp(5, 20) // min 5, max 20
@@ -219,17 +433,31 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     // Use at least 8 to fit the "Worktree" header
     let max_worktree_width = row_data
         .iter()
-        .map(|(_, _, worktree_display, _, _, _, _, _, _, _)| worktree_display.len())
+        .map(|(_, _, worktree_display, _, _, _, _, _, _, _, _, _, _, _)| worktree_display.chars().count())
         .max()
         .unwrap_or(8)
         .max(8) // min 8 (header width)
         + 1; // padding
 
+    // Calculate max diff stat width ("+123 -45"), at least the header
+    let max_diff_width = row_data
+        .iter()
+        .map(|(_, _, _, _, _, diff_spans, _, _, _, _, _, _, _, _)| {
+            diff_spans
+                .iter()
+                .map(|(text, _)| text.chars().count())
+                .sum::<usize>()
+        })
+        .max()
+        .unwrap_or(4)
+        .clamp(4, 16)
+        + 1; // padding
+
     // Calculate max git status width (sum of all span character counts)
     // Use chars().count() instead of len() because Nerd Font icons are multi-byte
     let max_git_width = row_data
         .iter()
-        .map(|(_, _, _, _, _, git_spans, _, _, _, _)| {
+        .map(|(_, _, _, _, _, _, git_spans, _, _, _, _, _, _, _)| {
             git_spans
                 .iter()
                 .map(|(text, _)| text.chars().count())
@@ -247,21 +475,24 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 jump_key,
                 project,
                 worktree_display,
-                is_main,
+                worktree_style,
                 is_current,
+                diff_spans,
                 git_spans,
                 status_text,
                 status_color,
                 duration,
                 title,
+                is_alert,
+                (check_text, check_style),
+                agent_usage,
             )| {
-                let worktree_style = if is_current {
-                    Style::default().fg(Color::White)
-                } else if is_main {
-                    Style::default().fg(Color::DarkGray)
-                } else {
-                    Style::default()
-                };
+                let diff_line = Line::from(
+                    diff_spans
+                        .into_iter()
+                        .map(|(text, style)| Span::styled(text, style))
+                        .collect::<Vec<_>>(),
+                );
                 // Convert git spans to a Line
                 let git_line = Line::from(
                     git_spans
@@ -269,51 +500,90 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                         .map(|(text, style)| Span::styled(text, style))
                         .collect::<Vec<_>>(),
                 );
-                let row = Row::new(vec![
+                let mut cells = vec![
                     Cell::from(jump_key).style(Style::default().fg(Color::Yellow)),
                     Cell::from(project),
                     Cell::from(worktree_display).style(worktree_style),
+                    Cell::from(diff_line),
                     Cell::from(git_line),
+                    Cell::from(check_text).style(check_style),
                     Cell::from(status_text).style(Style::default().fg(status_color)),
                     Cell::from(duration),
-                    Cell::from(title),
-                ]);
+                ];
+                if show_usage {
+                    let (cost, context) = format_usage(&agent_usage);
+                    cells.push(cost);
+                    cells.push(context);
+                }
+                cells.push(Cell::from(title));
+                let row = Row::new(cells);
                 // Subtle background for the active worktree row
+                let mut row_style = Style::default();
                 if is_current {
-                    row.style(Style::default().bg(Color::Rgb(35, 40, 35)))
-                } else {
-                    row
+                    row_style = row_style.bg(Color::Rgb(35, 40, 35));
                 }
+                // Past its alert threshold for the current status
+                if is_alert {
+                    row_style = row_style.fg(Color::Red);
+                }
+                row.style(row_style)
             },
         )
         .collect();
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(2),                         // #: jump key
-            Constraint::Length(max_project_width as u16),  // Project: auto-sized
-            Constraint::Length(max_worktree_width as u16), // Worktree: auto-sized
-            Constraint::Length(max_git_width as u16),      // Git: auto-sized
-            Constraint::Length(8),                         // Status: fixed (icons)
-            Constraint::Length(10),                        // Time: HH:MM:SS + padding
-            Constraint::Fill(1),                           // Title: takes remaining space
-        ],
-    )
-    .header(header)
-    .block(Block::default())
-    .row_highlight_style(Style::default().bg(Color::Rgb(50, 50, 55)))
-    .highlight_symbol("> ");
+    let mut widths = vec![
+        Constraint::Length(2),                         // #: jump key
+        Constraint::Length(max_project_width as u16),  // Project: auto-sized
+        Constraint::Length(max_worktree_width as u16), // Worktree: auto-sized
+        Constraint::Length(max_diff_width as u16),     // Diff: auto-sized
+        Constraint::Length(max_git_width as u16),      // Git: auto-sized
+        Constraint::Length(7),                         // Checks: fixed (icon)
+        Constraint::Length(8),                         // Status: fixed (icons)
+        Constraint::Length(10),                        // Time: HH:MM:SS + padding
+    ];
+    if show_usage {
+        widths.push(Constraint::Length(8)); // Cost: "$123.45" + padding
+        widths.push(Constraint::Length(5)); // Ctx: "100%" + padding
+    }
+    widths.push(Constraint::Fill(1)); // Title: takes remaining space
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default())
+        .row_highlight_style(Style::default().bg(Color::Rgb(50, 50, 55)))
+        .highlight_symbol("> ");
 
     f.render_stateful_widget(table, area, &mut app.table_state);
 }
 
+/// Cost and Ctx cells. Context use turns yellow past 60% and red past 80%,
+/// when the agent is close to compacting or running out.
+fn format_usage(usage: &AgentUsage) -> (Cell<'static>, Cell<'static>) {
+    let cost = usage
+        .cost_usd
+        .map(usage::format_cost)
+        .unwrap_or_else(|| "-".to_string());
+    let (context, color) = match usage.context_pct {
+        Some(pct) if pct >= 80 => (format!("{}%", pct), Color::Red),
+        Some(pct) if pct >= 60 => (format!("{}%", pct), Color::Yellow),
+        Some(pct) => (format!("{}%", pct), Color::Reset),
+        None => ("-".to_string(), Color::DarkGray),
+    };
+    (
+        Cell::from(cost),
+        Cell::from(context).style(Style::default().fg(color)),
+    )
+}
+
 fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
     // Get info about the selected agent for the title
     let selected_agent = app
         .table_state
         .selected()
         .and_then(|idx| app.agents.get(idx));
+    let selected_offline = app
+        .selected_offline()
+        .map(|e| agent::extract_worktree_name(&e.window_name, app.config.window_prefix()).0);
 
     let (title, title_style, border_style) = if app.input_mode {
         let worktree_name = selected_agent
@@ -326,6 +596,24 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
                 .add_modifier(Modifier::BOLD),
             Style::default().fg(Color::Green),
         )
+    } else if let Some(agent) = selected_agent
+        && let Some(search) = app.preview_search.as_ref().filter(|s| !s.query.is_empty())
+    {
+        let worktree_name = app.extract_worktree_name(agent).0;
+        let matches = app.preview_matches().len();
+        let position = if matches == 0 {
+            "no matches".to_string()
+        } else {
+            format!("{}/{}", search.current + 1, matches)
+        };
+        (
+            format!(
+                " Preview: {}  search \"{}\" {} ",
+                worktree_name, search.query, position
+            ),
+            Style::default().fg(Color::Yellow),
+            Style::default().fg(Color::Yellow),
+        )
     } else if let Some(agent) = selected_agent {
         let worktree_name = app.extract_worktree_name(agent).0;
         (
@@ -333,6 +621,12 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
             Style::default().fg(Color::Cyan),
             Style::default().fg(Color::DarkGray),
         )
+    } else if let Some(ref worktree_name) = selected_offline {
+        (
+            format!(" Offline: {} ", worktree_name),
+            Style::default().fg(Color::DarkGray),
+            Style::default().fg(Color::DarkGray),
+        )
     } else {
         (
             " Preview ".to_string(),
@@ -341,10 +635,21 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
         )
     };
 
-    let block = Block::bordered()
+    let mut block = Block::bordered()
         .title(title)
         .title_style(title_style)
         .border_style(border_style);
+    if !app.input_mode
+        && let Some(metadata) = selected_agent.and_then(|a| app.worktree_metadata.get(&a.path))
+    {
+        let summary = format_worktree_metadata(metadata, crate::daemon::now_secs());
+        if !summary.is_empty() {
+            block = block.title_bottom(Line::from(Span::styled(
+                format!(" {} ", summary),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
 
     // Calculate the inner area to determine scroll offset
     let inner_area = block.inner(area);
@@ -374,9 +679,34 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
             }
         }
         (None, Some(_)) => (Text::raw("(pane not available)"), 1),
+        (_, None) if selected_offline.is_some() => {
+            (Text::raw("(window closed - press o to reopen)"), 1)
+        }
         (_, None) => (Text::raw("(no agent selected)"), 1),
     };
 
+    // Mark search matches, the one scrolled to in a stronger color
+    let text = match app.preview_search.as_ref().filter(|s| !s.query.is_empty()) {
+        Some(search) if selected_agent.is_some() => {
+            let matches = app.preview_matches();
+            let current = matches.get(search.current).copied();
+            let mut text = text;
+            for &idx in &matches {
+                if let Some(line) = text.lines.get_mut(idx) {
+                    let color = if Some(idx) == current {
+                        Color::LightRed
+                    } else {
+                        Color::Yellow
+                    };
+                    let style = Style::default().bg(color).fg(Color::Black);
+                    *line = search::highlight(std::mem::take(line), &search.query, style);
+                }
+            }
+            text
+        }
+        _ => text,
+    };
+
     // Update line count for scroll calculations
     app.preview_line_count = line_count;
 
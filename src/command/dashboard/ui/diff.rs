@@ -8,10 +8,11 @@ use ratatui::{
     widgets::{Block, List, ListItem, Paragraph},
 };
 
-use super::super::diff::DiffView;
+use super::super::diff::{DiffTarget, DiffView};
 
-/// Render the diff view (replaces the entire dashboard).
-pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
+/// Render the diff view (replaces the entire dashboard). `review_count` is the
+/// number of review comments collected for the worktree.
+pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView, review_count: usize) {
     let area = f.area();
 
     // Layout: content area + footer
@@ -47,49 +48,22 @@ pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
 
     if diff.patch_mode {
         // Patch mode with optional file list sidebar
-        render_patch_mode(f, diff, diff_area, chunks[1]);
+        render_patch_mode(f, diff, review_count, diff_area, chunks[1]);
         if let Some(file_area) = file_list_area {
             render_file_list(f, diff, file_area);
         }
     } else {
         // Normal diff mode with optional file list
-        render_normal_diff(f, diff, diff_area, chunks[1]);
+        render_normal_diff(f, diff, review_count, diff_area, chunks[1]);
         if let Some(file_area) = file_list_area {
             render_file_list(f, diff, file_area);
         }
     }
 }
 
-/// Determine which file is currently visible based on scroll position or current hunk.
-fn get_current_file_index(diff: &DiffView) -> Option<usize> {
-    if diff.file_list.is_empty() {
-        return None;
-    }
-
-    // In patch mode, use the current hunk's filename
-    if diff.patch_mode && !diff.hunks.is_empty() {
-        let current_filename = &diff.hunks[diff.current_hunk].filename;
-        return diff
-            .file_list
-            .iter()
-            .position(|f| &f.filename == current_filename);
-    }
-
-    // Find the last file whose start_line is <= current scroll position
-    let mut current_idx = 0;
-    for (idx, file) in diff.file_list.iter().enumerate() {
-        if file.start_line <= diff.scroll {
-            current_idx = idx;
-        } else {
-            break;
-        }
-    }
-    Some(current_idx)
-}
-
 /// Render the file list sidebar (full paths, directory dimmed, left-truncate if needed).
 fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
-    let current_file_idx = get_current_file_index(diff);
+    let current_file_idx = diff.current_file_index();
 
     let block = Block::bordered()
         .title(format!(" Files ({}) ", diff.file_list.len()))
@@ -230,26 +204,41 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
 }
 
 /// Render normal diff view (full diff with scroll).
-fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_area: Rect) {
+fn render_normal_diff(
+    f: &mut Frame,
+    diff: &DiffView,
+    review_count: usize,
+    content_area: Rect,
+    footer_area: Rect,
+) {
     // Create block with title including diff stats
-    let title = Line::from(vec![
-        Span::styled(
-            format!(" {} ", diff.title),
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(
-            format!("+{}", diff.lines_added),
-            Style::default().fg(Color::Green),
-        ),
-        Span::raw(" "),
-        Span::styled(
-            format!("-{}", diff.lines_removed),
-            Style::default().fg(Color::Red),
-        ),
-        Span::raw(" "),
-    ]);
+    let mut title_spans = vec![Span::styled(
+        format!(" {} ", diff.title),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some(idx) = diff.focused_file
+        && let Some(file) = diff.file_list.get(idx)
+    {
+        // Stats of the file shown on its own
+        title_spans.push(Span::styled(
+            format!("{} [{}/{}] ", file.filename, idx + 1, diff.file_list.len()),
+            Style::default().fg(Color::Yellow),
+        ));
+        title_spans.extend(diffstat_spans(file.lines_added, file.lines_removed));
+    } else {
+        if !diff.file_list.is_empty() {
+            let files = diff.file_list.len();
+            title_spans.push(Span::raw(format!(
+                "{} file{} changed, ",
+                files,
+                if files == 1 { "" } else { "s" }
+            )));
+        }
+        title_spans.extend(diffstat_spans(diff.lines_added, diff.lines_removed));
+    }
+    let title = Line::from(title_spans);
     let block = Block::bordered()
         .title(title)
         .border_style(Style::default().fg(Color::DarkGray));
@@ -257,10 +246,13 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
     // Calculate inner area (content area minus borders)
     let inner_height = content_area.height.saturating_sub(2) as usize;
 
-    // Virtualize: slice only the visible lines from cached parsed_lines
-    let max_start = diff.parsed_lines.len().saturating_sub(1);
+    // Virtualize: slice only the visible lines from cached parsed_lines,
+    // stopping at the end of the focused file
+    let range = diff.visible_range();
+    let range_end = range.end.min(diff.parsed_lines.len());
+    let max_start = range_end.saturating_sub(1);
     let start = diff.scroll.min(max_start);
-    let end = (start + inner_height).min(diff.parsed_lines.len());
+    let end = (start + inner_height).min(range_end);
     let visible_lines: Vec<Line> = diff.parsed_lines[start..end].to_vec();
     let text = Text::from(visible_lines);
 
@@ -269,17 +261,46 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
 
     f.render_widget(paragraph, content_area);
 
-    // Footer with keybindings - show which diff type is active (toggle with d)
-    let (wip_style, review_style) = if diff.is_branch_diff {
-        (
-            Style::default().fg(Color::DarkGray),
-            Style::default().fg(Color::Green),
-        )
-    } else {
-        (
-            Style::default().fg(Color::Green),
-            Style::default().fg(Color::DarkGray),
-        )
+    // Footer: show the comment or compare prompt if open, otherwise keybindings
+    if let Some(ref input) = diff.comment_input {
+        let footer = Paragraph::new(comment_input_line(input, diff.comment_to_review));
+        f.render_widget(footer, footer_area);
+        return;
+    }
+    if let Some(ref input) = diff.ref_input {
+        let mut spans = vec![
+            Span::styled("  [Enter]", Style::default().fg(Color::Green)),
+            Span::raw(" show  "),
+            Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
+            Span::raw(" suggest  "),
+            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::raw(" cancel  "),
+            Span::styled("| Compare with: ", Style::default().fg(Color::DarkGray)),
+        ];
+
+        if input.is_empty() {
+            spans.push(Span::styled("|", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                "base, checkpoint, or any ref...",
+                Style::default().fg(Color::DarkGray),
+            ));
+        } else {
+            spans.push(Span::raw(input));
+            spans.push(Span::styled("|", Style::default().fg(Color::White)));
+        }
+
+        let footer = Paragraph::new(Line::from(spans));
+        f.render_widget(footer, footer_area);
+        return;
+    }
+
+    // Footer with keybindings - show which diff type is active (toggle with Tab)
+    let active = Style::default().fg(Color::Green);
+    let inactive = Style::default().fg(Color::DarkGray);
+    let (wip_style, review_style) = match diff.target {
+        DiffTarget::Uncommitted => (active, inactive),
+        DiffTarget::Base => (inactive, active),
+        DiffTarget::Checkpoint | DiffTarget::Ref(_) => (inactive, inactive),
     };
 
     let mut footer_spans = vec![
@@ -289,18 +310,44 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
         Span::styled("WIP", wip_style),
         Span::styled(" | ", Style::default().fg(Color::DarkGray)),
         Span::styled("review", review_style),
-        Span::raw("  "),
     ];
 
+    // Show the custom target when comparing against something other than base
+    match diff.target {
+        DiffTarget::Checkpoint => {
+            footer_spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+            footer_spans.push(Span::styled("checkpoint", active));
+        }
+        DiffTarget::Ref(ref rev) => {
+            footer_spans.push(Span::styled(" | ", Style::default().fg(Color::DarkGray)));
+            footer_spans.push(Span::styled(rev.clone(), active));
+        }
+        _ => {}
+    }
+    footer_spans.push(Span::raw("  "));
+
     // Show [a] patch option only for WIP mode with changes
-    if !diff.is_branch_diff && (diff.lines_added > 0 || diff.lines_removed > 0) {
+    if !diff.is_branch_diff() && (diff.lines_added > 0 || diff.lines_removed > 0) {
         footer_spans.push(Span::styled("[a]", Style::default().fg(Color::Magenta)));
         footer_spans.push(Span::raw(" patch  "));
     }
 
+    push_review_hints(&mut footer_spans, review_count);
     footer_spans.extend(vec![
+        Span::styled("[r]", Style::default().fg(Color::Yellow)),
+        Span::raw(" compare  "),
+        Span::styled("[R]", Style::default().fg(Color::Green)),
+        Span::raw(" reviewed  "),
         Span::styled("[j/k]", Style::default().fg(Color::Cyan)),
         Span::raw(" scroll  "),
+        Span::styled("[n/p]", Style::default().fg(Color::Cyan)),
+        Span::raw(" file  "),
+        Span::styled("[f]", Style::default().fg(Color::Cyan)),
+        Span::raw(if diff.focused_file.is_some() {
+            " all files  "
+        } else {
+            " this file  "
+        }),
         Span::styled("[c]", Style::default().fg(Color::Green)),
         Span::raw(" commit  "),
         Span::styled("[m]", Style::default().fg(Color::Yellow)),
@@ -314,7 +361,13 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
 }
 
 /// Render patch mode (hunk-by-hunk staging like git add -p).
-fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_area: Rect) {
+fn render_patch_mode(
+    f: &mut Frame,
+    diff: &DiffView,
+    review_count: usize,
+    content_area: Rect,
+    footer_area: Rect,
+) {
     let hunk = &diff.hunks[diff.current_hunk];
 
     // Title shows filename and hunk progress
@@ -376,28 +429,7 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
 
     // Footer: show comment input if in comment mode, otherwise show keybindings
     if let Some(ref input) = diff.comment_input {
-        // Comment input mode - hints on left stay fixed, input on right
-        let mut spans = vec![
-            Span::styled("  [Enter]", Style::default().fg(Color::Green)),
-            Span::raw(" send  "),
-            Span::styled("[Esc]", Style::default().fg(Color::Red)),
-            Span::raw(" cancel  "),
-            Span::styled("| ", Style::default().fg(Color::DarkGray)),
-        ];
-
-        if input.is_empty() {
-            // Show cursor then placeholder when empty
-            spans.push(Span::styled("|", Style::default().fg(Color::White)));
-            spans.push(Span::styled(
-                "Type your comment...",
-                Style::default().fg(Color::DarkGray),
-            ));
-        } else {
-            spans.push(Span::raw(input));
-            spans.push(Span::styled("|", Style::default().fg(Color::White)));
-        }
-
-        let footer = Paragraph::new(Line::from(spans));
+        let footer = Paragraph::new(comment_input_line(input, diff.comment_to_review));
         f.render_widget(footer, footer_area);
     } else {
         // Normal patch mode keybindings
@@ -420,6 +452,9 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
             Span::raw(" split  "),
             Span::styled("[o]", Style::default().fg(Color::Cyan)),
             Span::raw(" comment  "),
+        ]);
+        push_review_hints(&mut footer_spans, review_count);
+        footer_spans.extend(vec![
             Span::styled("[j/k]", Style::default().fg(Color::Cyan)),
             Span::raw(" nav  "),
             Span::styled("[q]", Style::default().fg(Color::Cyan)),
@@ -430,3 +465,53 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
         f.render_widget(footer, footer_area);
     }
 }
+
+/// "+12 -3 " in green and red
+fn diffstat_spans(added: usize, removed: usize) -> [Span<'static>; 4] {
+    [
+        Span::styled(format!("+{}", added), Style::default().fg(Color::Green)),
+        Span::raw(" "),
+        Span::styled(format!("-{}", removed), Style::default().fg(Color::Red)),
+        Span::raw(" "),
+    ]
+}
+
+/// Footer line for typing a comment; review comments are collected, others
+/// are sent to the agent right away
+fn comment_input_line(input: &str, to_review: bool) -> Line<'_> {
+    // Hints on left stay fixed, input on right
+    let mut spans = vec![
+        Span::styled("  [Enter]", Style::default().fg(Color::Green)),
+        Span::raw(if to_review {
+            " add to review  "
+        } else {
+            " send  "
+        }),
+        Span::styled("[Esc]", Style::default().fg(Color::Red)),
+        Span::raw(" cancel  "),
+        Span::styled("| ", Style::default().fg(Color::DarkGray)),
+    ];
+
+    if input.is_empty() {
+        // Show cursor then placeholder when empty
+        spans.push(Span::styled("|", Style::default().fg(Color::White)));
+        spans.push(Span::styled(
+            "Type your comment...",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        spans.push(Span::raw(input));
+        spans.push(Span::styled("|", Style::default().fg(Color::White)));
+    }
+    Line::from(spans)
+}
+
+/// Keybinding hints for review comments, with the number collected so far
+fn push_review_hints(spans: &mut Vec<Span<'static>>, review_count: usize) {
+    spans.push(Span::styled("[O]", Style::default().fg(Color::Cyan)));
+    spans.push(Span::raw(" review  "));
+    if review_count > 0 {
+        spans.push(Span::styled("[S]", Style::default().fg(Color::Green)));
+        spans.push(Span::raw(format!(" send review ({})  ", review_count)));
+    }
+}
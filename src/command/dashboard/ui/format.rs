@@ -2,104 +2,97 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::checks::CheckResult;
+use crate::duration;
 use crate::git::GitStatus;
+use crate::metadata::WorktreeMetadata;
 
 use super::super::spinner::SPINNER_FRAMES;
 
-/// Format git status for the Git column: base branch, diff stats, then indicators
-/// Format: "→branch +N -M 󰏫 +X -Y 󰀪 ↑A ↓B"
-/// When there are uncommitted changes that differ from total, branch totals are dimmed
-pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(String, Style)> {
+/// Format the Diff column: lines added/removed since the merge base with the
+/// base branch, committed or not. Dimmed when everything is committed, bright
+/// while there is uncommitted work.
+pub fn format_diff_stat(status: Option<&GitStatus>) -> Vec<(String, Style)> {
+    let Some(status) = status else {
+        return Vec::new();
+    };
+    if status.total_added == 0 && status.total_removed == 0 {
+        return vec![("-".to_string(), Style::default().fg(Color::DarkGray))];
+    }
+    let has_uncommitted =
+        status.uncommitted_added > 0 || status.uncommitted_removed > 0 || status.is_dirty;
+    let modifier = if has_uncommitted {
+        Modifier::empty()
+    } else {
+        Modifier::DIM
+    };
+
+    let mut spans = Vec::new();
+    if status.total_added > 0 {
+        spans.push((
+            format!("+{}", status.total_added),
+            Style::default().fg(Color::Green).add_modifier(modifier),
+        ));
+    }
+    if status.total_removed > 0 {
+        if !spans.is_empty() {
+            spans.push((" ".to_string(), Style::default()));
+        }
+        spans.push((
+            format!("-{}", status.total_removed),
+            Style::default().fg(Color::Red).add_modifier(modifier),
+        ));
+    }
+    spans
+}
+
+/// Format the Checks column: the last `workmux test --record` result
+pub fn format_check(result: Option<&CheckResult>) -> (String, Style) {
+    match result {
+        Some(result) if result.passed => ("✓".to_string(), Style::default().fg(Color::Green)),
+        Some(_) => ("✗".to_string(), Style::default().fg(Color::Red)),
+        None => (String::new(), Style::default()),
+    }
+}
+
+/// Format git status for the Git column: PR, base branch, then indicators
+/// Format: "#PR →branch 󰏫 󰀪 ⚠ ↑A ↓B" (line counts are in the Diff column).
+/// `overlapping` marks worktrees that change files another worktree changes.
+pub fn format_git_status(
+    status: Option<&GitStatus>,
+    overlapping: bool,
+    spinner_frame: u8,
+) -> Vec<(String, Style)> {
     if let Some(status) = status {
         let mut spans: Vec<(String, Style)> = Vec::new();
         let has_uncommitted =
             status.uncommitted_added > 0 || status.uncommitted_removed > 0 || status.is_dirty;
 
-        // Check if uncommitted equals total (all changes are uncommitted, nothing committed yet)
-        let all_uncommitted = status.uncommitted_added == status.lines_added
-            && status.uncommitted_removed == status.lines_removed;
+        // Pull request recorded by `pr create`
+        if let Some(number) = status.pr_number {
+            spans.push((format!("#{}", number), Style::default().fg(Color::Cyan)));
+        }
 
         // Base branch (dimmed) - only show if not default (main/master)
         if !status.base_branch.is_empty()
             && status.base_branch != "main"
             && status.base_branch != "master"
         {
+            if !spans.is_empty() {
+                spans.push((" ".to_string(), Style::default()));
+            }
             spans.push((
                 format!("→{}", status.base_branch),
                 Style::default().fg(Color::DarkGray),
             ));
         }
 
-        // Always dim branch totals (historical), always bright uncommitted (active work)
-        // - Clean: dim branch totals only
-        // - All uncommitted: icon + bright uncommitted only
-        // - Mixed: dim branch totals + icon + bright uncommitted
-        if has_uncommitted && all_uncommitted {
-            // All changes are uncommitted - show icon + bright numbers only
+        // Uncommitted changes indicator
+        if has_uncommitted {
             if !spans.is_empty() {
                 spans.push((" ".to_string(), Style::default()));
             }
             spans.push(("\u{f03eb}".to_string(), Style::default().fg(Color::Magenta)));
-
-            if status.uncommitted_added > 0 {
-                spans.push((" ".to_string(), Style::default()));
-                spans.push((
-                    format!("+{}", status.uncommitted_added),
-                    Style::default().fg(Color::Green),
-                ));
-            }
-            if status.uncommitted_removed > 0 {
-                spans.push((" ".to_string(), Style::default()));
-                spans.push((
-                    format!("-{}", status.uncommitted_removed),
-                    Style::default().fg(Color::Red),
-                ));
-            }
-        } else {
-            // Either clean or mixed - show dim branch totals
-            if status.lines_added > 0 {
-                if !spans.is_empty() {
-                    spans.push((" ".to_string(), Style::default()));
-                }
-                spans.push((
-                    format!("+{}", status.lines_added),
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::DIM),
-                ));
-            }
-            if status.lines_removed > 0 {
-                if !spans.is_empty() {
-                    spans.push((" ".to_string(), Style::default()));
-                }
-                spans.push((
-                    format!("-{}", status.lines_removed),
-                    Style::default().fg(Color::Red).add_modifier(Modifier::DIM),
-                ));
-            }
-
-            // If there are uncommitted changes, show icon + bright uncommitted
-            if has_uncommitted {
-                if !spans.is_empty() {
-                    spans.push((" ".to_string(), Style::default()));
-                }
-                spans.push(("\u{f03eb}".to_string(), Style::default().fg(Color::Magenta)));
-
-                if status.uncommitted_added > 0 {
-                    spans.push((" ".to_string(), Style::default()));
-                    spans.push((
-                        format!("+{}", status.uncommitted_added),
-                        Style::default().fg(Color::Green),
-                    ));
-                }
-                if status.uncommitted_removed > 0 {
-                    spans.push((" ".to_string(), Style::default()));
-                    spans.push((
-                        format!("-{}", status.uncommitted_removed),
-                        Style::default().fg(Color::Red),
-                    ));
-                }
-            }
         }
 
         // Conflict indicator
@@ -110,6 +103,14 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
             spans.push(("\u{f002a}".to_string(), Style::default().fg(Color::Red)));
         }
 
+        // Changes overlapping another worktree's (`workmux conflicts`)
+        if overlapping {
+            if !spans.is_empty() {
+                spans.push((" ".to_string(), Style::default()));
+            }
+            spans.push(("⚠".to_string(), Style::default().fg(Color::Yellow)));
+        }
+
         // Ahead/behind upstream
         if status.ahead > 0 {
             if !spans.is_empty() {
@@ -141,3 +142,79 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
         vec![(frame.to_string(), Style::default().fg(Color::DarkGray))]
     }
 }
+
+/// Summarize recorded worktree metadata for the preview's bottom border:
+/// agent, age, issue and the first line of the prompt. The PR and base are
+/// already in the Git column.
+pub fn format_worktree_metadata(metadata: &WorktreeMetadata, now: u64) -> String {
+    let mut parts = Vec::new();
+    if let Some(agent) = &metadata.agent {
+        parts.push(agent.clone());
+    }
+    if let Some(created) = metadata.created_at {
+        parts.push(format!(
+            "created {} ago",
+            duration::format_duration(now.saturating_sub(created))
+        ));
+    }
+    if let Some(issue) = metadata.issue {
+        parts.push(format!("issue #{}", issue));
+    }
+    if let Some(line) = metadata
+        .prompt
+        .as_deref()
+        .and_then(|p| p.lines().map(str::trim).find(|l| !l.is_empty()))
+    {
+        parts.push(format!("\"{}\"", line));
+    }
+    parts.join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_stat_shows_totals_dimmed_when_committed() {
+        assert!(format_diff_stat(None).is_empty());
+        assert_eq!(
+            format_diff_stat(Some(&GitStatus::default())),
+            vec![("-".to_string(), Style::default().fg(Color::DarkGray))]
+        );
+
+        let mut status = GitStatus {
+            lines_added: 100,
+            lines_removed: 40,
+            total_added: 123,
+            total_removed: 45,
+            ..Default::default()
+        };
+        let spans = format_diff_stat(Some(&status));
+        let text: String = spans.iter().map(|(t, _)| t.as_str()).collect();
+        assert_eq!(text, "+123 -45");
+        assert!(spans[0].1.add_modifier.contains(Modifier::DIM));
+
+        status.uncommitted_added = 23;
+        let spans = format_diff_stat(Some(&status));
+        assert!(!spans[0].1.add_modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn worktree_metadata_summary_skips_missing_fields() {
+        assert_eq!(
+            format_worktree_metadata(&WorktreeMetadata::default(), 0),
+            ""
+        );
+        let metadata = WorktreeMetadata {
+            agent: Some("claude".to_string()),
+            created_at: Some(1_000),
+            issue: Some(42),
+            prompt: Some("\nFix the login redirect\nand add a test".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_worktree_metadata(&metadata, 1_000 + 3600),
+            "claude · created 01:00:00 ago · issue #42 · \"Fix the login redirect\""
+        );
+    }
+}
@@ -15,19 +15,31 @@ use super::super::keymap::{Context, help_rows};
 fn get_help_context(app: &App) -> Context {
     match &app.view_mode {
         ViewMode::Dashboard => {
-            if app.input_mode {
+            if app.log_view.is_some() {
+                Context::Log
+            } else if app.pending_remove.is_some() {
+                Context::ConfirmRemove
+            } else if app.pending_snooze.is_some() {
+                Context::Snooze
+            } else if app.composer.is_some() {
+                Context::Composer
+            } else if app.input_mode {
                 Context::DashboardInput
+            } else if app.filter_input {
+                Context::Filter
+            } else if app.preview_search.as_ref().is_some_and(|s| s.input) {
+                Context::PreviewSearch
             } else {
                 Context::DashboardNormal
             }
         }
         ViewMode::Diff(diff) => {
-            if diff.patch_mode {
-                if diff.comment_input.is_some() {
-                    Context::Comment
-                } else {
-                    Context::Patch
-                }
+            if diff.comment_input.is_some() {
+                Context::Comment
+            } else if diff.patch_mode {
+                Context::Patch
+            } else if diff.ref_input.is_some() {
+                Context::CompareInput
             } else {
                 Context::DiffNormal
             }
@@ -40,9 +52,16 @@ fn context_title(ctx: Context) -> &'static str {
     match ctx {
         Context::DashboardNormal => "Dashboard",
         Context::DashboardInput => "Input Mode",
+        Context::Composer => "Prompt Composer",
+        Context::Filter => "Filter",
+        Context::PreviewSearch => "Preview Search",
+        Context::ConfirmRemove => "Remove Worktree",
+        Context::Snooze => "Snooze Agent",
         Context::DiffNormal => "Diff View",
+        Context::CompareInput => "Compare",
         Context::Patch => "Patch Mode",
         Context::Comment => "Comment",
+        Context::Log => "Commit Log",
     }
 }
 
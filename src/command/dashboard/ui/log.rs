@@ -0,0 +1,60 @@
+//! Commit log modal rendering.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use super::super::app::App;
+
+/// Render the commit log as a large centered box over the dashboard.
+pub fn render_log(f: &mut Frame, app: &mut App) {
+    let Some(ref mut log) = app.log_view else {
+        return;
+    };
+
+    let area = f.area();
+    let width = (area.width * 9 / 10).max(40).min(area.width);
+    let height = (area.height * 8 / 10).max(10).min(area.height);
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    log.viewport_height = popup_area.height.saturating_sub(2) as usize;
+    // Keep the scroll position valid after a resize
+    log.scroll_by(0);
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(100, 100, 120)))
+        .title(Line::from(vec![
+            Span::raw(" "),
+            Span::styled(
+                "Log",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": ", Style::default().fg(Color::DarkGray)),
+            Span::styled(log.title.clone(), Style::default().fg(Color::White)),
+            Span::raw(" "),
+        ]))
+        .title_bottom(Line::from(vec![
+            Span::styled(" [j/k]", Style::default().fg(Color::Cyan)),
+            Span::raw(" scroll  "),
+            Span::styled("[q]", Style::default().fg(Color::Red)),
+            Span::raw(" close "),
+        ]));
+
+    let paragraph = Paragraph::new(log.lines.clone())
+        .block(block)
+        .scroll((log.scroll as u16, 0));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
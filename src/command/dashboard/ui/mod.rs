@@ -1,24 +1,44 @@
 //! TUI rendering logic for the dashboard.
 
+mod composer;
 mod dashboard;
 mod diff;
 mod format;
 mod help;
+mod log;
 
 use ratatui::Frame;
 
 use super::app::{App, ViewMode};
 
+pub use self::composer::render_composer;
 pub use self::dashboard::render_dashboard;
 pub use self::diff::render_diff_view;
 pub use self::help::render_help;
+pub use self::log::render_log;
 
 /// Main UI entry point - renders the appropriate view based on app state.
 pub fn ui(f: &mut Frame, app: &mut App) {
     // Render either dashboard or diff view based on view mode
     match &mut app.view_mode {
         ViewMode::Dashboard => render_dashboard(f, app),
-        ViewMode::Diff(diff_view) => render_diff_view(f, diff_view),
+        ViewMode::Diff(diff_view) => {
+            let review_count = app
+                .review_comments
+                .get(&diff_view.worktree_path)
+                .map_or(0, Vec::len);
+            render_diff_view(f, diff_view, review_count)
+        }
+    }
+
+    // Render the prompt composer over the dashboard if open
+    if app.composer.is_some() {
+        render_composer(f, app);
+    }
+
+    // Render the commit log over the dashboard if open
+    if app.log_view.is_some() {
+        render_log(f, app);
     }
 
     // Render help overlay on top if active
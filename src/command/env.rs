@@ -0,0 +1,112 @@
+use crate::workflow::hooks::HookEnv;
+use crate::{config, git};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde_json::json;
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default)]
+pub enum EnvFormat {
+    /// `KEY=value` lines, grouped by where workmux sets them
+    #[default]
+    Text,
+    /// Quoted `KEY="value"` lines, for `set -a; . <(workmux env --format dotenv)`
+    Dotenv,
+    /// JSON object
+    Json,
+}
+
+pub fn run(name: Option<&str>, format: EnvFormat) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    let config = config::Config::load(None)?;
+    let project_root = git::get_main_worktree_root()?;
+
+    let hook_env = HookEnv::new(&handle, &path, &project_root).with_branch(&branch);
+    let hook_vars = hook_env.vars();
+    let window_env = config.window_env.unwrap_or_default();
+
+    match format {
+        EnvFormat::Json => {
+            let hooks: serde_json::Map<String, serde_json::Value> = hook_vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), json!(v)))
+                .collect();
+            let output = json!({
+                "hooks": hooks,
+                "panes": {
+                    "set": window_env.set,
+                    "unset": window_env.unset,
+                    "inherit": window_env.inherit,
+                },
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        EnvFormat::Dotenv => {
+            for (key, value) in &hook_vars {
+                println!("{}", dotenv_line(key, value));
+            }
+            for (key, value) in &window_env.set {
+                println!("{}", dotenv_line(key, value));
+            }
+        }
+        EnvFormat::Text => {
+            println!("# Hooks, `run` and `test`");
+            for (key, value) in &hook_vars {
+                println!("{}={}", key, value);
+            }
+            if !window_env.set.is_empty() {
+                println!("\n# Panes (window_env)");
+                for (key, value) in &window_env.set {
+                    println!("{}={}", key, value);
+                }
+            }
+            if let Some(inherit) = &window_env.inherit {
+                let names: Vec<&str> = config::ALWAYS_INHERITED
+                    .into_iter()
+                    .chain(inherit.iter().map(String::as_str))
+                    .collect();
+                println!("\n# Panes only inherit: {}", names.join(", "));
+            }
+            if !window_env.unset.is_empty() {
+                println!("\n# Panes don't inherit: {}", window_env.unset.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `KEY="value"`, escaped so POSIX shells read the value back unchanged
+fn dotenv_line(key: &str, value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    format!("{}=\"{}\"", key, escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotenv_line_escapes_shell_specials() {
+        assert_eq!(
+            dotenv_line("WM_HANDLE", "fix-auth"),
+            "WM_HANDLE=\"fix-auth\""
+        );
+        assert_eq!(
+            dotenv_line("MSG", r#"say "hi" to $USER `now` \o/"#),
+            r#"MSG="say \"hi\" to \$USER \`now\` \\o/""#
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use crate::error::ErrorKind;
+use crate::workflow::types::WorktreeInfo;
+use crate::{config, daemon, duration, git, spinner, workflow};
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::path::Path;
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+/// Directories holding installed dependencies or build output, which can be
+/// recreated and usually make up most of a worktree's size
+const BUILD_DIRS: &[&str] = &["node_modules", "target", ".venv", ".next", ".turbo", "dist"];
+
+#[derive(Tabled)]
+struct GcRow {
+    #[tabled(rename = "WORKTREE")]
+    handle: String,
+    #[tabled(rename = "SIZE")]
+    size: String,
+    #[tabled(rename = "DEPS/BUILD")]
+    build: String,
+    #[tabled(rename = "AGE")]
+    age: String,
+    #[tabled(rename = "IDLE")]
+    idle: String,
+    #[tabled(rename = "MERGED")]
+    merged: String,
+}
+
+/// Bytes used by a worktree's files
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct DiskUsage {
+    total: u64,
+    /// The part of `total` in [`BUILD_DIRS`]
+    build: u64,
+}
+
+struct Entry {
+    wt: WorktreeInfo,
+    usage: DiskUsage,
+    /// Unix timestamp of the last agent status change or commit
+    last_active: Option<u64>,
+}
+
+/// Report disk usage, age, activity and merge status of every worktree, and
+/// offer to remove merged ones that have been idle for `older_than_days`
+pub fn run(auto: bool, older_than_days: u64) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let main_root = git::get_main_worktree_root()?;
+    let worktrees: Vec<WorktreeInfo> = workflow::list(&config, false)?
+        .into_iter()
+        .filter(|wt| wt.path != main_root)
+        .collect();
+    if worktrees.is_empty() {
+        println!("No worktrees found");
+        return Ok(());
+    }
+
+    let entries = spinner::with_spinner("Measuring disk usage", || Ok(measure(worktrees)))?;
+    let now = daemon::now_secs();
+    let min_idle = older_than_days * 86_400;
+
+    let rows: Vec<GcRow> = entries
+        .iter()
+        .map(|entry| GcRow {
+            handle: entry.wt.handle.clone(),
+            size: format_size(entry.usage.total),
+            build: format_size(entry.usage.build),
+            age: elapsed(entry.wt.created_at, now),
+            idle: elapsed(entry.last_active, now),
+            merged: if entry.wt.has_unmerged { "no" } else { "yes" }.to_string(),
+        })
+        .collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..5), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+
+    let total: u64 = entries.iter().map(|e| e.usage.total).sum();
+    let build: u64 = entries.iter().map(|e| e.usage.build).sum();
+    println!(
+        "\nTotal: {} ({} in dependency and build directories)",
+        format_size(total),
+        format_size(build)
+    );
+
+    let stale: Vec<&Entry> = entries
+        .iter()
+        .filter(|entry| is_stale(entry, now, min_idle))
+        .collect();
+    if stale.is_empty() {
+        println!(
+            "\nNo merged worktrees have been idle for {}+ days.",
+            older_than_days
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\n{} merged worktree(s) idle for {}+ days:",
+        stale.len(),
+        older_than_days
+    );
+    for entry in &stale {
+        println!(
+            "  - {} ({}, idle {})",
+            entry.wt.handle,
+            format_size(entry.usage.total),
+            elapsed(entry.last_active, now)
+        );
+    }
+    let freed: u64 = stale.iter().map(|e| e.usage.total).sum();
+
+    if !auto {
+        print!("\nRemove them, freeing {}? [y/N] ", format_size(freed));
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            return Err(ErrorKind::Aborted.msg("Aborted."));
+        }
+    }
+
+    let mut failed: Vec<(String, String)> = Vec::new();
+    for entry in &stale {
+        // Merged and clean, so nothing is lost by forcing
+        if let Err(e) = super::remove::remove_worktree(&entry.wt.handle, true, false, false) {
+            failed.push((entry.wt.handle.clone(), e.to_string()));
+        }
+    }
+    if failed.len() < stale.len() {
+        println!(
+            "\n✓ Removed {} worktree(s), freeing about {}",
+            stale.len() - failed.len(),
+            format_size(freed)
+        );
+    }
+    if !failed.is_empty() {
+        eprintln!("\nFailed to remove {} worktree(s):", failed.len());
+        for (handle, error) in &failed {
+            eprintln!("  - {}: {}", handle, error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Disk usage and last activity of each worktree, measured in parallel
+fn measure(worktrees: Vec<WorktreeInfo>) -> Vec<Entry> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = worktrees
+            .into_iter()
+            .map(|wt| {
+                scope.spawn(move || {
+                    let usage = disk_usage(&wt.path);
+                    let last_active = [wt.status_ts, git::last_commit_time(&wt.path)]
+                        .into_iter()
+                        .flatten()
+                        .max()
+                        .or(wt.created_at);
+                    Entry {
+                        wt,
+                        usage,
+                        last_active,
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("disk usage thread panicked"))
+            .collect()
+    })
+}
+
+/// Size of the files under `path`, without following symlinks. Unreadable
+/// entries are skipped.
+fn disk_usage(path: &Path) -> DiskUsage {
+    let mut usage = DiskUsage::default();
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return usage;
+    };
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else { continue };
+        if meta.is_dir() {
+            let sub = disk_usage(&entry.path());
+            usage.total += sub.total;
+            let name = entry.file_name();
+            if BUILD_DIRS.iter().any(|dir| name == *dir) {
+                usage.build += sub.total;
+            } else {
+                usage.build += sub.build;
+            }
+        } else if meta.is_file() {
+            usage.total += meta.len();
+        }
+    }
+    usage
+}
+
+/// Merged into the main branch, without uncommitted changes or a working
+/// agent, and idle for at least `min_idle` seconds
+fn is_stale(entry: &Entry, now: u64, min_idle: u64) -> bool {
+    let wt = &entry.wt;
+    let dirty = wt.git_status.as_ref().is_some_and(|s| s.is_dirty);
+    let working = wt.agent_running && wt.agent_status.as_deref() == Some("working");
+    let idle = entry
+        .last_active
+        .is_some_and(|ts| now.saturating_sub(ts) >= min_idle);
+    !wt.has_unmerged && !git::is_detached(&wt.branch) && !dirty && !working && idle
+}
+
+fn elapsed(ts: Option<u64>, now: u64) -> String {
+    ts.map(|ts| duration::format_duration(now.saturating_sub(ts)))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Human-readable size in powers of 1024, like `du -h`
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if size < 10.0 {
+        format!("{:.1} {}", size, UNITS[unit])
+    } else {
+        format!("{:.0} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitStatus;
+    use std::path::PathBuf;
+
+    #[test]
+    fn format_size_picks_unit() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(340 * 1024 * 1024), "340 MB");
+        assert_eq!(format_size(21 * 1024 * 1024 * 1024 / 10), "2.1 GB");
+    }
+
+    #[test]
+    fn disk_usage_counts_build_dirs_at_any_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::create_dir_all(root.join("web/node_modules/react")).unwrap();
+        std::fs::write(root.join("src/main.rs"), [0u8; 100]).unwrap();
+        std::fs::write(root.join("target/debug/app"), [0u8; 1000]).unwrap();
+        std::fs::write(root.join("web/node_modules/react/index.js"), [0u8; 50]).unwrap();
+
+        assert_eq!(
+            disk_usage(root),
+            DiskUsage {
+                total: 1150,
+                build: 1050
+            }
+        );
+    }
+
+    fn entry(has_unmerged: bool, dirty: bool, last_active: Option<u64>) -> Entry {
+        Entry {
+            wt: WorktreeInfo {
+                handle: "fix-auth".to_string(),
+                branch: "fix-auth".to_string(),
+                path: PathBuf::from("/wt/fix-auth"),
+                has_tmux: false,
+                has_unmerged,
+                package: None,
+                agent_status: None,
+                status_ts: None,
+                agent_running: false,
+                dead_panes: 0,
+                pr_info: None,
+                pr_number: None,
+                git_status: Some(GitStatus {
+                    is_dirty: dirty,
+                    ..Default::default()
+                }),
+                created_at: None,
+                agent: None,
+            },
+            usage: DiskUsage::default(),
+            last_active,
+        }
+    }
+
+    #[test]
+    fn stale_means_merged_clean_and_idle() {
+        let now = 100 * 86_400;
+        let week = 7 * 86_400;
+        assert!(is_stale(&entry(false, false, Some(now - week)), now, week));
+        assert!(!is_stale(&entry(false, false, Some(now - 60)), now, week));
+        assert!(!is_stale(&entry(true, false, Some(0)), now, week));
+        assert!(!is_stale(&entry(false, true, Some(0)), now, week));
+        assert!(!is_stale(&entry(false, false, None), now, week));
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::PathBuf;
+
+use crate::workflow::{import, workspace};
+
+pub fn run(dir: Option<PathBuf>, move_worktrees: bool, dry_run: bool, depth: usize) -> Result<()> {
+    let root = match dir {
+        Some(dir) => dir,
+        None => std::env::current_dir().context("Failed to get current directory")?,
+    };
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Directory '{}' does not exist", root.display()))?;
+
+    let repos = import::find_repos(&root, depth);
+    if repos.is_empty() {
+        return Err(anyhow!("No git repositories found in {}", root.display()));
+    }
+
+    let (summary, moved, recorded) = if dry_run {
+        ("Would import", "would move to", "would record base")
+    } else {
+        ("Imported", "moved to", "recorded base")
+    };
+
+    let mut total = 0;
+    let mut failed = 0;
+    let mut misplaced = 0;
+    for repo in &repos {
+        println!("==> {}", repo.display());
+        let result = workspace::enter_repo(repo, None)
+            .and_then(|context| import::import(&context, move_worktrees, dry_run));
+        let worktrees = match result {
+            Ok(worktrees) => worktrees,
+            Err(e) => {
+                println!("  ✗ {:#}", e);
+                failed += 1;
+                continue;
+            }
+        };
+        if worktrees.is_empty() {
+            println!("  No linked worktrees");
+        }
+        for wt in &worktrees {
+            let mut notes = Vec::new();
+            if wt.moved {
+                notes.push(format!("{} {}", moved, wt.path.display()));
+            }
+            if let Some(base) = &wt.recorded_base {
+                notes.push(format!("{} '{}'", recorded, base));
+            }
+            if let Some(expected) = &wt.expected_path {
+                notes.push(format!("not at {}", expected.display()));
+            }
+            if notes.is_empty() {
+                println!("  ✓ {}", wt.branch);
+            } else {
+                println!("  ✓ {} ({})", wt.branch, notes.join(", "));
+            }
+        }
+        total += worktrees.len();
+        misplaced += worktrees
+            .iter()
+            .filter(|wt| wt.expected_path.is_some())
+            .count();
+    }
+
+    println!(
+        "\n{} {} worktree(s) from {} repositories",
+        summary,
+        total,
+        repos.len() - failed
+    );
+    if !move_worktrees && misplaced > 0 {
+        println!("Use --move to relocate worktrees to the workmux layout");
+    }
+    Ok(())
+}
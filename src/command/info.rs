@@ -0,0 +1,95 @@
+use crate::{daemon, duration, git, metadata};
+use anyhow::{Context, Result};
+use serde_json::json;
+
+pub fn run(name: Option<&str>, json: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    let metadata = metadata::load_or_infer(&handle, &branch);
+
+    if json {
+        let output = json!({
+            "handle": handle,
+            "path": path,
+            "branch": metadata.branch,
+            "created_at": metadata.created_at,
+            "base": metadata.base,
+            "base_sha": metadata.base_sha,
+            "agent": metadata.agent,
+            "prompt": metadata.prompt,
+            "template": metadata.template,
+            "pr": metadata.pr,
+            "issue": metadata.issue,
+            "parent": metadata.parent,
+            "layout": metadata.layout,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let unknown = || "-".to_string();
+    let created = metadata
+        .created_at
+        .map(|ts| {
+            let age = daemon::now_secs().saturating_sub(ts);
+            format!("{} ago", duration::format_duration(age))
+        })
+        .unwrap_or_else(unknown);
+    let base = match (&metadata.base, &metadata.base_sha) {
+        (Some(base), Some(sha)) if !sha.starts_with(base.as_str()) => {
+            format!("{} ({})", base, &sha[..sha.len().min(7)])
+        }
+        (Some(base), _) => base.clone(),
+        (None, Some(sha)) => sha[..sha.len().min(7)].to_string(),
+        (None, None) => unknown(),
+    };
+
+    println!("Worktree: {}", handle);
+    println!("Branch:   {}", metadata.branch);
+    println!("Path:     {}", path.display());
+    println!("Created:  {}", created);
+    println!("Base:     {}", base);
+    if let Some(parent) = &metadata.parent {
+        println!("Parent:   {}", parent);
+    }
+    println!("Agent:    {}", metadata.agent.unwrap_or_else(unknown));
+    if let Some(layout) = &metadata.layout {
+        println!("Layout:   {}", layout);
+    }
+    println!(
+        "PR:       {}",
+        metadata
+            .pr
+            .map(|n| format!("#{}", n))
+            .unwrap_or_else(unknown)
+    );
+    println!(
+        "Issue:    {}",
+        metadata
+            .issue
+            .map(|n| format!("#{}", n))
+            .unwrap_or_else(unknown)
+    );
+    if let Some(template) = &metadata.template {
+        println!("Template: {}", template);
+    }
+    match metadata.prompt {
+        Some(prompt) => {
+            println!("Prompt:");
+            for line in prompt.trim().lines() {
+                println!("  {}", line);
+            }
+        }
+        None => println!("Prompt:   -"),
+    }
+    Ok(())
+}
@@ -0,0 +1,443 @@
+//! `workmux init`: write a `.workmux.yaml` tailored to the project.
+//!
+//! The project type is detected from its manifest and lock files, which gives
+//! the proposed install hook, pre-merge check and dev server pane. Each
+//! proposal can be changed or cleared at its prompt. `init --example` writes
+//! the fully commented example configuration instead.
+
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::setup::Prompter;
+use crate::cmd::Cmd;
+use crate::config;
+
+const CONFIG_FILE: &str = ".workmux.yaml";
+
+/// What the files of a project say about how to work on it
+#[derive(Debug, Default, PartialEq)]
+struct Project {
+    /// e.g. "Node.js (pnpm)"; None when no known manifest was found
+    kind: Option<&'static str>,
+    /// Installs dependencies in a new worktree
+    install: Option<String>,
+    /// Checks the branch before merging
+    check: Option<String>,
+    /// Runs a development server
+    dev: Option<String>,
+}
+
+/// Pane layouts `init` offers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// The agent alone
+    Agent,
+    /// The agent with a shell beside it
+    Split,
+    /// The agent with a shell beside it, and the dev server below the shell
+    Dev,
+}
+
+impl Layout {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "agent" => Some(Self::Agent),
+            "split" => Some(Self::Split),
+            "dev" => Some(Self::Dev),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Agent => "agent",
+            Self::Split => "split",
+            Self::Dev => "dev",
+        }
+    }
+}
+
+/// How files such as `.env` get into new worktrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileMode {
+    Copy,
+    Symlink,
+    None,
+}
+
+impl FileMode {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "copy" => Some(Self::Copy),
+            "symlink" => Some(Self::Symlink),
+            "none" | "no" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+
+/// Everything the generated file is made from
+#[derive(Debug)]
+struct Answers {
+    kind: Option<&'static str>,
+    agent: String,
+    layout: Layout,
+    dev: Option<String>,
+    post_create: String,
+    pre_merge: String,
+    files: Vec<String>,
+    file_mode: FileMode,
+}
+
+pub fn run(yes: bool) -> Result<()> {
+    let config_path = Path::new(CONFIG_FILE);
+    if config_path.exists() {
+        return Err(anyhow!(
+            ".workmux.yaml already exists. Remove it first if you want to regenerate it."
+        ));
+    }
+
+    let prompter = Prompter { yes };
+    let root = Path::new(".");
+    let project = detect_project(root);
+
+    println!("workmux init\n");
+    match project.kind {
+        Some(kind) => println!("Detected a {} project", kind),
+        None => println!("No known project type detected"),
+    }
+
+    let default_agent = config::Config::load(None)
+        .ok()
+        .and_then(|c| c.agent)
+        .unwrap_or_else(|| "claude".to_string());
+    let agent = prompter.ask("Agent command", &default_agent)?;
+
+    println!("\nPane layouts:");
+    println!("  agent  the agent alone");
+    println!("  split  the agent with a shell beside it");
+    if let Some(dev) = &project.dev {
+        println!(
+            "  dev    the agent with a shell beside it, and '{}' below the shell",
+            dev
+        );
+    }
+    let layout = loop {
+        let answer = prompter.ask("Layout", Layout::Split.as_str())?;
+        match Layout::parse(&answer) {
+            Some(Layout::Dev) if project.dev.is_none() => {
+                println!("No dev server was detected; please answer agent or split")
+            }
+            Some(layout) => break layout,
+            None => println!("Please answer agent, split, or dev"),
+        }
+    };
+
+    let post_create = ask_command(
+        &prompter,
+        "Command to run in new worktrees",
+        project.install.as_deref(),
+    )?;
+    let pre_merge = ask_command(
+        &prompter,
+        "Check to run before merging",
+        project.check.as_deref(),
+    )?;
+
+    let files = local_env_files(root);
+    let file_mode = if files.is_empty() {
+        FileMode::None
+    } else {
+        loop {
+            let answer = prompter.ask(
+                &format!(
+                    "Bring {} into new worktrees (copy, symlink, none)",
+                    files.join(", ")
+                ),
+                "copy",
+            )?;
+            match FileMode::parse(&answer) {
+                Some(mode) => break mode,
+                None => println!("Please answer copy, symlink, or none"),
+            }
+        }
+    };
+
+    let answers = Answers {
+        kind: project.kind,
+        agent,
+        layout,
+        dev: project.dev,
+        post_create,
+        pre_merge,
+        files,
+        file_mode,
+    };
+    fs::write(config_path, render(&answers))?;
+
+    println!("\n✓ Created .workmux.yaml");
+    println!("\nThis file provides project-specific overrides.");
+    println!("For global settings, edit ~/.config/workmux/config.yaml");
+    println!("For every option, run 'workmux init --example' in an empty directory.");
+    Ok(())
+}
+
+/// Ask for a command, where "none" (or a blank answer without a proposal)
+/// means no command
+fn ask_command(prompter: &Prompter, question: &str, proposed: Option<&str>) -> Result<String> {
+    let answer = prompter.ask(
+        &format!("{} ('none' for none)", question),
+        proposed.unwrap_or("none"),
+    )?;
+    Ok(if answer.eq_ignore_ascii_case("none") {
+        String::new()
+    } else {
+        answer
+    })
+}
+
+/// Detect the project type from the manifest and lock files in `root`
+fn detect_project(root: &Path) -> Project {
+    let has = |name: &str| root.join(name).exists();
+
+    if has("Cargo.toml") {
+        return Project {
+            kind: Some("Rust (Cargo)"),
+            install: None,
+            check: Some("cargo test".to_string()),
+            dev: None,
+        };
+    }
+    if has("package.json") {
+        let (kind, runner) = if has("pnpm-lock.yaml") {
+            ("Node.js (pnpm)", "pnpm")
+        } else if has("bun.lock") || has("bun.lockb") {
+            ("Bun", "bun")
+        } else if has("yarn.lock") {
+            ("Node.js (Yarn)", "yarn")
+        } else {
+            ("Node.js (npm)", "npm")
+        };
+        let scripts = fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+            .and_then(|json| json.get("scripts").cloned());
+        let script = |name: &str| {
+            scripts
+                .as_ref()
+                .and_then(|s| s.get(name))
+                .is_some()
+                .then(|| match (runner, name) {
+                    ("npm", "test") => "npm test".to_string(),
+                    ("npm", _) => format!("npm run {}", name),
+                    _ => format!("{} {}", runner, name),
+                })
+        };
+        return Project {
+            kind: Some(kind),
+            install: Some(format!("{} install", runner)),
+            check: script("test"),
+            dev: script("dev"),
+        };
+    }
+    if has("uv.lock") {
+        return Project {
+            kind: Some("Python (uv)"),
+            install: Some("uv sync".to_string()),
+            ..Default::default()
+        };
+    }
+    if has("poetry.lock") {
+        return Project {
+            kind: Some("Python (Poetry)"),
+            install: Some("poetry install".to_string()),
+            ..Default::default()
+        };
+    }
+    if has("go.mod") {
+        return Project {
+            kind: Some("Go"),
+            install: Some("go mod download".to_string()),
+            check: Some("go test ./...".to_string()),
+            dev: None,
+        };
+    }
+    if has("Gemfile") {
+        return Project {
+            kind: Some("Ruby (Bundler)"),
+            install: Some("bundle install".to_string()),
+            ..Default::default()
+        };
+    }
+    Project::default()
+}
+
+/// `.env` files in `root` that git doesn't track, so new worktrees lack them.
+/// Templates such as `.env.example` are left out.
+fn local_env_files(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_local_env_file(name))
+        .filter(|name| !is_tracked(root, name))
+        .collect();
+    files.sort();
+    files
+}
+
+fn is_local_env_file(name: &str) -> bool {
+    (name == ".env" || name.starts_with(".env."))
+        && ![".example", ".sample", ".template", ".dist"]
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+}
+
+fn is_tracked(root: &Path, name: &str) -> bool {
+    Cmd::new("git")
+        .workdir(root)
+        .args(&["ls-files", "--error-unmatch", "--", name])
+        .run_as_check()
+        .unwrap_or(false)
+}
+
+/// The `.workmux.yaml` for the answers
+fn render(answers: &Answers) -> String {
+    let mut out = String::from("# workmux project configuration");
+    if let Some(kind) = answers.kind {
+        out.push_str(&format!(" for a {} project", kind));
+    }
+    out.push_str(
+        "\n# For global settings, edit ~/.config/workmux/config.yaml\n\
+         # For every option, see 'workmux docs' or 'workmux init --example'.\n\n",
+    );
+
+    out.push_str(&format!("agent: {}\n\n", yaml_scalar(&answers.agent)));
+
+    out.push_str("panes:\n  - command: <agent>\n    focus: true\n");
+    if answers.layout != Layout::Agent {
+        out.push_str("  - split: horizontal\n");
+    }
+    if answers.layout == Layout::Dev
+        && let Some(dev) = &answers.dev
+    {
+        out.push_str(&format!(
+            "  - command: {}\n    split: vertical\n    size: 15\n",
+            yaml_scalar(dev)
+        ));
+    }
+
+    if !answers.post_create.is_empty() {
+        out.push_str(&format!(
+            "\npost_create:\n  - {}\n",
+            yaml_scalar(&answers.post_create)
+        ));
+    }
+    if !answers.pre_merge.is_empty() {
+        out.push_str(&format!(
+            "\npre_merge:\n  - {}\n",
+            yaml_scalar(&answers.pre_merge)
+        ));
+    }
+
+    let key = match answers.file_mode {
+        FileMode::Copy => Some("copy"),
+        FileMode::Symlink => Some("symlink"),
+        FileMode::None => None,
+    };
+    if let Some(key) = key
+        && !answers.files.is_empty()
+    {
+        out.push_str(&format!("\nfiles:\n  {}:\n", key));
+        for file in &answers.files {
+            out.push_str(&format!("    - {}\n", yaml_scalar(file)));
+        }
+    }
+    out
+}
+
+/// A string as a YAML scalar, quoted only when it has to be
+fn yaml_scalar(s: &str) -> String {
+    serde_yaml::to_string(s)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{:?}", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_node_runner_and_scripts() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"dev": "vite", "test": "vitest"}}"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(
+            detect_project(dir.path()),
+            Project {
+                kind: Some("Node.js (pnpm)"),
+                install: Some("pnpm install".to_string()),
+                check: Some("pnpm test".to_string()),
+                dev: Some("pnpm dev".to_string()),
+            }
+        );
+
+        fs::remove_file(dir.path().join("pnpm-lock.yaml")).unwrap();
+        let npm = detect_project(dir.path());
+        assert_eq!(npm.check.as_deref(), Some("npm test"));
+        assert_eq!(npm.dev.as_deref(), Some("npm run dev"));
+    }
+
+    #[test]
+    fn detects_cargo_and_unknown_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_project(dir.path()), Project::default());
+        fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        assert_eq!(
+            detect_project(dir.path()).check.as_deref(),
+            Some("cargo test")
+        );
+    }
+
+    #[test]
+    fn env_templates_are_not_local_files() {
+        assert!(is_local_env_file(".env"));
+        assert!(is_local_env_file(".env.local"));
+        assert!(!is_local_env_file(".env.example"));
+        assert!(!is_local_env_file(".envrc"));
+    }
+
+    #[test]
+    fn renders_a_valid_config() {
+        let answers = Answers {
+            kind: Some("Node.js (pnpm)"),
+            agent: "claude --verbose".to_string(),
+            layout: Layout::Dev,
+            dev: Some("pnpm dev".to_string()),
+            post_create: "pnpm install".to_string(),
+            pre_merge: String::new(),
+            files: vec![".env".to_string(), ".env.local".to_string()],
+            file_mode: FileMode::Copy,
+        };
+        let config: config::Config = serde_yaml::from_str(&render(&answers)).unwrap();
+        assert_eq!(config.agent.as_deref(), Some("claude --verbose"));
+        let panes = config.panes.unwrap();
+        assert_eq!(panes.len(), 3);
+        assert_eq!(panes[2].command.as_deref(), Some("pnpm dev"));
+        assert_eq!(config.post_create, Some(vec!["pnpm install".to_string()]));
+        assert_eq!(config.pre_merge, None);
+        assert_eq!(
+            config.files.copy,
+            Some(vec![".env".to_string(), ".env.local".to_string()])
+        );
+    }
+}
@@ -1,26 +1,40 @@
-use crate::{config, workflow};
+use crate::command::OutputFormat;
+use crate::git::{self, GitStatus};
+use crate::workflow::types::WorktreeInfo;
+use crate::{a11y, config, daemon, duration, porcelain, registry, workflow};
 use anyhow::Result;
 use pathdiff::diff_paths;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use tabled::{
     Table, Tabled,
     settings::{Padding, Style, disable::Remove, object::Columns},
 };
 
-#[derive(Tabled)]
+#[derive(Tabled, Default)]
 struct WorktreeRow {
     #[tabled(rename = "BRANCH")]
     branch: String,
+    #[tabled(rename = "PACKAGE")]
+    package: String,
     #[tabled(rename = "PR")]
     pr_status: String,
     #[tabled(rename = "TMUX")]
     tmux_status: String,
+    #[tabled(rename = "AGENT")]
+    agent_health: String,
+    #[tabled(rename = "GIT")]
+    git_status: String,
     #[tabled(rename = "UNMERGED")]
     unmerged_status: String,
+    #[tabled(rename = "AGE")]
+    age: String,
     #[tabled(rename = "PATH")]
     path_str: String,
 }
 
-fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
+fn format_pr_status(pr_info: Option<crate::github::PrSummary>, pr_number: Option<u32>) -> String {
     pr_info
         .map(|pr| {
             // Nerd Font icons with GitHub-style colors
@@ -34,12 +48,105 @@ fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
             };
             format!("#{} {}{}\x1b[0m", pr.number, color, icon)
         })
+        // Fall back to the PR recorded by `pr create` when gh has no match
+        .or_else(|| pr_number.map(|n| format!("#{}", n)))
         .unwrap_or_else(|| "-".to_string())
 }
 
-pub fn run(show_pr: bool) -> Result<()> {
+/// Summarize git status like the dashboard's Git column (without colors, so
+/// the table stays easy to parse): committed diff stats, uncommitted changes
+/// after a pencil icon, conflicts, then ahead/behind.
+pub fn format_git_status(status: Option<&GitStatus>) -> String {
+    let Some(status) = status else {
+        return "-".to_string();
+    };
+    let mut parts = Vec::new();
+    if status.lines_added > 0 {
+        parts.push(format!("+{}", status.lines_added));
+    }
+    if status.lines_removed > 0 {
+        parts.push(format!("-{}", status.lines_removed));
+    }
+    if status.is_dirty || status.uncommitted_added > 0 || status.uncommitted_removed > 0 {
+        parts.push("\u{f03eb}".to_string());
+        if status.uncommitted_added > 0 {
+            parts.push(format!("+{}", status.uncommitted_added));
+        }
+        if status.uncommitted_removed > 0 {
+            parts.push(format!("-{}", status.uncommitted_removed));
+        }
+    }
+    if status.has_conflict {
+        parts.push("\u{f002a}".to_string());
+    }
+    if status.ahead > 0 {
+        parts.push(format!("↑{}", status.ahead));
+    }
+    if status.behind > 0 {
+        parts.push(format!("↓{}", status.behind));
+    }
+    if parts.is_empty() {
+        "-".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// Describe the agent in a worktree's window: its status while running,
+/// "exited" when the window is left without an agent, "-" without a window.
+fn format_agent_health(wt: &WorktreeInfo) -> String {
+    if !wt.has_tmux {
+        return "-".to_string();
+    }
+    let health = if wt.agent_running {
+        wt.agent_status
+            .clone()
+            .unwrap_or_else(|| "running".to_string())
+    } else {
+        "exited".to_string()
+    };
+    if wt.dead_panes > 0 {
+        format!("{} ({} dead)", health, wt.dead_panes)
+    } else {
+        health
+    }
+}
+
+/// A repository and its worktrees, as listed by `list --all`
+#[derive(Serialize)]
+struct ProjectWorktrees {
+    project: String,
+    path: PathBuf,
+    worktrees: Vec<WorktreeInfo>,
+}
+
+pub fn run(
+    show_pr: bool,
+    package: Option<&str>,
+    all: bool,
+    format: OutputFormat,
+    a11y: bool,
+    porcelain: bool,
+) -> Result<()> {
+    let a11y = a11y::enabled(a11y);
+    if all {
+        return run_all(show_pr, package, format, a11y, porcelain);
+    }
+
     let config = config::Config::load(None)?;
-    let worktrees = workflow::list(&config, show_pr)?;
+    let worktrees = list_filtered(&config, show_pr, package)?;
+
+    if let Some(output) = format.serialize(&worktrees)? {
+        println!("{}", output);
+        return Ok(());
+    }
+
+    if porcelain {
+        for wt in &worktrees {
+            println!("{}", porcelain_line(wt));
+        }
+        return Ok(());
+    }
 
     if worktrees.is_empty() {
         println!("No worktrees found");
@@ -47,50 +154,309 @@ pub fn run(show_pr: bool) -> Result<()> {
     }
 
     let current_dir = std::env::current_dir()?;
-
-    let display_data: Vec<WorktreeRow> = worktrees
+    let now = daemon::now_secs();
+    if a11y {
+        for wt in &worktrees {
+            println!("{}", a11y_line(wt, &current_dir, now));
+        }
+        return Ok(());
+    }
+    let show_package = worktrees.iter().any(|wt| wt.package.is_some());
+    let rows: Vec<WorktreeRow> = worktrees
         .into_iter()
-        .map(|wt| {
-            let path_str = diff_paths(&wt.path, &current_dir)
-                .map(|p| {
-                    let s = p.display().to_string();
-                    if s.is_empty() || s == "." {
-                        "(here)".to_string()
-                    } else {
-                        s
-                    }
-                })
-                .unwrap_or_else(|| wt.path.display().to_string());
-
-            WorktreeRow {
-                branch: wt.branch,
-                pr_status: format_pr_status(wt.pr_info),
-                path_str,
-                tmux_status: if wt.has_tmux {
-                    "✓".to_string()
-                } else {
-                    "-".to_string()
-                },
-                unmerged_status: if wt.has_unmerged {
-                    "●".to_string()
-                } else {
-                    "-".to_string()
-                },
+        .map(|wt| worktree_row(wt, &current_dir, now))
+        .collect();
+    print_table(rows, show_pr, show_package);
+
+    Ok(())
+}
+
+/// `list --all`: the worktrees of every repository workmux knows about,
+/// grouped by project
+fn run_all(
+    show_pr: bool,
+    package: Option<&str>,
+    format: OutputFormat,
+    a11y: bool,
+    porcelain: bool,
+) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let mut projects = Vec::new();
+    for repo in discover_repos() {
+        // Config and worktree listing both work on the current repository
+        if let Err(e) = std::env::set_current_dir(&repo) {
+            tracing::debug!(repo = %repo.display(), error = %e, "list:skipping repo");
+            continue;
+        }
+        let worktrees =
+            config::Config::load(None).and_then(|config| list_filtered(&config, show_pr, package));
+        match worktrees {
+            Ok(worktrees) if !worktrees.is_empty() => projects.push(ProjectWorktrees {
+                project: project_name(&repo),
+                path: repo,
+                worktrees,
+            }),
+            Ok(_) => {}
+            Err(e) => tracing::debug!(repo = %repo.display(), error = %e, "list:skipping repo"),
+        }
+    }
+    std::env::set_current_dir(&current_dir)?;
+    projects.sort_by(|a, b| a.project.cmp(&b.project).then(a.path.cmp(&b.path)));
+
+    if let Some(output) = format.serialize(&projects)? {
+        println!("{}", output);
+        return Ok(());
+    }
+
+    // Worktree paths tell the projects apart, so the lines stay the same as
+    // without --all
+    if porcelain {
+        for wt in projects.iter().flat_map(|p| &p.worktrees) {
+            println!("{}", porcelain_line(wt));
+        }
+        return Ok(());
+    }
+
+    if projects.is_empty() {
+        println!("No worktrees found");
+        return Ok(());
+    }
+
+    let now = daemon::now_secs();
+    if a11y {
+        for project in &projects {
+            println!(
+                "{}",
+                a11y::line(&[
+                    ("Project", Some(project.project.clone())),
+                    ("Path", Some(relative_path(&project.path, &current_dir))),
+                    ("Worktrees", Some(project.worktrees.len().to_string())),
+                ])
+            );
+            for wt in &project.worktrees {
+                println!("{}", a11y_line(wt, &current_dir, now));
+            }
+        }
+        return Ok(());
+    }
+    let show_package = projects
+        .iter()
+        .flat_map(|p| &p.worktrees)
+        .any(|wt| wt.package.is_some());
+    let mut rows = Vec::new();
+    for project in projects {
+        rows.push(WorktreeRow {
+            branch: project.project,
+            path_str: relative_path(&project.path, &current_dir),
+            ..Default::default()
+        });
+        let count = project.worktrees.len();
+        for (i, wt) in project.worktrees.into_iter().enumerate() {
+            let mut row = worktree_row(wt, &current_dir, now);
+            let branch = if i + 1 == count { "└─ " } else { "├─ " };
+            row.branch = format!("{}{}", branch, row.branch);
+            rows.push(row);
+        }
+    }
+    print_table(rows, show_pr, show_package);
+
+    Ok(())
+}
+
+fn list_filtered(
+    config: &config::Config,
+    show_pr: bool,
+    package: Option<&str>,
+) -> Result<Vec<WorktreeInfo>> {
+    let mut worktrees = workflow::list(config, show_pr)?;
+    if let Some(package) = package {
+        let package = crate::naming::normalize_package_path(package)?;
+        worktrees.retain(|wt| wt.package.as_deref() == Some(package.as_str()));
+    }
+    Ok(worktrees)
+}
+
+/// Main worktrees of the current repository, of repositories with live
+/// agents, and of those recorded in the worktree registry
+fn discover_repos() -> Vec<PathBuf> {
+    let agent_paths = daemon::agent_panes().into_iter().map(|a| a.path);
+    let registry_paths = registry::load().into_iter().map(|e| e.path);
+    let mut repos = BTreeSet::new();
+    if let Ok(root) = git::get_main_worktree_root() {
+        repos.insert(root);
+    }
+    let mut seen = BTreeSet::new();
+    for path in agent_paths.chain(registry_paths) {
+        if !seen.insert(path.clone()) || !path.is_dir() {
+            continue;
+        }
+        if let Ok(root) = git::get_main_worktree_root_in(Some(&path)) {
+            repos.insert(root);
+        }
+    }
+    repos.into_iter().collect()
+}
+
+fn project_name(repo: &Path) -> String {
+    repo.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo.display().to_string())
+}
+
+fn relative_path(path: &Path, current_dir: &Path) -> String {
+    diff_paths(path, current_dir)
+        .map(|p| {
+            let s = p.display().to_string();
+            if s.is_empty() || s == "." {
+                "(here)".to_string()
+            } else {
+                s
             }
         })
-        .collect();
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn worktree_row(wt: WorktreeInfo, current_dir: &Path, now: u64) -> WorktreeRow {
+    let path_str = relative_path(&wt.path, current_dir);
+    let agent_health = format_agent_health(&wt);
+    let age = wt
+        .created_at
+        .map(|ts| duration::format_duration(now.saturating_sub(ts)))
+        .unwrap_or_else(|| "-".to_string());
+    let git_status = format_git_status(wt.git_status.as_ref());
+    WorktreeRow {
+        branch: wt.branch,
+        package: wt.package.unwrap_or_else(|| "-".to_string()),
+        pr_status: format_pr_status(wt.pr_info, wt.pr_number),
+        path_str,
+        agent_health,
+        git_status,
+        tmux_status: if wt.has_tmux {
+            "✓".to_string()
+        } else {
+            "-".to_string()
+        },
+        unmerged_status: if wt.has_unmerged {
+            "●".to_string()
+        } else {
+            "-".to_string()
+        },
+        age,
+    }
+}
+
+/// A worktree as labeled fields for screen readers (`--a11y`)
+fn a11y_line(wt: &WorktreeInfo, current_dir: &Path, now: u64) -> String {
+    let pr = wt
+        .pr_info
+        .as_ref()
+        .map(|pr| {
+            let state = match pr.state.as_str() {
+                "OPEN" if pr.is_draft => "draft",
+                "OPEN" => "open",
+                "MERGED" => "merged",
+                "CLOSED" => "closed",
+                other => other,
+            };
+            format!("#{} {}", pr.number, state.to_lowercase())
+        })
+        .or_else(|| wt.pr_number.map(|n| format!("#{}", n)));
+    let agent = if wt.has_tmux {
+        format_agent_health(wt)
+    } else {
+        "no window".to_string()
+    };
+    let agent = match wt.status_ts {
+        Some(ts) if wt.has_tmux && wt.agent_running && wt.agent_status.is_some() => format!(
+            "{} for {}",
+            agent,
+            a11y::spoken_duration(now.saturating_sub(ts))
+        ),
+        _ => agent,
+    };
+    a11y::line(&[
+        ("Worktree", Some(wt.branch.clone())),
+        ("Package", wt.package.clone()),
+        ("PR", pr),
+        ("Agent", Some(agent)),
+        ("Git", a11y::describe_git_status(wt.git_status.as_ref())),
+        (
+            "Merged",
+            Some(if wt.has_unmerged { "no" } else { "yes" }.to_string()),
+        ),
+        (
+            "Age",
+            wt.created_at
+                .map(|ts| a11y::spoken_duration(now.saturating_sub(ts))),
+        ),
+        ("Path", Some(relative_path(&wt.path, current_dir))),
+    ])
+}
+
+/// A worktree as tab-separated fields (`--porcelain`): handle, branch,
+/// absolute path, whether it has a tmux window, agent status, whether it has
+/// unmerged commits, commits ahead and behind its base, whether it has
+/// uncommitted changes, and PR number
+fn porcelain_line(wt: &WorktreeInfo) -> String {
+    let agent = if !wt.has_tmux {
+        porcelain::NONE.to_string()
+    } else if wt.agent_running {
+        wt.agent_status
+            .clone()
+            .unwrap_or_else(|| "running".to_string())
+    } else {
+        "exited".to_string()
+    };
+    let git = wt.git_status.as_ref();
+    porcelain::line(&[
+        wt.handle.clone(),
+        wt.branch.clone(),
+        wt.path.display().to_string(),
+        porcelain::flag(wt.has_tmux).to_string(),
+        agent,
+        porcelain::flag(wt.has_unmerged).to_string(),
+        porcelain::opt(git.map(|s| s.ahead)),
+        porcelain::opt(git.map(|s| s.behind)),
+        porcelain::opt(git.map(|s| porcelain::flag(s.is_dirty))),
+        porcelain::opt(wt.pr_info.as_ref().map(|pr| pr.number).or(wt.pr_number)),
+    ])
+}
 
-    let mut table = Table::new(display_data);
+fn print_table(rows: Vec<WorktreeRow>, show_pr: bool, show_package: bool) {
+    let mut table = Table::new(rows);
     table
         .with(Style::blank())
-        .modify(Columns::new(0..4), Padding::new(0, 1, 0, 0));
+        .modify(Columns::new(0..8), Padding::new(0, 1, 0, 0));
 
-    // Hide PR column if --pr flag not used
+    // Hide PR column if --pr flag not used (removed first so PACKAGE's index is unaffected)
     if !show_pr {
+        table.with(Remove::column(Columns::new(2..3)));
+    }
+
+    // Hide PACKAGE column unless some worktree is package-scoped
+    if !show_package {
         table.with(Remove::column(Columns::new(1..2)));
     }
 
     println!("{table}");
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_git_status_shows_changes_and_sync_state() {
+        assert_eq!(format_git_status(None), "-");
+        assert_eq!(format_git_status(Some(&GitStatus::default())), "-");
+
+        let status = GitStatus {
+            lines_added: 12,
+            is_dirty: true,
+            uncommitted_removed: 2,
+            behind: 1,
+            ..Default::default()
+        };
+        assert_eq!(format_git_status(Some(&status)), "+12 \u{f03eb} -2 ↓1");
+    }
 }
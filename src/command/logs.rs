@@ -0,0 +1,163 @@
+//! `workmux logs`: the recorded output of a worktree's hooks and agent.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::workflow::hooks::{self, Hook, LOG_RUNS};
+use crate::{daemon, duration, git};
+
+/// How often `--follow` checks the log for new output
+const FOLLOW_POLL: Duration = Duration::from_millis(250);
+
+pub fn run(
+    name: Option<&str>,
+    hook: Option<Hook>,
+    previous: usize,
+    agent: bool,
+    follow: bool,
+) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    // Logs outlive the worktree, so a removed worktree's handle works too
+    let handle = git::find_worktree(&name)
+        .ok()
+        .and_then(|(path, _)| Some(path.file_name()?.to_str()?.to_string()))
+        .unwrap_or(name);
+    let dir = hooks::logs_dir(&handle)?;
+
+    if agent {
+        let path = hooks::agent_log_path(&dir);
+        if !path.exists() {
+            return Err(anyhow!(
+                "No agent log for '{}'. Open it with --log-agent (or set log_agent: true) to record one.",
+                handle
+            ));
+        }
+        return print_log(&path, follow);
+    }
+
+    let Some(hook) = hook else {
+        return list(&dir, &handle);
+    };
+    if previous >= LOG_RUNS {
+        return Err(anyhow!(
+            "Only the last {} runs of each hook are kept",
+            LOG_RUNS
+        ));
+    }
+    let path = hooks::log_path(&dir, hook, previous);
+    if !path.exists() {
+        return Err(anyhow!(
+            "No {} log for '{}'{}",
+            hook.key(),
+            handle,
+            if previous > 0 {
+                format!(" (--previous {})", previous)
+            } else {
+                String::new()
+            }
+        ));
+    }
+    print_log(&path, follow)
+}
+
+/// Print the log, then with `follow` whatever is written to it until
+/// interrupted. A log that shrinks was started over (a new hook run), so it
+/// is read again from the start.
+fn print_log(path: &Path, follow: bool) -> Result<()> {
+    let mut stdout = io::stdout();
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut read = io::copy(&mut file, &mut stdout)?;
+    if !follow {
+        return Ok(());
+    }
+    loop {
+        std::thread::sleep(FOLLOW_POLL);
+        let len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        if len < read {
+            file = File::open(path)?;
+            read = 0;
+        }
+        file.seek(SeekFrom::Start(read))?;
+        let mut chunk = Vec::new();
+        read += file.read_to_end(&mut chunk)? as u64;
+        stdout.write_all(&chunk)?;
+        stdout.flush()?;
+    }
+}
+
+/// The hooks with logs, when each last ran and whether it failed
+fn list(dir: &Path, handle: &str) -> Result<()> {
+    let now = daemon::now_secs();
+    let rows: Vec<(Hook, String, usize, &str)> = Hook::ALL
+        .into_iter()
+        .filter_map(|hook| {
+            let latest = hooks::log_path(dir, hook, 0);
+            let modified = fs::metadata(&latest).ok()?.modified().ok()?;
+            let age = modified
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| now.saturating_sub(d.as_secs()))
+                .unwrap_or_default();
+            let runs = (0..LOG_RUNS)
+                .filter(|&run| hooks::log_path(dir, hook, run).exists())
+                .count();
+            let result = match fs::read_to_string(&latest) {
+                Ok(text) if failed(&text) => "failed",
+                _ => "ok",
+            };
+            Some((
+                hook,
+                format!("{} ago", duration::format_duration(age)),
+                runs,
+                result,
+            ))
+        })
+        .collect();
+
+    let agent_log = fs::metadata(hooks::agent_log_path(dir)).ok();
+    if rows.is_empty() && agent_log.is_none() {
+        println!("No hook logs for '{}'", handle);
+        return Ok(());
+    }
+    if !rows.is_empty() {
+        println!("{:<12} {:<12} {:<7} RUNS", "HOOK", "LAST RUN", "RESULT");
+        for (hook, age, runs, result) in &rows {
+            println!("{:<12} {:<12} {:<7} {}", hook.key(), age, result, runs);
+        }
+        println!(
+            "\nShow one with: workmux logs {} --hook <hook> [--previous <n>]",
+            handle
+        );
+    }
+    if let Some(meta) = agent_log {
+        println!(
+            "{}Agent output recorded ({} KB), show it with: workmux logs {} --agent [--follow]",
+            if rows.is_empty() { "" } else { "\n" },
+            meta.len().div_ceil(1024),
+            handle
+        );
+    }
+    Ok(())
+}
+
+/// Whether the run's last command failed
+fn failed(log: &str) -> bool {
+    log.lines()
+        .last()
+        .is_some_and(|line| line.starts_with("[exit code "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_reads_the_last_exit_code_line() {
+        assert!(failed("$ pnpm install\nERR!\n[exit code 1]\n"));
+        assert!(!failed("$ pnpm install\ndone\n"));
+        assert!(!failed(""));
+    }
+}
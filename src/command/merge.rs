@@ -1,25 +1,76 @@
 use crate::config::MergeStrategy;
+use crate::conflicts::{self, MergeCandidate};
+use crate::error::ErrorKind;
 use crate::workflow::WorkflowContext;
-use crate::{config, workflow};
+use crate::workflow::types::MergeResult;
+use crate::{config, daemon, git, notification, spinner, workflow};
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Instant;
 
-#[allow(clippy::too_many_arguments)]
-pub fn run(
-    name: Option<&str>,
-    into_branch: Option<&str>,
-    ignore_uncommitted: bool,
-    mut rebase: bool,
-    mut squash: bool,
-    keep: bool,
-    no_verify: bool,
-    notification: bool,
-) -> Result<()> {
-    let config = config::Config::load(None)?;
+/// Flags for `merge`
+#[derive(Default)]
+pub struct MergeOptions<'a> {
+    pub ignore_uncommitted: bool,
+    pub rebase: bool,
+    pub squash: bool,
+    pub keep: bool,
+    pub no_verify: bool,
+    pub delete_remote: bool,
+    pub notification: bool,
+    /// Only report conflicts (`--check`)
+    pub check: bool,
+    /// Merge every done worktree in a planned order (`--plan`)
+    pub plan: bool,
+    /// Merge the branch's PR on the forge (`--pr`)
+    pub pr: bool,
+    /// With `pr`, return once the PR is queued instead of waiting for it
+    pub no_wait: bool,
+    /// Cherry-pick these commits instead of merging the branch (`--commits`)
+    pub commits: &'a [String],
+    pub json: bool,
+}
+
+pub fn run(name: Option<&str>, into_branch: Option<&str>, options: MergeOptions) -> Result<()> {
+    let MergeOptions {
+        ignore_uncommitted,
+        mut rebase,
+        mut squash,
+        keep,
+        no_verify,
+        delete_remote,
+        notification,
+        check,
+        plan,
+        pr,
+        commits,
+        json,
+        ..
+    } = options;
+    let started = Instant::now();
+    if json {
+        spinner::reserve_stdout();
+    }
+    if plan {
+        return plan_merges(into_branch, &options);
+    }
+
+    // Resolve name from argument or current directory
+    // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
+    let name_to_merge = super::resolve_name(name)?;
+
+    let mut config = config::Config::load(None)?.for_worktree(&name_to_merge);
+    if delete_remote {
+        config.merge.delete_remote_branch = Some(true);
+    }
 
     // Apply default strategy from config if no CLI flags are provided
     if !rebase
         && !squash
-        && let Some(strategy) = config.merge_strategy
+        && let Some(strategy) = config.default_merge_strategy()
     {
         match strategy {
             MergeStrategy::Rebase => rebase = true,
@@ -28,12 +79,27 @@ pub fn run(
         }
     }
 
-    // Resolve name from argument or current directory
-    // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
-    let name_to_merge = super::resolve_name(name)?;
-
     let context = WorkflowContext::new(config)?;
 
+    if !commits.is_empty() {
+        return cherry_pick(&name_to_merge, into_branch, &options, started, &context);
+    }
+
+    if check {
+        return check_only(&name_to_merge, into_branch, &context);
+    }
+
+    if pr {
+        let strategy = if rebase {
+            MergeStrategy::Rebase
+        } else if squash {
+            MergeStrategy::Squash
+        } else {
+            MergeStrategy::Merge
+        };
+        return merge_pr(&name_to_merge, strategy, &options, &context);
+    }
+
     // Announce pre-merge hooks if any (unless --no-verify is passed)
     if !no_verify {
         super::announce_hooks(&context.config, None, super::HookPhase::PreMerge);
@@ -44,7 +110,7 @@ pub fn run(
         super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
     }
 
-    let result = workflow::merge(
+    let result = match workflow::merge(
         &name_to_merge,
         into_branch,
         ignore_uncommitted,
@@ -54,8 +120,30 @@ pub fn run(
         no_verify,
         notification,
         &context,
-    )
-    .context("Failed to merge worktree")?;
+    ) {
+        Ok(result) => result,
+        Err(e) => {
+            // Queued merges run unattended, so failures need reporting too
+            if notification {
+                let reason = e.to_string();
+                notification::show(&format!(
+                    "Failed to merge '{}': {}",
+                    name_to_merge,
+                    reason.lines().next().unwrap_or_default()
+                ));
+            }
+            return Err(e.context("Failed to merge worktree"));
+        }
+    };
+
+    if json {
+        let summary = Summary {
+            result: &result,
+            duration_ms: started.elapsed().as_millis(),
+        };
+        println!("{}", serde_json::to_string(&summary)?);
+        return Ok(());
+    }
 
     if result.had_staged_changes {
         println!("✓ Committed staged changes");
@@ -74,7 +162,309 @@ pub fn run(
             "✓ Successfully merged and cleaned up '{}'",
             result.branch_merged
         );
+        if result
+            .cleanup
+            .as_ref()
+            .is_some_and(|c| c.remote_branch_deleted)
+        {
+            println!("✓ Deleted branch '{}' on its remote", result.branch_merged);
+        }
+    }
+
+    Ok(())
+}
+
+/// `merge --json` output: the merge result and how long it took
+#[derive(Serialize)]
+struct Summary<'a> {
+    #[serde(flatten)]
+    result: &'a MergeResult,
+    duration_ms: u128,
+}
+
+/// `merge --commits`: cherry-pick selected commits, keeping the worktree
+fn cherry_pick(
+    name: &str,
+    into_branch: Option<&str>,
+    options: &MergeOptions,
+    started: Instant,
+    context: &WorkflowContext,
+) -> Result<()> {
+    if !options.no_verify {
+        super::announce_hooks(&context.config, None, super::HookPhase::PreMerge);
+    }
+    let result = workflow::cherry_pick(
+        name,
+        into_branch,
+        options.commits,
+        options.no_verify,
+        options.notification,
+        context,
+    )
+    .context("Failed to cherry-pick commits")?;
+
+    if options.json {
+        let summary = Summary {
+            result: &result,
+            duration_ms: started.elapsed().as_millis(),
+        };
+        println!("{}", serde_json::to_string(&summary)?);
+        return Ok(());
+    }
+
+    println!(
+        "✓ Cherry-picked {} commit(s) from '{}' onto '{}'",
+        result.cherry_picked.len(),
+        result.branch_merged,
+        result.main_branch
+    );
+    println!("Worktree, window, and branch kept");
+    Ok(())
+}
+
+/// `merge --pr`: merge the branch's PR on the forge, then clean up locally
+fn merge_pr(
+    name: &str,
+    strategy: MergeStrategy,
+    options: &MergeOptions,
+    context: &WorkflowContext,
+) -> Result<()> {
+    let MergeOptions {
+        keep,
+        no_verify,
+        notification,
+        no_wait,
+        ..
+    } = *options;
+    if !no_verify {
+        super::announce_hooks(&context.config, None, super::HookPhase::PreMerge);
+    }
+    if !keep {
+        super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
+    }
+
+    let result = match workflow::merge_pr(
+        name,
+        strategy,
+        keep,
+        no_verify,
+        notification,
+        !no_wait,
+        context,
+    ) {
+        Ok(result) => result,
+        Err(e) => {
+            if notification {
+                let reason = e.to_string();
+                notification::show(&format!(
+                    "Failed to merge '{}': {}",
+                    name,
+                    reason.lines().next().unwrap_or_default()
+                ));
+            }
+            return Err(e.context("Failed to merge PR"));
+        }
+    };
+
+    if !result.merged {
+        println!(
+            "{} is queued to merge but has not landed yet; worktree, window, and branch kept",
+            result.label
+        );
+        println!("Once it lands, clean up with: workmux remove --gone");
+        return Ok(());
+    }
+
+    println!("✓ Merged {} ('{}')", result.label, result.branch_merged);
+    if keep {
+        println!("Worktree, window, and branch kept");
+    } else {
+        println!(
+            "✓ Successfully merged and cleaned up '{}'",
+            result.branch_merged
+        );
+    }
+    Ok(())
+}
+
+/// `merge --check`: report conflicts without merging
+fn check_only(name: &str, into_branch: Option<&str>, context: &WorkflowContext) -> Result<()> {
+    let result = workflow::check_merge(name, into_branch, context)?;
+    if result.conflicts.is_empty() {
+        println!(
+            "✓ '{}' merges cleanly into '{}'",
+            result.branch, result.target
+        );
+        return Ok(());
     }
 
+    println!(
+        "✗ '{}' conflicts with '{}' in:",
+        result.branch, result.target
+    );
+    for file in &result.conflicts {
+        println!("  {}", file);
+    }
+    Err(ErrorKind::Conflicts.msg(format!(
+        "{} file(s) would conflict. Rebase or merge '{}' into the branch first.",
+        result.conflicts.len(),
+        result.target
+    )))
+}
+
+/// A done worktree as placed in a merge plan
+struct PlannedMerge {
+    handle: String,
+    target: String,
+    candidate: MergeCandidate,
+}
+
+/// `merge --plan`: order the worktrees whose agents are done so they merge
+/// with as few conflicts as possible, then offer to merge them in that order
+fn plan_merges(into_branch: Option<&str>, options: &MergeOptions) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let done_icon = config.status_icons.done().to_string();
+    let done_paths: HashSet<PathBuf> = daemon::agent_panes()
+        .into_iter()
+        .filter(|agent| agent.status.as_deref() == Some(done_icon.as_str()))
+        .map(|agent| agent.path)
+        .collect();
+
+    let main_root = git::get_main_worktree_root()?;
+    let worktrees: Vec<(PathBuf, String)> = git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, branch)| {
+            *path != main_root && !git::is_detached(branch) && done_paths.contains(path)
+        })
+        .collect();
+    if worktrees.is_empty() {
+        println!("No agents are done, nothing to merge.");
+        return Ok(());
+    }
+
+    let paths: Vec<PathBuf> = worktrees.iter().map(|(path, _)| path.clone()).collect();
+    let statuses = git::cached_git_statuses(&paths);
+    let branches: HashSet<&str> = worktrees.iter().map(|(_, b)| b.as_str()).collect();
+    let context = WorkflowContext::new(config)?;
+
+    let mut planned = Vec::new();
+    for (path, branch) in &worktrees {
+        let handle = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| branch.clone());
+        // Fall back to "no known conflicts" when the check can't run (older git)
+        let (target, conflicts_with_target) =
+            match workflow::check_merge(&handle, into_branch, &context) {
+                Ok(check) => (check.target, !check.conflicts.is_empty()),
+                Err(_) => (
+                    into_branch.unwrap_or(&context.main_branch).to_string(),
+                    false,
+                ),
+            };
+        let parent = git::get_branch_base(branch)
+            .ok()
+            .filter(|base| branches.contains(base.as_str()));
+        planned.push(PlannedMerge {
+            handle,
+            target,
+            candidate: MergeCandidate {
+                branch: branch.clone(),
+                parent,
+                files: statuses
+                    .get(path)
+                    .map(|s| s.changed_files.clone())
+                    .unwrap_or_default(),
+                conflicts_with_target,
+            },
+        });
+    }
+
+    let candidates: Vec<MergeCandidate> = planned.iter().map(|p| p.candidate.clone()).collect();
+    let order = conflicts::merge_order(&candidates);
+    // Merging the parent deletes its branch, so a stacked branch then merges
+    // wherever its parent went
+    if !options.keep {
+        for &i in &order {
+            let parent_target = planned[i].candidate.parent.as_ref().and_then(|parent| {
+                planned
+                    .iter()
+                    .find(|p| p.candidate.branch == *parent)
+                    .map(|p| p.target.clone())
+            });
+            if let Some(target) = parent_target {
+                planned[i].target = target;
+            }
+        }
+    }
+    print_plan(&planned, &order);
+
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+    print!("\nMerge in this order? [y/N] ");
+    io::stdout().flush().context("Failed to flush stdout")?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .context("Failed to read user input")?;
+    if input.trim().to_lowercase() != "y" {
+        return Err(ErrorKind::Aborted.msg("Aborted."));
+    }
+
+    for (step, &i) in order.iter().enumerate() {
+        let handle = &planned[i].handle;
+        println!("\n[{}/{}] {}", step + 1, order.len(), handle);
+        run(
+            Some(handle),
+            into_branch,
+            MergeOptions {
+                ignore_uncommitted: options.ignore_uncommitted,
+                rebase: options.rebase,
+                squash: options.squash,
+                keep: options.keep,
+                no_verify: options.no_verify,
+                delete_remote: options.delete_remote,
+                notification: options.notification,
+                ..Default::default()
+            },
+        )
+        .with_context(|| {
+            format!(
+                "Stopped the plan at '{}' ({} of {} merged)",
+                handle,
+                step,
+                order.len()
+            )
+        })?;
+    }
     Ok(())
 }
+
+fn print_plan(planned: &[PlannedMerge], order: &[usize]) {
+    println!("Merge plan:");
+    for (step, &i) in order.iter().enumerate() {
+        let merge = &planned[i];
+        println!("  {}. {} → {}", step + 1, merge.handle, merge.target);
+        if let Some(parent) = &merge.candidate.parent {
+            println!("       stacked on {}", parent);
+        }
+        if merge.candidate.conflicts_with_target {
+            println!(
+                "       already conflicts with {}, rebase it first",
+                merge.target
+            );
+        }
+        for &earlier in &order[..step] {
+            let shared =
+                conflicts::common_files(&planned[earlier].candidate.files, &merge.candidate.files);
+            if !shared.is_empty() {
+                println!(
+                    "       may need a rebase after {}: {}",
+                    planned[earlier].handle,
+                    shared.join(", ")
+                );
+            }
+        }
+    }
+}
@@ -1,20 +1,80 @@
 pub mod add;
+pub mod archive;
 pub mod args;
+pub mod attach;
+pub mod await_agent;
+pub mod cd;
 pub mod changelog;
 pub mod close;
+pub mod config;
+pub mod conflicts;
+pub mod daemon;
 pub mod dashboard;
 pub mod docs;
+pub mod env;
+pub mod gc;
+pub mod import;
+pub mod info;
+pub mod init;
 pub mod list;
+pub mod logs;
 pub mod merge;
 pub mod open;
 pub mod path;
+pub mod pr;
 pub mod remove;
+pub mod replay;
+pub mod restore;
+pub mod run;
+pub mod self_update;
+pub mod serve;
 pub mod set_base;
 pub mod set_window_status;
+pub mod setup;
+pub mod shell_init;
+pub mod status;
+pub mod statusline;
+pub mod sync;
+pub mod test;
+pub mod top;
+pub mod undo;
+pub mod watch;
+pub mod workspace;
 
 use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{config::Config, nuon, spinner, workflow::SetupOptions};
+
+/// Output of `list` and `status`
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Table for reading
+    #[default]
+    Table,
+    /// JSON, the same as `--json`
+    Json,
+    /// Nushell object notation, for `| from nuon`
+    Nuon,
+}
 
-use crate::{config::Config, workflow::SetupOptions};
+impl OutputFormat {
+    /// `format`, or JSON when the older `--json` flag was given
+    pub fn or_json(format: Self, json: bool) -> Self {
+        if json { OutputFormat::Json } else { format }
+    }
+
+    /// `value` serialized in this format, or None for a table, which each
+    /// command renders itself
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<Option<String>> {
+        match self {
+            OutputFormat::Table => Ok(None),
+            OutputFormat::Json => Ok(Some(serde_json::to_string_pretty(value)?)),
+            OutputFormat::Nuon => Ok(Some(nuon::to_string(value)?)),
+        }
+    }
+}
 
 /// Represents the different phases where hooks can be executed
 pub enum HookPhase {
@@ -32,7 +92,7 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
                 && config.post_create.as_ref().is_some_and(|v| !v.is_empty());
 
             if should_run {
-                println!("Running setup commands...");
+                spinner::message("Running setup commands...");
             }
             should_run
         }
@@ -40,7 +100,7 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
             let should_run = config.pre_merge.as_ref().is_some_and(|v| !v.is_empty());
 
             if should_run {
-                println!("Running pre-merge commands...");
+                spinner::message("Running pre-merge commands...");
             }
             should_run
         }
@@ -48,7 +108,7 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
             let should_run = config.pre_remove.as_ref().is_some_and(|v| !v.is_empty());
 
             if should_run {
-                println!("Running pre-remove commands...");
+                spinner::message("Running pre-remove commands...");
             }
             should_run
         }
@@ -63,6 +123,12 @@ pub fn resolve_name(arg: Option<&str>) -> Result<String> {
     match arg {
         Some(name) => Ok(name.to_string()),
         None => {
+            // The worktree's top level names it, wherever `worktree_dir` puts it
+            if let Ok(root) = crate::git::get_repo_root()
+                && let Some(name) = root.file_name().and_then(|n| n.to_str())
+            {
+                return Ok(name.to_string());
+            }
             let cwd = std::env::current_dir().context("Failed to get current directory")?;
             resolve_name_from_path(&cwd)
         }
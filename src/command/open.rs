@@ -1,7 +1,8 @@
 use crate::command::args::PromptArgs;
+use crate::prompt::{Prompt, parse_prompt_document, substitute_template_vars};
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt};
 use crate::workflow::{SetupOptions, WorkflowContext};
-use crate::{config, workflow};
+use crate::{config, git, metadata, workflow};
 use anyhow::{Context, Result};
 
 pub fn run(
@@ -9,9 +10,16 @@ pub fn run(
     run_hooks: bool,
     force_files: bool,
     new_window: bool,
+    layout: Option<&str>,
+    log_agent: bool,
     prompt_args: PromptArgs,
 ) -> Result<()> {
-    let config = config::Config::load(None)?;
+    let layout = layout
+        .map(str::to_string)
+        .or_else(|| metadata::load(name).and_then(|m| m.layout));
+    let config = config::Config::load(None)?
+        .for_worktree(name)
+        .with_layout(layout.as_deref())?;
     let context = WorkflowContext::new(config)?;
 
     // Load prompt if any prompt argument is provided
@@ -19,8 +27,26 @@ pub fn run(
         prompt_editor: prompt_args.prompt_editor,
         prompt_inline: prompt_args.prompt.as_deref(),
         prompt_file: prompt_args.prompt_file.as_ref(),
+        prompt_template: prompt_args.prompt_template.as_deref(),
     })?;
 
+    // Fill in template variables from the worktree being opened
+    let prompt = match (prompt, prompt_args.prompt_template.is_some()) {
+        (Some(template), true) => {
+            let mut values = prompt_args.template_vars()?;
+            let (_, branch) = git::find_worktree(name)
+                .with_context(|| format!("No worktree found with name '{}'", name))?;
+            if let Ok(base) = git::get_branch_base(&branch) {
+                values.insert("base".to_string(), base);
+            }
+            values.insert("branch".to_string(), branch);
+            values.insert("handle".to_string(), name.to_string());
+            let body = parse_prompt_document(&template)?.body;
+            Some(Prompt::Inline(substitute_template_vars(&body, &values)?))
+        }
+        (prompt, _) => prompt,
+    };
+
     // Write prompt to temp file if provided
     // Use unique filename with timestamp to prevent race condition when opening multiple duplicates
     let prompt_file_path = if let Some(ref p) = prompt {
@@ -40,6 +66,7 @@ pub fn run(
     // Construct setup options (pane commands always run on open)
     let mut options = SetupOptions::new(run_hooks, force_files, true);
     options.prompt_file_path = prompt_file_path;
+    options.log_agent = log_agent;
 
     // Only announce hooks if we're forcing a new window (otherwise we might just switch)
     if new_window {
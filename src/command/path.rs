@@ -1,14 +1,34 @@
-use crate::git;
+use crate::{git, porcelain};
 use anyhow::{Context, Result};
+use serde_json::json;
 
-pub fn run(name: &str) -> Result<()> {
+pub fn run(name: &str, json: bool, porcelain: bool) -> Result<()> {
     // Smart resolution: try handle first, then branch name
-    let (path, _branch) = git::find_worktree(name).with_context(|| {
+    let (path, branch) = git::find_worktree(name).with_context(|| {
         format!(
             "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
             name
         )
     })?;
-    println!("{}", path.display());
+
+    let handle = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.to_string());
+    if porcelain {
+        println!(
+            "{}",
+            porcelain::line(&[handle, branch, path.display().to_string()])
+        );
+    } else if json {
+        let output = json!({
+            "handle": handle,
+            "branch": branch,
+            "path": path,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("{}", path.display());
+    }
     Ok(())
 }
@@ -0,0 +1,278 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::forge::Forge;
+use crate::github::{ChecksState, PrStatus};
+use crate::workflow::{self, pr};
+use crate::{git, metadata, spinner};
+
+pub struct CreateOptions<'a> {
+    pub title: Option<&'a str>,
+    pub body: Option<&'a str>,
+    pub from_prompt: bool,
+    pub base: Option<&'a str>,
+    pub draft: bool,
+}
+
+pub fn create(name: Option<&str>, options: CreateOptions) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    if git::is_detached(&branch) {
+        return Err(anyhow!("Worktree '{}' is not on a branch", name));
+    }
+
+    let base = match options.base {
+        Some(base) => base.to_string(),
+        None => git::get_branch_base_in(&branch, Some(&path))
+            .or_else(|_| git::get_default_branch_in(Some(&path)))
+            .context("Failed to determine the base branch. Use --base to specify it.")?,
+    };
+
+    let commits = git::get_commit_messages(&path, &base)?;
+    if commits.is_empty() {
+        return Err(anyhow!(
+            "Branch '{}' has no commits on top of '{}'",
+            branch,
+            base
+        ));
+    }
+    let (generated_title, generated_body) = pr::pr_text_from_commits(&branch, &commits);
+
+    let prompt_body = if options.from_prompt {
+        let prompt_path = workflow::prompt_file_path(&branch);
+        let content = std::fs::read_to_string(&prompt_path).with_context(|| {
+            format!(
+                "No prompt file found for '{}' at {}",
+                branch,
+                prompt_path.display()
+            )
+        })?;
+        Some(content.trim().to_string())
+    } else {
+        None
+    };
+
+    let title = options.title.map(str::to_string).unwrap_or(generated_title);
+    let body = options
+        .body
+        .map(str::to_string)
+        .or(prompt_body)
+        .unwrap_or(generated_body);
+    // Close the issue the worktree was started from when the PR merges
+    let body = match git::get_branch_issue_in(&branch, Some(&path)) {
+        Some(issue) => workflow::issue::with_closing_reference(&body, issue),
+        None => body,
+    };
+
+    spinner::with_spinner(&format!("Pushing '{}'", branch), || {
+        git::push_branch(&path, "origin", &branch)
+    })?;
+    let forge = Forge::detect();
+    let (number, url) = spinner::with_spinner("Creating pull request", || {
+        forge.create_pr(&branch, &base, &title, &body, options.draft)
+    })?;
+    let label = forge.pr_label(number);
+
+    git::set_branch_pr(&branch, number)
+        .with_context(|| format!("Created {} but failed to record it", label))?;
+    if let Some(handle) = path.file_name().and_then(|n| n.to_str()) {
+        metadata::update(handle, |m| m.pr = Some(number));
+    }
+
+    println!("✓ Created {}: {}", label, title);
+    println!("  {}", url);
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct PrStatusRow {
+    #[tabled(rename = "WORKTREE")]
+    handle: String,
+    #[tabled(rename = "PR")]
+    number: String,
+    #[tabled(rename = "STATE")]
+    state: String,
+    #[tabled(rename = "REVIEW")]
+    review: String,
+    #[tabled(rename = "CI")]
+    checks: String,
+    #[tabled(rename = "MERGEABLE")]
+    mergeable: String,
+    #[tabled(rename = "TITLE")]
+    title: String,
+}
+
+#[derive(Serialize)]
+struct WorktreePrStatus {
+    handle: String,
+    branch: String,
+    pr: Option<PrStatus>,
+}
+
+fn format_review(review: Option<&str>) -> String {
+    match review {
+        Some("APPROVED") => "approved",
+        Some("CHANGES_REQUESTED") => "changes requested",
+        Some("REVIEW_REQUIRED") => "required",
+        Some(_) | None => "-",
+    }
+    .to_string()
+}
+
+fn format_checks(checks: ChecksState) -> String {
+    match checks {
+        ChecksState::None => "-",
+        ChecksState::Pending => "pending",
+        ChecksState::Passing => "passing",
+        ChecksState::Failing => "failing",
+    }
+    .to_string()
+}
+
+fn format_mergeable(pr: &PrStatus) -> String {
+    // Only meaningful while the PR is open
+    if pr.state != "OPEN" {
+        return "-".to_string();
+    }
+    match pr.mergeable.as_str() {
+        "MERGEABLE" => "yes",
+        "CONFLICTING" => "conflicts",
+        _ => "?",
+    }
+    .to_string()
+}
+
+fn status_row(status: &WorktreePrStatus) -> PrStatusRow {
+    let Some(pr) = &status.pr else {
+        return PrStatusRow {
+            handle: status.handle.clone(),
+            number: "-".to_string(),
+            state: "-".to_string(),
+            review: "-".to_string(),
+            checks: "-".to_string(),
+            mergeable: "-".to_string(),
+            title: "-".to_string(),
+        };
+    };
+    let state = if pr.state == "OPEN" && pr.is_draft {
+        "draft".to_string()
+    } else {
+        pr.state.to_lowercase()
+    };
+    PrStatusRow {
+        handle: status.handle.clone(),
+        number: format!("#{}", pr.number),
+        state,
+        review: format_review(pr.review.as_deref()),
+        checks: format_checks(pr.checks),
+        mergeable: format_mergeable(pr),
+        title: pr.title.clone(),
+    }
+}
+
+/// Show review, CI and merge state of each worktree's PR, fetched in one call
+pub fn status(names: &[String], web: bool, json: bool) -> Result<()> {
+    let worktrees = if names.is_empty() {
+        let main_root = git::get_main_worktree_root()?;
+        git::list_worktrees()?
+            .into_iter()
+            .filter(|(path, branch)| *path != main_root && !git::is_detached(branch))
+            .collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                git::find_worktree(name)
+                    .with_context(|| format!("No worktree found with name '{}'", name))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let forge = Forge::detect();
+    let mut prs = spinner::with_spinner("Fetching PR status", || forge.list_pr_statuses())?;
+
+    let statuses: Vec<WorktreePrStatus> = worktrees
+        .into_iter()
+        .map(|(path, branch)| WorktreePrStatus {
+            handle: path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string(),
+            pr: prs.remove(&branch),
+            branch,
+        })
+        .collect();
+
+    if web {
+        // Without names, open every PR that still needs attention
+        let to_open: Vec<&PrStatus> = statuses
+            .iter()
+            .filter_map(|s| s.pr.as_ref())
+            .filter(|pr| !names.is_empty() || pr.state == "OPEN")
+            .collect();
+        if to_open.is_empty() {
+            println!("No pull requests to open");
+        }
+        for pr in to_open {
+            forge.open_pr_in_browser(pr.number)?;
+        }
+        return Ok(());
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!("No worktrees found");
+        return Ok(());
+    }
+
+    let rows: Vec<PrStatusRow> = statuses.iter().map(status_row).collect();
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..6), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_row_describes_open_pr() {
+        let status = WorktreePrStatus {
+            handle: "login".to_string(),
+            branch: "fix-login".to_string(),
+            pr: Some(PrStatus {
+                number: 12,
+                title: "Fix login".to_string(),
+                state: "OPEN".to_string(),
+                is_draft: false,
+                url: String::new(),
+                review: Some("CHANGES_REQUESTED".to_string()),
+                checks: ChecksState::Failing,
+                mergeable: "CONFLICTING".to_string(),
+            }),
+        };
+        let row = status_row(&status);
+        assert_eq!(row.number, "#12");
+        assert_eq!(row.state, "open");
+        assert_eq!(row.review, "changes requested");
+        assert_eq!(row.checks, "failing");
+        assert_eq!(row.mergeable, "conflicts");
+    }
+}
@@ -1,29 +1,68 @@
+use crate::error::ErrorKind;
+use crate::forge::Forge;
+use crate::vcs::Vcs;
 use crate::workflow::WorkflowContext;
-use crate::{config, git, spinner, workflow};
+use crate::workflow::types::RemoveResult;
+use crate::{config, git, metadata, spinner, workflow};
 use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::io::{self, Write};
 use std::path::PathBuf;
+use std::time::Instant;
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     names: Vec<String>,
     gone: bool,
+    merged: bool,
     all: bool,
     force: bool,
     keep_branch: bool,
+    delete_remote: bool,
+    json: bool,
 ) -> Result<()> {
     if all {
-        return run_all(force, keep_branch);
+        return run_all(force, keep_branch, delete_remote);
     }
 
     if gone {
-        return run_gone(force, keep_branch);
+        return run_gone(force, keep_branch, delete_remote);
     }
 
-    run_specified(names, force, keep_branch)
+    if merged {
+        return run_merged(force, keep_branch, delete_remote);
+    }
+
+    if json {
+        spinner::reserve_stdout();
+    }
+    run_specified(names, force, keep_branch, delete_remote, json)
+}
+
+/// `remove --json` output
+#[derive(Serialize)]
+struct Summary {
+    removed: Vec<RemoveResult>,
+    failed: Vec<Failure>,
+    duration_ms: u128,
+}
+
+#[derive(Serialize)]
+struct Failure {
+    handle: String,
+    error: String,
 }
 
 /// Remove specific worktrees provided by user (or current if empty)
-fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<()> {
+fn run_specified(
+    names: Vec<String>,
+    force: bool,
+    keep_branch: bool,
+    delete_remote: bool,
+    json: bool,
+) -> Result<()> {
+    let started = Instant::now();
     // Normalize all inputs (handles "." and other special cases)
     let resolved_names: Vec<String> = if names.is_empty() {
         vec![super::resolve_name(None)?]
@@ -34,10 +73,19 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
             .collect::<Result<Vec<_>>>()?
     };
 
+    let context = WorkflowContext::new(config::Config::load(None)?)?;
+
     // 2. Resolve all targets and validate they exist
     let mut candidates: Vec<(String, PathBuf, String)> = Vec::new();
     for name in resolved_names {
-        let (worktree_path, branch_name) = git::find_worktree(&name)
+        let (worktree_path, branch_name) = context
+            .vcs
+            .find_worktree(
+                &name,
+                &context.config,
+                &context.main_worktree_root,
+                &context.main_branch,
+            )
             .with_context(|| format!("No worktree found with name '{}'", name))?;
 
         let handle = worktree_path
@@ -56,14 +104,19 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
 
     // 3. If forced, skip all checks and remove
     if force {
+        let mut removed = Vec::new();
         let mut failed: Vec<(String, String)> = Vec::new();
 
         for (handle, _, _) in candidates {
-            if let Err(e) = remove_worktree(&handle, true, keep_branch) {
-                failed.push((handle, e.to_string()));
+            match remove_worktree(&handle, true, keep_branch, delete_remote) {
+                Ok(result) => removed.push(result),
+                Err(e) => failed.push((handle, e.to_string())),
             }
         }
 
+        if json {
+            print_summary(removed, &failed, started)?;
+        }
         if !failed.is_empty() {
             eprintln!("\nFailed to remove {} worktree(s):", failed.len());
             for (handle, error) in &failed {
@@ -82,13 +135,16 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
 
     for (handle, path, branch) in candidates {
         // Check uncommitted (blocking)
-        if path.exists() && git::has_uncommitted_changes(&path).unwrap_or(false) {
+        if path.exists() && context.vcs.has_uncommitted_changes(&path).unwrap_or(false) {
             uncommitted.push(handle);
             continue;
         }
 
         // Check unmerged (promptable), only if we're deleting the branch
-        if !keep_branch && let Some(base) = is_unmerged(&branch)? {
+        if !keep_branch
+            && !git::is_detached(&branch)
+            && let Some(base) = is_unmerged(&context, &handle, &branch)?
+        {
             unmerged.push((handle, branch, base));
             continue;
         }
@@ -102,12 +158,18 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
         for handle in &uncommitted {
             eprintln!("  - {}", handle);
         }
-        return Err(anyhow!(
-            "Cannot remove worktrees with uncommitted changes. Use --force to override."
-        ));
+        return Err(ErrorKind::Dirty
+            .msg("Cannot remove worktrees with uncommitted changes. Use --force to override."));
     }
 
     // 6. Handle warnings (unmerged branches)
+    if !unmerged.is_empty() && json {
+        let branches: Vec<&str> = unmerged.iter().map(|(_, b, _)| b.as_str()).collect();
+        return Err(anyhow!(
+            "Branches have commits not merged into their base: {}. Use --force to remove them anyway.",
+            branches.join(", ")
+        ));
+    }
     if !unmerged.is_empty() {
         println!("The following branches have commits not merged into their base:");
         for (_, branch, base) in &unmerged {
@@ -123,8 +185,7 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
             .context("Failed to read input")?;
 
         if input.trim().to_lowercase() != "y" {
-            println!("Aborted.");
-            return Ok(());
+            return Err(ErrorKind::Aborted.msg("Aborted."));
         }
 
         // Add unmerged candidates to safe list for processing
@@ -134,21 +195,53 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
     }
 
     // 7. Execute removal
+    let mut removed = Vec::new();
     for handle in safe {
         // force=true because we already checked/prompted
-        remove_worktree(&handle, true, keep_branch)?;
+        removed.push(remove_worktree(&handle, true, keep_branch, delete_remote)?);
     }
 
+    if json {
+        print_summary(removed, &[], started)?;
+    }
+    Ok(())
+}
+
+fn print_summary(
+    removed: Vec<RemoveResult>,
+    failed: &[(String, String)],
+    started: Instant,
+) -> Result<()> {
+    let summary = Summary {
+        removed,
+        failed: failed
+            .iter()
+            .map(|(handle, error)| Failure {
+                handle: handle.clone(),
+                error: error.clone(),
+            })
+            .collect(),
+        duration_ms: started.elapsed().as_millis(),
+    };
+    println!("{}", serde_json::to_string(&summary)?);
     Ok(())
 }
 
 /// Check if a branch has unmerged commits. Returns Some(base) if unmerged, None otherwise.
-fn is_unmerged(branch: &str) -> Result<Option<String>> {
+fn is_unmerged(context: &WorkflowContext, handle: &str, branch: &str) -> Result<Option<String>> {
+    if context.vcs == Vcs::Jj {
+        let base = metadata::load(handle)
+            .and_then(|m| m.base)
+            .unwrap_or_else(|| context.main_branch.clone());
+        let unmerged = context
+            .vcs
+            .is_unmerged(&context.main_worktree_root, handle, branch, &base);
+        return Ok(unmerged.then_some(base));
+    }
+
     let main_branch = git::get_default_branch().unwrap_or_else(|_| "main".to_string());
 
-    let base = git::get_branch_base(branch)
-        .ok()
-        .unwrap_or_else(|| main_branch.clone());
+    let base = git::get_branch_base_ref_in(branch, None).unwrap_or_else(|| main_branch.clone());
 
     let base_commit = match git::get_merge_base(&base) {
         Ok(b) => b,
@@ -170,7 +263,7 @@ fn is_unmerged(branch: &str) -> Result<Option<String>> {
 }
 
 /// Remove all managed worktrees (except main)
-fn run_all(force: bool, keep_branch: bool) -> Result<()> {
+fn run_all(force: bool, keep_branch: bool, delete_remote: bool) -> Result<()> {
     let worktrees = git::list_worktrees()?;
     let main_branch = git::get_default_branch()?;
     let main_worktree_root = git::get_main_worktree_root()?;
@@ -181,7 +274,7 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
 
     for (path, branch) in worktrees {
         // Skip main branch/worktree and detached HEAD
-        if branch == main_branch || branch == "(detached)" {
+        if branch == main_branch || git::is_detached(&branch) {
             continue;
         }
 
@@ -198,9 +291,8 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
 
         // Check for unmerged commits (only when deleting the branch)
         if !force && !keep_branch {
-            let base = git::get_branch_base(&branch)
-                .ok()
-                .unwrap_or_else(|| main_branch.clone());
+            let base =
+                git::get_branch_base_ref_in(&branch, None).unwrap_or_else(|| main_branch.clone());
             if let Ok(merge_base) = git::get_merge_base(&base)
                 && let Ok(unmerged_branches) = git::get_unmerged_branches(&merge_base)
                 && unmerged_branches.contains(&branch)
@@ -288,8 +380,7 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
             .context("Failed to read user input")?;
 
         if input.trim().to_lowercase() != "y" {
-            println!("Aborted.");
-            return Ok(());
+            return Err(ErrorKind::Aborted.msg("Aborted."));
         }
     }
 
@@ -298,8 +389,8 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
     let mut failed: Vec<(String, String)> = Vec::new();
 
     for (_, branch, handle) in to_remove {
-        match remove_worktree(&handle, true, keep_branch) {
-            Ok(()) => success_count += 1,
+        match remove_worktree(&handle, true, keep_branch, delete_remote) {
+            Ok(_) => success_count += 1,
             Err(e) => failed.push((branch, e.to_string())),
         }
     }
@@ -319,34 +410,96 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
     Ok(())
 }
 
-/// Remove worktrees whose upstream remote branch has been deleted
-fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
+/// Remove worktrees whose upstream remote branch has been deleted, or whose
+/// PR has been merged
+fn run_gone(force: bool, keep_branch: bool, delete_remote: bool) -> Result<()> {
     // Fetch with prune to update remote-tracking refs
     spinner::with_spinner("Fetching from remote", git::fetch_prune)?;
 
-    let worktrees = git::list_worktrees()?;
+    let candidates = batch_candidates(&git::get_default_branch()?)?;
+    let branches: Vec<&str> = candidates.iter().map(|(_, b)| b.as_str()).collect();
+    let reasons = gone_reasons(&branches);
+    if !candidates.iter().any(|(_, b)| reasons.contains_key(b)) {
+        println!("No worktrees with gone upstreams or merged PRs found.");
+        return Ok(());
+    }
+    remove_batch(
+        candidates,
+        &reasons,
+        "gone",
+        force,
+        keep_branch,
+        delete_remote,
+    )
+    .map(|_| ())
+}
+
+/// Remove worktrees whose branch is fully merged into the default branch
+fn run_merged(force: bool, keep_branch: bool, delete_remote: bool) -> Result<()> {
     let main_branch = git::get_default_branch()?;
+    let candidates = batch_candidates(&main_branch)?;
+    let branches: Vec<&str> = candidates.iter().map(|(_, b)| b.as_str()).collect();
+    let reasons = merged_reasons(&branches, &main_branch)?;
+    if reasons.is_empty() {
+        println!("No worktrees merged into '{}' found.", main_branch);
+        return Ok(());
+    }
+    remove_batch(
+        candidates,
+        &reasons,
+        "merged",
+        force,
+        keep_branch,
+        delete_remote,
+    )
+    .map(|_| ())
+}
+
+/// Worktrees `--gone` and `--merged` may remove: all but the main worktree,
+/// the main branch and detached ones
+fn batch_candidates(main_branch: &str) -> Result<Vec<(PathBuf, String)>> {
     let main_worktree_root = git::get_main_worktree_root()?;
+    Ok(git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, branch)| {
+            branch != main_branch && !git::is_detached(branch) && *path != main_worktree_root
+        })
+        .collect())
+}
 
-    let gone_branches = git::get_gone_branches().unwrap_or_default();
+/// Why each branch counts as merged into `main_branch`, keyed by branch.
+///
+/// A branch still at the commit it was created from is fully merged too, but
+/// it is new work rather than finished work, so it is left out.
+fn merged_reasons(branches: &[&str], main_branch: &str) -> Result<HashMap<String, String>> {
+    let unmerged = git::get_unmerged_branches(main_branch)?;
+    Ok(branches
+        .iter()
+        .filter(|b| !unmerged.contains(**b))
+        .filter(|b| {
+            let tip = git::resolve_commit(&format!("refs/heads/{}", b)).ok();
+            tip.is_some() && tip != git::get_branch_base_sha(b)
+        })
+        .map(|b| (b.to_string(), format!("merged into {}", main_branch)))
+        .collect())
+}
 
-    // Find worktrees whose upstream is gone
+/// Remove the candidates that have a reason, after a single confirmation.
+/// Worktrees with uncommitted changes are skipped unless `force`. Returns the
+/// handles of the removed worktrees.
+pub(super) fn remove_batch(
+    candidates: Vec<(PathBuf, String)>,
+    reasons: &HashMap<String, String>,
+    label: &str,
+    force: bool,
+    keep_branch: bool,
+    delete_remote: bool,
+) -> Result<Vec<String>> {
     let mut to_remove: Vec<(PathBuf, String, String)> = Vec::new();
     let mut skipped_uncommitted: Vec<String> = Vec::new();
 
-    for (path, branch) in worktrees {
-        // Skip main branch/worktree and detached HEAD
-        if branch == main_branch || branch == "(detached)" {
-            continue;
-        }
-
-        // Skip the main worktree itself
-        if path == main_worktree_root {
-            continue;
-        }
-
-        // Check if upstream is gone
-        if !gone_branches.contains(&branch) {
+    for (path, branch) in candidates {
+        if !reasons.contains_key(&branch) {
             continue;
         }
 
@@ -365,11 +518,6 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
         to_remove.push((path, branch, handle));
     }
 
-    if to_remove.is_empty() && skipped_uncommitted.is_empty() {
-        println!("No worktrees with gone upstreams found.");
-        return Ok(());
-    }
-
     if to_remove.is_empty() {
         println!("No worktrees to remove.");
         if !skipped_uncommitted.is_empty() {
@@ -382,13 +530,13 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
             }
             println!("\nUse --force to remove these anyway.");
         }
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     // Show what will be removed
-    println!("The following worktrees have gone upstreams and will be removed:");
+    println!("The following worktrees are {} and will be removed:", label);
     for (_, branch, _) in &to_remove {
-        println!("  - {}", branch);
+        println!("  - {} ({})", branch, reasons[branch]);
     }
 
     if !skipped_uncommitted.is_empty() {
@@ -415,25 +563,24 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
             .context("Failed to read user input")?;
 
         if input.trim().to_lowercase() != "y" {
-            println!("Aborted.");
-            return Ok(());
+            return Err(ErrorKind::Aborted.msg("Aborted."));
         }
     }
 
     // Execute removal
-    let mut success_count = 0;
+    let mut removed = Vec::new();
     let mut failed: Vec<(String, String)> = Vec::new();
 
     for (_, branch, handle) in to_remove {
-        match remove_worktree(&handle, true, keep_branch) {
-            Ok(()) => success_count += 1,
+        match remove_worktree(&handle, true, keep_branch, delete_remote) {
+            Ok(_) => removed.push(handle),
             Err(e) => failed.push((branch, e.to_string())),
         }
     }
 
     // Report results
-    if success_count > 0 {
-        println!("\n✓ Successfully removed {} worktree(s)", success_count);
+    if !removed.is_empty() {
+        println!("\n✓ Successfully removed {} worktree(s)", removed.len());
     }
 
     if !failed.is_empty() {
@@ -443,30 +590,97 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(removed)
 }
 
-/// Execute the actual worktree removal
-fn remove_worktree(handle: &str, force: bool, keep_branch: bool) -> Result<()> {
-    let config = config::Config::load(None)?;
+/// Why each gone branch counts as gone, keyed by branch.
+///
+/// A branch is gone when its upstream was deleted, or when its PR was merged
+/// with the branch still at the PR head. The PR check covers branches that
+/// were squash-merged in the web UI without ever having an upstream set, and
+/// the head comparison keeps a reused branch name or unpushed work from being
+/// mistaken for the merged PR.
+pub(super) fn gone_reasons(branches: &[&str]) -> HashMap<String, String> {
+    let gone_upstreams = git::get_gone_branches().unwrap_or_default();
+    let mut reasons: HashMap<String, String> = branches
+        .iter()
+        .filter(|b| gone_upstreams.contains(**b))
+        .map(|b| (b.to_string(), "upstream deleted".to_string()))
+        .collect();
+
+    if branches.iter().all(|b| reasons.contains_key(*b)) {
+        return reasons;
+    }
+
+    let forge = Forge::detect();
+    let prs = spinner::with_spinner("Checking PR status", || {
+        Ok(forge.list_prs().unwrap_or_default())
+    })
+    .unwrap_or_default();
+
+    for branch in branches {
+        if reasons.contains_key(*branch) {
+            continue;
+        }
+        let Some(pr) = prs.get(*branch) else {
+            continue;
+        };
+        if pr.state != "MERGED" {
+            continue;
+        }
+        let branch_ref = format!("refs/heads/{}", branch);
+        let at_pr_head = match (&pr.head_sha, git::resolve_commit(&branch_ref)) {
+            (Some(head), Ok(tip)) => *head == tip,
+            _ => false,
+        };
+        if at_pr_head {
+            reasons.insert(
+                branch.to_string(),
+                format!("{} merged", forge.pr_label(pr.number)),
+            );
+        }
+    }
+
+    reasons
+}
+
+/// Execute the actual worktree removal. The remote branch is deleted with
+/// `delete_remote` or `remove.delete_remote_branch`, unless the branch is kept.
+pub(super) fn remove_worktree(
+    handle: &str,
+    force: bool,
+    keep_branch: bool,
+    delete_remote: bool,
+) -> Result<RemoveResult> {
+    let config = config::Config::load(None)?.for_worktree(handle);
     let context = WorkflowContext::new(config)?;
+    let delete_remote = !keep_branch
+        && (delete_remote || context.config.remove.delete_remote_branch.unwrap_or(false));
 
     super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
 
-    let result = workflow::remove(handle, force, keep_branch, &context)
+    let result = workflow::remove(handle, force, keep_branch, delete_remote, &context)
         .context("Failed to remove worktree")?;
 
-    if keep_branch {
-        println!(
+    if git::is_detached(&result.branch_removed) {
+        spinner::message(&format!("✓ Removed detached worktree '{}'", handle));
+    } else if keep_branch {
+        spinner::message(&format!(
             "✓ Removed worktree '{}' (branch '{}' kept)",
             handle, result.branch_removed
-        );
+        ));
     } else {
-        println!(
+        spinner::message(&format!(
             "✓ Removed worktree '{}' and branch '{}'",
             handle, result.branch_removed
-        );
+        ));
+    }
+    if result.cleanup.remote_branch_deleted {
+        spinner::message(&format!(
+            "✓ Deleted branch '{}' on its remote",
+            result.branch_removed
+        ));
     }
 
-    Ok(())
+    Ok(result)
 }
@@ -0,0 +1,147 @@
+use crate::command::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
+use crate::git;
+use crate::metadata::{self, WorktreeMetadata};
+use anyhow::{Context, Result, anyhow};
+use tracing::info;
+
+/// Only used when `add` creates several worktrees, which a replay never does
+const BRANCH_TEMPLATE: &str = "{{ base_name }}";
+
+/// Create a new worktree the way an existing one was created: from the same
+/// base, with the same agent, prompt (or prompt template and `--var` values),
+/// package and setup steps.
+pub fn run(name: &str, branch: Option<&str>, same_commit: bool, background: bool) -> Result<()> {
+    let (path, _) = git::find_worktree(name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.to_string());
+    let recorded = metadata::load(&handle).ok_or_else(|| {
+        anyhow!(
+            "No record of how '{}' was created. Only worktrees created with `workmux add` can be replayed.",
+            handle
+        )
+    })?;
+
+    let base = if same_commit {
+        Some(
+            recorded
+                .base_sha
+                .clone()
+                .ok_or_else(|| anyhow!("The base commit of '{}' was not recorded", handle))?,
+        )
+    } else {
+        recorded.base.clone()
+    };
+    let new_branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => next_attempt_branch(&recorded.branch)?,
+    };
+    info!(handle, new_branch, base = ?base, "replay:start");
+    println!("Replaying '{}' as '{}'", handle, new_branch);
+
+    let setup = recorded.setup.unwrap_or(metadata::SetupSteps {
+        hooks: true,
+        file_ops: true,
+        pane_commands: true,
+    });
+    super::add::run(
+        Some(&new_branch),
+        None,
+        recorded.issue,
+        false,
+        base.as_deref(),
+        None,
+        recorded.layout.as_deref(),
+        false,
+        false,
+        None,
+        recorded.package.as_deref(),
+        prompt_args(&recorded),
+        SetupFlags {
+            no_hooks: !setup.hooks,
+            no_file_ops: !setup.file_ops,
+            no_pane_cmds: !setup.pane_commands,
+            background,
+            log_agent: false,
+        },
+        RescueArgs {
+            with_changes: false,
+            patch: false,
+            include_untracked: false,
+        },
+        MultiArgs {
+            agent: recorded.agent.into_iter().collect(),
+            count: None,
+            foreach: None,
+            stdin: false,
+            branch_template: BRANCH_TEMPLATE.to_string(),
+            max_concurrent: None,
+        },
+        false,
+        false,
+    )
+}
+
+/// A prompt template is rendered again so `{branch}` and `{handle}` name the
+/// new worktree; otherwise the agent gets the recorded prompt text. Worktrees
+/// started from an issue get the issue text again through `--from-issue`.
+fn prompt_args(recorded: &WorktreeMetadata) -> PromptArgs {
+    let prompt = match (&recorded.template, recorded.issue) {
+        (None, None) => recorded.prompt.clone(),
+        _ => None,
+    };
+    PromptArgs {
+        prompt,
+        prompt_file: None,
+        prompt_editor: false,
+        prompt_template: recorded.template.clone(),
+        vars: recorded
+            .template_vars
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    }
+}
+
+/// `<branch>-2`, or the next free number when earlier attempts exist
+fn next_attempt_branch(branch: &str) -> Result<String> {
+    for attempt in 2.. {
+        let candidate = format!("{}-{}", branch, attempt);
+        if !git::branch_exists(&candidate)? {
+            return Ok(candidate);
+        }
+    }
+    unreachable!("ran out of attempt numbers")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_args_prefer_template_over_recorded_prompt() {
+        let mut recorded = WorktreeMetadata {
+            branch: "fix-login".to_string(),
+            prompt: Some("Fix the login for fix-login".to_string()),
+            ..Default::default()
+        };
+        let args = prompt_args(&recorded);
+        assert_eq!(args.prompt.as_deref(), Some("Fix the login for fix-login"));
+        assert_eq!(args.prompt_template, None);
+
+        recorded.template = Some("bugfix".to_string());
+        recorded
+            .template_vars
+            .insert("ticket".to_string(), "ABC-1".to_string());
+        let args = prompt_args(&recorded);
+        assert_eq!(args.prompt, None);
+        assert_eq!(args.prompt_template.as_deref(), Some("bugfix"));
+        assert_eq!(args.vars, vec![("ticket".to_string(), "ABC-1".to_string())]);
+    }
+}
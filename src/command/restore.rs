@@ -0,0 +1,175 @@
+use crate::archive::{self, ArchivedWorktree};
+use crate::metadata::WorktreeMetadata;
+use crate::workflow::types::CreateArgs;
+use crate::workflow::{SetupOptions, WorkflowContext};
+use crate::{config, daemon, duration, git, metadata, spinner, workflow};
+use anyhow::{Context, Result, anyhow};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+use tracing::warn;
+
+#[derive(Tabled)]
+struct ArchiveRow {
+    #[tabled(rename = "NAME")]
+    handle: String,
+    #[tabled(rename = "BRANCH")]
+    branch: String,
+    #[tabled(rename = "ARCHIVED")]
+    archived: String,
+    #[tabled(rename = "CHANGES")]
+    changes: String,
+}
+
+pub fn run(name: Option<&str>) -> Result<()> {
+    match name {
+        Some(name) => restore(name),
+        None => list(),
+    }
+}
+
+/// List archived worktrees
+fn list() -> Result<()> {
+    let archived = archive::load_all();
+    if archived.is_empty() {
+        println!("No archived worktrees");
+        return Ok(());
+    }
+    let now = daemon::now_secs();
+    let rows: Vec<ArchiveRow> = archived
+        .into_iter()
+        .map(|(handle, record)| ArchiveRow {
+            handle,
+            branch: record.metadata.branch,
+            archived: format!(
+                "{} ago",
+                duration::format_duration(now.saturating_sub(record.archived_at))
+            ),
+            changes: if record.wip_commit {
+                "uncommitted".to_string()
+            } else {
+                "-".to_string()
+            },
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..3), Padding::new(0, 1, 0, 0));
+    println!("{table}");
+    Ok(())
+}
+
+/// Recreate an archived worktree, its branch, and its window
+fn restore(handle: &str) -> Result<()> {
+    let record = archive::load(handle).ok_or_else(|| {
+        anyhow!(
+            "No archived worktree named '{}'. Run 'workmux restore' to list them.",
+            handle
+        )
+    })?;
+    let branch = record.metadata.branch.as_str();
+    let config = config::Config::load(None)?.for_branch(branch);
+    let context = WorkflowContext::new(config)?;
+    if git::branch_exists(branch)? {
+        return Err(anyhow!(
+            "Branch '{}' already exists. Rename or delete it before restoring '{}'.",
+            branch,
+            handle
+        ));
+    }
+
+    if git::resolve_commit(&record.ref_name).is_err() {
+        let remote = record.remote.as_deref().ok_or_else(|| {
+            anyhow!(
+                "The archived branch is gone: {} no longer exists",
+                record.ref_name
+            )
+        })?;
+        spinner::with_spinner(
+            &format!("Fetching {} from '{}'", record.ref_name, remote),
+            || git::fetch_ref(remote, &record.ref_name),
+        )?;
+    }
+    git::create_branch_at(branch, &record.ref_name)?;
+    restore_branch_config(branch, &record.metadata);
+
+    let options = SetupOptions::new(true, true, true);
+    super::announce_hooks(
+        &context.config,
+        Some(&options),
+        super::HookPhase::PostCreate,
+    );
+    let result = workflow::create(
+        &context,
+        CreateArgs {
+            branch_name: branch,
+            handle,
+            base_branch: None,
+            remote_branch: None,
+            prompt: None,
+            options,
+            agent: record.metadata.agent.as_deref(),
+            package: record.package.as_deref(),
+            detached: false,
+        },
+    )
+    .context("Failed to recreate worktree")?;
+
+    if record.wip_commit {
+        git::uncommit_in(&result.worktree_path)
+            .context("Failed to turn the archived changes back into uncommitted changes")?;
+    }
+    // The worktree is back with its original history, not as a new one
+    if let Err(e) = metadata::save(handle, &record.metadata) {
+        warn!(handle, error = %e, "restore:failed to save worktree metadata");
+    }
+    forget(handle, &record);
+
+    println!(
+        "✓ Restored '{}' from {}\n  Worktree: {}",
+        handle,
+        record.ref_name,
+        result.worktree_path.display()
+    );
+    Ok(())
+}
+
+/// Bring back the per-branch git config that was deleted with the branch
+pub(super) fn restore_branch_config(branch: &str, metadata: &WorktreeMetadata) {
+    let results = [
+        metadata
+            .base
+            .as_deref()
+            .map(|base| git::set_branch_base(branch, base)),
+        metadata
+            .base_sha
+            .as_deref()
+            .map(|sha| git::set_branch_base_sha(branch, sha)),
+        metadata.pr.map(|pr| git::set_branch_pr(branch, pr)),
+        metadata
+            .issue
+            .map(|issue| git::set_branch_issue(branch, issue)),
+    ];
+    for e in results.into_iter().flatten().filter_map(Result::err) {
+        warn!(branch, error = %e, "restore:failed to restore branch config");
+    }
+}
+
+/// Drop the archive ref and record of a restored worktree
+fn forget(handle: &str, record: &ArchivedWorktree) {
+    if let Err(e) = git::delete_ref(&record.ref_name) {
+        warn!(handle, error = %e, "restore:failed to delete archive ref");
+    }
+    if let Some(ref remote) = record.remote
+        && let Err(e) = git::delete_remote_ref(remote, &record.ref_name)
+    {
+        eprintln!(
+            "Warning: could not delete {} on '{}': {:#}",
+            record.ref_name, remote, e
+        );
+    }
+    archive::delete(handle);
+}
@@ -0,0 +1,45 @@
+use crate::git;
+use crate::workflow::hooks::HookEnv;
+use anyhow::{Context, Result, anyhow};
+use std::process::Command;
+use tracing::info;
+
+/// Run a command in a worktree's directory, streaming its output. Exits with
+/// the command's exit code if it fails, so scripts can check it.
+pub fn run(name: &str, command: &[String], hook_env: bool) -> Result<()> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| anyhow!("No command given. Usage: workmux run <name> -- <command>"))?;
+
+    let (worktree_path, branch) = git::find_worktree(name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = worktree_path
+        .file_name()
+        .ok_or_else(|| anyhow!("Invalid worktree path: no directory name"))?
+        .to_string_lossy()
+        .to_string();
+
+    let mut cmd = Command::new(program);
+    cmd.args(args).current_dir(&worktree_path);
+    if hook_env {
+        // The same variables hooks get
+        let project_root = git::get_main_worktree_root()?;
+        let env = HookEnv::new(&handle, &worktree_path, &project_root).with_branch(&branch);
+        cmd.envs(env.vars());
+    }
+
+    info!(handle, command = ?command, "run:start");
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to run '{}'", program))?;
+    info!(handle, status = ?status.code(), "run:finished");
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
@@ -0,0 +1,286 @@
+//! Update the running binary from the latest GitHub release.
+//!
+//! Release artifacts are built by `.github/workflows/release.yml`: one
+//! `workmux-<os>-<arch>.tar.gz` per platform with a matching `.sha256` file.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::cmd::Cmd;
+use crate::config::Config;
+use crate::spinner;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/raine/workmux/releases/latest";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn asset_url(&self, name: &str) -> Result<&str> {
+        self.assets
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.browser_download_url.as_str())
+            .ok_or_else(|| anyhow!("Release {} has no asset named {}", self.tag_name, name))
+    }
+}
+
+pub fn run(check: bool, force: bool) -> Result<()> {
+    let config = Config::load(None)?;
+    if config.self_update == Some(false) {
+        return Err(anyhow!(
+            "Self-update is disabled by 'self_update: false' in your config. Update workmux with your package manager instead."
+        ));
+    }
+
+    let current = env!("CARGO_PKG_VERSION");
+    let release = spinner::with_spinner("Checking for updates", latest_release)?;
+    let latest = release.tag_name.trim_start_matches('v');
+
+    if !is_newer(latest, current) && !force {
+        println!("✓ workmux {} is up to date", current);
+        return Ok(());
+    }
+    if check {
+        println!(
+            "Update available: {} → {}. Run 'workmux self-update' to install it.",
+            current, latest
+        );
+        return Ok(());
+    }
+
+    let artifact = artifact_for(std::env::consts::OS, std::env::consts::ARCH).ok_or_else(|| {
+        anyhow!(
+            "No prebuilt binary for {}-{}. Build from source with 'cargo install workmux'.",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })?;
+    let exe = std::env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .context("Failed to locate the running executable")?;
+
+    let work_dir = std::env::temp_dir().join(format!("workmux-update-{}", std::process::id()));
+    std::fs::create_dir_all(&work_dir)
+        .with_context(|| format!("Failed to create {}", work_dir.display()))?;
+    let result = spinner::with_spinner(&format!("Downloading workmux {}", latest), || {
+        download_and_verify(&release, &artifact, &work_dir)
+    })
+    .and_then(|binary| replace_executable(&binary, &exe));
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result?;
+
+    println!("✓ Updated workmux {} → {}", current, latest);
+    println!("  {}", exe.display());
+    Ok(())
+}
+
+fn latest_release() -> Result<Release> {
+    let json = Cmd::new("curl")
+        .args(&[
+            "-fsSL",
+            "-H",
+            "Accept: application/vnd.github+json",
+            LATEST_RELEASE_URL,
+        ])
+        .run_and_capture_stdout()
+        .context("Failed to fetch the latest release from GitHub")?;
+    serde_json::from_str(&json).context("Failed to parse GitHub release JSON")
+}
+
+/// Download the release archive, check it against the published checksum and
+/// extract it, returning the path of the extracted binary
+fn download_and_verify(release: &Release, artifact: &str, work_dir: &Path) -> Result<PathBuf> {
+    let archive_name = format!("{}.tar.gz", artifact);
+    let checksum_name = format!("{}.sha256", artifact);
+    let archive = work_dir.join(&archive_name);
+    let checksum_file = work_dir.join(&checksum_name);
+
+    for (name, path) in [(&archive_name, &archive), (&checksum_name, &checksum_file)] {
+        Cmd::new("curl")
+            .args(&[
+                "-fsSL",
+                "-o",
+                &path.to_string_lossy(),
+                release.asset_url(name)?,
+            ])
+            .run()
+            .with_context(|| format!("Failed to download {}", name))?;
+    }
+
+    let published = std::fs::read_to_string(&checksum_file)?;
+    let expected = parse_checksum(&published)
+        .ok_or_else(|| anyhow!("{} does not contain a checksum", checksum_name))?;
+    let actual = sha256(&archive)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            archive_name,
+            expected,
+            actual
+        ));
+    }
+
+    Cmd::new("tar")
+        .args(&[
+            "-xzf",
+            &archive.to_string_lossy(),
+            "-C",
+            &work_dir.to_string_lossy(),
+        ])
+        .run()
+        .context("Failed to extract the release archive")?;
+    let binary = work_dir.join("workmux");
+    if !binary.is_file() {
+        return Err(anyhow!("Release archive does not contain a workmux binary"));
+    }
+    Ok(binary)
+}
+
+/// SHA-256 of a file, using whichever of `sha256sum` (Linux) or `shasum`
+/// (macOS) is available
+fn sha256(path: &Path) -> Result<String> {
+    let path = path.to_string_lossy();
+    let output = Cmd::new("sha256sum")
+        .arg(&path)
+        .run_and_capture_stdout()
+        .or_else(|_| {
+            Cmd::new("shasum")
+                .args(&["-a", "256", &path])
+                .run_and_capture_stdout()
+        })
+        .context("Failed to compute checksum (sha256sum or shasum is required)")?;
+    parse_checksum(&output)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Unexpected checksum output: {}", output))
+}
+
+/// Swap in the new binary. The file is staged next to the executable so the
+/// final rename stays on one filesystem and is atomic.
+fn replace_executable(new_binary: &Path, exe: &Path) -> Result<()> {
+    let staged = exe.with_file_name(format!(".workmux-update-{}", std::process::id()));
+    let result = stage_and_rename(new_binary, &staged, exe);
+    if result.is_err() {
+        let _ = std::fs::remove_file(&staged);
+    }
+    result.with_context(|| {
+        format!(
+            "Failed to replace {}. Check that you can write to its directory.",
+            exe.display()
+        )
+    })
+}
+
+fn stage_and_rename(new_binary: &Path, staged: &Path, exe: &Path) -> Result<()> {
+    std::fs::copy(new_binary, staged)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    std::fs::rename(staged, exe)?;
+    Ok(())
+}
+
+/// Release artifact name for a platform, as named by the release workflow
+fn artifact_for(os: &str, arch: &str) -> Option<String> {
+    let os = match os {
+        "macos" => "darwin",
+        "linux" => "linux",
+        _ => return None,
+    };
+    let arch = match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        _ => return None,
+    };
+    Some(format!("workmux-{}-{}", os, arch))
+}
+
+/// First field of `sha256sum`-style output (`<hex>  <file>`)
+fn parse_checksum(output: &str) -> Option<&str> {
+    output.split_whitespace().next()
+}
+
+/// Whether `candidate` is a newer dotted version than `current`
+fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+    parts(candidate) > parts(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_newer_compares_numerically() {
+        assert!(is_newer("0.1.87", "0.1.86"));
+        assert!(is_newer("0.2.0", "0.1.99"));
+        assert!(is_newer("0.1.10", "0.1.9"));
+        assert!(!is_newer("0.1.86", "0.1.86"));
+        assert!(!is_newer("0.1.85", "0.1.86"));
+    }
+
+    #[test]
+    fn artifact_for_matches_release_workflow() {
+        assert_eq!(
+            artifact_for("macos", "aarch64").as_deref(),
+            Some("workmux-darwin-arm64")
+        );
+        assert_eq!(
+            artifact_for("linux", "x86_64").as_deref(),
+            Some("workmux-linux-amd64")
+        );
+        assert_eq!(artifact_for("windows", "x86_64"), None);
+    }
+
+    #[test]
+    fn replace_executable_swaps_file_in_place() {
+        let tmp = tempfile::tempdir().unwrap();
+        let new_binary = tmp.path().join("new");
+        let exe = tmp.path().join("bin/workmux");
+        std::fs::create_dir_all(exe.parent().unwrap()).unwrap();
+        std::fs::write(&new_binary, "new").unwrap();
+        std::fs::write(&exe, "old").unwrap();
+
+        replace_executable(&new_binary, &exe).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&exe).unwrap(), "new");
+        let leftovers: Vec<_> = std::fs::read_dir(exe.parent().unwrap())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .collect();
+        assert_eq!(leftovers.len(), 1);
+    }
+
+    #[test]
+    fn parse_checksum_reads_first_field() {
+        assert_eq!(
+            parse_checksum("abc123  workmux-linux-amd64.tar.gz\n"),
+            Some("abc123")
+        );
+        assert_eq!(parse_checksum(""), None);
+    }
+}
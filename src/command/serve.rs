@@ -0,0 +1,483 @@
+//! `workmux serve`: core operations over JSON-RPC on a unix socket.
+//!
+//! Editor plugins connect to the socket and send JSON-RPC 2.0 requests, one
+//! per line, and get one response line back for each (none for
+//! notifications, which have no `id`). The `version` method reports
+//! [`SCHEMA_VERSION`], which changes whenever a method's params or result
+//! change incompatibly.
+//!
+//! Operations on a repository (`list`, `add`, `merge`, `remove`) run
+//! `workmux` in the directory given by their `path` param, the way the
+//! dashboard runs them, so requests for different repositories never share a
+//! working directory and hook output stays out of the responses. `status` and
+//! `send` work across all repositories, like the dashboard.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use crate::config::{Config, MergeStrategy};
+use crate::{daemon, tmux};
+
+use super::dashboard::agent::extract_worktree_name;
+
+/// Version of the methods' params and results
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Methods `serve` answers, listed by `version`
+const METHODS: &[&str] = &[
+    "version", "status", "list", "add", "merge", "remove", "send",
+];
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A `workmux` command failed; `data.exit_code` tells why (see `ErrorKind`)
+const COMMAND_FAILED: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl Response {
+    fn new(id: Value, outcome: Result<Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ListParams {
+    path: PathBuf,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AddParams {
+    path: PathBuf,
+    branch: String,
+    base: Option<String>,
+    prompt: Option<String>,
+    agent: Option<String>,
+    /// Create the window without switching to it
+    #[serde(default)]
+    background: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MergeParams {
+    path: PathBuf,
+    name: String,
+    into: Option<String>,
+    strategy: Option<MergeStrategy>,
+    #[serde(default)]
+    keep: bool,
+    #[serde(default)]
+    delete_remote: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RemoveParams {
+    path: PathBuf,
+    name: String,
+    /// Remove even with uncommitted changes or unmerged commits
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    keep_branch: bool,
+    #[serde(default)]
+    delete_remote: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SendParams {
+    /// Pane to send to, or else the agent pane of the worktree `handle`
+    pane_id: Option<String>,
+    handle: Option<String>,
+    text: String,
+}
+
+#[cfg(unix)]
+pub fn run(socket: &Path) -> Result<()> {
+    use anyhow::{Context, anyhow};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if socket.exists() {
+        // A socket nobody answers on is left over from a server that died
+        if UnixStream::connect(socket).is_ok() {
+            return Err(anyhow!(
+                "Another workmux serve is listening on {}",
+                socket.display()
+            ));
+        }
+        std::fs::remove_file(socket)
+            .with_context(|| format!("Failed to remove stale socket {}", socket.display()))?;
+    }
+    let listener = UnixListener::bind(socket)
+        .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+    // The socket can create and remove worktrees: only its owner may connect
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    println!(
+        "workmux serve listening on {} (schema {})",
+        socket.display(),
+        SCHEMA_VERSION
+    );
+    tracing::info!(socket = %socket.display(), "serve:start");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                std::thread::spawn(move || {
+                    if let Err(e) = serve_connection(&stream, &stream) {
+                        tracing::debug!(error = %e, "serve:connection closed");
+                    }
+                });
+            }
+            Err(e) => tracing::warn!(error = %e, "serve:failed to accept"),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run(_socket: &Path) -> Result<()> {
+    Err(anyhow::anyhow!("workmux serve needs unix sockets"))
+}
+
+/// Answer the requests of one client until it disconnects
+fn serve_connection(reader: impl std::io::Read, mut writer: impl std::io::Write) -> Result<()> {
+    use std::io::BufRead;
+
+    for line in std::io::BufReader::new(reader).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line) {
+            writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// The response to one request line, None for notifications
+fn handle_line(line: &str) -> Option<Response> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            return Some(Response::new(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, e.to_string())),
+            ));
+        }
+    };
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request = match serde_json::from_value::<Request>(value) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        _ => {
+            return Some(Response::new(
+                id,
+                Err(RpcError::new(
+                    INVALID_REQUEST,
+                    "Invalid JSON-RPC 2.0 request",
+                )),
+            ));
+        }
+    };
+    tracing::debug!(method = request.method, "serve:request");
+    let outcome = dispatch(&request.method, request.params);
+    request.id.map(|id| Response::new(id, outcome))
+}
+
+fn dispatch(method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "version" => Ok(json!({
+            "schema": SCHEMA_VERSION,
+            "workmux": env!("CARGO_PKG_VERSION"),
+            "methods": METHODS,
+        })),
+        "status" => {
+            let config = Config::load(None).map_err(failed)?;
+            Ok(json!(super::status::agent_statuses(&config)))
+        }
+        "list" => list(&parse::<ListParams>(params)?.path),
+        "add" => add(parse(params)?),
+        "merge" => merge(parse(params)?),
+        "remove" => remove(parse(params)?),
+        "send" => send(parse(params)?),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", method),
+        )),
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn failed(error: anyhow::Error) -> RpcError {
+    RpcError::new(COMMAND_FAILED, format!("{:#}", error))
+}
+
+/// Worktrees of the repository at `path`, as `list --json` prints them
+fn list(path: &Path) -> Result<Value, RpcError> {
+    let stdout = workmux(path, &["list", "--json"])?;
+    serde_json::from_str(&stdout).map_err(|e| failed(e.into()))
+}
+
+/// Create a worktree and return it as `list` shows it. The branch may get
+/// another name (`worktree_naming: date-suffix`), so the new worktree is the
+/// one that wasn't listed before.
+fn add(params: AddParams) -> Result<Value, RpcError> {
+    let before = list(&params.path)?;
+    // Values are attached to their flags and the branch comes after `--`, so
+    // client-supplied text starting with `-` can't be read as a flag
+    let mut args = vec!["add".to_string()];
+    if let Some(base) = &params.base {
+        args.push(format!("--base={}", base));
+    }
+    if let Some(prompt) = &params.prompt {
+        args.push(format!("--prompt={}", prompt));
+    }
+    if let Some(agent) = &params.agent {
+        args.push(format!("--agent={}", agent));
+    }
+    if params.background {
+        args.push("--background".to_string());
+    }
+    args.extend(["--".to_string(), params.branch]);
+    workmux(&params.path, &args)?;
+
+    let after = list(&params.path)?;
+    let known: Vec<&Value> = before
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|w| w.get("path"))
+        .collect();
+    let created = after
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|w| w.get("path").is_some_and(|p| !known.contains(&p)))
+        .cloned();
+    Ok(json!({ "worktree": created }))
+}
+
+/// Merge a worktree and return the `merge --json` summary
+fn merge(params: MergeParams) -> Result<Value, RpcError> {
+    let mut args = vec!["merge".to_string(), "--json".to_string()];
+    if let Some(into) = &params.into {
+        args.push(format!("--into={}", into));
+    }
+    match params.strategy {
+        Some(MergeStrategy::Rebase) => args.push("--rebase".to_string()),
+        Some(MergeStrategy::Squash) => args.push("--squash".to_string()),
+        Some(MergeStrategy::Merge) | None => {}
+    }
+    if params.keep {
+        args.push("--keep".to_string());
+    }
+    if params.delete_remote {
+        args.push("--delete-remote".to_string());
+    }
+    args.extend(["--".to_string(), params.name]);
+    let stdout = workmux(&params.path, &args)?;
+    serde_json::from_str(&stdout).map_err(|e| failed(e.into()))
+}
+
+/// Remove a worktree and return the `remove --json` summary
+fn remove(params: RemoveParams) -> Result<Value, RpcError> {
+    let mut args = vec!["remove", "--json"];
+    if params.force {
+        args.push("--force");
+    }
+    if params.keep_branch {
+        args.push("--keep-branch");
+    }
+    if params.delete_remote {
+        args.push("--delete-remote");
+    }
+    args.extend(["--", params.name.as_str()]);
+    let stdout = workmux(&params.path, &args)?;
+    serde_json::from_str(&stdout).map_err(|e| failed(e.into()))
+}
+
+/// Paste a prompt into an agent's pane and submit it
+fn send(params: SendParams) -> Result<Value, RpcError> {
+    let config = Config::load(None).map_err(failed)?;
+    let pane_id = match (params.pane_id, params.handle) {
+        (Some(pane_id), _) => pane_id,
+        (None, Some(handle)) => daemon::agent_panes()
+            .into_iter()
+            .find(|a| extract_worktree_name(&a.window_name, config.window_prefix()).0 == handle)
+            .map(|a| a.pane_id)
+            .ok_or_else(|| {
+                RpcError::new(
+                    COMMAND_FAILED,
+                    format!("No agent is running in '{}'", handle),
+                )
+            })?,
+        (None, None) => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                "Either pane_id or handle is required",
+            ));
+        }
+    };
+    let input = tmux::agent_input(&pane_id, &config);
+    tmux::paste_multiline(&pane_id, params.text.trim_end(), input).map_err(failed)?;
+    Ok(json!({ "pane_id": pane_id }))
+}
+
+/// Run `workmux` in `path` and return its stdout
+fn workmux<S: AsRef<OsStr>>(path: &Path, args: &[S]) -> Result<String, RpcError> {
+    let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("workmux"));
+    let output = Command::new(exe)
+        .args(args)
+        .current_dir(path)
+        .stdin(Stdio::null())
+        // Keep backtraces out of the error messages
+        .env("RUST_BACKTRACE", "0")
+        .env("RUST_LIB_BACKTRACE", "0")
+        .output()
+        .map_err(|e| {
+            RpcError::new(
+                COMMAND_FAILED,
+                format!("Failed to run workmux in {}: {}", path.display(), e),
+            )
+        })?;
+    if !output.status.success() {
+        return Err(command_error(&output));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The error a failed `workmux` printed, with its exit code and full stderr
+fn command_error(output: &Output) -> RpcError {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = match stderr.rfind("Error: ") {
+        Some(start) => &stderr[start + "Error: ".len()..],
+        None => &stderr,
+    };
+    RpcError {
+        code: COMMAND_FAILED,
+        message: message.trim().to_string(),
+        data: Some(json!({
+            "exit_code": output.status.code(),
+            "stderr": stderr.trim(),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn respond(line: &str) -> Value {
+        serde_json::to_value(handle_line(line).expect("a response")).unwrap()
+    }
+
+    #[test]
+    fn version_reports_the_schema() {
+        let response = respond(r#"{"jsonrpc":"2.0","id":1,"method":"version"}"#);
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["schema"], SCHEMA_VERSION);
+        assert!(response.get("error").is_none());
+    }
+
+    #[test]
+    fn notifications_get_no_response() {
+        assert!(handle_line(r#"{"jsonrpc":"2.0","method":"version"}"#).is_none());
+    }
+
+    #[test]
+    fn malformed_requests_get_json_rpc_errors() {
+        assert_eq!(respond("{not json")["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            respond(r#"{"id":2,"method":"version"}"#)["error"]["code"],
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            respond(r#"{"jsonrpc":"2.0","id":3,"method":"nope"}"#)["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        let response = respond(r#"{"jsonrpc":"2.0","id":4,"method":"merge","params":{}}"#);
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+        assert_eq!(response["id"], 4);
+    }
+
+    #[test]
+    fn serve_connection_answers_each_line() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"version"}"#,
+            "\n\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"nope"}"#,
+            "\n"
+        );
+        let mut output = Vec::new();
+        serve_connection(input.as_bytes(), &mut output).unwrap();
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["id"], 2);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::git;
+use crate::{git, metadata};
 use anyhow::{Context, Result, anyhow};
 
 pub fn run(base: &str) -> Result<()> {
@@ -18,6 +18,11 @@ pub fn run(base: &str) -> Result<()> {
 
     git::set_branch_base(&branch, base)
         .with_context(|| format!("Failed to set base branch for '{}'", branch))?;
+    if let Ok(root) = git::get_repo_root()
+        && let Some(handle) = root.file_name().and_then(|n| n.to_str())
+    {
+        metadata::update(handle, |m| m.base = Some(base.to_string()));
+    }
 
     println!("Set base branch for '{}' to '{}'", branch, base);
     Ok(())
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use clap::ValueEnum;
 
-use crate::cmd::Cmd;
+use crate::command::dashboard::agent::extract_project_name;
 use crate::config::Config;
+use crate::notification::{self, StatusChange};
 use crate::tmux;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(ValueEnum, Debug, Clone)]
 pub enum SetWindowStatusCommand {
@@ -31,18 +33,91 @@ pub fn run(cmd: SetWindowStatusCommand) -> Result<()> {
         let _ = tmux::ensure_status_format(&pane);
     }
 
+    // Capture the previous status before overwriting it, to detect transitions
+    let previous = tmux::get_pane_status(&pane);
+
     match cmd {
-        SetWindowStatusCommand::Working => set_status(&pane, config.status_icons.working()),
+        SetWindowStatusCommand::Working => {
+            set_status(&pane, config.status_icons.working())?;
+            report_transition(
+                &config,
+                &pane,
+                previous,
+                config.status_icons.working(),
+                "working",
+            );
+            Ok(())
+        }
         SetWindowStatusCommand::Waiting => {
-            set_status_with_auto_clear(&pane, config.status_icons.waiting())
+            set_status_with_auto_clear(&pane, config.status_icons.waiting())?;
+            report_transition(
+                &config,
+                &pane,
+                previous,
+                config.status_icons.waiting(),
+                "waiting",
+            );
+            Ok(())
         }
         SetWindowStatusCommand::Done => {
-            set_status_with_auto_clear(&pane, config.status_icons.done())
+            set_status_with_auto_clear(&pane, config.status_icons.done())?;
+            report_transition(&config, &pane, previous, config.status_icons.done(), "done");
+            Ok(())
         }
         SetWindowStatusCommand::Clear => clear_status(&pane),
     }
 }
 
+/// Fire a notification and the `status_change` webhook and command if the
+/// pane's status actually changed to `icon`
+fn report_transition(
+    config: &Config,
+    pane: &str,
+    previous: Option<tmux::PaneStatus>,
+    icon: &str,
+    status: &str,
+) {
+    let Some(previous) = previous else {
+        return;
+    };
+    if previous.status.as_deref() == Some(icon) {
+        return;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let elapsed = previous.status_ts.map(|ts| now.saturating_sub(ts));
+    let handle = previous
+        .window_name
+        .strip_prefix(config.window_prefix())
+        .unwrap_or(&previous.window_name);
+
+    // Snoozed agents are parked on purpose: no desktop notification
+    if previous.snoozed_until.is_none_or(|until| until <= now) {
+        notification::notify_status_change(&config.notifications, handle, status, elapsed);
+    }
+
+    let project = extract_project_name(&previous.path, config.worktree_dir.as_deref());
+    let previous_status = previous
+        .status
+        .as_deref()
+        .map(|icon| config.status_icons.status_name(icon).unwrap_or(icon));
+    let change = StatusChange {
+        handle,
+        project: &project,
+        status,
+        previous_status,
+        elapsed_secs: elapsed,
+        timestamp: now,
+        pane_id: pane,
+        window: &previous.window_name,
+        text: notification::status_message(handle, status, elapsed),
+    };
+    notification::report_status_change(&config.status_change, &change);
+}
+
 fn set_status(pane: &str, icon: &str) -> Result<()> {
     tmux::set_status_options(pane, icon, true);
     Ok(())
@@ -61,30 +136,30 @@ fn set_status_with_auto_clear(pane: &str, icon: &str) -> Result<()> {
         icon
     );
 
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-hook", "-w", "-t", pane, "pane-focus-in", &hook_cmd])
         .run();
 
     Ok(())
 }
 
-fn clear_status(pane: &str) -> Result<()> {
+pub(crate) fn clear_status(pane: &str) -> Result<()> {
     // Clear Window Options
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-option", "-uw", "-t", pane, "@workmux_status"])
         .run();
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-option", "-uw", "-t", pane, "@workmux_status_ts"])
         .run();
 
     // Clear Pane Options
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-option", "-up", "-t", pane, "@workmux_pane_status"])
         .run();
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-option", "-up", "-t", pane, "@workmux_pane_status_ts"])
         .run();
-    let _ = Cmd::new("tmux")
+    let _ = tmux::cmd()
         .args(&["set-option", "-up", "-t", pane, "@workmux_pane_command"])
         .run();
 
@@ -0,0 +1,332 @@
+use anyhow::{Context, Result, anyhow};
+use clap_complete::Shell;
+use serde_yaml::{Mapping, Value};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{claude, cli, config};
+
+/// Where notifications about agent status changes are delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationChannel {
+    None,
+    Desktop,
+    Command,
+}
+
+impl NotificationChannel {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "none" | "off" => Some(Self::None),
+            "desktop" => Some(Self::Desktop),
+            "command" => Some(Self::Command),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Desktop => "desktop",
+            Self::Command => "command",
+        }
+    }
+}
+
+/// Prompts on stdin, or accepts every default when `yes` is set
+pub(super) struct Prompter {
+    pub(super) yes: bool,
+}
+
+impl Prompter {
+    pub(super) fn ask(&self, question: &str, default: &str) -> Result<String> {
+        if self.yes {
+            println!("{}: {}", question, default);
+            return Ok(default.to_string());
+        }
+        if default.is_empty() {
+            print!("{}: ", question);
+        } else {
+            print!("{} [{}]: ", question, default);
+        }
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read input")?;
+        let input = input.trim();
+        Ok(if input.is_empty() { default } else { input }.to_string())
+    }
+
+    pub(super) fn confirm(&self, question: &str) -> Result<bool> {
+        let answer = self.ask(&format!("{} (y/n)", question), "y")?;
+        Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+    }
+}
+
+pub fn run(yes: bool) -> Result<()> {
+    let prompter = Prompter { yes };
+    let config_path =
+        config::global_config_path().ok_or_else(|| anyhow!("Could not find home directory"))?;
+    let mut settings = load_mapping(&config_path)?;
+
+    println!("workmux setup\n");
+
+    match which::which("tmux") {
+        Ok(_) => println!("Multiplexer: tmux"),
+        Err(_) => {
+            println!("Multiplexer: tmux (not found in PATH - install it before using workmux)")
+        }
+    }
+
+    let worktree_dir = prompter.ask(
+        "Worktree directory (blank for <project>__worktrees next to each repository)",
+        get_str(&settings, &["worktree_dir"]).unwrap_or(""),
+    )?;
+    set_or_remove(&mut settings, &["worktree_dir"], &worktree_dir);
+
+    let agent = prompter.ask(
+        "Agent command",
+        get_str(&settings, &["agent"]).unwrap_or("claude"),
+    )?;
+    set_or_remove(&mut settings, &["agent"], &agent);
+
+    let current_channel = if get_str(&settings, &["notifications", "command"]).is_some() {
+        NotificationChannel::Command
+    } else if get_bool(&settings, &["notifications", "waiting"])
+        || get_bool(&settings, &["notifications", "done"])
+    {
+        NotificationChannel::Desktop
+    } else {
+        NotificationChannel::None
+    };
+    let channel = loop {
+        let answer = prompter.ask(
+            "Notify when agents wait or finish (none, desktop, command)",
+            current_channel.as_str(),
+        )?;
+        match NotificationChannel::parse(&answer) {
+            Some(channel) => break channel,
+            None => println!("Please answer none, desktop, or command"),
+        }
+    };
+    let notify = channel != NotificationChannel::None;
+    set_value(
+        &mut settings,
+        &["notifications", "waiting"],
+        Value::Bool(notify),
+    );
+    set_value(
+        &mut settings,
+        &["notifications", "done"],
+        Value::Bool(notify),
+    );
+    let command = if channel == NotificationChannel::Command {
+        prompter.ask(
+            "Notification command (receives WM_HANDLE, WM_STATUS, WM_MESSAGE)",
+            get_str(&settings, &["notifications", "command"]).unwrap_or(""),
+        )?
+    } else {
+        String::new()
+    };
+    set_or_remove(&mut settings, &["notifications", "command"], &command);
+
+    write_mapping(&config_path, &settings)?;
+    println!("\n✓ Wrote {}", config_path.display());
+
+    install_completions(&prompter)?;
+
+    let is_claude = config::split_first_token(&agent)
+        .and_then(|(cmd, _)| Path::new(cmd).file_stem())
+        .is_some_and(|stem| stem == "claude");
+    if is_claude {
+        if prompter.confirm("Install Claude Code status hooks in ~/.claude/settings.json?")? {
+            if claude::install_status_hooks()? {
+                println!("✓ Installed status hooks");
+            } else {
+                println!("✓ Status hooks already installed");
+            }
+        }
+    } else {
+        println!(
+            "Status hooks for '{}' must be set up manually. See 'Agent status tracking' in 'workmux docs'.",
+            agent
+        );
+    }
+
+    println!("\nSetup complete. Run 'workmux init' in a repository to add project settings.");
+    Ok(())
+}
+
+fn install_completions(prompter: &Prompter) -> Result<()> {
+    let home = home::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
+    let Some((shell, path)) = Shell::from_env().and_then(|s| Some((s, completion_path(s, &home)?)))
+    else {
+        println!(
+            "Could not detect a supported shell; see 'workmux completions' to install completions manually."
+        );
+        return Ok(());
+    };
+
+    if !prompter.confirm(&format!(
+        "Install {} completions to {}?",
+        shell,
+        path.display()
+    ))? {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, cli::completion_script(shell))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("✓ Installed completions to {}", path.display());
+    if shell == Shell::Zsh {
+        println!("  Make sure ~/.zfunc is in your fpath before compinit: fpath=(~/.zfunc $fpath)");
+    }
+    Ok(())
+}
+
+/// Where completions are picked up automatically for each shell
+fn completion_path(shell: Shell, home: &Path) -> Option<PathBuf> {
+    match shell {
+        Shell::Bash => Some(home.join(".local/share/bash-completion/completions/workmux")),
+        Shell::Zsh => Some(home.join(".zfunc/_workmux")),
+        Shell::Fish => Some(home.join(".config/fish/completions/workmux.fish")),
+        _ => None,
+    }
+}
+
+fn load_mapping(path: &Path) -> Result<Mapping> {
+    if !path.exists() {
+        return Ok(Mapping::new());
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    match serde_yaml::from_str::<Value>(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?
+    {
+        Value::Mapping(mapping) => Ok(mapping),
+        Value::Null => Ok(Mapping::new()),
+        _ => Err(anyhow!("{} is not a YAML mapping", path.display())),
+    }
+}
+
+fn write_mapping(path: &Path, settings: &Mapping) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Rewriting drops comments, so keep the original around
+    if path.exists() {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        std::fs::copy(path, &backup).context("Failed to back up the global config")?;
+    }
+    std::fs::write(path, serde_yaml::to_string(settings)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn get<'a>(settings: &'a Mapping, keys: &[&str]) -> Option<&'a Value> {
+    let (last, parents) = keys.split_last()?;
+    let mut current = settings;
+    for key in parents {
+        current = current.get(*key)?.as_mapping()?;
+    }
+    current.get(*last)
+}
+
+fn get_str<'a>(settings: &'a Mapping, keys: &[&str]) -> Option<&'a str> {
+    get(settings, keys)?.as_str()
+}
+
+fn get_bool(settings: &Mapping, keys: &[&str]) -> bool {
+    get(settings, keys)
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Set a nested key, creating intermediate mappings as needed
+fn set_value(settings: &mut Mapping, keys: &[&str], value: Value) {
+    let Some((last, parents)) = keys.split_last() else {
+        return;
+    };
+    let mut current = settings;
+    for key in parents {
+        let entry = current
+            .entry(Value::from(*key))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+        if !entry.is_mapping() {
+            *entry = Value::Mapping(Mapping::new());
+        }
+        current = entry.as_mapping_mut().expect("entry is a mapping");
+    }
+    current.insert(Value::from(*last), value);
+}
+
+/// Set a nested string key, or remove it when `value` is empty
+fn set_or_remove(settings: &mut Mapping, keys: &[&str], value: &str) {
+    if !value.is_empty() {
+        set_value(settings, keys, Value::from(value));
+        return;
+    }
+    let Some((last, parents)) = keys.split_last() else {
+        return;
+    };
+    let mut current = settings;
+    for key in parents {
+        match current.get_mut(*key).and_then(Value::as_mapping_mut) {
+            Some(next) => current = next,
+            None => return,
+        }
+    }
+    current.remove(*last);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_value_creates_nested_mappings_and_keeps_other_keys() {
+        let mut settings: Mapping = serde_yaml::from_str("window_prefix: 'x-'\n").unwrap();
+        set_value(
+            &mut settings,
+            &["notifications", "waiting"],
+            Value::Bool(true),
+        );
+        set_or_remove(&mut settings, &["agent"], "codex");
+
+        assert_eq!(get_str(&settings, &["window_prefix"]), Some("x-"));
+        assert_eq!(get_str(&settings, &["agent"]), Some("codex"));
+        assert!(get_bool(&settings, &["notifications", "waiting"]));
+    }
+
+    #[test]
+    fn set_or_remove_drops_empty_values() {
+        let mut settings: Mapping =
+            serde_yaml::from_str("worktree_dir: /tmp/wt\nnotifications:\n  command: notify\n")
+                .unwrap();
+        set_or_remove(&mut settings, &["worktree_dir"], "");
+        set_or_remove(&mut settings, &["notifications", "command"], "");
+
+        assert!(get(&settings, &["worktree_dir"]).is_none());
+        assert!(get(&settings, &["notifications", "command"]).is_none());
+    }
+
+    #[test]
+    fn completion_path_per_shell() {
+        let home = Path::new("/home/me");
+        assert_eq!(
+            completion_path(Shell::Zsh, home),
+            Some(PathBuf::from("/home/me/.zfunc/_workmux"))
+        );
+        assert_eq!(
+            completion_path(Shell::Fish, home),
+            Some(PathBuf::from(
+                "/home/me/.config/fish/completions/workmux.fish"
+            ))
+        );
+        assert_eq!(completion_path(Shell::PowerShell, home), None);
+    }
+}
@@ -0,0 +1,45 @@
+//! `workmux shell-init`: shell functions to `eval` from the shell's rc file.
+//! They make `workmux cd` change the shell's own directory, which the binary
+//! can't, and add a `workmux_prompt` segment. Completions are included, so
+//! `workmux cd <TAB>` offers handles.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use clap_complete::Shell;
+
+/// Shells `workmux shell-init` supports
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum InitShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+pub fn run(shell: InitShell) -> Result<()> {
+    print!("{}", script(shell));
+    Ok(())
+}
+
+fn script(shell: InitShell) -> String {
+    let (completions, functions) = match shell {
+        InitShell::Bash => (Shell::Bash, include_str!("../scripts/shell_init/posix.sh")),
+        InitShell::Zsh => (Shell::Zsh, include_str!("../scripts/shell_init/posix.sh")),
+        InitShell::Fish => (Shell::Fish, include_str!("../scripts/shell_init/fish.fish")),
+    };
+    crate::cli::completion_script(completions) + functions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_script_wraps_cd_and_completes_handles() {
+        for shell in InitShell::value_variants() {
+            let script = script(*shell);
+            assert!(script.contains("command workmux cd --print"), "{shell:?}");
+            assert!(script.contains("workmux_prompt"), "{shell:?}");
+            assert!(script.contains("_complete-handles"), "{shell:?}");
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+use crate::command::OutputFormat;
+use crate::config::{Config, StatusIcons};
+use crate::git::{self, GitStatus};
+use crate::{a11y, daemon, porcelain, tmux};
+
+use super::dashboard::agent::{
+    STALE_THRESHOLD_SECS, elapsed_secs, extract_project_name, extract_worktree_name, is_stale,
+};
+use super::list::format_git_status;
+use crate::duration::format_duration;
+
+/// Agent status entry as emitted by `workmux status --json` (or `--format nuon`)
+#[derive(Serialize)]
+pub struct AgentStatus {
+    project: String,
+    handle: String,
+    session: String,
+    window: String,
+    pane_id: String,
+    path: PathBuf,
+    /// Semantic status (working/waiting/done), or the raw icon if unrecognized
+    status: Option<String>,
+    status_ts: Option<u64>,
+    elapsed_secs: Option<u64>,
+    title: Option<String>,
+    git_status: Option<GitStatus>,
+}
+
+#[derive(Tabled)]
+struct AgentRow {
+    #[tabled(rename = "PROJECT")]
+    project: String,
+    #[tabled(rename = "WORKTREE")]
+    handle: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+    #[tabled(rename = "GIT")]
+    git: String,
+    #[tabled(rename = "TIME")]
+    time: String,
+    #[tabled(rename = "TITLE")]
+    title: String,
+}
+
+pub fn run(format: OutputFormat, a11y: bool, porcelain: bool) -> Result<()> {
+    if !tmux::is_running().unwrap_or(false) {
+        if let Some(output) = format.serialize(&Vec::<AgentStatus>::new())? {
+            println!("{}", output);
+        } else if !porcelain {
+            println!("No tmux server running.");
+        }
+        return Ok(());
+    }
+
+    let config = Config::load(None)?;
+    let prefix = config.window_prefix();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    if format != OutputFormat::Table {
+        if let Some(output) = format.serialize(&agent_statuses(&config))? {
+            println!("{}", output);
+        }
+        return Ok(());
+    }
+
+    if porcelain {
+        for status in agent_statuses(&config) {
+            println!("{}", porcelain_line(&status));
+        }
+        return Ok(());
+    }
+
+    let agents = daemon::agent_panes();
+    let paths: Vec<PathBuf> = agents.iter().map(|a| a.path.clone()).collect();
+    let git_statuses = git::cached_git_statuses(&paths);
+
+    if agents.is_empty() {
+        println!("No active agents found");
+        return Ok(());
+    }
+
+    if a11y::enabled(a11y) {
+        for agent in &agents {
+            println!("{}", a11y_line(agent, &config, &git_statuses, now));
+        }
+        return Ok(());
+    }
+
+    let rows: Vec<AgentRow> = agents
+        .into_iter()
+        .map(|agent| AgentRow {
+            title: agent
+                .summary_title(&config)
+                .map(|t| t.strip_prefix("... ").unwrap_or(t).to_string())
+                .unwrap_or_default(),
+            project: extract_project_name(&agent.path, config.worktree_dir.as_deref()),
+            handle: extract_worktree_name(&agent.window_name, prefix).0,
+            status: if agent.exited {
+                "exited".to_string()
+            } else {
+                agent.status.unwrap_or_else(|| "-".to_string())
+            },
+            git: format_git_status(git_statuses.get(&agent.path)),
+            time: elapsed_secs(agent.status_ts, now)
+                .map(format_duration)
+                .unwrap_or_else(|| "-".to_string()),
+        })
+        .collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..5), Padding::new(0, 1, 0, 0));
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// How many agents have each status, for `status --once`
+#[derive(Debug, Default, PartialEq)]
+struct StatusCounts {
+    working: usize,
+    waiting: usize,
+    done: usize,
+    /// Agents whose status hasn't changed for an hour, whatever it is
+    stale: usize,
+}
+
+impl StatusCounts {
+    fn count(&mut self, status: Option<&str>, stale: bool) {
+        match status {
+            _ if stale => self.stale += 1,
+            Some("working") => self.working += 1,
+            Some("waiting") => self.waiting += 1,
+            Some("done") => self.done += 1,
+            _ => {}
+        }
+    }
+
+    /// "🤖 2 · 💬 1 · 1 stale", leaving out statuses no agent has
+    fn line(&self, icons: &StatusIcons) -> String {
+        let mut parts = Vec::new();
+        for (icon, count) in [
+            (icons.working(), self.working),
+            (icons.waiting(), self.waiting),
+            (icons.done(), self.done),
+        ] {
+            if count > 0 {
+                parts.push(format!("{} {}", icon, count));
+            }
+        }
+        if self.stale > 0 {
+            parts.push(format!("{} stale", self.stale));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Print agent counts by status on one line, or nothing when no agent has a
+/// status. Meant to be run over and over by a status bar, so it stays quiet.
+pub fn run_once(by_project: bool) -> Result<()> {
+    if !tmux::is_running().unwrap_or(false) {
+        println!();
+        return Ok(());
+    }
+    let config = Config::load(None).unwrap_or_default();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut counts: BTreeMap<String, StatusCounts> = BTreeMap::new();
+    for agent in daemon::agent_panes() {
+        // Snoozed agents were put aside on purpose
+        if agent.exited || agent.is_snoozed(now) {
+            continue;
+        }
+        let project = if by_project {
+            extract_project_name(&agent.path, config.worktree_dir.as_deref())
+        } else {
+            String::new()
+        };
+        let status = agent
+            .status
+            .as_deref()
+            .and_then(|icon| config.status_icons.status_name(icon));
+        let stale = is_stale(agent.status_ts, STALE_THRESHOLD_SECS, now);
+        counts.entry(project).or_default().count(status, stale);
+    }
+    println!("{}", once_line(&counts, &config.status_icons));
+    Ok(())
+}
+
+/// The `status --once` line: counts by project, separated by " | ", with the
+/// project name in front unless everything is counted together
+fn once_line(counts: &BTreeMap<String, StatusCounts>, icons: &StatusIcons) -> String {
+    counts
+        .iter()
+        .filter_map(|(project, counts)| {
+            let line = counts.line(icons);
+            match (line.is_empty(), project.is_empty()) {
+                (true, _) => None,
+                (false, true) => Some(line),
+                (false, false) => Some(format!("{} {}", project, line)),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Status of every agent, as `status --json` prints it
+pub fn agent_statuses(config: &Config) -> Vec<AgentStatus> {
+    let prefix = config.window_prefix();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let agents = daemon::agent_panes();
+    let paths: Vec<PathBuf> = agents.iter().map(|a| a.path.clone()).collect();
+    let git_statuses = git::cached_git_statuses(&paths);
+    agents
+        .into_iter()
+        .map(|agent| AgentStatus {
+            title: agent.summary_title(config).map(str::to_string),
+            project: extract_project_name(&agent.path, config.worktree_dir.as_deref()),
+            handle: extract_worktree_name(&agent.window_name, prefix).0,
+            status: if agent.exited {
+                Some("exited".to_string())
+            } else {
+                agent.status.map(|icon| {
+                    config
+                        .status_icons
+                        .status_name(&icon)
+                        .map(str::to_string)
+                        .unwrap_or(icon)
+                })
+            },
+            elapsed_secs: elapsed_secs(agent.status_ts, now),
+            git_status: git_statuses.get(&agent.path).cloned(),
+            status_ts: agent.status_ts,
+            session: agent.session,
+            window: agent.window_name,
+            pane_id: agent.pane_id,
+            path: agent.path,
+        })
+        .collect()
+}
+
+/// An agent as tab-separated fields (`--porcelain`): handle, project, pane
+/// ID, status name, seconds in that status, worktree path, and title. The
+/// title comes last as the only free-form text.
+fn porcelain_line(status: &AgentStatus) -> String {
+    porcelain::line(&[
+        status.handle.clone(),
+        status.project.clone(),
+        status.pane_id.clone(),
+        porcelain::opt(status.status.as_ref()),
+        porcelain::opt(status.elapsed_secs),
+        status.path.display().to_string(),
+        porcelain::opt(status.title.as_ref()),
+    ])
+}
+
+/// An agent as labeled fields for screen readers: its status by name rather
+/// than icon, and how long it has had it in words
+pub fn a11y_line(
+    agent: &tmux::AgentPane,
+    config: &Config,
+    git_statuses: &HashMap<PathBuf, GitStatus>,
+    now: u64,
+) -> String {
+    let status = if agent.exited {
+        "exited".to_string()
+    } else {
+        match &agent.status {
+            Some(icon) => config
+                .status_icons
+                .status_name(icon)
+                .map(str::to_string)
+                .unwrap_or_else(|| icon.clone()),
+            None => "no status".to_string(),
+        }
+    };
+    let status = match elapsed_secs(agent.status_ts, now) {
+        Some(secs) if !agent.exited => format!("{} for {}", status, a11y::spoken_duration(secs)),
+        _ => status,
+    };
+    a11y::line(&[
+        (
+            "Worktree",
+            Some(extract_worktree_name(&agent.window_name, config.window_prefix()).0),
+        ),
+        (
+            "Project",
+            Some(extract_project_name(
+                &agent.path,
+                config.worktree_dir.as_deref(),
+            )),
+        ),
+        ("Status", Some(status)),
+        (
+            "Git",
+            a11y::describe_git_status(git_statuses.get(&agent.path)),
+        ),
+        (
+            "Title",
+            agent
+                .summary_title(config)
+                .map(|t| t.strip_prefix("... ").unwrap_or(t).to_string()),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_line_keeps_field_positions() {
+        let status = AgentStatus {
+            project: "api".to_string(),
+            handle: "fix-auth".to_string(),
+            session: "main".to_string(),
+            window: "wm-fix-auth".to_string(),
+            pane_id: "%3".to_string(),
+            path: PathBuf::from("/src/api__worktrees/fix-auth"),
+            status: Some("waiting".to_string()),
+            status_ts: Some(100),
+            elapsed_secs: Some(42),
+            title: Some("Fix\tlogin".to_string()),
+            git_status: None,
+        };
+        assert_eq!(
+            porcelain_line(&status),
+            "fix-auth\tapi\t%3\twaiting\t42\t/src/api__worktrees/fix-auth\tFix login"
+        );
+
+        let status = AgentStatus {
+            status: None,
+            elapsed_secs: None,
+            title: None,
+            ..status
+        };
+        assert_eq!(
+            porcelain_line(&status),
+            "fix-auth\tapi\t%3\t-\t-\t/src/api__worktrees/fix-auth\t-"
+        );
+    }
+
+    #[test]
+    fn once_line_counts_statuses_with_stale_apart() {
+        let mut all = StatusCounts::default();
+        all.count(Some("working"), false);
+        all.count(Some("working"), false);
+        all.count(Some("waiting"), false);
+        all.count(Some("waiting"), true);
+        all.count(None, false);
+        let icons = StatusIcons::default();
+        assert_eq!(
+            once_line(&BTreeMap::from([(String::new(), all)]), &icons),
+            "🤖 2 · 💬 1 · 1 stale"
+        );
+        assert_eq!(once_line(&BTreeMap::new(), &icons), "");
+    }
+
+    #[test]
+    fn once_line_names_projects_that_have_agents() {
+        let counts = |status| {
+            let mut counts = StatusCounts::default();
+            counts.count(status, false);
+            counts
+        };
+        let by_project = BTreeMap::from([
+            ("api".to_string(), counts(Some("waiting"))),
+            ("docs".to_string(), counts(None)),
+            ("web".to_string(), counts(Some("done"))),
+        ]);
+        assert_eq!(
+            once_line(&by_project, &StatusIcons::default()),
+            "api 💬 1 | web ✅ 1"
+        );
+    }
+}
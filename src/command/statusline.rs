@@ -0,0 +1,82 @@
+//! `workmux statusline`: a status line command for Claude Code. Claude pipes
+//! it the session's JSON; the cost and context use are recorded on the pane
+//! for the dashboard, and a line is printed for Claude to show.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::process::Stdio;
+
+use crate::usage::{self, AgentUsage};
+use crate::{cmd, tmux};
+
+pub fn run(command: Option<&str>) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read the status line JSON")?;
+    let json: Value = serde_json::from_str(&input).unwrap_or(Value::Null);
+
+    let usage = usage::from_status_line(&json);
+    if let Ok(pane) = std::env::var("TMUX_PANE")
+        && !usage.is_empty()
+    {
+        tmux::set_pane_usage(&pane, &usage);
+    }
+
+    match command {
+        Some(command) => print!("{}", run_status_command(command, &input)?),
+        None => println!("{}", status_line(&json, &usage)),
+    }
+    Ok(())
+}
+
+/// Output of the user's own status line command, given the same JSON
+fn run_status_command(command: &str, input: &str) -> Result<String> {
+    let mut child = cmd::shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run status line command: {}", command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores its input may exit before reading it
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    let output = child
+        .wait_with_output()
+        .context("Failed to read status line command output")?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// "Opus · $0.42 · 37% context", leaving out what isn't known
+fn status_line(json: &Value, usage: &AgentUsage) -> String {
+    let model = json
+        .pointer("/model/display_name")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let cost = usage.cost_usd.map(usage::format_cost);
+    let context = usage.context_pct.map(|pct| format!("{}% context", pct));
+    [model, cost, context]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn status_line_skips_unknown_parts() {
+        let json = json!({
+            "model": {"display_name": "Opus"},
+            "cost": {"total_cost_usd": 0.4249},
+            "context_window": {"used_percentage": 37},
+        });
+        let usage = usage::from_status_line(&json);
+        assert_eq!(status_line(&json, &usage), "Opus · $0.42 · 37% context");
+        assert_eq!(status_line(&json!({}), &AgentUsage::default()), "");
+    }
+}
@@ -0,0 +1,178 @@
+use crate::config::{self, SyncStrategy};
+use crate::error::{self, ErrorKind};
+use crate::workflow::WorkflowContext;
+use crate::workflow::sync::{SyncOutcome, SyncReport};
+use crate::{git, notification, spinner, tmux, workflow};
+use anyhow::{Result, anyhow};
+
+pub fn run(
+    mut names: Vec<String>,
+    rebase: bool,
+    merge: bool,
+    notify: bool,
+    cleanup: bool,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let cleanup = cleanup || config.auto_cleanup.unwrap_or(false);
+
+    // CLI flags override the configured strategy
+    let strategy = if rebase {
+        SyncStrategy::Rebase
+    } else if merge {
+        SyncStrategy::Merge
+    } else {
+        config.sync_strategy.unwrap_or_default()
+    };
+
+    let context = WorkflowContext::new(config)?;
+
+    spinner::with_spinner("Fetching from remote", git::fetch_prune)?;
+
+    if cleanup {
+        let removed = cleanup_merged(&names, &context)?;
+        if !names.is_empty() && !removed.is_empty() {
+            // Drop the names that pointed at removed worktrees
+            names.retain(|name| git::find_worktree(name).is_ok());
+            if names.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    let reports = workflow::sync::sync(&names, strategy, &context)?;
+    if reports.is_empty() {
+        println!("No worktrees to sync.");
+        return Ok(());
+    }
+
+    for report in &reports {
+        print_report(report, strategy);
+    }
+
+    let conflicted: Vec<&SyncReport> = reports
+        .iter()
+        .filter(|r| r.outcome == SyncOutcome::Conflict)
+        .collect();
+
+    if notify {
+        for report in &conflicted {
+            notify_agents(report, strategy, &context.config);
+        }
+    }
+
+    if conflicted.is_empty() {
+        return Ok(());
+    }
+    if !notify {
+        println!("\nUse --notify to ask the agents in conflicted worktrees to resolve them.");
+    }
+    Err(anyhow!(
+        "{} worktree(s) could not be synced because of conflicts",
+        conflicted.len()
+    ))
+}
+
+/// Remove worktrees that were merged outside workmux (PR merged or upstream
+/// deleted), like `remove --gone`: the list is confirmed first and worktrees
+/// with uncommitted changes are left alone. Declining, or running without a
+/// terminal to confirm on, skips the cleanup but not the sync. Returns the
+/// removed handles.
+fn cleanup_merged(names: &[String], context: &WorkflowContext) -> Result<Vec<String>> {
+    let candidates: Vec<(std::path::PathBuf, String)> = git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, branch)| {
+            *branch != context.main_branch
+                && !git::is_detached(branch)
+                && *path != context.main_worktree_root
+        })
+        .filter(|(path, branch)| {
+            let handle = path.file_name().and_then(|n| n.to_str()).unwrap_or(branch);
+            names.is_empty() || names.iter().any(|n| n == handle || n == branch)
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let branches: Vec<&str> = candidates.iter().map(|(_, b)| b.as_str()).collect();
+    let gone_reasons = super::remove::gone_reasons(&branches);
+    if gone_reasons.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let removed =
+        match super::remove::remove_batch(candidates, &gone_reasons, "gone", false, false, false) {
+            Ok(removed) => removed,
+            Err(e) if error::kind(&e) == Some(ErrorKind::Aborted) => {
+                println!("Skipped cleanup.");
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+
+    if !removed.is_empty() {
+        notification::show(&format!(
+            "Cleaned up {} merged worktree(s): {}",
+            removed.len(),
+            removed.join(", ")
+        ));
+    }
+    Ok(removed)
+}
+
+fn print_report(report: &SyncReport, strategy: SyncStrategy) {
+    match &report.outcome {
+        SyncOutcome::Updated => {
+            let verb = match strategy {
+                SyncStrategy::Rebase => "Rebased onto",
+                SyncStrategy::Merge => "Merged",
+            };
+            println!("✓ {}: {} {}", report.handle, verb, report.target);
+        }
+        SyncOutcome::UpToDate => {
+            println!(
+                "✓ {}: Already up to date with {}",
+                report.handle, report.target
+            );
+        }
+        SyncOutcome::Conflict => {
+            println!(
+                "✗ {}: Conflicts with {} (left unchanged)",
+                report.handle, report.target
+            );
+        }
+        SyncOutcome::Skipped(reason) => {
+            println!("- {}: Skipped ({})", report.handle, reason);
+        }
+    }
+}
+
+/// Ask the agents running in a conflicted worktree to do the sync themselves
+fn notify_agents(report: &SyncReport, strategy: SyncStrategy, config: &config::Config) {
+    let command = match strategy {
+        SyncStrategy::Rebase => format!("git rebase {}", report.target),
+        SyncStrategy::Merge => format!("git merge {}", report.target),
+    };
+    let message = format!(
+        "Your branch '{}' conflicts with {}. Run `{}`, resolve the conflicts, and continue until it completes.",
+        report.branch, report.target, command
+    );
+
+    let panes: Vec<_> = tmux::get_all_agent_panes()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|pane| pane.path.starts_with(&report.path))
+        .collect();
+
+    if panes.is_empty() {
+        println!("  No running agent in '{}' to notify", report.handle);
+        return;
+    }
+    for pane in panes {
+        let input = tmux::agent_input(&pane.pane_id, config);
+        match tmux::paste_multiline(&pane.pane_id, &message, input) {
+            Ok(()) => println!("  Notified agent in {}", pane.window_name),
+            Err(e) => eprintln!("  Failed to notify agent in {}: {}", pane.window_name, e),
+        }
+    }
+}
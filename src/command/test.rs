@@ -0,0 +1,217 @@
+use crate::checks::{self, CheckResult};
+use crate::cmd::{self, Cmd};
+use crate::workflow::hooks::HookEnv;
+use crate::{config, git};
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Mutex, mpsc};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info};
+
+/// Lines of output shown for each worktree whose tests failed
+const FAILURE_TAIL_LINES: usize = 20;
+
+struct TestRun {
+    handle: String,
+    path: PathBuf,
+    /// Exit code, or None if the command could not be started or was killed
+    exit_code: Option<i32>,
+    output: String,
+    elapsed: Duration,
+}
+
+impl TestRun {
+    fn passed(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+pub fn run(
+    names: Vec<String>,
+    command: Option<String>,
+    jobs: Option<usize>,
+    record: bool,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let command = command.or(config.test_command).ok_or_else(|| {
+        anyhow!(
+            "No test command configured. Set `test_command` in .workmux.yaml or pass --command."
+        )
+    })?;
+
+    let main_root = git::get_main_worktree_root()?;
+    let worktrees: Vec<(PathBuf, String)> = if names.is_empty() {
+        git::list_worktrees()?
+            .into_iter()
+            .filter(|(path, _)| *path != main_root)
+            .collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                git::find_worktree(name)
+                    .with_context(|| format!("No worktree found with name '{}'", name))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    if worktrees.is_empty() {
+        println!("No worktrees to test.");
+        return Ok(());
+    }
+
+    let jobs = jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(4, |n| n.get()))
+        .clamp(1, worktrees.len());
+    info!(command, jobs, count = worktrees.len(), "test:start");
+    println!(
+        "Running '{}' in {} worktree(s), {} at a time...",
+        command,
+        worktrees.len(),
+        jobs
+    );
+
+    // Workers take worktrees off a shared queue and report each run as it
+    // finishes, so results print in completion order
+    let queue = Mutex::new(worktrees.into_iter());
+    let (tx, rx) = mpsc::channel();
+    let mut runs = Vec::new();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let queue = &queue;
+            let command = &command;
+            let main_root = &main_root;
+            scope.spawn(move || {
+                loop {
+                    let next = queue.lock().map(|mut q| q.next()).unwrap_or(None);
+                    let Some((path, branch)) = next else {
+                        break;
+                    };
+                    let run = run_in_worktree(command, path, &branch, main_root);
+                    if tx.send(run).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+        for run in rx {
+            print_run(&run);
+            runs.push(run);
+        }
+    });
+
+    let failed: Vec<&TestRun> = runs.iter().filter(|r| !r.passed()).collect();
+    for run in &failed {
+        print_failure_output(run);
+    }
+
+    if record {
+        let finished_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let results: HashMap<PathBuf, CheckResult> = runs
+            .iter()
+            .map(|run| {
+                let commit = Cmd::new("git")
+                    .workdir(&run.path)
+                    .args(&["rev-parse", "HEAD"])
+                    .run_and_capture_stdout()
+                    .ok()
+                    .map(|sha| sha.trim().to_string());
+                let result = CheckResult {
+                    passed: run.passed(),
+                    commit,
+                    finished_at,
+                };
+                (run.path.clone(), result)
+            })
+            .collect();
+        checks::record(&results);
+    }
+
+    println!(
+        "\n{} passed, {} failed",
+        runs.len() - failed.len(),
+        failed.len()
+    );
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Tests failed in {} worktree(s)", failed.len()))
+    }
+}
+
+/// Run the command with its output (stdout and stderr interleaved) captured
+fn run_in_worktree(command: &str, path: PathBuf, branch: &str, main_root: &Path) -> TestRun {
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(branch)
+        .to_string();
+    let env = HookEnv::new(&handle, &path, main_root).with_branch(branch);
+    let started = Instant::now();
+    let result = run_captured(command, &path, &env);
+    let elapsed = started.elapsed();
+
+    let (exit_code, output) = match result {
+        Ok(result) => result,
+        Err(e) => (None, format!("Failed to run command: {}", e)),
+    };
+    debug!(handle, ?exit_code, ?elapsed, "test:finished");
+    TestRun {
+        handle,
+        path,
+        exit_code,
+        output,
+        elapsed,
+    }
+}
+
+/// Run `command` in the hook shell with stdout and stderr written to one pipe,
+/// so the output keeps the order it was printed in
+fn run_captured(command: &str, path: &Path, env: &HookEnv) -> io::Result<(Option<i32>, String)> {
+    let (mut reader, writer) = io::pipe()?;
+    let mut shell = cmd::shell(command);
+    shell
+        .current_dir(path)
+        .envs(env.vars())
+        .stdin(Stdio::null())
+        .stdout(writer.try_clone()?)
+        .stderr(writer);
+    let mut child = shell.spawn()?;
+    // Close our copies of the write end, so reading stops when the command exits
+    drop(shell);
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output)?;
+    let status = child.wait()?;
+    Ok((status.code(), String::from_utf8_lossy(&output).into_owned()))
+}
+
+fn print_run(run: &TestRun) {
+    let secs = run.elapsed.as_secs_f64();
+    match run.exit_code {
+        Some(0) => println!("✓ {} ({:.1}s)", run.handle, secs),
+        Some(code) => println!("✗ {}: exit code {} ({:.1}s)", run.handle, code, secs),
+        None => println!("✗ {}: did not finish ({:.1}s)", run.handle, secs),
+    }
+}
+
+fn print_failure_output(run: &TestRun) {
+    let lines: Vec<&str> = run.output.lines().collect();
+    let tail = &lines[lines.len().saturating_sub(FAILURE_TAIL_LINES)..];
+    println!("\n--- {} ---", run.handle);
+    if lines.is_empty() {
+        println!("(no output)");
+    }
+    if lines.len() > tail.len() {
+        println!("({} earlier lines omitted)", lines.len() - tail.len());
+    }
+    for line in tail {
+        println!("{}", line);
+    }
+}
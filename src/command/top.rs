@@ -0,0 +1,374 @@
+//! `workmux top`: a compact agent monitor for a small pane kept open next to
+//! your work.
+//!
+//! Unlike the dashboard it has no preview and doesn't check git or PRs. It
+//! reads the agent list (from the daemon's snapshot when one is running),
+//! keeps it sorted by priority, and highlights the rows whose status just
+//! changed. It only redraws when the list refreshes or a key is pressed.
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{
+    Frame,
+    backend::CrosstermBackend,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table, TableState},
+};
+use std::collections::HashMap;
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::daemon;
+use crate::tmux::{self, AgentPane};
+
+use super::dashboard::agent::{
+    cluster_groups, elapsed_secs, extract_worktree_name, is_stale, status_priority,
+};
+
+/// How long a row stays highlighted after its status changes
+const HIGHLIGHT_FOR: Duration = Duration::from_secs(10);
+
+/// Agents whose status hasn't changed for this long sort last
+const STALE_THRESHOLD_SECS: u64 = 60 * 60;
+
+struct Top {
+    config: Config,
+    agents: Vec<AgentPane>,
+    /// Status of each pane at the last refresh, to spot changes
+    last_status: HashMap<String, Option<String>>,
+    /// When each pane's status last changed while `top` was watching
+    changed_at: HashMap<String, Instant>,
+    /// Pane ID of the selected row, so the selection follows it when re-sorted
+    selected: Option<String>,
+    should_quit: bool,
+}
+
+impl Top {
+    fn new(config: Config) -> Self {
+        let mut top = Self {
+            config,
+            agents: Vec::new(),
+            last_status: HashMap::new(),
+            changed_at: HashMap::new(),
+            selected: None,
+            should_quit: false,
+        };
+        top.refresh();
+        // Agents already running when `top` starts aren't transitions
+        top.changed_at.clear();
+        top
+    }
+
+    fn refresh(&mut self) {
+        let mut agents = daemon::agent_panes();
+        let now = daemon::now_secs();
+        sort_by_priority(&mut agents, &self.config, now);
+
+        let instant = Instant::now();
+        for agent in &agents {
+            let status = status_key(agent);
+            if self.last_status.get(&agent.pane_id) != Some(&status) {
+                self.changed_at.insert(agent.pane_id.clone(), instant);
+            }
+        }
+        self.last_status = agents
+            .iter()
+            .map(|a| (a.pane_id.clone(), status_key(a)))
+            .collect();
+        self.changed_at
+            .retain(|pane, at| self.last_status.contains_key(pane) && at.elapsed() < HIGHLIGHT_FOR);
+
+        if self
+            .selected
+            .as_ref()
+            .is_none_or(|pane| !agents.iter().any(|a| &a.pane_id == pane))
+        {
+            self.selected = agents.first().map(|a| a.pane_id.clone());
+        }
+        self.agents = agents;
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let pane = self.selected.as_ref()?;
+        self.agents.iter().position(|a| &a.pane_id == pane)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.agents.is_empty() {
+            return;
+        }
+        let current = self.selected_index().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, self.agents.len() as isize - 1) as usize;
+        self.selected = Some(self.agents[next].pane_id.clone());
+    }
+
+    /// Returns whether the screen needs redrawing
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true
+            }
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('r') => self.refresh(),
+            // Stays open: it's meant to be left running
+            KeyCode::Enter => {
+                if let Some(pane) = &self.selected {
+                    let _ = tmux::switch_to_pane(pane);
+                }
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some(agent) = self.agents.get(c as usize - '1' as usize) {
+                    let _ = tmux::switch_to_pane(&agent.pane_id);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// What counts as a status change: the icon, or the agent exiting
+fn status_key(agent: &AgentPane) -> Option<String> {
+    if agent.exited {
+        Some("exited".to_string())
+    } else {
+        agent.status.clone()
+    }
+}
+
+/// The dashboard's Priority sort: waiting, done, working, then stale; most
+/// recent first within each, keeping groups together
+fn sort_by_priority(agents: &mut Vec<AgentPane>, config: &Config, now: u64) {
+    let icons = &config.status_icons;
+    agents.sort_by_cached_key(|a| {
+        let stale = is_stale(a.status_ts, STALE_THRESHOLD_SECS, now);
+        (
+            status_priority(a.status.as_deref(), icons, stale || a.exited),
+            elapsed_secs(a.status_ts, now).unwrap_or(u64::MAX),
+            a.pane_id
+                .trim_start_matches('%')
+                .parse::<u32>()
+                .unwrap_or(u32::MAX),
+        )
+    });
+    *agents = cluster_groups(std::mem::take(agents), |a| {
+        a.group
+            .as_deref()
+            .map(|g| (a.session.clone(), g.to_string()))
+    });
+}
+
+/// An elapsed time in at most four characters: "42s", "5m", "3h", "2d"
+fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 60 * 60 => format!("{}m", s / 60),
+        s if s < 24 * 60 * 60 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+fn render(f: &mut Frame, top: &Top) {
+    let chunks = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(f.area());
+    let icons = &top.config.status_icons;
+    let count = |icon: &str| {
+        top.agents
+            .iter()
+            .filter(|a| !a.exited && a.status.as_deref() == Some(icon))
+            .count()
+    };
+    let summary = format!(
+        " {} {}  {} {}  {} {}",
+        icons.waiting(),
+        count(icons.waiting()),
+        icons.done(),
+        count(icons.done()),
+        icons.working(),
+        count(icons.working()),
+    );
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(summary, Style::default().bold()),
+            Span::styled(
+                format!("  {} agents", top.agents.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])),
+        chunks[0],
+    );
+
+    if top.agents.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                " No active agents",
+                Style::default().fg(Color::DarkGray),
+            )),
+            chunks[1],
+        );
+        return;
+    }
+
+    let now = daemon::now_secs();
+    let prefix = top.config.window_prefix();
+    let rows = top.agents.iter().enumerate().map(|(i, agent)| {
+        let stale = is_stale(agent.status_ts, STALE_THRESHOLD_SECS, now);
+        let (status, color) = if agent.exited {
+            ("exited".to_string(), Color::DarkGray)
+        } else {
+            let status = agent.status.clone().unwrap_or_else(|| "-".to_string());
+            let color = match status.as_str() {
+                _ if stale => Color::DarkGray,
+                s if s == icons.waiting() => Color::Magenta,
+                s if s == icons.done() => Color::Green,
+                s if s == icons.working() => Color::Cyan,
+                _ => Color::White,
+            };
+            (status, color)
+        };
+        let age = elapsed_secs(agent.status_ts, now)
+            .map(format_age)
+            .unwrap_or_else(|| "-".to_string());
+        let title = agent
+            .summary_title(&top.config)
+            .map(|t| t.strip_prefix("... ").unwrap_or(t).to_string())
+            .unwrap_or_default();
+        let row = Row::new(vec![
+            Cell::from(if i < 9 {
+                format!("{}", i + 1)
+            } else {
+                String::new()
+            })
+            .style(Style::default().fg(Color::DarkGray)),
+            Cell::from(status).style(Style::default().fg(color)),
+            Cell::from(extract_worktree_name(&agent.window_name, prefix).0),
+            Cell::from(age).style(Style::default().fg(Color::DarkGray)),
+            Cell::from(title).style(Style::default().fg(Color::Gray)),
+        ]);
+        if top.changed_at.contains_key(&agent.pane_id) {
+            row.style(Style::default().bg(Color::Rgb(60, 50, 20)).bold())
+        } else {
+            row
+        }
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Max(24),
+            Constraint::Length(4),
+            Constraint::Fill(1),
+        ],
+    )
+    .column_spacing(1)
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(top.selected_index());
+    f.render_stateful_widget(table, chunks[1], &mut state);
+}
+
+pub fn run() -> Result<()> {
+    tmux::ensure_running()?;
+    let config = Config::load(None)?;
+    let refresh_interval = config.dashboard.refresh_interval();
+    let mut top = Top::new(config);
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = ratatui::Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let mut last_refresh = Instant::now();
+    let mut dirty = true;
+    let result = loop {
+        if dirty {
+            if let Err(e) = terminal.draw(|f| render(f, &top)) {
+                break Err(e.into());
+            }
+            dirty = false;
+        }
+        let timeout = refresh_interval.saturating_sub(last_refresh.elapsed());
+        match event::poll(timeout) {
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    dirty = top.handle_key(key.code, key.modifiers);
+                }
+                Ok(Event::Resize(..)) => dirty = true,
+                Ok(_) => {}
+                Err(e) => break Err(e.into()),
+            },
+            Ok(false) => {}
+            Err(e) => break Err(e.into()),
+        }
+        if top.should_quit {
+            break Ok(());
+        }
+        if last_refresh.elapsed() >= refresh_interval {
+            top.refresh();
+            last_refresh = Instant::now();
+            dirty = true;
+        }
+    };
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn agent(pane_id: &str, status: &str, status_ts: u64) -> AgentPane {
+        AgentPane {
+            session: "s".to_string(),
+            window_name: format!("wm-{}", pane_id.trim_start_matches('%')),
+            pane_id: pane_id.to_string(),
+            path: PathBuf::from("/tmp/project"),
+            pane_title: None,
+            status: Some(status.to_string()),
+            status_ts: Some(status_ts),
+            group: None,
+            agent: None,
+            merge_queued: false,
+            exited: false,
+            snoozed_until: None,
+            usage: Default::default(),
+        }
+    }
+
+    #[test]
+    fn sort_puts_waiting_then_done_then_working_then_stale() {
+        let config = Config::default();
+        let now = 100_000;
+        let mut agents = vec![
+            agent("%1", "🤖", now - 10),
+            agent("%2", "✅", now - 10),
+            agent("%3", "💬", now - 2 * STALE_THRESHOLD_SECS),
+            agent("%4", "💬", now - 30),
+            agent("%5", "💬", now - 5),
+        ];
+        sort_by_priority(&mut agents, &config, now);
+        let order: Vec<&str> = agents.iter().map(|a| a.pane_id.as_str()).collect();
+        assert_eq!(order, ["%5", "%4", "%2", "%1", "%3"]);
+    }
+
+    #[test]
+    fn age_fits_four_characters() {
+        assert_eq!(format_age(42), "42s");
+        assert_eq!(format_age(5 * 60 + 30), "5m");
+        assert_eq!(format_age(3 * 3600), "3h");
+        assert_eq!(format_age(2 * 86400 + 5), "2d");
+    }
+}
@@ -0,0 +1,210 @@
+use crate::error::ErrorKind;
+use crate::events::{self, Event, Operation};
+use crate::metadata::{self, WorktreeMetadata};
+use crate::workflow::types::CreateArgs;
+use crate::workflow::{SetupOptions, WorkflowContext};
+use crate::{config, daemon, duration, git, workflow};
+use anyhow::{Context, Result, anyhow};
+use std::io::{self, Write};
+use tracing::warn;
+
+pub fn run(yes: bool) -> Result<()> {
+    let Some(event) = events::load().pop() else {
+        println!("Nothing to undo");
+        return Ok(());
+    };
+    let ago = duration::format_duration(daemon::now_secs().saturating_sub(event.at));
+    println!("Undoing {} ({} ago)", event.describe(), ago);
+
+    match &event.op {
+        Operation::Remove {
+            branch,
+            commit,
+            metadata,
+        } => undo_remove(&event, branch, commit.as_deref(), metadata.as_ref())?,
+        Operation::Merge {
+            branch,
+            target,
+            before,
+            after,
+            commit,
+            metadata,
+        } => {
+            undo_merge(target, before, after, yes)?;
+            // The revert is committed now, so running undo again must not revert it twice
+            events::drop_last().context("Failed to update the event log")?;
+            if let Some(commit) = commit {
+                restore_branch(branch, commit, metadata.as_ref()).with_context(|| {
+                    format!(
+                        "The merge was reverted, but branch '{}' could not be restored. \
+                         Recreate it with 'git branch {} {}'.",
+                        branch, branch, commit
+                    )
+                })?;
+                println!(
+                    "✓ Restored branch '{}'. Run 'workmux add {}' to work on it again.",
+                    branch, branch
+                );
+            }
+            return Ok(());
+        }
+        Operation::Close => undo_close(&event.handle)?,
+    }
+
+    events::drop_last().context("Failed to update the event log")
+}
+
+/// Recreate a removed worktree from its branch, bringing the branch back
+/// first if it was deleted
+fn undo_remove(
+    event: &Event,
+    branch: &str,
+    commit: Option<&str>,
+    metadata: Option<&WorktreeMetadata>,
+) -> Result<()> {
+    let handle = event.handle.as_str();
+    if git::find_worktree(handle).is_ok() {
+        return Err(ErrorKind::AlreadyExists.msg(format!(
+            "A worktree named '{}' exists again, so there is nothing to recreate.",
+            handle
+        )));
+    }
+    match commit {
+        Some(commit) => restore_branch(branch, commit, metadata)?,
+        None if !git::branch_exists(branch)? => {
+            return Err(ErrorKind::NotFound.msg(format!(
+                "Branch '{}' was deleted since, so '{}' cannot be recreated.",
+                branch, handle
+            )));
+        }
+        None => {}
+    }
+
+    let config = config::Config::load(None)?.for_branch(branch);
+    let context = WorkflowContext::new(config)?;
+    let options = SetupOptions::new(true, true, true);
+    super::announce_hooks(
+        &context.config,
+        Some(&options),
+        super::HookPhase::PostCreate,
+    );
+    let result = workflow::create(
+        &context,
+        CreateArgs {
+            branch_name: branch,
+            handle,
+            base_branch: None,
+            remote_branch: None,
+            prompt: None,
+            options,
+            agent: metadata.and_then(|m| m.agent.as_deref()),
+            package: metadata.and_then(|m| m.package.as_deref()),
+            detached: false,
+        },
+    )
+    .context("Failed to recreate worktree")?;
+
+    // The worktree is back with its original history, not as a new one
+    if let Some(metadata) = metadata
+        && let Err(e) = metadata::save(handle, metadata)
+    {
+        warn!(handle, error = %e, "undo:failed to save worktree metadata");
+    }
+    println!(
+        "✓ Recreated '{}'\n  Worktree: {}",
+        handle,
+        result.worktree_path.display()
+    );
+    Ok(())
+}
+
+/// Point a deleted branch back at the commit it was deleted at
+fn restore_branch(branch: &str, commit: &str, metadata: Option<&WorktreeMetadata>) -> Result<()> {
+    if git::branch_exists(branch)? {
+        return Ok(());
+    }
+    git::create_branch_at(branch, commit)
+        .with_context(|| format!("Failed to recreate branch '{}' at {}", branch, commit))?;
+    if let Some(metadata) = metadata {
+        super::restore::restore_branch_config(branch, metadata);
+    }
+    Ok(())
+}
+
+/// Revert what a merge brought into `target`, with new commits on top
+fn undo_merge(target: &str, before: &str, after: &str, yes: bool) -> Result<()> {
+    if !git::is_ancestor(after, target)? {
+        return Err(anyhow!(
+            "'{}' no longer contains the merge (it was reset or rewritten), so there is nothing to revert.",
+            target
+        ));
+    }
+    let target_path = git::get_worktree_path(target).map_err(|_| {
+        anyhow!(
+            "'{}' is not checked out in any worktree. Check it out to revert the merge.",
+            target
+        )
+    })?;
+    if git::has_tracked_changes(&target_path)? {
+        return Err(ErrorKind::Dirty.msg(format!(
+            "Target worktree ({}) has uncommitted changes. Please commit or stash them first.",
+            target_path.display()
+        )));
+    }
+
+    // A merge commit is reverted as a whole, anything else commit by commit
+    let merge_commit = git::resolve_commit(&format!("{}^2", after)).is_ok()
+        && git::resolve_commit(&format!("{}^1", after)).ok().as_deref() == Some(before);
+    let (rev, count) = if merge_commit {
+        (after.to_string(), 1)
+    } else {
+        let range = format!("{}..{}", before, after);
+        let count = git::count_commits_ahead(before, after).unwrap_or(0);
+        (range, count)
+    };
+
+    if !yes {
+        print!(
+            "Revert it on '{}' with {} new commit(s)? [y/N] ",
+            target, count
+        );
+        io::stdout().flush().context("Failed to flush stdout")?;
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+        if input.trim().to_lowercase() != "y" {
+            return Err(ErrorKind::Aborted.msg("Aborted."));
+        }
+    }
+
+    if let Err(e) = git::revert_in_worktree(&target_path, &rev, merge_commit) {
+        let _ = git::abort_revert_in_worktree(&target_path);
+        return Err(ErrorKind::Conflicts.wrap(
+            e,
+            format!(
+                "Reverting the merge conflicts with later changes on '{}'. Nothing was changed.",
+                target
+            ),
+        ));
+    }
+    println!("✓ Reverted the merge on '{}'", target);
+    Ok(())
+}
+
+/// Reopen the window of a worktree whose window was closed
+fn undo_close(handle: &str) -> Result<()> {
+    git::find_worktree(handle)
+        .with_context(|| format!("Worktree '{}' was removed since", handle))?;
+    let config = config::Config::load(None)?.for_worktree(handle);
+    let context = WorkflowContext::new(config)?;
+    workflow::open(
+        handle,
+        &context,
+        SetupOptions::new(false, false, true),
+        false,
+    )
+    .context("Failed to reopen window")?;
+    println!("✓ Reopened '{}'", handle);
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! `workmux watch`: agent status changes as newline-delimited JSON.
+//!
+//! Polls the agent panes (from the daemon's snapshot when one is fresh, like
+//! `status`) and prints an event whenever an agent's status changes, appears,
+//! or goes away, for piping into `jq`, notifiers, or a log file.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::Duration;
+use tracing::info;
+
+use crate::config::Config;
+use crate::daemon;
+use crate::tmux::AgentPane;
+
+use super::dashboard::agent::{extract_project_name, extract_worktree_name};
+
+/// An agent's status as last seen
+#[derive(Debug, Clone, PartialEq)]
+struct Seen {
+    handle: String,
+    project: String,
+    /// Status name, "exited" once the agent has exited, None before the
+    /// agent has set one
+    status: Option<String>,
+}
+
+/// A change of an agent's status. `from` is null for an agent that just
+/// appeared and `to` is null for one whose pane went away.
+#[derive(Debug, PartialEq, Serialize)]
+struct StatusChange {
+    handle: String,
+    project: String,
+    pane_id: String,
+    from: Option<String>,
+    to: Option<String>,
+    ts: u64,
+}
+
+pub fn run(interval_secs: u64) -> Result<()> {
+    info!(interval_secs, "watch:start");
+    let config = Config::load(None).unwrap_or_default();
+    let interval = Duration::from_secs(interval_secs);
+    let mut stdout = std::io::stdout();
+    // Agents already running are the starting point, not changes
+    let mut seen = snapshot(&daemon::agent_panes(), &config);
+
+    loop {
+        std::thread::sleep(interval);
+        let agents = daemon::agent_panes();
+        let current = snapshot(&agents, &config);
+        let now = daemon::now_secs();
+        for change in changes(&seen, &current, now) {
+            // The status timestamp is when the agent set its status, which
+            // can be a poll interval before we noticed
+            let ts = agents
+                .iter()
+                .find(|a| a.pane_id == change.pane_id)
+                .and_then(|a| a.status_ts)
+                .filter(|_| change.to.is_some())
+                .unwrap_or(now);
+            let change = StatusChange { ts, ..change };
+            let written = writeln!(stdout, "{}", serde_json::to_string(&change)?);
+            // Whatever we were piped into (e.g. `head`) has stopped reading
+            if let Err(e) = written.and_then(|_| stdout.flush()) {
+                if e.kind() == std::io::ErrorKind::BrokenPipe {
+                    return Ok(());
+                }
+                return Err(e.into());
+            }
+        }
+        seen = current;
+    }
+}
+
+/// Agents by pane ID
+fn snapshot(agents: &[AgentPane], config: &Config) -> BTreeMap<String, Seen> {
+    agents
+        .iter()
+        .map(|agent| {
+            let status = if agent.exited {
+                Some("exited".to_string())
+            } else {
+                agent.status.as_ref().map(|icon| {
+                    config
+                        .status_icons
+                        .status_name(icon)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| icon.clone())
+                })
+            };
+            let seen = Seen {
+                handle: extract_worktree_name(&agent.window_name, config.window_prefix()).0,
+                project: extract_project_name(&agent.path, config.worktree_dir.as_deref()),
+                status,
+            };
+            (agent.pane_id.clone(), seen)
+        })
+        .collect()
+}
+
+/// Changes from `before` to `after`, in pane order
+fn changes(
+    before: &BTreeMap<String, Seen>,
+    after: &BTreeMap<String, Seen>,
+    now: u64,
+) -> Vec<StatusChange> {
+    let change =
+        |pane_id: &str, seen: &Seen, from: Option<&Seen>, to: Option<&Seen>| StatusChange {
+            handle: seen.handle.clone(),
+            project: seen.project.clone(),
+            pane_id: pane_id.to_string(),
+            from: from.and_then(|s| s.status.clone()),
+            to: to.and_then(|s| s.status.clone()),
+            ts: now,
+        };
+    let mut changes = Vec::new();
+    for (pane_id, now_seen) in after {
+        match before.get(pane_id) {
+            Some(was) if was.status == now_seen.status => {}
+            was => changes.push(change(pane_id, now_seen, was, Some(now_seen))),
+        }
+    }
+    for (pane_id, was) in before {
+        if !after.contains_key(pane_id) {
+            changes.push(change(pane_id, was, Some(was), None));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seen(handle: &str, status: Option<&str>) -> Seen {
+        Seen {
+            handle: handle.to_string(),
+            project: "api".to_string(),
+            status: status.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn changes_report_transitions_arrivals_and_departures() {
+        let before = BTreeMap::from([
+            ("%1".to_string(), seen("fix-auth", Some("working"))),
+            ("%2".to_string(), seen("docs", Some("done"))),
+            ("%3".to_string(), seen("gone", Some("waiting"))),
+        ]);
+        let after = BTreeMap::from([
+            ("%1".to_string(), seen("fix-auth", Some("waiting"))),
+            ("%2".to_string(), seen("docs", Some("done"))),
+            ("%4".to_string(), seen("new", None)),
+        ]);
+        let summary: Vec<(String, Option<String>, Option<String>)> = changes(&before, &after, 9)
+            .into_iter()
+            .map(|c| (c.handle, c.from, c.to))
+            .collect();
+        let s = |v: &str| Some(v.to_string());
+        assert_eq!(
+            summary,
+            vec![
+                ("fix-auth".to_string(), s("working"), s("waiting")),
+                ("new".to_string(), None, None),
+                ("gone".to_string(), s("waiting"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn status_change_serializes_as_one_json_object() {
+        let change = StatusChange {
+            handle: "fix-auth".to_string(),
+            project: "api".to_string(),
+            pane_id: "%1".to_string(),
+            from: Some("working".to_string()),
+            to: Some("waiting".to_string()),
+            ts: 1700000000,
+        };
+        assert_eq!(
+            serde_json::to_string(&change).unwrap(),
+            r#"{"handle":"fix-auth","project":"api","pane_id":"%1","from":"working","to":"waiting","ts":1700000000}"#
+        );
+    }
+}
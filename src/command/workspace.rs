@@ -0,0 +1,199 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::PathBuf;
+
+use crate::command::args::SetupFlags;
+use crate::workflow::{SetupOptions, workspace};
+use crate::{config, git, tmux};
+
+/// Resolve the workspace from config, using the current repository (if any)
+/// to pick between several configured workspaces.
+fn resolve(name: Option<&str>) -> Result<(String, Vec<PathBuf>)> {
+    let config = config::Config::load(None)?;
+    let current_repo = git::get_main_worktree_root().ok();
+    let home = home::home_dir();
+    workspace::resolve_workspace(&config, name, current_repo.as_deref(), home.as_deref())
+}
+
+/// Find the repositories of a workspace that have a worktree for `name`.
+/// Returns (repo, worktree_path) pairs, and changes into each repo to do so.
+fn find_members(repos: &[PathBuf], name: &str) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut found = Vec::new();
+    for repo in repos {
+        workspace::enter_repo(repo, None)?;
+        match git::find_worktree(name) {
+            Ok((worktree_path, _)) => found.push((repo.clone(), worktree_path)),
+            Err(_) => println!("No worktree for '{}' in {}, skipping", name, repo.display()),
+        }
+    }
+    if found.is_empty() {
+        return Err(anyhow!(
+            "No worktree found with name '{}' in any workspace repository",
+            name
+        ));
+    }
+    Ok(found)
+}
+
+/// Merging or removing closes the combined window, which would kill this
+/// process mid-way if it is running inside that window.
+fn ensure_outside_window(members: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let Some((repo, worktree_path)) = members.first() else {
+        return Ok(());
+    };
+    let context = workspace::enter_repo(repo, None)?;
+    let Some(handle) = worktree_path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(());
+    };
+    let window_name = tmux::prefixed(&context.prefix, handle);
+    if tmux::current_window_name().ok().flatten().as_deref() == Some(window_name.as_str()) {
+        return Err(anyhow!(
+            "Cannot run from inside the workspace window '{}'. Switch to another window first.",
+            window_name
+        ));
+    }
+    Ok(())
+}
+
+/// Abort if any of the worktrees have uncommitted changes
+fn ensure_clean(members: &[(PathBuf, PathBuf)], hint: &str) -> Result<()> {
+    let dirty: Vec<&PathBuf> = members
+        .iter()
+        .filter(|(_, path)| git::has_uncommitted_changes(path).unwrap_or(false))
+        .map(|(_, path)| path)
+        .collect();
+    if dirty.is_empty() {
+        return Ok(());
+    }
+    eprintln!("The following worktrees have uncommitted changes:");
+    for path in dirty {
+        eprintln!("  - {}", path.display());
+    }
+    Err(anyhow!("{}", hint))
+}
+
+pub fn add(
+    workspace_name: Option<&str>,
+    branch_name: &str,
+    base: Option<&str>,
+    agent: Option<&str>,
+    setup: SetupFlags,
+) -> Result<()> {
+    let (name, repos) = resolve(workspace_name)?;
+
+    let mut options = SetupOptions::new(!setup.no_hooks, !setup.no_file_ops, !setup.no_pane_cmds);
+    options.focus_window = !setup.background;
+    options.log_agent = setup.log_agent;
+
+    let members = workspace::create(&repos, branch_name, base, agent, options)
+        .with_context(|| format!("Failed to create workspace worktrees for '{}'", name))?;
+
+    println!(
+        "✓ Created '{}' in workspace '{}' ({} repositories)",
+        branch_name,
+        name,
+        members.len()
+    );
+    for member in &members {
+        println!("  {}", member.worktree_path.display());
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn merge(
+    workspace_name: Option<&str>,
+    branch_name: &str,
+    ignore_uncommitted: bool,
+    rebase: bool,
+    squash: bool,
+    keep: bool,
+    no_verify: bool,
+) -> Result<()> {
+    let (_, repos) = resolve(workspace_name)?;
+    let members = find_members(&repos, branch_name)?;
+    ensure_outside_window(&members)?;
+
+    if !ignore_uncommitted {
+        ensure_clean(
+            &members,
+            "Cannot merge worktrees with uncommitted changes. Use --ignore-uncommitted to override.",
+        )?;
+    }
+
+    for (repo, _) in &members {
+        println!("\n==> {}", repo.display());
+        workspace::enter_repo(repo, None)?;
+        super::merge::run(
+            Some(branch_name),
+            None,
+            super::merge::MergeOptions {
+                ignore_uncommitted,
+                rebase,
+                squash,
+                keep,
+                no_verify,
+                ..Default::default()
+            },
+        )
+        .with_context(|| format!("Failed to merge '{}' in {}", branch_name, repo.display()))?;
+    }
+    Ok(())
+}
+
+pub fn remove(
+    workspace_name: Option<&str>,
+    branch_name: &str,
+    force: bool,
+    keep_branch: bool,
+) -> Result<()> {
+    let (_, repos) = resolve(workspace_name)?;
+    let members = find_members(&repos, branch_name)?;
+    ensure_outside_window(&members)?;
+
+    if !force {
+        ensure_clean(
+            &members,
+            "Cannot remove worktrees with uncommitted changes. Use --force to override.",
+        )?;
+    }
+
+    for (repo, _) in &members {
+        println!("\n==> {}", repo.display());
+        workspace::enter_repo(repo, None)?;
+        super::remove::run(
+            vec![branch_name.to_string()],
+            false,
+            false,
+            false,
+            force,
+            keep_branch,
+            false, // delete_remote
+            false,
+        )
+        .with_context(|| format!("Failed to remove '{}' in {}", branch_name, repo.display()))?;
+    }
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let config = config::Config::load(None)?;
+    let Some(workspaces) = config.workspaces.as_ref().filter(|w| !w.is_empty()) else {
+        println!("No workspaces configured");
+        return Ok(());
+    };
+
+    let base = git::get_main_worktree_root()
+        .ok()
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    let home = home::home_dir();
+    for (name, ws) in workspaces {
+        println!("{}", name);
+        for raw in &ws.repos {
+            let path = workspace::resolve_repo_path(raw, &base, home.as_deref());
+            let missing = if path.is_dir() { "" } else { " (missing)" };
+            println!("  {}{}", path.display(), missing);
+        }
+    }
+    Ok(())
+}
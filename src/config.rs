@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use tracing::{debug, warn};
 
-use crate::{cmd, git};
+use crate::vcs::Vcs;
+use crate::{git, jj, tmux};
 use which::{which, which_in};
 
 /// Default script for cleaning up node_modules directories before worktree deletion.
@@ -12,6 +14,9 @@ use which::{which, which_in};
 /// making the workmux remove command return almost instantly.
 const NODE_MODULES_CLEANUP_SCRIPT: &str = include_str!("scripts/cleanup_node_modules.sh");
 
+/// Layout used in place of `panes` when that isn't set
+const DEFAULT_LAYOUT: &str = "default";
+
 /// Configuration for file operations during worktree creation
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct FileConfig {
@@ -47,6 +52,142 @@ impl StatusIcons {
     pub fn done(&self) -> &str {
         self.done.as_deref().unwrap_or("✅")
     }
+
+    /// Map a stored status icon back to its semantic name (working/waiting/done).
+    pub fn status_name(&self, icon: &str) -> Option<&'static str> {
+        if icon == self.working() {
+            Some("working")
+        } else if icon == self.waiting() {
+            Some("waiting")
+        } else if icon == self.done() {
+            Some("done")
+        } else {
+            None
+        }
+    }
+}
+
+/// How workmux integrates with an agent CLI, keyed in the `agents` config
+/// section by the agent's executable name. Unset fields fall back to the
+/// built-in profile for that agent, if there is one.
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
+pub struct AgentProfile {
+    /// Command that launches the agent when `agent` is set to the profile name
+    pub command: Option<String>,
+    /// Arguments that pass the initial prompt, with `{prompt}` standing for the
+    /// prompt text (e.g. `-i {prompt}`), or `paste` to type the prompt into
+    /// the agent once it has started. Default: `-- {prompt}`
+    pub prompt: Option<String>,
+    /// Whether the agent reports its status through workmux hooks, so a
+    /// prompted agent can be marked as working right away. Default: false
+    pub status_hooks: Option<bool>,
+    /// Whether the pane title the agent sets is worth showing in the dashboard.
+    /// Default: true
+    pub pane_title: Option<bool>,
+    /// Regex reading the agent's cost and context use from its pane title,
+    /// with named groups `cost` (dollars) and `context` (percent)
+    pub usage_pattern: Option<String>,
+    /// Regex matching the agent's screen once it takes input, checked before
+    /// a prompt is pasted in. Default: wait for the output to settle
+    pub ready_pattern: Option<String>,
+    /// Whether prompts are typed in as keypresses rather than pasted, for
+    /// programs without bracketed paste support. Newlines are typed as
+    /// Alt+Enter. Default: false
+    pub literal_input: Option<bool>,
+    /// Milliseconds between the pieces a long prompt is sent in. Default: 30
+    pub input_delay_ms: Option<u64>,
+    /// Whether Enter is sent on its own once the agent has shown the prompt,
+    /// rather than right after it. Default: true
+    pub enter_separately: Option<bool>,
+}
+
+impl AgentProfile {
+    /// Built-in profile for a known agent
+    pub fn builtin(name: &str) -> Option<Self> {
+        let profile = |command: Option<&str>, prompt: &str, status_hooks, pane_title| Self {
+            command: command.map(str::to_string),
+            prompt: Some(prompt.to_string()),
+            status_hooks: Some(status_hooks),
+            pane_title: Some(pane_title),
+            usage_pattern: None,
+            ready_pattern: None,
+            literal_input: None,
+            input_delay_ms: None,
+            enter_separately: None,
+        };
+        match name {
+            "claude" => Some(profile(None, "-- {prompt}", true, true)),
+            "opencode" => Some(profile(None, "--prompt {prompt}", true, true)),
+            // Gemini's window title is just the product name
+            "gemini" => Some(profile(None, "-i {prompt}", false, false)),
+            // Codex runs `notify` with a JSON payload after every turn
+            "codex" => Some(profile(
+                Some(r#"codex -c 'notify=["workmux","set-window-status","done"]'"#),
+                "-- {prompt}",
+                true,
+                false,
+            )),
+            // Aider's --message exits after one reply, so the prompt is typed in
+            "aider" => Some(profile(
+                Some(
+                    "aider --notifications --notifications-command 'workmux set-window-status waiting'",
+                ),
+                "paste",
+                true,
+                false,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Fill unset fields from another profile
+    fn or(self, fallback: Self) -> Self {
+        Self {
+            command: self.command.or(fallback.command),
+            prompt: self.prompt.or(fallback.prompt),
+            status_hooks: self.status_hooks.or(fallback.status_hooks),
+            pane_title: self.pane_title.or(fallback.pane_title),
+            usage_pattern: self.usage_pattern.or(fallback.usage_pattern),
+            ready_pattern: self.ready_pattern.or(fallback.ready_pattern),
+            literal_input: self.literal_input.or(fallback.literal_input),
+            input_delay_ms: self.input_delay_ms.or(fallback.input_delay_ms),
+            enter_separately: self.enter_separately.or(fallback.enter_separately),
+        }
+    }
+
+    /// The prompt arguments with `{prompt}` replaced by `prompt`, or None
+    /// when the prompt is pasted in instead
+    pub fn prompt_args(&self, prompt: &str) -> Option<String> {
+        match self.prompt.as_deref().unwrap_or("-- {prompt}").trim() {
+            "paste" => None,
+            template => Some(template.replace("{prompt}", prompt)),
+        }
+    }
+
+    pub fn pastes_prompt(&self) -> bool {
+        self.prompt_args("").is_none()
+    }
+
+    pub fn status_hooks(&self) -> bool {
+        self.status_hooks.unwrap_or(false)
+    }
+
+    pub fn pane_title(&self) -> bool {
+        self.pane_title.unwrap_or(true)
+    }
+
+    /// How input is typed into the agent's pane
+    pub fn input_options(&self) -> tmux::InputOptions {
+        let default = tmux::InputOptions::default();
+        tmux::InputOptions {
+            literal: self.literal_input.unwrap_or(default.literal),
+            delay: self
+                .input_delay_ms
+                .map(std::time::Duration::from_millis)
+                .unwrap_or(default.delay),
+            enter_separately: self.enter_separately.unwrap_or(default.enter_separately),
+        }
+    }
 }
 
 /// Configuration for LLM-based branch name generation
@@ -75,6 +216,136 @@ pub struct DashboardConfig {
     /// Size of the preview pane as a percentage of terminal height (1-90).
     /// Default: 60 (60% for preview, 40% for table)
     pub preview_size: Option<u8>,
+
+    /// How long an agent may stay in each status before its row is flagged.
+    #[serde(default)]
+    pub alerts: AlertThresholds,
+
+    /// Ring the terminal bell when an agent crosses its alert threshold.
+    /// Default: false
+    pub alert_bell: Option<bool>,
+
+    /// Show a desktop notification when an agent crosses its alert threshold.
+    /// Default: false
+    pub alert_notify: Option<bool>,
+
+    /// How often the agent list and git status refresh, in milliseconds.
+    /// Accepts "500ms", "2s" or a plain number of milliseconds. Default: 2s
+    #[serde(default, deserialize_with = "deserialize_duration_ms")]
+    pub refresh_interval: Option<u64>,
+
+    /// How often the preview of the selected agent refreshes, in milliseconds.
+    /// Default: 500ms
+    #[serde(default, deserialize_with = "deserialize_duration_ms")]
+    pub preview_refresh_interval: Option<u64>,
+}
+
+/// Per-status idle thresholds in seconds. Accepts durations like "90s",
+/// "5m", "2h" or "1h30m", or a plain number of seconds.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct AlertThresholds {
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub working: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub waiting: Option<u64>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    pub done: Option<u64>,
+}
+
+impl AlertThresholds {
+    /// Threshold for a semantic status (working/waiting/done)
+    pub fn for_status(&self, status: &str) -> Option<u64> {
+        match status {
+            "working" => self.working,
+            "waiting" => self.waiting,
+            "done" => self.done,
+            _ => None,
+        }
+    }
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Duration {
+        Secs(u64),
+        Text(String),
+    }
+    match Option::<Duration>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Duration::Secs(secs)) => Ok(Some(secs)),
+        Some(Duration::Text(text)) => parse_duration(&text)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_duration_ms<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Duration {
+        Millis(u64),
+        Text(String),
+    }
+    match Option::<Duration>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Duration::Millis(ms)) => Ok(Some(ms)),
+        Some(Duration::Text(text)) => parse_duration_ms(&text)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Parse a duration such as "500ms" or "2s" into milliseconds. A plain number
+/// is taken as milliseconds.
+pub fn parse_duration_ms(text: &str) -> anyhow::Result<u64> {
+    let text = text.trim();
+    if let Ok(ms) = text.parse::<u64>() {
+        return Ok(ms);
+    }
+    if let Some(ms) = text.strip_suffix("ms") {
+        return ms
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid duration '{}' (expected e.g. 500ms, 2s)", text));
+    }
+    Ok(parse_duration(text)? * 1000)
+}
+
+/// Parse a duration such as "45s", "5m", "2h", "1d" or "1h30m" into seconds
+pub fn parse_duration(text: &str) -> anyhow::Result<u64> {
+    let text = text.trim();
+    if let Ok(secs) = text.parse::<u64>() {
+        return Ok(secs);
+    }
+    let invalid = || anyhow::anyhow!("Invalid duration '{}' (expected e.g. 90s, 5m, 2h)", text);
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = digits.parse().map_err(|_| invalid())?;
+        total += value * unit;
+        digits.clear();
+    }
+    if !digits.is_empty() || text.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
 }
 
 impl DashboardConfig {
@@ -93,6 +364,216 @@ impl DashboardConfig {
     pub fn preview_size(&self) -> u8 {
         self.preview_size.unwrap_or(60).clamp(10, 90)
     }
+
+    /// Agent list refresh interval (at least 250ms).
+    /// Default: 2s
+    pub fn refresh_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.refresh_interval.unwrap_or(2000).max(250))
+    }
+
+    /// Preview refresh interval (at least 100ms).
+    /// Default: 500ms
+    pub fn preview_refresh_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.preview_refresh_interval.unwrap_or(500).max(100))
+    }
+}
+
+/// Configuration for desktop notifications on agent status changes
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct NotificationConfig {
+    /// Notify when an agent starts waiting for input. Default: false
+    pub waiting: Option<bool>,
+
+    /// Notify when an agent finishes. Default: false
+    pub done: Option<bool>,
+
+    /// Shell command to run instead of the built-in system notification.
+    /// Receives WM_HANDLE, WM_STATUS, WM_ELAPSED_SECS, and WM_MESSAGE.
+    pub command: Option<String>,
+}
+
+impl NotificationConfig {
+    /// Whether a transition into the given semantic status should notify
+    pub fn enabled_for(&self, status: &str) -> bool {
+        match status {
+            "waiting" => self.waiting.unwrap_or(false),
+            "done" => self.done.unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+/// Where to report every agent status change, e.g. to chat or a metrics
+/// system
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct StatusChangeConfig {
+    /// URL to POST a JSON payload to
+    pub webhook: Option<String>,
+
+    /// Shell command to run. Receives WM_HANDLE, WM_PROJECT, WM_STATUS,
+    /// WM_PREVIOUS_STATUS, WM_ELAPSED_SECS, and the JSON payload in
+    /// WM_PAYLOAD.
+    pub command: Option<String>,
+}
+
+/// How `workmux merge` merges, so a repository can standardize it
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct MergeConfig {
+    /// Default strategy. CLI flags (--rebase, --squash) always override it.
+    pub strategy: Option<MergeStrategy>,
+
+    /// Refuse to merge a worktree with any uncommitted changes, staged ones
+    /// included, even with --ignore-uncommitted. Default: false
+    pub require_clean: Option<bool>,
+
+    /// Delete the branch on its remote after merging it. Default: false
+    pub delete_remote_branch: Option<bool>,
+}
+
+/// Defaults for `workmux remove`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct RemoveConfig {
+    /// Delete the branch on its remote after removing it, like
+    /// --delete-remote. Default: false
+    pub delete_remote_branch: Option<bool>,
+}
+
+/// Where `workmux archive` keeps archived branches
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct ArchiveConfig {
+    /// Ref namespace archived branches are stored under.
+    /// Default: refs/workmux/archive
+    pub namespace: Option<String>,
+
+    /// Remote to also push archived branches to (optional, local only by default)
+    pub remote: Option<String>,
+}
+
+impl ArchiveConfig {
+    pub fn namespace(&self) -> &str {
+        self.namespace
+            .as_deref()
+            .map(|ns| ns.trim_end_matches('/'))
+            .unwrap_or("refs/workmux/archive")
+    }
+
+    /// The ref a worktree's branch is archived to
+    pub fn ref_name(&self, handle: &str) -> String {
+        format!("{}/{}", self.namespace(), handle)
+    }
+}
+
+/// A named set of repositories that share feature branches (`workmux workspace`)
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct WorkspaceConfig {
+    /// Repository paths. Absolute, `~/`-prefixed, or relative to the current repository root.
+    pub repos: Vec<String>,
+}
+
+/// Variables panes in workmux windows always inherit, since the shell and the
+/// agent status hooks need them
+pub const ALWAYS_INHERITED: [&str; 5] = ["HOME", "PATH", "TERM", "TMUX", "TMUX_PANE"];
+
+/// What the panes of workmux windows inherit from the tmux environment, which
+/// otherwise holds everything the shell that started tmux exported
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct WindowEnv {
+    /// Only pass these variables through, plus HOME, PATH, TERM, TMUX and
+    /// TMUX_PANE. Default: pass everything through
+    #[serde(default)]
+    pub inherit: Option<Vec<String>>,
+
+    /// Variables to remove
+    #[serde(default)]
+    pub unset: Vec<String>,
+
+    /// Variables to set, overriding inherited values
+    #[serde(default)]
+    pub set: BTreeMap<String, String>,
+}
+
+impl WindowEnv {
+    /// POSIX `env` invocation that runs a command with this environment, or
+    /// None if it leaves the environment as is. Invalid names are skipped.
+    pub fn env_command(&self) -> Option<String> {
+        let valid = |name: &&str| {
+            let ok = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !ok {
+                warn!(name, "window_env:skipping invalid variable name");
+            }
+            ok
+        };
+
+        let mut parts = vec!["env".to_string()];
+        if let Some(inherit) = &self.inherit {
+            parts.push("-i".to_string());
+            let mut names: Vec<&str> = Vec::new();
+            for name in ALWAYS_INHERITED
+                .into_iter()
+                .chain(inherit.iter().map(String::as_str))
+                .filter(|name| !self.unset.iter().any(|u| u == name))
+                .filter(valid)
+            {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            // Pass through the values the pane has, and nothing for unset ones
+            for name in names {
+                parts.push(format!("${{{name}+\"{name}=${name}\"}}"));
+            }
+        } else {
+            for name in self.unset.iter().map(String::as_str).filter(valid) {
+                parts.push(format!("-u {}", name));
+            }
+        }
+        for (name, value) in self.set.iter().filter(|(name, _)| valid(&name.as_str())) {
+            parts.push(format!("{}='{}'", name, value.replace('\'', "'\\''")));
+        }
+        (parts.len() > 1).then(|| parts.join(" "))
+    }
+}
+
+/// Overrides for branches whose name matches a glob (`profiles`)
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct BranchProfile {
+    /// Branch glob, e.g. `hotfix/*`
+    #[serde(rename = "match")]
+    pub pattern: String,
+
+    /// Base branch for new worktrees when `--base` isn't given
+    #[serde(default)]
+    pub base: Option<String>,
+
+    /// Prompt template (`.workmux/templates/<name>.md`) used when `add` is
+    /// given no prompt
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+
+    #[serde(default)]
+    pub panes: Option<Vec<PaneConfig>>,
+
+    #[serde(default)]
+    pub post_create: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub pre_merge: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub post_merge: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub pre_remove: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub post_remove: Option<Vec<String>>,
+}
+
+impl BranchProfile {
+    pub fn matches(&self, branch: &str) -> bool {
+        glob::Pattern::new(&self.pattern).is_ok_and(|pattern| pattern.matches(branch))
+    }
 }
 
 /// Configuration for the workmux tool, read from .workmux.yaml
@@ -102,11 +583,34 @@ pub struct Config {
     #[serde(default)]
     pub main_branch: Option<String>,
 
+    /// Version control backend for worktrees (optional, defaults to git)
+    #[serde(default)]
+    pub vcs: Option<Vcs>,
+
     /// Directory where worktrees should be created (optional, defaults to <project>__worktrees pattern)
-    /// Can be relative to repo root or absolute path
+    /// Can be relative to repo root or absolute path, and may use `~`, `{repo}` and `{handle}`
     #[serde(default)]
     pub worktree_dir: Option<String>,
 
+    /// Which tmux session worktree windows are created in (optional, defaults to
+    /// the current session)
+    #[serde(default)]
+    pub session_strategy: Option<SessionStrategy>,
+
+    /// Session name for `session_strategy: named` (defaults to "workmux")
+    #[serde(default)]
+    pub session_name: Option<String>,
+
+    /// Where to go when `remove` or `merge` closes the window they were run
+    /// from (optional, defaults to the main window)
+    #[serde(default)]
+    pub after_remove: Option<AfterRemove>,
+
+    /// Whether `open` starts agents in the main worktree and the dashboard
+    /// sends prompts to agents there (optional, defaults to warn)
+    #[serde(default)]
+    pub main_worktree_agents: Option<MainWorktreeAgents>,
+
     /// Prefix for tmux window names (optional, defaults to "wm-")
     #[serde(default)]
     pub window_prefix: Option<String>,
@@ -115,6 +619,15 @@ pub struct Config {
     #[serde(default)]
     pub panes: Option<Vec<PaneConfig>>,
 
+    /// Named pane layouts, picked with `add --layout` or `open --layout`.
+    /// `default`, when set, is used in place of `panes` when that isn't set.
+    #[serde(default)]
+    pub layouts: BTreeMap<String, Vec<PaneConfig>>,
+
+    /// Environment the panes of workmux windows start with
+    #[serde(default)]
+    pub window_env: Option<WindowEnv>,
+
     /// Commands to run after creating the worktree
     #[serde(default)]
     pub post_create: Option<Vec<String>>,
@@ -123,18 +636,60 @@ pub struct Config {
     #[serde(default)]
     pub pre_merge: Option<Vec<String>>,
 
+    /// Commands to run after merging, in the worktree merged into
+    #[serde(default)]
+    pub post_merge: Option<Vec<String>>,
+
     /// Commands to run before removing the worktree (e.g., for backups)
     #[serde(default)]
     pub pre_remove: Option<Vec<String>>,
 
+    /// Commands to run after removing the worktree, in the main worktree
+    #[serde(default)]
+    pub post_remove: Option<Vec<String>>,
+
     /// The agent command to use (e.g., "claude", "gemini")
     #[serde(default)]
     pub agent: Option<String>,
 
-    /// Default merge strategy for `workmux merge`
+    /// Per-agent launch commands and integration settings, keyed by the
+    /// agent's executable name
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentProfile>,
+
+    /// Whether the first plain `git push` of a branch created by workmux sets its
+    /// upstream (via `push.autoSetupRemote`), so `remove --gone` can track it.
+    /// Default: false
+    #[serde(default)]
+    pub auto_track_upstream: Option<bool>,
+
+    /// Record the agent pane's output of every new window, like `--log-agent`.
+    /// Default: false
+    #[serde(default)]
+    pub log_agent: Option<bool>,
+
+    /// Default merge strategy for `workmux merge` (older spelling of
+    /// `merge.strategy`, which takes precedence)
     #[serde(default)]
     pub merge_strategy: Option<MergeStrategy>,
 
+    /// Merge policy: strategy, clean worktrees, remote branch deletion
+    #[serde(default)]
+    pub merge: MergeConfig,
+
+    /// Defaults for `workmux remove`
+    #[serde(default)]
+    pub remove: RemoveConfig,
+
+    /// How `workmux sync` brings worktrees up to date with their base branch
+    #[serde(default)]
+    pub sync_strategy: Option<SyncStrategy>,
+
+    /// Whether `workmux sync` also removes worktrees whose PR was merged or
+    /// whose upstream was deleted. Default: false
+    #[serde(default)]
+    pub auto_cleanup: Option<bool>,
+
     /// Strategy for deriving worktree/window names from branch names
     #[serde(default)]
     pub worktree_naming: WorktreeNaming,
@@ -147,6 +702,10 @@ pub struct Config {
     #[serde(default)]
     pub files: FileConfig,
 
+    /// Extra directories to include in the sparse checkout for `add --package`
+    #[serde(default)]
+    pub sparse_paths: Option<Vec<String>>,
+
     /// Whether to auto-apply workmux status to tmux window format.
     /// Default: true
     #[serde(default)]
@@ -163,6 +722,37 @@ pub struct Config {
     /// Dashboard actions configuration
     #[serde(default)]
     pub dashboard: DashboardConfig,
+
+    /// Desktop notifications when agents start waiting or finish
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+
+    /// Webhook or command run on every agent status change
+    #[serde(default)]
+    pub status_change: StatusChangeConfig,
+
+    /// Where `workmux archive` keeps archived branches
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+
+    /// Named multi-repo workspaces for `workmux workspace`
+    #[serde(default)]
+    pub workspaces: Option<BTreeMap<String, WorkspaceConfig>>,
+
+    /// Command `workmux test` runs in every worktree (e.g., "cargo test")
+    #[serde(default)]
+    pub test_command: Option<String>,
+
+    /// Overrides for branches matching a glob; the first match applies.
+    /// Project profiles are checked before global ones.
+    #[serde(default)]
+    pub profiles: Vec<BranchProfile>,
+
+    /// Whether `workmux self-update` may replace the binary. Set to false for
+    /// installs managed by a package manager (Homebrew, cargo).
+    /// Default: true
+    #[serde(default)]
+    pub self_update: Option<bool>,
 }
 
 /// Configuration for a single tmux pane
@@ -197,6 +787,11 @@ pub struct PaneConfig {
     /// Only used when `split` is specified.
     #[serde(default)]
     pub target: Option<usize>,
+
+    /// Keep the agent listed in the dashboard, marked "exited", once it exits.
+    /// The pane stays open with its shell either way.
+    #[serde(default)]
+    pub keep_alive: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -215,6 +810,56 @@ pub enum MergeStrategy {
     Squash,
 }
 
+/// How `workmux sync` updates a worktree branch with its base
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStrategy {
+    #[default]
+    Rebase,
+    Merge,
+}
+
+/// Which tmux session worktree windows live in
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionStrategy {
+    /// The session workmux is run from
+    #[default]
+    Current,
+    /// A session named after the repository, created on demand
+    PerProject,
+    /// A single session named by `session_name`, created on demand
+    Named,
+}
+
+/// Window shown after `remove` or `merge` closes the window it was run from
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AfterRemove {
+    /// The main worktree's window, or the merge target's
+    #[default]
+    Main,
+    /// The window that was active before the closed one
+    Previous,
+    /// The previous window, with the dashboard open in a popup
+    Dashboard,
+    /// Keep the window open, to be closed by hand
+    None,
+}
+
+/// Policy for agents in the main worktree, where their changes land outside
+/// any worktree branch
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MainWorktreeAgents {
+    Allow,
+    /// Start agents and send prompts, with a warning
+    #[default]
+    Warn,
+    /// Refuse to start agents or send prompts
+    Block,
+}
+
 /// Strategy for deriving worktree/window names from branch names
 #[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -224,13 +869,17 @@ pub enum WorktreeNaming {
     Full,
     /// Use only the part after the last `/` (e.g., `prj-123/feature` → `feature`)
     Basename,
+    /// Use the full branch name, with today's date appended to new branches
+    /// (e.g., `deps-update` → `deps-update-0212`) so recurring tasks don't collide
+    #[serde(rename = "date-suffix")]
+    DateSuffix,
 }
 
 impl WorktreeNaming {
     /// Derive a name from a branch name using this strategy
     pub fn derive_name(&self, branch: &str) -> String {
         match self {
-            Self::Full => branch.to_string(),
+            Self::Full | Self::DateSuffix => branch.to_string(),
             Self::Basename => branch
                 .trim_end_matches('/')
                 .rsplit('/')
@@ -299,6 +948,7 @@ impl Config {
         debug!("config:loading");
         let global_config = Self::load_global()?.unwrap_or_default();
         let project_config = Self::load_project()?.unwrap_or_default();
+        warn_about_problems(&global_config, &project_config);
 
         let final_agent = cli_agent
             .map(|s| s.to_string())
@@ -309,6 +959,11 @@ impl Config {
         let mut config = global_config.merge(project_config);
         config.agent = Some(final_agent);
 
+        // The default layout stands in for `panes`
+        if config.panes.is_none() {
+            config.panes = config.layouts.get(DEFAULT_LAYOUT).cloned();
+        }
+
         // After merging, apply sensible defaults for any values that are not configured.
         if let Ok(repo_root) = git::get_repo_root() {
             // Apply defaults that require inspecting the repository.
@@ -345,7 +1000,7 @@ impl Config {
     }
 
     /// Load configuration from a specific path.
-    fn load_from_path(path: &Path) -> anyhow::Result<Option<Self>> {
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Option<Self>> {
         if !path.exists() {
             return Ok(None);
         }
@@ -358,53 +1013,132 @@ impl Config {
 
     /// Load the global configuration file from the XDG config directory.
     fn load_global() -> anyhow::Result<Option<Self>> {
-        // Check ~/.config/workmux (XDG convention, works cross-platform)
-        if let Some(home_dir) = home::home_dir() {
-            let xdg_config_path = home_dir.join(".config/workmux/config.yaml");
-            if xdg_config_path.exists() {
-                return Self::load_from_path(&xdg_config_path);
-            }
-            let xdg_config_path_yml = home_dir.join(".config/workmux/config.yml");
-            if xdg_config_path_yml.exists() {
-                return Self::load_from_path(&xdg_config_path_yml);
-            }
+        match global_config_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Ok(None),
         }
-        Ok(None)
     }
 
-    /// Load the project-specific configuration file.
-    ///
-    /// Searches for `.workmux.yaml` or `.workmux.yml` in the following order:
-    /// 1. Current worktree root (allows branch-specific config overrides)
-    /// 2. Main worktree root (shared config across all worktrees)
-    /// 3. Falls back gracefully when not in a git repository
+    /// Load the project-specific configuration file (see [`project_config_path`]).
     fn load_project() -> anyhow::Result<Option<Self>> {
-        let config_names = [".workmux.yaml", ".workmux.yml"];
+        match project_config_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Ok(None),
+        }
+    }
 
-        // Build list of directories to search
-        let mut search_dirs = Vec::new();
-        if let Ok(repo_root) = git::get_repo_root() {
-            search_dirs.push(repo_root.clone());
-            // Also check main worktree root if different from current worktree
-            if let Ok(main_root) = git::get_main_worktree_root()
-                && main_root != repo_root
-            {
-                search_dirs.push(main_root);
+    /// Problems in this config file, described precisely enough to fix.
+    ///
+    /// `global` is the global config when checking a project config, used to
+    /// resolve `"<global>"` placeholders, and None when checking the global
+    /// config itself.
+    pub fn lint(&self, global: Option<&Config>) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let layouts = self
+            .layouts
+            .iter()
+            .map(|(name, panes)| (format!("layouts.{}", name), panes));
+        for (key, panes) in self
+            .panes
+            .iter()
+            .map(|panes| ("panes".to_string(), panes))
+            .chain(layouts)
+        {
+            if let Err(e) = validate_panes_config(panes) {
+                problems.push(format!("{}: {}", key, e));
+            }
+            for (i, pane) in panes.iter().enumerate() {
+                if let Some(ref command) = pane.command
+                    && command.trim() != "<agent>"
+                    && command.contains("<agent>")
+                {
+                    problems.push(format!(
+                        "{}[{}].command: \"<agent>\" is only substituted when it is the whole command; \
+                         put extra flags in `agent` instead",
+                        key, i
+                    ));
+                }
             }
         }
 
-        // Search for config in each directory
-        for dir in search_dirs {
-            for name in &config_names {
-                let config_path = dir.join(name);
-                if config_path.exists() {
-                    debug!(path = %config_path.display(), "config:found project config");
-                    return Self::load_from_path(&config_path);
+        let lists = |c: &Config| {
+            [
+                ("post_create", c.post_create.clone()),
+                ("pre_merge", c.pre_merge.clone()),
+                ("post_merge", c.post_merge.clone()),
+                ("pre_remove", c.pre_remove.clone()),
+                ("post_remove", c.post_remove.clone()),
+                ("files.copy", c.files.copy.clone()),
+                ("files.symlink", c.files.symlink.clone()),
+                ("sparse_paths", c.sparse_paths.clone()),
+            ]
+        };
+        let global_lists = global.map(lists);
+        for (index, (key, items)) in lists(self).into_iter().enumerate() {
+            for (i, item) in items.iter().flatten().enumerate() {
+                if item.trim().is_empty() {
+                    problems.push(format!("{}[{}]: empty entry", key, i));
+                } else if item == "<global>" {
+                    match global_lists {
+                        None => problems.push(format!(
+                            "{}[{}]: \"<global>\" only works in a project config",
+                            key, i
+                        )),
+                        Some(ref global_lists) if global_lists[index].1.is_none() => {
+                            problems.push(format!(
+                                "{}[{}]: \"<global>\" refers to the global {}, which isn't set",
+                                key, i, key
+                            ))
+                        }
+                        Some(_) => {}
+                    }
                 }
             }
         }
 
-        Ok(None)
+        for (name, profile) in &self.agents {
+            if let Some(ref command) = profile.command
+                && command.trim().is_empty()
+            {
+                problems.push(format!("agents.{}.command: empty command", name));
+            }
+            if let Some(ref prompt) = profile.prompt
+                && prompt.trim() != "paste"
+                && !prompt.contains("{prompt}")
+            {
+                problems.push(format!(
+                    "agents.{}.prompt: missing the {{prompt}} placeholder, so the prompt \
+                     would never be passed (use `paste` to type it in instead)",
+                    name
+                ));
+            }
+        }
+
+        for (i, profile) in self.profiles.iter().enumerate() {
+            if let Err(e) = glob::Pattern::new(&profile.pattern) {
+                problems.push(format!(
+                    "profiles[{}].match: invalid glob \"{}\": {}",
+                    i, profile.pattern, e
+                ));
+            }
+            if let Some(ref panes) = profile.panes
+                && let Err(e) = validate_panes_config(panes)
+            {
+                problems.push(format!("profiles[{}].panes: {}", i, e));
+            }
+        }
+
+        if let Some(ref namespace) = self.archive.namespace
+            && !namespace.starts_with("refs/")
+        {
+            problems.push(format!(
+                "archive.namespace: \"{}\" is not a ref namespace (it must start with refs/)",
+                namespace
+            ));
+        }
+
+        problems
     }
 
     /// Merge a project config into a global config.
@@ -453,14 +1187,27 @@ impl Config {
             self,
             project,
             main_branch,
+            vcs,
             worktree_dir,
             window_prefix,
+            session_strategy,
+            session_name,
+            after_remove,
+            main_worktree_agents,
             agent,
             merge_strategy,
+            sync_strategy,
+            auto_cleanup,
+            auto_track_upstream,
+            log_agent,
             worktree_prefix,
             panes,
+            window_env,
             status_format,
             auto_name,
+            workspaces,
+            self_update,
+            test_command,
         );
 
         // Special case: worktree_naming (project wins if not default)
@@ -473,7 +1220,10 @@ impl Config {
         // List values with "<global>" placeholder support
         merged.post_create = merge_vec_with_placeholder(self.post_create, project.post_create);
         merged.pre_merge = merge_vec_with_placeholder(self.pre_merge, project.pre_merge);
+        merged.post_merge = merge_vec_with_placeholder(self.post_merge, project.post_merge);
         merged.pre_remove = merge_vec_with_placeholder(self.pre_remove, project.pre_remove);
+        merged.post_remove = merge_vec_with_placeholder(self.post_remove, project.post_remove);
+        merged.sparse_paths = merge_vec_with_placeholder(self.sparse_paths, project.sparse_paths);
 
         // File config with placeholder support
         merged.files = FileConfig {
@@ -496,11 +1246,176 @@ impl Config {
                 .dashboard
                 .preview_size
                 .or(self.dashboard.preview_size),
+            alerts: AlertThresholds {
+                working: project
+                    .dashboard
+                    .alerts
+                    .working
+                    .or(self.dashboard.alerts.working),
+                waiting: project
+                    .dashboard
+                    .alerts
+                    .waiting
+                    .or(self.dashboard.alerts.waiting),
+                done: project.dashboard.alerts.done.or(self.dashboard.alerts.done),
+            },
+            alert_bell: project.dashboard.alert_bell.or(self.dashboard.alert_bell),
+            alert_notify: project
+                .dashboard
+                .alert_notify
+                .or(self.dashboard.alert_notify),
+            refresh_interval: project
+                .dashboard
+                .refresh_interval
+                .or(self.dashboard.refresh_interval),
+            preview_refresh_interval: project
+                .dashboard
+                .preview_refresh_interval
+                .or(self.dashboard.preview_refresh_interval),
+        };
+
+        // Agent profiles: per-agent, per-field override
+        merged.agents = self.agents;
+        for (name, profile) in project.agents {
+            let profile = match merged.agents.remove(&name) {
+                Some(global) => profile.or(global),
+                None => profile,
+            };
+            merged.agents.insert(name, profile);
+        }
+
+        // Notifications: per-field override
+        merged.notifications = NotificationConfig {
+            waiting: project.notifications.waiting.or(self.notifications.waiting),
+            done: project.notifications.done.or(self.notifications.done),
+            command: project.notifications.command.or(self.notifications.command),
+        };
+
+        // Status change reporting: per-field override
+        merged.status_change = StatusChangeConfig {
+            webhook: project.status_change.webhook.or(self.status_change.webhook),
+            command: project.status_change.command.or(self.status_change.command),
+        };
+
+        // Merge policy: per-field override
+        merged.merge = MergeConfig {
+            strategy: project.merge.strategy.or(self.merge.strategy),
+            require_clean: project.merge.require_clean.or(self.merge.require_clean),
+            delete_remote_branch: project
+                .merge
+                .delete_remote_branch
+                .or(self.merge.delete_remote_branch),
         };
 
+        // Remove defaults: per-field override
+        merged.remove = RemoveConfig {
+            delete_remote_branch: project
+                .remove
+                .delete_remote_branch
+                .or(self.remove.delete_remote_branch),
+        };
+
+        // Archive: per-field override
+        merged.archive = ArchiveConfig {
+            namespace: project.archive.namespace.or(self.archive.namespace),
+            remote: project.archive.remote.or(self.archive.remote),
+        };
+
+        // Layouts: project ones replace global ones of the same name
+        merged.layouts = self.layouts;
+        merged.layouts.extend(project.layouts);
+
+        // Branch profiles: project ones take precedence by coming first
+        merged.profiles = project.profiles.into_iter().chain(self.profiles).collect();
+
         merged
     }
 
+    /// The first profile whose glob matches the branch
+    pub fn branch_profile(&self, branch: &str) -> Option<&BranchProfile> {
+        self.profiles.iter().find(|profile| profile.matches(branch))
+    }
+
+    /// This config with the panes and hooks of the branch's profile, if one
+    /// matches
+    pub fn for_branch(mut self, branch: &str) -> Self {
+        let Some(profile) = self.branch_profile(branch).cloned() else {
+            return self;
+        };
+        debug!(
+            branch,
+            pattern = profile.pattern,
+            "config:applying branch profile"
+        );
+        self.panes = profile.panes.or(self.panes);
+        self.post_create = profile.post_create.or(self.post_create);
+        self.pre_merge = profile.pre_merge.or(self.pre_merge);
+        self.post_merge = profile.post_merge.or(self.post_merge);
+        self.pre_remove = profile.pre_remove.or(self.pre_remove);
+        self.post_remove = profile.post_remove.or(self.post_remove);
+        self
+    }
+
+    /// This config with the panes of the named layout (`--layout`). Naming
+    /// `default` without a `default` layout keeps `panes`.
+    pub fn with_layout(mut self, name: Option<&str>) -> anyhow::Result<Self> {
+        let Some(name) = name else {
+            return Ok(self);
+        };
+        match self.layouts.get(name) {
+            Some(panes) => {
+                debug!(layout = name, "config:applying layout");
+                self.panes = Some(panes.clone());
+            }
+            None if name == DEFAULT_LAYOUT => {}
+            None if self.layouts.is_empty() => {
+                anyhow::bail!("Unknown layout '{}': no layouts are configured", name)
+            }
+            None => anyhow::bail!(
+                "Unknown layout '{}'. Available layouts: {}",
+                name,
+                self.layouts.keys().cloned().collect::<Vec<_>>().join(", ")
+            ),
+        }
+        Ok(self)
+    }
+
+    /// [`Config::for_branch`] for the branch of the named worktree. Left
+    /// unchanged if there is no such worktree, for the command to report.
+    pub fn for_worktree(self, name: &str) -> Self {
+        match git::find_worktree(name) {
+            Ok((_, branch)) => self.for_branch(&branch),
+            Err(_) => self,
+        }
+    }
+
+    /// Integration profile for an agent command, looked up by its executable name
+    pub fn agent_profile(&self, agent_command: &str) -> AgentProfile {
+        self.agent_profile_named(&agent_name(agent_command).unwrap_or_default())
+    }
+
+    /// The `agents` entry for an agent name, falling back to the built-in profile
+    pub fn agent_profile_named(&self, name: &str) -> AgentProfile {
+        let builtin = AgentProfile::builtin(name).unwrap_or_default();
+        match self.agents.get(name) {
+            Some(profile) => profile.clone().or(builtin),
+            None => builtin,
+        }
+    }
+
+    /// Command that launches an agent. An agent given by bare name uses its
+    /// profile's `command`, if set; anything else is run as written.
+    pub fn agent_launch_command(&self, agent: &str) -> String {
+        let agent = agent.trim();
+        let is_bare_name = !agent.contains(|c: char| c.is_whitespace() || c == '/' || c == '\\');
+        let profile_command = if is_bare_name {
+            self.agent_profile_named(agent).command
+        } else {
+            None
+        };
+        profile_command.unwrap_or_else(|| agent.to_string())
+    }
+
     /// Get default panes.
     fn default_panes() -> Vec<PaneConfig> {
         vec![
@@ -511,6 +1426,7 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
             PaneConfig {
                 command: Some("clear".to_string()),
@@ -519,6 +1435,7 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None, // Splits most recent (pane 0)
+                keep_alive: false,
             },
         ]
     }
@@ -533,6 +1450,7 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
             PaneConfig {
                 command: Some("clear".to_string()),
@@ -541,15 +1459,92 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None, // Splits most recent (pane 0)
+                keep_alive: false,
             },
         ]
     }
 
+    /// Default strategy for `workmux merge`: `merge.strategy`, or the older
+    /// top-level `merge_strategy`
+    pub fn default_merge_strategy(&self) -> Option<MergeStrategy> {
+        self.merge.strategy.or(self.merge_strategy)
+    }
+
     /// Get the window prefix to use, defaulting to "wm-" if not configured
     pub fn window_prefix(&self) -> &str {
         self.window_prefix.as_deref().unwrap_or("wm-")
     }
 
+    /// Whether any pane runs the agent
+    pub fn panes_run_agent(&self) -> bool {
+        let agent = self.agent.as_deref().unwrap_or_default();
+        self.panes.iter().flatten().any(|pane| {
+            pane.command
+                .as_deref()
+                .is_some_and(|cmd| is_agent_command(cmd, agent))
+        })
+    }
+
+    /// Tmux session for worktree windows of the repository at `main_worktree_root`,
+    /// or `None` to use the current session
+    pub fn window_session(&self, main_worktree_root: &Path) -> Option<String> {
+        let name = match self.session_strategy.unwrap_or_default() {
+            SessionStrategy::Current => return None,
+            SessionStrategy::PerProject => main_worktree_root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "workmux".to_string()),
+            SessionStrategy::Named => self
+                .session_name
+                .clone()
+                .unwrap_or_else(|| "workmux".to_string()),
+        };
+        // tmux doesn't allow '.' or ':' in session names
+        Some(name.replace(['.', ':'], "_"))
+    }
+
+    /// Path of the worktree for `handle` in the repository at `main_worktree_root`.
+    ///
+    /// Without `worktree_dir`, worktrees go in a `<repo>__worktrees` sibling
+    /// directory. `worktree_dir` can be absolute, relative to the repository or
+    /// start with `~`, and may contain `{repo}` (the main worktree's directory
+    /// name) and `{handle}`. Without `{handle}`, the handle is appended.
+    pub fn worktree_path(
+        &self,
+        main_worktree_root: &Path,
+        handle: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let repo = main_worktree_root
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Could not determine project name"))?;
+
+        let Some(template) = self.worktree_dir.as_deref() else {
+            return Ok(main_worktree_root
+                .parent()
+                .ok_or_else(|| anyhow::anyhow!("Could not determine parent directory"))?
+                .join(format!("{}__worktrees", repo))
+                .join(handle));
+        };
+
+        let has_handle = template.contains("{handle}");
+        let last_component = Path::new(template).file_name().and_then(|n| n.to_str());
+        if has_handle && last_component != Some("{handle}") {
+            anyhow::bail!(
+                "Invalid worktree_dir '{}': {{handle}} can only be used as the last path component",
+                template
+            );
+        }
+
+        let expanded = expand_home(&template.replace("{repo}", repo).replace("{handle}", handle));
+        let path = if expanded.is_absolute() {
+            expanded
+        } else {
+            main_worktree_root.join(expanded)
+        };
+        Ok(if has_handle { path } else { path.join(handle) })
+    }
+
     /// Create an example .workmux.yaml configuration file
     pub fn init() -> anyhow::Result<()> {
         use std::path::PathBuf;
@@ -574,22 +1569,68 @@ impl Config {
 # Default: Auto-detected from remote HEAD, falls back to main/master.
 # main_branch: main
 
-# Default merge strategy for `workmux merge`.
-# Options: merge (default), rebase, squash
-# CLI flags (--rebase, --squash) always override this.
-# merge_strategy: rebase
+# Version control backend: git worktrees, or jj workspaces.
+# Default: git, also in colocated jj repositories.
+# vcs: jj
+
+# How `workmux merge` merges.
+# strategy: merge (default), rebase, or squash. CLI flags (--rebase, --squash)
+#   always override it. The older top-level `merge_strategy` still works.
+# require_clean: refuse to merge worktrees with uncommitted changes, even
+#   staged ones, and ignore --ignore-uncommitted. Default: false
+# delete_remote_branch: after merging, also delete the branch on the remote it
+#   tracks, like --delete-remote. Default: false
+# merge:
+#   strategy: squash
+#   require_clean: true
+#   delete_remote_branch: true
+
+# Make `workmux remove` also delete the branch on the remote it tracks, like
+# --delete-remote. Default: false
+# remove:
+#   delete_remote_branch: true
+
+# How `workmux sync` brings worktrees up to date with their base branch.
+# Options: rebase (default), merge
+# CLI flags (--rebase, --merge) always override this.
+# sync_strategy: merge
+
+# Make every `workmux sync` also remove worktrees that were merged outside
+# workmux (PR merged or upstream deleted), like `workmux sync --cleanup`.
+# Default: false
+# auto_cleanup: true
+
+# Make the first plain `git push` of a new workmux branch set its upstream to
+# origin/<branch>, even if your git config doesn't, so `workmux remove --gone`
+# can tell when it was deleted. Sets `push.autoSetupRemote` in the repository's
+# local git config unless you have configured it yourself (needs git 2.37+).
+# Default: false
+# auto_track_upstream: true
+
+# Record the output of the agent pane, so it can still be read with
+# `workmux logs <name> --agent` after the scrollback is gone. Default: false
+# log_agent: true
+
+# Where `workmux archive` keeps archived branches, and a remote to also push
+# them to. Default: refs/workmux/archive, local only.
+# archive:
+#   namespace: refs/workmux/archive
+#   remote: origin
 
 #-------------------------------------------------------------------------------
 # Naming & Paths
 #-------------------------------------------------------------------------------
 
 # Directory where worktrees are created.
-# Can be relative to repo root or absolute.
+# Can be relative to repo root or absolute, and may start with '~'.
+# Supports {repo} (repository directory name) and {handle} (worktree name).
 # Default: Sibling directory '<project>__worktrees'.
 # worktree_dir: .worktrees
+# worktree_dir: ~/worktrees/{repo}/{handle}
 
 # Strategy for deriving names from branch names.
-# Options: full (default), basename (part after last '/').
+# Options: full (default), basename (part after last '/'),
+# date-suffix (full, with today's date appended to new branches: deps-update-0212).
 # worktree_naming: basename
 
 # Prefix added to worktree directories and tmux window names.
@@ -599,6 +1640,26 @@ impl Config {
 # Default: "wm-"
 # window_prefix: "wm-"
 
+# Which tmux session worktree windows are created in.
+# Options: current (default), per-project (a session named after the
+# repository), named (the session given by `session_name`).
+# Sessions are created on demand and `add`/`open` switch to them.
+# session_strategy: per-project
+# session_name: agents
+
+# Window to show when `remove` or `merge` closes the window it was run from.
+# Options: main (default; the merge target for merge), previous, dashboard
+# (the previous window with the dashboard in a popup), none (keep the window
+# open and close it yourself).
+# Nothing moves if you have switched to another window in the meantime.
+# after_remove: previous
+
+# Agents in the main worktree change files outside any worktree branch.
+# Options: warn (default; `open` prints a warning and the dashboard marks the
+# prompt composer), block (`open` refuses to start the agent and the dashboard
+# won't send prompts there), allow.
+# main_worktree_agents: block
+
 #-------------------------------------------------------------------------------
 # Tmux
 #-------------------------------------------------------------------------------
@@ -613,6 +1674,26 @@ impl Config {
 #     split: vertical
 #     size: 5
 
+# Named pane layouts, picked with `workmux add --layout <name>` or
+# `workmux open --layout <name>`. A `default` layout is used when `panes` isn't
+# set.
+# layouts:
+#   review:
+#     - command: <agent>
+#     - command: git diff main
+#       split: horizontal
+#       percentage: 60
+#       focus: true
+
+# Environment of panes in workmux windows. By default they inherit the tmux
+# server's environment, which includes everything your login shell exported.
+# `inherit` passes only the listed variables (plus HOME, PATH, TERM, TMUX and
+# TMUX_PANE), `unset` removes variables, and `set` adds or overrides them.
+# window_env:
+#   unset: [AWS_SECRET_ACCESS_KEY, GITHUB_TOKEN]
+#   set:
+#     NODE_ENV: development
+
 # Auto-apply agent status icons to tmux window format.
 # Default: true
 # status_format: true
@@ -623,6 +1704,24 @@ impl Config {
 #   waiting: "💬"
 #   done: "✅"
 
+# Desktop notifications when an agent starts waiting for input or finishes.
+# Default: disabled.
+# Set `command` to use your own notifier instead (receives WM_HANDLE,
+# WM_STATUS, WM_ELAPSED_SECS, and WM_MESSAGE).
+# notifications:
+#   waiting: true
+#   done: true
+#   command: terminal-notifier -title workmux -message "$WM_MESSAGE"
+
+# Report every agent status change (working, waiting, done), e.g. to Slack or
+# a metrics system. `webhook` receives a JSON POST with handle, project,
+# status, previous_status, elapsed_secs, timestamp, and a `text` summary;
+# `command` receives WM_HANDLE, WM_PROJECT, WM_STATUS, WM_PREVIOUS_STATUS,
+# WM_ELAPSED_SECS, and the JSON in WM_PAYLOAD.
+# status_change:
+#   webhook: https://hooks.slack.com/services/T000/B000/XXXX
+#   command: ~/bin/record-agent-status
+
 #-------------------------------------------------------------------------------
 # Agent & AI
 #-------------------------------------------------------------------------------
@@ -651,7 +1750,8 @@ impl Config {
 # Commands to run before merging (e.g., linting, tests).
 # Aborts the merge if any command fails.
 # Use "<global>" to inherit from global config.
-# Environment variables available:
+# Environment variables available (also as WORKMUX_BRANCH, WORKMUX_TARGET,
+# WORKMUX_WORKTREE_PATH, WORKMUX_PROJECT_ROOT and WORKMUX_HANDLE):
 #   - WM_BRANCH_NAME: The name of the branch being merged
 #   - WM_TARGET_BRANCH: The name of the target branch (e.g., main)
 #   - WM_WORKTREE_PATH: Absolute path to the worktree
@@ -662,6 +1762,12 @@ impl Config {
 #   - cargo test
 #   - cargo clippy -- -D warnings
 
+# Commands to run after a successful merge, in the worktree merged into.
+# Same environment variables as pre_merge. Failures are reported but don't
+# undo the merge.
+# post_merge:
+#   - git push origin "$WM_TARGET_BRANCH"
+
 # Commands to run before worktree removal (during merge or remove).
 # Useful for backing up gitignored files before cleanup.
 # Default: Auto-detects Node.js projects and fast-deletes node_modules.
@@ -674,6 +1780,31 @@ impl Config {
 #   - mkdir -p "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE"
 #   - cp -r test-results/ "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE/"
 
+# Commands to run after worktree removal (during merge or remove), in the
+# main worktree. Same environment variables as pre_remove, plus
+# WM_BRANCH_NAME. Failures are reported but don't stop the removal.
+# post_remove:
+#   - docker compose -p "$WM_HANDLE" down
+
+# Command `workmux test` runs in every worktree, in parallel.
+# Gets the same environment variables as post_create.
+# test_command: cargo test
+
+# Overrides for branches matching a glob. The first matching profile can
+# replace panes and hooks, set the base branch when `--base` isn't given, and
+# pick a prompt template when `add` gets no prompt.
+# profiles:
+#   - match: "hotfix/*"
+#     base: production
+#     prompt_template: hotfix
+#     pre_merge:
+#       - cargo test
+#   - match: "spike/*"
+#     post_create: []
+#     panes:
+#       - command: <agent>
+#         focus: true
+
 #-------------------------------------------------------------------------------
 # Files
 #-------------------------------------------------------------------------------
@@ -691,6 +1822,12 @@ impl Config {
 #     - "<global>"
 #     - node_modules
 
+# Extra directories to check out in worktrees scoped with `workmux add --package`.
+# The package directory and root-level files are always included.
+# Use "<global>" to inherit from global config.
+# sparse_paths:
+#   - libs/shared
+
 #-------------------------------------------------------------------------------
 # Dashboard
 #-------------------------------------------------------------------------------
@@ -698,10 +1835,34 @@ impl Config {
 # Actions for dashboard keybindings (c = commit, m = merge).
 # Values are sent to the agent's pane. Use ! prefix for shell commands.
 # Preview size (10-90): larger = more preview, less table. Use +/- keys to adjust.
+# Alerts flag agents (red row) that have been in a status longer than the
+# given duration, optionally ringing the terminal bell or sending a
+# desktop notification when they cross it.
+# Refresh intervals trade liveliness for tmux/git polling (Space pauses it).
 # dashboard:
 #   commit: "Commit staged changes with a descriptive message"
 #   merge: "!workmux merge"
 #   preview_size: 60
+#   alerts:
+#     waiting: 5m
+#     working: 2h
+#   alert_bell: true
+#   alert_notify: false
+#   refresh_interval: 2s
+#   preview_refresh_interval: 500ms
+
+#-------------------------------------------------------------------------------
+# Workspaces
+#-------------------------------------------------------------------------------
+
+# Repositories that share feature branches (`workmux workspace add <branch>`).
+# Usually defined in the global config. Paths can be absolute, start with ~/,
+# or be relative to the current repository root.
+# workspaces:
+#   shop:
+#     repos:
+#       - ~/code/shop-frontend
+#       - ~/code/shop-backend
 "#;
 
         fs::write(&config_path, example_config)?;
@@ -751,13 +1912,131 @@ pub fn resolve_executable_path(executable: &str) -> Option<String> {
 }
 
 pub fn tmux_global_path() -> Option<String> {
-    let output = cmd::Cmd::new("tmux")
+    let output = tmux::cmd()
         .args(&["show-environment", "-g", "PATH"])
         .run_and_capture_stdout()
         .ok()?;
     output.strip_prefix("PATH=").map(|s| s.to_string())
 }
 
+/// Path of the global configuration file: `~/.config/workmux/config.yaml`, or
+/// `config.yml` if only that exists (XDG convention, works cross-platform)
+pub fn global_config_path() -> Option<PathBuf> {
+    let dir = home::home_dir()?.join(".config/workmux");
+    let yml = dir.join("config.yml");
+    if !dir.join("config.yaml").exists() && yml.exists() {
+        return Some(yml);
+    }
+    Some(dir.join("config.yaml"))
+}
+
+/// Path of the project configuration file, if there is one.
+///
+/// Searches for `.workmux.yaml` or `.workmux.yml` in the following order:
+/// 1. Current worktree root (allows branch-specific config overrides)
+/// 2. Main worktree root (shared config across all worktrees)
+/// 3. Falls back gracefully when not in a git repository
+pub fn project_config_path() -> Option<PathBuf> {
+    // Build list of directories to search
+    let mut search_dirs = Vec::new();
+    if let Ok(repo_root) = git::get_repo_root() {
+        search_dirs.push(repo_root.clone());
+        // Also check main worktree root if different from current worktree
+        if let Ok(main_root) = git::get_main_worktree_root()
+            && main_root != repo_root
+        {
+            search_dirs.push(main_root);
+        }
+    } else if let Ok(workspace_root) = jj::workspace_root() {
+        // jj workspaces other than the default one have no `.git`
+        search_dirs.push(workspace_root.clone());
+        if let Ok(main_root) = jj::main_workspace_root()
+            && main_root != workspace_root
+        {
+            search_dirs.push(main_root);
+        }
+    }
+
+    // Search for config in each directory
+    let path = search_dirs.iter().find_map(|dir| find_project_config(dir));
+    if let Some(ref path) = path {
+        debug!(path = %path.display(), "config:found project config");
+    }
+    path
+}
+
+/// Print what [`Config::lint`] finds in the config files, once per process,
+/// so mistakes show up when a command runs rather than when the setting is
+/// first needed
+fn warn_about_problems(global: &Config, project: &Config) {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    WARNED.call_once(|| {
+        let path_of: [fn() -> Option<PathBuf>; 2] = [global_config_path, project_config_path];
+        let files = [global.lint(None), project.lint(Some(global))]
+            .into_iter()
+            .zip(path_of);
+        for (problems, path) in files {
+            if problems.is_empty() {
+                continue;
+            }
+            let path = path().map(|p| p.display().to_string()).unwrap_or_default();
+            for problem in problems {
+                warn!(path, problem, "config:problem");
+                eprintln!("Warning: {}: {}", path, problem);
+            }
+        }
+    });
+}
+
+/// Find the project configuration file (`.workmux.yaml` or `.workmux.yml`) in `dir`
+pub fn find_project_config(dir: &Path) -> Option<PathBuf> {
+    [".workmux.yaml", ".workmux.yml"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest.trim_start_matches('/'),
+        _ => return PathBuf::from(path),
+    };
+    match home::home_dir() {
+        Some(home) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Project name of a worktree path, read from the `{repo}` component of an
+/// absolute `worktree_dir` template such as `~/worktrees/{repo}/{handle}`.
+pub fn project_from_worktree_dir(template: &str, path: &Path) -> Option<String> {
+    if !template.contains("{repo}") {
+        return None;
+    }
+    let template = expand_home(template);
+    if !template.is_absolute() {
+        return None;
+    }
+
+    let mut path_components = path.components();
+    for component in template.components() {
+        let pattern = component.as_os_str().to_str()?;
+        let actual = path_components.next()?.as_os_str().to_str()?;
+        if let Some((prefix, suffix)) = pattern.split_once("{repo}") {
+            return actual
+                .strip_prefix(prefix)?
+                .strip_suffix(suffix)
+                .filter(|repo| !repo.is_empty())
+                .map(str::to_string);
+        }
+        if pattern != actual {
+            return None;
+        }
+    }
+    None
+}
+
 pub fn split_first_token(command: &str) -> Option<(&str, &str)> {
     let trimmed = command.trim_start();
     if trimmed.is_empty() {
@@ -770,6 +2049,17 @@ pub fn split_first_token(command: &str) -> Option<(&str, &str)> {
     )
 }
 
+/// Executable name of an agent command, which keys its profile
+/// (e.g. "claude" for "/usr/local/bin/claude --verbose")
+pub fn agent_name(agent_command: &str) -> Option<String> {
+    let (token, _) = split_first_token(agent_command)?;
+    let resolved = resolve_executable_path(token).unwrap_or_else(|| token.to_string());
+    Path::new(&resolved)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(str::to_string)
+}
+
 /// Checks if a command string corresponds to the given agent command.
 ///
 /// Returns true if:
@@ -804,7 +2094,87 @@ pub fn is_agent_command(command_line: &str, agent_command: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{is_agent_command, split_first_token};
+    use super::{
+        Config, DashboardConfig, SessionStrategy, StatusIcons, WindowEnv, is_agent_command,
+        parse_duration, parse_duration_ms, project_from_worktree_dir, split_first_token,
+    };
+    use crate::vcs::Vcs;
+    use std::path::{Path, PathBuf};
+
+    fn with_worktree_dir(dir: Option<&str>) -> Config {
+        Config {
+            worktree_dir: dir.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn worktree_path_defaults_to_sibling_directory() {
+        let root = Path::new("/code/app");
+        assert_eq!(
+            with_worktree_dir(None).worktree_path(root, "fix").unwrap(),
+            PathBuf::from("/code/app__worktrees/fix")
+        );
+        assert_eq!(
+            with_worktree_dir(Some(".worktrees"))
+                .worktree_path(root, "fix")
+                .unwrap(),
+            PathBuf::from("/code/app/.worktrees/fix")
+        );
+    }
+
+    #[test]
+    fn worktree_path_expands_template_variables() {
+        let root = Path::new("/code/app");
+        assert_eq!(
+            with_worktree_dir(Some("/wt/{repo}/{handle}"))
+                .worktree_path(root, "fix")
+                .unwrap(),
+            PathBuf::from("/wt/app/fix")
+        );
+        assert_eq!(
+            with_worktree_dir(Some("/wt/{repo}-trees"))
+                .worktree_path(root, "fix")
+                .unwrap(),
+            PathBuf::from("/wt/app-trees/fix")
+        );
+        assert!(
+            with_worktree_dir(Some("/wt/{handle}/src"))
+                .worktree_path(root, "fix")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn project_from_worktree_dir_reads_repo_component() {
+        let path = Path::new("/wt/app-trees/fix/src");
+        assert_eq!(
+            project_from_worktree_dir("/wt/{repo}-trees/{handle}", path),
+            Some("app".to_string())
+        );
+        assert_eq!(
+            project_from_worktree_dir("/other/{repo}/{handle}", path),
+            None
+        );
+        assert_eq!(project_from_worktree_dir("/wt/{handle}", path), None);
+        assert_eq!(project_from_worktree_dir("{repo}/{handle}", path), None);
+    }
+
+    #[test]
+    fn status_name_maps_default_and_custom_icons() {
+        let icons = StatusIcons::default();
+        assert_eq!(icons.status_name("🤖"), Some("working"));
+        assert_eq!(icons.status_name("💬"), Some("waiting"));
+        assert_eq!(icons.status_name("✅"), Some("done"));
+        assert_eq!(icons.status_name("?"), None);
+
+        let custom = StatusIcons {
+            working: Some("W".to_string()),
+            ..StatusIcons::default()
+        };
+        assert_eq!(custom.status_name("W"), Some("working"));
+        assert_eq!(custom.status_name("🤖"), None);
+    }
 
     #[test]
     fn split_first_token_single_word() {
@@ -878,4 +2248,271 @@ mod tests {
         assert!(!is_agent_command("", "claude"));
         assert!(!is_agent_command("   ", "claude"));
     }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert_eq!(parse_duration("45s").unwrap(), 45);
+        assert_eq!(parse_duration("5m").unwrap(), 300);
+        assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+        assert_eq!(parse_duration("1d").unwrap(), 86400);
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("5").is_ok());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("1h5").is_err());
+    }
+
+    #[test]
+    fn parse_duration_ms_units() {
+        assert_eq!(parse_duration_ms("750").unwrap(), 750);
+        assert_eq!(parse_duration_ms("500ms").unwrap(), 500);
+        assert_eq!(parse_duration_ms("2s").unwrap(), 2000);
+        assert_eq!(parse_duration_ms("1m").unwrap(), 60_000);
+        assert!(parse_duration_ms("fastms").is_err());
+    }
+
+    #[test]
+    fn vcs_is_set_per_project() {
+        let global: Config = serde_yaml::from_str("vcs: git\n").unwrap();
+        let project: Config = serde_yaml::from_str("vcs: jj\n").unwrap();
+        assert_eq!(global.clone().merge(project).vcs, Some(Vcs::Jj));
+        assert_eq!(global.merge(Config::default()).vcs, Some(Vcs::Git));
+        assert!(serde_yaml::from_str::<Config>("vcs: hg\n").is_err());
+    }
+
+    #[test]
+    fn lint_reports_config_mistakes() {
+        let project: Config = serde_yaml::from_str(
+            r#"
+panes:
+  - command: "<agent> --verbose"
+  - command: vim
+post_create:
+  - "<global>"
+  - ""
+pre_merge:
+  - "<global>"
+agents:
+  mycli:
+    prompt: --task
+"#,
+        )
+        .unwrap();
+        let global: Config = serde_yaml::from_str("post_create:\n  - direnv allow\n").unwrap();
+
+        assert_eq!(
+            project.lint(Some(&global)),
+            vec![
+                "panes: Pane 1 must have a 'split' direction specified.".to_string(),
+                "panes[0].command: \"<agent>\" is only substituted when it is the whole \
+                 command; put extra flags in `agent` instead"
+                    .to_string(),
+                "post_create[1]: empty entry".to_string(),
+                "pre_merge[0]: \"<global>\" refers to the global pre_merge, which isn't set"
+                    .to_string(),
+                "agents.mycli.prompt: missing the {prompt} placeholder, so the prompt would \
+                 never be passed (use `paste` to type it in instead)"
+                    .to_string(),
+            ]
+        );
+
+        // The placeholder has nothing to expand to in the global config itself
+        assert_eq!(
+            project.lint(None)[2..4],
+            [
+                "post_create[0]: \"<global>\" only works in a project config".to_string(),
+                "post_create[1]: empty entry".to_string(),
+            ]
+        );
+        assert!(global.lint(None).is_empty());
+    }
+
+    #[test]
+    fn agent_profiles_fall_back_to_builtins() {
+        let config: Config = serde_yaml::from_str(
+            "agents:\n  codex:\n    command: codex --full-auto\n  mycli:\n    status_hooks: true\n",
+        )
+        .unwrap();
+
+        let codex = config.agent_profile_named("codex");
+        assert_eq!(codex.command.as_deref(), Some("codex --full-auto"));
+        assert!(codex.status_hooks());
+        assert!(!codex.pane_title());
+        assert_eq!(config.agent_launch_command("codex"), "codex --full-auto");
+
+        // Agents given as a full command run as written
+        assert_eq!(config.agent_launch_command("codex -m o3"), "codex -m o3");
+
+        let custom = config.agent_profile_named("mycli");
+        assert!(custom.status_hooks());
+        assert!(custom.pane_title());
+        assert_eq!(custom.prompt_args("P").as_deref(), Some("-- P"));
+
+        assert!(config.agent_profile_named("aider").pastes_prompt());
+        assert_eq!(
+            config
+                .agent_profile_named("gemini")
+                .prompt_args("P")
+                .as_deref(),
+            Some("-i P")
+        );
+        assert_eq!(config.agent_launch_command("claude"), "claude");
+    }
+
+    #[test]
+    fn agent_profiles_merge_per_field() {
+        let global: Config =
+            serde_yaml::from_str("agents:\n  aider:\n    command: aider --model sonnet\n").unwrap();
+        let project: Config =
+            serde_yaml::from_str("agents:\n  aider:\n    pane_title: true\n").unwrap();
+        let merged = global.merge(project);
+        let aider = merged.agent_profile_named("aider");
+        assert_eq!(aider.command.as_deref(), Some("aider --model sonnet"));
+        assert!(aider.pane_title());
+        assert!(aider.pastes_prompt());
+    }
+
+    #[test]
+    fn agent_input_options_default_to_paced_pastes() {
+        let config: Config = serde_yaml::from_str(
+            "agents:\n  myrepl:\n    literal_input: true\n    input_delay_ms: 120\n    enter_separately: false\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.agent_profile_named("claude").input_options(),
+            crate::tmux::InputOptions::default()
+        );
+        assert_eq!(
+            config.agent_profile_named("myrepl").input_options(),
+            crate::tmux::InputOptions {
+                literal: true,
+                delay: std::time::Duration::from_millis(120),
+                enter_separately: false,
+            }
+        );
+    }
+
+    #[test]
+    fn dashboard_alerts_accept_durations_and_seconds() {
+        let config: DashboardConfig =
+            serde_yaml::from_str("alerts:\n  waiting: 5m\n  working: 7200\n").unwrap();
+        assert_eq!(config.alerts.for_status("waiting"), Some(300));
+        assert_eq!(config.alerts.for_status("working"), Some(7200));
+        assert_eq!(config.alerts.for_status("done"), None);
+        assert!(serde_yaml::from_str::<DashboardConfig>("alerts:\n  waiting: soon\n").is_err());
+    }
+
+    #[test]
+    fn window_session_follows_strategy() {
+        let root = Path::new("/code/my.app");
+        let config = |strategy, name: Option<&str>| Config {
+            session_strategy: strategy,
+            session_name: name.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(config(None, None).window_session(root), None);
+        assert_eq!(
+            config(Some(SessionStrategy::PerProject), None).window_session(root),
+            Some("my_app".to_string())
+        );
+        assert_eq!(
+            config(Some(SessionStrategy::Named), None).window_session(root),
+            Some("workmux".to_string())
+        );
+        assert_eq!(
+            config(Some(SessionStrategy::Named), Some("agents")).window_session(root),
+            Some("agents".to_string())
+        );
+    }
+
+    #[test]
+    fn window_env_builds_env_command() {
+        let parse = |yaml: &str| serde_yaml::from_str::<WindowEnv>(yaml).unwrap();
+
+        assert_eq!(WindowEnv::default().env_command(), None);
+        assert_eq!(
+            parse("unset: [GITHUB_TOKEN, 'BAD NAME']\nset:\n  MODE: \"it's\"\n").env_command(),
+            Some("env -u GITHUB_TOKEN MODE='it'\\''s'".to_string())
+        );
+        assert_eq!(
+            parse("inherit: [LANG, PATH, TMUX_PANE]\nunset: [TMUX_PANE]\n").env_command(),
+            Some(
+                "env -i ${HOME+\"HOME=$HOME\"} ${PATH+\"PATH=$PATH\"} ${TERM+\"TERM=$TERM\"} \
+                 ${TMUX+\"TMUX=$TMUX\"} ${LANG+\"LANG=$LANG\"}"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn branch_profiles_override_panes_and_hooks_of_matching_branches() {
+        let global: Config = serde_yaml::from_str(
+            "pre_merge: [cargo test]\nprofiles:\n  - match: 'spike/*'\n    pre_merge: []\n",
+        )
+        .unwrap();
+        let project: Config = serde_yaml::from_str(
+            "post_create: [make setup]\nprofiles:\n  - match: 'hotfix/*'\n    base: production\n    \
+             post_create: []\n  - match: '*'\n    prompt_template: kickoff\n",
+        )
+        .unwrap();
+        let config = global.merge(project);
+
+        let hotfix = config.branch_profile("hotfix/login").unwrap();
+        assert_eq!(hotfix.base.as_deref(), Some("production"));
+        // Project profiles come first, so the catch-all shadows the global one
+        assert_eq!(
+            config.branch_profile("spike/idea").unwrap().prompt_template,
+            Some("kickoff".to_string())
+        );
+
+        let hotfix_config = config.clone().for_branch("hotfix/login");
+        assert_eq!(hotfix_config.post_create, Some(vec![]));
+        assert_eq!(
+            hotfix_config.pre_merge,
+            Some(vec!["cargo test".to_string()])
+        );
+        let other = config.for_branch("feature");
+        assert_eq!(other.post_create, Some(vec!["make setup".to_string()]));
+    }
+
+    #[test]
+    fn layouts_merge_by_name_and_replace_panes() {
+        let global: Config = serde_yaml::from_str(
+            "layouts:\n  review:\n    - command: tig\n  solo:\n    - command: <agent>\n",
+        )
+        .unwrap();
+        let project: Config =
+            serde_yaml::from_str("layouts:\n  review:\n    - command: git diff\n").unwrap();
+        let config = global.merge(project);
+        assert_eq!(
+            config.layouts.keys().collect::<Vec<_>>(),
+            ["review", "solo"]
+        );
+
+        let review = config.clone().with_layout(Some("review")).unwrap();
+        let panes = review.panes.unwrap();
+        assert_eq!(panes[0].command.as_deref(), Some("git diff"));
+        assert!(config.clone().with_layout(Some("default")).is_ok());
+        let err = config.with_layout(Some("nope")).unwrap_err().to_string();
+        assert!(err.contains("Available layouts: review, solo"), "{}", err);
+    }
+
+    #[test]
+    fn lint_checks_each_layout() {
+        let config: Config =
+            serde_yaml::from_str("layouts:\n  review:\n    - split: horizontal\n").unwrap();
+        assert_eq!(
+            config.lint(None),
+            ["layouts.review: First pane (index 0) cannot have a 'split' direction."]
+        );
+    }
+
+    #[test]
+    fn lint_reports_invalid_profile_globs() {
+        let config: Config = serde_yaml::from_str("profiles:\n  - match: 'fix/[a'\n").unwrap();
+        let problems = config.lint(None);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("profiles[0].match: invalid glob"));
+    }
 }
@@ -0,0 +1,253 @@
+//! Likely conflicts between parallel worktrees.
+//!
+//! Two branches that change the same files will probably conflict when the
+//! second one is merged. The changed files come from the cached git status
+//! (see [`GitStatus::changed_files`]), so checking is cheap enough for every
+//! dashboard refresh.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::git::GitStatus;
+
+/// Two worktrees whose changes touch the same files
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap {
+    pub first: PathBuf,
+    pub second: PathBuf,
+    /// Files both changed, sorted
+    pub files: Vec<String>,
+}
+
+/// Every pair of worktrees that changed at least one file in common, in the
+/// order the worktrees are given
+pub fn find_overlaps(worktrees: &[(&Path, &GitStatus)]) -> Vec<Overlap> {
+    let mut overlaps = Vec::new();
+    for (i, (first, first_status)) in worktrees.iter().enumerate() {
+        for (second, second_status) in &worktrees[i + 1..] {
+            // changed_files is sorted, so a merge walk finds the common ones
+            let files = common_files(&first_status.changed_files, &second_status.changed_files);
+            if !files.is_empty() {
+                overlaps.push(Overlap {
+                    first: first.to_path_buf(),
+                    second: second.to_path_buf(),
+                    files,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// For each worktree in an overlap, the other worktrees it overlaps with
+pub fn by_worktree(overlaps: &[Overlap]) -> HashMap<PathBuf, Vec<PathBuf>> {
+    let mut map: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for overlap in overlaps {
+        map.entry(overlap.first.clone())
+            .or_default()
+            .push(overlap.second.clone());
+        map.entry(overlap.second.clone())
+            .or_default()
+            .push(overlap.first.clone());
+    }
+    map
+}
+
+/// Overlaps among worktrees of the same repository, with worktrees grouped
+/// by `repo_of`
+pub fn find_overlaps_by_repo<K: Ord>(
+    worktrees: &[(&Path, &GitStatus)],
+    repo_of: impl Fn(&Path) -> K,
+) -> Vec<Overlap> {
+    let mut repos: BTreeMap<K, Vec<(&Path, &GitStatus)>> = BTreeMap::new();
+    for &(path, status) in worktrees {
+        repos.entry(repo_of(path)).or_default().push((path, status));
+    }
+    repos
+        .values()
+        .flat_map(|worktrees| find_overlaps(worktrees))
+        .collect()
+}
+
+/// A branch to place in a merge plan
+#[derive(Debug, Clone, Default)]
+pub struct MergeCandidate {
+    pub branch: String,
+    /// The branch it was created from, when that is another candidate
+    /// (stacked branches)
+    pub parent: Option<String>,
+    /// Changed files, sorted (see [`GitStatus::changed_files`])
+    pub files: Vec<String>,
+    /// Already conflicts with the branch it merges into
+    pub conflicts_with_target: bool,
+}
+
+/// Order in which to merge `candidates`, as indices into it.
+///
+/// Parents come before the branches stacked on them, and branches that
+/// already conflict with their target come last. Otherwise the next branch is
+/// the one sharing the fewest files with the branches still to merge: a
+/// branch that overlaps several others is merged after them, so it's the only
+/// one that needs a rebase.
+pub fn merge_order(candidates: &[MergeCandidate]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+    let mut order = Vec::with_capacity(candidates.len());
+    while !remaining.is_empty() {
+        let waits_for_parent = |i: usize| {
+            candidates[i].parent.as_ref().is_some_and(|parent| {
+                remaining
+                    .iter()
+                    .any(|&j| j != i && candidates[j].branch == *parent)
+            })
+        };
+        let mut ready: Vec<usize> = remaining
+            .iter()
+            .copied()
+            .filter(|&i| !waits_for_parent(i))
+            .collect();
+        // Recorded bases can't form a cycle through git alone, but don't loop
+        // forever if they somehow do
+        if ready.is_empty() {
+            ready = remaining.clone();
+        }
+        let shared = |i: usize| -> usize {
+            remaining
+                .iter()
+                .filter(|&&j| j != i)
+                .map(|&j| common_files(&candidates[i].files, &candidates[j].files).len())
+                .sum()
+        };
+        let next = ready
+            .into_iter()
+            .min_by_key(|&i| {
+                (
+                    candidates[i].conflicts_with_target,
+                    shared(i),
+                    candidates[i].files.len(),
+                    &candidates[i].branch,
+                )
+            })
+            .expect("remaining is not empty");
+        remaining.retain(|&i| i != next);
+        order.push(next);
+    }
+    order
+}
+
+/// Files two sorted file lists have in common
+pub fn common_files(a: &[String], b: &[String]) -> Vec<String> {
+    let (mut i, mut j) = (0, 0);
+    let mut common = Vec::new();
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common.push(a[i].clone());
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    common
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(files: &[&str]) -> GitStatus {
+        GitStatus {
+            changed_files: files.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn overlaps_pair_worktrees_sharing_files() {
+        let auth = status(&["README.md", "src/auth.rs", "src/session.rs"]);
+        let session = status(&["src/session.rs", "tests/session.rs"]);
+        let docs = status(&["docs/index.md"]);
+        let worktrees = [
+            (Path::new("/wt/auth"), &auth),
+            (Path::new("/wt/session"), &session),
+            (Path::new("/wt/docs"), &docs),
+        ];
+
+        let overlaps = find_overlaps(&worktrees);
+        assert_eq!(
+            overlaps,
+            vec![Overlap {
+                first: PathBuf::from("/wt/auth"),
+                second: PathBuf::from("/wt/session"),
+                files: vec!["src/session.rs".to_string()],
+            }]
+        );
+        let map = by_worktree(&overlaps);
+        assert_eq!(
+            map[Path::new("/wt/session")],
+            vec![PathBuf::from("/wt/auth")]
+        );
+        assert!(!map.contains_key(Path::new("/wt/docs")));
+    }
+
+    #[test]
+    fn overlaps_ignore_worktrees_of_other_repositories() {
+        let a = status(&["src/main.rs"]);
+        let b = status(&["src/main.rs"]);
+        let worktrees = [
+            (Path::new("/api__worktrees/a"), &a),
+            (Path::new("/web__worktrees/b"), &b),
+        ];
+        let repo = |path: &Path| path.parent().map(Path::to_path_buf);
+
+        assert!(find_overlaps_by_repo(&worktrees, repo).is_empty());
+        assert_eq!(find_overlaps(&worktrees).len(), 1);
+    }
+
+    fn candidate(branch: &str, files: &[&str]) -> MergeCandidate {
+        MergeCandidate {
+            branch: branch.to_string(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn ordered(candidates: &[MergeCandidate]) -> Vec<&str> {
+        merge_order(candidates)
+            .into_iter()
+            .map(|i| candidates[i].branch.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn merge_order_puts_widely_overlapping_branch_last() {
+        let candidates = [
+            candidate("refactor", &["src/a.rs", "src/b.rs"]),
+            candidate("fix-a", &["src/a.rs"]),
+            candidate("fix-b", &["src/b.rs"]),
+            candidate("docs", &["README.md"]),
+        ];
+        assert_eq!(
+            ordered(&candidates),
+            vec!["docs", "fix-a", "fix-b", "refactor"]
+        );
+    }
+
+    #[test]
+    fn merge_order_respects_stacks_and_known_conflicts() {
+        let mut child = candidate("api-client", &["src/client.rs"]);
+        child.parent = Some("api".to_string());
+        let mut conflicting = candidate("old-branch", &[]);
+        conflicting.conflicts_with_target = true;
+        let candidates = [
+            conflicting,
+            child,
+            candidate("api", &["src/api.rs", "src/lib.rs", "src/server.rs"]),
+        ];
+        assert_eq!(
+            ordered(&candidates),
+            vec!["api", "api-client", "old-branch"]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Shared state snapshot written by `workmux daemon`.
+//!
+//! The daemon periodically collects agent panes and their git status and
+//! writes them to a JSON file. Readers (dashboard, `list`, `status`) use the
+//! snapshot while it is fresh and fall back to querying tmux/git directly.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::git::GitStatus;
+use crate::state::{self, StateFile};
+use crate::tmux::{self, AgentPane};
+
+const STATE_FILE: StateFile = StateFile {
+    name: "daemon_state.json",
+    migrations: &[state::adopt_unversioned],
+};
+
+/// Snapshots older than this many refresh intervals are ignored
+const STALE_INTERVALS: u64 = 3;
+
+/// Lower bound for the staleness window, so short intervals tolerate hiccups
+const MIN_STALE_SECS: u64 = 10;
+
+/// State persisted by the daemon on every refresh
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonState {
+    /// Process ID of the daemon that wrote this snapshot
+    pub pid: u32,
+    /// Refresh interval of the daemon (seconds)
+    pub interval_secs: u64,
+    /// Timestamp of the last refresh (UNIX seconds)
+    pub updated_at: u64,
+    /// Agent panes across all tmux sessions
+    pub agents: Vec<AgentPane>,
+    /// Git status for each agent worktree path
+    #[serde(default)]
+    pub git_statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl DaemonState {
+    /// Whether the snapshot is recent enough to be trusted at `now`
+    pub fn is_fresh(&self, now: u64) -> bool {
+        let max_age = (self.interval_secs * STALE_INTERVALS).max(MIN_STALE_SECS);
+        now.saturating_sub(self.updated_at) <= max_age
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Get the path to the daemon state file
+pub fn get_state_path() -> Result<PathBuf> {
+    STATE_FILE.path()
+}
+
+/// Load the daemon state regardless of its age
+pub fn load_state() -> Option<DaemonState> {
+    STATE_FILE.load()
+}
+
+/// Load the daemon state if a daemon has refreshed it recently
+pub fn load_fresh_state() -> Option<DaemonState> {
+    load_state().filter(|state| state.is_fresh(now_secs()))
+}
+
+/// Write the daemon state atomically
+pub fn save_state(state: &DaemonState) -> Result<()> {
+    STATE_FILE.save(state)
+}
+
+/// Get all agent panes, preferring a fresh daemon snapshot over querying tmux
+pub fn agent_panes() -> Vec<AgentPane> {
+    match load_fresh_state() {
+        Some(state) => state.agents,
+        None => tmux::get_all_agent_panes().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(interval_secs: u64, updated_at: u64) -> DaemonState {
+        DaemonState {
+            interval_secs,
+            updated_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn is_fresh_within_interval_window() {
+        let s = state(5, 1000);
+        assert!(s.is_fresh(1000));
+        assert!(s.is_fresh(1015));
+        assert!(!s.is_fresh(1016));
+    }
+
+    #[test]
+    fn is_fresh_uses_minimum_window_for_short_intervals() {
+        let s = state(1, 1000);
+        assert!(s.is_fresh(1010));
+        assert!(!s.is_fresh(1011));
+    }
+
+    #[test]
+    fn is_fresh_tolerates_clock_going_backwards() {
+        assert!(state(2, 1000).is_fresh(900));
+    }
+
+    #[test]
+    fn state_round_trips_through_json() {
+        let mut s = state(2, 1000);
+        s.agents.push(AgentPane {
+            session: "main".to_string(),
+            window_name: "wm-feature".to_string(),
+            pane_id: "%3".to_string(),
+            path: PathBuf::from("/tmp/repo__worktrees/feature"),
+            pane_title: None,
+            status: Some("🤖".to_string()),
+            status_ts: Some(990),
+            group: None,
+            agent: None,
+            merge_queued: false,
+            exited: false,
+            snoozed_until: None,
+            usage: Default::default(),
+        });
+        let json = serde_json::to_string(&s).unwrap();
+        let parsed: DaemonState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.agents.len(), 1);
+        assert_eq!(parsed.agents[0].pane_id, "%3");
+        assert_eq!(parsed.agents[0].status_ts, Some(990));
+        assert!(parsed.git_statuses.is_empty());
+    }
+}
@@ -0,0 +1,22 @@
+//! Formatting of elapsed times, shared by the dashboard, `status`, `list` and
+//! notifications.
+
+/// Format a duration in seconds as HH:MM:SS.
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let mins = (secs % 3600) / 60;
+    let secs = secs % 60;
+    format!("{:02}:{:02}:{:02}", hours, mins, secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "00:00:00");
+        assert_eq!(format_duration(61), "00:01:01");
+        assert_eq!(format_duration(3661), "01:01:01");
+    }
+}
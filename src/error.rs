@@ -0,0 +1,148 @@
+//! Failure kinds that scripts can tell apart by exit code, instead of
+//! matching on error text.
+//!
+//! Errors stay `anyhow::Error`. The places that detect one of these failures
+//! create it with [`ErrorKind::msg`], and any context added on the way up
+//! keeps it findable by [`exit_code`].
+
+use std::fmt;
+
+use crate::git::WorktreeNotFound;
+
+/// Exit code of errors without a kind
+pub const GENERAL_FAILURE: u8 = 1;
+
+/// Exit code of invalid arguments. clap uses 2, which means conflicts here.
+pub const USAGE: u8 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The merge or rebase would conflict, or did
+    Conflicts,
+    /// A worktree has uncommitted changes in the way
+    Dirty,
+    /// The worktree, branch, remote, or ref doesn't exist
+    NotFound,
+    /// The user declined a confirmation prompt
+    Aborted,
+    /// A worktree, branch, or window with that name already exists
+    AlreadyExists,
+    /// The command needs a tmux server and none is running
+    TmuxNotRunning,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Conflicts => 2,
+            ErrorKind::Dirty => 3,
+            ErrorKind::NotFound => 4,
+            ErrorKind::Aborted => 5,
+            ErrorKind::AlreadyExists => 6,
+            ErrorKind::TmuxNotRunning => 7,
+        }
+    }
+
+    /// An error of this kind
+    pub fn msg(self, message: impl fmt::Display) -> anyhow::Error {
+        KindError {
+            kind: self,
+            message: message.to_string(),
+            source: None,
+        }
+        .into()
+    }
+
+    /// `err` with `message` on top, as an error of this kind. Unlike
+    /// `.context()`, the kind stays findable under further context.
+    pub fn wrap(self, err: anyhow::Error, message: impl fmt::Display) -> anyhow::Error {
+        KindError {
+            kind: self,
+            message: message.to_string(),
+            source: Some(err),
+        }
+        .into()
+    }
+}
+
+#[derive(Debug)]
+struct KindError {
+    kind: ErrorKind,
+    message: String,
+    source: Option<anyhow::Error>,
+}
+
+impl fmt::Display for KindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for KindError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|e| e.as_ref() as _)
+    }
+}
+
+/// The kind of the first error in the chain that has one
+pub fn kind(err: &anyhow::Error) -> Option<ErrorKind> {
+    err.chain().find_map(|cause| {
+        if let Some(e) = cause.downcast_ref::<KindError>() {
+            Some(e.kind)
+        } else if cause.is::<WorktreeNotFound>() {
+            Some(ErrorKind::NotFound)
+        } else {
+            None
+        }
+    })
+}
+
+/// The exit code for an error: its kind's, or [`GENERAL_FAILURE`]
+pub fn exit_code(err: &anyhow::Error) -> u8 {
+    kind(err).map_or(GENERAL_FAILURE, ErrorKind::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn exit_code_survives_context() {
+        let err = Err::<(), _>(ErrorKind::Conflicts.msg("would conflict in a.rs"))
+            .context("Failed to merge worktree")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), 2);
+        assert_eq!(
+            format!("{:#}", err),
+            "Failed to merge worktree: would conflict in a.rs"
+        );
+
+        let err = anyhow::Error::new(WorktreeNotFound("x".into())).context("No worktree found");
+        assert_eq!(exit_code(&err), 4);
+
+        let err = ErrorKind::Conflicts
+            .wrap(anyhow::anyhow!("git rebase failed"), "Rebase failed")
+            .context("Failed to merge worktree");
+        assert_eq!(exit_code(&err), 2);
+        assert_eq!(err.chain().count(), 3);
+        assert_eq!(exit_code(&anyhow::anyhow!("boom")), GENERAL_FAILURE);
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let kinds = [
+            ErrorKind::Conflicts,
+            ErrorKind::Dirty,
+            ErrorKind::NotFound,
+            ErrorKind::Aborted,
+            ErrorKind::AlreadyExists,
+            ErrorKind::TmuxNotRunning,
+        ];
+        let mut codes: Vec<u8> = kinds.iter().map(|k| k.exit_code()).collect();
+        codes.extend([GENERAL_FAILURE, USAGE]);
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), kinds.len() + 2);
+    }
+}
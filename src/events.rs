@@ -0,0 +1,164 @@
+//! Log of recent operations that `workmux undo` can reverse.
+//!
+//! `remove`, `merge` and `close` append an event to
+//! `<git common dir>/workmux/events.json` with what it takes to reverse them:
+//! the commit a deleted branch pointed at, where the merge target was before
+//! and after, and the worktree's metadata. Only the last [`MAX_EVENTS`] are
+//! kept, and `undo` drops an event once it has been reversed.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::debug;
+
+use crate::git;
+use crate::metadata::WorktreeMetadata;
+use crate::state::StateFile;
+
+const EVENTS_FILE: StateFile = StateFile {
+    name: "events",
+    migrations: &[],
+};
+
+/// Events kept in the log, oldest dropped first
+const MAX_EVENTS: usize = 20;
+
+/// An operation on a worktree, as recorded for `workmux undo`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Unix timestamp of the operation
+    pub at: u64,
+    pub handle: String,
+    #[serde(flatten)]
+    pub op: Operation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// The worktree was removed, and its branch too unless `commit` is None
+    Remove {
+        branch: String,
+        /// Commit the deleted branch pointed at
+        commit: Option<String>,
+        metadata: Option<WorktreeMetadata>,
+    },
+    /// The branch was merged (or its commits picked) into `target`, moving it
+    /// from `before` to `after`
+    Merge {
+        branch: String,
+        target: String,
+        before: String,
+        after: String,
+        /// Commit the branch pointed at, when cleanup deleted it
+        commit: Option<String>,
+        metadata: Option<WorktreeMetadata>,
+    },
+    /// The worktree's window was closed
+    Close,
+}
+
+impl Event {
+    pub fn new(handle: &str, op: Operation) -> Self {
+        Self {
+            at: crate::daemon::now_secs(),
+            handle: handle.to_string(),
+            op,
+        }
+    }
+
+    /// "remove 'fix-auth'", for confirmations and messages
+    pub fn describe(&self) -> String {
+        match &self.op {
+            Operation::Remove { .. } => format!("remove '{}'", self.handle),
+            Operation::Merge { branch, target, .. } => {
+                format!("merge of '{}' into '{}'", branch, target)
+            }
+            Operation::Close => format!("close '{}'", self.handle),
+        }
+    }
+}
+
+fn events_path() -> Result<PathBuf> {
+    Ok(git::get_git_common_dir()?
+        .join("workmux")
+        .join("events.json"))
+}
+
+/// Recorded events of the repository, oldest first
+pub fn load() -> Vec<Event> {
+    events_path()
+        .ok()
+        .and_then(|path| EVENTS_FILE.load_from(&path))
+        .unwrap_or_default()
+}
+
+fn save(events: &[Event]) -> Result<()> {
+    let path = events_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    EVENTS_FILE.save_to(&path, &events)
+}
+
+/// Append an event, dropping the oldest past [`MAX_EVENTS`]. Failures are
+/// only logged: the operation itself already happened.
+pub fn record(event: Event) {
+    let mut events = load();
+    push(&mut events, event);
+    if let Err(e) = save(&events) {
+        debug!(error = %e, "events:failed to record");
+    }
+}
+
+fn push(events: &mut Vec<Event>, event: Event) {
+    events.push(event);
+    let excess = events.len().saturating_sub(MAX_EVENTS);
+    events.drain(..excess);
+}
+
+/// Forget the most recent event, once it has been undone
+pub fn drop_last() -> Result<()> {
+    let mut events = load();
+    events.pop();
+    save(&events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_keeps_the_latest_events() {
+        let mut events = Vec::new();
+        for i in 0..MAX_EVENTS + 3 {
+            push(
+                &mut events,
+                Event::new(&format!("wt-{}", i), Operation::Close),
+            );
+        }
+        assert_eq!(events.len(), MAX_EVENTS);
+        assert_eq!(events[0].handle, "wt-3");
+        assert_eq!(
+            events.last().unwrap().handle,
+            format!("wt-{}", MAX_EVENTS + 2)
+        );
+    }
+
+    #[test]
+    fn event_round_trips_with_op_tag() {
+        let event = Event::new(
+            "fix",
+            Operation::Remove {
+                branch: "fix".to_string(),
+                commit: Some("abc".to_string()),
+                metadata: None,
+            },
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["op"], "remove");
+        assert_eq!(json["branch"], "fix");
+        let parsed: Event = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+    }
+}
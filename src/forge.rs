@@ -0,0 +1,170 @@
+//! Code forge detection.
+//!
+//! PR lookups go through [`Forge`], which picks GitHub (`gh`) or GitLab
+//! (`glab`) based on the host of the origin remote.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use tracing::debug;
+
+use crate::config::MergeStrategy;
+use crate::github::{IssueDetails, PrDetails, PrStatus, PrSummary};
+use crate::{git, github, gitlab};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    /// Detect the forge from the origin remote, defaulting to GitHub
+    pub fn detect() -> Self {
+        let forge = git::get_remote_url("origin")
+            .map(|url| Self::from_remote_url(&url))
+            .unwrap_or(Forge::GitHub);
+        debug!(?forge, "forge:detected");
+        forge
+    }
+
+    /// GitLab for gitlab.com and self-hosted hosts with "gitlab" in the name
+    fn from_remote_url(url: &str) -> Self {
+        match remote_host(url) {
+            Some(host) if host.to_lowercase().contains("gitlab") => Forge::GitLab,
+            _ => Forge::GitHub,
+        }
+    }
+
+    /// How the forge refers to a PR, e.g. "PR #12" or "MR !12"
+    pub fn pr_label(self, number: u32) -> String {
+        match self {
+            Forge::GitHub => format!("PR #{}", number),
+            Forge::GitLab => format!("MR !{}", number),
+        }
+    }
+
+    pub fn get_pr_details(self, number: u32) -> Result<PrDetails> {
+        match self {
+            Forge::GitHub => github::get_pr_details(number),
+            Forge::GitLab => gitlab::get_mr_details(number),
+        }
+    }
+
+    pub fn get_issue(self, number: u32) -> Result<IssueDetails> {
+        match self {
+            Forge::GitHub => github::get_issue(number),
+            Forge::GitLab => gitlab::get_issue(number),
+        }
+    }
+
+    /// Find a PR by head branch. `owner` is only used on GitHub.
+    pub fn find_pr_by_head_ref(self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        match self {
+            Forge::GitHub => github::find_pr_by_head_ref(owner, branch),
+            Forge::GitLab => gitlab::find_mr_by_source_branch(branch),
+        }
+    }
+
+    /// All PRs for the current repository, keyed by head branch
+    pub fn list_prs(self) -> Result<HashMap<String, PrSummary>> {
+        match self {
+            Forge::GitHub => github::list_prs(),
+            Forge::GitLab => gitlab::list_mrs(),
+        }
+    }
+
+    /// Review, CI and merge state of all PRs, keyed by head branch
+    pub fn list_pr_statuses(self) -> Result<HashMap<String, PrStatus>> {
+        match self {
+            Forge::GitHub => github::list_pr_statuses(),
+            Forge::GitLab => gitlab::list_mr_statuses(),
+        }
+    }
+
+    /// Merge a PR on the forge, which enforces required checks and reviews
+    pub fn merge_pr(self, number: u32, strategy: MergeStrategy) -> Result<()> {
+        match self {
+            Forge::GitHub => github::merge_pr(number, strategy),
+            Forge::GitLab => gitlab::merge_mr(number, strategy),
+        }
+    }
+
+    pub fn open_pr_in_browser(self, number: u32) -> Result<()> {
+        match self {
+            Forge::GitHub => github::view_pr_in_browser(number),
+            Forge::GitLab => gitlab::view_mr_in_browser(number),
+        }
+    }
+
+    /// Create a PR, returning its number and URL
+    pub fn create_pr(
+        self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<(u32, String)> {
+        match self {
+            Forge::GitHub => github::create_pr(head, base, title, body, draft),
+            Forge::GitLab => gitlab::create_mr(head, base, title, body, draft),
+        }
+    }
+}
+
+/// Host of an HTTPS, `ssh://` or scp-style (`git@host:path`) remote URL
+fn remote_host(url: &str) -> Option<&str> {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        // scp-style needs a colon before any slash, otherwise it's a local path
+        None => {
+            let colon = url.find(':')?;
+            if url.find('/').is_some_and(|slash| slash < colon) {
+                return None;
+            }
+            url
+        }
+    };
+    let authority = rest.split(['/', ':']).next()?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_remote_url_detects_gitlab_hosts() {
+        assert_eq!(
+            Forge::from_remote_url("git@gitlab.com:group/repo.git"),
+            Forge::GitLab
+        );
+        assert_eq!(
+            Forge::from_remote_url("https://gitlab.example.com/group/sub/repo.git"),
+            Forge::GitLab
+        );
+        assert_eq!(
+            Forge::from_remote_url("ssh://git@gitlab.internal:2222/team/repo.git"),
+            Forge::GitLab
+        );
+    }
+
+    #[test]
+    fn from_remote_url_defaults_to_github() {
+        assert_eq!(
+            Forge::from_remote_url("git@github.com:owner/repo.git"),
+            Forge::GitHub
+        );
+        assert_eq!(
+            Forge::from_remote_url("https://git.example.com/owner/repo.git"),
+            Forge::GitHub
+        );
+        assert_eq!(
+            Forge::from_remote_url("/local/path/repo.git"),
+            Forge::GitHub
+        );
+    }
+}
@@ -2,12 +2,13 @@ use anyhow::{Context, Result, anyhow};
 use git_url_parse::GitUrl;
 use git_url_parse::types::provider::GenericProvider;
 use std::collections::{HashMap, HashSet};
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, info};
 
-use crate::cmd::Cmd;
+use crate::cmd::{self, Cmd};
+use crate::state::{self, StateFile};
 
 #[derive(Debug, Clone)]
 pub struct RemoteBranchSpec {
@@ -47,39 +48,148 @@ pub struct GitStatus {
     /// Lines removed in uncommitted changes only (working tree)
     #[serde(default)]
     pub uncommitted_removed: usize,
+    /// Lines added since the merge base with the base branch, committed or not
+    #[serde(default)]
+    pub total_added: usize,
+    /// Lines removed since the merge base with the base branch, committed or not
+    #[serde(default)]
+    pub total_removed: usize,
     /// Timestamp when this status was cached (UNIX seconds)
     #[serde(default)]
     pub cached_at: Option<u64>,
     /// The base branch used for comparison (e.g., "main")
     #[serde(default)]
     pub base_branch: String,
+    /// Pull request recorded by `pr create`
+    #[serde(default)]
+    pub pr_number: Option<u32>,
+    /// Files changed since the branch left its base, committed or not
+    #[serde(default)]
+    pub changed_files: Vec<String>,
 }
 
-/// Get the path to the git status cache file
-pub fn get_cache_path() -> Result<PathBuf> {
-    let home = home::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
-    let cache_dir = home.join(".cache").join("workmux");
-    std::fs::create_dir_all(&cache_dir)?;
-    Ok(cache_dir.join("git_status_cache.json"))
-}
+const STATUS_CACHE_FILE: StateFile = StateFile {
+    name: "git_status_cache.json",
+    migrations: &[state::adopt_unversioned],
+};
+
+/// Maximum number of worktrees kept in the git status cache
+const STATUS_CACHE_MAX_ENTRIES: usize = 256;
 
 /// Load the git status cache from disk
 pub fn load_status_cache() -> HashMap<PathBuf, GitStatus> {
-    if let Ok(path) = get_cache_path()
-        && path.exists()
-        && let Ok(content) = std::fs::read_to_string(&path)
-    {
-        return serde_json::from_str(&content).unwrap_or_default();
-    }
-    HashMap::new()
+    STATUS_CACHE_FILE.load().unwrap_or_default()
 }
 
-/// Save the git status cache to disk
+/// Save the git status cache to disk.
+///
+/// The file is replaced atomically, so a killed process never leaves a
+/// truncated cache behind. Statuses are merged into what is already on disk,
+/// so dashboards and the daemon running side by side keep each other's
+/// entries, with the most recently fetched status winning.
 pub fn save_status_cache(statuses: &HashMap<PathBuf, GitStatus>) {
-    if let Ok(path) = get_cache_path()
-        && let Ok(content) = serde_json::to_string(statuses)
-    {
-        let _ = std::fs::write(path, content);
+    let mut cache = load_status_cache();
+    merge_status_cache(&mut cache, statuses, Path::exists);
+    if let Err(e) = STATUS_CACHE_FILE.save(&cache) {
+        debug!(error = %e, "git:failed to save status cache");
+    }
+}
+
+/// How long one-shot commands (`list`, `status`) trust a cached status
+const STATUS_CACHE_TTL_SECS: u64 = 30;
+
+/// Most git statuses fetched at once by [`cached_git_statuses`]
+const GIT_STATUS_WORKERS: usize = 8;
+
+/// Git status for each of `paths`, served from the cache kept warm by the
+/// dashboard and daemon. Entries that are missing or older than the TTL are
+/// fetched a few at a time in parallel and written back, so the next caller
+/// gets them for free.
+pub fn cached_git_statuses(paths: &[PathBuf]) -> HashMap<PathBuf, GitStatus> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut cache = load_status_cache();
+    let outdated: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| is_status_outdated(cache.get(*path), now))
+        .collect();
+
+    if !outdated.is_empty() {
+        let workers = std::thread::available_parallelism()
+            .map_or(4, |n| n.get())
+            .clamp(1, GIT_STATUS_WORKERS)
+            .min(outdated.len());
+        // Workers take worktrees off a shared queue, so a repository with
+        // many worktrees doesn't run a git status for all of them at once
+        let queue = std::sync::Mutex::new(outdated.into_iter());
+        let fresh: HashMap<PathBuf, GitStatus> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut fetched = Vec::new();
+                        loop {
+                            let next = queue.lock().map(|mut q| q.next()).unwrap_or(None);
+                            let Some(path) = next else {
+                                break;
+                            };
+                            fetched.push((path.clone(), get_git_status(path)));
+                        }
+                        fetched
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .filter_map(|h| h.join().ok())
+                .flatten()
+                .collect()
+        });
+        debug!(fetched = fresh.len(), "git:refreshed cached statuses");
+        save_status_cache(&fresh);
+        cache.extend(fresh);
+    }
+
+    paths
+        .iter()
+        .filter_map(|path| Some((path.clone(), cache.remove(path)?)))
+        .collect()
+}
+
+fn is_status_outdated(status: Option<&GitStatus>, now: u64) -> bool {
+    status
+        .and_then(|s| s.cached_at)
+        .is_none_or(|cached_at| now.saturating_sub(cached_at) > STATUS_CACHE_TTL_SECS)
+}
+
+/// Merge `statuses` into `cache`, dropping worktrees that no longer exist and
+/// evicting the least recently fetched entries beyond the size limit
+fn merge_status_cache(
+    cache: &mut HashMap<PathBuf, GitStatus>,
+    statuses: &HashMap<PathBuf, GitStatus>,
+    exists: impl Fn(&Path) -> bool,
+) {
+    for (path, status) in statuses {
+        let is_newer = cache
+            .get(path)
+            .is_none_or(|cached| status.cached_at >= cached.cached_at);
+        if is_newer {
+            cache.insert(path.clone(), status.clone());
+        }
+    }
+    cache.retain(|path, _| exists(path));
+
+    if cache.len() > STATUS_CACHE_MAX_ENTRIES {
+        let mut by_age: Vec<(PathBuf, Option<u64>)> = cache
+            .iter()
+            .map(|(path, status)| (path.clone(), status.cached_at))
+            .collect();
+        by_age.sort_by_key(|(_, cached_at)| *cached_at);
+        let excess = cache.len() - STATUS_CACHE_MAX_ENTRIES;
+        for (path, _) in by_age.into_iter().take(excess) {
+            cache.remove(&path);
+        }
     }
 }
 
@@ -102,14 +212,38 @@ pub fn get_repo_root() -> Result<PathBuf> {
     let path = Cmd::new("git")
         .args(&["rev-parse", "--show-toplevel"])
         .run_and_capture_stdout()?;
-    Ok(PathBuf::from(path))
+    Ok(cmd::native_path(&path))
+}
+
+/// Get the git directory shared by all worktrees of the repository
+pub fn get_git_common_dir() -> Result<PathBuf> {
+    get_git_common_dir_in(None)
+}
+
+/// Get the git common directory for a repository at a specific path
+pub fn get_git_common_dir_in(workdir: Option<&Path>) -> Result<PathBuf> {
+    let cmd = Cmd::new("git").args(&["rev-parse", "--path-format=absolute", "--git-common-dir"]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    Ok(cmd::native_path(&cmd.run_and_capture_stdout()?))
 }
 
 /// Get the main worktree root directory (not a linked worktree)
 pub fn get_main_worktree_root() -> Result<PathBuf> {
+    get_main_worktree_root_in(None)
+}
+
+/// Get the main worktree root directory for a repository at a specific path
+pub fn get_main_worktree_root_in(workdir: Option<&Path>) -> Result<PathBuf> {
     // Get all worktrees
-    let list_str = Cmd::new("git")
-        .args(&["worktree", "list", "--porcelain"])
+    let cmd = Cmd::new("git").args(&["worktree", "list", "--porcelain"]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    let list_str = cmd
         .run_and_capture_stdout()
         .context("Failed to list worktrees while locating main worktree")?;
 
@@ -388,6 +522,7 @@ pub fn create_worktree(
     create_branch: bool,
     base_branch: Option<&str>,
     track_upstream: bool,
+    sparse_paths: &[String],
 ) -> Result<()> {
     let path_str = worktree_path
         .to_str()
@@ -395,6 +530,12 @@ pub fn create_worktree(
 
     let mut cmd = Cmd::new("git").arg("worktree").arg("add");
 
+    // Defer checkout until the sparse patterns are in place, so the
+    // full tree is never written to disk
+    if !sparse_paths.is_empty() {
+        cmd = cmd.arg("--no-checkout");
+    }
+
     if create_branch {
         cmd = cmd.arg("-b").arg(branch_name).arg(path_str);
         if let Some(base) = base_branch {
@@ -406,6 +547,10 @@ pub fn create_worktree(
 
     cmd.run().context("Failed to create worktree")?;
 
+    if !sparse_paths.is_empty() {
+        sparse_checkout(worktree_path, sparse_paths)?;
+    }
+
     // When creating a new branch from a remote tracking branch (e.g., origin/main),
     // git automatically sets up tracking for the new branch. This is desirable when
     // opening a remote branch locally, but we unset the upstream when the new branch
@@ -417,6 +562,57 @@ pub fn create_worktree(
     Ok(())
 }
 
+/// Create a worktree with a detached HEAD at `commit`, without a branch
+pub fn create_detached_worktree(worktree_path: &Path, commit: &str) -> Result<()> {
+    let path_str = worktree_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid worktree path"))?;
+
+    Cmd::new("git")
+        .args(&["worktree", "add", "--detach", path_str, commit])
+        .run()
+        .context("Failed to create worktree")?;
+    Ok(())
+}
+
+/// Enable `push.autoSetupRemote` in the repository's local config, so the first
+/// plain `git push` of a new branch sets its upstream. A value the user already
+/// configured at any level is left alone. Returns true if the setting was written.
+pub fn ensure_push_auto_setup_remote() -> Result<bool> {
+    let current = Cmd::new("git")
+        .args(&["config", "--get", "push.autoSetupRemote"])
+        .run_and_capture_stdout()
+        .ok();
+    if current.is_some_and(|value| !value.is_empty()) {
+        return Ok(false);
+    }
+    Cmd::new("git")
+        .args(&["config", "--local", "push.autoSetupRemote", "true"])
+        .run()
+        .context("Failed to set push.autoSetupRemote")?;
+    Ok(true)
+}
+
+/// Restrict a freshly created (unchecked-out) worktree to the given
+/// directories using cone-mode sparse checkout, then populate it.
+fn sparse_checkout(worktree_path: &Path, paths: &[String]) -> Result<()> {
+    let path_args: Vec<&str> = paths.iter().map(String::as_str).collect();
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["sparse-checkout", "set", "--cone"])
+        .args(&path_args)
+        .run()
+        .context("Failed to configure sparse checkout")?;
+
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .arg("checkout")
+        .run()
+        .context("Failed to check out sparse worktree")?;
+
+    Ok(())
+}
+
 /// Unset the upstream tracking for a branch
 pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     if !branch_has_upstream(branch_name)? {
@@ -430,6 +626,39 @@ pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remote and remote branch name that a local branch tracks, e.g.
+/// `("origin", "fix-auth")`. None for branches tracking nothing or another
+/// local branch.
+pub fn branch_upstream(branch_name: &str) -> Option<(String, String)> {
+    let get = |key: &str| {
+        Cmd::new("git")
+            .args(&[
+                "config",
+                "--get",
+                &format!("branch.{}.{}", branch_name, key),
+            ])
+            .run_and_capture_stdout()
+            .ok()
+            .filter(|value| !value.is_empty())
+    };
+    let remote = get("remote").filter(|remote| remote != ".")?;
+    let merge = get("merge")?;
+    let branch = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+    Some((remote, branch.to_string()))
+}
+
+/// Delete a branch on a remote. Returns false if it was already gone.
+pub fn delete_remote_branch(remote: &str, branch: &str) -> Result<bool> {
+    match Cmd::new("git")
+        .args(&["push", remote, "--delete", branch])
+        .run()
+    {
+        Ok(_) => Ok(true),
+        Err(e) if format!("{:#}", e).contains("remote ref does not exist") => Ok(false),
+        Err(e) => Err(e.context(format!("Failed to delete '{}' on '{}'", branch, remote))),
+    }
+}
+
 fn branch_has_upstream(branch_name: &str) -> Result<bool> {
     // Check for the existence of tracking config for this branch.
     // We check both 'merge' and 'remote' to catch edge cases where one might be set without the other.
@@ -460,6 +689,28 @@ pub fn prune_worktrees() -> Result<()> {
     Ok(())
 }
 
+/// Move a linked worktree to a new location
+pub fn move_worktree(from: &Path, to: &Path) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "worktree",
+            "move",
+            &from.to_string_lossy(),
+            &to.to_string_lossy(),
+        ])
+        .run()
+        .with_context(|| format!("Failed to move worktree '{}'", from.display()))?;
+    Ok(())
+}
+
+/// Branch name reported for a worktree with a detached HEAD
+pub const DETACHED: &str = "(detached)";
+
+/// Whether a branch name from [`list_worktrees`] stands for a detached HEAD
+pub fn is_detached(branch: &str) -> bool {
+    branch == DETACHED
+}
+
 /// Parse the output of `git worktree list --porcelain`
 fn parse_worktree_list_porcelain(output: &str) -> Result<Vec<(PathBuf, String)>> {
     let mut worktrees = Vec::new();
@@ -469,11 +720,11 @@ fn parse_worktree_list_porcelain(output: &str) -> Result<Vec<(PathBuf, String)>>
 
         for line in block.lines() {
             if let Some(p) = line.strip_prefix("worktree ") {
-                path = Some(PathBuf::from(p));
+                path = Some(cmd::native_path(p));
             } else if let Some(b) = line.strip_prefix("branch refs/heads/") {
                 branch = Some(b.to_string());
             } else if line.trim() == "detached" {
-                branch = Some("(detached)".to_string());
+                branch = Some(DETACHED.to_string());
             }
         }
 
@@ -606,6 +857,16 @@ pub fn has_unstaged_changes(worktree_path: &Path) -> Result<bool> {
     Ok(!no_changes)
 }
 
+/// Unix timestamp of the last commit on the worktree's HEAD
+pub fn last_commit_time(worktree_path: &Path) -> Option<u64> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["log", "-1", "--format=%ct"])
+        .run_and_capture_stdout()
+        .ok()
+        .and_then(|ts| ts.parse().ok())
+}
+
 /// Commit staged changes in a worktree using the user's editor
 pub fn commit_with_editor(worktree_path: &Path) -> Result<()> {
     let status = Command::new("git")
@@ -712,6 +973,58 @@ pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()>
     Ok(())
 }
 
+/// Merge a branch into the current branch of a worktree with an explicit commit message
+pub fn merge_in_worktree_with_message(
+    worktree_path: &Path,
+    branch_name: &str,
+    message: &str,
+) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["merge", "--no-edit", "-m", message, branch_name])
+        .run()
+        .context("Failed to merge")?;
+    Ok(())
+}
+
+/// Files that would conflict when merging `branch` into `target`. Uses
+/// `git merge-tree`, so no worktree or index is touched. Empty when the merge
+/// is clean; an error on Git older than 2.38.
+pub fn merge_conflicts(target: &str, branch: &str) -> Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .args([
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            target,
+            branch,
+        ])
+        .output()
+        .context("Failed to run git merge-tree")?;
+    match output.status.code() {
+        Some(0) => Ok(Vec::new()),
+        Some(1) => Ok(parse_merge_tree_conflicts(&String::from_utf8_lossy(
+            &output.stdout,
+        ))),
+        _ => Err(anyhow!(
+            "git merge-tree failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// Conflicted file names from `git merge-tree --write-tree --name-only`
+/// output: the tree OID on the first line, then one file per line.
+fn parse_merge_tree_conflicts(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 /// Rebase the current branch in a worktree onto a base branch
 pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str) -> Result<()> {
     Cmd::new("git")
@@ -722,6 +1035,139 @@ pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str) -> Resul
     Ok(())
 }
 
+/// Abort a rebase in progress in a specific worktree
+pub fn abort_rebase_in_worktree(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["rebase", "--abort"])
+        .run()
+        .context("Failed to abort rebase")?;
+    Ok(())
+}
+
+/// Replay the worktree's commits after `upstream` onto `onto`, leaving out
+/// the commits `upstream` already has
+pub fn rebase_onto_in_worktree(worktree_path: &Path, onto: &str, upstream: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["rebase", "--onto", onto, upstream])
+        .run()
+        .with_context(|| format!("Failed to rebase onto '{}'", onto))?;
+    Ok(())
+}
+
+/// Best common ancestor of the worktree's HEAD and `rev`
+pub fn merge_base_with_head(worktree_path: &Path, rev: &str) -> Result<String> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["merge-base", "HEAD", rev])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to find where HEAD forked from '{}'", rev))
+}
+
+/// Check whether `ancestor` is already contained in the worktree's HEAD
+pub fn head_contains(worktree_path: &Path, ancestor: &str) -> Result<bool> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["merge-base", "--is-ancestor", ancestor, "HEAD"])
+        .run_as_check()
+}
+
+/// Number of commits on `branch` that `target` doesn't have yet
+pub fn count_commits_ahead(target: &str, branch: &str) -> Result<usize> {
+    let range = format!("{}..{}", target, branch);
+    let count = Cmd::new("git")
+        .args(&["rev-list", "--count", &range])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to count commits in '{}'", range))?;
+    count
+        .trim()
+        .parse()
+        .with_context(|| format!("Unexpected commit count '{}'", count))
+}
+
+/// Commits in `range` (e.g. "abc123..HEAD"), oldest first, with revisions
+/// resolved in the worktree
+pub fn commits_in_range(worktree_path: &Path, range: &str) -> Result<Vec<String>> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["rev-list", "--reverse", range])
+        .run_and_capture_stdout()
+        .with_context(|| format!("'{}' is not a valid commit range", range))?;
+    Ok(output.lines().map(String::from).collect())
+}
+
+/// Check whether `commit` is reachable from `rev`
+pub fn is_ancestor(commit: &str, rev: &str) -> Result<bool> {
+    Cmd::new("git")
+        .args(&["merge-base", "--is-ancestor", commit, rev])
+        .run_as_check()
+}
+
+/// Apply `commits` in order on top of the current branch of a worktree
+pub fn cherry_pick_in_worktree(worktree_path: &Path, commits: &[String]) -> Result<()> {
+    let mut args = vec!["cherry-pick"];
+    args.extend(commits.iter().map(String::as_str));
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&args)
+        .run()
+        .context("Failed to cherry-pick")?;
+    Ok(())
+}
+
+/// Commit the reverse of `rev` (a commit, or a range such as "a..b") on top
+/// of the current branch of a worktree. `mainline` selects the parent a
+/// merge commit is reverted to.
+pub fn revert_in_worktree(worktree_path: &Path, rev: &str, mainline: bool) -> Result<()> {
+    let mut args = vec!["revert", "--no-edit"];
+    if mainline {
+        args.extend(["-m", "1"]);
+    }
+    args.push(rev);
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&args)
+        .run()
+        .with_context(|| format!("Failed to revert {}", rev))?;
+    Ok(())
+}
+
+/// Abort a revert in progress in a specific worktree
+pub fn abort_revert_in_worktree(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["revert", "--abort"])
+        .run()
+        .context("Failed to abort revert")?;
+    Ok(())
+}
+
+/// Abort a cherry-pick in progress in a specific worktree
+pub fn abort_cherry_pick_in_worktree(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["cherry-pick", "--abort"])
+        .run()
+        .context("Failed to abort cherry-pick")?;
+    Ok(())
+}
+
+/// The remote-tracking branch a local branch follows (e.g. "origin/main"), if any
+pub fn get_upstream_branch(branch: &str) -> Option<String> {
+    let upstream = format!("{}@{{upstream}}", branch);
+    Cmd::new("git")
+        .args(&[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            &upstream,
+        ])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
 /// Perform a squash merge in a specific worktree (does not commit)
 pub fn merge_squash_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
     Cmd::new("git")
@@ -732,6 +1178,27 @@ pub fn merge_squash_in_worktree(worktree_path: &Path, branch_name: &str) -> Resu
     Ok(())
 }
 
+/// Append text to the message prepared by a squash merge, so it shows up in the editor
+pub fn append_to_squash_message(worktree_path: &Path, text: &str) -> Result<()> {
+    let msg_path = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "rev-parse",
+            "--path-format=absolute",
+            "--git-path",
+            "SQUASH_MSG",
+        ])
+        .run_and_capture_stdout()
+        .context("Failed to locate SQUASH_MSG")?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&msg_path)
+        .with_context(|| format!("Failed to open {}", msg_path))?;
+    writeln!(file, "\n{}", text).context("Failed to write squash message")?;
+    Ok(())
+}
+
 /// Switch to a different branch in a specific worktree
 pub fn switch_branch_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
     Cmd::new("git")
@@ -755,6 +1222,52 @@ pub fn get_current_branch() -> Result<String> {
         .run_and_capture_stdout()
 }
 
+fn get_current_branch_in(worktree_path: &Path) -> Result<String> {
+    let branch = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["branch", "--show-current"])
+        .run_and_capture_stdout()?;
+    if branch.is_empty() {
+        return Err(anyhow!("HEAD is detached"));
+    }
+    Ok(branch)
+}
+
+/// Mark the worktree's HEAD as reviewed, so later diffs can show only what
+/// changed since. Returns the checkpoint commit.
+pub fn set_review_checkpoint(worktree_path: &Path) -> Result<String> {
+    let branch = get_current_branch_in(worktree_path)?;
+    let sha = resolve_commit_in("HEAD", Some(worktree_path))?;
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-review", branch),
+            &sha,
+        ])
+        .run()
+        .context("Failed to set workmux-review config")?;
+    Ok(sha)
+}
+
+/// Commit last marked as reviewed on the worktree's branch, if any
+pub fn get_review_checkpoint(worktree_path: &Path) -> Option<String> {
+    let branch = get_current_branch_in(worktree_path).ok()?;
+    let config_key = format!("branch.{}.workmux-review", branch);
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["config", "--local", &config_key])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|sha| !sha.is_empty())
+}
+
+/// Whether a revision names a commit in the given worktree
+pub fn commit_exists_in(rev: &str, worktree_path: &Path) -> bool {
+    resolve_commit_in(rev, Some(worktree_path)).is_ok()
+}
+
 /// List all checkout-able branches (local and remote) for shell completion.
 /// Excludes branches that are already checked out in existing worktrees.
 pub fn list_checkout_branches() -> Result<Vec<String>> {
@@ -804,6 +1317,89 @@ pub fn delete_branch(branch_name: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Create a branch pointing at a commit, without checking it out
+pub fn create_branch_at(branch_name: &str, commit: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&["branch", "--end-of-options", branch_name, commit])
+        .run()
+        .with_context(|| format!("Failed to create branch '{}'", branch_name))?;
+    Ok(())
+}
+
+/// Point a ref (e.g. `refs/workmux/archive/fix-login`) at a commit
+pub fn update_ref(ref_name: &str, commit: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&["update-ref", ref_name, commit])
+        .run()
+        .with_context(|| format!("Failed to update ref '{}'", ref_name))?;
+    Ok(())
+}
+
+/// Delete a ref
+pub fn delete_ref(ref_name: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&["update-ref", "-d", ref_name])
+        .run()
+        .with_context(|| format!("Failed to delete ref '{}'", ref_name))?;
+    Ok(())
+}
+
+/// Push a ref to the same name on a remote
+pub fn push_ref(remote: &str, ref_name: &str) -> Result<()> {
+    let refspec = format!("{}:{}", ref_name, ref_name);
+    Cmd::new("git")
+        .args(&["push", remote, &refspec])
+        .run()
+        .with_context(|| format!("Failed to push '{}' to '{}'", ref_name, remote))?;
+    Ok(())
+}
+
+/// Fetch a ref from a remote into the same local name
+pub fn fetch_ref(remote: &str, ref_name: &str) -> Result<()> {
+    let refspec = format!("{}:{}", ref_name, ref_name);
+    Cmd::new("git")
+        .args(&["fetch", remote, &refspec])
+        .run()
+        .with_context(|| format!("Failed to fetch '{}' from '{}'", ref_name, remote))?;
+    Ok(())
+}
+
+/// Delete a ref on a remote
+pub fn delete_remote_ref(remote: &str, ref_name: &str) -> Result<()> {
+    let refspec = format!(":{}", ref_name);
+    Cmd::new("git")
+        .args(&["push", remote, &refspec])
+        .run()
+        .with_context(|| format!("Failed to delete '{}' on '{}'", ref_name, remote))?;
+    Ok(())
+}
+
+/// Commit everything in a worktree, untracked files included, without
+/// running commit hooks
+pub fn commit_all_in(worktree_path: &Path, message: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["add", "-A"])
+        .run()
+        .context("Failed to stage changes")?;
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["commit", "--no-verify", "-m", message])
+        .run()
+        .context("Failed to commit changes")?;
+    Ok(())
+}
+
+/// Undo the last commit in a worktree, leaving its changes uncommitted
+pub fn uncommit_in(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["reset", "--mixed", "HEAD~1"])
+        .run()
+        .context("Failed to undo the last commit")?;
+    Ok(())
+}
+
 /// Stash uncommitted changes, optionally including untracked files or using patch mode.
 pub fn stash_push(message: &str, include_untracked: bool, patch: bool) -> Result<()> {
     use std::process::Command;
@@ -900,6 +1496,245 @@ pub fn get_branch_base_in(branch: &str, workdir: Option<&Path>) -> Result<String
     Ok(output)
 }
 
+/// Record the commit a branch was created from. Unlike the base name, it stays
+/// valid when the base branch or tag is later deleted or moved.
+pub fn set_branch_base_sha(branch: &str, sha: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-base-sha", branch),
+            sha,
+        ])
+        .run()
+        .context("Failed to set workmux-base-sha config")?;
+    Ok(())
+}
+
+/// Base to compare a branch against: the recorded base while it still resolves,
+/// otherwise the commit the branch was created from. None if neither was recorded.
+pub fn get_branch_base_ref_in(branch: &str, workdir: Option<&Path>) -> Option<String> {
+    let base = get_branch_base_in(branch, workdir).ok();
+    if base
+        .as_deref()
+        .is_some_and(|base| resolve_commit_in(base, workdir).is_ok())
+    {
+        return base;
+    }
+    get_branch_base_sha_in(branch, workdir).or(base)
+}
+
+/// Commit a branch was created from (or last synced with), if recorded
+pub fn get_branch_base_sha(branch: &str) -> Option<String> {
+    get_branch_base_sha_in(branch, None)
+}
+
+fn get_branch_base_sha_in(branch: &str, workdir: Option<&Path>) -> Option<String> {
+    let config_key = format!("branch.{}.workmux-base-sha", branch);
+    let cmd = Cmd::new("git").args(&["config", "--local", &config_key]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout()
+        .ok()
+        .filter(|sha| !sha.is_empty())
+}
+
+/// Resolve a revision (branch, tag, SHA, or expression like `@{u}`) to a full commit SHA
+pub fn resolve_commit(rev: &str) -> Result<String> {
+    resolve_commit_in(rev, None)
+}
+
+pub fn resolve_commit_in(rev: &str, workdir: Option<&Path>) -> Result<String> {
+    let commit = format!("{}^{{commit}}", rev);
+    let cmd = Cmd::new("git").args(&["rev-parse", "--verify", "--end-of-options", &commit]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout()
+        .with_context(|| {
+            format!(
+                "'{}' does not name a commit (expected a branch, tag, commit SHA, or a ref such as @{{u}})",
+                rev
+            )
+        })
+}
+
+/// The ref name a revision refers to (e.g. `@{u}` -> `origin/main`), or None
+/// for commit SHAs and other expressions that are not a ref
+pub fn symbolic_ref_name(rev: &str) -> Option<String> {
+    Cmd::new("git")
+        .args(&["rev-parse", "--abbrev-ref", "--symbolic-full-name", rev])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+/// Store the monorepo package a branch's worktree is scoped to (`add --package`)
+pub fn set_branch_package(branch: &str, package: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-package", branch),
+            package,
+        ])
+        .run()
+        .context("Failed to set workmux-package config")?;
+    Ok(())
+}
+
+/// Retrieve the package a branch's worktree is scoped to, if any
+pub fn get_branch_package(branch: &str) -> Option<String> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-package", branch),
+        ])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|s| !s.is_empty())
+}
+
+/// Record the pull request opened for a branch (`pr create`)
+pub fn set_branch_pr(branch: &str, number: u32) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-pr", branch),
+            &number.to_string(),
+        ])
+        .run()
+        .context("Failed to set workmux-pr config")?;
+    Ok(())
+}
+
+/// Get the pull request recorded for a branch in a specific workdir
+pub fn get_branch_pr_in(branch: &str, workdir: Option<&Path>) -> Option<u32> {
+    let config_key = format!("branch.{}.workmux-pr", branch);
+    let cmd = Cmd::new("git").args(&["config", "--local", &config_key]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout().ok()?.parse().ok()
+}
+
+/// Link a branch to the issue it was created from (`add --from-issue`)
+pub fn set_branch_issue(branch: &str, number: u32) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-issue", branch),
+            &number.to_string(),
+        ])
+        .run()
+        .context("Failed to set workmux-issue config")?;
+    Ok(())
+}
+
+/// Get the issue a branch was created from in a specific workdir
+pub fn get_branch_issue_in(branch: &str, workdir: Option<&Path>) -> Option<u32> {
+    let config_key = format!("branch.{}.workmux-issue", branch);
+    let cmd = Cmd::new("git").args(&["config", "--local", &config_key]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout().ok()?.parse().ok()
+}
+
+/// Get the recorded pull request of every branch in one call, keyed by branch name
+pub fn get_all_branch_prs() -> HashMap<String, u32> {
+    let output = Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            "--get-regexp",
+            r"^branch\..*\.workmux-pr$",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, number) = line.split_once(' ')?;
+            let branch = key.strip_prefix("branch.")?.strip_suffix(".workmux-pr")?;
+            Some((branch.to_string(), number.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Push a branch and set its upstream
+pub fn push_branch(worktree_path: &Path, remote: &str, branch: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["push", "--set-upstream", remote, branch])
+        .run()
+        .with_context(|| format!("Failed to push '{}' to '{}'", branch, remote))?;
+    Ok(())
+}
+
+/// A commit's subject line and body
+pub struct CommitMessage {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Commit messages in `base..HEAD`, oldest first
+pub fn get_commit_messages(worktree_path: &Path, base: &str) -> Result<Vec<CommitMessage>> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "log",
+            "--reverse",
+            "--format=%s%x1f%b%x1e",
+            &format!("{}..HEAD", base),
+        ])
+        .run_and_capture_stdout()
+        .context("Failed to read commit log")?;
+    Ok(output
+        .split('\x1e')
+        .filter_map(|entry| {
+            let (subject, body) = entry.trim_start_matches('\n').split_once('\x1f')?;
+            Some(CommitMessage {
+                subject: subject.to_string(),
+                body: body.trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Get the package scope of every branch in one call, keyed by branch name
+pub fn get_all_branch_packages() -> HashMap<String, String> {
+    let output = Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            "--get-regexp",
+            r"^branch\..*\.workmux-package$",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, package) = line.split_once(' ')?;
+            let branch = key
+                .strip_prefix("branch.")?
+                .strip_suffix(".workmux-package")?;
+            Some((branch.to_string(), package.to_string()))
+        })
+        .collect()
+}
+
 /// Parse git status porcelain v2 output to extract branch info and dirty state.
 /// Returns (branch_name, ahead, behind, is_dirty).
 fn parse_porcelain_v2_status(output: &str) -> (Option<String>, usize, usize, bool) {
@@ -910,8 +1745,8 @@ fn parse_porcelain_v2_status(output: &str) -> (Option<String>, usize, usize, boo
 
     for line in output.lines() {
         if let Some(rest) = line.strip_prefix("# branch.head ") {
-            // "(detached)" indicates detached HEAD state
-            if rest != "(detached)" {
+            // git reports a detached HEAD the same way
+            if !is_detached(rest) {
                 branch_name = Some(rest.to_string());
             }
         } else if let Some(rest) = line.strip_prefix("# branch.ab ") {
@@ -984,6 +1819,8 @@ fn count_lines(path: &Path) -> std::io::Result<usize> {
 /// Separates committed and uncommitted changes:
 /// - Committed: changes in base...HEAD (what's been committed on the branch)
 /// - Uncommitted: working tree changes + untracked files
+/// - Total: working tree + untracked files vs the merge base, i.e. what merging
+///   everything would change
 struct DiffStats {
     /// Lines added in committed changes only (base...HEAD)
     committed_added: usize,
@@ -993,6 +1830,27 @@ struct DiffStats {
     uncommitted_added: usize,
     /// Lines removed in uncommitted changes (working tree)
     uncommitted_removed: usize,
+    /// Lines added vs the merge base (working tree + untracked)
+    total_added: usize,
+    /// Lines removed vs the merge base (working tree)
+    total_removed: usize,
+    /// Files in any of the diffs above, sorted
+    changed_files: Vec<String>,
+}
+
+/// Path of a `git diff --numstat` line, the new one for renames
+/// (`old => new`, or `dir/{old => new}/file`)
+fn numstat_path(line: &str) -> Option<String> {
+    let path = line.splitn(3, '\t').nth(2)?;
+    let Some((before, after)) = path.split_once(" => ") else {
+        return Some(path.to_string());
+    };
+    match (before.split_once('{'), after.split_once('}')) {
+        (Some((prefix, _)), Some((new, suffix))) => {
+            Some(format!("{}{}{}", prefix, new, suffix).replace("//", "/"))
+        }
+        _ => Some(after.to_string()),
+    }
 }
 
 fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
@@ -1000,6 +1858,8 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
     let mut committed_removed = 0;
     let mut uncommitted_added = 0;
     let mut uncommitted_removed = 0;
+    let mut untracked_added = 0;
+    let mut changed_files = std::collections::BTreeSet::new();
 
     // Helper to parse numstat output
     let parse_numstat = |output: &str| -> (usize, usize) {
@@ -1026,6 +1886,7 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
         let (a, r) = parse_numstat(&output);
         committed_added += a;
         committed_removed += r;
+        changed_files.extend(output.lines().filter_map(numstat_path));
     }
 
     // 2. Uncommitted changes (HEAD vs working tree)
@@ -1038,6 +1899,7 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
         let (a, r) = parse_numstat(&output);
         uncommitted_added += a;
         uncommitted_removed += r;
+        changed_files.extend(output.lines().filter_map(numstat_path));
     }
 
     // 3. Untracked files (all lines count as added to uncommitted)
@@ -1051,6 +1913,7 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
             if file_path.is_empty() {
                 continue;
             }
+            changed_files.insert(file_path.to_string());
 
             let full_path = worktree_path.join(file_path);
 
@@ -1058,21 +1921,43 @@ fn get_diff_stats(worktree_path: &Path, base_ref: &str) -> DiffStats {
             if let Ok(metadata) = std::fs::symlink_metadata(&full_path)
                 && metadata.file_type().is_symlink()
             {
-                uncommitted_added += 1;
+                untracked_added += 1;
                 continue;
             }
 
             if let Ok(lines) = count_lines(&full_path) {
-                uncommitted_added += lines;
+                untracked_added += lines;
             }
         }
     }
+    uncommitted_added += untracked_added;
+
+    // 4. Total (merge base vs working tree). Changes made by a commit and
+    // reverted in the working tree cancel out here, unlike in the sum above.
+    // `--merge-base` needs Git 2.30+; fall back to the sum.
+    let (total_added, total_removed) = match Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["diff", "--numstat", "--merge-base", base_ref])
+        .run_and_capture_stdout()
+    {
+        Ok(output) => {
+            let (a, r) = parse_numstat(&output);
+            (a + untracked_added, r)
+        }
+        Err(_) => (
+            committed_added + uncommitted_added,
+            committed_removed + uncommitted_removed,
+        ),
+    };
 
     DiffStats {
         committed_added,
         committed_removed,
         uncommitted_added,
         uncommitted_removed,
+        total_added,
+        total_removed,
+        changed_files: changed_files.into_iter().collect(),
     }
 }
 
@@ -1115,8 +2000,7 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
 
     // Determine base branch for conflict check and diff stats
     // First try workmux-base config, then fall back to default branch
-    let base_branch = get_branch_base_in(&branch, Some(worktree_path))
-        .ok()
+    let base_branch = get_branch_base_ref_in(&branch, Some(worktree_path))
         .or_else(|| get_default_branch_in(Some(worktree_path)).ok())
         .unwrap_or_else(|| "main".to_string());
 
@@ -1130,6 +2014,9 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
             is_dirty,
             uncommitted_added: stats.uncommitted_added,
             uncommitted_removed: stats.uncommitted_removed,
+            total_added: stats.total_added,
+            total_removed: stats.total_removed,
+            changed_files: stats.changed_files,
             cached_at: now,
             base_branch,
             ..Default::default()
@@ -1164,14 +2051,51 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
         lines_removed: diff_stats.committed_removed,
         uncommitted_added: diff_stats.uncommitted_added,
         uncommitted_removed: diff_stats.uncommitted_removed,
+        total_added: diff_stats.total_added,
+        total_removed: diff_stats.total_removed,
+        changed_files: diff_stats.changed_files,
         cached_at: now,
+        pr_number: get_branch_pr_in(&branch, Some(worktree_path)),
         base_branch,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::parse_owner_from_git_url;
+    use super::{numstat_path, parse_merge_tree_conflicts, parse_owner_from_git_url};
+
+    #[test]
+    fn numstat_path_takes_new_name_of_renames() {
+        assert_eq!(
+            numstat_path("3\t1\tsrc/lib.rs"),
+            Some("src/lib.rs".to_string())
+        );
+        assert_eq!(
+            numstat_path("0\t0\told.rs => new.rs"),
+            Some("new.rs".to_string())
+        );
+        assert_eq!(
+            numstat_path("2\t2\tsrc/{auth => login}/mod.rs"),
+            Some("src/login/mod.rs".to_string())
+        );
+        assert_eq!(
+            numstat_path("2\t2\tsrc/{auth => }/mod.rs"),
+            Some("src/mod.rs".to_string())
+        );
+        assert_eq!(numstat_path("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_merge_tree_conflicts() {
+        let output = "4b825dc642cb6eb9a060e54bf8d69288fbee4904\nsrc/lib.rs\nREADME.md\n";
+        assert_eq!(
+            parse_merge_tree_conflicts(output),
+            vec!["src/lib.rs", "README.md"]
+        );
+        assert!(
+            parse_merge_tree_conflicts("4b825dc642cb6eb9a060e54bf8d69288fbee4904\n").is_empty()
+        );
+    }
 
     #[test]
     fn test_parse_repo_owner_https_github_com() {
@@ -1394,4 +2318,66 @@ mod tests {
         assert_eq!(branch, Some("feature".to_string()));
         assert!(is_dirty);
     }
+
+    use super::{GitStatus, STATUS_CACHE_MAX_ENTRIES, is_status_outdated, merge_status_cache};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn merge_status_cache_keeps_other_entries_and_newest_status() {
+        let status = |cached_at: u64, ahead: usize| GitStatus {
+            ahead,
+            cached_at: Some(cached_at),
+            ..Default::default()
+        };
+        let mut cache = HashMap::from([
+            (PathBuf::from("/wt/a"), status(100, 1)),
+            (PathBuf::from("/wt/b"), status(300, 2)),
+            (PathBuf::from("/wt/gone"), status(100, 0)),
+        ]);
+        // Another process saved a newer status for b while we held an older one
+        let ours = HashMap::from([
+            (PathBuf::from("/wt/a"), status(200, 5)),
+            (PathBuf::from("/wt/b"), status(250, 9)),
+        ]);
+
+        merge_status_cache(&mut cache, &ours, |p| p != Path::new("/wt/gone"));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache[Path::new("/wt/a")].ahead, 5);
+        assert_eq!(cache[Path::new("/wt/b")].ahead, 2);
+    }
+
+    #[test]
+    fn merge_status_cache_evicts_least_recently_fetched() {
+        let mut cache = HashMap::new();
+        let statuses: HashMap<PathBuf, GitStatus> = (0..STATUS_CACHE_MAX_ENTRIES as u64 + 2)
+            .map(|i| {
+                let status = GitStatus {
+                    cached_at: Some(i),
+                    ..Default::default()
+                };
+                (PathBuf::from(format!("/wt/{}", i)), status)
+            })
+            .collect();
+
+        merge_status_cache(&mut cache, &statuses, |_| true);
+
+        assert_eq!(cache.len(), STATUS_CACHE_MAX_ENTRIES);
+        assert!(!cache.contains_key(Path::new("/wt/0")));
+        assert!(!cache.contains_key(Path::new("/wt/1")));
+        assert!(cache.contains_key(Path::new("/wt/2")));
+    }
+
+    #[test]
+    fn status_is_outdated_when_missing_or_past_ttl() {
+        let status = |cached_at: Option<u64>| GitStatus {
+            cached_at,
+            ..Default::default()
+        };
+        assert!(is_status_outdated(None, 100));
+        assert!(is_status_outdated(Some(&status(None)), 100));
+        assert!(!is_status_outdated(Some(&status(Some(90))), 100));
+        assert!(is_status_outdated(Some(&status(Some(10))), 100));
+    }
 }
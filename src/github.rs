@@ -1,9 +1,11 @@
 use anyhow::{Context, Result, anyhow};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
 use tracing::debug;
 
+use crate::config::MergeStrategy;
+
 #[derive(Debug, Deserialize)]
 pub struct PrDetails {
     #[serde(rename = "headRefName")]
@@ -33,14 +35,26 @@ impl PrDetails {
     }
 }
 
+/// Title, body and state of an issue (`add --from-issue`)
+#[derive(Debug, Deserialize)]
+pub struct IssueDetails {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub state: String,
+}
+
 /// Summary of a PR found by head ref search
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PrSummary {
     pub number: u32,
     pub title: String,
     pub state: String,
-    #[serde(rename = "isDraft")]
+    #[serde(rename(deserialize = "isDraft"))]
     pub is_draft: bool,
+    /// Commit the PR head pointed at, when the lookup returned it
+    #[serde(default, skip_serializing)]
+    pub head_sha: Option<String>,
 }
 
 /// Internal struct for parsing PR list results with owner info
@@ -111,6 +125,7 @@ pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary
         title: pr.title,
         state: pr.state,
         is_draft: pr.is_draft,
+        head_sha: None,
     }))
 }
 
@@ -159,6 +174,44 @@ pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
     Ok(pr_details)
 }
 
+/// Fetches issue details using the GitHub CLI
+pub fn get_issue(issue_number: u32) -> Result<IssueDetails> {
+    let output = Command::new("gh")
+        .args([
+            "issue",
+            "view",
+            &issue_number.to_string(),
+            "--json",
+            "title,body,state",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!("github:gh CLI not found");
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for --from-issue. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(issue = issue_number, stderr = %stderr, "github:issue view failed");
+        return Err(anyhow!(
+            "Failed to fetch issue #{}: {}",
+            issue_number,
+            stderr.trim()
+        ));
+    }
+
+    serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")
+}
+
 /// Internal struct for parsing batch PR list results
 #[derive(Debug, Deserialize)]
 struct PrBatchItem {
@@ -169,6 +222,8 @@ struct PrBatchItem {
     is_draft: bool,
     #[serde(rename = "headRefName")]
     head_ref_name: String,
+    #[serde(rename = "headRefOid", default)]
+    head_ref_oid: Option<String>,
 }
 
 /// Fetch all PRs for the current repository.
@@ -180,7 +235,7 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
             "--state",
             "all",
             "--json",
-            "number,title,state,isDraft,headRefName",
+            "number,title,state,isDraft,headRefName,headRefOid",
             "--limit",
             "200",
         ])
@@ -217,6 +272,7 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
                     title: pr.title,
                     state: pr.state,
                     is_draft: pr.is_draft,
+                    head_sha: pr.head_ref_oid,
                 },
             )
         })
@@ -224,3 +280,281 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
 
     Ok(pr_map)
 }
+
+/// Combined result of a PR's CI checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksState {
+    /// No checks reported
+    None,
+    Pending,
+    Passing,
+    Failing,
+}
+
+/// Review, CI and merge state of a PR, as shown by `workmux pr status`
+#[derive(Debug, Clone, Serialize)]
+pub struct PrStatus {
+    pub number: u32,
+    pub title: String,
+    pub state: String,
+    pub is_draft: bool,
+    pub url: String,
+    /// APPROVED, CHANGES_REQUESTED or REVIEW_REQUIRED (None when not reported)
+    pub review: Option<String>,
+    pub checks: ChecksState,
+    /// MERGEABLE, CONFLICTING or UNKNOWN
+    pub mergeable: String,
+}
+
+/// A check run or commit status from `statusCheckRollup`
+#[derive(Debug, Deserialize)]
+struct CheckItem {
+    /// Check runs: QUEUED, IN_PROGRESS, COMPLETED, ...
+    #[serde(default)]
+    status: Option<String>,
+    /// Completed check runs: SUCCESS, FAILURE, NEUTRAL, SKIPPED, ...
+    #[serde(default)]
+    conclusion: Option<String>,
+    /// Commit statuses: SUCCESS, PENDING, FAILURE, ERROR, EXPECTED
+    #[serde(default)]
+    state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrStatusItem {
+    number: u32,
+    title: String,
+    state: String,
+    #[serde(rename = "isDraft")]
+    is_draft: bool,
+    #[serde(rename = "headRefName")]
+    head_ref_name: String,
+    #[serde(default)]
+    url: String,
+    #[serde(rename = "reviewDecision", default)]
+    review_decision: Option<String>,
+    #[serde(rename = "statusCheckRollup", default)]
+    status_check_rollup: Option<Vec<CheckItem>>,
+    #[serde(default)]
+    mergeable: Option<String>,
+}
+
+/// Reduce individual checks to one state: any failure wins, then anything
+/// still running
+fn checks_state(items: &[CheckItem]) -> ChecksState {
+    let mut state = ChecksState::None;
+    for item in items {
+        let failed = matches!(
+            item.conclusion.as_deref(),
+            Some("FAILURE" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" | "STARTUP_FAILURE")
+        ) || matches!(item.state.as_deref(), Some("FAILURE" | "ERROR"));
+        let pending = item.status.as_deref().is_some_and(|s| s != "COMPLETED")
+            || matches!(item.state.as_deref(), Some("PENDING" | "EXPECTED"));
+        if failed {
+            return ChecksState::Failing;
+        }
+        if pending {
+            state = ChecksState::Pending;
+        } else if state == ChecksState::None {
+            state = ChecksState::Passing;
+        }
+    }
+    state
+}
+
+/// Fetch review, CI and merge state for all PRs in one call, keyed by head
+/// branch. When a branch has several PRs, the newest one is kept.
+pub fn list_pr_statuses() -> Result<HashMap<String, PrStatus>> {
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "list",
+            "--state",
+            "all",
+            "--json",
+            "number,title,state,isDraft,headRefName,url,reviewDecision,statusCheckRollup,mergeable",
+            "--limit",
+            "200",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for pr status. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to list PRs: {}", stderr.trim()));
+    }
+
+    let prs: Vec<PrStatusItem> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh JSON output")?;
+
+    let mut statuses = HashMap::new();
+    // gh lists the newest PRs first
+    for pr in prs {
+        let checks = checks_state(pr.status_check_rollup.as_deref().unwrap_or_default());
+        statuses.entry(pr.head_ref_name).or_insert(PrStatus {
+            number: pr.number,
+            title: pr.title,
+            state: pr.state,
+            is_draft: pr.is_draft,
+            url: pr.url,
+            review: pr.review_decision.filter(|r| !r.is_empty()),
+            checks,
+            mergeable: pr.mergeable.unwrap_or_else(|| "UNKNOWN".to_string()),
+        });
+    }
+    Ok(statuses)
+}
+
+/// Open a PR in the browser
+pub fn view_pr_in_browser(pr_number: u32) -> Result<()> {
+    let number = pr_number.to_string();
+    let status = Command::new("gh")
+        .args(["pr", "view", &number, "--web"])
+        .status()
+        .context("Failed to execute gh command")?;
+    if !status.success() {
+        return Err(anyhow!("Failed to open PR #{} in the browser", pr_number));
+    }
+    Ok(())
+}
+
+/// Merge a pull request with the given strategy. Fails while branch
+/// protection (required checks or reviews) blocks the merge; with a merge
+/// queue, the PR is queued instead.
+pub fn merge_pr(pr_number: u32, strategy: MergeStrategy) -> Result<()> {
+    let method = match strategy {
+        MergeStrategy::Merge => "--merge",
+        MergeStrategy::Rebase => "--rebase",
+        MergeStrategy::Squash => "--squash",
+    };
+    let output = Command::new("gh")
+        .args(["pr", "merge", &pr_number.to_string(), method])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for merge --pr. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(pr = pr_number, stderr = %stderr, "github:pr merge failed");
+        return Err(anyhow!(
+            "Failed to merge PR #{}: {}",
+            pr_number,
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Create a pull request for `head` against `base` using the GitHub CLI.
+/// Returns the PR number and URL.
+pub fn create_pr(
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<(u32, String)> {
+    let mut args = vec![
+        "pr", "create", "--head", head, "--base", base, "--title", title, "--body", body,
+    ];
+    if draft {
+        args.push("--draft");
+    }
+    let output = Command::new("gh").args(&args).output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for pr create. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(head, stderr = %stderr, "github:pr create failed");
+        return Err(anyhow!("Failed to create PR: {}", stderr.trim()));
+    }
+
+    // gh prints the URL of the new PR as the last line of stdout
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let url = stdout.lines().last().unwrap_or_default().trim().to_string();
+    let number = parse_pr_number(&url)
+        .ok_or_else(|| anyhow!("Could not find the PR number in gh output: {}", url))?;
+    Ok((number, url))
+}
+
+/// Extract the PR number from a URL like https://github.com/owner/repo/pull/42
+fn parse_pr_number(url: &str) -> Option<u32> {
+    let (_, number) = url.trim_end_matches('/').rsplit_once("/pull/")?;
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pr_number_from_url() {
+        assert_eq!(
+            parse_pr_number("https://github.com/owner/repo/pull/42"),
+            Some(42)
+        );
+        assert_eq!(
+            parse_pr_number("https://github.example.com/o/r/pull/7/"),
+            Some(7)
+        );
+        assert_eq!(parse_pr_number("https://github.com/owner/repo"), None);
+    }
+
+    #[test]
+    fn checks_state_prefers_failures_then_pending() {
+        let items: Vec<CheckItem> = serde_json::from_str(
+            r#"[{"status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"state": "SUCCESS"},
+                {"status": "COMPLETED", "conclusion": "SKIPPED"}]"#,
+        )
+        .unwrap();
+        assert_eq!(checks_state(&items), ChecksState::Passing);
+
+        let items: Vec<CheckItem> = serde_json::from_str(
+            r#"[{"status": "IN_PROGRESS", "conclusion": ""}, {"state": "SUCCESS"}]"#,
+        )
+        .unwrap();
+        assert_eq!(checks_state(&items), ChecksState::Pending);
+
+        let items: Vec<CheckItem> = serde_json::from_str(
+            r#"[{"status": "IN_PROGRESS"}, {"state": "ERROR"}, {"state": "SUCCESS"}]"#,
+        )
+        .unwrap();
+        assert_eq!(checks_state(&items), ChecksState::Failing);
+
+        assert_eq!(checks_state(&[]), ChecksState::None);
+    }
+}
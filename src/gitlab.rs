@@ -0,0 +1,425 @@
+//! GitLab merge request lookups via the GitLab CLI (`glab`).
+//!
+//! Results are converted to the GitHub types in `github.rs`, with merge
+//! request states normalized to GitHub's (OPEN, MERGED, CLOSED), so callers
+//! don't need to care which forge they are talking to.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::process::{Command, Output};
+use tracing::debug;
+
+use crate::config::MergeStrategy;
+use crate::git;
+use crate::github::{
+    Author, ChecksState, IssueDetails, PrDetails, PrStatus, PrSummary, RepositoryOwner,
+};
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u32,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    source_branch: String,
+    #[serde(default)]
+    sha: Option<String>,
+    #[serde(default)]
+    source_project_id: Option<u64>,
+    #[serde(default)]
+    target_project_id: Option<u64>,
+    #[serde(default)]
+    author: Option<GitLabUser>,
+    #[serde(default)]
+    web_url: String,
+    #[serde(default)]
+    has_conflicts: bool,
+    #[serde(default)]
+    detailed_merge_status: Option<String>,
+    #[serde(default)]
+    head_pipeline: Option<Pipeline>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pipeline {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    namespace: Namespace,
+}
+
+#[derive(Debug, Deserialize)]
+struct Namespace {
+    full_path: String,
+}
+
+impl MergeRequest {
+    fn summary(&self) -> PrSummary {
+        PrSummary {
+            number: self.iid,
+            title: self.title.clone(),
+            state: normalize_state(&self.state).to_string(),
+            is_draft: self.draft,
+            head_sha: self.sha.clone(),
+        }
+    }
+
+    fn status(&self) -> PrStatus {
+        let checks = match self.head_pipeline.as_ref().map(|p| p.status.as_str()) {
+            None => ChecksState::None,
+            Some("success") => ChecksState::Passing,
+            Some("failed" | "canceled") => ChecksState::Failing,
+            Some(_) => ChecksState::Pending,
+        };
+        let mergeable = if self.has_conflicts {
+            "CONFLICTING"
+        } else if self.detailed_merge_status.as_deref() == Some("mergeable") {
+            "MERGEABLE"
+        } else {
+            "UNKNOWN"
+        };
+        PrStatus {
+            number: self.iid,
+            title: self.title.clone(),
+            state: normalize_state(&self.state).to_string(),
+            is_draft: self.draft,
+            url: self.web_url.clone(),
+            // Approvals aren't part of the MR list response
+            review: None,
+            checks,
+            mergeable: mergeable.to_string(),
+        }
+    }
+}
+
+/// Map a GitLab merge request state to the GitHub equivalent
+fn normalize_state(state: &str) -> &'static str {
+    match state {
+        "merged" => "MERGED",
+        "closed" | "locked" => "CLOSED",
+        _ => "OPEN",
+    }
+}
+
+/// Run glab, mapping a missing binary to `None`
+fn run_glab(args: &[&str]) -> Result<Option<Output>> {
+    match Command::new("glab").args(args).output() {
+        Ok(out) => Ok(Some(out)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!("gitlab:glab CLI not found");
+            Ok(None)
+        }
+        Err(e) => Err(e).context("Failed to execute glab command"),
+    }
+}
+
+fn missing_glab() -> anyhow::Error {
+    anyhow!(
+        "GitLab CLI (glab) is required for GitLab remotes. Install from https://gitlab.com/gitlab-org/cli"
+    )
+}
+
+/// Fetch merge request details, shaped like a GitHub PR
+pub fn get_mr_details(mr_number: u32) -> Result<PrDetails> {
+    let number = mr_number.to_string();
+    let output =
+        run_glab(&["mr", "view", &number, "--output", "json"])?.ok_or_else(missing_glab)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(mr = mr_number, stderr = %stderr, "gitlab:mr view failed");
+        return Err(anyhow!(
+            "Failed to fetch MR !{}: {}",
+            mr_number,
+            stderr.trim()
+        ));
+    }
+
+    let mr: MergeRequest =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+
+    // Same-project MRs come from origin; for forks, look up the source namespace
+    let owner = match (mr.source_project_id, mr.target_project_id) {
+        (Some(source), Some(target)) if source != target => project_namespace(source)?,
+        _ => git::get_repo_owner().unwrap_or_default(),
+    };
+
+    Ok(PrDetails {
+        head_ref_name: mr.source_branch.clone(),
+        head_repository_owner: RepositoryOwner { login: owner },
+        state: normalize_state(&mr.state).to_string(),
+        is_draft: mr.draft,
+        title: mr.title.clone(),
+        author: Author {
+            login: mr
+                .author
+                .as_ref()
+                .map(|a| a.username.clone())
+                .unwrap_or_default(),
+        },
+    })
+}
+
+/// Fetch issue details, with the state normalized to OPEN or CLOSED
+pub fn get_issue(issue_number: u32) -> Result<IssueDetails> {
+    let number = issue_number.to_string();
+    let output =
+        run_glab(&["issue", "view", &number, "--output", "json"])?.ok_or_else(missing_glab)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(issue = issue_number, stderr = %stderr, "gitlab:issue view failed");
+        return Err(anyhow!(
+            "Failed to fetch issue #{}: {}",
+            issue_number,
+            stderr.trim()
+        ));
+    }
+
+    let issue: Issue =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    Ok(IssueDetails {
+        title: issue.title,
+        body: issue.description.unwrap_or_default(),
+        state: normalize_state(&issue.state).to_string(),
+    })
+}
+
+/// Namespace (user or group path) of a GitLab project
+fn project_namespace(project_id: u64) -> Result<String> {
+    let endpoint = format!("projects/{}", project_id);
+    let output = run_glab(&["api", &endpoint])?.ok_or_else(missing_glab)?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Failed to look up source project {}: {}",
+            project_id,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let project: Project =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    Ok(project.namespace.full_path)
+}
+
+/// Find a merge request by source branch. GitLab has no `owner:branch`
+/// syntax, so the first MR from that branch is returned.
+pub fn find_mr_by_source_branch(branch: &str) -> Result<Option<PrSummary>> {
+    let Some(output) = run_glab(&[
+        "mr",
+        "list",
+        "--all",
+        "--source-branch",
+        branch,
+        "--output",
+        "json",
+    ])?
+    else {
+        return Ok(None);
+    };
+    if !output.status.success() {
+        debug!(branch, "gitlab:mr list failed, treating as no MR found");
+        return Ok(None);
+    }
+    let mrs: Vec<MergeRequest> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    Ok(mrs.first().map(MergeRequest::summary))
+}
+
+/// Fetch all merge requests for the current project, keyed by source branch
+pub fn list_mrs() -> Result<HashMap<String, PrSummary>> {
+    let Some(output) = run_glab(&[
+        "mr",
+        "list",
+        "--all",
+        "--per-page",
+        "100",
+        "--output",
+        "json",
+    ])?
+    else {
+        return Ok(HashMap::new());
+    };
+    if !output.status.success() {
+        debug!("gitlab:mr list batch failed, treating as no MRs found");
+        return Ok(HashMap::new());
+    }
+    let mrs: Vec<MergeRequest> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    Ok(mrs
+        .into_iter()
+        .map(|mr| (mr.source_branch.clone(), mr.summary()))
+        .collect())
+}
+
+/// Fetch pipeline and merge state for all merge requests, keyed by source
+/// branch. When a branch has several MRs, the newest one is kept.
+pub fn list_mr_statuses() -> Result<HashMap<String, PrStatus>> {
+    let output = run_glab(&[
+        "mr",
+        "list",
+        "--all",
+        "--per-page",
+        "100",
+        "--output",
+        "json",
+    ])?
+    .ok_or_else(missing_glab)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to list merge requests: {}", stderr.trim()));
+    }
+    let mrs: Vec<MergeRequest> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    let mut statuses = HashMap::new();
+    for mr in mrs {
+        let status = mr.status();
+        statuses.entry(mr.source_branch).or_insert(status);
+    }
+    Ok(statuses)
+}
+
+/// Open a merge request in the browser
+pub fn view_mr_in_browser(mr_number: u32) -> Result<()> {
+    let number = mr_number.to_string();
+    let output = run_glab(&["mr", "view", &number, "--web"])?.ok_or_else(missing_glab)?;
+    if !output.status.success() {
+        return Err(anyhow!("Failed to open MR !{} in the browser", mr_number));
+    }
+    Ok(())
+}
+
+/// Merge a merge request with the given strategy. GitLab refuses while the
+/// pipeline or approvals block it.
+pub fn merge_mr(mr_number: u32, strategy: MergeStrategy) -> Result<()> {
+    let number = mr_number.to_string();
+    let mut args = vec!["mr", "merge", &number, "--yes"];
+    match strategy {
+        MergeStrategy::Merge => {}
+        MergeStrategy::Rebase => args.push("--rebase"),
+        MergeStrategy::Squash => args.push("--squash"),
+    }
+    let output = run_glab(&args)?.ok_or_else(missing_glab)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(mr = mr_number, stderr = %stderr, "gitlab:mr merge failed");
+        return Err(anyhow!(
+            "Failed to merge MR !{}: {}",
+            mr_number,
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Create a merge request for `source` into `target`.
+/// Returns the MR number and URL.
+pub fn create_mr(
+    source: &str,
+    target: &str,
+    title: &str,
+    description: &str,
+    draft: bool,
+) -> Result<(u32, String)> {
+    let mut args = vec![
+        "mr",
+        "create",
+        "--source-branch",
+        source,
+        "--target-branch",
+        target,
+        "--title",
+        title,
+        "--description",
+        description,
+        "--yes",
+    ];
+    if draft {
+        args.push("--draft");
+    }
+    let output = run_glab(&args)?.ok_or_else(missing_glab)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(source, stderr = %stderr, "gitlab:mr create failed");
+        return Err(anyhow!("Failed to create MR: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let url = stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| line.contains("/merge_requests/"))
+        .unwrap_or_default()
+        .to_string();
+    let number = parse_mr_number(&url)
+        .ok_or_else(|| anyhow!("Could not find the MR number in glab output: {}", stdout))?;
+    Ok((number, url))
+}
+
+/// Extract the MR number from a URL like https://gitlab.com/group/repo/-/merge_requests/42
+fn parse_mr_number(url: &str) -> Option<u32> {
+    let (_, number) = url.trim_end_matches('/').rsplit_once("/merge_requests/")?;
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_state_maps_to_github_states() {
+        assert_eq!(normalize_state("opened"), "OPEN");
+        assert_eq!(normalize_state("merged"), "MERGED");
+        assert_eq!(normalize_state("closed"), "CLOSED");
+        assert_eq!(normalize_state("locked"), "CLOSED");
+    }
+
+    #[test]
+    fn merge_request_summary_from_glab_json() {
+        let json = r#"{"iid": 7, "title": "Fix login", "state": "opened", "draft": true,
+            "source_branch": "fix-login", "source_project_id": 1, "target_project_id": 1,
+            "author": {"username": "dev"}}"#;
+        let mr: MergeRequest = serde_json::from_str(json).unwrap();
+        let summary = mr.summary();
+        assert_eq!(summary.number, 7);
+        assert_eq!(summary.state, "OPEN");
+        assert!(summary.is_draft);
+    }
+
+    #[test]
+    fn merge_request_status_maps_pipeline_and_conflicts() {
+        let json = r#"{"iid": 3, "title": "Add export", "state": "opened",
+            "source_branch": "export", "web_url": "https://gitlab.com/g/r/-/merge_requests/3",
+            "has_conflicts": true, "head_pipeline": {"status": "running"}}"#;
+        let mr: MergeRequest = serde_json::from_str(json).unwrap();
+        let status = mr.status();
+        assert_eq!(status.checks, ChecksState::Pending);
+        assert_eq!(status.mergeable, "CONFLICTING");
+        assert_eq!(status.review, None);
+    }
+
+    #[test]
+    fn parse_mr_number_from_url() {
+        assert_eq!(
+            parse_mr_number("https://gitlab.com/group/sub/repo/-/merge_requests/42"),
+            Some(42)
+        );
+        assert_eq!(parse_mr_number("https://gitlab.com/group/repo"), None);
+    }
+}
@@ -0,0 +1,265 @@
+//! Jujutsu (`jj`) operations used by the jj backend (see [`crate::vcs`]).
+//!
+//! A jj repository has one workspace per working copy, like git worktrees.
+//! The main workspace is `default`; workmux names the others after their
+//! handle. Branches are bookmarks.
+
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::cmd::Cmd;
+
+/// Name jj gives the workspace created with the repository
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// Root of the workspace containing the current directory
+pub fn workspace_root() -> Result<PathBuf> {
+    let root = Cmd::new("jj")
+        .args(&["workspace", "root"])
+        .run_and_capture_stdout()
+        .context("Not in a jj repository")?;
+    Ok(PathBuf::from(root))
+}
+
+/// Root of the repository's default workspace, also from inside another
+/// workspace
+pub fn main_workspace_root() -> Result<PathBuf> {
+    let root = workspace_root()?;
+    main_root_of(&root)
+}
+
+/// A secondary workspace's `.jj/repo` is a file holding the path of the
+/// default workspace's `.jj/repo` directory
+fn main_root_of(workspace_root: &Path) -> Result<PathBuf> {
+    let repo = workspace_root.join(".jj").join("repo");
+    if repo.is_dir() {
+        return Ok(workspace_root.to_path_buf());
+    }
+    let pointer = std::fs::read_to_string(&repo)
+        .with_context(|| format!("Failed to read {}", repo.display()))?;
+    let repo_dir = PathBuf::from(pointer.trim());
+    let repo_dir = if repo_dir.is_relative() {
+        repo.parent().unwrap_or(workspace_root).join(repo_dir)
+    } else {
+        repo_dir
+    };
+    repo_dir
+        .parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("Unexpected jj repo path '{}'", repo_dir.display()))
+}
+
+/// Where workmux keeps per-repository state: `.git` of a colocated
+/// repository, so it is shared with git commands, or `.jj/repo`
+pub fn state_dir() -> Result<PathBuf> {
+    let main_root = main_workspace_root()?;
+    let git_dir = main_root.join(".git");
+    if git_dir.is_dir() {
+        Ok(git_dir)
+    } else {
+        Ok(main_root.join(".jj").join("repo"))
+    }
+}
+
+/// Names of all workspaces, the default one first
+pub fn list_workspaces(main_root: &Path) -> Result<Vec<String>> {
+    let output = Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["workspace", "list"])
+        .run_and_capture_stdout()
+        .context("Failed to list jj workspaces")?;
+    Ok(parse_workspace_list(&output))
+}
+
+/// `jj workspace list` prints `<name>: <change> <commit> <description>`
+fn parse_workspace_list(output: &str) -> Vec<String> {
+    let mut names: Vec<String> = output
+        .lines()
+        .filter_map(|line| line.split_once(": ").map(|(name, _)| name.trim()))
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    if let Some(i) = names.iter().position(|n| n == DEFAULT_WORKSPACE) {
+        let default = names.remove(i);
+        names.insert(0, default);
+    }
+    names
+}
+
+/// Create a workspace named `name` at `path`, with a new working-copy change
+/// on top of `revision`
+pub fn add_workspace(main_root: &Path, path: &Path, name: &str, revision: &str) -> Result<()> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid workspace path"))?;
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["workspace", "add", "--name", name, "--revision", revision])
+        .arg(path_str)
+        .run()
+        .context("Failed to create jj workspace")?;
+    Ok(())
+}
+
+/// Stop tracking a workspace. Its directory is left alone.
+pub fn forget_workspace(main_root: &Path, name: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["workspace", "forget", name])
+        .run()
+        .context("Failed to forget jj workspace")?;
+    Ok(())
+}
+
+/// Whether a local bookmark exists
+pub fn bookmark_exists(main_root: &Path, name: &str) -> Result<bool> {
+    // Unknown names only produce a warning, so check the output
+    let output = Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["bookmark", "list", name])
+        .run_and_capture_stdout()
+        .context("Failed to list jj bookmarks")?;
+    Ok(output
+        .lines()
+        .any(|line| line.split_once(':').is_some_and(|(n, _)| n == name)))
+}
+
+/// Point a new bookmark at `revision`
+pub fn create_bookmark(main_root: &Path, name: &str, revision: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["bookmark", "create", name, "--revision", revision])
+        .run()
+        .with_context(|| format!("Failed to create bookmark '{}'", name))?;
+    Ok(())
+}
+
+/// Move a bookmark forward to `revision`
+pub fn set_bookmark(main_root: &Path, name: &str, revision: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["bookmark", "set", name, "--revision", revision])
+        .run()
+        .with_context(|| format!("Failed to move bookmark '{}'", name))?;
+    Ok(())
+}
+
+pub fn delete_bookmark(main_root: &Path, name: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["bookmark", "delete", name])
+        .run()
+        .with_context(|| format!("Failed to delete bookmark '{}'", name))?;
+    Ok(())
+}
+
+/// Whether the workspace's working-copy change has changes. jj records them
+/// automatically, but they haven't been described or moved past yet.
+pub fn has_working_copy_changes(workspace_path: &Path) -> Result<bool> {
+    let output = Cmd::new("jj")
+        .workdir(workspace_path)
+        .args(&["diff", "--summary"])
+        .run_and_capture_stdout()
+        .context("Failed to check jj working copy")?;
+    Ok(!output.is_empty())
+}
+
+/// IDs of the commits in `revset`
+pub fn commit_ids(main_root: &Path, revset: &str) -> Result<Vec<String>> {
+    let output = Cmd::new("jj")
+        .workdir(main_root)
+        .args(&[
+            "log",
+            "--no-graph",
+            "--revisions",
+            revset,
+            "--template",
+            "commit_id ++ \"\\n\"",
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to evaluate revset '{}'", revset))?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Non-empty changes of `workspace` that aren't in `base`
+pub fn unmerged_revset(workspace: &str, base: &str) -> String {
+    format!("({}..{}@) ~ empty()", symbol(base), workspace)
+}
+
+/// A bookmark name as a revset symbol, quoted so names like `feature/x`
+/// aren't parsed as operators
+pub fn symbol(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Rebase the changes of `workspace` that aren't in `destination` onto it
+pub fn rebase_workspace(main_root: &Path, workspace: &str, destination: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["rebase", "--branch", &format!("{}@", workspace)])
+        .args(&["--destination", destination])
+        .run()
+        .context("jj rebase failed")?;
+    Ok(())
+}
+
+/// ID of the latest operation, to restore after a failed merge
+pub fn current_operation(main_root: &Path) -> Result<String> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&[
+            "operation",
+            "log",
+            "--no-graph",
+            "--limit",
+            "1",
+            "--template",
+            "id",
+        ])
+        .run_and_capture_stdout()
+        .context("Failed to read the jj operation log")
+}
+
+/// Undo everything since `operation`
+pub fn restore_operation(main_root: &Path, operation: &str) -> Result<()> {
+    Cmd::new("jj")
+        .workdir(main_root)
+        .args(&["operation", "restore", operation])
+        .run()
+        .context("Failed to restore the jj operation")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_list_puts_default_first() {
+        let output = "fix-auth: kmkuslsw 5a1b2c3d Fix login\n\
+                      default: qpvuntsm 230dd059 (empty) (no description set)\n\
+                      docs: rlvkpnrz 8e9f0a1b (empty) (no description set)";
+        assert_eq!(
+            parse_workspace_list(output),
+            vec!["default", "fix-auth", "docs"]
+        );
+    }
+
+    #[test]
+    fn main_root_follows_repo_pointer() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("api");
+        let ws = dir.path().join("api__worktrees").join("fix-auth");
+        std::fs::create_dir_all(main.join(".jj/repo")).unwrap();
+        std::fs::create_dir_all(ws.join(".jj")).unwrap();
+        std::fs::write(
+            ws.join(".jj/repo"),
+            main.join(".jj/repo").display().to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(main_root_of(&main).unwrap(), main);
+        assert_eq!(main_root_of(&ws).unwrap(), main);
+    }
+}
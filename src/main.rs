@@ -1,35 +1,64 @@
+mod a11y;
+mod archive;
+mod checks;
 mod claude;
 mod cli;
 mod cmd;
 mod command;
 mod config;
+mod conflicts;
+mod daemon;
+mod duration;
+mod error;
+mod events;
+mod forge;
 mod git;
 mod github;
+mod gitlab;
+mod jj;
 mod llm;
 mod logger;
 mod markdown;
+mod merge_queue;
+mod metadata;
 mod naming;
+mod notification;
+mod nuon;
+mod pins;
+mod porcelain;
 mod prompt;
+mod registry;
 mod spinner;
+mod state;
 mod template;
 mod tmux;
+mod usage;
+mod vcs;
 mod workflow;
 
-use anyhow::Result;
+use std::process::ExitCode;
 use tracing::{error, info};
 
-fn main() -> Result<()> {
-    logger::init()?;
+fn main() -> ExitCode {
+    if let Err(err) = logger::init() {
+        eprintln!("Error: {:?}", err);
+        return ExitCode::FAILURE;
+    }
     info!(args = ?std::env::args().collect::<Vec<_>>(), "workmux start");
 
     match cli::run() {
-        Ok(result) => {
+        Ok(()) => {
             info!("workmux finished successfully");
-            Ok(result)
+            ExitCode::SUCCESS
         }
         Err(err) => {
             error!(error = ?err, "workmux failed");
-            Err(err)
+            if error::kind(&err) == Some(error::ErrorKind::Aborted) {
+                eprintln!("{}", err);
+            } else {
+                eprintln!("Error: {:?}", err);
+            }
+            ExitCode::from(error::exit_code(&err))
         }
     }
 }
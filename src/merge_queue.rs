@@ -0,0 +1,96 @@
+//! Merges queued from the dashboard to run once the agent is done.
+//!
+//! Queuing sets `@workmux_merge_queued` on the agent's window. Whatever is
+//! watching agent status (the daemon if one is running, otherwise the
+//! dashboard) starts `workmux merge` for queued agents that reached `done`.
+//! The merge runs inside the tmux server, so it finishes even if the watcher
+//! exits, and reports success or failure as a system notification.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::tmux::{self, AgentPane};
+
+/// Queued agents whose status is `done`, one per window
+pub fn due<'a>(agents: &'a [AgentPane], done_icon: &str) -> Vec<&'a AgentPane> {
+    let mut seen = HashSet::new();
+    agents
+        .iter()
+        .filter(|a| a.merge_queued && a.status.as_deref() == Some(done_icon))
+        .filter(|a| seen.insert((a.session.as_str(), a.window_name.as_str())))
+        .collect()
+}
+
+/// Start the merge of every queued agent that is done. Returns the handles
+/// whose merge was started.
+pub fn run_due(agents: &[AgentPane], config: &Config) -> Vec<String> {
+    let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("workmux"));
+    let command = format!(
+        "'{}' merge --notification",
+        exe.to_string_lossy().replace('\'', "'\\''")
+    );
+
+    let mut started = Vec::new();
+    for agent in due(agents, config.status_icons.done()) {
+        let handle = agent
+            .window_name
+            .strip_prefix(config.window_prefix())
+            .unwrap_or(&agent.window_name)
+            .to_string();
+        // Unqueue first, so the next refresh doesn't start a second merge
+        if let Err(e) = tmux::set_merge_queued(&agent.pane_id, false) {
+            warn!(handle, error = %e, "merge_queue:failed to unqueue");
+            continue;
+        }
+        // merge resolves the worktree from its working directory
+        match tmux::run_shell_detached(&command, &agent.path) {
+            Ok(()) => {
+                info!(handle, "merge_queue:started merge");
+                started.push(handle);
+            }
+            Err(e) => warn!(handle, error = %e, "merge_queue:failed to start merge"),
+        }
+    }
+    started
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(pane_id: &str, window_name: &str, status: &str, merge_queued: bool) -> AgentPane {
+        AgentPane {
+            session: "main".to_string(),
+            window_name: window_name.to_string(),
+            pane_id: pane_id.to_string(),
+            path: PathBuf::from("/tmp/repo__worktrees").join(window_name),
+            pane_title: None,
+            status: Some(status.to_string()),
+            status_ts: None,
+            group: None,
+            agent: None,
+            merge_queued,
+            exited: false,
+            snoozed_until: None,
+            usage: Default::default(),
+        }
+    }
+
+    #[test]
+    fn due_requires_queued_and_done() {
+        let agents = vec![
+            agent("%1", "wm-a", "✅", true),
+            agent("%2", "wm-b", "🤖", true),
+            agent("%3", "wm-c", "✅", false),
+            // Second agent pane in an already due window
+            agent("%4", "wm-a", "✅", true),
+        ];
+        let due: Vec<&str> = due(&agents, "✅")
+            .into_iter()
+            .map(|a| a.pane_id.as_str())
+            .collect();
+        assert_eq!(due, vec!["%1"]);
+    }
+}
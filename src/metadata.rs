@@ -0,0 +1,210 @@
+//! Per-worktree metadata kept in the repository.
+//!
+//! Each worktree created by workmux gets a JSON file at
+//! `<git common dir>/workmux/worktrees/<handle>.json` recording how it was
+//! created: when, from which base, with which agent, prompt and setup steps,
+//! and the PR or issue it is linked to. Living in the git directory, it is shared by all
+//! worktrees and goes away with the repository. jj repositories that aren't
+//! colocated with git keep it under `.jj/repo` instead.
+//!
+//! The base, PR, and issue are also stored in per-branch git config, which
+//! remains what `merge` and `remove` read. Worktrees created before the store
+//! existed have no file, so readers fall back to git config for those fields
+//! (see [`load_or_infer`]).
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::state::StateFile;
+use crate::{git, jj};
+
+const METADATA_FILE: StateFile = StateFile {
+    name: "worktree metadata",
+    migrations: &[],
+};
+
+/// How and when a worktree was created
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorktreeMetadata {
+    pub branch: String,
+    /// Unix timestamp of `workmux add`
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    /// Branch or commit the worktree was created from
+    #[serde(default)]
+    pub base: Option<String>,
+    /// Commit the base pointed at, refreshed by `workmux sync`
+    #[serde(default)]
+    pub base_sha: Option<String>,
+    /// Agent command the worktree was started with
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Prompt the agent was started with
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Prompt template the prompt was rendered from (`--prompt-template`)
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Custom `--var` values the template was rendered with
+    #[serde(default)]
+    pub template_vars: BTreeMap<String, String>,
+    /// Monorepo package the worktree is scoped to (`--package`)
+    #[serde(default)]
+    pub package: Option<String>,
+    /// Setup steps that ran, None for worktrees recorded before they were kept
+    #[serde(default)]
+    pub setup: Option<SetupSteps>,
+    /// PR opened with `pr create` or checked out with `add --pr`
+    #[serde(default)]
+    pub pr: Option<u32>,
+    /// Issue the worktree was started from (`add --from-issue`)
+    #[serde(default)]
+    pub issue: Option<u32>,
+    /// Handle of the worktree this one is stacked on (`add --stack`)
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Pane layout the window was created with (`add --layout`)
+    #[serde(default)]
+    pub layout: Option<String>,
+}
+
+/// Which setup steps `workmux add` ran (`--no-hooks`, `--no-file-ops` and
+/// `--no-pane-cmds` turn them off)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SetupSteps {
+    pub hooks: bool,
+    pub file_ops: bool,
+    pub pane_commands: bool,
+}
+
+fn metadata_dir() -> Result<PathBuf> {
+    let state_dir = git::get_git_common_dir().or_else(|_| jj::state_dir())?;
+    Ok(state_dir.join("workmux").join("worktrees"))
+}
+
+/// Metadata directory of the git repository at `workdir`, for readers such as
+/// the dashboard that see worktrees of several repositories
+pub fn dir_in(workdir: &Path) -> Option<PathBuf> {
+    let state_dir = git::get_git_common_dir_in(Some(workdir)).ok()?;
+    Some(state_dir.join("workmux").join("worktrees"))
+}
+
+fn metadata_path(dir: &Path, handle: &str) -> PathBuf {
+    dir.join(format!("{}.json", handle))
+}
+
+/// Metadata recorded for a worktree, if any
+pub fn load(handle: &str) -> Option<WorktreeMetadata> {
+    load_from(&metadata_dir().ok()?, handle)
+}
+
+/// Metadata recorded for a worktree in `dir` (see [`dir_in`]), if any
+pub fn load_from(dir: &Path, handle: &str) -> Option<WorktreeMetadata> {
+    METADATA_FILE.load_from(&metadata_path(dir, handle))
+}
+
+/// Metadata for all worktrees of the repository, keyed by handle
+pub fn load_all() -> HashMap<String, WorktreeMetadata> {
+    let Ok(entries) = metadata_dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let handle = path.file_stem()?.to_str()?.to_string();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some((handle, METADATA_FILE.load_from(&path)?))
+        })
+        .collect()
+}
+
+pub fn save(handle: &str, metadata: &WorktreeMetadata) -> Result<()> {
+    let dir = metadata_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    METADATA_FILE.save_to(&metadata_path(&dir, handle), metadata)
+}
+
+/// Change a worktree's recorded metadata. Worktrees without a file (the main
+/// worktree, or ones created before the store) are left alone. Failures are
+/// logged, never fatal: the metadata only adds context.
+pub fn update(handle: &str, change: impl FnOnce(&mut WorktreeMetadata)) {
+    let Some(mut metadata) = load(handle) else {
+        return;
+    };
+    change(&mut metadata);
+    if let Err(e) = save(handle, &metadata) {
+        debug!(handle, error = %e, "metadata:failed to save");
+    }
+}
+
+/// Forget a removed worktree
+pub fn delete(handle: &str) {
+    if let Ok(dir) = metadata_dir() {
+        let path = metadata_path(&dir, handle);
+        if path.exists()
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            debug!(handle, error = %e, "metadata:failed to delete");
+        }
+    }
+}
+
+/// Recorded metadata, with fields missing from it filled in from the
+/// per-branch git config
+pub fn load_or_infer(handle: &str, branch: &str) -> WorktreeMetadata {
+    let mut metadata = load(handle).unwrap_or_default();
+    metadata.branch = branch.to_string();
+    if git::is_detached(branch) {
+        return metadata;
+    }
+    if metadata.base.is_none() {
+        metadata.base = git::get_branch_base(branch).ok();
+    }
+    if metadata.base_sha.is_none() {
+        metadata.base_sha = git::get_branch_base_sha(branch);
+    }
+    if metadata.pr.is_none() {
+        metadata.pr = git::get_branch_pr_in(branch, None);
+    }
+    if metadata.issue.is_none() {
+        metadata.issue = git::get_branch_issue_in(branch, None);
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_round_trips_through_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = metadata_path(dir.path(), "fix-login");
+        let metadata = WorktreeMetadata {
+            branch: "fix-login".to_string(),
+            created_at: Some(1_700_000_000),
+            base: Some("main".to_string()),
+            agent: Some("claude".to_string()),
+            prompt: Some("Fix the login redirect".to_string()),
+            ..Default::default()
+        };
+        METADATA_FILE.save_to(&path, &metadata).unwrap();
+        assert_eq!(METADATA_FILE.load_from(&path), Some(metadata));
+    }
+
+    #[test]
+    fn metadata_tolerates_missing_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = metadata_path(dir.path(), "old");
+        std::fs::write(&path, r#"{"version": 0, "data": {"branch": "old"}}"#).unwrap();
+        let metadata: WorktreeMetadata = METADATA_FILE.load_from(&path).unwrap();
+        assert_eq!(metadata.branch, "old");
+        assert_eq!(metadata.created_at, None);
+    }
+}
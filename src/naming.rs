@@ -1,5 +1,6 @@
-use anyhow::{Result, bail};
+use anyhow::{Result, anyhow, bail};
 use slug::slugify;
+use std::path::{Component, Path};
 
 use crate::config::Config;
 
@@ -16,6 +17,21 @@ pub fn derive_handle(
     branch_name: &str,
     explicit_name: Option<&str>,
     config: &Config,
+) -> Result<String> {
+    derive_scoped_handle(branch_name, explicit_name, None, config)
+}
+
+/// Like [`derive_handle`], but for worktrees scoped to a monorepo package
+/// (`add --package`).
+///
+/// The package's directory name is prepended to the derived name, so
+/// `packages/api` with branch `fix-auth` becomes `api-fix-auth`. The
+/// worktree_prefix still comes first, and an explicit name still wins.
+pub fn derive_scoped_handle(
+    branch_name: &str,
+    explicit_name: Option<&str>,
+    package: Option<&str>,
+    config: &Config,
 ) -> Result<String> {
     let handle = if let Some(name) = explicit_name {
         // Explicit --name takes priority and bypasses prefix
@@ -24,6 +40,12 @@ pub fn derive_handle(
         // Apply naming strategy
         let derived = config.worktree_naming.derive_name(branch_name);
 
+        // Scope to the package directory name if given
+        let derived = match package.and_then(|p| Path::new(p).file_name()) {
+            Some(package_name) => format!("{}-{}", package_name.to_string_lossy(), derived),
+            None => derived,
+        };
+
         // Apply prefix if configured
         let with_prefix = if let Some(ref prefix) = config.worktree_prefix {
             format!("{}{}", prefix, derived)
@@ -38,6 +60,79 @@ pub fn derive_handle(
     Ok(handle)
 }
 
+/// Normalizes a `--package` path to a clean path relative to the repo root.
+///
+/// Strips leading `./` and trailing slashes, and rejects absolute paths or
+/// paths that escape the repository.
+pub fn normalize_package_path(package: &str) -> Result<String> {
+    let mut parts = Vec::new();
+    for component in Path::new(package.trim()).components() {
+        match component {
+            Component::Normal(part) => parts.push(
+                part.to_str()
+                    .ok_or_else(|| anyhow!("Package path contains non-UTF8 characters"))?,
+            ),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                bail!(
+                    "Package path '{}' must be relative to the repository root",
+                    package
+                );
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        bail!("Package path cannot be empty");
+    }
+
+    Ok(parts.join("/"))
+}
+
+/// Longest title slug used in a branch name derived from an issue
+const ISSUE_SLUG_MAX_LEN: usize = 40;
+
+/// Derives a branch name from an issue (`add --from-issue`), e.g. issue 42
+/// "Fix login redirect on Safari" becomes `42-fix-login-redirect-on-safari`.
+///
+/// Long titles are cut at a word boundary so branch names stay readable.
+pub fn branch_name_from_issue(number: u32, title: &str) -> String {
+    let slug = slugify(title);
+    let mut end = 0;
+    for (i, _) in slug.match_indices('-').chain([(slug.len(), "")]) {
+        if i > ISSUE_SLUG_MAX_LEN {
+            break;
+        }
+        end = i;
+    }
+    // A single word longer than the limit is cut mid-word
+    let slug = if end == 0 {
+        &slug[..slug.len().min(ISSUE_SLUG_MAX_LEN)]
+    } else {
+        &slug[..end]
+    };
+    if slug.is_empty() {
+        format!("issue-{}", number)
+    } else {
+        format!("{}-{}", number, slug)
+    }
+}
+
+/// Appends `date` (e.g. `0212`) to a new branch name for the `date-suffix`
+/// naming strategy, then a counter if that name is `taken` too, so
+/// `deps-update` becomes `deps-update-0212`, or `deps-update-0212-2` when it
+/// already ran that day.
+pub fn date_suffixed(branch: &str, date: &str, taken: impl Fn(&str) -> bool) -> String {
+    let dated = format!("{}-{}", branch, date);
+    if !taken(&dated) {
+        return dated;
+    }
+    (2..)
+        .map(|n| format!("{}-{}", dated, n))
+        .find(|name| !taken(name))
+        .expect("counter is unbounded")
+}
+
 /// Validates that a handle is safe for filesystem and tmux use.
 fn validate_handle(handle: &str) -> Result<()> {
     if handle.is_empty() {
@@ -256,4 +351,94 @@ mod tests {
     fn worktree_naming_basename_simple_branch() {
         assert_eq!(WorktreeNaming::Basename.derive_name("main"), "main");
     }
+
+    // === Package scoping tests ===
+
+    #[test]
+    fn derive_scoped_handle_prepends_package_name() {
+        let result =
+            derive_scoped_handle("fix-auth", None, Some("packages/api"), &default_config())
+                .unwrap();
+        assert_eq!(result, "api-fix-auth");
+    }
+
+    #[test]
+    fn derive_scoped_handle_prefix_comes_first() {
+        let result = derive_scoped_handle(
+            "prj/feature",
+            None,
+            Some("apps/web"),
+            &config_with_basename_and_prefix("wt-"),
+        )
+        .unwrap();
+        assert_eq!(result, "wt-web-feature");
+    }
+
+    #[test]
+    fn derive_scoped_handle_explicit_name_wins() {
+        let result = derive_scoped_handle(
+            "feature",
+            Some("custom"),
+            Some("packages/api"),
+            &default_config(),
+        )
+        .unwrap();
+        assert_eq!(result, "custom");
+    }
+
+    #[test]
+    fn normalize_package_path_cleans_input() {
+        assert_eq!(
+            normalize_package_path("./packages/api/").unwrap(),
+            "packages/api"
+        );
+        assert_eq!(normalize_package_path("libs").unwrap(), "libs");
+    }
+
+    #[test]
+    fn normalize_package_path_rejects_escaping_paths() {
+        assert!(normalize_package_path("/abs/path").is_err());
+        assert!(normalize_package_path("../other").is_err());
+        assert!(normalize_package_path("packages/../../x").is_err());
+        assert!(normalize_package_path("./").is_err());
+    }
+
+    #[test]
+    fn branch_name_from_issue_slugifies_and_truncates_titles() {
+        assert_eq!(
+            branch_name_from_issue(42, "Fix login redirect on Safari!"),
+            "42-fix-login-redirect-on-safari"
+        );
+        assert_eq!(
+            branch_name_from_issue(
+                7,
+                "Dashboard: show cost per agent and total cost across all sessions"
+            ),
+            "7-dashboard-show-cost-per-agent-and-total"
+        );
+        assert_eq!(branch_name_from_issue(3, "!!!"), "issue-3");
+    }
+
+    #[test]
+    fn date_suffixed_appends_the_date_then_a_counter() {
+        assert_eq!(
+            date_suffixed("deps-update", "0212", |_| false),
+            "deps-update-0212"
+        );
+        let taken = ["deps-update-0212", "deps-update-0212-2"];
+        assert_eq!(
+            date_suffixed("deps-update", "0212", |name| taken.contains(&name)),
+            "deps-update-0212-3"
+        );
+    }
+
+    #[test]
+    fn date_suffix_strategy_keeps_the_full_branch_name() {
+        assert_eq!(
+            WorktreeNaming::DateSuffix.derive_name("chore/deps-update-0212"),
+            "chore/deps-update-0212"
+        );
+        let parsed: WorktreeNaming = serde_yaml::from_str("date-suffix").unwrap();
+        assert_eq!(parsed, WorktreeNaming::DateSuffix);
+    }
 }
@@ -0,0 +1,173 @@
+//! Desktop notifications for merges and agent status transitions, and
+//! status change reports to a webhook or command.
+
+use serde::Serialize;
+use std::process::{Command, Stdio};
+
+use crate::cmd;
+use crate::config::{NotificationConfig, StatusChangeConfig};
+
+/// Shows a system notification on macOS or Linux
+pub fn show(message: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        use mac_notification_sys::{Notification, set_application};
+        // Set application to Terminal to use its icon
+        if let Err(e) = set_application("com.apple.Terminal") {
+            tracing::debug!("Failed to set notification application: {:?}", e);
+        }
+        if let Err(e) = Notification::default()
+            .title("workmux")
+            .message(message)
+            .send()
+        {
+            tracing::debug!("Failed to send notification: {:?}", e);
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        if let Err(e) = notify_rust::Notification::new()
+            .summary("workmux")
+            .body(message)
+            .show()
+        {
+            tracing::debug!("Failed to send notification: {:?}", e);
+        }
+    }
+}
+
+/// Notify that an agent moved into the `waiting` or `done` status.
+///
+/// `elapsed_secs` is how long the agent spent in its previous status.
+/// Uses the configured `notifications.command` if set, otherwise the
+/// built-in system notification.
+pub fn notify_status_change(
+    config: &NotificationConfig,
+    handle: &str,
+    status: &str,
+    elapsed_secs: Option<u64>,
+) {
+    if !config.enabled_for(status) {
+        return;
+    }
+
+    let message = status_message(handle, status, elapsed_secs);
+    match config.command.as_deref() {
+        Some(command) => {
+            let elapsed = elapsed_secs.map(|s| s.to_string()).unwrap_or_default();
+            let workdir = std::env::current_dir().unwrap_or_else(|_| "/".into());
+            let env = [
+                ("WM_HANDLE", handle),
+                ("WM_STATUS", status),
+                ("WM_ELAPSED_SECS", elapsed.as_str()),
+                ("WM_MESSAGE", message.as_str()),
+            ];
+            if let Err(e) = cmd::shell_command_with_env(command, &workdir, &env) {
+                tracing::debug!("Failed to run notification command: {:?}", e);
+            }
+        }
+        None => show(&message),
+    }
+}
+
+/// An agent status change, as POSTed to `status_change.webhook`
+#[derive(Debug, Serialize)]
+pub struct StatusChange<'a> {
+    pub handle: &'a str,
+    pub project: &'a str,
+    pub status: &'a str,
+    /// None when the agent had no status yet
+    pub previous_status: Option<&'a str>,
+    /// Time spent in the previous status
+    pub elapsed_secs: Option<u64>,
+    pub timestamp: u64,
+    pub pane_id: &'a str,
+    pub window: &'a str,
+    /// Summary for chat webhooks like Slack's, which show the `text` field
+    pub text: String,
+}
+
+/// Report a status change to the configured webhook and command. Both run
+/// in the background, so the agent hook calling `set-window-status` isn't
+/// held up by a slow endpoint; failures are only logged.
+pub fn report_status_change(config: &StatusChangeConfig, change: &StatusChange) {
+    if config.webhook.is_none() && config.command.is_none() {
+        return;
+    }
+    let payload = match serde_json::to_string(change) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::debug!("Failed to serialize status change: {:?}", e);
+            return;
+        }
+    };
+
+    if let Some(url) = config.webhook.as_deref() {
+        let spawned = Command::new("curl")
+            .args(["-fsS", "--max-time", "10", "-X", "POST"])
+            .args(["-H", "Content-Type: application/json"])
+            .args(["--data-binary", &payload, url])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Err(e) = spawned {
+            tracing::debug!("Failed to post status change: {:?}", e);
+        }
+    }
+
+    if let Some(command) = config.command.as_deref() {
+        let elapsed = change
+            .elapsed_secs
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+        let spawned = cmd::shell(command)
+            .env("WM_HANDLE", change.handle)
+            .env("WM_PROJECT", change.project)
+            .env("WM_STATUS", change.status)
+            .env("WM_PREVIOUS_STATUS", change.previous_status.unwrap_or(""))
+            .env("WM_ELAPSED_SECS", elapsed)
+            .env("WM_PAYLOAD", &payload)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Err(e) = spawned {
+            tracing::debug!("Failed to run status change command: {:?}", e);
+        }
+    }
+}
+
+/// Build the notification text, e.g. "fix-auth is waiting for input (after 00:12:00)"
+pub fn status_message(handle: &str, status: &str, elapsed_secs: Option<u64>) -> String {
+    let what = match status {
+        "working" => "is working",
+        "waiting" => "is waiting for input",
+        "done" => "is done",
+        other => other,
+    };
+    match elapsed_secs {
+        Some(secs) => format!(
+            "{} {} (after {})",
+            handle,
+            what,
+            crate::duration::format_duration(secs)
+        ),
+        None => format!("{} {}", handle, what),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_message_includes_handle_and_elapsed() {
+        assert_eq!(
+            status_message("fix-auth", "waiting", Some(20 * 60)),
+            "fix-auth is waiting for input (after 00:20:00)"
+        );
+        assert_eq!(status_message("fix-auth", "done", None), "fix-auth is done");
+    }
+}
@@ -0,0 +1,132 @@
+//! Nushell object notation, so `workmux list --format nuon | from nuon` gives
+//! nushell records instead of text to parse.
+//!
+//! Values go through `serde_json::Value`, so anything printed with `--json`
+//! can be printed as NUON with the same field names (in alphabetical order).
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Serialize `value` as indented NUON
+pub fn to_string<T: Serialize>(value: &T) -> Result<String> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&mut out, &value, 0);
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            let s = n.to_string();
+            out.push_str(&s);
+            // `1` would read back as an int
+            if n.is_f64() && !s.contains(['.', 'e', 'E']) {
+                out.push_str(".0");
+            }
+        }
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) if items.is_empty() => out.push_str("[]"),
+        Value::Array(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                indent(out, depth + 1);
+                write_value(out, item, depth + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            indent(out, depth);
+            out.push(']');
+        }
+        Value::Object(fields) if fields.is_empty() => out.push_str("{}"),
+        Value::Object(fields) => {
+            out.push_str("{\n");
+            for (i, (key, field)) in fields.iter().enumerate() {
+                indent(out, depth + 1);
+                if is_bare_key(key) {
+                    out.push_str(key);
+                } else {
+                    write_string(out, key);
+                }
+                out.push_str(": ");
+                write_value(out, field, depth + 1);
+                out.push_str(if i + 1 < fields.len() { ",\n" } else { "\n" });
+            }
+            indent(out, depth);
+            out.push('}');
+        }
+    }
+}
+
+fn indent(out: &mut String, depth: usize) {
+    out.push_str(&"  ".repeat(depth));
+}
+
+/// Keys that can be written without quotes
+fn is_bare_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A double-quoted string. Nushell spells other control characters
+/// `\u{...}`, not JSON's `\uXXXX`.
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn records_and_lists() {
+        let value = json!([
+            {"handle": "fix-auth", "pr": null, "ahead": 2, "dirty": true},
+            {"handle": "docs", "pr": 7, "ahead": 0, "dirty": false},
+        ]);
+        assert_eq!(
+            to_string(&value).unwrap(),
+            "[\n  {\n    ahead: 2,\n    dirty: true,\n    handle: \"fix-auth\",\n    pr: null\n  },\n  \
+             {\n    ahead: 0,\n    dirty: false,\n    handle: \"docs\",\n    pr: 7\n  }\n]"
+        );
+        assert_eq!(
+            to_string(&json!({"a": [], "b": {}})).unwrap(),
+            "{\n  a: [],\n  b: {}\n}"
+        );
+    }
+
+    #[test]
+    fn quotes_keys_and_escapes_strings() {
+        assert_eq!(
+            to_string(&json!({"two words": "say \"hi\"\n\u{1b}[0m\\"})).unwrap(),
+            "{\n  \"two words\": \"say \\\"hi\\\"\\n\\u{1b}[0m\\\\\"\n}"
+        );
+    }
+
+    #[test]
+    fn floats_keep_a_decimal_point() {
+        assert_eq!(to_string(&json!(1.0)).unwrap(), "1.0");
+        assert_eq!(to_string(&json!(0.25)).unwrap(), "0.25");
+    }
+}
@@ -0,0 +1,33 @@
+//! Agents pinned to the top of the dashboard (`*`).
+//!
+//! Kept in `~/.cache/workmux/pins.json` as a list of worktree paths, like the
+//! checks file, so pins survive closing the dashboard or the agent's window
+//! and work for agents of any repository.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::state::StateFile;
+
+const PINS_FILE: StateFile = StateFile {
+    name: "pins.json",
+    migrations: &[],
+};
+
+/// Paths of the pinned worktrees
+pub fn load() -> BTreeSet<PathBuf> {
+    PINS_FILE.load().unwrap_or_default()
+}
+
+/// Pin or unpin a worktree, forgetting pinned worktrees that no longer exist
+pub fn set_pinned(path: &Path, pinned: bool) -> Result<()> {
+    let mut pins = load();
+    pins.retain(|p| p.exists());
+    if pinned {
+        pins.insert(path.to_path_buf());
+    } else {
+        pins.remove(path);
+    }
+    PINS_FILE.save(&pins)
+}
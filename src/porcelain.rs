@@ -0,0 +1,47 @@
+//! Stable, script-friendly output (`--porcelain`).
+//!
+//! Like git's porcelain formats, this output is a contract rather than a
+//! presentation: one line per item, tab-separated fields in a fixed order,
+//! no colors, icons, or headers, and nothing printed when there is nothing to
+//! list. New fields are only ever appended, so parsers that split on tabs and
+//! read fields by position keep working.
+
+/// Stands in for a missing value, so fields keep their position
+pub const NONE: &str = "-";
+
+/// One item as tab-separated fields. Tabs and line breaks inside a value are
+/// replaced by spaces, and empty values by [`NONE`].
+pub fn line<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| match field.as_ref() {
+            "" => NONE.to_string(),
+            value => value.replace(['\t', '\n', '\r'], " "),
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// A flag as `1` or `0`
+pub fn flag(value: bool) -> &'static str {
+    if value { "1" } else { "0" }
+}
+
+/// An optional value, or [`NONE`]
+pub fn opt(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| NONE.to_string(), |v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_keeps_one_field_per_value() {
+        assert_eq!(
+            line(&["fix-auth", "", "a\tb\nc", &opt(Some(3)), &opt(None::<u64>)]),
+            "fix-auth\t-\ta b c\t3\t-"
+        );
+        assert_eq!(flag(true), "1");
+    }
+}
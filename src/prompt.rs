@@ -144,6 +144,76 @@ pub fn foreach_from_frontmatter(
     Ok(rows)
 }
 
+/// Variables every prompt template can use, filled in per worktree
+pub const TEMPLATE_BUILTIN_VARS: &[&str] = &["branch", "base", "issue_title", "handle"];
+
+/// Replace `{name}` placeholders in a prompt template with their values.
+///
+/// Only names present in `vars` are replaced, so other braces (code samples,
+/// JSON) pass through untouched. A built-in variable with no value here, such
+/// as `{issue_title}` without `--from-issue`, is an error rather than being
+/// sent to the agent verbatim.
+pub fn substitute_template_vars(text: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut missing: Vec<&str> = Vec::new();
+    let mut rest = text;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let name = after
+            .find('}')
+            .map(|close| &after[..close])
+            .filter(|name| is_var_name(name));
+        match name {
+            Some(name) => {
+                match vars.get(name) {
+                    Some(value) => out.push_str(value),
+                    None => {
+                        if TEMPLATE_BUILTIN_VARS.contains(&name) && !missing.contains(&name) {
+                            missing.push(name);
+                        }
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                }
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+
+    if !missing.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Prompt template uses {} but no value is available here{}",
+            missing
+                .iter()
+                .map(|name| format!("{{{}}}", name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            if missing.contains(&"issue_title") {
+                " ({issue_title} needs --from-issue)"
+            } else {
+                ""
+            }
+        ));
+    }
+    Ok(out)
+}
+
+/// Whether `name` can be a template variable (letters, digits, `_`, `-`)
+pub fn is_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,4 +368,32 @@ mod tests {
                 .contains("at least one value")
         );
     }
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitute_template_vars_replaces_known_names_only() {
+        let text = "Work on {branch} ({ticket}).\nfn f() { {x} }\n{{ num }} {unclosed";
+        let result =
+            substitute_template_vars(text, &vars(&[("branch", "feat/{a}"), ("ticket", "T-1")]))
+                .unwrap();
+        assert_eq!(
+            result,
+            "Work on feat/{a} (T-1).\nfn f() { {x} }\n{{ num }} {unclosed"
+        );
+    }
+
+    #[test]
+    fn substitute_template_vars_rejects_builtins_without_value() {
+        let err = substitute_template_vars("Fix {issue_title} on {branch}", &vars(&[]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("{issue_title}, {branch}"), "{}", err);
+        assert!(err.contains("--from-issue"), "{}", err);
+    }
 }
@@ -0,0 +1,208 @@
+//! Registry of workmux worktrees across repositories.
+//!
+//! Live agents are discovered from tmux pane options, which disappear when a
+//! window is closed. The registry remembers every worktree `workmux add` or
+//! `open` created a window for, and every worktree an agent was seen in, so
+//! the dashboard can list the ones without an agent as "offline" and reopen or
+//! remove them. Entries are dropped when the worktree is removed, or once its
+//! directory no longer exists.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::state::{self, StateFile};
+use crate::tmux::AgentPane;
+
+const REGISTRY_FILE: StateFile = StateFile {
+    name: "worktree_registry.json",
+    migrations: &[state::adopt_unversioned],
+};
+
+/// A worktree that workmux opened a window for or saw an agent in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredWorktree {
+    /// Worktree path (the directory its panes start in)
+    pub path: PathBuf,
+    /// Tmux session the agent was last seen in
+    pub session: String,
+    /// Window name the agent was last seen in (e.g., wm-feature-auth)
+    pub window_name: String,
+}
+
+/// Load the registry, returning an empty list if it is missing or unreadable
+pub fn load() -> Vec<RegisteredWorktree> {
+    REGISTRY_FILE.load().unwrap_or_default()
+}
+
+fn save(entries: &[RegisteredWorktree]) -> Result<()> {
+    REGISTRY_FILE.save(&entries)
+}
+
+/// Record the worktrees of the given live agents and return the updated registry.
+///
+/// Worktrees whose directory no longer exists are pruned. The file is only
+/// rewritten when something changed.
+pub fn record(agents: &[AgentPane]) -> Vec<RegisteredWorktree> {
+    let mut entries = load();
+    if update(&mut entries, agents, Path::exists)
+        && let Err(e) = save(&entries)
+    {
+        tracing::debug!(error = %e, "registry:failed to save");
+    }
+    entries
+}
+
+/// Record a worktree whose window was just created, so it stays listed
+/// before any agent runs in it
+pub fn register(path: &Path, session: &str, window_name: &str) {
+    let mut entries = load();
+    let before = entries.clone();
+    upsert(
+        &mut entries,
+        RegisteredWorktree {
+            path: path.to_path_buf(),
+            session: session.to_string(),
+            window_name: window_name.to_string(),
+        },
+    );
+    if entries != before
+        && let Err(e) = save(&entries)
+    {
+        tracing::debug!(error = %e, "registry:failed to save");
+    }
+}
+
+/// Forget a removed worktree, including entries for directories inside it
+pub fn forget(worktree_path: &Path) {
+    let mut entries = load();
+    // Entries hold resolved paths; the worktree itself may already be gone
+    let resolved = worktree_path
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .zip(worktree_path.file_name())
+        .map(|(parent, name)| parent.join(name));
+    let removed = remove_under(&mut entries, worktree_path)
+        | resolved.is_some_and(|path| remove_under(&mut entries, &path));
+    if removed && let Err(e) = save(&entries) {
+        tracing::debug!(error = %e, "registry:failed to save");
+    }
+}
+
+/// Merge live agents into `entries` and prune missing worktrees.
+/// Returns true if the entries changed.
+fn update(
+    entries: &mut Vec<RegisteredWorktree>,
+    agents: &[AgentPane],
+    exists: impl Fn(&Path) -> bool,
+) -> bool {
+    let before = entries.clone();
+    for agent in agents {
+        upsert(
+            entries,
+            RegisteredWorktree {
+                path: agent.path.clone(),
+                session: agent.session.clone(),
+                window_name: agent.window_name.clone(),
+            },
+        );
+    }
+    entries.retain(|e| exists(&e.path));
+    *entries != before
+}
+
+/// Add an entry, or refresh the session and window of the one at its path
+fn upsert(entries: &mut Vec<RegisteredWorktree>, seen: RegisteredWorktree) {
+    match entries.iter_mut().find(|e| e.path == seen.path) {
+        Some(entry) => *entry = seen,
+        None => entries.push(seen),
+    }
+}
+
+/// Drop entries at or under `path`. Returns true if any were dropped.
+fn remove_under(entries: &mut Vec<RegisteredWorktree>, path: &Path) -> bool {
+    let len = entries.len();
+    entries.retain(|e| !e.path.starts_with(path));
+    entries.len() != len
+}
+
+/// Registered worktrees that currently have no live agent pane
+pub fn offline(entries: Vec<RegisteredWorktree>, agents: &[AgentPane]) -> Vec<RegisteredWorktree> {
+    let live: HashSet<&Path> = agents.iter().map(|a| a.path.as_path()).collect();
+    entries
+        .into_iter()
+        .filter(|e| !live.contains(e.path.as_path()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(path: &str, window_name: &str) -> AgentPane {
+        AgentPane {
+            session: "main".to_string(),
+            window_name: window_name.to_string(),
+            pane_id: "%1".to_string(),
+            path: PathBuf::from(path),
+            pane_title: None,
+            status: None,
+            status_ts: None,
+            group: None,
+            agent: None,
+            merge_queued: false,
+            exited: false,
+            snoozed_until: None,
+            usage: Default::default(),
+        }
+    }
+
+    fn entry(path: &str, window_name: &str) -> RegisteredWorktree {
+        RegisteredWorktree {
+            path: PathBuf::from(path),
+            session: "main".to_string(),
+            window_name: window_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn update_adds_new_worktrees_and_refreshes_window_names() {
+        let mut entries = vec![entry("/wt/a", "wm-old")];
+        let agents = [agent("/wt/a", "wm-a"), agent("/wt/b", "wm-b")];
+        assert!(update(&mut entries, &agents, |_| true));
+        assert_eq!(
+            entries,
+            vec![entry("/wt/a", "wm-a"), entry("/wt/b", "wm-b")]
+        );
+
+        // Seeing the same agents again is not a change
+        assert!(!update(&mut entries, &agents, |_| true));
+    }
+
+    #[test]
+    fn update_prunes_missing_worktrees() {
+        let mut entries = vec![entry("/wt/a", "wm-a"), entry("/wt/gone", "wm-gone")];
+        assert!(update(&mut entries, &[], |p| p != Path::new("/wt/gone")));
+        assert_eq!(entries, vec![entry("/wt/a", "wm-a")]);
+    }
+
+    #[test]
+    fn remove_under_drops_the_worktree_and_its_subdirectories() {
+        let mut entries = vec![
+            entry("/wt/a", "wm-a"),
+            entry("/wt/a/packages/web", "wm-a"),
+            entry("/wt/ab", "wm-ab"),
+        ];
+        assert!(remove_under(&mut entries, Path::new("/wt/a")));
+        assert_eq!(entries, vec![entry("/wt/ab", "wm-ab")]);
+        assert!(!remove_under(&mut entries, Path::new("/wt/a")));
+    }
+
+    #[test]
+    fn offline_excludes_worktrees_with_live_agents() {
+        let entries = vec![entry("/wt/a", "wm-a"), entry("/wt/b", "wm-b")];
+        let offline = offline(entries, &[agent("/wt/a", "wm-a")]);
+        assert_eq!(offline, vec![entry("/wt/b", "wm-b")]);
+    }
+}
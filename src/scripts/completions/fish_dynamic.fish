@@ -1,5 +1,5 @@
 # Dynamic worktree handle completion (directory names)
-# Used for open/remove/merge/path - these accept handles or branch names
+# Used for open/attach/remove/archive/replay/merge/path/env/run/test/info/sync/cd - these accept handles or branch names
 function __workmux_handles
     workmux _complete-handles 2>/dev/null
 end
@@ -11,6 +11,6 @@ end
 
 # Add dynamic completions for commands that take worktree handles or branch names
 # (handles are the primary identifier shown in completions)
-complete -c workmux -n '__fish_seen_subcommand_from open remove rm path merge' -f -a '(__workmux_handles)'
+complete -c workmux -n '__fish_seen_subcommand_from open attach remove rm archive replay path env run test info merge sync cd' -f -a '(__workmux_handles)'
 # Add dynamic completions for add command (uses git branches)
 complete -c workmux -n '__fish_seen_subcommand_from add' -f -a '(__workmux_git_branches)'
@@ -0,0 +1,20 @@
+
+    # Dynamic worktree handle and git branch completion
+    $workmuxElements = $commandAst.CommandElements
+    $workmuxPrevIndex = if ($wordToComplete) { $workmuxElements.Count - 2 } else { $workmuxElements.Count - 1 }
+    if ($workmuxPrevIndex -ge 1 -and -not $wordToComplete.StartsWith('-')) {
+        $workmuxCommand = $workmuxElements[1].Extent.Text
+        $workmuxPrev = $workmuxElements[$workmuxPrevIndex].Extent.Text
+        $workmuxSource = switch -Regex ($workmuxCommand) {
+            # Commands that take worktree handles or branch names
+            '^(open|attach|remove|rm|archive|replay|path|env|run|test|info|merge|sync|cd)$' { '_complete-handles' }
+            # add takes git branches, except for the prompt file
+            '^add$' { if ($workmuxPrev -notin '--prompt-file', '-P') { '_complete-git-branches' } }
+        }
+        if ($workmuxSource) {
+            workmux $workmuxSource 2>$null |
+                Where-Object { $_ -like "$wordToComplete*" } |
+                ForEach-Object { [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_) }
+            return
+        }
+    }
@@ -0,0 +1,28 @@
+
+# `workmux cd [name]` changes this shell's directory to a worktree (the main
+# worktree without a name). Everything else goes to the workmux binary.
+function workmux
+    if test "$argv[1]" = cd
+        set -e argv[1]
+        set -l dir (command workmux cd --print $argv); or return
+        builtin cd -- $dir
+    else
+        command workmux $argv
+    end
+end
+
+# Prompt segment: the handle of the worktree you're in and its agent status,
+# e.g. "fix-auth 🤖". Prints nothing outside linked worktrees.
+function workmux_prompt
+    set -l dirs (git rev-parse --path-format=absolute --git-dir --git-common-dir --show-toplevel 2>/dev/null); or return 0
+    test "$dirs[1]" = "$dirs[2]"; and return 0
+    set -l icon
+    if set -q TMUX; and set -q TMUX_PANE
+        set icon (tmux display-message -p -t $TMUX_PANE '#{@workmux_status}' 2>/dev/null)
+    end
+    if test -n "$icon"
+        printf '%s %s' (basename $dirs[3]) $icon
+    else
+        printf '%s' (basename $dirs[3])
+    end
+end
@@ -0,0 +1,27 @@
+
+# `workmux cd [name]` changes this shell's directory to a worktree (the main
+# worktree without a name). Everything else goes to the workmux binary.
+workmux() {
+    if [ "$1" = "cd" ]; then
+        shift
+        local dir
+        dir="$(command workmux cd --print "$@")" || return
+        builtin cd -- "$dir"
+    else
+        command workmux "$@"
+    fi
+}
+
+# Prompt segment: the handle of the worktree you're in and its agent status,
+# e.g. "fix-auth 🤖". Prints nothing outside linked worktrees.
+workmux_prompt() {
+    local dirs git_dir common_dir top icon
+    dirs="$(git rev-parse --path-format=absolute --git-dir --git-common-dir --show-toplevel 2>/dev/null)" || return 0
+    { read -r git_dir; read -r common_dir; read -r top; } <<< "$dirs"
+    [ "$git_dir" = "$common_dir" ] && return 0
+    icon=""
+    if [ -n "$TMUX" ] && [ -n "$TMUX_PANE" ]; then
+        icon="$(tmux display-message -p -t "$TMUX_PANE" '#{@workmux_status}' 2>/dev/null)"
+    fi
+    printf '%s%s' "${top##*/}" "${icon:+ $icon}"
+}
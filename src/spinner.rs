@@ -1,19 +1,174 @@
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::time::Duration;
-
-/// Create a spinner with consistent styling.
-fn create_spinner(msg: &str) -> ProgressBar {
-    let pb = ProgressBar::new_spinner();
-    pb.enable_steady_tick(Duration::from_millis(120));
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
-            .template("{spinner:.blue} {msg}")
-            .unwrap(),
-    );
-    pb.set_message(msg.to_string());
-    pb
+use std::cell::RefCell;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Reports the steps of a long-running operation: to a spinner, to plain log
+/// lines, or to the tmux status line (see [`crate::tmux::WorktreeOp`]).
+pub trait Progress {
+    /// The operation moved on to `step`
+    fn step(&self, step: &str);
+
+    /// The operation is over; `ok` is false if it failed
+    fn finish(&self, _ok: bool) {}
+}
+
+impl<P: Progress> Progress for Option<P> {
+    fn step(&self, step: &str) {
+        if let Some(progress) = self {
+            progress.step(step);
+        }
+    }
+
+    fn finish(&self, ok: bool) {
+        if let Some(progress) = self {
+            progress.finish(ok);
+        }
+    }
+}
+
+/// Report to both
+impl<A: Progress, B: Progress> Progress for (A, B) {
+    fn step(&self, step: &str) {
+        self.0.step(step);
+        self.1.step(step);
+    }
+
+    fn finish(&self, ok: bool) {
+        self.0.finish(ok);
+        self.1.finish(ok);
+    }
+}
+
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Keep stdout for a machine-readable result such as `merge --json`: from
+/// now on, progress messages and hook output go to stderr
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+pub fn stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Print a progress message, on stderr if stdout is reserved
+pub fn message(msg: &str) {
+    if stdout_reserved() {
+        eprintln!("{}", msg);
+    } else {
+        println!("{}", msg);
+    }
+}
+
+/// Whether stderr can't show a spinner: it's a pipe or file, a dumb terminal,
+/// or a CI log. Redrawing there would only leave control characters behind.
+pub fn is_plain() -> bool {
+    static PLAIN: OnceLock<bool> = OnceLock::new();
+    *PLAIN.get_or_init(|| {
+        !std::io::stderr().is_terminal()
+            || std::env::var_os("CI").is_some()
+            || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+    })
+}
+
+/// A spinner with consistent styling, showing the current step
+struct Spinner {
+    bar: ProgressBar,
+    msg: RefCell<String>,
+}
+
+impl Spinner {
+    fn new(msg: &str) -> Self {
+        let bar = ProgressBar::new_spinner();
+        bar.enable_steady_tick(Duration::from_millis(120));
+        bar.set_style(
+            ProgressStyle::default_spinner()
+                .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
+                .template("{spinner:.blue} {msg}")
+                .unwrap(),
+        );
+        bar.set_message(msg.to_string());
+        Self {
+            bar,
+            msg: RefCell::new(msg.to_string()),
+        }
+    }
+}
+
+impl Progress for Spinner {
+    fn step(&self, step: &str) {
+        self.bar.set_message(step.to_string());
+        *self.msg.borrow_mut() = step.to_string();
+    }
+
+    fn finish(&self, ok: bool) {
+        let icon = if ok { "✔" } else { "✘" };
+        self.bar
+            .finish_with_message(format!("{} {}", icon, self.msg.borrow()));
+    }
+}
+
+/// One timestamped line on stderr per step, for output that isn't a terminal
+pub struct LogLines {
+    msg: RefCell<Option<String>>,
+}
+
+impl LogLines {
+    pub fn new() -> Self {
+        Self {
+            msg: RefCell::new(None),
+        }
+    }
+}
+
+impl Progress for LogLines {
+    fn step(&self, step: &str) {
+        eprintln!("[{}] {}", timestamp(SystemTime::now()), step);
+        *self.msg.borrow_mut() = Some(step.to_string());
+    }
+
+    fn finish(&self, ok: bool) {
+        if let Some(msg) = self.msg.borrow().as_deref() {
+            let icon = if ok { "✔" } else { "✘" };
+            eprintln!("[{}] {} {}", timestamp(SystemTime::now()), icon, msg);
+        }
+    }
+}
+
+/// `HH:MM:SS` in UTC
+fn timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        % 86_400;
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Start reporting an operation: a spinner on a terminal, log lines otherwise
+pub fn start(msg: &str) -> Box<dyn Progress> {
+    if is_plain() {
+        let log = LogLines::new();
+        log.step(msg);
+        Box::new(log)
+    } else {
+        Box::new(Spinner::new(msg))
+    }
+}
+
+/// Log lines for the steps of a workflow when stderr isn't a terminal. On a
+/// terminal the workflow's own output says enough.
+pub fn step_log() -> Option<LogLines> {
+    is_plain().then(LogLines::new)
 }
 
 /// Run an operation with a spinner, showing success/failure.
@@ -21,11 +176,45 @@ pub fn with_spinner<T, F>(msg: &str, op: F) -> Result<T>
 where
     F: FnOnce() -> Result<T>,
 {
-    let pb = create_spinner(msg);
+    let progress = start(msg);
     let result = op();
-    match &result {
-        Ok(_) => pb.finish_with_message(format!("✔ {}", msg)),
-        Err(_) => pb.finish_with_message(format!("✘ {}", msg)),
-    }
+    progress.finish(result.is_ok());
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<String>>);
+
+    impl Progress for Recorder {
+        fn step(&self, step: &str) {
+            self.0.borrow_mut().push(step.to_string());
+        }
+
+        fn finish(&self, ok: bool) {
+            self.0.borrow_mut().push(format!("finish {}", ok));
+        }
+    }
+
+    #[test]
+    fn progress_pairs_and_options_forward_steps() {
+        let progress = (Recorder::default(), Some(Recorder::default()));
+        progress.step("merging…");
+        progress.finish(true);
+        (None::<Recorder>).step("ignored");
+
+        let expected = vec!["merging…".to_string(), "finish true".to_string()];
+        assert_eq!(*progress.0.0.borrow(), expected);
+        assert_eq!(*progress.1.as_ref().unwrap().0.borrow(), expected);
+    }
+
+    #[test]
+    fn timestamp_is_utc_time_of_day() {
+        let time = UNIX_EPOCH + Duration::from_secs(19_000 * 86_400 + 3_723);
+        assert_eq!(timestamp(time), "01:02:03");
+    }
+}
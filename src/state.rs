@@ -0,0 +1,189 @@
+//! Versioned state files in `~/.cache/workmux`.
+//!
+//! State is stored as `{"version": N, "data": ...}`. When the format of a file
+//! changes, add a migration from the previous version instead of letting old
+//! files fail to parse: files written by older releases are upgraded when
+//! loaded. A file that cannot be read as the current version (corrupt, or
+//! written by a newer workmux) is moved aside to `<name>.bak` rather than
+//! being silently overwritten.
+
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Upgrades the data of a state file by one version
+pub type Migration = fn(Value) -> Result<Value>;
+
+/// A state file and the migrations that bring older copies up to date
+pub struct StateFile {
+    /// File name inside the state directory
+    pub name: &'static str,
+    /// `migrations[n]` upgrades version `n` to `n + 1`, so the current version
+    /// is `migrations.len()`. Version 0 is a file written before state was
+    /// versioned, holding the bare data.
+    pub migrations: &'static [Migration],
+}
+
+/// Migration for files written before versioning: the data was stored bare
+pub fn adopt_unversioned(data: Value) -> Result<Value> {
+    Ok(data)
+}
+
+/// Directory holding workmux state files, created if missing
+pub fn state_dir() -> Result<PathBuf> {
+    let home = home::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
+    let dir = home.join(".cache").join("workmux");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+impl StateFile {
+    pub fn version(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    pub fn path(&self) -> Result<PathBuf> {
+        Ok(state_dir()?.join(self.name))
+    }
+
+    /// Load the file, migrating it if it was written by an older version.
+    /// Returns `None` if it is missing or unusable.
+    pub fn load<T: DeserializeOwned>(&self) -> Option<T> {
+        self.load_from(&self.path().ok()?)
+    }
+
+    /// Write the file atomically (temp file, then rename)
+    pub fn save<T: Serialize>(&self, data: &T) -> Result<()> {
+        self.save_to(&self.path()?, data)
+    }
+
+    /// Like [`StateFile::load`], for a file outside the state directory
+    pub fn load_from<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let result = self.upgrade(&content).and_then(|(data, from_version)| {
+            let parsed = serde_json::from_value(data.clone())?;
+            if from_version < self.version() {
+                debug!(file = self.name, from_version, "state:migrated");
+                if let Err(e) = self.save_to(path, &data) {
+                    debug!(file = self.name, error = %e, "state:failed to save migrated file");
+                }
+            }
+            Ok(parsed)
+        });
+        match result {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                warn!(file = self.name, error = %e, "state:unreadable, moving aside");
+                let mut backup = path.as_os_str().to_owned();
+                backup.push(".bak");
+                let _ = std::fs::rename(path, backup);
+                None
+            }
+        }
+    }
+
+    /// Like [`StateFile::save`], for a file outside the state directory
+    pub fn save_to<T: Serialize>(&self, path: &Path, data: &T) -> Result<()> {
+        let content = serde_json::to_string(&json!({
+            "version": self.version(),
+            "data": data,
+        }))?;
+        // Include the pid so concurrent writers never share a temp file
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(format!(".{}.tmp", std::process::id()));
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Parse file contents and migrate them to the current version.
+    /// Returns the data and the version it was stored as.
+    fn upgrade(&self, content: &str) -> Result<(Value, u32)> {
+        let value: Value = serde_json::from_str(content)?;
+        let envelope = value
+            .as_object()
+            .filter(|map| map.len() == 2)
+            .and_then(|map| Some((map.get("version")?.as_u64()?, map.get("data")?)));
+        let (mut data, from_version) = match envelope {
+            Some((version, data)) => (data.clone(), u32::try_from(version)?),
+            None => (value, 0),
+        };
+        if from_version > self.version() {
+            return Err(anyhow!(
+                "written by a newer workmux (version {}, expected {})",
+                from_version,
+                self.version()
+            ));
+        }
+        for migration in &self.migrations[from_version as usize..] {
+            data = migration(data)?;
+        }
+        Ok((data, from_version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_count(mut data: Value) -> Result<Value> {
+        let count = data
+            .as_object_mut()
+            .and_then(|map| map.remove("count"))
+            .ok_or_else(|| anyhow!("missing count"))?;
+        Ok(json!({ "total": count }))
+    }
+
+    const FILE: StateFile = StateFile {
+        name: "test.json",
+        migrations: &[adopt_unversioned, rename_count],
+    };
+
+    #[test]
+    fn upgrade_migrates_unversioned_files() {
+        let (data, from) = FILE.upgrade(r#"{"count": 3}"#).unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(data, json!({ "total": 3 }));
+    }
+
+    #[test]
+    fn upgrade_runs_only_newer_migrations() {
+        let (data, from) = FILE
+            .upgrade(r#"{"version": 1, "data": {"count": 3}}"#)
+            .unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(data, json!({ "total": 3 }));
+
+        let (data, from) = FILE
+            .upgrade(r#"{"version": 2, "data": {"total": 3}}"#)
+            .unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(data, json!({ "total": 3 }));
+    }
+
+    #[test]
+    fn upgrade_rejects_newer_versions() {
+        assert!(FILE.upgrade(r#"{"version": 3, "data": {}}"#).is_err());
+    }
+
+    #[test]
+    fn load_rewrites_migrated_files_and_moves_bad_ones_aside() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("test.json");
+
+        std::fs::write(&path, r#"{"count": 3}"#).unwrap();
+        let loaded: Value = FILE.load_from(&path).unwrap();
+        assert_eq!(loaded, json!({ "total": 3 }));
+        let on_disk: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(on_disk, json!({ "version": 2, "data": { "total": 3 } }));
+
+        std::fs::write(&path, "{truncated").unwrap();
+        assert!(FILE.load_from::<Value>(&path).is_none());
+        assert!(!path.exists());
+        assert!(tmp.path().join("test.json.bak").exists());
+    }
+}
@@ -12,6 +12,9 @@ pub struct WorktreeSpec {
     pub branch_name: String,
     pub agent: Option<String>,
     pub template_context: JsonValue,
+    /// Prompt given with the spec itself (a `--stdin` task line), used when
+    /// there is no prompt document to render
+    pub prompt: Option<String>,
 }
 
 pub type TemplateEnv = Environment<'static>;
@@ -95,6 +98,7 @@ pub fn generate_worktree_specs(
             branch_name: base_name.to_string(),
             agent,
             template_context: context,
+            prompt: None,
         }]);
     }
 
@@ -190,6 +194,7 @@ fn build_spec(
         branch_name,
         agent: effective_agent,
         template_context: context,
+        prompt: None,
     })
 }
 
@@ -1,13 +1,126 @@
 use anyhow::{Context, Result, anyhow};
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, trace, warn};
 
 use crate::cmd::Cmd;
-use crate::config::{PaneConfig, SplitDirection};
+use crate::config::{AgentProfile, Config, PaneConfig, SplitDirection, WindowEnv};
+use crate::error::ErrorKind;
+use crate::usage::AgentUsage;
+
+/// Environment variable naming a private tmux server socket (as for `tmux -L`)
+/// that every tmux command is run against, instead of the default server or
+/// the one in `$TMUX`. The end-to-end tests use it to stay isolated.
+pub const SOCKET_ENV: &str = "WORKMUX_TMUX_SOCKET";
+
+static SOCKET: OnceLock<Option<String>> = OnceLock::new();
+
+fn socket() -> Option<&'static str> {
+    SOCKET
+        .get_or_init(|| std::env::var(SOCKET_ENV).ok().filter(|s| !s.is_empty()))
+        .as_deref()
+}
+
+/// Whether tmux runs inside WSL: on Windows without a native tmux, when
+/// `wsl` is available. Paths passed to and read from tmux are translated.
+fn via_wsl() -> bool {
+    static VIA_WSL: OnceLock<bool> = OnceLock::new();
+    *VIA_WSL.get_or_init(|| {
+        cfg!(windows) && which::which("tmux").is_err() && which::which("wsl").is_ok()
+    })
+}
+
+/// A tmux command, run through WSL when needed and against the server from
+/// [`SOCKET_ENV`] if set
+pub fn cmd<'a>() -> Cmd<'a> {
+    let cmd = if via_wsl() {
+        Cmd::new("wsl").arg("tmux")
+    } else {
+        Cmd::new("tmux")
+    };
+    match socket() {
+        Some(socket) => cmd.args(&["-L", socket]),
+        None => cmd,
+    }
+}
+
+/// Like [`cmd`], for the few callers that need a `std::process::Command`
+fn command() -> std::process::Command {
+    let mut command = if via_wsl() {
+        let mut command = std::process::Command::new("wsl");
+        command.arg("tmux");
+        command
+    } else {
+        std::process::Command::new("tmux")
+    };
+    if let Some(socket) = socket() {
+        command.args(["-L", socket]);
+    }
+    command
+}
+
+/// A working directory as the tmux server sees it
+fn tmux_path(path: &Path) -> Result<String> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
+    Ok(if via_wsl() {
+        crate::cmd::windows_to_wsl_path(path)
+    } else {
+        path.to_string()
+    })
+}
+
+/// A path reported by tmux (e.g. `pane_current_path`) as a native path
+fn host_path(path: &str) -> PathBuf {
+    if via_wsl() {
+        PathBuf::from(crate::cmd::wsl_to_windows_path(path))
+    } else {
+        PathBuf::from(path)
+    }
+}
+
+/// Session that worktree windows are created in and looked up from
+/// (`session_strategy`). `None` means the current session.
+static WINDOW_SESSION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Set the session used for worktree windows by the functions in this module
+pub fn set_window_session(session: Option<String>) {
+    debug!(session = ?session, "tmux:window session");
+    *WINDOW_SESSION.lock().unwrap_or_else(|e| e.into_inner()) = session;
+}
+
+fn window_session() -> Option<String> {
+    WINDOW_SESSION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Exact-match target for a window by full name, in the window session
+fn window_target(full_name: &str) -> String {
+    match window_session() {
+        Some(session) => format!("={}:={}", session, full_name),
+        None => format!(":={}", full_name),
+    }
+}
+
+/// Exact-match target for the window session, if one is set
+fn session_target() -> Option<String> {
+    window_session().map(|session| format!("={}", session))
+}
+
+/// Scope a session-wide command (`list-windows`, `list-panes -s`) to `session`
+fn scoped<'a>(cmd: Cmd<'a>, session: Option<&'a str>) -> Cmd<'a> {
+    match session {
+        Some(target) => cmd.args(&["-t", target]),
+        None => cmd,
+    }
+}
 
 /// Helper function to add prefix to window name
 pub fn prefixed(prefix: &str, window_name: &str) -> String {
@@ -17,8 +130,9 @@ pub fn prefixed(prefix: &str, window_name: &str) -> String {
 /// Get all tmux window names in a single call
 pub fn get_all_window_names() -> Result<HashSet<String>> {
     // tmux list-windows may exit with error if no windows exist
-    let windows = Cmd::new("tmux")
-        .args(&["list-windows", "-F", "#{window_name}"])
+    let session = session_target();
+    let windows = scoped(cmd().arg("list-windows"), session.as_deref())
+        .args(&["-F", "#{window_name}"])
         .run_and_capture_stdout()
         .unwrap_or_default(); // Return empty string if command fails
 
@@ -39,7 +153,18 @@ pub fn filter_active_windows(windows: &[String]) -> Result<Vec<String>> {
 
 /// Check if tmux server is running
 pub fn is_running() -> Result<bool> {
-    Cmd::new("tmux").arg("has-session").run_as_check()
+    // Not having tmux installed at all counts as not running
+    Ok(cmd().arg("has-session").run_as_check().unwrap_or(false))
+}
+
+/// Fail with exit code 7 unless a tmux server is running, for commands that
+/// can't do anything without one
+pub fn ensure_running() -> Result<()> {
+    if !is_running()? {
+        return Err(ErrorKind::TmuxNotRunning
+            .msg("tmux is not running. Please start a tmux session first."));
+    }
+    Ok(())
 }
 
 /// Find the last window (by index) that starts with the given prefix.
@@ -47,8 +172,9 @@ pub fn is_running() -> Result<bool> {
 /// Uses window IDs rather than names for stability.
 pub fn find_last_window_with_prefix(prefix: &str) -> Result<Option<String>> {
     // tmux list-windows outputs in index order, so the last match is the highest index.
-    let output = Cmd::new("tmux")
-        .args(&["list-windows", "-F", "#{window_id} #{window_name}"])
+    let session = session_target();
+    let output = scoped(cmd().arg("list-windows"), session.as_deref())
+        .args(&["-F", "#{window_id} #{window_name}"])
         .run_and_capture_stdout()
         .unwrap_or_default();
 
@@ -74,8 +200,9 @@ pub fn window_exists(prefix: &str, window_name: &str) -> Result<bool> {
 
 /// Check if a window exists by its full name (including prefix)
 pub fn window_exists_by_full_name(full_name: &str) -> Result<bool> {
-    let windows = Cmd::new("tmux")
-        .args(&["list-windows", "-F", "#{window_name}"])
+    let session = session_target();
+    let windows = scoped(cmd().arg("list-windows"), session.as_deref())
+        .args(&["-F", "#{window_name}"])
         .run_and_capture_stdout();
 
     match windows {
@@ -84,9 +211,88 @@ pub fn window_exists_by_full_name(full_name: &str) -> Result<bool> {
     }
 }
 
+/// Session holding the window with this full name: the window session when one
+/// is set, otherwise the first session with such a window
+pub fn find_window_session(full_name: &str) -> Option<String> {
+    if let Some(session) = window_session() {
+        return window_exists_by_full_name(full_name)
+            .unwrap_or(false)
+            .then_some(session);
+    }
+    let output = cmd()
+        .args(&["list-windows", "-a", "-F", "#{session_name}:#{window_name}"])
+        .run_and_capture_stdout()
+        .ok()?;
+    // Session names can't contain ':', so the first one ends the session name
+    output.lines().find_map(|line| {
+        let (session, window) = line.split_once(':')?;
+        (window == full_name).then(|| session.to_string())
+    })
+}
+
+/// Start a tmux server with a detached session, returning the ID of the
+/// session's initial window
+pub fn start_detached_session(session: &str, working_dir: &Path) -> Result<String> {
+    let window_id = cmd()
+        .args(&[
+            "new-session",
+            "-d",
+            "-s",
+            session,
+            "-c",
+            &tmux_path(working_dir)?,
+            "-P",
+            "-F",
+            "#{window_id}",
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to start tmux session '{}'", session))?;
+    Ok(window_id.trim().to_string())
+}
+
+/// Kill a tmux window by its ID (e.g. @3)
+pub fn kill_window_by_id(window_id: &str) -> Result<()> {
+    cmd()
+        .args(&["kill-window", "-t", window_id])
+        .run()
+        .context("Failed to kill tmux window")?;
+    Ok(())
+}
+
+/// Bring the terminal to a window. Inside tmux the client switches to it, since
+/// attaching would nest tmux; outside, this process becomes `tmux attach`.
+pub fn attach_to_window(session: &str, full_name: &str) -> Result<()> {
+    let target = format!("={}:={}", session, full_name);
+    if std::env::var_os("TMUX").is_some() {
+        cmd()
+            .args(&["switch-client", "-t", &target])
+            .run()
+            .context("Failed to switch tmux client")?;
+        return Ok(());
+    }
+
+    let mut command = command();
+    command.args(["attach-session", "-t", &target]);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Only returns on failure
+        let err = command.exec();
+        Err(anyhow!(err).context("Failed to attach to tmux"))
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command.status().context("Failed to attach to tmux")?;
+        if !status.success() {
+            return Err(anyhow!("tmux attach exited with {}", status));
+        }
+        Ok(())
+    }
+}
+
 /// Return the tmux window name for the current pane, if any
 pub fn current_window_name() -> Result<Option<String>> {
-    match Cmd::new("tmux")
+    match cmd()
         .args(&["display-message", "-p", "#{window_name}"])
         .run_and_capture_stdout()
     {
@@ -97,7 +303,7 @@ pub fn current_window_name() -> Result<Option<String>> {
 
 /// Get the current foreground command for a pane
 pub fn get_pane_current_command(pane_id: &str) -> Result<String> {
-    let output = Cmd::new("tmux")
+    let output = cmd()
         .args(&[
             "display-message",
             "-p",
@@ -110,29 +316,160 @@ pub fn get_pane_current_command(pane_id: &str) -> Result<String> {
     Ok(output.trim().to_string())
 }
 
+/// Whether a command runs in the foreground of a pane's shell, i.e. the
+/// terminal's foreground process group isn't the shell's. Unlike the pane's
+/// current command, this also sees scripts run by the shell's own interpreter.
+pub fn pane_runs_command(pane_id: &str) -> Result<bool> {
+    let pid = cmd()
+        .args(&["display-message", "-p", "-t", pane_id, "#{pane_pid}"])
+        .run_and_capture_stdout()
+        .context("Failed to get pane pid")?;
+    let pid = pid.trim();
+    let ps = if via_wsl() {
+        Cmd::new("wsl").arg("ps")
+    } else {
+        Cmd::new("ps")
+    };
+    let tpgid = ps
+        .args(&["-o", "tpgid=", "-p", pid])
+        .run_and_capture_stdout()
+        .context("Failed to get the pane's foreground process group")?;
+    let tpgid = tpgid.trim();
+    if tpgid.is_empty() {
+        return Err(anyhow!("The pane's shell has exited"));
+    }
+    Ok(tpgid != pid)
+}
+
+/// Status of a single pane as recorded by `set-window-status`
+pub struct PaneStatus {
+    pub window_name: String,
+    /// Current status icon (if set)
+    pub status: Option<String>,
+    /// Unix timestamp when status was last set
+    pub status_ts: Option<u64>,
+    /// Unix timestamp until which the window is snoozed
+    pub snoozed_until: Option<u64>,
+    /// The pane's working directory
+    pub path: PathBuf,
+}
+
+/// Get the window name, recorded status and directory of a pane
+pub fn get_pane_status(pane_id: &str) -> Option<PaneStatus> {
+    let output = cmd()
+        .args(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{window_name}\t#{@workmux_pane_status}\t#{@workmux_pane_status_ts}\t#{@workmux_snoozed_until}\t#{pane_current_path}",
+        ])
+        .run_and_capture_stdout()
+        .ok()?;
+    let mut parts = output.splitn(5, '\t');
+    let window_name = parts.next()?.to_string();
+    let status = parts.next().filter(|s| !s.is_empty()).map(str::to_string);
+    let status_ts = parts.next().and_then(|s| s.parse().ok());
+    let snoozed_until = parts.next().and_then(|s| s.parse().ok());
+    let path = PathBuf::from(parts.next().unwrap_or_default());
+    Some(PaneStatus {
+        window_name,
+        status,
+        status_ts,
+        snoozed_until,
+        path,
+    })
+}
+
+/// Health of the panes in a tmux window, used by `workmux list`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowHealth {
+    /// Number of panes in the window
+    pub panes: usize,
+    /// Panes whose process has exited but are kept open (remain-on-exit)
+    pub dead_panes: usize,
+    /// Whether an agent process is running in any of the panes
+    pub agent_running: bool,
+}
+
+/// Get pane health for every window in the window session, keyed by window name.
+///
+/// A pane counts as running the agent if its foreground command matches the
+/// agent executable, or matches the command recorded when the agent last set
+/// its status (the same check used to detect exited agents).
+pub fn get_window_health(agent_command: &str) -> HashMap<String, WindowHealth> {
+    let session = session_target();
+    // Not trimmed: the last pane's stored command is usually empty, and
+    // trimming would drop its tab separator
+    let output = scoped(cmd().args(&["list-panes", "-s"]), session.as_deref())
+        .args(&[
+            "-F",
+            "#{window_name}\t#{pane_dead}\t#{pane_current_command}\t#{@workmux_pane_command}",
+        ])
+        .run()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+    parse_window_health(&output, agent_command)
+}
+
+fn parse_window_health(output: &str, agent_command: &str) -> HashMap<String, WindowHealth> {
+    let agent_stem = crate::config::split_first_token(agent_command)
+        .and_then(|(token, _)| Path::new(token).file_stem())
+        .and_then(|stem| stem.to_str());
+
+    let mut health: HashMap<String, WindowHealth> = HashMap::new();
+    for line in output.lines() {
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        // A trailing empty stored command may have lost its separator
+        let (window_name, dead, current_cmd) = (parts[0], parts[1], parts[2]);
+        let stored_cmd = parts.get(3).copied().unwrap_or_default();
+        let entry = health.entry(window_name.to_string()).or_default();
+        entry.panes += 1;
+        if dead == "1" {
+            entry.dead_panes += 1;
+            continue;
+        }
+        let matches_agent = agent_stem.is_some_and(|stem| stem == current_cmd);
+        let matches_stored = !stored_cmd.is_empty() && stored_cmd == current_cmd;
+        if matches_agent || matches_stored {
+            entry.agent_running = true;
+        }
+    }
+    health
+}
+
 /// Get the working directory of the active pane in the current client's session.
 /// This is useful when running inside a tmux popup, where `std::env::current_dir()`
 /// returns the popup's directory rather than the underlying pane's directory.
 pub fn get_client_active_pane_path() -> Result<PathBuf> {
-    // Single shell command to get the active pane's path from the client's session
-    let output = Cmd::new("sh")
+    let session = cmd()
+        .args(&["display-message", "-p", "#{client_session}"])
+        .run_and_capture_stdout()
+        .context("Failed to get client session")?;
+    let path = cmd()
         .args(&[
-            "-c",
-            "tmux display-message -p -t \"$(tmux display-message -p '#{client_session}')\" '#{pane_current_path}'",
+            "display-message",
+            "-p",
+            "-t",
+            session.trim(),
+            "#{pane_current_path}",
         ])
         .run_and_capture_stdout()
         .context("Failed to get client active pane path")?;
 
-    let path = output.trim();
+    let path = path.trim();
     if path.is_empty() {
         return Err(anyhow!("Empty path returned from tmux"));
     }
 
-    Ok(PathBuf::from(path))
+    Ok(host_path(path))
 }
 
 /// Information about a specific pane running a workmux agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AgentPane {
     /// Tmux session name
     pub session: String,
@@ -148,6 +485,40 @@ pub struct AgentPane {
     pub status: Option<String>,
     /// Unix timestamp when status was last set
     pub status_ts: Option<u64>,
+    /// Group shared by worktrees created together (e.g., `add --count 3`)
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Name of the agent workmux launched in the pane (e.g., "codex")
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Whether the window is queued to merge once the agent is done
+    #[serde(default)]
+    pub merge_queued: bool,
+    /// The agent exited and the pane dropped to its shell (`keep_alive` panes)
+    #[serde(default)]
+    pub exited: bool,
+    /// Unix timestamp until which the window is snoozed (dashboard `z`)
+    #[serde(default)]
+    pub snoozed_until: Option<u64>,
+    /// Cost and context use the agent reported (see [`crate::usage`])
+    #[serde(default)]
+    pub usage: AgentUsage,
+}
+
+impl AgentPane {
+    /// The pane title, unless the agent's profile says it isn't a useful summary
+    pub fn summary_title(&self, config: &Config) -> Option<&str> {
+        let shows_title = self
+            .agent
+            .as_deref()
+            .is_none_or(|name| config.agent_profile_named(name).pane_title());
+        self.pane_title.as_deref().filter(|_| shows_title)
+    }
+
+    /// Whether the agent is snoozed at `now` (a Unix timestamp)
+    pub fn is_snoozed(&self, now: u64) -> bool {
+        self.snoozed_until.is_some_and(|until| until > now)
+    }
 }
 
 /// Fetch all panes across all sessions that have workmux pane status set.
@@ -156,22 +527,26 @@ pub struct AgentPane {
 /// Automatically removes panes from the list when the agent has exited.
 /// This is detected by comparing the stored command (from when status was set)
 /// with the current foreground command. If they differ, the agent has exited.
+/// Panes set up with `keep_alive` stay in the list, marked as exited.
 pub fn get_all_agent_panes() -> Result<Vec<AgentPane>> {
     // Format string to extract all needed info in one call
     // Using tab as delimiter since it's less likely to appear in paths/names
     // Note: Uses @workmux_pane_status (pane-level) not @workmux_status (window-level)
     // Also includes @workmux_pane_command (stored) and pane_current_command (live) for exit detection
-    let format = "#{session_name}\t#{window_name}\t#{pane_id}\t#{pane_current_path}\t#{pane_title}\t#{@workmux_pane_status}\t#{@workmux_pane_status_ts}\t#{@workmux_pane_command}\t#{pane_current_command}";
+    let format = "#{session_name}\t#{window_name}\t#{pane_id}\t#{pane_current_path}\t#{pane_title}\t#{@workmux_pane_status}\t#{@workmux_pane_status_ts}\t#{@workmux_pane_command}\t#{pane_current_command}\t#{@workmux_group}\t#{@workmux_agent}\t#{@workmux_merge_queued}\t#{@workmux_keep_alive}\t#{@workmux_pane_cost}\t#{@workmux_pane_context}\t#{@workmux_snoozed_until}";
 
-    let output = Cmd::new("tmux")
+    // Not trimmed: the last pane's trailing options are usually empty, and
+    // trimming would drop their tab separators
+    let output = cmd()
         .args(&["list-panes", "-a", "-F", format])
-        .run_and_capture_stdout()
+        .run()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
         .unwrap_or_default();
 
     let mut agents = Vec::new();
     for line in output.lines() {
         let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 9 {
+        if parts.len() < 16 {
             continue;
         }
 
@@ -191,8 +566,11 @@ pub fn get_all_agent_panes() -> Result<Vec<AgentPane>> {
         let original_cmd = parts[7]; // @workmux_pane_command (stored when status set)
         let current_cmd = parts[8]; // pane_current_command (live)
 
-        // If command changed, agent has exited - clear status and skip
-        if !original_cmd.is_empty() && current_cmd != original_cmd {
+        // If command changed, agent has exited - clear status and skip,
+        // unless the pane keeps exited agents listed
+        let exited = !original_cmd.is_empty() && current_cmd != original_cmd;
+        let keep_alive = !parts[12].is_empty();
+        if exited && !keep_alive {
             clear_pane_status(pane_id);
             continue;
         }
@@ -214,10 +592,21 @@ pub fn get_all_agent_panes() -> Result<Vec<AgentPane>> {
             session: parts[0].to_string(),
             window_name: parts[1].to_string(),
             pane_id: pane_id.to_string(),
-            path: PathBuf::from(parts[3]),
+            path: host_path(parts[3]),
             pane_title,
             status,
             status_ts,
+            group: Some(parts[9]).filter(|g| !g.is_empty()).map(str::to_string),
+            agent: Some(parts[10])
+                .filter(|a| !a.is_empty())
+                .map(str::to_string),
+            merge_queued: !parts[11].is_empty(),
+            exited,
+            snoozed_until: parts[15].parse().ok(),
+            usage: AgentUsage {
+                cost_usd: parts[13].parse().ok(),
+                context_pct: parts[14].parse().ok(),
+            },
         });
     }
 
@@ -229,10 +618,10 @@ pub fn get_all_agent_panes() -> Result<Vec<AgentPane>> {
 /// 1. Multiple panes in a window may have different agents
 /// 2. Window status uses "last write wins" - an active agent will re-set it
 fn clear_pane_status(pane_id: &str) {
-    let _ = Cmd::new("tmux")
+    let _ = cmd()
         .args(&["set-option", "-up", "-t", pane_id, "@workmux_pane_status"])
         .run();
-    let _ = Cmd::new("tmux")
+    let _ = cmd()
         .args(&[
             "set-option",
             "-up",
@@ -241,14 +630,47 @@ fn clear_pane_status(pane_id: &str) {
             "@workmux_pane_status_ts",
         ])
         .run();
-    let _ = Cmd::new("tmux")
+    let _ = cmd()
         .args(&["set-option", "-up", "-t", pane_id, "@workmux_pane_command"])
         .run();
+    for option in ["@workmux_pane_cost", "@workmux_pane_context"] {
+        let _ = cmd()
+            .args(&["set-option", "-up", "-t", pane_id, option])
+            .run();
+    }
+}
+
+/// Record the cost and context use an agent reported on its pane, for the
+/// dashboard
+pub fn set_pane_usage(pane_id: &str, usage: &AgentUsage) {
+    let cost = usage.cost_usd.map(|c| c.to_string());
+    let context = usage.context_pct.map(|c| c.to_string());
+    for (option, value) in [
+        ("@workmux_pane_cost", cost),
+        ("@workmux_pane_context", context),
+    ] {
+        if let Some(value) = value {
+            let _ = cmd()
+                .args(&["set-option", "-p", "-t", pane_id, option, &value])
+                .run();
+        }
+    }
+}
+
+/// Show a message in the status line of the client viewing a pane
+pub fn display_message(pane_id: &str, message: &str) {
+    let _ = cmd()
+        .args(&["display-message", "-t", pane_id, message])
+        .run();
 }
 
-/// Switch the tmux client to a specific pane
+/// Switch the tmux client to a specific pane, in whichever session it lives
 pub fn switch_to_pane(pane_id: &str) -> Result<()> {
-    Cmd::new("tmux")
+    // Make the pane current in its own session first, so switching to another
+    // session lands on it rather than on that session's last active window
+    let _ = cmd().args(&["select-window", "-t", pane_id]).run();
+    let _ = cmd().args(&["select-pane", "-t", pane_id]).run();
+    cmd()
         .args(&["switch-client", "-t", pane_id])
         .run()
         .context("Failed to switch to pane")?;
@@ -261,7 +683,7 @@ pub fn capture_pane(pane_id: &str, lines: u16) -> Option<String> {
     // Capture from history to get scrollable content.
     // -e flag preserves ANSI escape sequences (colors)
     let start_line = format!("-{}", lines);
-    let output = Cmd::new("tmux")
+    let output = cmd()
         .args(&[
             "capture-pane",
             "-p",        // Print to stdout
@@ -291,11 +713,34 @@ pub fn create_window(
     after_window: Option<&str>,
 ) -> Result<String> {
     let prefixed_name = prefixed(prefix, window_name);
-    let working_dir_str = working_dir
-        .to_str()
-        .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
+    let working_dir_str = &tmux_path(working_dir)?;
 
-    let mut cmd = Cmd::new("tmux").arg("new-window");
+    // With a window session, the first window creates the session itself
+    let session = window_session();
+    if let Some(ref session) = session
+        && !session_exists(session)
+    {
+        let pane_id = cmd()
+            .args(&[
+                "new-session",
+                "-d",
+                "-s",
+                session,
+                "-n",
+                &prefixed_name,
+                "-c",
+                working_dir_str,
+                "-P",
+                "-F",
+                "#{pane_id}",
+            ])
+            .run_and_capture_stdout()
+            .with_context(|| format!("Failed to create tmux session '{}'", session))?;
+        return Ok(pane_id.trim().to_string());
+    }
+    let session_target = session.map(|session| format!("={}:", session));
+
+    let mut cmd = cmd().arg("new-window");
     if detached {
         cmd = cmd.arg("-d");
     }
@@ -303,6 +748,8 @@ pub fn create_window(
     // Insert after the target window if specified (keeps workmux windows grouped)
     if let Some(target) = after_window {
         cmd = cmd.arg("-a").args(&["-t", target]);
+    } else if let Some(ref target) = session_target {
+        cmd = cmd.args(&["-t", target]);
     }
 
     // Use -P to print pane info, -F to format output to just the pane ID
@@ -322,9 +769,18 @@ pub fn create_window(
     Ok(pane_id.trim().to_string())
 }
 
+/// Check whether a tmux session with exactly this name exists
+fn session_exists(session: &str) -> bool {
+    let target = format!("={}", session);
+    cmd()
+        .args(&["has-session", "-t", &target])
+        .run_as_check()
+        .unwrap_or(false)
+}
+
 /// Select a specific pane by its ID
 pub fn select_pane(pane_id: &str) -> Result<()> {
-    Cmd::new("tmux")
+    cmd()
         .args(&["select-pane", "-t", pane_id])
         .run()
         .context("Failed to select pane")?;
@@ -332,24 +788,173 @@ pub fn select_pane(pane_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Apply a preset layout (e.g., "even-horizontal") to the window containing a pane
+pub fn select_layout(pane_id: &str, layout: &str) -> Result<()> {
+    cmd()
+        .args(&["select-layout", "-t", pane_id, layout])
+        .run()
+        .context("Failed to select layout")?;
+
+    Ok(())
+}
+
+/// Tag a window as part of a group of worktrees created together.
+/// The dashboard lists agents of the same group next to each other.
+pub fn set_window_group(full_window_name: &str, group: &str) -> Result<()> {
+    // set-option takes a pane target, so the window needs an explicit session part
+    let target = window_target(full_window_name);
+    cmd()
+        .args(&["set-option", "-w", "-t", &target, "@workmux_group", group])
+        .run()
+        .context("Failed to set window group")?;
+
+    Ok(())
+}
+
+// --- Operation Progress ---
+
+/// Prefix of the global options holding each worktree's in-flight operation
+const OP_OPTION_PREFIX: &str = "@workmux_op_";
+
+/// A long-running operation on a worktree (`add`, `merge`) publishing its
+/// current step to tmux, so the dashboard and status line show e.g.
+/// "merging…" instead of looking frozen.
+///
+/// The step is kept in a global `@workmux_op_<handle>` option, since `add` runs
+/// hooks before the worktree's window exists, and mirrored into the window's
+/// `@workmux_op` option for the status line. Both are cleared on drop.
+pub struct WorktreeOp {
+    handle: String,
+    window: String,
+    enabled: bool,
+}
+
+impl WorktreeOp {
+    pub fn start(prefix: &str, handle: &str) -> Self {
+        Self {
+            handle: handle.to_string(),
+            window: prefixed(prefix, handle),
+            enabled: is_running().unwrap_or(false),
+        }
+    }
+}
+
+impl crate::spinner::Progress for WorktreeOp {
+    fn step(&self, step: &str) {
+        if !self.enabled {
+            return;
+        }
+        debug!(handle = %self.handle, step, "tmux:worktree op");
+        let option = format!("{}{}", OP_OPTION_PREFIX, self.handle);
+        let _ = cmd().args(&["set-option", "-g", &option, step]).run();
+        let target = window_target(&self.window);
+        let _ = cmd()
+            .args(&["set-option", "-w", "-t", &target, "@workmux_op", step])
+            .run();
+    }
+}
+
+impl Drop for WorktreeOp {
+    fn drop(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        let option = format!("{}{}", OP_OPTION_PREFIX, self.handle);
+        let _ = cmd().args(&["set-option", "-gu", &option]).run();
+        let target = window_target(&self.window);
+        let _ = cmd()
+            .args(&["set-option", "-wu", "-t", &target, "@workmux_op"])
+            .run();
+    }
+}
+
+/// In-flight operations by worktree handle (see [`WorktreeOp`])
+pub fn get_worktree_ops() -> HashMap<String, String> {
+    cmd()
+        .args(&["show-options", "-g"])
+        .run_and_capture_stdout()
+        .map(|output| parse_worktree_ops(&output))
+        .unwrap_or_default()
+}
+
+fn parse_worktree_ops(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.strip_prefix(OP_OPTION_PREFIX)?.split_once(' ')?;
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| v.replace("\\\"", "\"").replace("\\\\", "\\"))
+                .unwrap_or_else(|| value.to_string());
+            Some((name.to_string(), value))
+        })
+        .collect()
+}
+
+/// Mark (or unmark) a pane's window to be merged once its agent is done
+pub fn set_merge_queued(pane_id: &str, queued: bool) -> Result<()> {
+    let mut args = vec!["set-option", "-w", "-t", pane_id];
+    if queued {
+        args.extend(["@workmux_merge_queued", "1"]);
+    } else {
+        args.extend(["-u", "@workmux_merge_queued"]);
+    }
+    cmd()
+        .args(&args)
+        .run()
+        .context("Failed to update merge queue")?;
+    Ok(())
+}
+
+/// Snooze a pane's window until the given Unix timestamp, or wake it with None
+pub fn set_snoozed_until(pane_id: &str, until: Option<u64>) -> Result<()> {
+    let until = until.map(|ts| ts.to_string());
+    let mut args = vec!["set-option", "-w", "-t", pane_id];
+    match &until {
+        Some(ts) => args.extend(["@workmux_snoozed_until", ts.as_str()]),
+        None => args.extend(["-u", "@workmux_snoozed_until"]),
+    }
+    cmd().args(&args).run().context("Failed to update snooze")?;
+    Ok(())
+}
+
+/// Run a shell command in the background inside the tmux server, so it
+/// outlives the workmux process that started it
+pub fn run_shell_detached(command: &str, workdir: &Path) -> Result<()> {
+    let workdir = workdir.to_string_lossy().replace('\'', "'\\''");
+    let script = format!("cd '{}' && {} >/dev/null 2>&1", workdir, command);
+    cmd()
+        .args(&["run-shell", "-b", &script])
+        .run()
+        .context("Failed to start background command")?;
+    Ok(())
+}
+
 /// Select a specific window
 pub fn select_window(prefix: &str, window_name: &str) -> Result<()> {
     let prefixed_name = prefixed(prefix, window_name);
-    let target = format!("={}", prefixed_name);
+    let target = window_target(&prefixed_name);
 
-    Cmd::new("tmux")
+    cmd()
         .args(&["select-window", "-t", &target])
         .run()
         .context("Failed to select window")?;
 
+    // The window may live in another session: bring the client there.
+    // Outside of an attached client there is nothing to switch.
+    if window_session().is_some() {
+        let _ = cmd().args(&["switch-client", "-t", &target]).run();
+    }
+
     Ok(())
 }
 
 /// Kill a tmux window by its full name (including prefix)
 pub fn kill_window_by_full_name(full_name: &str) -> Result<()> {
-    let target = format!("={}", full_name);
+    let target = window_target(full_name);
 
-    Cmd::new("tmux")
+    cmd()
         .args(&["kill-window", "-t", &target])
         .run()
         .context("Failed to kill tmux window")?;
@@ -359,7 +964,7 @@ pub fn kill_window_by_full_name(full_name: &str) -> Result<()> {
 
 /// Execute a shell script via tmux run-shell
 pub fn run_shell(script: &str) -> Result<()> {
-    Cmd::new("tmux")
+    cmd()
         .args(&["run-shell", script])
         .run()
         .context("Failed to run shell command via tmux")?;
@@ -371,7 +976,7 @@ pub fn run_shell(script: &str) -> Result<()> {
 pub fn schedule_window_close_by_full_name(full_name: &str, delay: Duration) -> Result<()> {
     let delay_secs = format!("{:.3}", delay.as_secs_f64());
     // Shell-escape the target with = inside quotes to handle spaces in window names
-    let target = format!("={}", full_name);
+    let target = window_target(full_name);
     let escaped_target = format!("'{}'", target.replace('\'', r#"'\''"#));
     let script = format!(
         "sleep {delay}; tmux kill-window -t {target} >/dev/null 2>&1",
@@ -384,7 +989,7 @@ pub fn schedule_window_close_by_full_name(full_name: &str, delay: Duration) -> R
 
 /// Get the default shell configured in tmux
 fn get_default_shell() -> Result<String> {
-    let output = Cmd::new("tmux")
+    let output = cmd()
         .args(&["show-option", "-gqv", "default-shell"])
         .run_and_capture_stdout()?;
     let shell = output.trim();
@@ -395,6 +1000,27 @@ fn get_default_shell() -> Result<String> {
     }
 }
 
+/// POSIX command that replaces itself with the user's shell as a login
+/// shell, started through `env` when `window_env` changes its environment
+fn shell_launch(shell: &str, window_env: Option<&WindowEnv>) -> String {
+    let shell = shell.replace('\'', "'\\''");
+    match window_env.and_then(WindowEnv::env_command) {
+        Some(env) => format!("exec {} '{}' -l", env, shell),
+        None => format!("exec '{}' -l", shell),
+    }
+}
+
+/// Run a POSIX script with `sh -c "..."`, escaped for the double-quoted
+/// context (see `PaneHandshake::wrapper_command` for why double quotes)
+fn sh_double_quoted(script: &str) -> String {
+    let escaped = script
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('`', "\\`");
+    format!("sh -c \"{}\"", escaped)
+}
+
 /// Check if a shell is POSIX-compatible (supports `$(...)` syntax)
 fn is_posix_shell(shell: &str) -> bool {
     let shell_name = Path::new(shell)
@@ -437,7 +1063,7 @@ impl PaneHandshake {
         let channel = format!("wm_ready_{}_{}", pid, nanos);
 
         // Lock the channel (ensures we don't miss the signal)
-        Cmd::new("tmux")
+        cmd()
             .args(&["wait-for", "-L", &channel])
             .run()
             .context("Failed to initialize wait channel")?;
@@ -454,20 +1080,11 @@ impl PaneHandshake {
     /// tmux's default-shell is a non-POSIX shell like nushell. Single-quote escaping
     /// (`'\''`) doesn't work reliably when nushell parses the command before passing
     /// it to sh.
-    fn wrapper_command(&self, shell: &str) -> String {
-        // Two-step escaping for the shell path:
-        // 1. Escape for inner single-quoted context (exec '...')
-        let inner_shell = shell.replace('\'', "'\\''");
-        // 2. Escape for outer double-quoted context (sh -c "...")
-        let escaped_shell = inner_shell
-            .replace('\\', "\\\\")
-            .replace('"', "\\\"")
-            .replace('$', "\\$")
-            .replace('`', "\\`");
-        format!(
-            "sh -c \"stty -echo 2>/dev/null; tmux wait-for -U {}; stty echo 2>/dev/null; exec '{}' -l\"",
-            self.channel, escaped_shell
-        )
+    fn wrapper_command(&self, launch: &str) -> String {
+        sh_double_quoted(&format!(
+            "stty -echo 2>/dev/null; tmux wait-for -U {}; stty echo 2>/dev/null; {}",
+            self.channel, launch
+        ))
     }
 
     /// Wait for the shell to signal it is ready, then clean up.
@@ -478,7 +1095,7 @@ impl PaneHandshake {
     fn wait(self) -> Result<()> {
         debug!(channel = %self.channel, "tmux:handshake start");
 
-        let mut child = std::process::Command::new("tmux")
+        let mut child = command()
             .args(["wait-for", "-L", &self.channel])
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -493,7 +1110,7 @@ impl PaneHandshake {
                 Ok(Some(status)) => {
                     if status.success() {
                         // Cleanup: unlock the channel we just re-locked
-                        Cmd::new("tmux")
+                        cmd()
                             .args(&["wait-for", "-U", &self.channel])
                             .run()
                             .context("Failed to cleanup wait channel")?;
@@ -501,9 +1118,7 @@ impl PaneHandshake {
                         return Ok(());
                     } else {
                         // Attempt cleanup even on failure
-                        let _ = Cmd::new("tmux")
-                            .args(&["wait-for", "-U", &self.channel])
-                            .run();
+                        let _ = cmd().args(&["wait-for", "-U", &self.channel]).run();
                         warn!(channel = %self.channel, status = ?status.code(), "tmux:handshake failed (wait-for error)");
                         return Err(anyhow!(
                             "Pane handshake failed - tmux wait-for returned error"
@@ -516,9 +1131,7 @@ impl PaneHandshake {
                         let _ = child.wait(); // Ensure process is reaped
 
                         // Attempt cleanup
-                        let _ = Cmd::new("tmux")
-                            .args(&["wait-for", "-U", &self.channel])
-                            .run();
+                        let _ = cmd().args(&["wait-for", "-U", &self.channel]).run();
 
                         warn!(
                             channel = %self.channel,
@@ -540,9 +1153,7 @@ impl PaneHandshake {
                 Err(e) => {
                     let _ = child.kill();
                     let _ = child.wait();
-                    let _ = Cmd::new("tmux")
-                        .args(&["wait-for", "-U", &self.channel])
-                        .run();
+                    let _ = cmd().args(&["wait-for", "-U", &self.channel]).run();
                     warn!(channel = %self.channel, error = %e, "tmux:handshake error");
                     return Err(anyhow!("Error waiting for pane handshake: {}", e));
                 }
@@ -565,11 +1176,9 @@ pub fn split_pane_with_command(
         SplitDirection::Vertical => "-v",
     };
 
-    let working_dir_str = working_dir
-        .to_str()
-        .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
+    let working_dir_str = &tmux_path(working_dir)?;
 
-    let mut cmd = Cmd::new("tmux").args(&[
+    let mut cmd = cmd().args(&[
         "split-window",
         split_arg,
         "-t",
@@ -603,12 +1212,9 @@ pub fn split_pane_with_command(
 
 /// Respawn a pane by its ID
 pub fn respawn_pane(pane_id: &str, working_dir: &Path, shell_command: Option<&str>) -> Result<()> {
-    let working_dir_str = working_dir
-        .to_str()
-        .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
+    let working_dir_str = &tmux_path(working_dir)?;
 
-    let mut cmd =
-        Cmd::new("tmux").args(&["respawn-pane", "-t", pane_id, "-c", working_dir_str, "-k"]);
+    let mut cmd = cmd().args(&["respawn-pane", "-t", pane_id, "-c", working_dir_str, "-k"]);
 
     if let Some(shell_cmd) = shell_command {
         cmd = cmd.arg(shell_cmd);
@@ -626,12 +1232,12 @@ pub fn respawn_pane(pane_id: &str, working_dir: &Path, shell_command: Option<&st
 pub fn send_keys(pane_id: &str, command: &str) -> Result<()> {
     // Use -l for literal keys (avoids interpretation of special characters)
     // Then send Enter separately to execute the command
-    Cmd::new("tmux")
-        .args(&["send-keys", "-t", pane_id, "-l", command])
+    cmd()
+        .args(&["send-keys", "-t", pane_id, "-l", "--", command])
         .run()
         .context("Failed to send keys to pane")?;
 
-    Cmd::new("tmux")
+    cmd()
         .args(&["send-keys", "-t", pane_id, "Enter"])
         .run()
         .context("Failed to send Enter key to pane")?;
@@ -642,29 +1248,140 @@ pub fn send_keys(pane_id: &str, command: &str) -> Result<()> {
 /// Send a single key to a pane without pressing Enter.
 /// Used for interactive input mode where each keystroke is forwarded.
 pub fn send_key(pane_id: &str, key: &str) -> Result<()> {
-    Cmd::new("tmux")
+    cmd()
         .args(&["send-keys", "-t", pane_id, key])
         .run()
         .context("Failed to send key to pane")?;
     Ok(())
 }
 
-/// Paste multiline content into a pane using tmux buffer and bracketed paste.
-/// This ensures newlines are treated as content, not as Enter keypresses.
-/// After pasting, sends Enter to submit the content.
-pub fn paste_multiline(pane_id: &str, content: &str) -> Result<()> {
+/// Largest piece of content pasted at once. Agents' input handling can drop or
+/// reorder parts of a single huge paste.
+const PASTE_CHUNK_BYTES: usize = 4096;
+
+/// Largest piece of content typed as keypresses at once, smaller than a paste
+/// since every character is a key event of its own
+const TYPE_CHUNK_BYTES: usize = 256;
+
+/// Pause between pasted pieces, so the agent takes in one before the next
+const PASTE_CHUNK_DELAY: Duration = Duration::from_millis(30);
+
+/// How long a pane gets to show pasted content before it is submitted
+const PASTE_SETTLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How input is typed into an agent's pane, from the agent's profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputOptions {
+    /// Type the input as keypresses (`send-keys -l`) instead of pasting it
+    pub literal: bool,
+    /// Pause between pieces of the input
+    pub delay: Duration,
+    /// Send Enter once the pane has shown the input and settled, rather than
+    /// right after it
+    pub enter_separately: bool,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            literal: false,
+            delay: PASTE_CHUNK_DELAY,
+            enter_separately: true,
+        }
+    }
+}
+
+/// Input options for the agent running in a pane, by the `@workmux_agent`
+/// name it was launched with
+pub fn agent_input(pane_id: &str, config: &Config) -> InputOptions {
+    let agent = cmd()
+        .args(&["display-message", "-p", "-t", pane_id, "#{@workmux_agent}"])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+    config.agent_profile_named(agent.trim()).input_options()
+}
+
+/// Paste multiline content into a pane using tmux buffers and bracketed paste,
+/// then send Enter to submit it. Newlines are treated as content, not as Enter
+/// keypresses. With `input.literal` the content is typed as keypresses
+/// instead, for programs that don't understand bracketed paste, with
+/// newlines typed as Alt+Enter so they don't submit each line.
+///
+/// Large content is sent in pieces, and Enter is only sent once the pane has
+/// shown the input and stopped changing: an Enter that arrives while an agent
+/// is still taking in a long paste ends up inside it.
+pub fn paste_multiline(pane_id: &str, content: &str, input: InputOptions) -> Result<()> {
+    let content = sanitize_paste(content);
+    let before = capture_pane(pane_id, 0).ok_or_else(|| anyhow!("Pane {} not found", pane_id))?;
+
+    let pieces = if input.literal {
+        typed_pieces(&content, TYPE_CHUNK_BYTES)
+    } else {
+        paste_chunks(&content, PASTE_CHUNK_BYTES)
+            .into_iter()
+            .map(Some)
+            .collect()
+    };
+    for (i, piece) in pieces.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(input.delay);
+        }
+        match piece {
+            Some(text) if input.literal => {
+                cmd()
+                    .args(&["send-keys", "-t", pane_id, "-l", "--", text])
+                    .run()
+                    .context("Failed to send keys to pane")?;
+            }
+            Some(text) => paste_buffer(pane_id, text)?,
+            None => {
+                cmd()
+                    .args(&["send-keys", "-t", pane_id, "M-Enter"])
+                    .run()
+                    .context("Failed to send line break to pane")?;
+            }
+        }
+    }
+    // Programs that don't echo input never show it, so this only delays Enter
+    if input.enter_separately && !pieces.is_empty() && !wait_for_pane_to_settle(pane_id, &before) {
+        warn!(
+            pane_id,
+            "tmux:pane didn't show the paste, submitting anyway"
+        );
+    }
+
+    // Send Enter to submit the pasted content
+    cmd()
+        .args(&["send-keys", "-t", pane_id, "Enter"])
+        .run()
+        .context("Failed to send Enter after paste")?;
+
+    Ok(())
+}
+
+/// Paste text into a pane through a buffer of its own, with bracketed paste
+fn paste_buffer(pane_id: &str, text: &str) -> Result<()> {
     use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // A named buffer, so concurrent pastes and the user's buffers don't mix
+    static PASTES: AtomicUsize = AtomicUsize::new(0);
+    let buffer = format!(
+        "workmux-paste-{}-{}",
+        std::process::id(),
+        PASTES.fetch_add(1, Ordering::Relaxed)
+    );
 
-    // Load content into a temporary tmux buffer via stdin
-    let mut child = std::process::Command::new("tmux")
-        .args(["load-buffer", "-"])
+    // Load content into the buffer via stdin
+    let mut child = command()
+        .args(["load-buffer", "-b", &buffer, "-"])
         .stdin(std::process::Stdio::piped())
         .spawn()
         .context("Failed to spawn tmux load-buffer")?;
 
     if let Some(mut stdin) = child.stdin.take() {
         stdin
-            .write_all(content.as_bytes())
+            .write_all(text.as_bytes())
             .context("Failed to write to tmux buffer")?;
     }
 
@@ -676,18 +1393,80 @@ pub fn paste_multiline(pane_id: &str, content: &str) -> Result<()> {
     }
 
     // Paste the buffer with bracketed paste (-p) and delete after (-d)
-    Cmd::new("tmux")
-        .args(&["paste-buffer", "-t", pane_id, "-p", "-d"])
+    cmd()
+        .args(&["paste-buffer", "-b", &buffer, "-t", pane_id, "-p", "-d"])
         .run()
         .context("Failed to paste buffer to pane")?;
+    Ok(())
+}
 
-    // Send Enter to submit the pasted content
-    Cmd::new("tmux")
-        .args(&["send-keys", "-t", pane_id, "Enter"])
-        .run()
-        .context("Failed to send Enter after paste")?;
+/// Drop control characters other than newlines and tabs from pasted text. An
+/// escape character could end the bracketed paste early (`ESC [201~`) and
+/// have the rest read as keypresses. Carriage returns become newlines.
+fn sanitize_paste(content: &str) -> String {
+    content
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect()
+}
 
-    Ok(())
+/// Split `content` into pieces of at most `max` bytes, after the last line
+/// that fits, or at a character boundary when a single line doesn't
+fn paste_chunks(content: &str, max: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = content;
+    while rest.len() > max {
+        let mut end = max;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(newline) = rest[..end].rfind('\n') {
+            end = newline + 1;
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Pieces of `content` to type as keys, at most `max` bytes each, with None
+/// for each line break between lines
+fn typed_pieces(content: &str, max: usize) -> Vec<Option<&str>> {
+    let mut pieces = Vec::new();
+    for (i, line) in content.split('\n').enumerate() {
+        if i > 0 {
+            pieces.push(None);
+        }
+        pieces.extend(paste_chunks(line, max).into_iter().map(Some));
+    }
+    pieces
+}
+
+/// Wait for a pane to differ from `before` and then stop changing, for at most
+/// [`PASTE_SETTLE_TIMEOUT`]. Returns whether it changed at all.
+fn wait_for_pane_to_settle(pane_id: &str, before: &str) -> bool {
+    let deadline = Instant::now() + PASTE_SETTLE_TIMEOUT;
+    let mut last = before.to_string();
+    let mut changed = false;
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+        let Some(screen) = capture_pane(pane_id, 0) else {
+            return false;
+        };
+        if screen != last {
+            changed = true;
+            last = screen;
+        } else if changed {
+            return true;
+        }
+    }
+    changed
 }
 
 /// Result of setting up panes
@@ -699,6 +1478,8 @@ pub struct PaneSetupResult {
 pub struct PaneSetupOptions<'a> {
     pub run_commands: bool,
     pub prompt_file_path: Option<&'a Path>,
+    /// File the agent pane's output is appended to
+    pub agent_log: Option<&'a Path>,
 }
 
 /// Setup panes in a window according to configuration
@@ -707,7 +1488,7 @@ pub fn setup_panes(
     panes: &[PaneConfig],
     working_dir: &Path,
     pane_options: PaneSetupOptions<'_>,
-    config: &crate::config::Config,
+    config: &Config,
     task_agent: Option<&str>,
 ) -> Result<PaneSetupResult> {
     if panes.is_empty() {
@@ -720,11 +1501,19 @@ pub fn setup_panes(
     let mut pane_ids: Vec<String> = vec![initial_pane_id.to_string()];
     let effective_agent = task_agent.or(config.agent.as_deref());
     let shell = get_default_shell()?;
+    let launch = shell_launch(&shell, config.window_env.as_ref());
+    // Panes without a command get a shell with the configured environment
+    // instead of the one tmux starts
+    let plain_shell = config
+        .window_env
+        .as_ref()
+        .and_then(WindowEnv::env_command)
+        .map(|_| sh_double_quoted(&launch));
 
     // Handle the first pane (initial pane from window creation)
     if let Some(pane_config) = panes.first() {
         let command_to_run = if pane_config.command.as_deref() == Some("<agent>") {
-            effective_agent.map(|agent_cmd| agent_cmd.to_string())
+            effective_agent.map(|agent_cmd| config.agent_launch_command(agent_cmd))
         } else {
             pane_config.command.clone()
         };
@@ -737,6 +1526,7 @@ pub fn setup_panes(
                     working_dir,
                     effective_agent,
                     &shell,
+                    config,
                 )
             })
         } else {
@@ -746,19 +1536,35 @@ pub fn setup_panes(
         if let Some(cmd_str) = adjusted_command.as_ref().map(|c| c.as_ref()) {
             // Use PaneHandshake to ensure shell is ready before sending keys
             let handshake = PaneHandshake::new()?;
-            let wrapper = handshake.wrapper_command(&shell);
+            let wrapper = handshake.wrapper_command(&launch);
 
             respawn_pane(initial_pane_id, working_dir, Some(&wrapper))?;
             handshake.wait()?;
+            if let Some(command) = &command_to_run {
+                start_agent_log(
+                    initial_pane_id,
+                    command,
+                    pane_options.agent_log,
+                    effective_agent,
+                );
+            }
             send_keys(initial_pane_id, cmd_str)?;
 
-            // Set "working" status if prompt was injected into a hook-supporting agent.
-            // See: agent_needs_auto_status()
-            if let Some(Cow::Owned(_)) = &adjusted_command
-                && agent_needs_auto_status(effective_agent)
-            {
-                let _ = set_pane_working_status(initial_pane_id, config);
+            if let Some(command) = &command_to_run {
+                finish_agent_launch(
+                    initial_pane_id,
+                    command,
+                    matches!(adjusted_command, Some(Cow::Owned(_))),
+                    pane_options.prompt_file_path,
+                    effective_agent,
+                    config,
+                );
             }
+        } else if let Some(plain_shell) = &plain_shell {
+            respawn_pane(initial_pane_id, working_dir, Some(plain_shell))?;
+        }
+        if pane_config.keep_alive && adjusted_command.is_some() {
+            set_keep_alive(initial_pane_id);
         }
         if pane_config.focus {
             focus_pane_id = Some(initial_pane_id.to_string());
@@ -775,7 +1581,7 @@ pub fn setup_panes(
                 .ok_or_else(|| anyhow!("Invalid target pane index: {}", target_pane_idx))?;
 
             let command_to_run = if pane_config.command.as_deref() == Some("<agent>") {
-                effective_agent.map(|agent_cmd| agent_cmd.to_string())
+                effective_agent.map(|agent_cmd| config.agent_launch_command(agent_cmd))
             } else {
                 pane_config.command.clone()
             };
@@ -788,6 +1594,7 @@ pub fn setup_panes(
                         working_dir,
                         effective_agent,
                         &shell,
+                        config,
                     )
                 })
             } else {
@@ -797,7 +1604,7 @@ pub fn setup_panes(
             let new_pane_id = if let Some(cmd_str) = adjusted_command.as_ref().map(|c| c.as_ref()) {
                 // Use PaneHandshake to ensure shell is ready before sending keys
                 let handshake = PaneHandshake::new()?;
-                let wrapper = handshake.wrapper_command(&shell);
+                let wrapper = handshake.wrapper_command(&launch);
 
                 let pane_id = split_pane_with_command(
                     target_pane_id,
@@ -809,14 +1616,23 @@ pub fn setup_panes(
                 )?;
 
                 handshake.wait()?;
+                if let Some(command) = &command_to_run {
+                    start_agent_log(&pane_id, command, pane_options.agent_log, effective_agent);
+                }
                 send_keys(&pane_id, cmd_str)?;
 
-                // Set "working" status if prompt was injected into a hook-supporting agent.
-                // See: agent_needs_auto_status()
-                if let Some(Cow::Owned(_)) = &adjusted_command
-                    && agent_needs_auto_status(effective_agent)
-                {
-                    let _ = set_pane_working_status(&pane_id, config);
+                if let Some(command) = &command_to_run {
+                    finish_agent_launch(
+                        &pane_id,
+                        command,
+                        matches!(adjusted_command, Some(Cow::Owned(_))),
+                        pane_options.prompt_file_path,
+                        effective_agent,
+                        config,
+                    );
+                }
+                if pane_config.keep_alive {
+                    set_keep_alive(&pane_id);
                 }
 
                 pane_id
@@ -827,7 +1643,7 @@ pub fn setup_panes(
                     working_dir,
                     pane_config.size,
                     pane_config.percentage,
-                    None,
+                    plain_shell.as_deref(),
                 )?
             };
 
@@ -850,10 +1666,17 @@ fn adjust_command<'a>(
     working_dir: &Path,
     effective_agent: Option<&str>,
     shell: &str,
+    config: &Config,
 ) -> Cow<'a, str> {
     if let Some(prompt_path) = prompt_file_path
-        && let Some(rewritten) =
-            rewrite_agent_command(command, prompt_path, working_dir, effective_agent, shell)
+        && let Some(rewritten) = rewrite_agent_command(
+            command,
+            prompt_path,
+            working_dir,
+            effective_agent,
+            shell,
+            config,
+        )
     {
         return Cow::Owned(rewritten);
     }
@@ -870,9 +1693,10 @@ fn adjust_command<'a>(
 /// Only rewrites commands that match the configured agent. For instance, if the config
 /// specifies "gemini" as the agent, a "claude" command won't be rewritten.
 ///
-/// Special handling:
-/// - gemini: Adds `-i` flag for interactive mode after the prompt
-/// - Other agents (claude, codex, etc.): Just passes the prompt as first argument
+/// How the prompt is passed comes from the agent's profile (see
+/// [`AgentProfile::prompt_args`]): e.g. `-i` for gemini, `--prompt` for opencode,
+/// and `--` for claude, codex, and unknown agents. Agents whose prompt is pasted
+/// in are not rewritten.
 ///
 /// For non-POSIX shells (nushell, fish, pwsh), the command is wrapped in `sh -c '...'`
 /// to ensure the `$(cat ...)` command substitution works correctly.
@@ -887,6 +1711,7 @@ fn rewrite_agent_command(
     working_dir: &Path,
     effective_agent: Option<&str>,
     shell: &str,
+    config: &Config,
 ) -> Option<String> {
     let agent_command = effective_agent?;
     let trimmed_command = command.trim();
@@ -924,17 +1749,11 @@ fn rewrite_agent_command(
     }
 
     // Add the prompt argument (agent-specific handling)
-    let pane_stem_str = pane_stem.and_then(|s| s.to_str());
-    if pane_stem_str == Some("gemini") {
-        // gemini uses -i flag with the prompt as its argument
-        inner_cmd.push_str(&format!(" -i \"$(cat {})\"", prompt_path));
-    } else if pane_stem_str == Some("opencode") {
-        // opencode uses --prompt flag for interactive TUI with initial prompt
-        inner_cmd.push_str(&format!(" --prompt \"$(cat {})\"", prompt_path));
-    } else {
-        // Other agents use -- separator
-        inner_cmd.push_str(&format!(" -- \"$(cat {})\"", prompt_path));
-    }
+    let prompt_args = config
+        .agent_profile(agent_command)
+        .prompt_args(&format!("\"$(cat {})\"", prompt_path))?;
+    inner_cmd.push(' ');
+    inner_cmd.push_str(&prompt_args);
 
     // For POSIX shells (bash, zsh, sh, etc.), use the command directly.
     // For non-POSIX shells (nushell, fish, pwsh), wrap in sh -c '...' to ensure
@@ -950,25 +1769,145 @@ fn rewrite_agent_command(
 
 // --- Status Management ---
 
-/// Checks if an agent supports hooks and needs auto-status when launched with a prompt.
-/// Currently only Claude and opencode support hooks that would normally set the status.
+/// Keep the pane's agent listed once it exits (`keep_alive` in the pane config)
+fn set_keep_alive(pane_id: &str) {
+    let _ = cmd()
+        .args(&[
+            "set-option",
+            "-p",
+            "-t",
+            pane_id,
+            "@workmux_keep_alive",
+            "1",
+        ])
+        .run();
+}
+
+/// Follow-up once a pane's command has been sent, for panes running the agent.
 ///
-/// This is a workaround for Claude Code's broken UserPromptSubmit hook:
-/// https://github.com/anthropics/claude-code/issues/17284
-fn agent_needs_auto_status(effective_agent: Option<&str>) -> bool {
+/// The pane is tagged with the agent's name (read by the dashboard), prompted
+/// agents with status hooks are marked as working, and prompted agents are
+/// checked to have started, with the prompt pasted in afterwards for agents
+/// that can't take it as an argument.
+///
+/// Marking them as working is a workaround for Claude Code's broken
+/// UserPromptSubmit hook: https://github.com/anthropics/claude-code/issues/17284
+fn finish_agent_launch(
+    pane_id: &str,
+    command: &str,
+    prompt_injected: bool,
+    prompt_file_path: Option<&Path>,
+    effective_agent: Option<&str>,
+    config: &Config,
+) {
     let Some(agent) = effective_agent else {
-        return false;
+        return;
     };
+    if !crate::config::is_agent_command(command, agent) {
+        return;
+    }
+    let profile = config.agent_profile(agent);
 
-    let (token, _) = crate::config::split_first_token(agent).unwrap_or((agent, ""));
-    let resolved =
-        crate::config::resolve_executable_path(token).unwrap_or_else(|| token.to_string());
-    let stem = Path::new(&resolved)
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
+    if let Some(name) = crate::config::agent_name(agent) {
+        let _ = cmd()
+            .args(&["set-option", "-p", "-t", pane_id, "@workmux_agent", &name])
+            .run();
+    }
+
+    // Agents that can't take the prompt as an argument get it pasted in
+    let paste = prompt_file_path.filter(|_| !prompt_injected && profile.pastes_prompt());
+    if !prompt_injected && paste.is_none() {
+        return;
+    }
+    if profile.status_hooks() {
+        let _ = set_pane_working_status(pane_id, config);
+    }
+    // The executable actually launched, which is what the shell names when
+    // it can't find it
+    let name = crate::config::agent_name(command)
+        .or_else(|| crate::config::agent_name(agent))
+        .unwrap_or_else(|| agent.to_string());
+    if let Err(e) = await_agent_start(pane_id, &name, paste, &profile) {
+        warn!(pane_id, error = %e, "tmux:failed to schedule agent start check");
+    }
+}
 
-    matches!(stem, "claude" | "opencode")
+/// Record the output of an agent pane to `log`, from before the agent starts
+fn start_agent_log(
+    pane_id: &str,
+    command: &str,
+    log: Option<&Path>,
+    effective_agent: Option<&str>,
+) {
+    let (Some(log), Some(agent)) = (log, effective_agent) else {
+        return;
+    };
+    if !crate::config::is_agent_command(command, agent) {
+        return;
+    }
+    if let Err(e) = pipe_pane_to(pane_id, log) {
+        warn!(pane_id, error = %e, "tmux:failed to start agent log");
+    }
+}
+
+/// Append everything the pane prints to `log`, so the output outlives the
+/// scrollback
+fn pipe_pane_to(pane_id: &str, log: &Path) -> Result<()> {
+    if let Some(dir) = log.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let log = log.to_string_lossy().replace('\'', "'\\''");
+    cmd()
+        .args(&[
+            "pipe-pane",
+            "-o",
+            "-t",
+            pane_id,
+            &format!("cat >> '{}'", log),
+        ])
+        .run()?;
+    Ok(())
+}
+
+/// Check in the background that the agent started (`workmux _await-agent`),
+/// then paste in its prompt if it takes one that way. The wait runs in the tmux
+/// server, so workmux doesn't block on it.
+fn await_agent_start(
+    pane_id: &str,
+    agent: &str,
+    paste: Option<&Path>,
+    profile: &AgentProfile,
+) -> Result<()> {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+    let exe = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("workmux"));
+    let mut script = format!(
+        "{} _await-agent {} --agent {}",
+        quote(&exe.to_string_lossy()),
+        quote(pane_id),
+        quote(agent),
+    );
+    if let Some(file) = paste {
+        script.push_str(&format!(" --paste {}", quote(&file.to_string_lossy())));
+    }
+    if let Some(pattern) = &profile.ready_pattern {
+        script.push_str(&format!(" --ready-pattern {}", quote(pattern)));
+    }
+    if paste.is_some() {
+        let input = profile.input_options();
+        script.push_str(&format!(" --input-delay-ms {}", input.delay.as_millis()));
+        if input.literal {
+            script.push_str(" --literal-input");
+        }
+        if !input.enter_separately {
+            script.push_str(" --enter-with-input");
+        }
+    }
+    script.push_str(" >/dev/null 2>&1");
+    cmd()
+        .args(&["run-shell", "-b", &script])
+        .run()
+        .context("Failed to start agent start check")?;
+    Ok(())
 }
 
 /// Sets the "working" status on a pane. Used when launching an agent with a prompt
@@ -977,7 +1916,7 @@ fn agent_needs_auto_status(effective_agent: Option<&str>) -> bool {
 /// Note: This intentionally does NOT enable exit detection. When called right after
 /// `send_keys()`, the shell hasn't started the agent yet, so capturing the command
 /// would get `zsh`/`bash` instead of `node`/`claude`.
-fn set_pane_working_status(pane_id: &str, config: &crate::config::Config) -> Result<()> {
+fn set_pane_working_status(pane_id: &str, config: &Config) -> Result<()> {
     let icon = config.status_icons.working();
 
     // Ensure the status format is applied so the icon shows up
@@ -1008,13 +1947,13 @@ pub fn set_status_options(pane: &str, icon: &str, enable_exit_detection: bool) {
 
     // 1. Set Window Option (for tmux status bar display)
     // "Last write wins" behavior for the window icon
-    if let Err(e) = Cmd::new("tmux")
+    if let Err(e) = cmd()
         .args(&["set-option", "-w", "-t", pane, "@workmux_status", icon])
         .run()
     {
         eprintln!("workmux: failed to set window status: {}", e);
     }
-    let _ = Cmd::new("tmux")
+    let _ = cmd()
         .args(&[
             "set-option",
             "-w",
@@ -1027,13 +1966,13 @@ pub fn set_status_options(pane: &str, icon: &str, enable_exit_detection: bool) {
 
     // 2. Set Pane Option (for dashboard tracking)
     // Use a DISTINCT key to avoid inheritance issues in list-panes
-    if let Err(e) = Cmd::new("tmux")
+    if let Err(e) = cmd()
         .args(&["set-option", "-p", "-t", pane, "@workmux_pane_status", icon])
         .run()
     {
         eprintln!("workmux: failed to set pane status: {}", e);
     }
-    let _ = Cmd::new("tmux")
+    let _ = cmd()
         .args(&[
             "set-option",
             "-p",
@@ -1049,7 +1988,7 @@ pub fn set_status_options(pane: &str, icon: &str, enable_exit_detection: bool) {
     if enable_exit_detection {
         let current_cmd = get_pane_current_command(pane).unwrap_or_default();
         if !current_cmd.is_empty() {
-            let _ = Cmd::new("tmux")
+            let _ = cmd()
                 .args(&[
                     "set-option",
                     "-p",
@@ -1067,7 +2006,8 @@ pub fn set_status_options(pane: &str, icon: &str, enable_exit_detection: bool) {
 
 /// Format string to inject into tmux window-status-format.
 /// Uses conditional: only shows space + icon when @workmux_status is set.
-const WORKMUX_STATUS_FORMAT: &str = "#{?@workmux_status, #{@workmux_status},}";
+const WORKMUX_STATUS_FORMAT: &str =
+    "#{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}";
 
 /// Ensures the tmux window's status format includes workmux status.
 /// Sets format per-window to avoid affecting non-workmux windows or other sessions.
@@ -1082,7 +2022,7 @@ pub fn ensure_status_format(pane: &str) -> Result<()> {
 fn update_format_option(pane: &str, option: &str) -> Result<()> {
     // Read current format. Try window-level first, fall back to global.
     // Note: show-option -wv returns empty string (not error) when no window option exists.
-    let window_format = Cmd::new("tmux")
+    let window_format = cmd()
         .args(&["show-option", "-wv", "-t", pane, option])
         .run_and_capture_stdout()
         .ok()
@@ -1090,7 +2030,7 @@ fn update_format_option(pane: &str, option: &str) -> Result<()> {
 
     let current = match window_format {
         Some(fmt) => fmt,
-        None => Cmd::new("tmux")
+        None => cmd()
             .args(&["show-option", "-gv", option])
             .run_and_capture_stdout()
             .ok()
@@ -1101,7 +2041,7 @@ fn update_format_option(pane: &str, option: &str) -> Result<()> {
     if !current.contains("@workmux_status") {
         let new_format = inject_status_format(&current);
         // Set per-window to avoid affecting other windows/sessions
-        Cmd::new("tmux")
+        cmd()
             .args(&["set-option", "-w", "-t", pane, option, &new_format])
             .run()?;
     }
@@ -1172,6 +2112,66 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn paste_chunks_split_after_whole_lines() {
+        assert_eq!(paste_chunks("", 8), Vec::<&str>::new());
+        assert_eq!(paste_chunks("short", 8), vec!["short"]);
+        assert_eq!(
+            paste_chunks("one\ntwo\nthree\n", 8),
+            vec!["one\ntwo\n", "three\n"]
+        );
+        // A line longer than a chunk is cut, but not inside a character
+        assert_eq!(
+            paste_chunks("abcdéfghij\nk", 5),
+            vec!["abcd", "éfgh", "ij\nk"]
+        );
+        let long = "line of a long prompt\n".repeat(1000);
+        let chunks = paste_chunks(&long, PASTE_CHUNK_BYTES);
+        assert!(chunks.iter().all(|c| c.len() <= PASTE_CHUNK_BYTES));
+        assert_eq!(chunks.concat(), long);
+    }
+
+    #[test]
+    fn sanitize_paste_drops_escape_sequences() {
+        assert_eq!(
+            sanitize_paste("fix it\x1b[201~\r\nthen\ttest\rdone\x07"),
+            "fix it[201~\nthen\ttest\ndone"
+        );
+    }
+
+    #[test]
+    fn typed_pieces_break_lines_without_submitting() {
+        assert_eq!(
+            typed_pieces("Fix:\n- the bug\n\nthanks", 4),
+            vec![
+                Some("Fix:"),
+                None,
+                Some("- th"),
+                Some("e bu"),
+                Some("g"),
+                None,
+                None,
+                Some("than"),
+                Some("ks"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_launch_applies_window_env() {
+        assert_eq!(shell_launch("/bin/zsh", None), "exec '/bin/zsh' -l");
+        let window_env = WindowEnv {
+            unset: vec!["GITHUB_TOKEN".to_string()],
+            ..Default::default()
+        };
+        let launch = shell_launch("/bin/zsh", Some(&window_env));
+        assert_eq!(launch, "exec env -u GITHUB_TOKEN '/bin/zsh' -l");
+        assert_eq!(
+            sh_double_quoted("echo \"$HOME\""),
+            "sh -c \"echo \\\"\\$HOME\\\"\""
+        );
+    }
+
     // --- is_posix_shell tests ---
 
     #[test]
@@ -1216,6 +2216,7 @@ mod tests {
             &working_dir,
             Some("claude"),
             "/bin/zsh",
+            &Config::default(),
         );
         // POSIX shell: no wrapper, prefixed with space to prevent history
         assert_eq!(result, Some(" claude -- \"$(cat PROMPT.md)\"".to_string()));
@@ -1232,6 +2233,7 @@ mod tests {
             &working_dir,
             Some("gemini"),
             "/bin/bash",
+            &Config::default(),
         );
         assert_eq!(result, Some(" gemini -i \"$(cat PROMPT.md)\"".to_string()));
     }
@@ -1247,6 +2249,7 @@ mod tests {
             &working_dir,
             Some("opencode"),
             "/bin/zsh",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1265,6 +2268,7 @@ mod tests {
             &working_dir,
             Some("claude"),
             "/bin/bash",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1285,6 +2289,7 @@ mod tests {
             &working_dir,
             Some("claude"),
             "/opt/homebrew/bin/nu",
+            &Config::default(),
         );
         // Non-POSIX shell: wrap in sh -c, prefixed with space
         assert_eq!(
@@ -1304,6 +2309,7 @@ mod tests {
             &working_dir,
             Some("gemini"),
             "/usr/bin/fish",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1323,6 +2329,7 @@ mod tests {
             &working_dir,
             Some("/path/with'quote/claude"),
             "/opt/homebrew/bin/nu",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1344,6 +2351,7 @@ mod tests {
             &working_dir,
             Some("gemini"),
             "/bin/zsh",
+            &Config::default(),
         );
         assert_eq!(result, None);
     }
@@ -1353,8 +2361,14 @@ mod tests {
         let prompt_file = PathBuf::from("/tmp/worktree/PROMPT.md");
         let working_dir = PathBuf::from("/tmp/worktree");
 
-        let result =
-            rewrite_agent_command("", &prompt_file, &working_dir, Some("claude"), "/bin/zsh");
+        let result = rewrite_agent_command(
+            "",
+            &prompt_file,
+            &working_dir,
+            Some("claude"),
+            "/bin/zsh",
+            &Config::default(),
+        );
         assert_eq!(result, None);
     }
 
@@ -1369,6 +2383,7 @@ mod tests {
             &working_dir,
             Some("/usr/local/bin/claude"),
             "/bin/zsh",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1387,6 +2402,7 @@ mod tests {
             &working_dir,
             Some("unknown-agent"),
             "/bin/bash",
+            &Config::default(),
         );
         assert_eq!(
             result,
@@ -1394,8 +2410,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rewrite_codex_command_posix() {
+        let prompt_file = PathBuf::from("/tmp/worktree/PROMPT.md");
+        let working_dir = PathBuf::from("/tmp/worktree");
+
+        let config = Config::default();
+        let command = config.agent_launch_command("codex");
+        let result = rewrite_agent_command(
+            &command,
+            &prompt_file,
+            &working_dir,
+            Some("codex"),
+            "/bin/bash",
+            &config,
+        );
+        assert_eq!(
+            result,
+            Some(format!(" {} -- \"$(cat PROMPT.md)\"", command))
+        );
+    }
+
+    #[test]
+    fn test_rewrite_skips_pasted_prompt() {
+        let prompt_file = PathBuf::from("/tmp/worktree/PROMPT.md");
+        let working_dir = PathBuf::from("/tmp/worktree");
+
+        // aider takes its prompt typed in after launch
+        let result = rewrite_agent_command(
+            "aider",
+            &prompt_file,
+            &working_dir,
+            Some("aider"),
+            "/bin/bash",
+            &Config::default(),
+        );
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_rewrite_uses_configured_prompt_args() {
+        let prompt_file = PathBuf::from("/tmp/worktree/PROMPT.md");
+        let working_dir = PathBuf::from("/tmp/worktree");
+
+        let config: Config =
+            serde_yaml::from_str("agents:\n  mycli:\n    prompt: --task {prompt} --yes\n").unwrap();
+        let result = rewrite_agent_command(
+            "mycli",
+            &prompt_file,
+            &working_dir,
+            Some("mycli"),
+            "/bin/bash",
+            &config,
+        );
+        assert_eq!(
+            result,
+            Some(" mycli --task \"$(cat PROMPT.md)\" --yes".to_string())
+        );
+    }
+
     // --- inject_status_format tests ---
 
+    // --- parse_window_health tests ---
+
+    #[test]
+    fn test_parse_window_health_detects_agent() {
+        let output = "wm-a\t0\tclaude\t\nwm-a\t0\tzsh\t\nwm-b\t0\tzsh\t";
+        let health = parse_window_health(output, "claude --verbose");
+        assert_eq!(
+            health["wm-a"],
+            WindowHealth {
+                panes: 2,
+                dead_panes: 0,
+                agent_running: true
+            }
+        );
+        assert!(!health["wm-b"].agent_running);
+    }
+
+    #[test]
+    fn test_parse_window_health_uses_stored_command_and_counts_dead_panes() {
+        // Agent runs under a different process name than the configured command
+        let output = "wm-a\t0\tnode\tnode\nwm-b\t1\tclaude\tclaude";
+        let health = parse_window_health(output, "/usr/local/bin/claude");
+        assert!(health["wm-a"].agent_running);
+        assert_eq!(health["wm-b"].dead_panes, 1);
+        assert!(!health["wm-b"].agent_running);
+    }
+
+    #[test]
+    fn test_parse_window_health_keeps_last_line_without_trailing_field() {
+        // Trimmed output loses the last line's empty stored command and its tab
+        let output = "wm-a\t0\tclaude\t\nwm-b\t0\tclaude";
+        let health = parse_window_health(output, "claude");
+        assert!(health["wm-a"].agent_running);
+        assert_eq!(health["wm-b"].panes, 1);
+        assert!(health["wm-b"].agent_running);
+    }
+
+    #[test]
+    fn test_parse_worktree_ops() {
+        let output = "@workmux_group \"g1\"\n\
+                      @workmux_op_feature-a \"running post-create hooks (1/2)…\"\n\
+                      @workmux_op_fix.b merging…\n\
+                      @workmux_op_quoted \"say \\\"hi\\\"\"\n";
+        let ops = parse_worktree_ops(output);
+        assert_eq!(ops.len(), 3);
+        assert_eq!(ops["feature-a"], "running post-create hooks (1/2)…");
+        assert_eq!(ops["fix.b"], "merging…");
+        assert_eq!(ops["quoted"], "say \"hi\"");
+    }
+
     #[test]
     fn test_inject_status_format_standard() {
         // Standard default format with conditional window_flags
@@ -1403,7 +2528,7 @@ mod tests {
         let result = inject_status_format(input);
         assert_eq!(
             result,
-            "#I:#W#{?@workmux_status, #{@workmux_status},}#{?window_flags,#{window_flags}, }"
+            "#I:#W#{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}#{?window_flags,#{window_flags}, }"
         );
     }
 
@@ -1412,7 +2537,10 @@ mod tests {
         // Short format with #{F}
         let input = "#I:#W#{F}";
         let result = inject_status_format(input);
-        assert_eq!(result, "#I:#W#{?@workmux_status, #{@workmux_status},}#{F}");
+        assert_eq!(
+            result,
+            "#I:#W#{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}#{F}"
+        );
     }
 
     #[test]
@@ -1420,7 +2548,10 @@ mod tests {
         // Format without window_flags - append to end
         let input = "#I:#W";
         let result = inject_status_format(input);
-        assert_eq!(result, "#I:#W#{?@workmux_status, #{@workmux_status},}");
+        assert_eq!(
+            result,
+            "#I:#W#{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}"
+        );
     }
 
     #[test]
@@ -1430,7 +2561,7 @@ mod tests {
         let result = inject_status_format(input);
         assert_eq!(
             result,
-            "#[fg=blue]#I#[default] #{?@workmux_status, #{@workmux_status},}#{?window_flags,#{window_flags},}"
+            "#[fg=blue]#I#[default] #{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}#{?window_flags,#{window_flags},}"
         );
     }
 
@@ -1441,7 +2572,7 @@ mod tests {
         let result = inject_status_format(input);
         assert_eq!(
             result,
-            "#I:#W#{?@workmux_status, #{@workmux_status},}#{window_flags}"
+            "#I:#W#{?@workmux_op, #{@workmux_op},}#{?@workmux_status, #{@workmux_status},}#{window_flags}"
         );
     }
 }
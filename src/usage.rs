@@ -0,0 +1,172 @@
+//! Spend and context window use reported by agents, for the dashboard's Cost
+//! and Ctx columns.
+//!
+//! Claude Code passes both to its status line command as JSON, so
+//! `workmux statusline` records them as pane options. Agents that show them
+//! in their pane title instead are read with the `usage_pattern` of their
+//! agent profile.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::tmux::AgentPane;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentUsage {
+    /// Session cost so far, in US dollars
+    pub cost_usd: Option<f64>,
+    /// Share of the context window in use, 0-100
+    pub context_pct: Option<u8>,
+}
+
+impl AgentUsage {
+    pub fn is_empty(&self) -> bool {
+        self.cost_usd.is_none() && self.context_pct.is_none()
+    }
+
+    /// Fill unset fields from another reading
+    fn or(self, fallback: Self) -> Self {
+        Self {
+            cost_usd: self.cost_usd.or(fallback.cost_usd),
+            context_pct: self.context_pct.or(fallback.context_pct),
+        }
+    }
+}
+
+/// Usage from Claude Code's status line JSON: `cost.total_cost_usd`, and
+/// `context_window.used_percentage` or, in versions without it, the tokens of
+/// the last request over the window size
+pub fn from_status_line(json: &Value) -> AgentUsage {
+    let cost_usd = json.pointer("/cost/total_cost_usd").and_then(Value::as_f64);
+    let window = json.get("context_window");
+    let used_pct = window
+        .and_then(|w| w.get("used_percentage"))
+        .and_then(Value::as_f64);
+    let context_pct = used_pct.or_else(|| {
+        let window = window?;
+        let size = window.get("context_window_size")?.as_f64()?;
+        let usage = window.get("current_usage")?;
+        let tokens: f64 = [
+            "input_tokens",
+            "cache_creation_input_tokens",
+            "cache_read_input_tokens",
+        ]
+        .iter()
+        .filter_map(|key| usage.get(key).and_then(Value::as_f64))
+        .sum();
+        (size > 0.0).then(|| tokens * 100.0 / size)
+    });
+    AgentUsage {
+        cost_usd,
+        context_pct: context_pct.map(|pct| pct.round().clamp(0.0, 100.0) as u8),
+    }
+}
+
+/// Usage from a pane title with `pattern`'s named groups `cost` and
+/// `context`, e.g. `\$(?<cost>[\d.]+).*?(?<context>\d+)% ctx`
+pub fn from_title(title: &str, pattern: &Regex) -> AgentUsage {
+    let Some(caps) = pattern.captures(title) else {
+        return AgentUsage::default();
+    };
+    AgentUsage {
+        cost_usd: caps.name("cost").and_then(|m| m.as_str().parse().ok()),
+        context_pct: caps
+            .name("context")
+            .and_then(|m| m.as_str().parse::<f64>().ok())
+            .map(|pct| pct.round().clamp(0.0, 100.0) as u8),
+    }
+}
+
+/// Fill in usage from the pane titles of agents whose profile has a
+/// `usage_pattern`. Usage recorded by `workmux statusline` wins.
+pub fn fill_from_titles(agents: &mut [AgentPane], config: &Config) {
+    let mut patterns: HashMap<String, Option<Regex>> = HashMap::new();
+    for agent in agents {
+        let (Some(name), Some(title)) = (agent.agent.as_deref(), agent.pane_title.as_deref())
+        else {
+            continue;
+        };
+        let pattern = patterns.entry(name.to_string()).or_insert_with(|| {
+            let pattern = config.agent_profile_named(name).usage_pattern?;
+            Regex::new(&pattern)
+                .inspect_err(|e| tracing::debug!(agent = name, error = %e, "usage:bad pattern"))
+                .ok()
+        });
+        if let Some(pattern) = pattern {
+            agent.usage = agent.usage.or(from_title(title, pattern));
+        }
+    }
+}
+
+/// A cost for a narrow column: "$0.42", "$12.30", "$130"
+pub fn format_cost(cost_usd: f64) -> String {
+    if cost_usd >= 100.0 {
+        format!("${:.0}", cost_usd)
+    } else {
+        format!("${:.2}", cost_usd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn status_line_reads_cost_and_used_percentage() {
+        let json = json!({
+            "model": {"display_name": "Opus"},
+            "cost": {"total_cost_usd": 1.234},
+            "context_window": {"used_percentage": 42.6, "context_window_size": 200000},
+        });
+        assert_eq!(
+            from_status_line(&json),
+            AgentUsage {
+                cost_usd: Some(1.234),
+                context_pct: Some(43),
+            }
+        );
+    }
+
+    #[test]
+    fn status_line_computes_context_from_current_usage() {
+        let json = json!({
+            "context_window": {
+                "context_window_size": 200000,
+                "current_usage": {
+                    "input_tokens": 1000,
+                    "output_tokens": 5000,
+                    "cache_creation_input_tokens": 9000,
+                    "cache_read_input_tokens": 40000,
+                },
+            },
+        });
+        let usage = from_status_line(&json);
+        assert_eq!(usage.cost_usd, None);
+        assert_eq!(usage.context_pct, Some(25));
+        assert!(from_status_line(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn title_pattern_reads_named_groups() {
+        let pattern = Regex::new(r"\$(?<cost>[\d.]+) .*?(?<context>\d+)% ctx").unwrap();
+        assert_eq!(
+            from_title("fix login · $0.87 · 61% ctx", &pattern),
+            AgentUsage {
+                cost_usd: Some(0.87),
+                context_pct: Some(61),
+            }
+        );
+        assert!(from_title("fix login", &pattern).is_empty());
+    }
+
+    #[test]
+    fn cost_fits_a_narrow_column() {
+        assert_eq!(format_cost(0.4249), "$0.42");
+        assert_eq!(format_cost(12.3), "$12.30");
+        assert_eq!(format_cost(129.6), "$130");
+    }
+}
@@ -0,0 +1,139 @@
+//! Version control backend selection.
+//!
+//! Worktree operations in `add`, `merge`, `remove` and `list` go through
+//! [`Vcs`], which uses git worktrees or jj workspaces. jj is opt-in with
+//! `vcs: jj` in `.workmux.yaml`: a colocated jj repository can have git
+//! worktrees of its own, which the jj backend doesn't list.
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::config::Config;
+use crate::{git, jj, metadata};
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Vcs {
+    #[default]
+    Git,
+    Jj,
+}
+
+impl Vcs {
+    /// The configured backend, git unless `vcs: jj` is set
+    pub fn from_config(config: &Config) -> Self {
+        let vcs = config.vcs.unwrap_or_default();
+        debug!(?vcs, "vcs:selected");
+        vcs
+    }
+
+    /// Error unless the current directory is in a repository of this backend
+    pub fn ensure_repo(self) -> Result<()> {
+        match self {
+            Vcs::Git if !git::is_git_repo()? => Err(anyhow!("Not in a git repository")),
+            Vcs::Jj if jj::workspace_root().is_err() => Err(anyhow!("Not in a jj repository")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Root of the main worktree (jj: the default workspace)
+    pub fn main_root(self) -> Result<PathBuf> {
+        match self {
+            Vcs::Git => git::get_main_worktree_root(),
+            Vcs::Jj => jj::main_workspace_root(),
+        }
+    }
+
+    /// All worktrees as (path, branch), the main one first. jj workspaces
+    /// are found where workmux creates them, under the configured
+    /// `worktree_dir`.
+    pub fn list_worktrees(
+        self,
+        config: &Config,
+        main_root: &Path,
+        main_branch: &str,
+    ) -> Result<Vec<(PathBuf, String)>> {
+        match self {
+            Vcs::Git => git::list_worktrees(),
+            Vcs::Jj => jj::list_workspaces(main_root)?
+                .into_iter()
+                .map(|name| {
+                    if name == jj::DEFAULT_WORKSPACE {
+                        return Ok((main_root.to_path_buf(), main_branch.to_string()));
+                    }
+                    let path = config.worktree_path(main_root, &name)?;
+                    let branch = metadata::load(&name).map_or(name, |m| m.branch);
+                    Ok((path, branch))
+                })
+                .collect(),
+        }
+    }
+
+    /// Find a worktree by handle, then by branch, as (path, branch)
+    pub fn find_worktree(
+        self,
+        name: &str,
+        config: &Config,
+        main_root: &Path,
+        main_branch: &str,
+    ) -> Result<(PathBuf, String)> {
+        if self == Vcs::Git {
+            return git::find_worktree(name);
+        }
+        let worktrees = self.list_worktrees(config, main_root, main_branch)?;
+        worktrees
+            .iter()
+            .find(|(path, _)| path.file_name().is_some_and(|dir| dir == name))
+            .or_else(|| worktrees.iter().find(|(_, branch)| branch == name))
+            .cloned()
+            .ok_or_else(|| git::WorktreeNotFound(name.to_string()).into())
+    }
+
+    /// Whether the worktree has changes not yet committed (jj: in its
+    /// working-copy change)
+    pub fn has_uncommitted_changes(self, worktree_path: &Path) -> Result<bool> {
+        match self {
+            Vcs::Git => git::has_uncommitted_changes(worktree_path),
+            Vcs::Jj => jj::has_working_copy_changes(worktree_path),
+        }
+    }
+
+    /// Whether `branch` has changes not in `base`. For jj, `handle`'s
+    /// workspace is checked, since its bookmark doesn't move with new
+    /// changes.
+    pub fn is_unmerged(self, main_root: &Path, handle: &str, branch: &str, base: &str) -> bool {
+        match self {
+            Vcs::Git => git::get_unmerged_branches(base)
+                .map(|branches| branches.contains(branch))
+                .unwrap_or(false),
+            Vcs::Jj => jj::commit_ids(main_root, &jj::unmerged_revset(handle, base))
+                .is_ok_and(|ids| !ids.is_empty()),
+        }
+    }
+
+    /// Drop the version control's record of a worktree whose directory has
+    /// been removed
+    pub fn forget_worktree(self, main_root: &Path, handle: &str) -> Result<()> {
+        match self {
+            Vcs::Git => git::prune_worktrees(),
+            Vcs::Jj => jj::forget_workspace(main_root, handle),
+        }
+    }
+
+    pub fn delete_branch(self, main_root: &Path, branch: &str, force: bool) -> Result<()> {
+        match self {
+            Vcs::Git => git::delete_branch(branch, force),
+            Vcs::Jj => jj::delete_bookmark(main_root, branch),
+        }
+    }
+
+    /// Error for a feature only the git backend supports
+    pub fn require_git(self, feature: &str) -> Result<()> {
+        match self {
+            Vcs::Git => Ok(()),
+            Vcs::Jj => Err(anyhow!("{} is not supported in jj repositories", feature)),
+        }
+    }
+}
@@ -4,10 +4,13 @@ use std::path::Path;
 use std::time::SystemTime;
 use std::{thread, time::Duration};
 
-use crate::{cmd, git, tmux};
+use crate::config::AfterRemove;
+use crate::vcs::Vcs;
+use crate::{git, metadata, registry, tmux};
 use tracing::{debug, info, warn};
 
 use super::context::WorkflowContext;
+use super::hooks::{self, Hook, HookEnv};
 use super::types::CleanupResult;
 
 const WINDOW_CLOSE_DELAY_MS: u64 = 300;
@@ -78,6 +81,7 @@ pub fn cleanup(
     worktree_path: &Path,
     force: bool,
     keep_branch: bool,
+    delete_remote: bool,
 ) -> Result<CleanupResult> {
     info!(
         branch = branch_name,
@@ -85,6 +89,7 @@ pub fn cleanup(
         path = %worktree_path.display(),
         force,
         keep_branch,
+        delete_remote,
         "cleanup:start"
     );
     // Change the CWD to main worktree before any destructive operations.
@@ -106,9 +111,19 @@ pub fn cleanup(
         tmux_window_killed: false,
         worktree_removed: false,
         local_branch_deleted: false,
+        remote_branch_deleted: false,
         window_to_close_later: None,
     };
 
+    // Read while the local branch, which records it, still exists
+    let upstream = (delete_remote && context.vcs == Vcs::Git)
+        .then(|| git::branch_upstream(branch_name))
+        .flatten();
+
+    // Resolved while the worktree still exists
+    let hook_env =
+        HookEnv::new(handle, worktree_path, &context.main_worktree_root).with_branch(branch_name);
+
     // Helper closure to perform the actual filesystem and git cleanup.
     // This avoids code duplication while enforcing the correct operational order.
     let perform_fs_git_cleanup = |result: &mut CleanupResult| -> Result<()> {
@@ -121,30 +136,9 @@ pub fn cleanup(
                     count = pre_remove_hooks.len(),
                     "cleanup:running pre-remove hooks"
                 );
-                // Resolve absolute paths for environment variables.
-                // canonicalize() ensures symlinks are resolved and paths are absolute.
-                let abs_worktree_path = worktree_path
-                    .canonicalize()
-                    .unwrap_or_else(|_| worktree_path.to_path_buf());
-                let abs_project_root = context
-                    .main_worktree_root
-                    .canonicalize()
-                    .unwrap_or_else(|_| context.main_worktree_root.clone());
-                let worktree_path_str = abs_worktree_path.to_string_lossy();
-                let project_root_str = abs_project_root.to_string_lossy();
-                let hook_env = [
-                    ("WORKMUX_HANDLE", handle),
-                    ("WM_HANDLE", handle),
-                    ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-                    ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-                ];
-                for command in pre_remove_hooks {
-                    // Run the hook with the worktree path as the working directory.
-                    // This allows for relative paths like `node_modules` in the command.
-                    cmd::shell_command_with_env(command, worktree_path, &hook_env).with_context(
-                        || format!("Failed to run pre-remove command: '{}'", command),
-                    )?;
-                }
+                // Run the hooks with the worktree path as the working directory.
+                // This allows for relative paths like `node_modules` in the command.
+                hooks::run_before(Hook::PreRemove, pre_remove_hooks, worktree_path, &hook_env)?;
             }
         } else {
             debug!(
@@ -218,18 +212,51 @@ pub fn cleanup(
             }
         }
 
+        metadata::delete(handle);
+        registry::forget(worktree_path);
+
         // 2. Prune worktrees to clean up git's metadata.
         // Git will see the original path as missing since we renamed it.
-        git::prune_worktrees().context("Failed to prune worktrees")?;
+        // jj workspaces are forgotten by name instead.
+        context
+            .vcs
+            .forget_worktree(&context.main_worktree_root, handle)
+            .context("Failed to prune worktrees")?;
         debug!("cleanup:git worktrees pruned");
 
-        // 3. Delete the local branch (unless keeping it).
-        if !keep_branch {
-            git::delete_branch(branch_name, force).context("Failed to delete local branch")?;
+        // 3. Delete the local branch (unless keeping it). Detached worktrees have none.
+        if !keep_branch && !git::is_detached(branch_name) {
+            context
+                .vcs
+                .delete_branch(&context.main_worktree_root, branch_name, force)
+                .context("Failed to delete local branch")?;
             result.local_branch_deleted = true;
             info!(branch = branch_name, "cleanup:local branch deleted");
         }
 
+        // The remote branch goes last: the local cleanup is done even if
+        // the remote can't be reached, so failing here would only mislead
+        if let Some((remote, remote_branch)) = &upstream {
+            match git::delete_remote_branch(remote, remote_branch) {
+                Ok(deleted) => {
+                    result.remote_branch_deleted = deleted;
+                    info!(
+                        remote,
+                        branch = remote_branch,
+                        deleted,
+                        "cleanup:remote branch deleted"
+                    );
+                }
+                Err(e) => {
+                    warn!(error = %e, "cleanup:failed to delete remote branch");
+                    eprintln!(
+                        "Warning: could not delete '{}' on '{}': {:#}",
+                        remote_branch, remote, e
+                    );
+                }
+            }
+        }
+
         // 4. Best-effort deletion of the trash directory.
         // If the shell is inside this directory, remove_dir_all on the root might fail
         // immediately. Clearing children first ensures we reclaim the space.
@@ -322,6 +349,14 @@ pub fn cleanup(
         perform_fs_git_cleanup(&mut result)?;
     }
 
+    // The worktree is gone, so these run in the main worktree
+    hooks::run_after(
+        Hook::PostRemove,
+        context.config.post_remove.as_ref(),
+        &context.main_worktree_root,
+        &hook_env,
+    );
+
     Ok(result)
 }
 
@@ -329,16 +364,16 @@ pub fn cleanup(
 /// Handles both cases: running inside the source window (async) and outside (sync).
 /// `target_window_name` is the tmux window name of the merge target.
 /// `source_handle` is the tmux window name of the branch being merged/removed.
+/// Where to go is set by `after_remove`; nothing moves unless the source
+/// window is still the active one when it closes.
 pub fn navigate_to_target_and_close(
-    prefix: &str,
+    context: &WorkflowContext,
     target_window_name: &str,
     source_handle: &str,
     cleanup_result: &CleanupResult,
 ) -> Result<()> {
-    /// Helper function to shell-escape strings for safe inclusion in shell commands
-    fn shell_escape(s: &str) -> String {
-        format!("'{}'", s.replace('\'', r#"'\''"#))
-    }
+    let prefix = context.prefix.as_str();
+    let after_remove = context.config.after_remove.unwrap_or_default();
 
     // Check if target window exists
     let tmux_running = tmux::is_running()?;
@@ -352,65 +387,43 @@ pub fn navigate_to_target_and_close(
         target_window_name = target_window_name,
         tmux_running = tmux_running,
         target_exists = target_exists,
+        after_remove = ?after_remove,
         window_to_close = ?cleanup_result.window_to_close_later,
         "navigate_to_target_and_close:entry"
     );
-    if !tmux_running || !target_exists {
-        // If target window doesn't exist, still need to close source window if running inside it
-        if let Some(ref window_to_close) = cleanup_result.window_to_close_later {
-            let delay = Duration::from_millis(WINDOW_CLOSE_DELAY_MS);
-            let delay_secs = format!("{:.3}", delay.as_secs_f64());
-            let source_spec = format!("={}", window_to_close);
-            let source_escaped = shell_escape(&source_spec);
-            let script = format!(
-                "sleep {delay}; tmux kill-window -t {source} >/dev/null 2>&1",
-                delay = delay_secs,
-                source = source_escaped,
-            );
-            debug!(
-                script = script,
-                "navigate_to_target_and_close:kill_only_script"
-            );
-            match tmux::run_shell(&script) {
-                Ok(_) => info!(
-                    window = window_to_close,
-                    script = script,
-                    "cleanup:scheduled window close"
-                ),
-                Err(e) => warn!(
-                    window = window_to_close,
-                    error = ?e,
-                    "cleanup:failed to schedule window close",
-                ),
-            }
-        }
-        return Ok(());
-    }
 
     if let Some(ref window_to_close) = cleanup_result.window_to_close_later {
-        // Running inside a matching window: schedule both navigation and kill together
-        let delay = Duration::from_millis(WINDOW_CLOSE_DELAY_MS);
-        let delay_secs = format!("{:.3}", delay.as_secs_f64());
-        let target_spec = format!("={}", tmux::prefixed(prefix, target_window_name));
-        let source_spec = format!("={}", window_to_close);
-        let target_escaped = shell_escape(&target_spec);
-        let source_escaped = shell_escape(&source_spec);
-        let script = format!(
-            "sleep {delay}; tmux select-window -t {target} >/dev/null 2>&1; tmux kill-window -t {source} >/dev/null 2>&1",
-            delay = delay_secs,
-            target = target_escaped,
-            source = source_escaped,
+        if after_remove == AfterRemove::None {
+            info!(
+                window = window_to_close,
+                "cleanup:leaving window open (after_remove: none)"
+            );
+            return Ok(());
+        }
+        // Running inside a matching window: schedule navigation and kill together,
+        // since closing the window ends this process
+        let target =
+            (tmux_running && target_exists).then(|| tmux::prefixed(prefix, target_window_name));
+        let dashboard_command = format!(
+            "{} dashboard",
+            std::env::current_exe()
+                .map(|exe| shell_escape(&exe.to_string_lossy()))
+                .unwrap_or_else(|_| "workmux".to_string())
         );
-        debug!(
-            script = script,
-            "navigate_to_target_and_close:nav_and_kill_script"
+        let script = close_script(
+            after_remove,
+            window_to_close,
+            target.as_deref(),
+            &dashboard_command,
+            Duration::from_millis(WINDOW_CLOSE_DELAY_MS),
         );
+        debug!(script = script, "navigate_to_target_and_close:close_script");
 
         match tmux::run_shell(&script) {
             Ok(_) => info!(
                 window = window_to_close,
                 target = target_window_name,
-                "cleanup:scheduled navigation to target and window close"
+                "cleanup:scheduled navigation and window close"
             ),
             Err(e) => warn!(
                 window = window_to_close,
@@ -418,7 +431,11 @@ pub fn navigate_to_target_and_close(
                 "cleanup:failed to schedule navigation and window close",
             ),
         }
-    } else if !cleanup_result.tmux_window_killed {
+    } else if tmux_running
+        && target_exists
+        && !cleanup_result.tmux_window_killed
+        && after_remove == AfterRemove::Main
+    {
         // Running outside and windows weren't killed yet (shouldn't happen normally)
         // but handle it for completeness
         tmux::select_window(prefix, target_window_name)?;
@@ -431,3 +448,88 @@ pub fn navigate_to_target_and_close(
 
     Ok(())
 }
+
+/// Shell-escape a string for safe inclusion in shell commands
+fn shell_escape(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r#"'\''"#))
+}
+
+/// Script run by tmux to close `source` (a full window name) after `delay`,
+/// moving to where `after_remove` says if `source` is still the active window.
+/// `target` is the full name of the main/target window, if it exists.
+fn close_script(
+    after_remove: AfterRemove,
+    source: &str,
+    target: Option<&str>,
+    dashboard_command: &str,
+    delay: Duration,
+) -> String {
+    let source = shell_escape(&format!("={}", source));
+    let mut script = format!(
+        "sleep {:.3}; active=$(tmux display-message -p -t {} '#{{window_active}}' 2>/dev/null); ",
+        delay.as_secs_f64(),
+        source
+    );
+    if after_remove == AfterRemove::Main
+        && let Some(target) = target
+    {
+        script.push_str(&format!(
+            "[ \"$active\" = 1 ] && tmux select-window -t {} >/dev/null 2>&1; ",
+            shell_escape(&format!("={}", target))
+        ));
+    }
+    // tmux falls back to the last window when the active one is killed
+    script.push_str(&format!("tmux kill-window -t {} >/dev/null 2>&1", source));
+    if after_remove == AfterRemove::Dashboard {
+        script.push_str(&format!(
+            "; [ \"$active\" = 1 ] && tmux display-popup -w 90% -h 90% -E {}",
+            shell_escape(dashboard_command)
+        ));
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(after_remove: AfterRemove, target: Option<&str>) -> String {
+        close_script(
+            after_remove,
+            "wm-feature",
+            target,
+            "'workmux' dashboard",
+            Duration::from_millis(300),
+        )
+    }
+
+    #[test]
+    fn close_script_selects_main_only_if_still_active() {
+        assert_eq!(
+            script(AfterRemove::Main, Some("wm-main")),
+            "sleep 0.300; active=$(tmux display-message -p -t '=wm-feature' '#{window_active}' 2>/dev/null); \
+             [ \"$active\" = 1 ] && tmux select-window -t '=wm-main' >/dev/null 2>&1; \
+             tmux kill-window -t '=wm-feature' >/dev/null 2>&1"
+        );
+    }
+
+    #[test]
+    fn close_script_without_target_only_kills() {
+        for after_remove in [AfterRemove::Main, AfterRemove::Previous] {
+            let script = script(after_remove, None);
+            assert!(!script.contains("select-window"));
+            assert!(script.ends_with("tmux kill-window -t '=wm-feature' >/dev/null 2>&1"));
+        }
+        assert!(!script(AfterRemove::Previous, Some("wm-main")).contains("select-window"));
+    }
+
+    #[test]
+    fn close_script_opens_dashboard_after_kill() {
+        let script = script(AfterRemove::Dashboard, Some("wm-main"));
+        assert!(!script.contains("select-window"));
+        assert!(script.ends_with(
+            "tmux kill-window -t '=wm-feature' >/dev/null 2>&1; \
+             [ \"$active\" = 1 ] && tmux display-popup -w 90% -h 90% -E ''\\''workmux'\\'' dashboard'"
+        ));
+    }
+}
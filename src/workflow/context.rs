@@ -1,6 +1,7 @@
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
 use std::path::PathBuf;
 
+use crate::vcs::Vcs;
 use crate::{config, git, tmux};
 use tracing::debug;
 
@@ -13,34 +14,41 @@ pub struct WorkflowContext {
     pub main_branch: String,
     pub prefix: String,
     pub config: config::Config,
+    /// Whether worktrees are git worktrees or jj workspaces
+    pub vcs: Vcs,
 }
 
 impl WorkflowContext {
     /// Create a new workflow context
     ///
-    /// Performs the git repository check and gathers all commonly needed data.
+    /// Performs the repository check and gathers all commonly needed data.
     /// Does NOT check if tmux is running or change the current directory - those
     /// are optional operations that can be performed via helper methods.
     pub fn new(config: config::Config) -> Result<Self> {
-        if !git::is_git_repo()? {
-            return Err(anyhow!("Not in a git repository"));
-        }
+        let vcs = Vcs::from_config(&config);
+        vcs.ensure_repo()?;
 
-        let main_worktree_root =
-            git::get_main_worktree_root().context("Could not find the main git worktree")?;
+        let main_worktree_root = vcs
+            .main_root()
+            .context("Could not find the main worktree")?;
 
         let main_branch = if let Some(ref branch) = config.main_branch {
             branch.clone()
+        } else if vcs == Vcs::Jj {
+            // jj has no default bookmark to ask for
+            "main".to_string()
         } else {
             git::get_default_branch().context("Failed to determine the main branch")?
         };
 
         let prefix = config.window_prefix().to_string();
+        tmux::set_window_session(config.window_session(&main_worktree_root));
 
         debug!(
             main_worktree_root = %main_worktree_root.display(),
             main_branch = %main_branch,
             prefix = %prefix,
+            ?vcs,
             "workflow_context:created"
         );
 
@@ -49,19 +57,20 @@ impl WorkflowContext {
             main_branch,
             prefix,
             config,
+            vcs,
         })
     }
 
+    /// Path of the worktree for `handle`, following the `worktree_dir` config
+    pub fn worktree_path(&self, handle: &str) -> Result<PathBuf> {
+        self.config.worktree_path(&self.main_worktree_root, handle)
+    }
+
     /// Ensure tmux is running, returning an error if not
     ///
     /// Call this at the start of workflows that require tmux.
     pub fn ensure_tmux_running(&self) -> Result<()> {
-        if !tmux::is_running()? {
-            return Err(anyhow!(
-                "tmux is not running. Please start a tmux session first."
-            ));
-        }
-        Ok(())
+        tmux::ensure_running()
     }
 
     /// Change working directory to main worktree root
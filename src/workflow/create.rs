@@ -1,7 +1,11 @@
 use anyhow::{Context, Result, anyhow};
 use std::path::Path;
 
-use crate::{git, spinner, tmux};
+use crate::error::ErrorKind;
+use crate::metadata::{self, SetupSteps, WorktreeMetadata};
+use crate::prompt::Prompt;
+use crate::vcs::Vcs;
+use crate::{daemon, git, jj, spinner, tmux};
 use tracing::{debug, info, warn};
 
 /// Check if a path is registered as a git worktree.
@@ -33,8 +37,33 @@ use super::context::WorkflowContext;
 use super::setup;
 use super::types::{CreateArgs, CreateResult, SetupOptions};
 
+/// Checks shared by git worktrees and jj workspaces, before anything is created
+fn preflight(context: &WorkflowContext, handle: &str, options: &SetupOptions) -> Result<()> {
+    // Validate pane config before any other operations
+    if let Some(panes) = &context.config.panes {
+        crate::config::validate_panes_config(panes)?;
+    }
+
+    if options.open_window {
+        context.ensure_tmux_running()?;
+    }
+
+    // Check tmux window using handle (the display name)
+    if options.open_window && tmux::window_exists(&context.prefix, handle)? {
+        return Err(ErrorKind::AlreadyExists.msg(format!(
+            "A tmux window named '{}{}' already exists",
+            context.prefix, handle
+        )));
+    }
+    Ok(())
+}
+
 /// Create a new worktree with tmux window and panes
 pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResult> {
+    if context.vcs == Vcs::Jj {
+        return create_workspace(context, args);
+    }
+
     let CreateArgs {
         branch_name,
         handle,
@@ -43,6 +72,8 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         prompt,
         options,
         agent,
+        package,
+        detached,
     } = args;
 
     info!(
@@ -50,44 +81,40 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         handle = handle,
         base = ?base_branch,
         remote = ?remote_branch,
+        package = ?package,
+        detached,
         "create:start"
     );
 
-    // Validate pane config before any other operations
-    if let Some(panes) = &context.config.panes {
-        crate::config::validate_panes_config(panes)?;
-    }
+    preflight(context, handle, &options)?;
 
-    // Pre-flight checks
-    context.ensure_tmux_running()?;
-
-    // Check tmux window using handle (the display name)
-    if tmux::window_exists(&context.prefix, handle)? {
-        return Err(anyhow!(
-            "A tmux window named '{}{}' already exists",
-            context.prefix,
-            handle
-        ));
+    if let Some(package) = package
+        && !context.main_worktree_root.join(package).is_dir()
+    {
+        return Err(ErrorKind::NotFound.msg(format!(
+            "Package directory '{}' does not exist in {}",
+            package,
+            context.main_worktree_root.display()
+        )));
     }
 
     // Check if branch already has a worktree
-    if git::worktree_exists(branch_name)? {
-        return Err(anyhow!(
+    if !detached && git::worktree_exists(branch_name)? {
+        return Err(ErrorKind::AlreadyExists.msg(format!(
             "A worktree for branch '{}' already exists. Use 'workmux open {}' to open it.",
-            branch_name,
-            branch_name
-        ));
+            branch_name, branch_name
+        )));
     }
 
     // Auto-detect: create branch if it doesn't exist
-    let branch_exists = git::branch_exists(branch_name)?;
+    let branch_exists = !detached && git::branch_exists(branch_name)?;
     if branch_exists && remote_branch.is_some() {
-        return Err(anyhow!(
+        return Err(ErrorKind::AlreadyExists.msg(format!(
             "Branch '{}' already exists. Remove '--remote' or pick a different branch name.",
             branch_name
-        ));
+        )));
     }
-    let create_new = !branch_exists;
+    let create_new = !branch_exists && !detached;
     let mut track_upstream = false;
     debug!(
         branch = branch_name,
@@ -95,14 +122,17 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     );
 
     // Determine the base for the new branch
-    let base_branch_for_creation = if let Some(remote_spec) = remote_branch {
+    let base_branch_for_creation = if detached {
+        // No branch to record a base for: pin the starting commit
+        Some(git::resolve_commit(base_branch.unwrap_or("HEAD"))?)
+    } else if let Some(remote_spec) = remote_branch {
         let spec = git::parse_remote_branch_spec(remote_spec)?;
         if !git::remote_exists(&spec.remote)? {
-            return Err(anyhow!(
+            return Err(ErrorKind::NotFound.msg(format!(
                 "Remote '{}' does not exist. Available remotes: {:?}",
                 spec.remote,
                 git::list_remotes()?
-            ));
+            )));
         }
         spinner::with_spinner(&format!("Fetching from '{}'", spec.remote), || {
             git::fetch_remote(&spec.remote)
@@ -110,17 +140,20 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         .with_context(|| format!("Failed to fetch from remote '{}'", spec.remote))?;
         let remote_ref = format!("{}/{}", spec.remote, spec.branch);
         if !git::branch_exists(&remote_ref)? {
-            return Err(anyhow!(
+            return Err(ErrorKind::NotFound.msg(format!(
                 "Remote branch '{}' was not found. Double-check the name or fetch it manually.",
                 remote_ref
-            ));
+            )));
         }
         track_upstream = true;
         Some(remote_ref)
     } else if create_new {
         if let Some(base) = base_branch {
-            // Use the explicitly provided base branch/commit/tag
-            Some(base.to_string())
+            // Use the explicitly provided base branch/commit/tag. Validate it before
+            // anything is created, and record a name that stays meaningful later:
+            // `@{u}` becomes e.g. `origin/main`, a short SHA the full one.
+            let sha = git::resolve_commit(base)?;
+            Some(git::symbolic_ref_name(base).unwrap_or(sha))
         } else {
             // Default to the current branch when no explicit base was provided
             let current_branch = git::get_current_branch()
@@ -140,33 +173,16 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         None
     };
 
+    // The commit the branch starts from, kept for when the base ref goes away
+    let base_sha = match base_branch_for_creation.as_deref() {
+        Some(base) => Some(git::resolve_commit(base)?),
+        None => None,
+    };
+
     // Determine worktree path: use config.worktree_dir or default to <project>__worktrees pattern
     // Always use main_worktree_root (not repo_root) to ensure consistent paths even when
-    // running from inside an existing worktree.
-    let base_dir = if let Some(ref worktree_dir) = context.config.worktree_dir {
-        let path = Path::new(worktree_dir);
-        if path.is_absolute() {
-            // Use absolute path as-is
-            path.to_path_buf()
-        } else {
-            // Relative path: resolve from main worktree root
-            context.main_worktree_root.join(path)
-        }
-    } else {
-        // Default behavior: <main_worktree_root>/../<project_name>__worktrees
-        let project_name = context
-            .main_worktree_root
-            .file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| anyhow!("Could not determine project name"))?;
-        context
-            .main_worktree_root
-            .parent()
-            .ok_or_else(|| anyhow!("Could not determine parent directory"))?
-            .join(format!("{}__worktrees", project_name))
-    };
-    // Use handle for the worktree directory name (not branch_name)
-    let worktree_path = base_dir.join(handle);
+    // running from inside an existing worktree. The handle (not branch_name) names the directory.
+    let worktree_path = context.worktree_path(handle)?;
 
     // Check if path already exists (handle collision detection)
     if worktree_path.exists() {
@@ -174,12 +190,12 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         // This can happen when cleanup renames a worktree but a background process (build tool,
         // file watcher, shell prompt) recreates the directory structure using stale $PWD.
         if is_registered_worktree(&worktree_path)? {
-            return Err(anyhow!(
+            return Err(ErrorKind::AlreadyExists.msg(format!(
                 "Worktree directory '{}' already exists and is registered with git.\n\
                  This may be from another branch with the same handle.\n\
                  Hint: Use --name to specify a different name.",
                 worktree_path.display()
-            ));
+            )));
         }
 
         // Safety check: if the directory contains a .git file/folder, it might be a
@@ -218,17 +234,51 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         "create:creating worktree"
     );
 
-    git::create_worktree(
-        &worktree_path,
-        branch_name,
-        create_new,
-        base_branch_for_creation.as_deref(),
-        track_upstream,
-    )
-    .context("Failed to create git worktree")?;
+    // Scoped worktrees only check out the package plus any configured extras
+    let sparse_paths: Vec<String> = match package {
+        Some(package) => std::iter::once(package.to_string())
+            .chain(context.config.sparse_paths.clone().unwrap_or_default())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    if let (true, Some(commit)) = (detached, base_branch_for_creation.as_deref()) {
+        git::create_detached_worktree(&worktree_path, commit)
+            .context("Failed to create git worktree")?;
+    } else {
+        git::create_worktree(
+            &worktree_path,
+            branch_name,
+            create_new,
+            base_branch_for_creation.as_deref(),
+            track_upstream,
+            &sparse_paths,
+        )
+        .context("Failed to create git worktree")?;
+    }
+
+    // Let the first plain `git push` set origin/<branch> as the upstream, which
+    // `remove --gone` needs to notice the branch was deleted on the remote
+    if create_new && context.config.auto_track_upstream == Some(true) {
+        match git::ensure_push_auto_setup_remote() {
+            Ok(true) => info!("create:enabled push.autoSetupRemote"),
+            Ok(false) => {}
+            Err(e) => warn!(error = %e, "create:failed to enable push.autoSetupRemote"),
+        }
+    }
+
+    // Record the package scope so panes open in it and `list` can show it
+    if let Some(package) = package {
+        git::set_branch_package(branch_name, package).with_context(|| {
+            format!(
+                "Failed to store package '{}' for branch '{}'",
+                package, branch_name
+            )
+        })?;
+    }
 
     // Store the base branch in git config for future reference (used during removal checks)
-    if let Some(ref base) = base_branch_for_creation {
+    if !detached && let Some(ref base) = base_branch_for_creation {
         git::set_branch_base(branch_name, base).with_context(|| {
             format!(
                 "Failed to store base branch '{}' for branch '{}'",
@@ -241,6 +291,58 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
             "create:stored base branch in git config"
         );
     }
+    if !detached && let Some(ref sha) = base_sha {
+        git::set_branch_base_sha(branch_name, sha).with_context(|| {
+            format!(
+                "Failed to store base commit '{}' for branch '{}'",
+                sha, branch_name
+            )
+        })?;
+    }
+
+    // Remember how the worktree was created for `workmux info`
+    let created = WorktreeMetadata {
+        branch: branch_name.to_string(),
+        created_at: Some(daemon::now_secs()),
+        base: base_branch_for_creation,
+        base_sha,
+        agent: agent
+            .or(context.config.agent.as_deref())
+            .map(str::to_string),
+        prompt: prompt.and_then(|p| p.read_content().ok()),
+        package: package.map(str::to_string),
+        setup: Some(SetupSteps {
+            hooks: options.run_hooks,
+            file_ops: options.run_file_ops,
+            pane_commands: options.run_pane_commands,
+        }),
+        ..Default::default()
+    };
+    finish(
+        context,
+        handle,
+        &worktree_path,
+        created,
+        prompt,
+        options,
+        agent,
+    )
+}
+
+/// Record the worktree's metadata, then set up its files, hooks and tmux window
+fn finish(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    created: WorktreeMetadata,
+    prompt: Option<&Prompt>,
+    options: SetupOptions,
+    agent: Option<&str>,
+) -> Result<CreateResult> {
+    let branch_name = created.branch.as_str();
+    if let Err(e) = metadata::save(handle, &created) {
+        warn!(handle, error = %e, "create:failed to save worktree metadata");
+    }
 
     // Setup the rest of the environment (tmux, files, hooks)
     let prompt_file_path = if let Some(p) = prompt {
@@ -257,12 +359,12 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     let mut result = setup::setup_environment(
         branch_name,
         handle,
-        &worktree_path,
+        worktree_path,
         &context.config,
         &options_with_prompt,
         agent,
     )?;
-    result.base_branch = base_branch_for_creation.clone();
+    result.base_branch = created.base.clone();
     info!(
         branch = branch_name,
         path = %result.worktree_path.display(),
@@ -272,6 +374,119 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     Ok(result)
 }
 
+/// Create a jj workspace named after the handle, on top of the bookmark
+/// `branch_name` if it exists, or else of the base with a new bookmark
+fn create_workspace(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResult> {
+    let CreateArgs {
+        branch_name,
+        handle,
+        base_branch,
+        remote_branch,
+        prompt,
+        options,
+        agent,
+        package,
+        detached,
+    } = args;
+    info!(
+        branch = branch_name,
+        handle = handle,
+        base = ?base_branch,
+        "create:start jj workspace"
+    );
+
+    if remote_branch.is_some() {
+        context.vcs.require_git("--remote")?;
+    }
+    if detached {
+        context.vcs.require_git("--detach")?;
+    }
+    if package.is_some() {
+        context.vcs.require_git("--package")?;
+    }
+    preflight(context, handle, &options)?;
+
+    let main_root = &context.main_worktree_root;
+    if jj::list_workspaces(main_root)?
+        .iter()
+        .any(|name| name == handle)
+    {
+        return Err(ErrorKind::AlreadyExists.msg(format!(
+            "A jj workspace named '{}' already exists. Use 'workmux open {}' to open it.",
+            handle, handle
+        )));
+    }
+
+    let worktree_path = context.worktree_path(handle)?;
+    if worktree_path.exists() {
+        // Left over from a removed workspace if jj doesn't track it
+        if worktree_path.join(".jj").exists() {
+            return Err(anyhow!(
+                "Directory '{}' already exists and contains a .jj directory.\n\
+                 Please remove it manually to prevent data loss.",
+                worktree_path.display()
+            ));
+        }
+        info!(
+            path = %worktree_path.display(),
+            "create:removing orphan directory from previous cleanup"
+        );
+        std::fs::remove_dir_all(&worktree_path).with_context(|| {
+            format!(
+                "Failed to remove orphan directory '{}'. Please remove it manually.",
+                worktree_path.display()
+            )
+        })?;
+    }
+
+    let bookmark_exists = jj::bookmark_exists(main_root, branch_name)?;
+    let base = if bookmark_exists {
+        branch_name
+    } else {
+        base_branch.unwrap_or(&context.main_branch)
+    };
+    let base_sha = jj::commit_ids(main_root, &jj::symbol(base))?
+        .into_iter()
+        .next();
+
+    info!(
+        branch = branch_name,
+        path = %worktree_path.display(),
+        base,
+        "create:creating jj workspace"
+    );
+    jj::add_workspace(main_root, &worktree_path, handle, &jj::symbol(base))?;
+    if !bookmark_exists {
+        jj::create_bookmark(main_root, branch_name, &format!("{}@", handle))?;
+    }
+
+    let created = WorktreeMetadata {
+        branch: branch_name.to_string(),
+        created_at: Some(daemon::now_secs()),
+        base: (!bookmark_exists).then(|| base.to_string()),
+        base_sha,
+        agent: agent
+            .or(context.config.agent.as_deref())
+            .map(str::to_string),
+        prompt: prompt.and_then(|p| p.read_content().ok()),
+        setup: Some(SetupSteps {
+            hooks: options.run_hooks,
+            file_ops: options.run_file_ops,
+            pane_commands: options.run_pane_commands,
+        }),
+        ..Default::default()
+    };
+    finish(
+        context,
+        handle,
+        &worktree_path,
+        created,
+        prompt,
+        options,
+        agent,
+    )
+}
+
 /// Create a new worktree and move uncommitted changes from the current worktree into it.
 pub fn create_with_changes(
     branch_name: &str,
@@ -306,7 +521,9 @@ pub fn create_with_changes(
     }
 
     if git::branch_exists(branch_name)? {
-        return Err(anyhow!("Branch '{}' already exists.", branch_name));
+        return Err(
+            ErrorKind::AlreadyExists.msg(format!("Branch '{}' already exists.", branch_name))
+        );
     }
 
     // 1. Stash changes
@@ -326,6 +543,8 @@ pub fn create_with_changes(
             prompt: None,
             options,
             agent: None,
+            package: None,
+            detached: false,
         },
     ) {
         Ok(result) => result,
@@ -369,6 +588,7 @@ pub fn create_with_changes(
                 &create_result.worktree_path,
                 true,  // force
                 false, // keep_branch
+                false, // delete_remote
             )
             .context(
                 "Rollback failed: could not clean up the new worktree. Please do so manually.",
@@ -376,18 +596,18 @@ pub fn create_with_changes(
 
             // Handle tmux window navigation/closing based on whether we're inside the source window
             cleanup::navigate_to_target_and_close(
-                &context.prefix,
+                context,
                 &context.main_branch,
                 handle,
                 &cleanup_result,
             )?;
 
-            Err(anyhow!(
+            Err(ErrorKind::Conflicts.msg(format!(
                 "Could not apply changes to '{}', likely due to conflicts.\n\n\
                 The new worktree has been removed.\n\
                 Your changes are safe in the latest stash. Run 'git stash pop' manually to resolve.",
                 branch_name
-            ))
+            )))
         }
     }
 }
@@ -0,0 +1,349 @@
+//! Environment and execution of configured hook commands (`post_create`,
+//! `pre_merge`, `post_merge`, `pre_remove`, `post_remove`).
+//!
+//! Hook output goes to the terminal as usual and is also recorded per
+//! worktree under `<git common dir>/workmux/logs/<handle>/`, one file per
+//! hook and run, for `workmux logs`.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::{cmd, git, jj, spinner};
+
+/// Runs of each hook kept per worktree, the latest included
+pub const LOG_RUNS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum Hook {
+    PostCreate,
+    PreMerge,
+    PostMerge,
+    PreRemove,
+    PostRemove,
+}
+
+impl Hook {
+    pub const ALL: [Hook; 5] = [
+        Hook::PostCreate,
+        Hook::PreMerge,
+        Hook::PostMerge,
+        Hook::PreRemove,
+        Hook::PostRemove,
+    ];
+
+    /// The config key, which also names its log files
+    pub fn key(self) -> &'static str {
+        match self {
+            Hook::PostCreate => "post_create",
+            Hook::PreMerge => "pre_merge",
+            Hook::PostMerge => "post_merge",
+            Hook::PreRemove => "pre_remove",
+            Hook::PostRemove => "post_remove",
+        }
+    }
+
+    /// Name used in messages, e.g. "Pre-merge"
+    fn label(self) -> &'static str {
+        match self {
+            Hook::PostCreate => "Post-create",
+            Hook::PreMerge => "Pre-merge",
+            Hook::PostMerge => "Post-merge",
+            Hook::PreRemove => "Pre-remove",
+            Hook::PostRemove => "Post-remove",
+        }
+    }
+}
+
+/// Environment variables passed to hook commands.
+///
+/// Each variable is set under its `WM_` name and a `WORKMUX_` alias.
+pub struct HookEnv {
+    handle: String,
+    vars: Vec<(&'static str, String)>,
+}
+
+impl HookEnv {
+    /// Variables every hook gets: the handle and the absolute paths of the
+    /// worktree and the main project directory
+    pub fn new(handle: &str, worktree_path: &Path, project_root: &Path) -> Self {
+        // canonicalize() ensures symlinks are resolved and paths are absolute
+        let absolute = |path: &Path| {
+            path.canonicalize()
+                .unwrap_or_else(|_| path.to_path_buf())
+                .to_string_lossy()
+                .into_owned()
+        };
+        let worktree_path = absolute(worktree_path);
+        let project_root = absolute(project_root);
+        Self {
+            handle: handle.to_string(),
+            vars: vec![
+                ("WM_HANDLE", handle.to_string()),
+                ("WORKMUX_HANDLE", handle.to_string()),
+                ("WM_WORKTREE_PATH", worktree_path.clone()),
+                ("WORKMUX_WORKTREE_PATH", worktree_path),
+                ("WM_PROJECT_ROOT", project_root.clone()),
+                ("WORKMUX_PROJECT_ROOT", project_root),
+            ],
+        }
+    }
+
+    /// Add the worktree's branch (`WM_BRANCH_NAME`, `WORKMUX_BRANCH`)
+    pub fn with_branch(mut self, branch: &str) -> Self {
+        self.vars.push(("WM_BRANCH_NAME", branch.to_string()));
+        self.vars.push(("WORKMUX_BRANCH", branch.to_string()));
+        self
+    }
+
+    /// Add the branch merged into (`WM_TARGET_BRANCH`, `WORKMUX_TARGET`)
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.vars.push(("WM_TARGET_BRANCH", target.to_string()));
+        self.vars.push(("WORKMUX_TARGET", target.to_string()));
+        self
+    }
+
+    pub fn vars(&self) -> Vec<(&str, &str)> {
+        self.vars.iter().map(|(k, v)| (*k, v.as_str())).collect()
+    }
+}
+
+/// Run hooks for an operation that has already happened (`post_merge`,
+/// `post_remove`). A failing command is reported, but doesn't fail the
+/// operation or stop the commands after it.
+pub fn run_after(hook: Hook, commands: Option<&Vec<String>>, workdir: &Path, env: &HookEnv) {
+    let Some(commands) = commands.filter(|c| !c.is_empty()) else {
+        return;
+    };
+    let phase = hook.label();
+    spinner::message(&format!("Running {} commands...", phase.to_lowercase()));
+    info!(phase, count = commands.len(), "hooks:running");
+    let vars = env.vars();
+    let log = HookLog::start(&env.handle, hook);
+    for command in commands {
+        if let Err(e) = run_logged(command, workdir, &vars, log.as_ref())
+            .with_context(|| format!("{} hook failed: '{}'", phase, command))
+        {
+            warn!(phase, command, error = %e, "hooks:command failed");
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+}
+
+/// Run hooks that guard an operation (`pre_merge`, `pre_remove`), stopping
+/// at the first command that fails
+pub fn run_before(hook: Hook, commands: &[String], workdir: &Path, env: &HookEnv) -> Result<()> {
+    let vars = env.vars();
+    let log = HookLog::start(&env.handle, hook);
+    for command in commands {
+        run_logged(command, workdir, &vars, log.as_ref())
+            .with_context(|| format!("{} hook failed: '{}'", hook.label(), command))?;
+    }
+    Ok(())
+}
+
+/// Directory holding a worktree's hook and agent logs
+pub fn logs_dir(handle: &str) -> Result<PathBuf> {
+    let state_dir = git::get_git_common_dir().or_else(|_| jj::state_dir())?;
+    Ok(logs_dir_in(&state_dir, handle))
+}
+
+/// [`logs_dir`] of the repository whose state lives in `state_dir`
+pub fn logs_dir_in(state_dir: &Path, handle: &str) -> PathBuf {
+    state_dir.join("workmux").join("logs").join(handle)
+}
+
+/// Log of one run of a hook: `<key>.log` is the latest, `<key>.1.log` the
+/// one before, and so on up to [`LOG_RUNS`]
+pub fn log_path(dir: &Path, hook: Hook, run: usize) -> PathBuf {
+    match run {
+        0 => dir.join(format!("{}.log", hook.key())),
+        n => dir.join(format!("{}.{}.log", hook.key(), n)),
+    }
+}
+
+/// Output of the worktree's agent pane, appended to while `log_agent` is on
+pub fn agent_log_path(dir: &Path) -> PathBuf {
+    dir.join("agent.log")
+}
+
+/// Where the output of one run of a hook is recorded
+pub struct HookLog {
+    file: Mutex<File>,
+}
+
+impl HookLog {
+    /// Start a new log for the hook, shifting older runs back and dropping
+    /// the oldest. None if it can't be written: logging never stops a hook.
+    pub fn start(handle: &str, hook: Hook) -> Option<Self> {
+        let dir = logs_dir(handle).ok()?;
+        Self::start_in(&dir, hook)
+            .inspect_err(|e| debug!(handle, hook = hook.key(), error = %e, "hooks:cannot log"))
+            .ok()
+    }
+
+    fn start_in(dir: &Path, hook: Hook) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        for run in (1..LOG_RUNS).rev() {
+            let older = log_path(dir, hook, run - 1);
+            if older.exists() {
+                fs::rename(&older, log_path(dir, hook, run))?;
+            }
+        }
+        let file = File::create(log_path(dir, hook, 0))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, bytes: &[u8]) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(bytes);
+        }
+    }
+}
+
+/// Run a hook command, copying its output to the log as it goes to the
+/// terminal
+pub fn run_logged(
+    command: &str,
+    workdir: &Path,
+    vars: &[(&str, &str)],
+    log: Option<&HookLog>,
+) -> Result<()> {
+    let Some(log) = log else {
+        if !spinner::stdout_reserved() {
+            return cmd::shell_command_with_env(command, workdir, vars);
+        }
+        let status = cmd::shell(command)
+            .current_dir(workdir)
+            .envs(vars.iter().copied())
+            .stdout(std::io::stderr())
+            .status()
+            .with_context(|| format!("Failed to execute shell command: {}", command))?;
+        return exit_ok(status, command);
+    };
+    log.write(format!("$ {}\n", command).as_bytes());
+
+    let mut child = cmd::shell(command);
+    child
+        .current_dir(workdir)
+        .envs(vars.iter().copied())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = child
+        .spawn()
+        .with_context(|| format!("Failed to execute shell command: {}", command))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    std::thread::scope(|scope| {
+        if let Some(stdout) = stdout {
+            scope.spawn(|| {
+                if spinner::stdout_reserved() {
+                    tee(stdout, std::io::stderr(), log)
+                } else {
+                    tee(stdout, std::io::stdout(), log)
+                }
+            });
+        }
+        if let Some(stderr) = stderr {
+            scope.spawn(|| tee(stderr, std::io::stderr(), log));
+        }
+    });
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to execute shell command: {}", command))?;
+
+    if !status.success() {
+        log.write(format!("[exit code {}]\n", status.code().unwrap_or(-1)).as_bytes());
+    }
+    exit_ok(status, command)
+}
+
+fn exit_ok(status: ExitStatus, command: &str) -> Result<()> {
+    if !status.success() {
+        return Err(anyhow!(
+            "Shell command failed with exit code {}: {}",
+            status.code().unwrap_or(-1),
+            command
+        ));
+    }
+    Ok(())
+}
+
+/// Copy a stream to the terminal and the log until it closes
+fn tee(mut from: impl Read, mut to: impl Write, log: &HookLog) {
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = match from.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => n,
+        };
+        let _ = to.write_all(&buf[..n]);
+        let _ = to.flush();
+        log.write(&buf[..n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_env_sets_wm_names_and_workmux_aliases() {
+        let env = HookEnv::new(
+            "fix-login",
+            Path::new("/nonexistent/repo__worktrees/fix-login"),
+            Path::new("/nonexistent/repo"),
+        )
+        .with_branch("fix/login")
+        .with_target("main");
+        let vars = env.vars();
+        let get = |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+
+        assert_eq!(get("WM_HANDLE"), Some("fix-login"));
+        assert_eq!(get("WORKMUX_HANDLE"), Some("fix-login"));
+        assert_eq!(
+            get("WORKMUX_WORKTREE_PATH"),
+            Some("/nonexistent/repo__worktrees/fix-login")
+        );
+        assert_eq!(get("WM_BRANCH_NAME"), Some("fix/login"));
+        assert_eq!(get("WORKMUX_BRANCH"), Some("fix/login"));
+        assert_eq!(get("WM_TARGET_BRANCH"), Some("main"));
+        assert_eq!(get("WORKMUX_TARGET"), Some("main"));
+    }
+
+    #[test]
+    fn hook_logs_keep_the_latest_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        for run in 0..LOG_RUNS + 2 {
+            let log = HookLog::start_in(dir.path(), Hook::PostCreate).unwrap();
+            log.write(format!("run {}", run).as_bytes());
+        }
+        let read = |run| fs::read_to_string(log_path(dir.path(), Hook::PostCreate, run)).ok();
+
+        assert_eq!(read(0).as_deref(), Some("run 6"));
+        assert_eq!(read(1).as_deref(), Some("run 5"));
+        assert_eq!(read(LOG_RUNS - 1).as_deref(), Some("run 2"));
+        assert_eq!(read(LOG_RUNS), None);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), LOG_RUNS);
+    }
+
+    #[test]
+    fn logged_command_output_and_exit_code_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = HookLog::start_in(dir.path(), Hook::PreMerge).unwrap();
+        run_logged("echo out; echo err >&2", dir.path(), &[], Some(&log)).unwrap();
+        assert!(run_logged("exit 3", dir.path(), &[], Some(&log)).is_err());
+
+        let text = fs::read_to_string(log_path(dir.path(), Hook::PreMerge, 0)).unwrap();
+        assert!(text.starts_with("$ echo out; echo err >&2\n"), "{}", text);
+        assert!(text.contains("out\n") && text.contains("err\n"), "{}", text);
+        assert!(text.ends_with("$ exit 3\n[exit code 3]\n"), "{}", text);
+    }
+}
@@ -0,0 +1,207 @@
+//! Adopt worktrees created by other tools (ghq clones, bare-repo layouts,
+//! plain `git worktree add`) into workmux conventions.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::{git, naming};
+
+use super::context::WorkflowContext;
+
+/// A linked worktree found in a repository and what importing did to it
+pub struct ImportedWorktree {
+    pub branch: String,
+    /// Location of the worktree after the import
+    pub path: PathBuf,
+    /// Conventional location, when the worktree was not (or could not be) moved there
+    pub expected_path: Option<PathBuf>,
+    /// True if the worktree was moved to its conventional location
+    pub moved: bool,
+    /// Base branch recorded for a branch that had none
+    pub recorded_base: Option<String>,
+}
+
+/// Find git repositories under `root`, descending at most `max_depth` levels.
+///
+/// Recognizes regular clones (`.git` directory), bare repositories and
+/// bare-repo layouts where `.git` is a file pointing at the bare directory.
+/// Linked worktrees are skipped since they are imported through their
+/// repository. Hidden directories are not searched, and the search does not
+/// descend into a repository once found.
+pub fn find_repos(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    walk(root, max_depth, &mut repos);
+    repos.sort();
+    repos
+}
+
+fn walk(dir: &Path, depth_left: usize, repos: &mut Vec<PathBuf>) {
+    let dot_git = dir.join(".git");
+    if dot_git.is_dir() || is_bare_repo(dir) {
+        repos.push(dir.to_path_buf());
+        return;
+    }
+    if dot_git.is_file() {
+        let is_linked_worktree = std::fs::read_to_string(&dot_git)
+            .map(|content| content.contains("/worktrees/"))
+            .unwrap_or(true);
+        if !is_linked_worktree {
+            repos.push(dir.to_path_buf());
+        }
+        return;
+    }
+    if depth_left == 0 {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        walk(&subdir, depth_left - 1, repos);
+    }
+}
+
+fn is_bare_repo(dir: &Path) -> bool {
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
+/// Adopt the linked worktrees of the current repository.
+///
+/// Records the main branch as the base of branches that have none, so merge
+/// and remove work as if the worktree had been created with `workmux add`.
+/// With `move_worktrees`, worktrees are moved to the directory `workmux add`
+/// would have used. With `dry_run`, nothing is changed.
+pub fn import(
+    context: &WorkflowContext,
+    move_worktrees: bool,
+    dry_run: bool,
+) -> Result<Vec<ImportedWorktree>> {
+    let mut imported = Vec::new();
+
+    for (path, branch) in git::list_worktrees()?.into_iter().skip(1) {
+        if git::is_detached(&branch) {
+            debug!(path = %path.display(), "import:skipping detached worktree");
+            continue;
+        }
+
+        let handle = naming::derive_handle(&branch, None, &context.config)?;
+        let target = context.worktree_path(&handle)?;
+        let mut worktree = ImportedWorktree {
+            branch: branch.clone(),
+            path: path.clone(),
+            expected_path: (path != target).then(|| target.clone()),
+            moved: false,
+            recorded_base: None,
+        };
+
+        if move_worktrees && worktree.expected_path.is_some() && !target.exists() {
+            if !dry_run {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create directory '{}'", parent.display())
+                    })?;
+                }
+                git::move_worktree(&path, &target)?;
+            }
+            worktree.path = target;
+            worktree.expected_path = None;
+            worktree.moved = true;
+        }
+
+        if git::get_branch_base(&branch).is_err() && branch != context.main_branch {
+            if !dry_run {
+                git::set_branch_base(&branch, &context.main_branch)?;
+            }
+            worktree.recorded_base = Some(context.main_branch.clone());
+        }
+
+        debug!(
+            branch = %branch,
+            path = %worktree.path.display(),
+            moved = worktree.moved,
+            "import:worktree adopted"
+        );
+        imported.push(worktree);
+    }
+
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn mkdirs(root: &Path, dirs: &[&str]) {
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+    }
+
+    #[test]
+    fn find_repos_detects_clones_and_bare_layouts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        mkdirs(
+            root,
+            &[
+                // ghq-style clone
+                "github.com/me/app/.git",
+                "github.com/me/app/src/nested/.git",
+                // bare repository
+                "github.com/me/lib.git/objects",
+                "github.com/me/lib.git/refs",
+                // bare layout with a .git file pointing at .bare
+                "github.com/me/tool/.bare",
+                // linked worktree of app
+                "github.com/me/app__worktrees/feature",
+                // hidden directories are not searched
+                ".cache/other/.git",
+            ],
+        );
+        fs::write(
+            root.join("github.com/me/lib.git/HEAD"),
+            "ref: refs/heads/main\n",
+        )
+        .unwrap();
+        fs::write(root.join("github.com/me/tool/.git"), "gitdir: ./.bare\n").unwrap();
+        fs::write(
+            root.join("github.com/me/app__worktrees/feature/.git"),
+            "gitdir: /code/app/.git/worktrees/feature\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_repos(root, 3),
+            vec![
+                root.join("github.com/me/app"),
+                root.join("github.com/me/lib.git"),
+                root.join("github.com/me/tool"),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_repos_respects_depth() {
+        let tmp = tempfile::tempdir().unwrap();
+        mkdirs(tmp.path(), &["a/b/c/.git"]);
+        assert!(find_repos(tmp.path(), 2).is_empty());
+        assert_eq!(find_repos(tmp.path(), 3), vec![tmp.path().join("a/b/c")]);
+    }
+
+    #[test]
+    fn find_repos_returns_root_itself() {
+        let tmp = tempfile::tempdir().unwrap();
+        mkdirs(tmp.path(), &[".git"]);
+        assert_eq!(find_repos(tmp.path(), 0), vec![tmp.path().to_path_buf()]);
+    }
+}
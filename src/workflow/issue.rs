@@ -0,0 +1,113 @@
+//! Bootstrapping worktrees from forge issues (`add --from-issue`).
+//!
+//! The issue title names the branch, its body becomes the agent prompt, and
+//! the branch is linked to the issue so `pr create` and `merge` can close it.
+
+use crate::forge::Forge;
+use crate::github::IssueDetails;
+use crate::{naming, spinner};
+use anyhow::{Context, Result};
+
+/// Result of resolving an issue for checkout.
+pub struct IssueCheckout {
+    pub branch: String,
+    pub title: String,
+    pub prompt: String,
+}
+
+/// Fetch an issue and derive the branch name and prompt for its worktree.
+pub fn resolve_issue(number: u32, custom_branch_name: Option<&str>) -> Result<IssueCheckout> {
+    let issue = spinner::with_spinner(&format!("Fetching issue #{}", number), || {
+        Forge::detect().get_issue(number)
+    })
+    .with_context(|| format!("Failed to fetch details for issue #{}", number))?;
+
+    println!("Issue #{}: {}", number, issue.title);
+    if issue.state != "OPEN" {
+        eprintln!(
+            "⚠️  Warning: issue #{} is {}. Proceeding anyway...",
+            number, issue.state
+        );
+    }
+
+    let branch = custom_branch_name
+        .map(String::from)
+        .unwrap_or_else(|| naming::branch_name_from_issue(number, &issue.title));
+
+    Ok(IssueCheckout {
+        branch,
+        prompt: issue_prompt(number, &issue),
+        title: issue.title,
+    })
+}
+
+/// Build the agent prompt for an issue.
+///
+/// Prompts are rendered as templates, so the issue text is wrapped in a raw
+/// block to keep `{{ ... }}` in code samples from being interpreted.
+fn issue_prompt(number: u32, issue: &IssueDetails) -> String {
+    let mut text = format!("Issue #{}: {}", number, issue.title.trim());
+    let body = issue.body.trim();
+    if !body.is_empty() {
+        text.push_str("\n\n");
+        text.push_str(body);
+    }
+    format!("{{% raw %}}{}{{% endraw %}}", text)
+}
+
+/// Line that closes the issue when the PR or commit lands
+pub fn closing_reference(number: u32) -> String {
+    format!("Closes #{}", number)
+}
+
+/// Append the closing reference to a PR body unless it already has one
+pub fn with_closing_reference(body: &str, number: u32) -> String {
+    let reference = closing_reference(number);
+    if body.to_lowercase().contains(&reference.to_lowercase()) {
+        return body.to_string();
+    }
+    let body = body.trim_end();
+    if body.is_empty() {
+        reference
+    } else {
+        format!("{}\n\n{}", body, reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(title: &str, body: &str) -> IssueDetails {
+        IssueDetails {
+            title: title.to_string(),
+            body: body.to_string(),
+            state: "OPEN".to_string(),
+        }
+    }
+
+    #[test]
+    fn issue_prompt_includes_title_and_body_verbatim() {
+        assert_eq!(
+            issue_prompt(12, &issue("Fix login", "Use {{ user }} here.\n")),
+            "{% raw %}Issue #12: Fix login\n\nUse {{ user }} here.{% endraw %}"
+        );
+        assert_eq!(
+            issue_prompt(3, &issue("Empty", "")),
+            "{% raw %}Issue #3: Empty{% endraw %}"
+        );
+    }
+
+    #[test]
+    fn with_closing_reference_appends_once() {
+        assert_eq!(with_closing_reference("", 5), "Closes #5");
+        assert_eq!(
+            with_closing_reference("- Add form\n", 5),
+            "- Add form\n\nCloses #5"
+        );
+        assert_eq!(
+            with_closing_reference("Done.\n\ncloses #5", 5),
+            "Done.\n\ncloses #5"
+        );
+    }
+}
@@ -1,16 +1,27 @@
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 
-use crate::{config, git, github, spinner, tmux};
+use crate::forge::Forge;
+use crate::vcs::Vcs;
+use crate::{config, daemon, git, metadata, spinner, tmux};
 
 use super::types::WorktreeInfo;
 
 /// List all worktrees with their status
 pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<WorktreeInfo>> {
-    if !git::is_git_repo()? {
-        return Err(anyhow!("Not in a git repository"));
-    }
+    let vcs = Vcs::from_config(config);
+    vcs.ensure_repo()?;
+
+    let main_root = vcs.main_root()?;
+    tmux::set_window_session(config.window_session(&main_root));
+
+    // Get the main branch for unmerged checks
+    let main_branch = match vcs {
+        Vcs::Git => git::get_default_branch().ok(),
+        Vcs::Jj => Some(config.main_branch.clone().unwrap_or_else(|| "main".into())),
+    };
 
-    let worktrees_data = git::list_worktrees()?;
+    let worktrees_data =
+        vcs.list_worktrees(config, &main_root, main_branch.as_deref().unwrap_or("main"))?;
 
     if worktrees_data.is_empty() {
         return Ok(Vec::new());
@@ -23,8 +34,32 @@ pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<Worktr
         std::collections::HashSet::new()
     };
 
-    // Get the main branch for unmerged checks
-    let main_branch = git::get_default_branch().ok();
+    // Pane health per window (agent process alive, dead panes)
+    let window_health = if tmux_windows.is_empty() {
+        std::collections::HashMap::new()
+    } else {
+        tmux::get_window_health(config.agent.as_deref().unwrap_or("claude"))
+    };
+
+    // Agent status per window, taken from the most recently updated agent pane
+    let mut agent_statuses: std::collections::HashMap<String, (String, Option<u64>)> =
+        std::collections::HashMap::new();
+    if !tmux_windows.is_empty() {
+        for agent in daemon::agent_panes() {
+            let Some(icon) = agent.status else { continue };
+            let status = config
+                .status_icons
+                .status_name(&icon)
+                .map(str::to_string)
+                .unwrap_or(icon);
+            let is_newer = agent_statuses
+                .get(&agent.window_name)
+                .is_none_or(|(_, ts)| agent.status_ts > *ts);
+            if is_newer {
+                agent_statuses.insert(agent.window_name, (status, agent.status_ts));
+            }
+        }
+    }
 
     // Get all unmerged branches in one go for efficiency
     // Prefer checking against remote tracking branch for more accurate results
@@ -34,15 +69,29 @@ pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<Worktr
         .and_then(|base| git::get_unmerged_branches(&base).ok())
         .unwrap_or_default(); // Use an empty set on failure
 
+    // Package scopes recorded by `add --package`
+    let packages = git::get_all_branch_packages();
+    // Pull requests recorded by `pr create`
+    let recorded_prs = git::get_all_branch_prs();
+    // Creation time and agent recorded by `workmux add`
+    let mut metadata = metadata::load_all();
+
     // Batch fetch all PRs if requested (single API call)
     let pr_map = if fetch_pr_status {
         spinner::with_spinner("Fetching PR status", || {
-            Ok(github::list_prs().unwrap_or_default())
+            Ok(Forge::detect().list_prs().unwrap_or_default())
         })?
     } else {
         std::collections::HashMap::new()
     };
 
+    // Served from the cache the dashboard and daemon keep warm
+    let worktree_paths: Vec<_> = worktrees_data
+        .iter()
+        .map(|(path, _)| path.clone())
+        .collect();
+    let mut git_statuses = git::cached_git_statuses(&worktree_paths);
+
     let prefix = config.window_prefix();
     let worktrees: Vec<WorktreeInfo> = worktrees_data
         .into_iter()
@@ -57,11 +106,21 @@ pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<Worktr
             // Use handle for tmux window check, not branch name
             let prefixed_window_name = tmux::prefixed(prefix, &handle);
             let has_tmux = tmux_windows.contains(&prefixed_window_name);
+            let (agent_status, status_ts) = agent_statuses
+                .get(&prefixed_window_name)
+                .cloned()
+                .map_or((None, None), |(status, ts)| (Some(status), ts));
+            let health = window_health
+                .get(&prefixed_window_name)
+                .cloned()
+                .unwrap_or_default();
 
             // Check for unmerged commits, but only if this isn't the main branch
             let has_unmerged = if let Some(ref main) = main_branch {
-                if branch == *main || branch == "(detached)" {
+                if branch == *main || git::is_detached(&branch) {
                     false
+                } else if vcs == Vcs::Jj {
+                    vcs.is_unmerged(&main_root, &handle, &branch, main)
                 } else {
                     unmerged_branches.contains(&branch)
                 }
@@ -72,12 +131,27 @@ pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<Worktr
             // Lookup PR info from batch fetch
             let pr_info = pr_map.get(&branch).cloned();
 
+            let package = packages.get(&branch).cloned();
+            let pr_number = recorded_prs.get(&branch).copied();
+            let git_status = git_statuses.remove(&path);
+            let created = metadata.remove(&handle).unwrap_or_default();
+
             WorktreeInfo {
+                handle,
                 branch,
                 path,
                 has_tmux,
                 has_unmerged,
+                package,
+                agent_status,
+                status_ts,
+                agent_running: health.agent_running,
+                dead_panes: health.dead_panes,
                 pr_info,
+                pr_number,
+                git_status,
+                created_at: created.created_at,
+                agent: created.agent,
             }
         })
         .collect();
@@ -1,11 +1,20 @@
 use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::{cmd, git};
+use crate::config::MergeStrategy;
+use crate::error::ErrorKind;
+use crate::events::{self, Event, Operation};
+use crate::forge::Forge;
+use crate::spinner::{self, Progress};
+use crate::vcs::Vcs;
+use crate::{git, jj, metadata, tmux};
 use tracing::{debug, info};
 
 use super::cleanup;
 use super::context::WorkflowContext;
-use super::types::MergeResult;
+use super::hooks::{self, Hook, HookEnv};
+use super::types::{MergeCheck, MergeResult, PrMergeResult};
 
 /// Merge a branch into the target branch and clean up
 #[allow(clippy::too_many_arguments)]
@@ -35,20 +44,18 @@ pub fn merge(
     // the worktree that is about to be deleted.
     context.chdir_to_main_worktree()?;
 
+    if context.vcs == Vcs::Jj {
+        if squash {
+            context.vcs.require_git("--squash")?;
+        }
+        // Rebasing is the only way jj workspaces are merged
+        return merge_workspace(name, into_branch, keep, no_verify, notification, context);
+    }
+
     // Smart resolution: try handle first, then branch name
     let (worktree_path, branch_to_merge) = git::find_worktree(name)
         .with_context(|| format!("No worktree found with name '{}'", name))?;
-
-    // The handle is the basename of the worktree directory (used for tmux operations)
-    let handle = worktree_path
-        .file_name()
-        .and_then(std::ffi::OsStr::to_str)
-        .ok_or_else(|| {
-            anyhow!(
-                "Could not derive handle from worktree path: {}",
-                worktree_path.display()
-            )
-        })?;
+    let handle = handle_of(&worktree_path)?;
 
     debug!(
         name = name,
@@ -57,92 +64,49 @@ pub fn merge(
         path = %worktree_path.display(),
         "merge:worktree resolved"
     );
+    // Steps show in the tmux status line, and as log lines when not on a terminal
+    let op = (
+        tmux::WorktreeOp::start(&context.prefix, handle),
+        spinner::step_log(),
+    );
 
-    // Determine the target branch:
-    // 1. Use explicit --into if provided
-    // 2. Otherwise, check if branch has a stored base (from workmux add)
-    // 3. Fall back to main_branch
-    let detected_base: Option<String> = if into_branch.is_some() {
-        None // User explicitly specified target, no auto-detection needed
-    } else {
-        match git::get_branch_base(&branch_to_merge) {
-            Ok(base) => {
-                // Verify the base branch still exists
-                if git::branch_exists(&base)? {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:auto-detected base branch"
-                    );
-                    Some(base)
-                } else {
-                    info!(
-                        branch = %branch_to_merge,
-                        base = %base,
-                        "merge:base branch not found, defaulting to main"
-                    );
-                    None
-                }
-            }
-            Err(_) => {
-                debug!(
-                    branch = %branch_to_merge,
-                    "merge:no base config found, defaulting to main"
-                );
-                None
-            }
-        }
-    };
+    if git::is_detached(&branch_to_merge) {
+        return Err(anyhow!(
+            "Worktree '{}' is detached and has no branch to merge.\n\
+             Create one with 'git -C {} switch -c <branch>' first, or discard it with 'workmux remove {}'.",
+            handle,
+            worktree_path.display(),
+            handle
+        ));
+    }
 
-    let target_branch = into_branch
-        .map(|s| s.to_string())
-        .or(detected_base)
-        .unwrap_or_else(|| context.main_branch.clone());
+    let target_branch = resolve_target_branch(into_branch, &branch_to_merge, context)?;
     let target_branch = target_branch.as_str();
 
-    // Resolve the worktree path and window handle for the TARGET branch.
-    // If the target branch is the configured main branch, we use the main worktree root
-    // and the main branch name as the window handle (standard workmux convention).
-    // Otherwise, we check if the target branch has a dedicated worktree.
-    // If it doesn't, we fallback to using the main worktree root but switch it to the target branch.
-    let (target_worktree_path, target_window_name) = if target_branch == context.main_branch {
-        (
-            context.main_worktree_root.clone(),
-            context.main_branch.clone(),
-        )
-    } else {
-        match git::get_worktree_path(target_branch) {
-            Ok(path) => {
-                // Check if the target is checked out in the main worktree.
-                // In that case, use the main branch name as the window handle
-                // (main worktree window is named after main_branch, not directory).
-                if path == context.main_worktree_root {
-                    (path, context.main_branch.clone())
-                } else {
-                    // Target has its own dedicated worktree. Use its directory name as the handle.
-                    let handle = path
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .ok_or_else(|| anyhow!("Invalid worktree path for target branch"))?
-                        .to_string();
-                    (path, handle)
-                }
-            }
-            Err(_) => {
-                // Target branch exists but is not checked out in any worktree.
-                // We will use the main worktree to perform the merge.
-                // The target window remains the main window (since that's where we are merging).
-                debug!(
-                    target = target_branch,
-                    "merge:target branch has no worktree, using main worktree"
-                );
-                (
-                    context.main_worktree_root.clone(),
-                    context.main_branch.clone(),
-                )
-            }
-        }
-    };
+    // Merging somewhere other than the local branch the worktree came from is
+    // usually a mistake (e.g. a stacked branch merged straight into main)
+    if into_branch.is_some()
+        && let Some(base) = metadata::load_or_infer(handle, &branch_to_merge).base
+        && base != target_branch
+        && git::branch_exists(&format!("refs/heads/{}", base)).unwrap_or(false)
+    {
+        eprintln!(
+            "Warning: '{}' was created from '{}' but is being merged into '{}'",
+            branch_to_merge, base, target_branch
+        );
+    }
+
+    let (target_worktree_path, target_window_name) = target_worktree(target_branch, context)?;
+
+    // Repository policy, which --ignore-uncommitted doesn't get around
+    if context.config.merge.require_clean.unwrap_or(false)
+        && git::has_uncommitted_changes(&worktree_path)?
+    {
+        return Err(ErrorKind::Dirty.msg(format!(
+            "Worktree for '{}' has uncommitted changes, and this repository only merges clean worktrees (merge.require_clean). Please commit or stash them first.",
+            branch_to_merge
+        )));
+    }
 
     // Handle changes in the source worktree
     // Only check for unstaged/untracked when worktree will be deleted (!keep)
@@ -158,11 +122,11 @@ pub fn merge(
         if has_untracked {
             issues.push("untracked files (will be lost)");
         }
-        return Err(anyhow!(
+        return Err(ErrorKind::Dirty.msg(format!(
             "Worktree for '{}' has {}. Please stage or stash them, or use --ignore-uncommitted.",
             branch_to_merge,
             issues.join(" and ")
-        ));
+        )));
     }
 
     let had_staged_changes = git::has_staged_changes(&worktree_path)?;
@@ -184,13 +148,56 @@ pub fn merge(
         "merge:target branch resolved"
     );
 
+    // Helper closure to generate the error message for merge conflicts
+    let conflict_err = |branch: &str, files: &[String]| -> anyhow::Error {
+        let retry_cmd = if into_branch.is_some() {
+            format!("workmux merge {} --into {}", branch, target_branch)
+        } else {
+            format!("workmux merge {}", branch)
+        };
+        let summary = if files.is_empty() {
+            "Merge failed due to conflicts. Target worktree kept clean.".to_string()
+        } else {
+            format!(
+                "Merging '{}' into '{}' would conflict in:\n  {}\nNothing was changed.",
+                branch,
+                target_branch,
+                files.join("\n  ")
+            )
+        };
+        ErrorKind::Conflicts.msg(format!(
+            "{}\n\n\
+            To resolve, update your branch in worktree at {}:\n\
+              git rebase {}  (recommended)\n\
+            Or:\n\
+              git merge {}\n\n\
+            After resolving conflicts, retry: {}",
+            summary,
+            worktree_path.display(),
+            target_branch,
+            target_branch,
+            retry_cmd
+        ))
+    };
+
+    // Pre-flight: detect conflicts up front instead of failing mid-merge.
+    // Older Git without `merge-tree --write-tree` just skips the check.
+    match git::merge_conflicts(target_branch, &branch_to_merge) {
+        Ok(files) if !files.is_empty() => {
+            info!(branch = %branch_to_merge, target = target_branch, ?files, "merge:pre-check found conflicts");
+            return Err(conflict_err(&branch_to_merge, &files));
+        }
+        Ok(_) => {}
+        Err(e) => debug!(error = %e, "merge:conflict pre-check unavailable"),
+    }
+
     // Safety check: Abort if the target worktree has uncommitted tracked changes.
     // Untracked files are allowed; git will fail safely if they collide with merged files.
     if git::has_tracked_changes(&target_worktree_path)? {
-        return Err(anyhow!(
+        return Err(ErrorKind::Dirty.msg(format!(
             "Target worktree ({}) has uncommitted changes. Please commit or stash them before merging.",
             target_worktree_path.display()
-        ));
+        )));
     }
 
     // Explicitly switch the target worktree to the target branch.
@@ -198,6 +205,10 @@ pub fn merge(
     // it is checked out to the correct branch.
     git::switch_branch_in_worktree(&target_worktree_path, target_branch)?;
 
+    let hook_env = HookEnv::new(handle, &worktree_path, &context.main_worktree_root)
+        .with_branch(&branch_to_merge)
+        .with_target(target_branch);
+
     // Run pre-merge hooks after all validations pass but before any merge operations begin.
     // Skip hooks if --no-verify flag is passed.
     if !no_verify
@@ -205,71 +216,48 @@ pub fn merge(
         && !hooks.is_empty()
     {
         info!(count = hooks.len(), "merge:running pre-merge hooks");
+        op.step("running pre-merge hooks…");
 
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.clone());
-        let abs_project_root = context
-            .main_worktree_root
-            .canonicalize()
-            .unwrap_or_else(|_| context.main_worktree_root.clone());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-
-        let hook_env = [
-            ("WORKMUX_HANDLE", handle),
-            ("WM_BRANCH_NAME", branch_to_merge.as_str()),
-            ("WM_TARGET_BRANCH", target_branch),
-            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-            ("WM_HANDLE", handle),
-        ];
-
-        for command in hooks {
-            cmd::shell_command_with_env(command, &worktree_path, &hook_env)
-                .with_context(|| format!("Pre-merge hook failed: '{}'", command))?;
-        }
+        hooks::run_before(Hook::PreMerge, hooks, &worktree_path, &hook_env)?;
     }
 
-    // Helper closure to generate the error message for merge conflicts
-    let conflict_err = |branch: &str| -> anyhow::Error {
-        let retry_cmd = if into_branch.is_some() {
-            format!("workmux merge {} --into {}", branch, target_branch)
-        } else {
-            format!("workmux merge {}", branch)
-        };
-        anyhow!(
-            "Merge failed due to conflicts. Target worktree kept clean.\n\n\
-            To resolve, update your branch in worktree at {}:\n\
-              git rebase {}  (recommended)\n\
-            Or:\n\
-              git merge {}\n\n\
-            After resolving conflicts, retry: {}",
-            worktree_path.display(),
-            target_branch,
-            target_branch,
-            retry_cmd
-        )
+    let strategy = if rebase {
+        MergeStrategy::Rebase
+    } else if squash {
+        MergeStrategy::Squash
+    } else {
+        MergeStrategy::Merge
     };
+    let commits_merged = git::count_commits_ahead(target_branch, &branch_to_merge).unwrap_or(0);
+    let target_before = branch_tip(target_branch);
+
+    if rebase {
+        op.step(&format!("rebasing onto {}…", target_branch));
+    } else {
+        op.step(&format!("merging into {}…", target_branch));
+    }
 
     if rebase {
         // Rebase the feature branch on top of target inside its own worktree.
         // This is where conflicts will be detected.
-        println!(
+        spinner::message(&format!(
             "Rebasing '{}' onto '{}'...",
             &branch_to_merge, target_branch
-        );
+        ));
         info!(
             branch = %branch_to_merge,
             base = target_branch,
             "merge:rebase start"
         );
-        git::rebase_branch_onto_base(&worktree_path, target_branch).with_context(|| {
-            format!(
-                "Rebase failed, likely due to conflicts.\n\n\
-                Please resolve them manually inside the worktree at '{}'.\n\
-                Then, run 'git rebase --continue' to proceed or 'git rebase --abort' to cancel.",
-                worktree_path.display()
+        git::rebase_branch_onto_base(&worktree_path, target_branch).map_err(|e| {
+            ErrorKind::Conflicts.wrap(
+                e,
+                format!(
+                    "Rebase failed, likely due to conflicts.\n\n\
+                    Please resolve them manually inside the worktree at '{}'.\n\
+                    Then, run 'git rebase --continue' to proceed or 'git rebase --abort' to cancel.",
+                    worktree_path.display()
+                ),
             )
         })?;
 
@@ -283,7 +271,17 @@ pub fn merge(
             info!(branch = %branch_to_merge, error = %e, "merge:squash merge failed, resetting target worktree");
             // Best effort to reset; ignore failure as the user message is the priority.
             let _ = git::reset_hard(&target_worktree_path);
-            return Err(conflict_err(&branch_to_merge));
+            return Err(conflict_err(&branch_to_merge, &[]));
+        }
+
+        // Pre-fill the closing reference for the issue the branch was started from
+        if let Some(issue) = git::get_branch_issue_in(&branch_to_merge, Some(&worktree_path))
+            && let Err(e) = git::append_to_squash_message(
+                &target_worktree_path,
+                &super::issue::closing_reference(issue),
+            )
+        {
+            debug!(error = %e, "merge:failed to add issue reference to squash message");
         }
 
         // Prompt the user to provide a commit message for the squashed changes.
@@ -292,37 +290,78 @@ pub fn merge(
             .context("Failed to commit squashed changes. You may need to commit them manually.")?;
         info!(branch = %branch_to_merge, "merge:squash merge committed");
     } else {
-        // Default merge commit workflow
-        if let Err(e) = git::merge_in_worktree(&target_worktree_path, &branch_to_merge) {
+        // Default merge commit workflow. Branches started from an issue get a
+        // closing reference in the merge commit message.
+        let merged = match git::get_branch_issue_in(&branch_to_merge, Some(&worktree_path)) {
+            Some(issue) => {
+                let message = format!(
+                    "Merge branch '{}'\n\n{}",
+                    branch_to_merge,
+                    super::issue::closing_reference(issue)
+                );
+                git::merge_in_worktree_with_message(
+                    &target_worktree_path,
+                    &branch_to_merge,
+                    &message,
+                )
+            }
+            None => git::merge_in_worktree(&target_worktree_path, &branch_to_merge),
+        };
+        if let Err(e) = merged {
             info!(branch = %branch_to_merge, error = %e, "merge:standard merge failed, aborting merge in target worktree");
             // Best effort to abort; ignore failure as the user message is the priority.
             let _ = git::abort_merge_in_worktree(&target_worktree_path);
-            return Err(conflict_err(&branch_to_merge));
+            return Err(conflict_err(&branch_to_merge, &[]));
         }
         info!(branch = %branch_to_merge, "merge:standard merge complete");
     }
 
+    record_merge(
+        handle,
+        &branch_to_merge,
+        target_branch,
+        target_before,
+        (!keep).then(|| branch_tip(&branch_to_merge)).flatten(),
+    );
+
     // Show notification before cleanup or early return (--keep),
     // since cleanup may kill the window and terminate this process
     if notification {
-        show_notification(&format!(
+        crate::notification::show(&format!(
             "Merged '{}' into '{}'",
             branch_to_merge, target_branch
         ));
     }
 
+    // Move worktrees stacked on this one while the branch they forked from
+    // still exists, and before cleanup may end this process
+    for report in super::stack::restack_children(handle, &branch_to_merge, target_branch) {
+        super::stack::print_report(&report, &branch_to_merge, target_branch);
+    }
+
     // Skip cleanup if --keep flag is used
     if keep {
         info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
+        hooks::run_after(
+            Hook::PostMerge,
+            context.config.post_merge.as_ref(),
+            &target_worktree_path,
+            &hook_env,
+        );
         return Ok(MergeResult {
             branch_merged: branch_to_merge,
             main_branch: target_branch.to_string(),
+            strategy: Some(strategy),
+            commits_merged,
+            cherry_picked: Vec::new(),
             had_staged_changes,
+            cleanup: None,
         });
     }
 
     // Always force cleanup after a successful merge
     info!(branch = %branch_to_merge, "merge:cleanup start");
+    op.step("removing worktree…");
     let cleanup_result = cleanup::cleanup(
         context,
         &branch_to_merge,
@@ -330,49 +369,651 @@ pub fn merge(
         &worktree_path,
         true,
         false, // keep_branch: always delete when merging
+        context.config.merge.delete_remote_branch.unwrap_or(false),
     )?;
 
+    // Clear the progress now: closing the source window may end this process
+    drop(op);
+
+    // Before the source window closes, which may end this process
+    hooks::run_after(
+        Hook::PostMerge,
+        context.config.post_merge.as_ref(),
+        &target_worktree_path,
+        &hook_env,
+    );
+
     // Navigate to the target branch window and close the source window
-    cleanup::navigate_to_target_and_close(
-        &context.prefix,
-        &target_window_name,
-        handle,
-        &cleanup_result,
-    )?;
+    cleanup::navigate_to_target_and_close(context, &target_window_name, handle, &cleanup_result)?;
 
     Ok(MergeResult {
         branch_merged: branch_to_merge,
         main_branch: target_branch.to_string(),
+        strategy: Some(strategy),
+        commits_merged,
+        cherry_picked: Vec::new(),
         had_staged_changes,
+        cleanup: Some(cleanup_result),
     })
 }
 
-/// Shows a system notification on macOS or Linux
-fn show_notification(message: &str) {
-    #[cfg(target_os = "macos")]
+/// Cherry-pick selected commits of a worktree's branch onto the target branch,
+/// leaving the worktree, window and branch in place (`merge --commits`).
+/// Each spec is a commit or a range such as `abc123..HEAD`, resolved in the
+/// worktree.
+pub fn cherry_pick(
+    name: &str,
+    into_branch: Option<&str>,
+    specs: &[String],
+    no_verify: bool,
+    notification: bool,
+    context: &WorkflowContext,
+) -> Result<MergeResult> {
+    info!(
+        name,
+        into = into_branch,
+        ?specs,
+        no_verify,
+        "merge:cherry-pick start"
+    );
+    context.vcs.require_git("--commits")?;
+    context.chdir_to_main_worktree()?;
+
+    let (worktree_path, branch) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let handle = handle_of(&worktree_path)?;
+    let op = (
+        tmux::WorktreeOp::start(&context.prefix, handle),
+        spinner::step_log(),
+    );
+
+    let target_branch = resolve_target_branch(into_branch, &branch, context)?;
+    let target_branch = target_branch.as_str();
+    let picks = resolve_picks(&worktree_path, specs, target_branch)?;
+    debug!(
+        handle,
+        target = target_branch,
+        ?picks,
+        "merge:commits resolved"
+    );
+
+    let (target_worktree_path, _) = target_worktree(target_branch, context)?;
+    if git::has_tracked_changes(&target_worktree_path)? {
+        return Err(ErrorKind::Dirty.msg(format!(
+            "Target worktree ({}) has uncommitted changes. Please commit or stash them before merging.",
+            target_worktree_path.display()
+        )));
+    }
+    git::switch_branch_in_worktree(&target_worktree_path, target_branch)?;
+
+    let hook_env = HookEnv::new(handle, &worktree_path, &context.main_worktree_root)
+        .with_branch(&branch)
+        .with_target(target_branch);
+    if !no_verify
+        && let Some(hooks) = &context.config.pre_merge
+        && !hooks.is_empty()
     {
-        use mac_notification_sys::{Notification, set_application};
-        // Set application to Terminal to use its icon
-        if let Err(e) = set_application("com.apple.Terminal") {
-            tracing::debug!("Failed to set notification application: {:?}", e);
+        op.step("running pre-merge hooks…");
+        hooks::run_before(Hook::PreMerge, hooks, &worktree_path, &hook_env)?;
+    }
+
+    op.step(&format!("cherry-picking onto {}…", target_branch));
+    let target_before = branch_tip(target_branch);
+    if let Err(e) = git::cherry_pick_in_worktree(&target_worktree_path, &picks) {
+        info!(error = %e, "merge:cherry-pick failed, aborting");
+        let _ = git::abort_cherry_pick_in_worktree(&target_worktree_path);
+        return Err(ErrorKind::Conflicts.wrap(
+            e,
+            format!(
+                "Cherry-picking onto '{}' conflicts. Nothing was changed.\n\n\
+                To resolve, rebase the worktree at {} onto '{}' and pick again.",
+                target_branch,
+                worktree_path.display(),
+                target_branch
+            ),
+        ));
+    }
+    info!(count = picks.len(), "merge:cherry-pick complete");
+    record_merge(handle, &branch, target_branch, target_before, None);
+    drop(op);
+
+    if notification {
+        crate::notification::show(&format!(
+            "Cherry-picked {} commit(s) from '{}' into '{}'",
+            picks.len(),
+            branch,
+            target_branch
+        ));
+    }
+    hooks::run_after(
+        Hook::PostMerge,
+        context.config.post_merge.as_ref(),
+        &target_worktree_path,
+        &hook_env,
+    );
+
+    Ok(MergeResult {
+        branch_merged: branch,
+        main_branch: target_branch.to_string(),
+        strategy: None,
+        commits_merged: picks.len(),
+        cherry_picked: picks,
+        had_staged_changes: false,
+        cleanup: None,
+    })
+}
+
+/// Commits named by `specs`, in order and without repeats. Each must be on
+/// the worktree's branch and not already on `target`.
+fn resolve_picks(worktree_path: &Path, specs: &[String], target: &str) -> Result<Vec<String>> {
+    let mut picks: Vec<String> = Vec::new();
+    for spec in specs {
+        let commits = if spec.contains("..") {
+            git::commits_in_range(worktree_path, spec)
+                .map_err(|e| ErrorKind::NotFound.wrap(e, format!("Invalid range '{}'", spec)))?
+        } else {
+            vec![
+                git::resolve_commit_in(spec, Some(worktree_path)).map_err(|e| {
+                    ErrorKind::NotFound.wrap(e, format!("Unknown commit '{}'", spec))
+                })?,
+            ]
+        };
+        if commits.is_empty() {
+            return Err(anyhow!("'{}' contains no commits", spec));
         }
-        if let Err(e) = Notification::default()
-            .title("workmux")
-            .message(message)
-            .send()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
+        for commit in commits {
+            if !git::head_contains(worktree_path, &commit)? {
+                return Err(anyhow!(
+                    "Commit {} is not on the worktree's branch",
+                    short(&commit)
+                ));
+            }
+            if git::is_ancestor(&commit, target)? {
+                return Err(anyhow!(
+                    "Commit {} is already on '{}'",
+                    short(&commit),
+                    target
+                ));
+            }
+            if !picks.contains(&commit) {
+                picks.push(commit);
+            }
         }
     }
+    Ok(picks)
+}
+
+fn short(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
 
-    #[cfg(not(target_os = "macos"))]
+fn branch_tip(branch: &str) -> Option<String> {
+    git::resolve_commit(&format!("refs/heads/{}", branch)).ok()
+}
+
+/// Record a merge into `target` for `workmux undo`. `commit` is the branch's
+/// tip when cleanup is about to delete it.
+fn record_merge(
+    handle: &str,
+    branch: &str,
+    target: &str,
+    before: Option<String>,
+    commit: Option<String>,
+) {
+    let (Some(before), Some(after)) = (before, branch_tip(target)) else {
+        return;
+    };
+    let metadata = commit.as_ref().and_then(|_| metadata::load(handle));
+    events::record(Event::new(
+        handle,
+        Operation::Merge {
+            branch: branch.to_string(),
+            target: target.to_string(),
+            before,
+            after,
+            commit,
+            metadata,
+        },
+    ));
+}
+
+/// The worktree path and window handle for the TARGET branch.
+/// If the target branch is the configured main branch, we use the main worktree root
+/// and the main branch name as the window handle (standard workmux convention).
+/// Otherwise, we check if the target branch has a dedicated worktree.
+/// If it doesn't, we fallback to using the main worktree root but switch it to the target branch.
+fn target_worktree(target_branch: &str, context: &WorkflowContext) -> Result<(PathBuf, String)> {
+    if target_branch == context.main_branch {
+        return Ok((
+            context.main_worktree_root.clone(),
+            context.main_branch.clone(),
+        ));
+    }
+    match git::get_worktree_path(target_branch) {
+        Ok(path) => {
+            // Check if the target is checked out in the main worktree.
+            // In that case, use the main branch name as the window handle
+            // (main worktree window is named after main_branch, not directory).
+            if path == context.main_worktree_root {
+                Ok((path, context.main_branch.clone()))
+            } else {
+                // Target has its own dedicated worktree. Use its directory name as the handle.
+                let handle = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .ok_or_else(|| anyhow!("Invalid worktree path for target branch"))?
+                    .to_string();
+                Ok((path, handle))
+            }
+        }
+        Err(_) => {
+            // Target branch exists but is not checked out in any worktree.
+            // We will use the main worktree to perform the merge.
+            // The target window remains the main window (since that's where we are merging).
+            debug!(
+                target = target_branch,
+                "merge:target branch has no worktree, using main worktree"
+            );
+            Ok((
+                context.main_worktree_root.clone(),
+                context.main_branch.clone(),
+            ))
+        }
+    }
+}
+
+/// How often `merge --pr` checks whether the PR has landed, e.g. from a merge queue
+const PR_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How long `merge --pr` waits for a queued PR before leaving it to the forge
+const PR_MERGE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Merge a worktree's open PR on the forge with `strategy`, wait until it is
+/// merged, then clean up the worktree, window and local branch.
+///
+/// With `wait` false, or when the PR is still queued after `PR_MERGE_TIMEOUT`,
+/// nothing is cleaned up and the result has `merged: false`.
+pub fn merge_pr(
+    name: &str,
+    strategy: MergeStrategy,
+    keep: bool,
+    no_verify: bool,
+    notification: bool,
+    wait: bool,
+    context: &WorkflowContext,
+) -> Result<PrMergeResult> {
+    info!(name, ?strategy, keep, no_verify, wait, "merge_pr:start");
+    context.vcs.require_git("merge --pr")?;
+    context.chdir_to_main_worktree()?;
+
+    let (worktree_path, branch) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let handle = handle_of(&worktree_path)?;
+    if git::is_detached(&branch) {
+        return Err(anyhow!(
+            "Worktree '{}' is detached and has no PR to merge.",
+            handle
+        ));
+    }
+
+    // The forge merges what was pushed, so anything else would be lost in cleanup
+    if !keep && git::has_uncommitted_changes(&worktree_path)? {
+        return Err(ErrorKind::Dirty.msg(format!(
+            "Worktree for '{}' has uncommitted changes. Commit and push them first.",
+            branch
+        )));
+    }
+
+    let forge = Forge::detect();
+    let prs = spinner::with_spinner("Looking up PR", || forge.list_prs())?;
+    let pr = prs
+        .get(&branch)
+        .filter(|pr| pr.state == "OPEN")
+        .ok_or_else(|| {
+            anyhow!(
+                "No open PR found for '{}'. Create one with 'workmux pr create {}'.",
+                branch,
+                handle
+            )
+        })?;
+    let label = forge.pr_label(pr.number);
+    if pr.is_draft {
+        return Err(anyhow!(
+            "{} is a draft. Mark it ready for review first.",
+            label
+        ));
+    }
+    if !keep
+        && let Some(head) = &pr.head_sha
+        && git::resolve_commit(&format!("refs/heads/{}", branch))? != *head
     {
-        if let Err(e) = notify_rust::Notification::new()
-            .summary("workmux")
-            .body(message)
-            .show()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
+        return Err(anyhow!(
+            "Local branch '{}' doesn't match the head of {}. Push (or pull) it first.",
+            branch,
+            label
+        ));
+    }
+
+    let hook_env = HookEnv::new(handle, &worktree_path, &context.main_worktree_root)
+        .with_branch(&branch)
+        .with_target(&context.main_branch);
+    if !no_verify
+        && let Some(hooks) = &context.config.pre_merge
+        && !hooks.is_empty()
+    {
+        info!(count = hooks.len(), "merge_pr:running pre-merge hooks");
+        hooks::run_before(Hook::PreMerge, hooks, &worktree_path, &hook_env)?;
+    }
+
+    spinner::with_spinner(&format!("Merging {}", label), || {
+        forge.merge_pr(pr.number, strategy)
+    })?;
+    let deadline = std::time::Instant::now() + PR_MERGE_TIMEOUT;
+    let merged = spinner::with_spinner(&format!("Waiting for {} to be merged", label), || {
+        loop {
+            match forge.get_pr_details(pr.number)?.state.as_str() {
+                "MERGED" => return Ok(true),
+                "CLOSED" => return Err(anyhow!("{} was closed without being merged", label)),
+                _ if !wait || std::time::Instant::now() >= deadline => return Ok(false),
+                _ => std::thread::sleep(PR_POLL_INTERVAL),
+            }
         }
+    })?;
+    if !merged {
+        info!(branch = %branch, pr = pr.number, "merge_pr:queued");
+        return Ok(PrMergeResult {
+            branch_merged: branch,
+            label,
+            merged: false,
+        });
+    }
+    info!(branch = %branch, pr = pr.number, "merge_pr:merged");
+
+    if notification {
+        crate::notification::show(&format!("Merged {} ('{}')", label, branch));
+    }
+
+    if keep {
+        hooks::run_after(
+            Hook::PostMerge,
+            context.config.post_merge.as_ref(),
+            &context.main_worktree_root,
+            &hook_env,
+        );
+        return Ok(PrMergeResult {
+            branch_merged: branch,
+            label,
+            merged: true,
+        });
+    }
+
+    // Forced: the local branch is usually not merged locally (squash, rebase)
+    let cleanup_result = cleanup::cleanup(
+        context,
+        &branch,
+        handle,
+        &worktree_path,
+        true,
+        false,
+        context.config.merge.delete_remote_branch.unwrap_or(false),
+    )?;
+    hooks::run_after(
+        Hook::PostMerge,
+        context.config.post_merge.as_ref(),
+        &context.main_worktree_root,
+        &hook_env,
+    );
+    cleanup::navigate_to_target_and_close(context, &context.main_branch, handle, &cleanup_result)?;
+
+    Ok(PrMergeResult {
+        branch_merged: branch,
+        label,
+        merged: true,
+    })
+}
+
+/// The handle is the basename of the worktree directory (used for tmux operations)
+fn handle_of(worktree_path: &Path) -> Result<&str> {
+    worktree_path
+        .file_name()
+        .and_then(std::ffi::OsStr::to_str)
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not derive handle from worktree path: {}",
+                worktree_path.display()
+            )
+        })
+}
+
+/// Merge a jj workspace: rebase its changes onto the target bookmark and move
+/// the bookmark forward to them, so there is no merge commit. On conflicts
+/// the repository is restored to where it was.
+fn merge_workspace(
+    name: &str,
+    into_branch: Option<&str>,
+    keep: bool,
+    no_verify: bool,
+    notification: bool,
+    context: &WorkflowContext,
+) -> Result<MergeResult> {
+    let main_root = &context.main_worktree_root;
+    let (worktree_path, branch_to_merge) = context
+        .vcs
+        .find_worktree(name, &context.config, main_root, &context.main_branch)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    let handle = handle_of(&worktree_path)?;
+    if worktree_path == *main_root {
+        return Err(anyhow!("Cannot merge the default jj workspace"));
+    }
+
+    // The recorded base, if its bookmark is still there, like the git backend
+    let target_branch = into_branch
+        .map(str::to_string)
+        .or_else(|| {
+            metadata::load(handle)
+                .and_then(|m| m.base)
+                .filter(|base| jj::bookmark_exists(main_root, base).unwrap_or(false))
+        })
+        .unwrap_or_else(|| context.main_branch.clone());
+    let target_branch = target_branch.as_str();
+    if branch_to_merge == target_branch {
+        return Err(anyhow!(
+            "Cannot merge branch '{}' into itself.",
+            branch_to_merge
+        ));
     }
+    debug!(
+        handle,
+        branch = %branch_to_merge,
+        target = target_branch,
+        "merge:jj workspace resolved"
+    );
+    let op = (
+        tmux::WorktreeOp::start(&context.prefix, handle),
+        spinner::step_log(),
+    );
+
+    let hook_env = HookEnv::new(handle, &worktree_path, main_root)
+        .with_branch(&branch_to_merge)
+        .with_target(target_branch);
+    if !no_verify
+        && let Some(hooks) = &context.config.pre_merge
+        && !hooks.is_empty()
+    {
+        info!(count = hooks.len(), "merge:running pre-merge hooks");
+        op.step("running pre-merge hooks…");
+        hooks::run_before(Hook::PreMerge, hooks, &worktree_path, &hook_env)?;
+    }
+
+    op.step(&format!("rebasing onto {}…", target_branch));
+    let target = jj::symbol(target_branch);
+    let before = jj::current_operation(main_root)?;
+    jj::rebase_workspace(main_root, handle, &target)?;
+
+    // jj records conflicts in the rebased changes instead of stopping
+    let conflicted = jj::commit_ids(
+        main_root,
+        &format!("({}..{}@) & conflicts()", target, handle),
+    )?;
+    let tips = jj::commit_ids(
+        main_root,
+        &format!("heads({})", jj::unmerged_revset(handle, target_branch)),
+    )?;
+    if !conflicted.is_empty() || tips.len() > 1 {
+        jj::restore_operation(main_root, &before)?;
+        let problem = if conflicted.is_empty() {
+            "its changes have more than one head"
+        } else {
+            "rebasing them conflicts"
+        };
+        return Err(ErrorKind::Conflicts.msg(format!(
+            "Cannot merge '{}' into '{}': {}. Nothing was changed.\n\n\
+            To resolve, update the workspace at {}:\n\
+              jj rebase -b @ -d {}\n\n\
+            After resolving, retry: workmux merge {}",
+            branch_to_merge,
+            target_branch,
+            problem,
+            worktree_path.display(),
+            target,
+            handle
+        )));
+    }
+    let commits_merged =
+        jj::commit_ids(main_root, &jj::unmerged_revset(handle, target_branch))?.len();
+    if let Some(tip) = tips.first() {
+        jj::set_bookmark(main_root, target_branch, tip)?;
+        info!(branch = %branch_to_merge, target = target_branch, tip, "merge:bookmark moved");
+    }
+
+    if notification {
+        crate::notification::show(&format!(
+            "Merged '{}' into '{}'",
+            branch_to_merge, target_branch
+        ));
+    }
+
+    if keep {
+        info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
+        hooks::run_after(
+            Hook::PostMerge,
+            context.config.post_merge.as_ref(),
+            main_root,
+            &hook_env,
+        );
+        return Ok(MergeResult {
+            branch_merged: branch_to_merge,
+            main_branch: target_branch.to_string(),
+            strategy: Some(MergeStrategy::Rebase),
+            commits_merged,
+            cherry_picked: Vec::new(),
+            had_staged_changes: false,
+            cleanup: None,
+        });
+    }
+
+    op.step("removing worktree…");
+    let cleanup_result = cleanup::cleanup(
+        context,
+        &branch_to_merge,
+        handle,
+        &worktree_path,
+        true,
+        false,
+        false,
+    )?;
+    drop(op);
+    hooks::run_after(
+        Hook::PostMerge,
+        context.config.post_merge.as_ref(),
+        main_root,
+        &hook_env,
+    );
+    cleanup::navigate_to_target_and_close(context, &context.main_branch, handle, &cleanup_result)?;
+
+    Ok(MergeResult {
+        branch_merged: branch_to_merge,
+        main_branch: target_branch.to_string(),
+        strategy: Some(MergeStrategy::Rebase),
+        commits_merged,
+        cherry_picked: Vec::new(),
+        had_staged_changes: false,
+        cleanup: Some(cleanup_result),
+    })
+}
+
+/// Report whether a worktree's branch would merge cleanly into its target,
+/// without changing anything (`merge --check`)
+pub fn check_merge(
+    name: &str,
+    into_branch: Option<&str>,
+    context: &WorkflowContext,
+) -> Result<MergeCheck> {
+    context.vcs.require_git("merge --check")?;
+    let (_, branch) = git::find_worktree(name)
+        .with_context(|| format!("No worktree found with name '{}'", name))?;
+    if git::is_detached(&branch) {
+        return Err(anyhow!(
+            "Worktree '{}' is detached and has no branch to merge.",
+            name
+        ));
+    }
+    let target = resolve_target_branch(into_branch, &branch, context)?;
+    if branch == target {
+        return Err(anyhow!("Cannot merge branch '{}' into itself.", branch));
+    }
+    let conflicts = git::merge_conflicts(&target, &branch)
+        .context("Conflict check requires Git 2.38 or later")?;
+    Ok(MergeCheck {
+        branch,
+        target,
+        conflicts,
+    })
+}
+
+/// The branch to merge into:
+/// 1. Use explicit --into if provided
+/// 2. Otherwise, check if branch has a stored base (from workmux add)
+/// 3. Fall back to main_branch
+fn resolve_target_branch(
+    into_branch: Option<&str>,
+    branch_to_merge: &str,
+    context: &WorkflowContext,
+) -> Result<String> {
+    let detected_base: Option<String> = if into_branch.is_some() {
+        None // User explicitly specified target, no auto-detection needed
+    } else {
+        match git::get_branch_base(branch_to_merge) {
+            Ok(base) => {
+                // Verify the base branch still exists
+                if git::branch_exists(&base)? {
+                    info!(
+                        branch = %branch_to_merge,
+                        base = %base,
+                        "merge:auto-detected base branch"
+                    );
+                    Some(base)
+                } else {
+                    info!(
+                        branch = %branch_to_merge,
+                        base = %base,
+                        "merge:base branch not found, defaulting to main"
+                    );
+                    None
+                }
+            }
+            Err(_) => {
+                debug!(
+                    branch = %branch_to_merge,
+                    "merge:no base config found, defaulting to main"
+                );
+                None
+            }
+        }
+    };
+
+    Ok(into_branch
+        .map(|s| s.to_string())
+        .or(detected_base)
+        .unwrap_or_else(|| context.main_branch.clone()))
 }
@@ -2,6 +2,9 @@
 mod cleanup;
 mod context;
 mod create;
+pub mod hooks;
+pub mod import;
+pub mod issue;
 mod list;
 mod merge;
 mod open;
@@ -9,15 +12,18 @@ pub mod pr;
 pub mod prompt_loader;
 mod remove;
 mod setup;
+pub mod stack;
+pub mod sync;
 pub mod types;
+pub mod workspace;
 
 // Public API re-exports
 pub use create::{create, create_with_changes};
 pub use list::list;
-pub use merge::merge;
+pub use merge::{check_merge, cherry_pick, merge, merge_pr};
 pub use open::open;
 pub use remove::remove;
-pub use setup::write_prompt_file;
+pub use setup::{prompt_file_path, write_prompt_file};
 
 // Re-export commonly used types for convenience
 pub use context::WorkflowContext;
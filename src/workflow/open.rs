@@ -1,6 +1,8 @@
 use anyhow::{Context, Result, anyhow};
 use regex::Regex;
+use std::path::Path;
 
+use crate::config::MainWorktreeAgents;
 use crate::{git, tmux};
 use tracing::info;
 
@@ -75,6 +77,24 @@ pub fn open(
         base_handle
     };
 
+    if options.run_pane_commands
+        && context.config.panes_run_agent()
+        && is_main_worktree(&worktree_path, context)
+    {
+        match context.config.main_worktree_agents.unwrap_or_default() {
+            MainWorktreeAgents::Allow => {}
+            MainWorktreeAgents::Warn => eprintln!(
+                "Warning: starting an agent in the main worktree, where its changes land outside any worktree branch"
+            ),
+            MainWorktreeAgents::Block => {
+                return Err(anyhow!(
+                    "Refusing to start an agent in the main worktree (main_worktree_agents: block).\n\
+                     Use 'workmux add <branch>' to give it a worktree of its own."
+                ));
+            }
+        }
+    }
+
     // Setup the environment
     let result = setup::setup_environment(
         &branch_name,
@@ -138,3 +158,13 @@ fn resolve_unique_handle(context: &WorkflowContext, base_handle: &str) -> Result
 
     Ok(new_handle)
 }
+
+fn is_main_worktree(path: &Path, context: &WorkflowContext) -> bool {
+    match (
+        path.canonicalize(),
+        context.main_worktree_root.canonicalize(),
+    ) {
+        (Ok(path), Ok(main)) => path == main,
+        _ => path == context.main_worktree_root,
+    }
+}
@@ -3,7 +3,8 @@
 //! This module extracts domain logic for resolving pull requests and fork branches
 //! from the command layer, making it reusable and testable.
 
-use crate::{git, github, spinner};
+use crate::forge::Forge;
+use crate::{git, spinner};
 use anyhow::{Context, Result, anyhow};
 
 /// Result of resolving a PR checkout.
@@ -20,25 +21,27 @@ pub fn resolve_pr_ref(
     pr_number: u32,
     custom_branch_name: Option<&str>,
 ) -> Result<PrCheckoutResult> {
-    let pr_details = spinner::with_spinner(&format!("Fetching PR #{}", pr_number), || {
-        github::get_pr_details(pr_number)
+    let forge = Forge::detect();
+    let label = forge.pr_label(pr_number);
+    let pr_details = spinner::with_spinner(&format!("Fetching {}", label), || {
+        forge.get_pr_details(pr_number)
     })
-    .with_context(|| format!("Failed to fetch details for PR #{}", pr_number))?;
+    .with_context(|| format!("Failed to fetch details for {}", label))?;
 
     // Display PR information
-    println!("PR #{}: {}", pr_number, pr_details.title);
+    println!("{}: {}", label, pr_details.title);
     println!("Author: {}", pr_details.author.login);
     println!("Branch: {}", pr_details.head_ref_name);
 
     // Warn about PR state
     if pr_details.state != "OPEN" {
         eprintln!(
-            "⚠️  Warning: PR #{} is {}. Proceeding with checkout...",
-            pr_number, pr_details.state
+            "⚠️  Warning: {} is {}. Proceeding with checkout...",
+            label, pr_details.state
         );
     }
     if pr_details.is_draft {
-        eprintln!("⚠️  Warning: PR #{} is a DRAFT.", pr_number);
+        eprintln!("⚠️  Warning: {} is a DRAFT.", label);
     }
 
     // Determine local branch name (match gh pr checkout behavior)
@@ -78,7 +81,8 @@ pub struct ForkBranchResult {
 /// Sets up the fork remote and optionally displays associated PR info.
 pub fn resolve_fork_branch(fork_spec: &git::ForkBranchSpec) -> Result<ForkBranchResult> {
     // Try to find an associated PR and display info (optional, non-blocking)
-    if let Ok(Some(pr)) = github::find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
+    let forge = Forge::detect();
+    if let Ok(Some(pr)) = forge.find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
         let state_suffix = match pr.state.as_str() {
             "OPEN" if pr.is_draft => " (draft)",
             "OPEN" => "",
@@ -86,7 +90,12 @@ pub fn resolve_fork_branch(fork_spec: &git::ForkBranchSpec) -> Result<ForkBranch
             "CLOSED" => " (closed)",
             _ => "",
         };
-        println!("PR #{}: {}{}", pr.number, pr.title, state_suffix);
+        println!(
+            "{}: {}{}",
+            forge.pr_label(pr.number),
+            pr.title,
+            state_suffix
+        );
     }
 
     // Ensure the fork remote exists
@@ -152,3 +161,62 @@ pub fn detect_remote_branch(
         Ok((None, branch_name.to_string()))
     }
 }
+
+/// Derive a PR title and body from the commits on a branch.
+///
+/// A single commit supplies both; with several, the title comes from the
+/// branch name and the body lists the commit subjects.
+pub fn pr_text_from_commits(branch: &str, commits: &[git::CommitMessage]) -> (String, String) {
+    if let [commit] = commits {
+        return (commit.subject.clone(), commit.body.clone());
+    }
+    let body = commits
+        .iter()
+        .map(|c| format!("- {}", c.subject))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (title_from_branch(branch), body)
+}
+
+/// Turn a branch name like "feature/add-user_auth" into "Add user auth"
+fn title_from_branch(branch: &str) -> String {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => branch.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(subject: &str, body: &str) -> git::CommitMessage {
+        git::CommitMessage {
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn pr_text_uses_single_commit_message() {
+        let (title, body) = pr_text_from_commits(
+            "fix-login",
+            &[commit("Fix login redirect", "Redirect back after auth.")],
+        );
+        assert_eq!(title, "Fix login redirect");
+        assert_eq!(body, "Redirect back after auth.");
+    }
+
+    #[test]
+    fn pr_text_lists_subjects_for_multiple_commits() {
+        let (title, body) = pr_text_from_commits(
+            "feature/add-user_auth",
+            &[commit("Add user model", ""), commit("Add login form", "")],
+        );
+        assert_eq!(title, "Add user auth");
+        assert_eq!(body, "- Add user model\n- Add login form");
+    }
+}
@@ -3,6 +3,7 @@
 //! This module extracts prompt-related domain logic from the command layer,
 //! making it reusable and testable.
 
+use crate::git;
 use crate::prompt::{Prompt, PromptDocument, PromptMetadata, parse_prompt_document};
 use anyhow::{Context, Result, anyhow};
 use edit::Builder;
@@ -13,10 +14,18 @@ pub struct PromptLoadArgs<'a> {
     pub prompt_editor: bool,
     pub prompt_inline: Option<&'a str>,
     pub prompt_file: Option<&'a PathBuf>,
+    /// Name of a template in `.workmux/templates`
+    pub prompt_template: Option<&'a str>,
 }
 
-/// Load a prompt from the provided arguments (editor, inline, or file).
+/// Directory holding named prompt templates, relative to the repository root
+pub const TEMPLATE_DIR: &str = ".workmux/templates";
+
+/// Load a prompt from the provided arguments (editor, inline, file, or template).
 pub fn load_prompt(args: &PromptLoadArgs) -> Result<Option<Prompt>> {
+    if let Some(name) = args.prompt_template {
+        return Ok(Some(Prompt::FromFile(find_prompt_template(name)?)));
+    }
     if args.prompt_editor {
         let mut builder = Builder::new();
         builder.suffix(".md");
@@ -37,6 +46,63 @@ pub fn load_prompt(args: &PromptLoadArgs) -> Result<Option<Prompt>> {
     }
 }
 
+/// Find `<name>.md` in the templates directory of the current worktree, falling
+/// back to the main worktree (the same places `.workmux.yaml` is looked up).
+pub fn find_prompt_template(name: &str) -> Result<PathBuf> {
+    let file_name = if name.ends_with(".md") {
+        name.to_string()
+    } else {
+        format!("{}.md", name)
+    };
+
+    let mut dirs = Vec::new();
+    if let Ok(repo_root) = git::get_repo_root() {
+        dirs.push(repo_root.join(TEMPLATE_DIR));
+    }
+    if let Ok(main_root) = git::get_main_worktree_root() {
+        let dir = main_root.join(TEMPLATE_DIR);
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    if let Some(path) = dirs
+        .iter()
+        .map(|d| d.join(&file_name))
+        .find(|p| p.is_file())
+    {
+        return Ok(path);
+    }
+
+    let mut available: Vec<String> = dirs
+        .iter()
+        .filter_map(|d| std::fs::read_dir(d).ok())
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            Some(file_name.to_str()?.strip_suffix(".md")?.to_string())
+        })
+        .collect();
+    available.sort();
+    available.dedup();
+
+    if available.is_empty() {
+        Err(anyhow!(
+            "Prompt template '{}' not found: no templates in {}",
+            name,
+            TEMPLATE_DIR
+        ))
+    } else {
+        Err(anyhow!(
+            "Prompt template '{}' not found in {}. Available: {}",
+            name,
+            TEMPLATE_DIR,
+            available.join(", ")
+        ))
+    }
+}
+
 /// Parse a prompt with optional frontmatter extraction.
 ///
 /// Returns a PromptDocument with parsed metadata and body.
@@ -1,8 +1,12 @@
 use anyhow::{Context, Result, anyhow};
 
-use crate::git;
 use tracing::{debug, info};
 
+use crate::error::ErrorKind;
+use crate::events::{self, Event, Operation};
+use crate::vcs::Vcs;
+use crate::{git, metadata};
+
 use super::cleanup;
 use super::context::WorkflowContext;
 use super::types::RemoveResult;
@@ -12,13 +16,24 @@ pub fn remove(
     handle: &str,
     force: bool,
     keep_branch: bool,
+    delete_remote: bool,
     context: &WorkflowContext,
 ) -> Result<RemoveResult> {
-    info!(handle = handle, force, keep_branch, "remove:start");
+    info!(
+        handle = handle,
+        force, keep_branch, delete_remote, "remove:start"
+    );
 
     // Get worktree path and branch - this also validates that the worktree exists
     // Smart resolution: try handle first, then branch name
-    let (worktree_path, branch_name) = git::find_worktree(handle)
+    let (worktree_path, branch_name) = context
+        .vcs
+        .find_worktree(
+            handle,
+            &context.config,
+            &context.main_worktree_root,
+            &context.main_branch,
+        )
         .with_context(|| format!("No worktree found with name '{}'", handle))?;
     debug!(handle = handle, branch = branch_name, path = %worktree_path.display(), "remove:worktree resolved");
 
@@ -57,14 +72,22 @@ pub fn remove(
         ));
     }
 
-    if worktree_path.exists() && git::has_uncommitted_changes(&worktree_path)? && !force {
-        return Err(anyhow!(
-            "Worktree has uncommitted changes. Use --force to delete anyway."
-        ));
+    if worktree_path.exists() && context.vcs.has_uncommitted_changes(&worktree_path)? && !force {
+        return Err(
+            ErrorKind::Dirty.msg("Worktree has uncommitted changes. Use --force to delete anyway.")
+        );
     }
 
     // Note: Unmerged branch check removed - git branch -d/D handles this natively
     // The CLI provides a user-friendly confirmation prompt before calling this function
+    // What `workmux undo` needs, read before cleanup deletes it
+    let undo_state = (context.vcs == Vcs::Git).then(|| {
+        (
+            git::resolve_commit(&format!("refs/heads/{}", branch_name)).ok(),
+            metadata::load(handle),
+        )
+    });
+
     info!(branch = %branch_name, keep_branch, "remove:cleanup start");
     let cleanup_result = cleanup::cleanup(
         context,
@@ -73,17 +96,26 @@ pub fn remove(
         &worktree_path,
         force,
         keep_branch,
+        delete_remote,
     )?;
 
+    if let Some((commit, metadata)) = undo_state {
+        events::record(Event::new(
+            handle,
+            Operation::Remove {
+                branch: branch_name.to_string(),
+                commit: commit.filter(|_| cleanup_result.local_branch_deleted),
+                metadata,
+            },
+        ));
+    }
+
     // Navigate to the main branch window and close the source window
-    cleanup::navigate_to_target_and_close(
-        &context.prefix,
-        &context.main_branch,
-        handle,
-        &cleanup_result,
-    )?;
+    cleanup::navigate_to_target_and_close(context, &context.main_branch, handle, &cleanup_result)?;
 
     Ok(RemoveResult {
+        handle: handle.to_string(),
         branch_removed: branch_name.to_string(),
+        cleanup: cleanup_result,
     })
 }
@@ -2,12 +2,15 @@ use anyhow::{Context, Result, anyhow};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::{cmd, config, git, prompt::Prompt, tmux};
+use crate::spinner::{self, Progress};
+use crate::vcs::Vcs;
+use crate::{config, git, prompt::Prompt, registry, tmux};
 use tracing::{debug, info};
 
 use fs_extra::dir as fs_dir;
 use fs_extra::file as fs_file;
 
+use super::hooks::{self, Hook, HookEnv, HookLog};
 use super::types::CreateResult;
 
 /// Sets up the tmux window, files, and hooks for a worktree.
@@ -38,10 +41,13 @@ pub fn setup_environment(
     );
     let prefix = config.window_prefix();
     // Use main worktree root for file operations since source files live there
-    let repo_root = git::get_main_worktree_root()?;
+    let repo_root = Vcs::from_config(config).main_root()?;
+    // Steps show in the tmux status line, and as log lines when not on a terminal
+    let op = (tmux::WorktreeOp::start(prefix, handle), spinner::step_log());
 
     // Perform file operations (copy and symlink) if requested
     if options.run_file_ops {
+        op.step("copying files…");
         handle_file_operations(&repo_root, worktree_path, &config.files)
             .context("Failed to perform file operations")?;
         debug!(
@@ -57,26 +63,18 @@ pub fn setup_environment(
         && !post_create.is_empty()
     {
         hooks_run = post_create.len();
-        // Resolve absolute paths for environment variables.
-        // canonicalize() ensures symlinks are resolved and paths are absolute.
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.to_path_buf());
-        let abs_project_root = repo_root
-            .canonicalize()
-            .unwrap_or_else(|_| repo_root.clone());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-        let hook_env = [
-            ("WORKMUX_HANDLE", handle),
-            ("WM_HANDLE", handle),
-            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-        ];
+        let hook_env = HookEnv::new(handle, worktree_path, &repo_root).with_branch(branch_name);
+        let hook_vars = hook_env.vars();
+        let log = HookLog::start(handle, Hook::PostCreate);
         for (idx, command) in post_create.iter().enumerate() {
+            op.step(&format!(
+                "running post-create hooks ({}/{})…",
+                idx + 1,
+                hooks_run
+            ));
             info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook start");
             info!(command = %command, "Running post-create hook {}/{}", idx + 1, hooks_run);
-            cmd::shell_command_with_env(command, worktree_path, &hook_env)
+            hooks::run_logged(command, worktree_path, &hook_vars, log.as_ref())
                 .with_context(|| format!("Failed to run post-create command: '{}'", command))?;
             info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook complete");
         }
@@ -87,17 +85,31 @@ pub fn setup_environment(
         );
     }
 
+    if !options.open_window {
+        return Ok(CreateResult {
+            worktree_path: worktree_path.to_path_buf(),
+            branch_name: branch_name.to_string(),
+            post_create_hooks_run: hooks_run,
+            base_branch: None,
+            did_switch: false,
+        });
+    }
+
     // Find the last workmux-managed window to insert the new one after.
     // This keeps worktree windows grouped together instead of appending at the end.
     // If not found (or error), falls back to default append behavior.
     let last_wm_window = tmux::find_last_window_with_prefix(prefix).unwrap_or(None);
 
+    // Panes start in the package directory for worktrees scoped with --package
+    let pane_dir = pane_working_dir(branch_name, worktree_path);
+
     // Create tmux window and get the initial pane's ID
     // Use handle for the window name (not branch_name)
+    op.step("opening window…");
     let initial_pane_id = tmux::create_window(
         prefix,
         handle,
-        worktree_path,
+        &pane_dir,
         /* detached: */ !options.focus_window,
         last_wm_window.as_deref(),
     )
@@ -108,6 +120,13 @@ pub fn setup_environment(
         pane_id = %initial_pane_id,
         "setup_environment:tmux window created"
     );
+    // Listed in the dashboard even while no agent runs in it
+    let window_name = tmux::prefixed(prefix, handle);
+    if let Some(session) = tmux::find_window_session(&window_name) {
+        // Resolved like the pane paths tmux reports for agents
+        let path = pane_dir.canonicalize().unwrap_or_else(|_| pane_dir.clone());
+        registry::register(&path, &session, &window_name);
+    }
 
     // Setup panes
     let panes = config.panes.as_deref().unwrap_or(&[]);
@@ -118,13 +137,18 @@ pub fn setup_environment(
         validate_prompt_consumption(&resolved_panes, agent, config, options)?;
     }
 
+    let agent_log = (options.log_agent || config.log_agent.unwrap_or(false))
+        .then(|| hooks::logs_dir(handle).ok())
+        .flatten()
+        .map(|dir| hooks::agent_log_path(&dir));
     let pane_setup_result = tmux::setup_panes(
         &initial_pane_id,
         &resolved_panes,
-        worktree_path,
+        &pane_dir,
         tmux::PaneSetupOptions {
             run_commands: options.run_pane_commands,
             prompt_file_path: options.prompt_file_path.as_deref(),
+            agent_log: agent_log.as_deref(),
         },
         config,
         agent,
@@ -155,6 +179,15 @@ pub fn setup_environment(
     })
 }
 
+/// Resolve the directory panes should start in: the recorded package
+/// directory if the worktree is package-scoped, otherwise the worktree root.
+fn pane_working_dir(branch_name: &str, worktree_path: &Path) -> PathBuf {
+    git::get_branch_package(branch_name)
+        .map(|package| worktree_path.join(package))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| worktree_path.to_path_buf())
+}
+
 pub fn resolve_pane_configuration(
     original_panes: &[config::PaneConfig],
     agent: Option<&str>,
@@ -189,6 +222,7 @@ pub fn resolve_pane_configuration(
         size: None,
         percentage: None,
         target: None,
+        keep_alive: false,
     }]
 }
 
@@ -370,6 +404,15 @@ pub fn handle_file_operations(
     Ok(())
 }
 
+/// Path of the prompt file written for a branch's agent. Slashes in the
+/// branch name (`hotfix/login`) would point into directories that don't exist.
+pub fn prompt_file_path(branch_name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "workmux-prompt-{}.md",
+        branch_name.replace('/', "-")
+    ))
+}
+
 pub fn write_prompt_file(branch_name: &str, prompt: &Prompt) -> Result<PathBuf> {
     let content = match prompt {
         Prompt::Inline(text) => text.clone(),
@@ -378,14 +421,14 @@ pub fn write_prompt_file(branch_name: &str, prompt: &Prompt) -> Result<PathBuf>
     };
 
     // Write to temp directory instead of the worktree to avoid polluting git status
-    let prompt_filename = format!("workmux-prompt-{}.md", branch_name);
-    let prompt_path = std::env::temp_dir().join(prompt_filename);
+    let prompt_path = prompt_file_path(branch_name);
     fs::write(&prompt_path, content)
         .with_context(|| format!("Failed to write prompt file '{}'", prompt_path.display()))?;
     Ok(prompt_path)
 }
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use super::*;
 
@@ -398,6 +441,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
 
         let result = resolve_pane_configuration(&original_panes, None);
@@ -414,6 +458,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
 
         let result = resolve_pane_configuration(&original_panes, Some("claude"));
@@ -431,6 +476,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
             config::PaneConfig {
                 command: Some("npm run dev".to_string()),
@@ -439,6 +485,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
         ];
 
@@ -456,6 +503,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
 
         let result = resolve_pane_configuration(&original_panes, Some("claude"));
@@ -486,6 +534,8 @@ mod tests {
             run_pane_commands,
             prompt_file_path: Some(std::path::PathBuf::from("/tmp/prompt.md")),
             focus_window: true,
+            open_window: true,
+            log_agent: false,
         }
     }
 
@@ -498,6 +548,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(false); // pane commands disabled
@@ -521,6 +572,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
         let config = make_config_with_agent(None); // no agent
         let options = make_options_with_prompt(true);
@@ -545,6 +597,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
             config::PaneConfig {
                 command: Some("clear".to_string()),
@@ -553,6 +606,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
         ];
         let config = make_config_with_agent(Some("claude"));
@@ -574,6 +628,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(true);
@@ -591,6 +646,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(true);
@@ -608,6 +664,7 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            keep_alive: false,
         }];
         let config = make_config_with_agent(Some("claude")); // config says claude
         let options = make_options_with_prompt(true);
@@ -631,6 +688,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
             config::PaneConfig {
                 command: Some("claude --verbose".to_string()), // matches
@@ -639,6 +697,7 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                keep_alive: false,
             },
         ];
         let config = make_config_with_agent(Some("claude"));
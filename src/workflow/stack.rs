@@ -0,0 +1,137 @@
+//! Stacked worktrees (`workmux add --stack <parent>`).
+//!
+//! A stacked worktree branches from another worktree's branch, which becomes
+//! its base, and records that worktree's handle as its parent. When the parent
+//! is merged, `merge` moves its children onto the branch it was merged into,
+//! so follow-up work doesn't have to be rebased by hand.
+
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::git;
+use crate::metadata::{self, WorktreeMetadata};
+
+use super::sync::SyncOutcome;
+
+/// Result of moving one child of a merged worktree
+pub struct RestackReport {
+    pub handle: String,
+    pub path: Option<PathBuf>,
+    /// Where the child forked from its parent, for restacking it by hand
+    pub fork_point: Option<String>,
+    pub outcome: SyncOutcome,
+}
+
+/// Worktrees stacked directly on `handle`, sorted by handle
+pub fn children(handle: &str) -> Vec<(String, WorktreeMetadata)> {
+    let mut children: Vec<_> = metadata::load_all()
+        .into_iter()
+        .filter(|(_, m)| m.parent.as_deref() == Some(handle))
+        .collect();
+    children.sort_by(|a, b| a.0.cmp(&b.0));
+    children
+}
+
+/// Move the worktrees stacked on `parent` onto `target`, the branch it was
+/// merged into. Their commits are rebased when the worktree is clean, and
+/// their base becomes `target` either way. Must run while `parent_branch`
+/// still exists.
+pub fn restack_children(parent: &str, parent_branch: &str, target: &str) -> Vec<RestackReport> {
+    let children = children(parent);
+    if children.is_empty() {
+        return Vec::new();
+    }
+    // A parent merged into its own parent leaves the children stacked on that
+    let new_parent = metadata::load_all()
+        .into_iter()
+        .find(|(_, m)| m.branch == target)
+        .map(|(handle, _)| handle);
+
+    children
+        .into_iter()
+        .map(|(handle, recorded)| {
+            let path = git::find_worktree(&handle).ok().map(|(path, _)| path);
+            let fork_point = path
+                .as_deref()
+                .and_then(|path| git::merge_base_with_head(path, parent_branch).ok());
+            let outcome = match (&path, &fork_point) {
+                (Some(path), Some(fork_point)) => restack(path, target, fork_point),
+                (None, _) => SyncOutcome::Skipped("worktree is missing".to_string()),
+                (Some(_), None) => {
+                    SyncOutcome::Skipped(format!("no common history with '{}'", parent_branch))
+                }
+            };
+            debug!(handle, target, ?outcome, "stack:restacked child");
+
+            let target_sha = matches!(outcome, SyncOutcome::Updated | SyncOutcome::UpToDate)
+                .then(|| git::resolve_commit(target).ok())
+                .flatten();
+            if let Err(e) = git::set_branch_base(&recorded.branch, target) {
+                debug!(handle, error = %e, "stack:failed to set base");
+            }
+            if let Some(sha) = &target_sha {
+                let _ = git::set_branch_base_sha(&recorded.branch, sha);
+            }
+            metadata::update(&handle, |m| {
+                m.base = Some(target.to_string());
+                m.parent = new_parent.clone();
+                if target_sha.is_some() {
+                    m.base_sha = target_sha.clone();
+                }
+            });
+
+            RestackReport {
+                handle,
+                path,
+                fork_point,
+                outcome,
+            }
+        })
+        .collect()
+}
+
+/// Rebase the worktree's commits since `fork_point` onto `target`, rolling
+/// back on conflicts
+fn restack(path: &Path, target: &str, fork_point: &str) -> SyncOutcome {
+    if git::has_tracked_changes(path).unwrap_or(true) {
+        return SyncOutcome::Skipped("uncommitted changes".to_string());
+    }
+    match git::head_contains(path, target) {
+        Ok(true) => return SyncOutcome::UpToDate,
+        Ok(false) => {}
+        Err(e) => return SyncOutcome::Skipped(e.to_string()),
+    }
+    if let Err(e) = git::rebase_onto_in_worktree(path, target, fork_point) {
+        debug!(path = %path.display(), error = %e, "stack:conflict, rolling back");
+        let _ = git::abort_rebase_in_worktree(path);
+        return SyncOutcome::Conflict;
+    }
+    SyncOutcome::Updated
+}
+
+/// Tell the user where a child of `parent_branch` ended up
+pub fn print_report(report: &RestackReport, parent_branch: &str, target: &str) {
+    let reason = match &report.outcome {
+        SyncOutcome::Updated | SyncOutcome::UpToDate => {
+            crate::spinner::message(&format!(
+                "✓ Restacked '{}' onto '{}'",
+                report.handle, target
+            ));
+            return;
+        }
+        SyncOutcome::Conflict => "it conflicts".to_string(),
+        SyncOutcome::Skipped(reason) => reason.clone(),
+    };
+    eprintln!(
+        "Warning: '{}' was stacked on '{}' and was not moved onto '{}' ({}).",
+        report.handle, parent_branch, target, reason
+    );
+    if let (Some(path), Some(fork_point)) = (&report.path, &report.fork_point) {
+        eprintln!(
+            "  To restack it: git -C {} rebase --onto {} {}",
+            path.display(),
+            target,
+            &fork_point[..fork_point.len().min(12)]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Bringing worktree branches up to date with their base (`workmux sync`).
+//!
+//! Each branch is rebased onto, or merged with, the branch it was created from
+//! (falling back to the main branch), preferring the base's upstream so a
+//! preceding fetch is picked up without touching the local base branch.
+//! Conflicts are rolled back so no worktree is left mid-rebase.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use crate::config::SyncStrategy;
+use crate::{git, metadata};
+
+use super::context::WorkflowContext;
+
+/// What happened to a single worktree
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    /// The branch already contains its base
+    UpToDate,
+    /// The branch was rebased onto, or merged with, its base
+    Updated,
+    /// Updating hit conflicts and was rolled back
+    Conflict,
+    /// Not attempted, with the reason
+    Skipped(String),
+}
+
+/// Result of syncing one worktree
+pub struct SyncReport {
+    pub handle: String,
+    pub branch: String,
+    pub path: PathBuf,
+    /// Ref the branch was synced with (e.g. "origin/main")
+    pub target: String,
+    pub outcome: SyncOutcome,
+}
+
+/// Sync the named worktrees, or every worktree when `names` is empty
+pub fn sync(
+    names: &[String],
+    strategy: SyncStrategy,
+    context: &WorkflowContext,
+) -> Result<Vec<SyncReport>> {
+    info!(?names, ?strategy, "sync:start");
+
+    let worktrees = if names.is_empty() {
+        git::list_worktrees()?
+            .into_iter()
+            .filter(|(path, _)| *path != context.main_worktree_root)
+            .collect()
+    } else {
+        names
+            .iter()
+            .map(|name| {
+                git::find_worktree(name)
+                    .with_context(|| format!("No worktree found with name '{}'", name))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut reports = Vec::new();
+    for (path, branch) in worktrees {
+        let handle = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&branch)
+            .to_string();
+        let target = sync_target(&branch, context);
+        let outcome = sync_worktree(&path, &branch, &target, strategy, context);
+        debug!(handle, branch, target, ?outcome, "sync:worktree");
+        reports.push(SyncReport {
+            handle,
+            branch,
+            path,
+            target,
+            outcome,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// The ref a branch syncs with: its recorded base (or the main branch),
+/// through that branch's upstream when it has one.
+fn sync_target(branch: &str, context: &WorkflowContext) -> String {
+    let base = git::get_branch_base(branch)
+        .ok()
+        .filter(|base| git::branch_exists(base).unwrap_or(false))
+        .unwrap_or_else(|| context.main_branch.clone());
+    git::get_upstream_branch(&base).unwrap_or(base)
+}
+
+fn sync_worktree(
+    path: &Path,
+    branch: &str,
+    target: &str,
+    strategy: SyncStrategy,
+    context: &WorkflowContext,
+) -> SyncOutcome {
+    if git::is_detached(branch) {
+        return SyncOutcome::Skipped("detached HEAD".to_string());
+    }
+    if branch == context.main_branch || target == branch {
+        return SyncOutcome::Skipped("is the base branch".to_string());
+    }
+    if !path.exists() {
+        return SyncOutcome::Skipped("worktree directory is missing".to_string());
+    }
+    if git::has_tracked_changes(path).unwrap_or(true) {
+        return SyncOutcome::Skipped("uncommitted changes".to_string());
+    }
+    match git::head_contains(path, target) {
+        Ok(true) => return SyncOutcome::UpToDate,
+        Ok(false) => {}
+        Err(e) => return SyncOutcome::Skipped(e.to_string()),
+    }
+
+    let result = match strategy {
+        SyncStrategy::Rebase => git::rebase_branch_onto_base(path, target),
+        SyncStrategy::Merge => {
+            let message = format!("Merge {} into {}", target, branch);
+            git::merge_in_worktree_with_message(path, target, &message)
+        }
+    };
+
+    if let Err(e) = result {
+        debug!(branch, error = %e, "sync:conflict, rolling back");
+        let _ = match strategy {
+            SyncStrategy::Rebase => git::abort_rebase_in_worktree(path),
+            SyncStrategy::Merge => git::abort_merge_in_worktree(path),
+        };
+        return SyncOutcome::Conflict;
+    }
+
+    // Keep the recorded fork point in step with the new base
+    if let Ok(sha) = git::resolve_commit(target) {
+        let _ = git::set_branch_base_sha(branch, &sha);
+        if let Some(handle) = path.file_name().and_then(|n| n.to_str()) {
+            metadata::update(handle, |m| m.base_sha = Some(sha));
+        }
+    }
+    SyncOutcome::Updated
+}
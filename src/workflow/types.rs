@@ -1,5 +1,8 @@
+use serde::Serialize;
 use std::path::PathBuf;
 
+use crate::config::MergeStrategy;
+use crate::git::GitStatus;
 use crate::github::PrSummary;
 use crate::prompt::Prompt;
 
@@ -12,6 +15,11 @@ pub struct CreateArgs<'a> {
     pub prompt: Option<&'a Prompt>,
     pub options: SetupOptions,
     pub agent: Option<&'a str>,
+    /// Monorepo package (relative path) to scope the worktree to
+    pub package: Option<&'a str>,
+    /// Check out the base commit with a detached HEAD instead of a branch
+    /// (`branch_name` then only names the worktree)
+    pub detached: bool,
 }
 
 /// Result of creating a worktree
@@ -24,24 +32,62 @@ pub struct CreateResult {
     pub did_switch: bool,
 }
 
-/// Result of merging a worktree
+/// Result of merging a worktree, printed by `merge --json`
+#[derive(Serialize)]
 pub struct MergeResult {
+    #[serde(rename = "branch")]
     pub branch_merged: String,
+    #[serde(rename = "target")]
     pub main_branch: String,
+    /// None when selected commits were cherry-picked (`--commits`)
+    pub strategy: Option<MergeStrategy>,
+    /// Commits on the branch that the target didn't have yet
+    pub commits_merged: usize,
+    /// The commits applied by `--commits`, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub cherry_picked: Vec<String>,
     pub had_staged_changes: bool,
+    /// None when the worktree was kept (`--keep`)
+    pub cleanup: Option<CleanupResult>,
 }
 
-/// Result of removing a worktree
+/// Result of merging a worktree's PR on the forge (`merge --pr`)
+pub struct PrMergeResult {
+    pub branch_merged: String,
+    /// How the forge refers to the PR, e.g. "PR #12"
+    pub label: String,
+    /// False when the PR was queued (merge queue, auto-merge) but had not
+    /// landed yet; the worktree is then left in place
+    pub merged: bool,
+}
+
+/// Result of checking whether a worktree would merge cleanly (`merge --check`)
+pub struct MergeCheck {
+    pub branch: String,
+    pub target: String,
+    /// Files that would conflict; empty when the merge is clean
+    pub conflicts: Vec<String>,
+}
+
+/// Result of removing a worktree, printed by `remove --json`
+#[derive(Serialize)]
 pub struct RemoveResult {
+    pub handle: String,
+    #[serde(rename = "branch")]
     pub branch_removed: String,
+    pub cleanup: CleanupResult,
 }
 
 /// Result of cleanup operations
+#[derive(Serialize)]
 pub struct CleanupResult {
     pub tmux_window_killed: bool,
     pub worktree_removed: bool,
     pub local_branch_deleted: bool,
+    /// Whether the branch the local branch tracked was deleted on its remote
+    pub remote_branch_deleted: bool,
     /// The actual window name to close later (when running inside a duplicate window)
+    #[serde(skip)]
     pub window_to_close_later: Option<String>,
 }
 
@@ -54,6 +100,10 @@ pub struct SetupOptions {
     pub prompt_file_path: Option<PathBuf>,
     /// If true, switch to the new tmux window when done; if false, leave it in the background.
     pub focus_window: bool,
+    /// If false, skip creating a tmux window (workspaces open one combined window instead).
+    pub open_window: bool,
+    /// Record the agent pane's output (`--log-agent`, or `log_agent` in config)
+    pub log_agent: bool,
 }
 
 impl SetupOptions {
@@ -66,6 +116,8 @@ impl SetupOptions {
             run_pane_commands: true,
             prompt_file_path: None,
             focus_window: true,
+            open_window: true,
+            log_agent: false,
         }
     }
 
@@ -77,6 +129,8 @@ impl SetupOptions {
             run_pane_commands,
             prompt_file_path: None,
             focus_window: true,
+            open_window: true,
+            log_agent: false,
         }
     }
 
@@ -94,15 +148,37 @@ impl SetupOptions {
             run_pane_commands,
             prompt_file_path,
             focus_window: true,
+            open_window: true,
+            log_agent: false,
         }
     }
 }
 
 /// List all worktrees with their status
+#[derive(Serialize)]
 pub struct WorktreeInfo {
+    pub handle: String,
     pub branch: String,
     pub path: PathBuf,
     pub has_tmux: bool,
     pub has_unmerged: bool,
+    /// Monorepo package the worktree is scoped to (`add --package`)
+    pub package: Option<String>,
+    /// Agent status (working/waiting/done) of the worktree's tmux window, if set
+    pub agent_status: Option<String>,
+    /// Unix timestamp when the agent status was last set
+    pub status_ts: Option<u64>,
+    /// Whether an agent process is running in the worktree's tmux window
+    pub agent_running: bool,
+    /// Panes in the worktree's tmux window whose process has exited
+    pub dead_panes: usize,
     pub pr_info: Option<PrSummary>,
+    /// Pull request recorded by `pr create`
+    pub pr_number: Option<u32>,
+    /// Diff stats and sync state, from the shared git status cache
+    pub git_status: Option<GitStatus>,
+    /// Unix timestamp when `workmux add` created the worktree
+    pub created_at: Option<u64>,
+    /// Agent the worktree was started with
+    pub agent: Option<String>,
 }
@@ -0,0 +1,368 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, PaneConfig, SplitDirection};
+use crate::{git, naming, tmux};
+use tracing::{debug, info};
+
+use super::context::WorkflowContext;
+use super::create::create as create_worktree;
+use super::hooks;
+use super::types::{CreateArgs, SetupOptions};
+
+/// A repository in a workspace together with the worktree created for it
+pub struct WorkspaceMember {
+    pub repo: PathBuf,
+    pub worktree_path: PathBuf,
+    pub config: Config,
+}
+
+/// Resolve a configured repository path to an absolute path.
+///
+/// `~` expands to the home directory; relative paths are resolved from `base`.
+pub fn resolve_repo_path(raw: &str, base: &Path, home: Option<&Path>) -> PathBuf {
+    if let Some(home) = home {
+        if raw == "~" {
+            return home.to_path_buf();
+        }
+        if let Some(rest) = raw.strip_prefix("~/") {
+            return home.join(rest);
+        }
+    }
+    let path = Path::new(raw);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+/// Pick the workspace to operate on and resolve its repository paths.
+///
+/// Uses the explicit `name` if given, otherwise the only configured workspace,
+/// otherwise the single workspace that contains `current_repo`.
+pub fn resolve_workspace(
+    config: &Config,
+    name: Option<&str>,
+    current_repo: Option<&Path>,
+    home: Option<&Path>,
+) -> Result<(String, Vec<PathBuf>)> {
+    let workspaces = config
+        .workspaces
+        .as_ref()
+        .filter(|w| !w.is_empty())
+        .ok_or_else(|| {
+            anyhow!("No workspaces configured. Add a 'workspaces' section to your config.")
+        })?;
+
+    let base = current_repo
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    let repos_of = |ws: &crate::config::WorkspaceConfig| -> Vec<PathBuf> {
+        ws.repos
+            .iter()
+            .map(|raw| resolve_repo_path(raw, &base, home))
+            .collect()
+    };
+    let available = || workspaces.keys().cloned().collect::<Vec<_>>().join(", ");
+
+    if let Some(name) = name {
+        let ws = workspaces.get(name).ok_or_else(|| {
+            anyhow!(
+                "Workspace '{}' not found. Available workspaces: {}",
+                name,
+                available()
+            )
+        })?;
+        return Ok((name.to_string(), repos_of(ws)));
+    }
+
+    if workspaces.len() == 1 {
+        let (name, ws) = workspaces.iter().next().expect("len checked above");
+        return Ok((name.clone(), repos_of(ws)));
+    }
+
+    if let Some(current) = current_repo {
+        let current = current.canonicalize().unwrap_or(current.to_path_buf());
+        let matching: Vec<_> = workspaces
+            .iter()
+            .filter(|(_, ws)| {
+                repos_of(ws)
+                    .iter()
+                    .any(|repo| repo.canonicalize().unwrap_or(repo.clone()) == current)
+            })
+            .collect();
+        if let [(name, ws)] = matching.as_slice() {
+            return Ok(((*name).clone(), repos_of(ws)));
+        }
+    }
+
+    Err(anyhow!(
+        "Multiple workspaces configured. Use --workspace to pick one of: {}",
+        available()
+    ))
+}
+
+/// Create a worktree for `branch_name` in every workspace repository and open
+/// one tmux window with a pane per repository.
+pub fn create(
+    repos: &[PathBuf],
+    branch_name: &str,
+    base_branch: Option<&str>,
+    agent: Option<&str>,
+    options: SetupOptions,
+) -> Result<Vec<WorkspaceMember>> {
+    info!(
+        branch = branch_name,
+        repos = repos.len(),
+        "workspace:create start"
+    );
+
+    tmux::ensure_running()?;
+
+    // Pre-flight: validate every repository before creating anything so a
+    // problem in the last repo doesn't leave the others half set up
+    let mut planned: Vec<(PathBuf, String, WorkflowContext)> = Vec::new();
+    for repo in repos {
+        let context = enter_repo(repo, agent)?;
+        if git::worktree_exists(branch_name)? {
+            return Err(anyhow!(
+                "A worktree for branch '{}' already exists in {}",
+                branch_name,
+                repo.display()
+            ));
+        }
+        let handle = naming::derive_handle(branch_name, None, &context.config)?;
+        planned.push((repo.clone(), handle, context));
+    }
+
+    let (_, window_handle, first_context) = planned
+        .first()
+        .ok_or_else(|| anyhow!("Workspace has no repositories"))?;
+    let prefix = first_context.prefix.clone();
+    let window_handle = window_handle.clone();
+    if tmux::window_exists(&prefix, &window_handle)? {
+        return Err(anyhow!(
+            "A tmux window named '{}{}' already exists",
+            prefix,
+            window_handle
+        ));
+    }
+
+    let mut members: Vec<WorkspaceMember> = Vec::new();
+    for (repo, handle, context) in planned {
+        std::env::set_current_dir(&repo)
+            .with_context(|| format!("Could not change directory to '{}'", repo.display()))?;
+        let result = create_worktree(
+            &context,
+            CreateArgs {
+                branch_name,
+                handle: &handle,
+                base_branch,
+                remote_branch: None,
+                prompt: None,
+                options: SetupOptions {
+                    open_window: false,
+                    ..options.clone()
+                },
+                agent,
+                package: None,
+                detached: false,
+            },
+        )
+        .with_context(|| {
+            let created: Vec<String> = members
+                .iter()
+                .map(|m| m.repo.display().to_string())
+                .collect();
+            if created.is_empty() {
+                format!("Failed to create worktree in {}", repo.display())
+            } else {
+                format!(
+                    "Failed to create worktree in {} (already created in: {}). \
+                     Run 'workmux workspace remove {}' to clean up.",
+                    repo.display(),
+                    created.join(", "),
+                    branch_name
+                )
+            }
+        })?;
+        debug!(repo = %repo.display(), path = %result.worktree_path.display(), "workspace:worktree created");
+        members.push(WorkspaceMember {
+            repo,
+            worktree_path: result.worktree_path,
+            config: context.config,
+        });
+    }
+
+    open_combined_window(&members, &prefix, &window_handle, &options, agent)?;
+    info!(branch = branch_name, handle = %window_handle, "workspace:create completed");
+    Ok(members)
+}
+
+/// Change into a repository and build its workflow context
+pub fn enter_repo(repo: &Path, agent: Option<&str>) -> Result<WorkflowContext> {
+    if !repo.is_dir() {
+        return Err(anyhow!(
+            "Workspace repository '{}' does not exist",
+            repo.display()
+        ));
+    }
+    std::env::set_current_dir(repo)
+        .with_context(|| format!("Could not change directory to '{}'", repo.display()))?;
+    let config = Config::load(agent)?;
+    WorkflowContext::new(config)
+        .with_context(|| format!("'{}' is not a git repository", repo.display()))
+}
+
+/// Open one tmux window with a pane per workspace member, side by side
+fn open_combined_window(
+    members: &[WorkspaceMember],
+    prefix: &str,
+    handle: &str,
+    options: &SetupOptions,
+    agent: Option<&str>,
+) -> Result<()> {
+    let Some(first) = members.first() else {
+        return Ok(());
+    };
+
+    let last_wm_window = tmux::find_last_window_with_prefix(prefix).unwrap_or(None);
+    let initial_pane_id = tmux::create_window(
+        prefix,
+        handle,
+        &first.worktree_path,
+        /* detached: */ !options.focus_window,
+        last_wm_window.as_deref(),
+    )
+    .context("Failed to create tmux window")?;
+
+    // Each repo gets a single pane running its agent
+    let agent_pane = [PaneConfig {
+        command: Some("<agent>".to_string()),
+        focus: true,
+        split: None,
+        size: None,
+        percentage: None,
+        target: None,
+        keep_alive: false,
+    }];
+
+    let mut pane_id = initial_pane_id.clone();
+    for (idx, member) in members.iter().enumerate() {
+        if idx > 0 {
+            pane_id = tmux::split_pane_with_command(
+                &pane_id,
+                &SplitDirection::Horizontal,
+                &member.worktree_path,
+                None,
+                None,
+                None,
+            )?;
+        }
+        // Each repository keeps its own log, like its hook logs
+        let agent_log = (options.log_agent || member.config.log_agent.unwrap_or(false))
+            .then(|| git::get_git_common_dir_in(Some(&member.repo)).ok())
+            .flatten()
+            .map(|dir| hooks::agent_log_path(&hooks::logs_dir_in(&dir, handle)));
+        tmux::setup_panes(
+            &pane_id,
+            &agent_pane,
+            &member.worktree_path,
+            tmux::PaneSetupOptions {
+                run_commands: options.run_pane_commands,
+                prompt_file_path: None,
+                agent_log: agent_log.as_deref(),
+            },
+            &member.config,
+            agent,
+        )
+        .with_context(|| format!("Failed to set up pane for {}", member.repo.display()))?;
+    }
+    tmux::select_layout(&initial_pane_id, "even-horizontal")?;
+
+    if options.focus_window {
+        tmux::select_pane(&initial_pane_id)?;
+        tmux::select_window(prefix, handle)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WorkspaceConfig;
+    use std::collections::BTreeMap;
+
+    fn config_with(workspaces: &[(&str, &[&str])]) -> Config {
+        let map: BTreeMap<String, WorkspaceConfig> = workspaces
+            .iter()
+            .map(|(name, repos)| {
+                (
+                    name.to_string(),
+                    WorkspaceConfig {
+                        repos: repos.iter().map(|r| r.to_string()).collect(),
+                    },
+                )
+            })
+            .collect();
+        Config {
+            workspaces: Some(map),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolve_repo_path_expands_home_and_relative() {
+        let base = Path::new("/code/frontend");
+        let home = Some(Path::new("/home/me"));
+        assert_eq!(
+            resolve_repo_path("~/code/api", base, home),
+            PathBuf::from("/home/me/code/api")
+        );
+        assert_eq!(
+            resolve_repo_path("/srv/api", base, home),
+            PathBuf::from("/srv/api")
+        );
+        assert_eq!(
+            resolve_repo_path("../backend", base, home),
+            PathBuf::from("/code/frontend/../backend")
+        );
+    }
+
+    #[test]
+    fn resolve_workspace_without_config_errors() {
+        let err = resolve_workspace(&Config::default(), None, None, None).unwrap_err();
+        assert!(err.to_string().contains("No workspaces configured"));
+    }
+
+    #[test]
+    fn resolve_workspace_uses_single_workspace() {
+        let config = config_with(&[("shop", &["/a", "/b"])]);
+        let (name, repos) = resolve_workspace(&config, None, None, None).unwrap();
+        assert_eq!(name, "shop");
+        assert_eq!(repos, vec![PathBuf::from("/a"), PathBuf::from("/b")]);
+    }
+
+    #[test]
+    fn resolve_workspace_by_name() {
+        let config = config_with(&[("shop", &["/a"]), ("blog", &["/c"])]);
+        let (name, repos) = resolve_workspace(&config, Some("blog"), None, None).unwrap();
+        assert_eq!(name, "blog");
+        assert_eq!(repos, vec![PathBuf::from("/c")]);
+
+        let err = resolve_workspace(&config, Some("nope"), None, None).unwrap_err();
+        assert!(err.to_string().contains("blog, shop"));
+    }
+
+    #[test]
+    fn resolve_workspace_by_current_repo() {
+        let config = config_with(&[("shop", &["/a", "/b"]), ("blog", &["/c"])]);
+        let (name, _) = resolve_workspace(&config, None, Some(Path::new("/b")), None).unwrap();
+        assert_eq!(name, "shop");
+
+        let err = resolve_workspace(&config, None, Some(Path::new("/z")), None).unwrap_err();
+        assert!(err.to_string().contains("--workspace"));
+    }
+}
@@ -540,13 +540,19 @@ def write_workmux_config(
     post_create: Optional[List[str]] = None,
     pre_merge: Optional[List[str]] = None,
     pre_remove: Optional[List[str]] = None,
+    post_merge: Optional[List[str]] = None,
+    post_remove: Optional[List[str]] = None,
     files: Optional[Dict[str, List[str]]] = None,
     env: Optional[TmuxEnvironment] = None,
     window_prefix: Optional[str] = None,
     agent: Optional[str] = None,
     merge_strategy: Optional[str] = None,
+    merge: Optional[Dict[str, Any]] = None,
     worktree_naming: Optional[str] = None,
     worktree_prefix: Optional[str] = None,
+    worktree_dir: Optional[str] = None,
+    profiles: Optional[List[Dict[str, Any]]] = None,
+    layouts: Optional[Dict[str, List[Dict[str, Any]]]] = None,
 ):
     """Creates a .workmux.yaml file from structured data and optionally commits it."""
     config: Dict[str, Any] = {}
@@ -558,6 +564,10 @@ def write_workmux_config(
         config["pre_merge"] = pre_merge
     if pre_remove:
         config["pre_remove"] = pre_remove
+    if post_merge:
+        config["post_merge"] = post_merge
+    if post_remove:
+        config["post_remove"] = post_remove
     if files:
         config["files"] = files
     if window_prefix:
@@ -566,10 +576,18 @@ def write_workmux_config(
         config["agent"] = agent
     if merge_strategy:
         config["merge_strategy"] = merge_strategy
+    if merge:
+        config["merge"] = merge
     if worktree_naming:
         config["worktree_naming"] = worktree_naming
     if worktree_prefix:
         config["worktree_prefix"] = worktree_prefix
+    if worktree_dir:
+        config["worktree_dir"] = worktree_dir
+    if profiles:
+        config["profiles"] = profiles
+    if layouts:
+        config["layouts"] = layouts
     (repo_path / ".workmux.yaml").write_text(yaml.dump(config))
 
     # If env is provided, commit the config file to avoid uncommitted changes in merge tests
@@ -832,7 +850,9 @@ def run_workmux_remove(
     force: bool = False,
     keep_branch: bool = False,
     gone: bool = False,
+    merged: bool = False,
     all: bool = False,
+    delete_remote: bool = False,
     user_input: Optional[str] = None,
     expect_fail: bool = False,
     from_window: Optional[str] = None,
@@ -851,7 +871,9 @@ def run_workmux_remove(
         force: Whether to use -f flag to skip confirmation
         keep_branch: Whether to use --keep-branch flag to keep the local branch
         gone: Whether to use --gone flag to remove worktrees with deleted upstreams
+        merged: Whether to use --merged flag to remove worktrees merged into main
         all: Whether to use --all flag to remove all worktrees
+        delete_remote: Whether to use --delete-remote to also delete the remote branch
         user_input: Optional string to pipe to stdin (e.g., 'y' for confirmation)
         expect_fail: If True, asserts the command fails (non-zero exit code)
         from_window: Optional tmux window name to run the command from (useful for testing remove from within worktree window)
@@ -868,7 +890,9 @@ def run_workmux_remove(
     force_flag = "-f " if force else ""
     keep_branch_flag = "--keep-branch " if keep_branch else ""
     gone_flag = "--gone " if gone else ""
+    merged_flag = "--merged " if merged else ""
     all_flag = "--all " if all else ""
+    delete_remote_flag = "--delete-remote " if delete_remote else ""
     branch_arg = branch_name if branch_name else ""
     input_cmd = f"echo '{user_input}' | " if user_input else ""
 
@@ -880,7 +904,7 @@ def run_workmux_remove(
         remove_script = (
             f"cd {worktree_path} && "
             f"{input_cmd}"
-            f"{workmux_exe_path} remove {force_flag}{keep_branch_flag}{gone_flag}{all_flag}{branch_arg} "
+            f"{workmux_exe_path} remove {force_flag}{keep_branch_flag}{gone_flag}{merged_flag}{all_flag}{delete_remote_flag}{branch_arg} "
             f"> {stdout_file} 2> {stderr_file}; "
             f"echo $? > {exit_code_file}"
         )
@@ -888,7 +912,7 @@ def run_workmux_remove(
         remove_script = (
             f"cd {repo_path} && "
             f"{input_cmd}"
-            f"{workmux_exe_path} remove {force_flag}{keep_branch_flag}{gone_flag}{all_flag}{branch_arg} "
+            f"{workmux_exe_path} remove {force_flag}{keep_branch_flag}{gone_flag}{merged_flag}{all_flag}{delete_remote_flag}{branch_arg} "
             f"> {stdout_file} 2> {stderr_file}; "
             f"echo $? > {exit_code_file}"
         )
@@ -1072,3 +1096,29 @@ exit 1
     env.env["PATH"] = new_path
     # CRITICAL: Also set PATH in the tmux session so workmux can find the fake gh
     env.tmux(["set-environment", "-g", "PATH", new_path])
+
+
+def install_fake_gh_pr_list(env: TmuxEnvironment, prs: list) -> None:
+    """Creates a fake 'gh' that answers 'pr list' (with any flags) with `prs`."""
+    import json
+
+    bin_dir = env.home_path / "bin"
+    bin_dir.mkdir(exist_ok=True)
+    json_output = json.dumps(prs).replace("'", "'\\''")
+
+    gh_script = bin_dir / "gh"
+    gh_script.write_text(
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo '{json_output}'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+"""
+    )
+    gh_script.chmod(0o755)
+
+    new_path = f"{bin_dir}:{env.env.get('PATH', '')}"
+    env.env["PATH"] = new_path
+    env.tmux(["set-environment", "-g", "PATH", new_path])
@@ -0,0 +1,207 @@
+//! A private tmux server, home directory and git repository per test.
+//!
+//! workmux is pointed at the server through `WORKMUX_TMUX_SOCKET` and at the
+//! temporary home through `HOME`, so nothing touches the user's tmux sessions,
+//! config or state.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+
+const WORKMUX: &str = env!("CARGO_BIN_EXE_workmux");
+
+/// Keeps sockets unique between tests running in parallel
+static NEXT_SOCKET: AtomicUsize = AtomicUsize::new(0);
+
+const CONFIG: &str = "\
+window_prefix: wm-
+panes:
+  - command: echo ready
+";
+
+pub struct TestEnv {
+    _dir: TempDir,
+    socket: String,
+    home: PathBuf,
+    /// Main worktree of the test repository, with `.workmux.yaml` committed
+    pub repo: PathBuf,
+}
+
+impl TestEnv {
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let root = dir.path().canonicalize().expect("resolve temp dir");
+        let home = root.join("home");
+        let repo = root.join("repo");
+        std::fs::create_dir_all(&home).expect("create home");
+        std::fs::create_dir_all(&repo).expect("create repo");
+        let socket = format!(
+            "workmux-e2e-{}-{}",
+            std::process::id(),
+            NEXT_SOCKET.fetch_add(1, Ordering::SeqCst)
+        );
+
+        let env = Self {
+            _dir: dir,
+            socket,
+            home,
+            repo,
+        };
+        env.git(&env.repo, &["init", "-q", "-b", "main"]);
+        std::fs::write(env.repo.join("README.md"), "# test\n").expect("write README");
+        std::fs::write(env.repo.join(".workmux.yaml"), CONFIG).expect("write config");
+        env.git(&env.repo, &["add", "."]);
+        env.git(&env.repo, &["commit", "-q", "-m", "initial"]);
+        env.tmux(&[
+            "-f",
+            "/dev/null",
+            "new-session",
+            "-d",
+            "-s",
+            "main",
+            "-x",
+            "200",
+            "-y",
+            "50",
+            "-c",
+            &env.repo.to_string_lossy(),
+        ]);
+        env
+    }
+
+    /// A command with the test's isolated environment
+    fn command(&self, program: &str) -> Command {
+        let mut command = Command::new(program);
+        command
+            .env("HOME", &self.home)
+            .env("WORKMUX_TMUX_SOCKET", &self.socket)
+            .env("SHELL", "/bin/sh")
+            .env("GIT_AUTHOR_NAME", "workmux")
+            .env("GIT_AUTHOR_EMAIL", "workmux@example.com")
+            .env("GIT_COMMITTER_NAME", "workmux")
+            .env("GIT_COMMITTER_EMAIL", "workmux@example.com")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env_remove("TMUX")
+            .env_remove("TMUX_PANE")
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("XDG_STATE_HOME")
+            .stdin(Stdio::null());
+        command
+    }
+
+    /// Run workmux in `dir`, returning its output whether it succeeded or not
+    pub fn workmux_in(&self, dir: &Path, args: &[&str]) -> Output {
+        self.command(WORKMUX)
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run workmux")
+    }
+
+    /// Run workmux in the main worktree and panic if it fails
+    pub fn workmux(&self, args: &[&str]) -> String {
+        let output = self.workmux_in(&self.repo, args);
+        assert!(
+            output.status.success(),
+            "workmux {} failed:\n{}{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    pub fn git(&self, dir: &Path, args: &[&str]) -> String {
+        let output = self
+            .command("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    pub fn tmux(&self, args: &[&str]) -> String {
+        let output = self
+            .command("tmux")
+            .args(["-L", &self.socket])
+            .args(args)
+            .output()
+            .expect("run tmux");
+        assert!(
+            output.status.success(),
+            "tmux {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    pub fn window_names(&self) -> Vec<String> {
+        self.tmux(&["list-windows", "-a", "-F", "#{window_name}"])
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn has_window(&self, name: &str) -> bool {
+        self.window_names().iter().any(|w| w == name)
+    }
+
+    pub fn branch_exists(&self, branch: &str) -> bool {
+        self.command("git")
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("refs/heads/{}", branch))
+            .current_dir(&self.repo)
+            .stdout(Stdio::null())
+            .status()
+            .expect("run git")
+            .success()
+    }
+
+    /// Where workmux puts a worktree with the default `worktree_dir`
+    pub fn worktree_path(&self, handle: &str) -> PathBuf {
+        self.repo
+            .parent()
+            .expect("repo has a parent")
+            .join("repo__worktrees")
+            .join(handle)
+    }
+
+    /// Commit a new file in `dir`
+    pub fn commit_file(&self, dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).expect("write file");
+        self.git(dir, &["add", name]);
+        self.git(dir, &["commit", "-q", "-m", &format!("Add {}", name)]);
+    }
+
+    /// Poll until `condition` holds; window cleanup runs in the tmux server
+    /// after workmux exits
+    pub fn wait_until(&self, what: &str, condition: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "timed out waiting for {}", what);
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = self
+            .command("tmux")
+            .args(["-L", &self.socket, "kill-server"])
+            .stderr(Stdio::null())
+            .status();
+    }
+}
@@ -0,0 +1,81 @@
+//! End-to-end tests: the workmux binary against a private tmux server and
+//! throwaway git repositories (see [`harness`]).
+//!
+//! They need `tmux` and `git` installed, so they are ignored by default. Run
+//! them with `just e2e` or `cargo test --test e2e -- --ignored`.
+
+mod harness;
+
+use harness::TestEnv;
+
+#[test]
+#[ignore = "needs tmux and git"]
+fn add_creates_worktree_branch_and_window() {
+    let env = TestEnv::new();
+
+    env.workmux(&["add", "feature"]);
+
+    assert!(env.worktree_path("feature").is_dir());
+    assert!(env.branch_exists("feature"));
+    assert!(env.has_window("wm-feature"));
+}
+
+#[test]
+#[ignore = "needs tmux and git"]
+fn open_recreates_closed_window() {
+    let env = TestEnv::new();
+    env.workmux(&["add", "feature"]);
+
+    env.workmux(&["close", "feature"]);
+    env.wait_until("the window to close", || !env.has_window("wm-feature"));
+
+    env.workmux(&["open", "feature"]);
+    assert!(env.has_window("wm-feature"));
+}
+
+#[test]
+#[ignore = "needs tmux and git"]
+fn merge_lands_commits_and_cleans_up() {
+    let env = TestEnv::new();
+    env.workmux(&["add", "feature"]);
+    let worktree = env.worktree_path("feature");
+    env.commit_file(&worktree, "feature.txt", "done\n");
+
+    env.workmux(&["merge", "feature"]);
+
+    assert!(env.repo.join("feature.txt").exists());
+    assert!(!worktree.exists());
+    assert!(!env.branch_exists("feature"));
+    env.wait_until("the window to close", || !env.has_window("wm-feature"));
+}
+
+#[test]
+#[ignore = "needs tmux and git"]
+fn merge_refuses_conflicting_branch_without_changing_main() {
+    let env = TestEnv::new();
+    env.workmux(&["add", "feature"]);
+    let worktree = env.worktree_path("feature");
+    env.commit_file(&worktree, "README.md", "# feature\n");
+    env.commit_file(&env.repo, "README.md", "# main\n");
+    let head = env.git(&env.repo, &["rev-parse", "HEAD"]);
+
+    let output = env.workmux_in(&env.repo, &["merge", "feature"]);
+
+    assert!(!output.status.success());
+    assert_eq!(env.git(&env.repo, &["rev-parse", "HEAD"]), head);
+    assert!(worktree.is_dir());
+    assert!(env.has_window("wm-feature"));
+}
+
+#[test]
+#[ignore = "needs tmux and git"]
+fn remove_discards_worktree_branch_and_window() {
+    let env = TestEnv::new();
+    env.workmux(&["add", "feature"]);
+
+    env.workmux(&["remove", "--force", "feature"]);
+
+    assert!(!env.worktree_path("feature").exists());
+    assert!(!env.branch_exists("feature"));
+    env.wait_until("the window to close", || !env.has_window("wm-feature"));
+}
@@ -0,0 +1,68 @@
+"""Tests for `profiles`, config overrides for branches matching a glob."""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    run_workmux_merge,
+    write_workmux_config,
+)
+
+
+def test_profile_replaces_hooks_and_sets_base(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """A matching profile swaps post_create and picks the base branch."""
+    env = isolated_tmux_server
+    env.run_command(["git", "branch", "production"], cwd=repo_path)
+    write_workmux_config(
+        repo_path,
+        post_create=["touch default-hook-ran"],
+        profiles=[
+            {
+                "match": "hotfix/*",
+                "base": "production",
+                "post_create": ["touch hotfix-hook-ran"],
+            }
+        ],
+        env=env,
+    )
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "hotfix/login")
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-x")
+
+    hotfix_path = get_worktree_path(repo_path, "hotfix-login")
+    assert (hotfix_path / "hotfix-hook-ran").exists()
+    assert not (hotfix_path / "default-hook-ran").exists()
+    feature_path = get_worktree_path(repo_path, "feature-x")
+    assert (feature_path / "default-hook-ran").exists()
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "info hotfix-login --json"
+    )
+    assert json.loads(result.stdout)["base"] == "production"
+
+
+def test_profile_pre_merge_applies_to_merge(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Merging a branch runs its profile's pre_merge instead of the default."""
+    env = isolated_tmux_server
+    marker = env.tmp_path / "pre_merge.txt"
+    write_workmux_config(
+        repo_path,
+        pre_merge=[f"echo default >> {marker}"],
+        profiles=[{"match": "spike/*", "pre_merge": [f"echo spike >> {marker}"]}],
+        env=env,
+    )
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "spike/idea")
+    create_commit(env, get_worktree_path(repo_path, "spike-idea"), "feat: idea")
+    run_workmux_merge(env, workmux_exe_path, repo_path, "spike-idea")
+
+    assert marker.read_text().split() == ["spike"]
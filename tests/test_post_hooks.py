@@ -0,0 +1,97 @@
+"""Tests for post_merge and post_remove hooks."""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_merge,
+    run_workmux_remove,
+    write_workmux_config,
+)
+
+
+class TestPostHooks:
+    """Tests for hooks that run after merge and removal."""
+
+    def test_post_merge_hook_runs_in_target_with_env(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies post_merge runs after the merge, in the target worktree."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-merge"
+        env_file = env.tmp_path / "post_merge_env.txt"
+
+        write_workmux_config(
+            repo_path,
+            post_merge=[
+                f'echo "BRANCH=$WORKMUX_BRANCH" >> {env_file}',
+                f'echo "TARGET=$WORKMUX_TARGET" >> {env_file}',
+                f'echo "CWD=$(pwd)" >> {env_file}',
+                f'git log -1 --format="HEAD=%s" >> {env_file}',
+            ],
+            env=env,
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        create_commit(env, worktree_path, "feat: post merge commit")
+
+        run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+        content = env_file.read_text()
+        assert f"BRANCH={branch_name}" in content
+        assert "TARGET=main" in content
+        assert f"CWD={repo_path}" in content
+        # The hook sees the merged commit
+        assert "HEAD=feat: post merge commit" in content
+
+    def test_post_remove_hook_runs_after_worktree_is_gone(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies post_remove runs once the worktree directory is removed."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-remove"
+        env_file = env.tmp_path / "post_remove_env.txt"
+
+        write_workmux_config(
+            repo_path,
+            post_remove=[
+                f'echo "HANDLE=$WORKMUX_HANDLE" >> {env_file}',
+                f'test -d "$WM_WORKTREE_PATH" || echo "GONE" >> {env_file}',
+            ],
+            env=env,
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        run_workmux_remove(env, workmux_exe_path, repo_path, branch_name, force=True)
+
+        content = env_file.read_text()
+        assert f"HANDLE={branch_name}" in content
+        assert "GONE" in content
+
+    def test_failing_post_remove_hook_does_not_fail_remove(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies a failing post_remove hook only warns."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-remove-fail"
+
+        write_workmux_config(repo_path, post_remove=["exit 1"], env=env)
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        run_workmux_remove(env, workmux_exe_path, repo_path, branch_name, force=True)
+
+        assert not worktree_path.exists()
@@ -249,6 +249,52 @@ printf '%s' "$2" > "{output_filename}"
         assert agent_output.read_text() == prompt_text
 
 
+class TestAgentLog:
+    """Tests for recording the agent pane's output with --log-agent."""
+
+    def test_add_log_agent_records_output_for_logs(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+        fake_agent_installer: FakeAgentInstaller,
+    ):
+        """`add --log-agent` appends agent output to a log `logs --agent` prints."""
+        env = isolated_tmux_server
+        branch_name = "feature-agent-log"
+
+        fake_claude_path = fake_agent_installer.install(
+            "claude",
+            """#!/bin/sh
+echo "agent-log-marker"
+sleep 30
+""",
+        )
+        write_workmux_config(
+            repo_path, agent=str(fake_claude_path), panes=[{"command": "<agent>"}]
+        )
+
+        add_branch_and_get_worktree(
+            env,
+            workmux_exe_path,
+            repo_path,
+            branch_name,
+            extra_args="--log-agent",
+        )
+
+        log_path = (
+            repo_path / ".git" / "workmux" / "logs" / branch_name / "agent.log"
+        )
+        assert poll_until(
+            lambda: log_path.exists() and "agent-log-marker" in log_path.read_text()
+        ), "agent output was not recorded"
+
+        result = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"logs {branch_name} --agent"
+        )
+        assert "agent-log-marker" in result.stdout
+
+
 class TestAgentWithArguments:
     """Tests for agent commands that include arguments."""
 
@@ -615,6 +661,68 @@ class TestBranchTemplate:
             assert worktree.is_dir(), f"Worktree {branch} not found"
 
 
+class TestPromptTemplate:
+    """Tests for named prompt templates (`--prompt-template`)."""
+
+    def test_add_prompt_template_substitutes_variables(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+        fake_agent_installer: FakeAgentInstaller,
+    ):
+        """Built-in and --var values are filled in; other braces are kept."""
+        env = isolated_tmux_server
+        branch_name = "feature-template"
+
+        fake_claude_path = fake_agent_installer.install("claude", "#!/bin/sh\n")
+        write_workmux_config(repo_path, panes=[{"command": str(fake_claude_path)}])
+        templates = repo_path / ".workmux" / "templates"
+        templates.mkdir(parents=True)
+        (templates / "kickoff.md").write_text(
+            "Work on {branch} ({handle}) from {base}. Ticket: {ticket}. Keep {this}.\n"
+        )
+
+        add_branch_and_get_worktree(
+            env,
+            workmux_exe_path,
+            repo_path,
+            branch_name,
+            extra_args="--prompt-template kickoff --var ticket=ABC-123",
+        )
+
+        assert_prompt_file_contents(
+            env,
+            branch_name,
+            f"Work on {branch_name} ({branch_name}) from main. "
+            "Ticket: ABC-123. Keep {this}.\n",
+        )
+
+    def test_add_prompt_template_lists_available_templates(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """An unknown template name fails before anything is created."""
+        env = isolated_tmux_server
+        write_workmux_config(repo_path)
+        templates = repo_path / ".workmux" / "templates"
+        templates.mkdir(parents=True)
+        (templates / "review.md").write_text("Review {branch}\n")
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add feature-missing --prompt-template nope",
+            expect_fail=True,
+        )
+
+        assert "Available: review" in result.stderr
+        assert not get_worktree_path(repo_path, "feature-missing").exists()
+
+
 class TestNoPrompt:
     """Tests for behavior without prompts."""
 
@@ -709,6 +817,27 @@ exit 1
 class TestAgentErrors:
     """Tests for error handling with agent flags."""
 
+    def test_add_with_count_tags_windows_with_group(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies --count tags every created window with the base name as its group."""
+        env = isolated_tmux_server
+        base_name = "feature-race"
+        write_workmux_config(repo_path)
+
+        run_workmux_command(env, workmux_exe_path, repo_path, f"add {base_name} -n 2")
+
+        for idx in (1, 2):
+            window = get_window_name(f"{base_name}-{idx}")
+            assert_window_exists(env, window)
+            result = env.tmux(
+                ["show-options", "-wv", "-t", f":={window}", "@workmux_group"]
+            )
+            assert result.stdout.strip() == base_name
+
     def test_add_fails_with_count_and_multiple_agents(
         self,
         isolated_tmux_server: TmuxEnvironment,
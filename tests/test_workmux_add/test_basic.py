@@ -229,6 +229,60 @@ class TestBaseFlag:
                 f"but found: {result.stdout.strip()}"
             )
 
+    def test_add_from_tag_and_short_sha_records_base_commit(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """Tags and short SHAs are accepted, and the resolved commit is recorded."""
+        env = isolated_tmux_server
+
+        write_workmux_config(repo_path)
+        create_commit(env, repo_path, "Tagged change")
+        env.run_command(["git", "tag", "-a", "v1", "-m", "v1"], cwd=repo_path)
+        tagged_sha = env.run_command(
+            ["git", "rev-parse", "HEAD"], cwd=repo_path
+        ).stdout.strip()
+        create_commit(env, repo_path, "Later change")
+
+        for branch, base in [("from-tag", "v1"), ("from-sha", tagged_sha[:8])]:
+            worktree_path = add_branch_and_get_worktree(
+                env, workmux_exe_path, repo_path, branch, extra_args=f"--base {base}"
+            )
+            assert file_for_commit(worktree_path, "Tagged change").exists()
+            assert not file_for_commit(worktree_path, "Later change").exists()
+
+            recorded_sha = env.run_command(
+                ["git", "config", f"branch.{branch}.workmux-base-sha"], cwd=repo_path
+            ).stdout.strip()
+            assert recorded_sha == tagged_sha
+
+        # Short SHAs are recorded in full so they stay unambiguous
+        recorded_base = env.run_command(
+            ["git", "config", "branch.from-sha.workmux-base"], cwd=repo_path
+        ).stdout.strip()
+        assert recorded_base == tagged_sha
+
+    def test_add_with_invalid_base_fails_before_creating_worktree(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """An unknown base is rejected with a clear error and nothing is created."""
+        env = isolated_tmux_server
+        branch_name = "feature-bad-base"
+
+        write_workmux_config(repo_path)
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            f"add {branch_name} --base no-such-ref",
+            expect_fail=True,
+        )
+
+        assert "'no-such-ref' does not name a commit" in result.stderr
+        assert not get_worktree_path(repo_path, branch_name).exists()
+        branches = env.run_command(["git", "branch"], cwd=repo_path).stdout
+        assert branch_name not in branches
+
 
 class TestDetachedHead:
     """Tests for behavior with detached HEAD states."""
@@ -281,6 +335,114 @@ class TestDetachedHead:
         assert expected_file.exists()
 
 
+class TestDetachedFlag:
+    """Tests for `add --detached` branchless worktrees."""
+
+    def test_add_detached_creates_worktree_without_branch(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """The worktree is checked out at the base commit and no branch is created."""
+        env = isolated_tmux_server
+        name = "experiment"
+
+        write_workmux_config(repo_path)
+        create_commit(env, repo_path, "Base change")
+        base_sha = env.run_command(
+            ["git", "rev-parse", "HEAD"], cwd=repo_path
+        ).stdout.strip()
+        create_commit(env, repo_path, "Later change")
+
+        worktree_path = add_branch_and_get_worktree(
+            env,
+            workmux_exe_path,
+            repo_path,
+            name,
+            extra_args="--detached --base HEAD~1",
+        )
+
+        head = env.run_command(
+            ["git", "rev-parse", "HEAD"], cwd=worktree_path
+        ).stdout.strip()
+        assert head == base_sha
+        symbolic = env.run_command(
+            ["git", "symbolic-ref", "-q", "HEAD"], cwd=worktree_path, check=False
+        )
+        assert symbolic.returncode != 0
+        branches = env.run_command(["git", "branch"], cwd=repo_path).stdout
+        assert name not in branches
+
+    def test_detached_worktree_can_be_removed_but_not_merged(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """Merge refuses a detached worktree; remove discards it."""
+        env = isolated_tmux_server
+        name = "throwaway"
+
+        write_workmux_config(repo_path)
+        worktree_path = add_branch_and_get_worktree(
+            env, workmux_exe_path, repo_path, name, extra_args="--detached"
+        )
+
+        result = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"merge {name}", expect_fail=True
+        )
+        assert "is detached" in result.stderr
+        assert worktree_path.exists()
+
+        run_workmux_command(env, workmux_exe_path, repo_path, f"remove -f {name}")
+        assert not worktree_path.exists()
+
+
+class TestTrackUpstream:
+    """Tests for upstream tracking on first push (`--track-upstream`)."""
+
+    def test_first_push_sets_upstream(
+        self, isolated_tmux_server, workmux_exe_path, repo_path, remote_repo_path
+    ):
+        """A plain `git push` from the new worktree sets origin/<branch> as upstream."""
+        env = isolated_tmux_server
+        branch_name = "feature-tracked"
+
+        write_workmux_config(repo_path)
+        env.run_command(
+            ["git", "remote", "add", "origin", str(remote_repo_path)], cwd=repo_path
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            env,
+            workmux_exe_path,
+            repo_path,
+            branch_name,
+            extra_args="--track-upstream",
+        )
+        create_commit(env, worktree_path, "Tracked change")
+        env.run_command(["git", "push"], cwd=worktree_path)
+
+        upstream = env.run_command(
+            ["git", "rev-parse", "--abbrev-ref", f"{branch_name}@{{u}}"],
+            cwd=worktree_path,
+        ).stdout.strip()
+        assert upstream == f"origin/{branch_name}"
+
+    def test_existing_push_setting_is_respected(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """An explicit push.autoSetupRemote is never overwritten."""
+        env = isolated_tmux_server
+
+        (repo_path / ".workmux.yaml").write_text("auto_track_upstream: true\n")
+        env.run_command(
+            ["git", "config", "push.autoSetupRemote", "false"], cwd=repo_path
+        )
+
+        add_branch_and_get_worktree(env, workmux_exe_path, repo_path, "feature-own")
+
+        value = env.run_command(
+            ["git", "config", "push.autoSetupRemote"], cwd=repo_path
+        ).stdout.strip()
+        assert value == "false"
+
+
 class TestExistingBranch:
     """Tests for behavior with existing branches."""
 
@@ -461,3 +623,25 @@ class TestBackgroundFlag:
         # Verify we're still on the initial window (didn't switch)
         current_after = env.tmux(["display-message", "-p", "#{window_name}"])
         assert initial_window in current_after.stdout
+
+
+class TestSessionStrategy:
+    """Tests for opening worktree windows in a dedicated session."""
+
+    def test_named_session_is_created_for_windows(
+        self, isolated_tmux_server, workmux_exe_path, repo_path
+    ):
+        """With `session_strategy: named`, the window lands in that session."""
+        env = isolated_tmux_server
+        branch_name = "feature-session"
+
+        (repo_path / ".workmux.yaml").write_text(
+            "session_strategy: named\nsession_name: agents\n"
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+        windows = env.tmux(
+            ["list-windows", "-t", "=agents", "-F", "#{window_name}"]
+        ).stdout.split()
+        assert get_window_name(branch_name) in windows
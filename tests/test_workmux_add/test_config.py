@@ -9,6 +9,7 @@ from ..conftest import (
     assert_symlink_to,
     assert_window_exists,
     get_window_name,
+    run_workmux_command,
     wait_for_pane_output,
     write_global_workmux_config,
     write_workmux_config,
@@ -261,3 +262,68 @@ class TestPaneOverrides:
 
         capture_result = env.tmux(["capture-pane", "-p", "-t", window_name])
         assert global_output not in capture_result.stdout
+
+    def test_layout_replaces_panes(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """`--layout` opens the window with the named layout's panes."""
+        env = isolated_tmux_server
+        branch_name = "feature-layout"
+        window_name = get_window_name(branch_name)
+        write_workmux_config(
+            repo_path,
+            panes=[{"command": "echo 'PANES_OUTPUT'"}],
+            layouts={
+                "review": [
+                    {"command": "echo 'REVIEW_OUTPUT'"},
+                    {"split": "horizontal", "percentage": 30},
+                ]
+            },
+        )
+
+        add_branch_and_get_worktree(
+            env, workmux_exe_path, repo_path, branch_name, extra_args="--layout review"
+        )
+
+        wait_for_pane_output(env, window_name, "REVIEW_OUTPUT")
+        panes = env.tmux(["list-panes", "-t", window_name, "-F", "#{pane_id}"])
+        assert len(panes.stdout.split()) == 2
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add other --layout nope",
+            expect_fail=True,
+        )
+        assert "Available layouts: review" in result.stderr
+
+
+class TestWorktreeDir:
+    """Tests for the worktree_dir config option."""
+
+    def test_worktree_dir_template_variables(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """{repo} and {handle} in worktree_dir should place the worktree accordingly."""
+        env = isolated_tmux_server
+        branch_name = "feature-custom-root"
+        root = env.tmp_path / "all_worktrees"
+        write_workmux_config(repo_path, worktree_dir=f"{root}/{{repo}}/{{handle}}")
+
+        run_workmux_command(env, workmux_exe_path, repo_path, f"add {branch_name}")
+
+        expected = root / repo_path.name / branch_name
+        assert expected.is_dir()
+        assert_window_exists(env, get_window_name(branch_name))
+
+        result = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"path {branch_name}"
+        )
+        assert result.stdout.strip() == str(expected)
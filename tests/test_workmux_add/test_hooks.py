@@ -6,6 +6,7 @@ from ..conftest import (
     TmuxEnvironment,
     configure_default_shell,
     get_window_name,
+    run_workmux_command,
     wait_for_pane_output,
     write_workmux_config,
 )
@@ -58,6 +59,43 @@ class TestPostCreateHooks:
 
         assert not (worktree_path / hook_file).exists()
 
+    def test_post_create_output_is_logged(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Hook output is saved for `workmux logs`, outside the worktree."""
+        env = isolated_tmux_server
+        branch_name = "feature-hook-log"
+
+        write_workmux_config(
+            repo_path, post_create=["echo 'installing deps'", "echo 'oops' >&2"]
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            env, workmux_exe_path, repo_path, branch_name
+        )
+
+        result = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"logs {branch_name} --hook post_create"
+        )
+        assert result.stdout.splitlines() == [
+            "$ echo 'installing deps'",
+            "installing deps",
+            "$ echo 'oops' >&2",
+            "oops",
+        ]
+
+        listing = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"logs {branch_name}"
+        )
+        assert "post_create" in listing.stdout
+        assert "pre_merge" not in listing.stdout
+
+        status = env.run_command(["git", "status", "--porcelain"], cwd=worktree_path)
+        assert status.stdout.strip() == ""
+
 
 class TestPaneCommands:
     """Tests for pane command execution."""
@@ -246,6 +246,61 @@ class TestCombinedNamingOptions:
         assert not (worktrees_dir / "ignored-simple-name").exists()
 
 
+class TestPackageFlag:
+    """Tests for the --package flag (monorepo package scoping)."""
+
+    def test_add_with_package_prefixes_handle_and_sparse_checks_out(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies --package prefixes the handle with the package name and
+        limits the checkout to that package."""
+        env = isolated_tmux_server
+
+        for package in ("api", "web"):
+            package_dir = repo_path / "packages" / package
+            package_dir.mkdir(parents=True)
+            (package_dir / "README.md").write_text(f"{package} package")
+        env.run_command(["git", "add", "."], cwd=repo_path)
+        env.run_command(["git", "commit", "-m", "Add packages"], cwd=repo_path)
+
+        run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add fix-auth --package packages/api",
+        )
+
+        worktree_path = (
+            repo_path.parent / f"{repo_path.name}__worktrees" / "api-fix-auth"
+        )
+        assert worktree_path.is_dir(), f"Expected worktree at {worktree_path}"
+        assert (worktree_path / "packages" / "api" / "README.md").exists()
+        assert not (worktree_path / "packages" / "web").exists()
+
+        assert_window_exists(env, f"{DEFAULT_WINDOW_PREFIX}api-fix-auth")
+
+    def test_add_with_missing_package_fails(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies --package rejects a directory that does not exist in the repo."""
+        env = isolated_tmux_server
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add fix-auth --package packages/missing",
+            expect_fail=True,
+        )
+        assert "packages/missing" in result.stderr
+
+
 class TestHandleEnvVar:
     """Tests for WORKMUX_HANDLE environment variable in hooks."""
 
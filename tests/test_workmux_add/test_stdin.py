@@ -5,6 +5,7 @@ from pathlib import Path
 from ..conftest import (
     DEFAULT_WINDOW_PREFIX,
     TmuxEnvironment,
+    assert_prompt_file_contents,
     assert_window_exists,
     run_workmux_command,
     slugify,
@@ -301,3 +302,80 @@ Task for {{ input }}
 
         # {{ index }} should be 1 for single item
         assert_window_exists(env, f"{DEFAULT_WINDOW_PREFIX}task-1")
+
+
+class TestStdinTaskList:
+    """Tests for `workmux add --stdin` task lists (`branch :: prompt` lines)."""
+
+    def test_task_list_creates_worktree_per_line_with_prompt(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies each line becomes a worktree for its branch, with its prompt."""
+        env = isolated_tmux_server
+        write_workmux_config(repo_path)
+
+        run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add --stdin",
+            stdin_input=(
+                "# login work\n"
+                "fix-login :: Fix the login redirect\n"
+                "add-tests :: Cover the parser :: edge cases"
+            ),
+        )
+
+        for branch in ["fix-login", "add-tests"]:
+            worktree_path = repo_path.parent / f"{repo_path.name}__worktrees" / branch
+            assert worktree_path.is_dir(), f"Expected worktree at {worktree_path}"
+            assert_window_exists(env, f"{DEFAULT_WINDOW_PREFIX}{branch}")
+        assert_prompt_file_contents(env, "fix-login", "Fix the login redirect")
+        assert_prompt_file_contents(env, "add-tests", "Cover the parser :: edge cases")
+
+    def test_task_list_dry_run_creates_nothing(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies --dry-run lists the tasks without creating worktrees."""
+        env = isolated_tmux_server
+        write_workmux_config(repo_path)
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add --stdin --dry-run",
+            stdin_input="fix-login :: Fix the login redirect\ndocs-typos",
+        )
+
+        assert "Would create 2 worktrees" in result.stdout
+        assert "Prompt: Fix the login redirect" in result.stdout
+        assert "docs-typos" in result.stdout
+        assert not (repo_path.parent / f"{repo_path.name}__worktrees").exists()
+
+    def test_task_list_rejects_line_without_separator(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies a prompt without ' :: ' is reported instead of used as a branch."""
+        env = isolated_tmux_server
+        write_workmux_config(repo_path)
+
+        result = run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            "add --stdin",
+            stdin_input="fix the login redirect",
+            expect_fail=True,
+        )
+
+        assert "contains spaces" in result.stderr
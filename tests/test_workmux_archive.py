@@ -0,0 +1,86 @@
+from pathlib import Path
+
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    create_dirty_file,
+    get_window_name,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_archive_keeps_branch_under_archive_ref(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux archive` tears down the worktree but keeps its commits."""
+    env = isolated_tmux_server
+    branch_name = "archived-feature"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: partial work")
+    tip = env.run_command(["git", "rev-parse", "HEAD"], cwd=worktree_path).stdout
+
+    run_workmux_command(env, workmux_exe_path, repo_path, f"archive {branch_name}")
+
+    assert not worktree_path.exists()
+    windows = env.tmux(["list-windows", "-F", "#{window_name}"]).stdout
+    assert get_window_name(branch_name) not in windows
+    branches = env.run_command(
+        ["git", "branch", "--list", branch_name], cwd=repo_path
+    ).stdout
+    assert branch_name not in branches
+    archived = env.run_command(
+        ["git", "rev-parse", f"refs/workmux/archive/{branch_name}"], cwd=repo_path
+    ).stdout
+    assert archived == tip
+
+
+def test_restore_brings_back_uncommitted_changes(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux restore` recreates the worktree, branch, and window."""
+    env = isolated_tmux_server
+    branch_name = "restored-feature"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: kept commit")
+    create_dirty_file(worktree_path)
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, f"remove --archive {branch_name}"
+    )
+    listing = run_workmux_command(env, workmux_exe_path, repo_path, "restore")
+    assert branch_name in listing.stdout
+
+    run_workmux_command(env, workmux_exe_path, repo_path, f"restore {branch_name}")
+
+    assert (worktree_path / "dirty.txt").read_text() == "uncommitted changes"
+    status = env.run_command(["git", "status", "--porcelain"], cwd=worktree_path)
+    assert "?? dirty.txt" in status.stdout
+    log = env.run_command(["git", "log", "--format=%s", "-1"], cwd=worktree_path)
+    assert log.stdout.strip() == "feat: kept commit"
+    windows = env.tmux(["list-windows", "-F", "#{window_name}"]).stdout
+    assert get_window_name(branch_name) in windows
+    refs = env.run_command(
+        ["git", "for-each-ref", "refs/workmux/archive"], cwd=repo_path
+    ).stdout
+    assert refs.strip() == ""
+
+
+def test_restore_unknown_name_fails(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux restore` reports names that were never archived."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "restore missing", expect_fail=True
+    )
+    assert "No archived worktree named 'missing'" in result.stderr
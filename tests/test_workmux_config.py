@@ -0,0 +1,76 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    run_workmux_command,
+    setup_git_repo,
+)
+
+
+def test_config_export_and_import_round_trip(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies a bundle carries the config, .workmux/ files, and hook scripts."""
+    env = isolated_tmux_server
+    (repo_path / ".workmux.yaml").write_text(
+        "post_create:\n  - ./scripts/setup.sh\n"
+    )
+    (repo_path / "scripts").mkdir()
+    (repo_path / "scripts" / "setup.sh").write_text("echo setup\n")
+    (repo_path / ".workmux" / "prompts").mkdir(parents=True)
+    (repo_path / ".workmux" / "prompts" / "review.md").write_text("Review this\n")
+    bundle = env.tmp_path.parent / "bundle.tar.gz"
+
+    run_workmux_command(env, workmux_exe_path, repo_path, f"config export {bundle}")
+    assert bundle.exists()
+
+    other_repo = env.tmp_path.parent / "other-repo"
+    other_repo.mkdir()
+    setup_git_repo(other_repo, env.env)
+    run_workmux_command(
+        env, workmux_exe_path, other_repo, f"config import {bundle}"
+    )
+
+    assert (other_repo / ".workmux.yaml").read_text().startswith("post_create")
+    assert (other_repo / "scripts" / "setup.sh").exists()
+    assert (other_repo / ".workmux" / "prompts" / "review.md").exists()
+
+    # Importing again refuses to overwrite without --force
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        other_repo,
+        f"config import {bundle}",
+        expect_fail=True,
+    )
+    assert "--force" in result.stderr
+
+
+def test_config_validate_lists_problems(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `config validate` reports each mistake and fails."""
+    env = isolated_tmux_server
+    (repo_path / ".workmux.yaml").write_text(
+        'panes:\n  - command: "<agent> --verbose"\npre_merge:\n  - "<global>"\n'
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "config validate", expect_fail=True
+    )
+
+    assert "panes[0].command" in result.stdout
+    assert "pre_merge[0]" in result.stdout
+    assert "Found 2 problems" in result.stderr
+
+
+def test_config_validate_accepts_valid_config(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies a valid project config passes."""
+    env = isolated_tmux_server
+    (repo_path / ".workmux.yaml").write_text("panes:\n  - command: <agent>\n")
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "config validate")
+
+    assert "✓" in result.stdout
@@ -0,0 +1,50 @@
+"""Tests for `workmux conflicts`, which flags worktrees changing the same files."""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_conflicts_lists_worktrees_changing_same_file(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Committed and uncommitted changes to one file pair their worktrees."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    for branch in ["auth", "session", "docs"]:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+
+    auth_path = get_worktree_path(repo_path, "auth")
+    (auth_path / "shared.txt").write_text("auth\n")
+    env.run_command(["git", "add", "shared.txt"], cwd=auth_path)
+    env.run_command(["git", "commit", "-m", "auth"], cwd=auth_path)
+    (get_worktree_path(repo_path, "session") / "shared.txt").write_text("session\n")
+    (get_worktree_path(repo_path, "docs") / "docs.md").write_text("docs\n")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "conflicts --json"
+    )
+
+    assert json.loads(result.stdout) == [
+        {"worktrees": ["auth", "session"], "files": ["shared.txt"]}
+    ]
+
+
+def test_conflicts_reports_none(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Worktrees with unrelated changes are not flagged."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "only-one")
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "conflicts")
+
+    assert "No worktrees change the same files." in result.stdout
@@ -0,0 +1,29 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_env_dotenv_lists_hook_variables(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux env --format dotenv` prints the variables hooks get."""
+    env = isolated_tmux_server
+    branch_name = "feature-env"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"env {branch_name} --format dotenv"
+    )
+
+    lines = result.stdout.strip().splitlines()
+    worktree_path = get_worktree_path(repo_path, branch_name).resolve()
+    assert f'WM_HANDLE="{branch_name}"' in lines
+    assert f'WORKMUX_BRANCH="{branch_name}"' in lines
+    assert f'WM_WORKTREE_PATH="{worktree_path}"' in lines
@@ -0,0 +1,52 @@
+"""Tests for `workmux gc`, which reports disk usage and prunes idle worktrees."""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_gc_reports_build_dirs_and_keeps_recent_worktrees(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Sizes include node_modules; nothing is offered while worktrees are fresh."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "web")
+    modules = get_worktree_path(repo_path, "web") / "node_modules" / "pkg"
+    modules.mkdir(parents=True)
+    (modules / "index.js").write_bytes(b"x" * 4096)
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "gc")
+
+    assert "DEPS/BUILD" in result.stdout
+    assert "4.0 KB" in result.stdout
+    assert "No merged worktrees have been idle for 7+ days." in result.stdout
+    assert get_worktree_path(repo_path, "web").exists()
+
+
+def test_gc_auto_removes_merged_but_keeps_unmerged(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--auto removes idle merged worktrees without asking."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged")
+    run_workmux_add(env, workmux_exe_path, repo_path, "unmerged")
+    unmerged_path = get_worktree_path(repo_path, "unmerged")
+    (unmerged_path / "feature.txt").write_text("feature\n")
+    env.run_command(["git", "add", "feature.txt"], cwd=unmerged_path)
+    env.run_command(["git", "commit", "-m", "feature"], cwd=unmerged_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "gc --auto --older-than 0"
+    )
+
+    assert "Removed 1 worktree(s)" in result.stdout
+    assert not get_worktree_path(repo_path, "merged").exists()
+    assert unmerged_path.exists()
@@ -0,0 +1,64 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_import_moves_worktree_and_records_base(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux import --move` adopts a worktree created outside workmux."""
+    env = isolated_tmux_server
+    branch_name = "feature/imported"
+    write_workmux_config(repo_path)
+    external_path = repo_path.parent / "external-worktree"
+    env.run_command(
+        ["git", "worktree", "add", "-b", branch_name, str(external_path)],
+        cwd=repo_path,
+    )
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "import --move")
+
+    expected_path = get_worktree_path(repo_path, branch_name)
+    assert f"moved to {expected_path}" in result.stdout
+    assert expected_path.is_dir()
+    assert not external_path.exists()
+
+    main_branch = env.run_command(
+        ["git", "rev-parse", "--abbrev-ref", "HEAD"], cwd=repo_path
+    ).stdout.strip()
+    base = env.run_command(
+        ["git", "config", f"branch.{branch_name}.workmux-base"], cwd=repo_path
+    ).stdout.strip()
+    assert base == main_branch
+
+
+def test_import_dry_run_changes_nothing(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux import --dry-run` only reports what it would do."""
+    env = isolated_tmux_server
+    branch_name = "feature-dry"
+    write_workmux_config(repo_path)
+    external_path = repo_path.parent / "external-dry"
+    env.run_command(
+        ["git", "worktree", "add", "-b", branch_name, str(external_path)],
+        cwd=repo_path,
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "import --move --dry-run"
+    )
+
+    assert "would move to" in result.stdout
+    assert external_path.is_dir()
+    base = env.run_command(
+        ["git", "config", f"branch.{branch_name}.workmux-base"],
+        cwd=repo_path,
+        check=False,
+    )
+    assert base.returncode != 0
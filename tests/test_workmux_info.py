@@ -0,0 +1,80 @@
+"""
+Tests for the per-worktree metadata shown by `workmux info`
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def metadata_file(repo_path: Path, handle: str) -> Path:
+    return repo_path / ".git" / "workmux" / "worktrees" / f"{handle}.json"
+
+
+def test_info_shows_how_worktree_was_created(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """`add` records base and prompt, which `info --json` reports."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, "add info-test --prompt 'Fix the login'"
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "info info-test --json"
+    )
+    info = json.loads(result.stdout)
+    assert info["handle"] == "info-test"
+    assert info["branch"] == "info-test"
+    assert info["base"] == "main"
+    assert info["prompt"] == "Fix the login"
+    assert isinstance(info["created_at"], int)
+    assert metadata_file(repo_path, "info-test").exists()
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "info info-test")
+    assert "Base:     main" in result.stdout
+    assert "  Fix the login" in result.stdout
+
+
+def test_info_falls_back_to_git_config(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Worktrees without a metadata file still show the base from git config."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "legacy")
+    metadata_file(repo_path, "legacy").unlink()
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "info legacy --json")
+
+    info = json.loads(result.stdout)
+    assert info["base"] == "main"
+    assert info["created_at"] is None
+
+
+def test_metadata_is_removed_with_worktree_and_merge_warns_about_base(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Merging into a branch other than the base warns, and cleans up metadata."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    env.run_command(["git", "branch", "release"], cwd=repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "stacked")
+    create_commit(env, get_worktree_path(repo_path, "stacked"), "Stacked work")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "merge stacked --into release"
+    )
+
+    assert "created from 'main' but is being merged into 'release'" in result.stderr
+    assert not metadata_file(repo_path, "stacked").exists()
@@ -11,13 +11,13 @@ from .conftest import TmuxEnvironment
 def test_init_creates_config_file_on_success(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path
 ):
-    """Verifies `workmux init` creates .workmux.yaml with correct content."""
+    """Verifies `workmux init --example` creates the commented example config."""
     env = isolated_tmux_server
     config_file_path = env.tmp_path / ".workmux.yaml"
 
     assert not config_file_path.exists()
 
-    result = env.run_command([str(workmux_exe_path), "init"])
+    result = env.run_command([str(workmux_exe_path), "init", "--example"])
 
     assert result.returncode == 0
     assert config_file_path.is_file(), "The .workmux.yaml file was not created"
@@ -67,7 +67,7 @@ def test_init_fails_in_readonly_directory(
 
     try:
         with pytest.raises(subprocess.CalledProcessError) as exc_info:
-            env.run_command([str(workmux_exe_path), "init"])
+            env.run_command([str(workmux_exe_path), "init", "--yes"])
 
         assert exc_info.value.returncode != 0
         assert (
@@ -78,3 +78,49 @@ def test_init_fails_in_readonly_directory(
 
     finally:
         os.chmod(test_dir, original_mode)
+
+
+def test_init_proposes_settings_for_detected_project(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path
+):
+    """Verifies `workmux init --yes` writes the proposals for a pnpm project."""
+    env = isolated_tmux_server
+    (env.tmp_path / "package.json").write_text(
+        '{"scripts": {"dev": "vite", "test": "vitest"}}'
+    )
+    (env.tmp_path / "pnpm-lock.yaml").write_text("")
+    (env.tmp_path / ".env").write_text("PORT=3000\n")
+    (env.tmp_path / ".env.example").write_text("PORT=\n")
+
+    result = env.run_command([str(workmux_exe_path), "init", "--yes"])
+
+    assert "Detected a Node.js (pnpm) project" in result.stdout
+    config = yaml.safe_load((env.tmp_path / ".workmux.yaml").read_text())
+    assert config["panes"][0] == {"command": "<agent>", "focus": True}
+    assert config["post_create"] == ["pnpm install"]
+    assert config["pre_merge"] == ["pnpm test"]
+    assert config["files"] == {"copy": [".env"]}
+
+
+def test_init_takes_answers_from_stdin(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path
+):
+    """Verifies answers replace the proposals, and 'none' drops a hook."""
+    env = isolated_tmux_server
+    (env.tmp_path / "Cargo.toml").write_text("")
+
+    subprocess.run(
+        [str(workmux_exe_path), "init"],
+        cwd=env.tmp_path,
+        env=env.env,
+        input="codex\nagent\ncargo fetch\nnone\n",
+        text=True,
+        check=True,
+        capture_output=True,
+    )
+
+    config = yaml.safe_load((env.tmp_path / ".workmux.yaml").read_text())
+    assert config["agent"] == "codex"
+    assert config["panes"] == [{"command": "<agent>", "focus": True}]
+    assert config["post_create"] == ["cargo fetch"]
+    assert "pre_merge" not in config
@@ -0,0 +1,105 @@
+"""
+Tests for starting worktrees from issues (workmux add --from-issue <number>)
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_command,
+    run_workmux_merge,
+    write_workmux_config,
+)
+
+
+def install_fake_gh_issue(
+    env: TmuxEnvironment, issue_number: int, issue: dict
+) -> None:
+    """Creates a fake 'gh' that answers 'issue view <number> --json' with `issue`."""
+    bin_dir = env.home_path / "bin"
+    bin_dir.mkdir(exist_ok=True)
+    json_output = json.dumps(issue).replace("'", "'\\''")
+
+    gh_script = bin_dir / "gh"
+    gh_script.write_text(
+        f"""#!/bin/sh
+if [ "$1" = "issue" ] && [ "$2" = "view" ] && [ "$3" = "{issue_number}" ]; then
+    echo '{json_output}'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+"""
+    )
+    gh_script.chmod(0o755)
+
+    new_path = f"{bin_dir}:{env.env.get('PATH', '')}"
+    env.env["PATH"] = new_path
+    env.tmux(["set-environment", "-g", "PATH", new_path])
+
+
+def test_add_from_issue_names_branch_and_links_issue(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """The branch is named after the issue title and linked to the issue."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    install_fake_gh_issue(
+        env,
+        42,
+        {"title": "Fix login redirect", "body": "Steps: {{ x }}", "state": "OPEN"},
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "add --from-issue 42"
+    )
+
+    branch_name = "42-fix-login-redirect"
+    assert "Issue #42: Fix login redirect" in result.stdout
+    assert get_worktree_path(repo_path, branch_name).is_dir()
+    linked = env.run_command(
+        ["git", "config", f"branch.{branch_name}.workmux-issue"], cwd=repo_path
+    ).stdout.strip()
+    assert linked == "42"
+
+
+def test_add_from_issue_accepts_custom_branch_name(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """An explicit branch name overrides the one derived from the title."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    install_fake_gh_issue(env, 7, {"title": "Whatever", "body": "", "state": "CLOSED"})
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "add my-fix --from-issue 7"
+    )
+
+    assert "issue #7 is CLOSED" in result.stderr
+    assert get_worktree_path(repo_path, "my-fix").is_dir()
+
+
+def test_merge_closes_linked_issue_in_merge_commit(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Merging a branch started from an issue adds a closing reference."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    install_fake_gh_issue(env, 12, {"title": "Add export", "body": "", "state": "OPEN"})
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "add --from-issue 12")
+    branch_name = "12-add-export"
+    create_commit(env, get_worktree_path(repo_path, branch_name), "Export data")
+    # Diverge main so the merge creates a merge commit
+    create_commit(env, repo_path, "Unrelated change")
+
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+    message = env.run_command(
+        ["git", "log", "-1", "--format=%B", "main"], cwd=repo_path
+    ).stdout
+    assert f"Merge branch '{branch_name}'" in message
+    assert "Closes #12" in message
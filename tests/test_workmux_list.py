@@ -1,3 +1,4 @@
+import json
 import os
 import re
 from pathlib import Path
@@ -10,6 +11,7 @@ from .conftest import (
     get_worktree_path,
     run_workmux_add,
     run_workmux_command,
+    setup_git_repo,
     write_workmux_config,
 )
 
@@ -189,6 +191,32 @@ def test_list_with_detached_window(
     assert worktree_entry["UNMERGED"] == "-"
 
 
+def test_list_shows_agent_health(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies the AGENT column reports whether the agent process is running."""
+    env = isolated_tmux_server
+    running_branch = "feature-running"
+    idle_branch = "feature-idle"
+    write_workmux_config(repo_path, agent="sleep", panes=[{"command": "sleep 300"}])
+    run_workmux_add(env, workmux_exe_path, repo_path, running_branch)
+    write_workmux_config(repo_path, agent="sleep", panes=[{"command": "true"}])
+    run_workmux_add(env, workmux_exe_path, repo_path, idle_branch)
+
+    parsed_output = parse_list_output(
+        run_workmux_list(env, workmux_exe_path, repo_path)
+    )
+    entries = {r["BRANCH"]: r for r in parsed_output}
+    assert entries["main"]["AGENT"] == "-"
+    assert entries[running_branch]["AGENT"] == "running"
+    assert entries[idle_branch]["AGENT"] == "exited"
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "list --json")
+    json_entries = {e["branch"]: e for e in json.loads(result.stdout)}
+    assert json_entries[running_branch]["agent_running"] is True
+    assert json_entries[idle_branch]["agent_running"] is False
+
+
 def test_list_alias_ls_works(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
@@ -205,3 +233,116 @@ def test_list_alias_ls_works(
     assert parsed_output[0]["BRANCH"] == "main"
     # When run from repo_path, main branch shows as "(here)"
     assert parsed_output[0]["PATH"] == "(here)"
+
+
+def test_list_json_output(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux list --json` emits machine-readable worktree data."""
+    env = isolated_tmux_server
+    branch_name = "feature-json"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "list --json")
+    entries = json.loads(result.stdout)
+
+    assert len(entries) == 2
+    entry = next(e for e in entries if e["branch"] == branch_name)
+    assert entry["handle"] == branch_name
+    assert entry["path"] == str(get_worktree_path(repo_path, branch_name))
+    assert entry["has_tmux"] is True
+    assert entry["has_unmerged"] is False
+    assert entry["pr_info"] is None
+
+
+def test_list_porcelain_output(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux list --porcelain` prints one tab-separated line per worktree."""
+    env = isolated_tmux_server
+    branch_name = "feature-porcelain"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "list --porcelain")
+    rows = [line.split("\t") for line in result.stdout.splitlines()]
+
+    assert len(rows) == 2
+    assert all(len(row) == 10 for row in rows)
+    row = next(r for r in rows if r[1] == branch_name)
+    handle, branch, path, window, _agent, unmerged = row[:6]
+    assert handle == branch_name
+    assert path == str(get_worktree_path(repo_path, branch_name))
+    assert window == "1"
+    assert unmerged == "0"
+    assert row[9] == "-"
+
+
+def test_list_all_groups_worktrees_of_repos_with_agents(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """`list --all` includes other repositories that have live agents."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "api-fix")
+
+    other_repo = env.tmp_path.parent / "web"
+    other_repo.mkdir()
+    setup_git_repo(other_repo, env.env)
+    write_workmux_config(other_repo)
+    run_workmux_add(env, workmux_exe_path, other_repo, "dark-mode")
+    env.tmux(
+        [
+            "set-option",
+            "-p",
+            "-t",
+            get_window_name("dark-mode"),
+            "@workmux_pane_status",
+            "🤖",
+        ]
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "list --all --json"
+    )
+    projects = {p["project"]: p for p in json.loads(result.stdout)}
+
+    assert Path(projects["web"]["path"]).resolve() == other_repo.resolve()
+    assert "dark-mode" in {wt["branch"] for wt in projects["web"]["worktrees"]}
+    current = projects[repo_path.name]
+    assert "api-fix" in {wt["branch"] for wt in current["worktrees"]}
+
+    table = run_workmux_command(env, workmux_exe_path, repo_path, "list --all")
+    assert "└─ dark-mode" in table.stdout
+
+
+def test_list_a11y_prints_labeled_lines(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--a11y prints one line of labeled fields per worktree, without icons."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "fix-auth")
+    env.tmux(
+        [
+            "set-option",
+            "-p",
+            "-t",
+            get_window_name("fix-auth"),
+            "@workmux_pane_status",
+            "💬",
+        ]
+    )
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "list --a11y")
+
+    line = next(
+        line
+        for line in result.stdout.splitlines()
+        if line.startswith("Worktree: fix-auth;")
+    )
+    assert re.search(r"Agent: (waiting|exited)", line)
+    assert "Merged: yes" in line
+    assert "Path: " in line
+    assert "✓" not in result.stdout and "●" not in result.stdout
@@ -1,3 +1,4 @@
+import json
 from pathlib import Path
 
 from .conftest import (
@@ -7,6 +8,7 @@ from .conftest import (
     get_window_name,
     get_worktree_path,
     run_workmux_add,
+    run_workmux_command,
     run_workmux_merge,
     write_workmux_config,
 )
@@ -164,6 +166,63 @@ def test_merge_strategy_config_rebase(
     )
 
 
+def test_merge_require_clean_rejects_ignore_uncommitted(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies merge.require_clean refuses dirty worktrees even with --ignore-uncommitted."""
+    env = isolated_tmux_server
+    branch_name = "feature-require-clean"
+    write_workmux_config(repo_path, env=env, merge={"require_clean": True})
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: clean work")
+    create_dirty_file(worktree_path)
+
+    run_workmux_merge(
+        env,
+        workmux_exe_path,
+        repo_path,
+        branch_name,
+        ignore_uncommitted=True,
+        expect_fail=True,
+    )
+
+    assert worktree_path.exists()
+    exit_code = (env.tmp_path / "workmux_merge_exit_code.txt").read_text().strip()
+    assert exit_code == "3"
+
+
+def test_merge_deletes_remote_branch_when_configured(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Verifies merge.delete_remote_branch deletes the branch the worktree tracked."""
+    env = isolated_tmux_server
+    branch_name = "feature-delete-remote"
+    write_workmux_config(
+        repo_path, env=env, merge={"strategy": "rebase", "delete_remote_branch": True}
+    )
+    env.run_command(
+        ["git", "remote", "add", "origin", str(remote_repo_path)], cwd=repo_path
+    )
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: pushed work")
+    env.run_command(["git", "push", "-u", "origin", branch_name], cwd=worktree_path)
+
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+    assert not worktree_path.exists()
+    remote_branches = env.run_command(["git", "branch"], cwd=remote_repo_path)
+    assert branch_name not in remote_branches.stdout
+    log_result = env.run_command(["git", "log", "--oneline", "main"])
+    assert "Merge branch" not in log_result.stdout
+
+
 def test_merge_squash_strategy_succeeds(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
@@ -505,3 +564,204 @@ def test_merge_falls_back_to_main_when_base_branch_deleted(
     assert child_commit_hash in main_log_result.stdout, (
         "Child commit should be on main branch (fallback when base deleted)"
     )
+
+
+def create_conflicting_branch(
+    env: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path, branch_name: str
+) -> Path:
+    """Adds a worktree whose change to shared.txt conflicts with main."""
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    (worktree_path / "shared.txt").write_text("branch version\n")
+    env.run_command(["git", "add", "shared.txt"], cwd=worktree_path)
+    env.run_command(["git", "commit", "-m", "Branch shared"], cwd=worktree_path)
+
+    (repo_path / "shared.txt").write_text("main version\n")
+    env.run_command(["git", "add", "shared.txt"], cwd=repo_path)
+    env.run_command(["git", "commit", "-m", "Main shared"], cwd=repo_path)
+    return worktree_path
+
+
+def test_merge_check_reports_conflicting_files(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--check lists the files that would conflict and changes nothing."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path, env=env)
+    worktree_path = create_conflicting_branch(
+        env, workmux_exe_path, repo_path, "check-conflict"
+    )
+    main_before = env.run_command(["git", "rev-parse", "main"], cwd=repo_path)
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        "merge --check check-conflict",
+        expect_fail=True,
+    )
+
+    assert result.exit_code == 2
+    assert "'check-conflict' conflicts with 'main' in:" in result.stdout
+    assert "shared.txt" in result.stdout
+    assert worktree_path.exists()
+    main_after = env.run_command(["git", "rev-parse", "main"], cwd=repo_path)
+    assert main_after.stdout == main_before.stdout
+
+
+def test_merge_check_clean_branch(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--check succeeds for a branch that merges cleanly."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path, env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, "check-clean")
+    create_commit(env, get_worktree_path(repo_path, "check-clean"), "Clean work")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "merge --check check-clean"
+    )
+
+    assert "'check-clean' merges cleanly into 'main'" in result.stdout
+    assert get_worktree_path(repo_path, "check-clean").exists()
+
+
+def test_merge_stops_before_conflicting_merge(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """A conflicting merge is refused up front, leaving the target untouched."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path, env=env)
+    worktree_path = create_conflicting_branch(
+        env, workmux_exe_path, repo_path, "preflight-conflict"
+    )
+
+    run_workmux_merge(
+        env, workmux_exe_path, repo_path, "preflight-conflict", expect_fail=True
+    )
+
+    exit_code = (env.tmp_path / "workmux_merge_exit_code.txt").read_text()
+    assert exit_code.strip() == "2"
+    stderr = (env.tmp_path / "workmux_merge_stderr.txt").read_text()
+    assert "would conflict in:" in stderr
+    assert "shared.txt" in stderr
+    assert worktree_path.exists()
+    assert not (repo_path / ".git" / "MERGE_HEAD").exists()
+    status = env.run_command(["git", "status", "--porcelain"], cwd=repo_path)
+    assert "shared.txt" not in status.stdout
+
+
+def test_merge_commits_cherry_picks_selected_commits(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--commits applies only the chosen commits and keeps the worktree."""
+    env = isolated_tmux_server
+    branch_name = "pick-some"
+    write_workmux_config(repo_path, env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    for message in ["keep one", "noise", "keep two"]:
+        create_commit(env, worktree_path, message)
+
+    run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        f"merge {branch_name} --commits HEAD~2 HEAD",
+    )
+
+    main_log = env.run_command(
+        ["git", "log", "--format=%s", "HEAD"], cwd=repo_path
+    ).stdout.splitlines()
+    assert main_log[:2] == ["keep two", "keep one"]
+    assert "noise" not in main_log
+    assert worktree_path.exists()
+    branches = env.run_command(["git", "branch", "--list", branch_name])
+    assert branch_name in branches.stdout
+
+
+def test_merge_plan_orders_done_worktrees(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Stacked branches follow their parent; widely overlapping branches go last."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    for branch in ["refactor", "fix-a", "docs", "busy"]:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+    run_workmux_add(env, workmux_exe_path, repo_path, "fix-a-tests", base="fix-a")
+
+    changes = {
+        "refactor": ["a.txt", "b.txt"],
+        "fix-a": ["a.txt"],
+        "docs": ["docs.md"],
+        "fix-a-tests": ["tests.txt"],
+        "busy": ["b.txt"],
+    }
+    for branch, files in changes.items():
+        worktree = get_worktree_path(repo_path, branch)
+        for name in files:
+            (worktree / name).write_text(f"{branch}\n")
+        env.run_command(["git", "add", "."], cwd=worktree)
+        env.run_command(["git", "commit", "-m", branch], cwd=worktree)
+        # Agents still working are left out of the plan
+        if branch != "busy":
+            env.tmux(
+                [
+                    "set-option",
+                    "-p",
+                    "-t",
+                    get_window_name(branch),
+                    "@workmux_pane_status",
+                    "✅",
+                ]
+            )
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "merge --plan")
+
+    steps = [
+        line.split(". ", 1)[1]
+        for line in result.stdout.splitlines()
+        if line.startswith("  ") and ". " in line and "→" in line
+    ]
+    assert steps == [
+        "docs → main",
+        "fix-a → main",
+        "fix-a-tests → main",
+        "refactor → main",
+    ]
+    assert "stacked on fix-a" in result.stdout
+    assert "may need a rebase after fix-a: a.txt" in result.stdout
+    # Without a terminal the plan is only printed
+    assert get_worktree_path(repo_path, "docs").exists()
+
+
+def test_merge_json_prints_summary(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `merge --json` prints only a parseable summary to stdout."""
+    env = isolated_tmux_server
+    branch_name = "feature-json"
+    main = env.run_command(
+        ["git", "branch", "--show-current"], cwd=repo_path
+    ).stdout.strip()
+    write_workmux_config(repo_path, pre_merge=["echo checking"], env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: one")
+    create_commit(env, worktree_path, "feat: two")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"merge {branch_name} --json"
+    )
+
+    summary = json.loads(result.stdout)
+    assert summary["branch"] == branch_name
+    assert summary["target"] == main
+    assert summary["strategy"] == "merge"
+    assert summary["commits_merged"] == 2
+    assert summary["cleanup"]["worktree_removed"] is True
+    assert summary["cleanup"]["local_branch_deleted"] is True
+    assert isinstance(summary["duration_ms"], int)
+    # Hook output is kept off stdout
+    assert "checking" in result.stderr
@@ -0,0 +1,167 @@
+"""
+Tests for merging through the forge (workmux merge --pr)
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def install_fake_gh_pr_merge(
+    env: TmuxEnvironment,
+    branch: str,
+    head_sha: str,
+    pr_number: int = 7,
+    state_after_merge: str = "MERGED",
+) -> Path:
+    """Creates a fake 'gh' with one open PR for `branch` that `pr merge` merges."""
+    bin_dir = env.home_path / "bin"
+    bin_dir.mkdir(exist_ok=True)
+    args_file = env.tmp_path.parent / "gh_merge_args.txt"
+    pr_list = json.dumps(
+        [
+            {
+                "number": pr_number,
+                "title": "Add the feature",
+                "state": "OPEN",
+                "isDraft": False,
+                "headRefName": branch,
+                "headRefOid": head_sha,
+            }
+        ]
+    )
+    pr_view = json.dumps(
+        {
+            "headRefName": branch,
+            "headRepositoryOwner": {"login": "testowner"},
+            "state": state_after_merge,
+            "isDraft": False,
+            "title": "Add the feature",
+            "author": {"login": "testowner"},
+        }
+    )
+
+    gh_script = bin_dir / "gh"
+    gh_script.write_text(
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo '{pr_list}'
+    exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "merge" ]; then
+    printf '%s\\n' "$@" > '{args_file}'
+    exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ] && [ -f '{args_file}' ]; then
+    echo '{pr_view}'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+"""
+    )
+    gh_script.chmod(0o755)
+
+    new_path = f"{bin_dir}:{env.env.get('PATH', '')}"
+    env.env["PATH"] = new_path
+    env.tmux(["set-environment", "-g", "PATH", new_path])
+    return args_file
+
+
+def test_merge_pr_merges_on_forge_and_cleans_up(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies `merge --pr` calls `gh pr merge` with the strategy, then cleans up."""
+    env = isolated_tmux_server
+    branch_name = "feature-merge-pr"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "Add the feature")
+    head_sha = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+
+    args_file = install_fake_gh_pr_merge(env, branch_name, head_sha)
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"merge --pr --squash {branch_name}"
+    )
+
+    assert "Merged PR #7" in result.stdout
+    gh_args = args_file.read_text().splitlines()
+    assert gh_args[:3] == ["pr", "merge", "7"]
+    assert "--squash" in gh_args
+    assert not worktree_path.exists()
+    branches = env.run_command(
+        ["git", "branch", "--list", branch_name], cwd=repo_path
+    ).stdout
+    assert branch_name not in branches
+
+
+def test_merge_pr_refuses_unpushed_commits(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies `merge --pr` stops when the local branch is ahead of the PR head."""
+    env = isolated_tmux_server
+    branch_name = "feature-unpushed"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "Pushed change")
+    head_sha = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+    create_commit(env, worktree_path, "Local change")
+
+    args_file = install_fake_gh_pr_merge(env, branch_name, head_sha)
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        f"merge --pr {branch_name}",
+        expect_fail=True,
+    )
+
+    assert "doesn't match the head of PR #7" in result.stderr
+    assert not args_file.exists()
+    assert worktree_path.exists()
+
+
+def test_merge_pr_no_wait_keeps_queued_worktree(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies `merge --pr --no-wait` reports a queued PR and keeps the worktree."""
+    env = isolated_tmux_server
+    branch_name = "feature-queued"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "Queued change")
+    head_sha = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+
+    args_file = install_fake_gh_pr_merge(
+        env, branch_name, head_sha, state_after_merge="OPEN"
+    )
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"merge --pr --no-wait {branch_name}"
+    )
+
+    assert "PR #7 is queued to merge" in result.stdout
+    assert args_file.read_text().splitlines()[:3] == ["pr", "merge", "7"]
+    assert worktree_path.exists()
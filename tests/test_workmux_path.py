@@ -1,3 +1,4 @@
+import json
 from pathlib import Path
 
 from .conftest import (
@@ -26,6 +27,45 @@ def test_path_returns_worktree_path(
     assert result.stdout.strip() == str(expected_path)
 
 
+def test_path_json_output(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux path --json` returns handle, branch, and path."""
+    env = isolated_tmux_server
+    branch_name = "feature-test"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"path {branch_name} --json"
+    )
+
+    data = json.loads(result.stdout)
+    assert data["handle"] == branch_name
+    assert data["branch"] == branch_name
+    assert data["path"] == str(get_worktree_path(repo_path, branch_name))
+
+
+def test_path_porcelain_output(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux path --porcelain` prints handle, branch, and path on one tab-separated line."""
+    env = isolated_tmux_server
+    branch_name = "feature-test"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"path {branch_name} --porcelain"
+    )
+
+    assert result.stdout.rstrip("\n").split("\t") == [
+        branch_name,
+        branch_name,
+        str(get_worktree_path(repo_path, branch_name)),
+    ]
+
+
 def test_path_fails_for_nonexistent_worktree(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
@@ -0,0 +1,105 @@
+"""
+Tests for PR creation (workmux pr create)
+"""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def install_fake_gh_pr_create(env: TmuxEnvironment, pr_number: int) -> Path:
+    """Creates a fake 'gh' that records 'pr create' arguments and prints a PR URL."""
+    bin_dir = env.home_path / "bin"
+    bin_dir.mkdir(exist_ok=True)
+    args_file = env.tmp_path.parent / "gh_args.txt"
+
+    gh_script = bin_dir / "gh"
+    gh_script.write_text(
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+    printf '%s\\n' "$@" > '{args_file}'
+    echo "https://github.com/testowner/testrepo/pull/{pr_number}"
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+"""
+    )
+    gh_script.chmod(0o755)
+
+    new_path = f"{bin_dir}:{env.env.get('PATH', '')}"
+    env.env["PATH"] = new_path
+    env.tmux(["set-environment", "-g", "PATH", new_path])
+    return args_file
+
+
+def test_pr_create_pushes_and_records_pr(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Verifies `workmux pr create` pushes the branch, opens a PR, and records it."""
+    env = isolated_tmux_server
+    branch_name = "feature-pr"
+    write_workmux_config(repo_path)
+    env.run_command(
+        ["git", "remote", "add", "origin", str(remote_repo_path)], cwd=repo_path
+    )
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "Add the feature")
+
+    args_file = install_fake_gh_pr_create(env, pr_number=42)
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"pr create {branch_name}"
+    )
+
+    assert "Created PR #42" in result.stdout
+    gh_args = args_file.read_text().splitlines()
+    assert gh_args[gh_args.index("--head") + 1] == branch_name
+    assert gh_args[gh_args.index("--title") + 1] == "Add the feature"
+
+    remote_branches = env.run_command(
+        ["git", "branch", "--list", branch_name], cwd=remote_repo_path
+    ).stdout
+    assert branch_name in remote_branches
+
+    recorded = env.run_command(
+        ["git", "config", f"branch.{branch_name}.workmux-pr"], cwd=repo_path
+    ).stdout.strip()
+    assert recorded == "42"
+
+
+def test_pr_create_closes_linked_issue(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Branches started with `add --from-issue` get a closing reference in the body."""
+    env = isolated_tmux_server
+    branch_name = "feature-issue"
+    write_workmux_config(repo_path)
+    env.run_command(
+        ["git", "remote", "add", "origin", str(remote_repo_path)], cwd=repo_path
+    )
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    env.run_command(
+        ["git", "config", f"branch.{branch_name}.workmux-issue", "7"], cwd=repo_path
+    )
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "Add the feature")
+
+    args_file = install_fake_gh_pr_create(env, pr_number=43)
+    run_workmux_command(env, workmux_exe_path, repo_path, f"pr create {branch_name}")
+
+    gh_args = args_file.read_text().splitlines()
+    assert gh_args[gh_args.index("--body") + 1] == "Closes #7"
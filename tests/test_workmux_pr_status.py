@@ -0,0 +1,93 @@
+"""
+Tests for summarizing worktree PRs (workmux pr status)
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    install_fake_gh_pr_list,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def make_pr(number: int, branch: str, **fields) -> dict:
+    pr = {
+        "number": number,
+        "title": f"Work on {branch}",
+        "state": "OPEN",
+        "isDraft": False,
+        "headRefName": branch,
+        "url": f"https://github.com/owner/repo/pull/{number}",
+        "reviewDecision": "",
+        "statusCheckRollup": [],
+        "mergeable": "UNKNOWN",
+    }
+    pr.update(fields)
+    return pr
+
+
+def test_pr_status_summarizes_review_ci_and_mergeability(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Each worktree's PR is shown with review, CI and merge state."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    for branch in ["approved-work", "failing-work", "no-pr"]:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+    install_fake_gh_pr_list(
+        env,
+        [
+            make_pr(
+                11,
+                "approved-work",
+                reviewDecision="APPROVED",
+                statusCheckRollup=[{"status": "COMPLETED", "conclusion": "SUCCESS"}],
+                mergeable="MERGEABLE",
+            ),
+            make_pr(
+                12,
+                "failing-work",
+                reviewDecision="CHANGES_REQUESTED",
+                statusCheckRollup=[
+                    {"status": "COMPLETED", "conclusion": "FAILURE"},
+                    {"state": "PENDING"},
+                ],
+                mergeable="CONFLICTING",
+            ),
+        ],
+    )
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "pr status")
+
+    lines = {line.split()[0]: line.split() for line in result.stdout.splitlines()}
+    assert lines["approved-work"][1:6] == ["#11", "open", "approved", "passing", "yes"]
+    assert lines["failing-work"][1:3] == ["#12", "open"]
+    assert "changes requested" in result.stdout
+    assert lines["failing-work"][-5:-3] == ["failing", "conflicts"]
+    assert lines["no-pr"][1:] == ["-"] * 6
+
+
+def test_pr_status_json_for_named_worktree(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--json reports only the named worktrees."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    for branch in ["first", "second"]:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+    install_fake_gh_pr_list(
+        env, [make_pr(5, "first", statusCheckRollup=[{"status": "IN_PROGRESS"}])]
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "pr status first --json"
+    )
+
+    statuses = json.loads(result.stdout)
+    assert [s["handle"] for s in statuses] == ["first"]
+    assert statuses[0]["pr"]["number"] == 5
+    assert statuses[0]["pr"]["checks"] == "pending"
@@ -1,3 +1,4 @@
+import json
 import uuid
 from pathlib import Path
 
@@ -9,7 +10,9 @@ from .conftest import (
     create_dirty_file,
     get_window_name,
     get_worktree_path,
+    install_fake_gh_pr_list,
     run_workmux_add,
+    run_workmux_command,
     run_workmux_remove,
     write_workmux_config,
 )
@@ -79,8 +82,16 @@ def test_remove_unmerged_branch_aborted(
 
     # Run remove, piping 'n' to abort
     run_workmux_remove(
-        env, workmux_exe_path, repo_path, branch_name, force=False, user_input="n"
+        env,
+        workmux_exe_path,
+        repo_path,
+        branch_name,
+        force=False,
+        user_input="n",
+        expect_fail=True,
     )
+    exit_code = (env.tmp_path / "workmux_remove_exit_code.txt").read_text()
+    assert exit_code.strip() == "5", "Declining the prompt exits with code 5"
 
     assert worktree_path.exists(), "Worktree should NOT be removed after aborting"
     list_windows_result = env.tmux(["list-windows", "-F", "#{window_name}"])
@@ -295,6 +306,7 @@ def test_remove_checks_against_stored_base_branch(
         child_branch,
         force=False,
         user_input="n",  # Abort to verify the prompt appears
+        expect_fail=True,
     )
 
     # Verify worktree still exists (removal was aborted)
@@ -463,6 +475,100 @@ def test_remove_gone_flag(
     assert local_branch in local_result.stdout, "Local branch should remain"
 
 
+def test_remove_merged_flag(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux remove --merged` removes worktrees merged into main."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged-branch")
+    merged_worktree = get_worktree_path(repo_path, "merged-branch")
+    create_commit(env, merged_worktree, "feat: merged work")
+    env.run_command(["git", "merge", "merged-branch"], cwd=repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "open-branch")
+    open_worktree = get_worktree_path(repo_path, "open-branch")
+    create_commit(env, open_worktree, "feat: open work")
+
+    # No commits of its own yet, so it is new work rather than merged work
+    run_workmux_add(env, workmux_exe_path, repo_path, "fresh-branch")
+    fresh_worktree = get_worktree_path(repo_path, "fresh-branch")
+
+    run_workmux_remove(
+        env,
+        workmux_exe_path,
+        repo_path,
+        branch_name=None,
+        merged=True,
+        user_input="y",
+    )
+
+    assert not merged_worktree.exists()
+    assert open_worktree.exists()
+    assert fresh_worktree.exists()
+    branches = env.run_command(["git", "branch"], cwd=repo_path).stdout
+    assert "merged-branch" not in branches
+    assert "open-branch" in branches
+    windows = env.tmux(["list-windows", "-F", "#{window_name}"]).stdout
+    assert get_window_name("merged-branch") not in windows
+
+
+def test_remove_gone_flag_detects_merged_prs_without_upstream(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `--gone` removes a squash-merged PR's worktree even with no upstream."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    def add_with_commit(branch: str) -> tuple:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+        worktree = get_worktree_path(repo_path, branch)
+        create_commit(env, worktree, f"feat: {branch}")
+        sha = env.run_command(
+            ["git", "rev-parse", "HEAD"], cwd=worktree
+        ).stdout.strip()
+        return worktree, sha
+
+    merged_worktree, merged_sha = add_with_commit("merged-branch")
+    # Committed to after the PR was merged, e.g. a reused branch name
+    moved_worktree, moved_sha = add_with_commit("moved-branch")
+    create_commit(env, moved_worktree, "feat: more work")
+    open_worktree, open_sha = add_with_commit("open-branch")
+
+    def pr(number: int, branch: str, state: str, sha: str) -> dict:
+        return {
+            "number": number,
+            "title": branch,
+            "state": state,
+            "isDraft": False,
+            "headRefName": branch,
+            "headRefOid": sha,
+        }
+
+    install_fake_gh_pr_list(
+        env,
+        [
+            pr(1, "merged-branch", "MERGED", merged_sha),
+            pr(2, "moved-branch", "MERGED", moved_sha),
+            pr(3, "open-branch", "OPEN", open_sha),
+        ],
+    )
+
+    run_workmux_remove(
+        env,
+        workmux_exe_path,
+        repo_path,
+        branch_name=None,
+        gone=True,
+        force=True,
+    )
+
+    assert not merged_worktree.exists(), "Merged PR worktree should be removed"
+    assert moved_worktree.exists(), "Branch moved past the PR head should remain"
+    assert open_worktree.exists(), "Open PR worktree should remain"
+
+
 def test_remove_all_flag(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
@@ -754,6 +860,7 @@ def test_remove_multiple_aborted_keeps_all(
         repo_path,
         branch_name=f"{branch1} {branch2}",
         user_input="n",
+        expect_fail=True,
     )
 
     # Verify both worktrees still exist
@@ -831,3 +938,56 @@ def test_remove_multiple_with_keep_branch(
     for branch in [branch1, branch2]:
         result = env.run_command(["git", "branch", "--list", branch], cwd=repo_path)
         assert branch in result.stdout, f"Branch {branch} should still exist"
+
+
+def test_remove_json_prints_summary(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `remove --json` prints what was removed as JSON."""
+    env = isolated_tmux_server
+    branch_name = "json-remove"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"remove {branch_name} --json"
+    )
+
+    summary = json.loads(result.stdout)
+    assert [r["handle"] for r in summary["removed"]] == [branch_name]
+    assert summary["removed"][0]["branch"] == branch_name
+    assert summary["removed"][0]["cleanup"]["worktree_removed"] is True
+    assert summary["failed"] == []
+    assert not worktree_path.exists()
+
+
+def test_remove_delete_remote_deletes_the_upstream_branch(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Verifies `remove --delete-remote` deletes the branch on its remote, and
+    that a remote branch that is already gone doesn't fail the removal."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    env.run_command(
+        ["git", "remote", "add", "origin", str(remote_repo_path)], cwd=repo_path
+    )
+    branches = ["pushed-work", "already-gone"]
+    for branch_name in branches:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        create_commit(env, worktree_path, f"feat: {branch_name}")
+        env.run_command(["git", "push", "-u", "origin", branch_name], cwd=worktree_path)
+    env.run_command(["git", "branch", "-D", "already-gone"], cwd=remote_repo_path)
+
+    for branch_name in branches:
+        run_workmux_remove(
+            env, workmux_exe_path, repo_path, branch_name, force=True, delete_remote=True
+        )
+        assert not get_worktree_path(repo_path, branch_name).exists()
+
+    remote_branches = env.run_command(["git", "branch"], cwd=remote_repo_path)
+    assert "pushed-work" not in remote_branches.stdout
@@ -0,0 +1,71 @@
+"""
+Tests for `workmux replay`, which recreates a worktree from its metadata
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_replay_creates_next_attempt_with_same_prompt(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """The new worktree gets the next free branch, the base and the prompt."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        "add replay-test --prompt 'Fix the login' --no-hooks",
+    )
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "replay replay-test -b")
+    run_workmux_command(env, workmux_exe_path, repo_path, "replay replay-test -b")
+
+    assert get_worktree_path(repo_path, "replay-test-2").is_dir()
+    assert get_worktree_path(repo_path, "replay-test-3").is_dir()
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "info replay-test-2 --json"
+    )
+    info = json.loads(result.stdout)
+    assert info["base"] == "main"
+    assert info["prompt"] == "Fix the login"
+
+
+def test_replay_uses_given_branch(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """An explicit branch name replaces the numbered default."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "first-try")
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, "replay first-try second-try -b"
+    )
+
+    assert get_worktree_path(repo_path, "second-try").is_dir()
+
+
+def test_replay_fails_without_metadata(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Worktrees with no creation record can't be replayed."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "legacy")
+    (repo_path / ".git" / "workmux" / "worktrees" / "legacy.json").unlink()
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "replay legacy", expect_fail=True
+    )
+
+    assert "No record of how 'legacy' was created" in result.stderr
@@ -0,0 +1,50 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_run_executes_in_worktree_with_hook_env(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux run` runs the command in the worktree with WM_* vars."""
+    env = isolated_tmux_server
+    branch_name = "feature-run"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        f"run {branch_name} -- sh -c 'pwd -P; echo $WM_HANDLE $WM_BRANCH_NAME'",
+    )
+
+    lines = result.stdout.strip().splitlines()
+    assert lines[0] == str(get_worktree_path(repo_path, branch_name).resolve())
+    assert lines[1] == f"{branch_name} {branch_name}"
+
+
+def test_run_passes_exit_code_through(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux run` exits with the command's exit code."""
+    env = isolated_tmux_server
+    branch_name = "feature-run"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        f"run {branch_name} -- sh -c 'exit 3'",
+        expect_fail=True,
+    )
+
+    assert result.exit_code == 3
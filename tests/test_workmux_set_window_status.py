@@ -0,0 +1,48 @@
+"""
+Tests for reporting agent status changes (status_change in config)
+"""
+
+import json
+from pathlib import Path
+
+import yaml
+
+from .conftest import TmuxEnvironment, poll_until, run_workmux_command
+
+
+def test_status_change_command_runs_on_each_transition(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies the command gets the new and previous status, once per change."""
+    env = isolated_tmux_server
+    log_file = env.tmp_path / "status_changes.log"
+    command = (
+        f'printf "%s %s %s\\n" "$WM_STATUS" "${{WM_PREVIOUS_STATUS:--}}" '
+        f'"$WM_PAYLOAD" >> {log_file}'
+    )
+    (repo_path / ".workmux.yaml").write_text(
+        yaml.dump({"status_change": {"command": command}})
+    )
+
+    for status in ["working", "waiting", "waiting", "done"]:
+        run_workmux_command(
+            env, workmux_exe_path, repo_path, f"set-window-status {status}"
+        )
+
+    def _has_three_lines() -> bool:
+        return log_file.exists() and len(log_file.read_text().splitlines()) >= 3
+
+    assert poll_until(_has_three_lines), "status_change command did not run"
+    lines = log_file.read_text().splitlines()
+    # The repeated `waiting` is not a change
+    assert [line.split(" ", 2)[:2] for line in lines] == [
+        ["working", "-"],
+        ["waiting", "working"],
+        ["done", "waiting"],
+    ]
+    payload = json.loads(lines[1].split(" ", 2)[2])
+    assert payload["status"] == "waiting"
+    assert payload["previous_status"] == "working"
+    assert "is waiting for input" in payload["text"]
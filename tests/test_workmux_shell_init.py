@@ -0,0 +1,46 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_cd_function_changes_directory(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies the bash function from `shell-init` makes `workmux cd` change dirs."""
+    env = isolated_tmux_server
+    branch_name = "feature-cd"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    script = (
+        f'PATH="{workmux_exe_path.parent}:$PATH"\n'
+        'eval "$(workmux shell-init bash)"\n'
+        f"workmux cd {branch_name} && pwd && workmux_prompt && echo\n"
+        "workmux cd && pwd\n"
+    )
+    result = env.run_command(["bash", "-c", script], cwd=repo_path)
+
+    lines = result.stdout.splitlines()
+    assert lines[0] == str(get_worktree_path(repo_path, branch_name))
+    assert lines[1] == branch_name
+    assert lines[2] == str(repo_path)
+
+
+def test_cd_without_shell_integration_explains_setup(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies the binary's own `workmux cd` points at `shell-init`."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "cd", expect_fail=True
+    )
+
+    assert "workmux shell-init" in result.stderr
@@ -0,0 +1,80 @@
+"""
+Tests for stacked worktrees (workmux add --stack)
+"""
+
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_stack_branches_from_parent_and_records_it(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies a stacked worktree starts at its parent's branch tip."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature")
+    parent_path = get_worktree_path(repo_path, "feature")
+    create_commit(env, parent_path, "Parent work")
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, "add feature-tests --stack feature"
+    )
+
+    child_path = get_worktree_path(repo_path, "feature-tests")
+    log = env.run_command(["git", "log", "--format=%s"], cwd=child_path).stdout
+    assert "Parent work" in log
+    info = json.loads(
+        run_workmux_command(
+            env, workmux_exe_path, repo_path, "info feature-tests --json"
+        ).stdout
+    )
+    assert info["base"] == "feature"
+    assert info["parent"] == "feature"
+
+
+def test_merging_parent_restacks_children(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+):
+    """Verifies a squash-merged parent's child is rebased onto the target."""
+    env = isolated_tmux_server
+    main = env.run_command(
+        ["git", "branch", "--show-current"], cwd=repo_path
+    ).stdout.strip()
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature")
+    parent_path = get_worktree_path(repo_path, "feature")
+    create_commit(env, parent_path, "Parent work")
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, "add feature-tests --stack feature"
+    )
+    child_path = get_worktree_path(repo_path, "feature-tests")
+    create_commit(env, child_path, "Child work")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "merge --squash feature"
+    )
+
+    assert f"Restacked 'feature-tests' onto '{main}'" in result.stdout
+    assert not parent_path.exists()
+    subjects = env.run_command(
+        ["git", "log", "--format=%s", f"{main}..HEAD"], cwd=child_path
+    ).stdout.splitlines()
+    # Only the child's own commit is left on top of main
+    assert subjects == ["Child work"]
+    base = env.run_command(
+        ["git", "config", "branch.feature-tests.workmux-base"], cwd=repo_path
+    ).stdout.strip()
+    assert base == main
@@ -0,0 +1,199 @@
+"""
+Tests for keeping worktrees up to date with their base (workmux sync)
+"""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    install_fake_gh_pr_list,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def head_parent(env: TmuxEnvironment, path: Path) -> str:
+    return env.run_command(["git", "rev-parse", "HEAD~1"], cwd=path).stdout.strip()
+
+
+def main_head(env: TmuxEnvironment, repo_path: Path) -> str:
+    return env.run_command(["git", "rev-parse", "main"], cwd=repo_path).stdout.strip()
+
+
+def file_name_in(worktree: Path) -> str:
+    """A tracked file created by create_commit, to modify in place."""
+    return next(p.name for p in worktree.glob("file_for_*.txt"))
+
+
+def test_sync_rebases_worktrees_onto_main(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Every worktree branch is rebased onto the updated main branch."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    for branch in ["sync-a", "sync-b"]:
+        run_workmux_add(env, workmux_exe_path, repo_path, branch)
+        create_commit(env, get_worktree_path(repo_path, branch), f"work on {branch}")
+    create_commit(env, repo_path, "Advance main")
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "sync")
+
+    assert "sync-a: Rebased onto main" in result.stdout
+    assert "sync-b: Rebased onto main" in result.stdout
+    for branch in ["sync-a", "sync-b"]:
+        worktree = get_worktree_path(repo_path, branch)
+        assert head_parent(env, worktree) == main_head(env, repo_path)
+
+
+def test_sync_merge_strategy_from_config(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """`sync_strategy: merge` merges main into the branch instead of rebasing."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    with (repo_path / ".workmux.yaml").open("a") as f:
+        f.write("sync_strategy: merge\n")
+    env.run_command(["git", "add", ".workmux.yaml"], cwd=repo_path)
+    env.run_command(["git", "commit", "-m", "Configure sync"], cwd=repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "sync-merge")
+    worktree = get_worktree_path(repo_path, "sync-merge")
+    create_commit(env, worktree, "Branch work")
+    create_commit(env, repo_path, "Advance main")
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "sync")
+
+    assert "sync-merge: Merged main" in result.stdout
+    parents = env.run_command(
+        ["git", "log", "-1", "--format=%P"], cwd=worktree
+    ).stdout.split()
+    assert len(parents) == 2
+    assert main_head(env, repo_path) in parents
+
+
+def test_sync_rolls_back_conflicts_and_skips_dirty_worktrees(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Conflicted worktrees are left unchanged and dirty ones are not touched."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "sync-conflict")
+    conflict_worktree = get_worktree_path(repo_path, "sync-conflict")
+    (conflict_worktree / "shared.txt").write_text("branch version\n")
+    env.run_command(["git", "add", "shared.txt"], cwd=conflict_worktree)
+    env.run_command(["git", "commit", "-m", "Branch shared"], cwd=conflict_worktree)
+    branch_head = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=conflict_worktree
+    ).stdout.strip()
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "sync-dirty")
+    dirty_worktree = get_worktree_path(repo_path, "sync-dirty")
+    create_commit(env, dirty_worktree, "Dirty work")
+    (dirty_worktree / file_name_in(dirty_worktree)).write_text("edited\n")
+
+    (repo_path / "shared.txt").write_text("main version\n")
+    env.run_command(["git", "add", "shared.txt"], cwd=repo_path)
+    env.run_command(["git", "commit", "-m", "Main shared"], cwd=repo_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "sync", expect_fail=True
+    )
+
+    assert "sync-conflict: Conflicts with main" in result.stdout
+    assert "sync-dirty: Skipped (uncommitted changes)" in result.stdout
+    assert "could not be synced" in result.stderr
+    head_after = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=conflict_worktree
+    ).stdout.strip()
+    assert head_after == branch_head
+    status = env.run_command(
+        ["git", "status", "--porcelain"], cwd=conflict_worktree
+    ).stdout
+    assert status.strip() == ""
+
+
+def test_sync_cleanup_removes_externally_merged_worktrees(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """--cleanup removes worktrees whose PR was merged, then syncs the rest."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged-work")
+    merged_worktree = get_worktree_path(repo_path, "merged-work")
+    create_commit(env, merged_worktree, "Merged work")
+    merged_sha = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=merged_worktree
+    ).stdout.strip()
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "active-work")
+    active_worktree = get_worktree_path(repo_path, "active-work")
+    create_commit(env, active_worktree, "Active work")
+    create_commit(env, repo_path, "Advance main")
+
+    install_fake_gh_pr_list(
+        env,
+        [
+            {
+                "number": 4,
+                "title": "Merged work",
+                "state": "MERGED",
+                "isDraft": False,
+                "headRefName": "merged-work",
+                "headRefOid": merged_sha,
+            }
+        ],
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "sync --cleanup", stdin_input="y\n"
+    )
+
+    assert "merged-work (PR #4 merged)" in result.stdout
+    assert "Successfully removed 1 worktree(s)" in result.stdout
+    assert not merged_worktree.exists()
+    assert "active-work: Rebased onto main" in result.stdout
+    assert head_parent(env, active_worktree) == main_head(env, repo_path)
+
+
+def test_sync_cleanup_declined_keeps_worktrees_and_still_syncs(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Answering no to the cleanup confirmation removes nothing but still syncs."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged-work")
+    merged_worktree = get_worktree_path(repo_path, "merged-work")
+    create_commit(env, merged_worktree, "Merged work")
+    merged_sha = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=merged_worktree
+    ).stdout.strip()
+    create_commit(env, repo_path, "Advance main")
+
+    install_fake_gh_pr_list(
+        env,
+        [
+            {
+                "number": 4,
+                "title": "Merged work",
+                "state": "MERGED",
+                "isDraft": False,
+                "headRefName": "merged-work",
+                "headRefOid": merged_sha,
+            }
+        ],
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "sync --cleanup", stdin_input="n\n"
+    )
+
+    assert "Skipped cleanup." in result.stdout
+    assert merged_worktree.exists()
+    assert "merged-work: Rebased onto main" in result.stdout
@@ -0,0 +1,53 @@
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_test_reports_each_worktree_and_fails_if_any_fails(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux test` runs in every worktree and reports failures."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-ok")
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-bad")
+    (get_worktree_path(repo_path, "feature-bad") / "broken").touch()
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        "test -c 'echo checking $WM_HANDLE; test ! -f broken'",
+        expect_fail=True,
+    )
+
+    assert "✓ feature-ok" in result.stdout
+    assert "✗ feature-bad: exit code 1" in result.stdout
+    assert "checking feature-bad" in result.stdout
+    assert "1 passed, 1 failed" in result.stdout
+
+
+def test_test_record_saves_results(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux test --record` saves results keyed by worktree path."""
+    env = isolated_tmux_server
+    branch_name = "feature-record"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, f"test {branch_name} -c true --record"
+    )
+
+    checks_file = env.home_path / ".cache" / "workmux" / "checks.json"
+    data = json.loads(checks_file.read_text())["data"]
+    worktree_path = str(get_worktree_path(repo_path, branch_name))
+    assert data[worktree_path]["passed"] is True
@@ -0,0 +1,58 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    run_workmux_merge,
+    run_workmux_remove,
+    write_workmux_config,
+)
+
+
+def test_undo_recreates_removed_worktree(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """`workmux undo` after `remove -f` brings back the branch and worktree."""
+    env = isolated_tmux_server
+    branch_name = "undo-remove"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "work to keep")
+    head = env.run_command(["git", "rev-parse", "HEAD"], cwd=worktree_path).stdout
+
+    run_workmux_remove(env, workmux_exe_path, repo_path, branch_name, force=True)
+    assert not worktree_path.exists()
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "undo")
+
+    assert f"Recreated '{branch_name}'" in result.stdout
+    assert worktree_path.exists()
+    restored = env.run_command(["git", "rev-parse", "HEAD"], cwd=worktree_path)
+    assert restored.stdout == head
+
+
+def test_undo_reverts_merge(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """`workmux undo --yes` reverts a merge and recreates the merged branch."""
+    env = isolated_tmux_server
+    branch_name = "undo-merge"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    create_commit(env, get_worktree_path(repo_path, branch_name), "merged work")
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+    merged_file = repo_path / "file_for_merged_work.txt"
+    assert merged_file.exists()
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "undo --yes")
+
+    assert not merged_file.exists()
+    branches = env.run_command(["git", "branch", "--list", branch_name])
+    assert branch_name in branches.stdout
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "undo")
+    assert "Nothing to undo" in result.stdout